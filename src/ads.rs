@@ -0,0 +1,279 @@
+//! NTFS alternate data stream (ADS) reporting
+//!
+//! Files on NTFS can carry extra named streams next to their main content.
+//! Most are tiny and harmless (`Zone.Identifier`, the "downloaded from the
+//! internet" mark), but they are invisible to Explorer and to normal size
+//! calculations, so large ones can hide real disk usage. This module lists
+//! streams, summarizes them for `wole analyze --ads`, and can strip
+//! `Zone.Identifier` from old downloads.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Stream Windows attaches to downloaded files
+pub const ZONE_IDENTIFIER: &str = "Zone.Identifier";
+
+/// Streams above this size are called out as suspicious
+pub const SUSPICIOUS_STREAM_BYTES: u64 = 1024 * 1024;
+
+/// Maximum number of suspicious streams kept in a report
+const MAX_SUSPICIOUS: usize = 50;
+
+/// Stream names written by Windows and common software that are expected to exist
+const KNOWN_STREAMS: &[&str] = &[
+    ZONE_IDENTIFIER,
+    "SmartScreen",
+    "encryptable",
+    "favicon",
+    "OECustomProperty",
+    "com.dropbox.attrs",
+    "com.dropbox.attributes",
+    "ms-properties",
+    "AFP_AfpInfo",
+    "AFP_Resource",
+];
+
+/// A named (non-default) data stream on a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamInfo {
+    /// Stream name without the leading ':' or trailing ':$DATA'
+    pub name: String,
+    pub size: u64,
+}
+
+/// Summary of alternate data streams under a directory
+#[derive(Debug, Default)]
+pub struct AdsReport {
+    pub files_scanned: u64,
+    pub files_with_streams: u64,
+    pub stream_count: u64,
+    pub total_bytes: u64,
+    /// Files carrying a Zone.Identifier stream
+    pub zone_identifier_files: u64,
+    /// Large or unrecognized streams: (file, stream name, size), largest first
+    pub suspicious: Vec<(PathBuf, String, u64)>,
+}
+
+/// Whether a stream deserves attention: large, or not one of the well-known names
+pub fn is_suspicious(stream: &StreamInfo) -> bool {
+    let known = KNOWN_STREAMS
+        .iter()
+        .any(|k| stream.name.eq_ignore_ascii_case(k));
+    stream.size >= SUSPICIOUS_STREAM_BYTES || (!known && stream.size > 0)
+}
+
+/// Strip the `:name:$DATA` decoration Windows returns from a raw stream name.
+///
+/// Returns None for the default (unnamed) data stream.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_stream_name(raw: &str) -> Option<String> {
+    let name = raw.strip_prefix(':').unwrap_or(raw);
+    let name = name.strip_suffix(":$DATA").unwrap_or(name);
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// List the named data streams on a file (empty on non-NTFS volumes and non-Windows)
+#[cfg(windows)]
+pub fn list_streams(path: &Path) -> Vec<StreamInfo> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{
+        FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard,
+        WIN32_FIND_STREAM_DATA,
+    };
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    let mut streams = Vec::new();
+    let mut data = WIN32_FIND_STREAM_DATA::default();
+
+    // SAFETY: `wide` is NUL-terminated and `data` outlives the find handle.
+    unsafe {
+        let handle = match FindFirstStreamW(
+            PCWSTR(wide.as_ptr()),
+            FindStreamInfoStandard,
+            &mut data as *mut _ as *mut core::ffi::c_void,
+            0,
+        ) {
+            Ok(h) => h,
+            Err(_) => return streams,
+        };
+
+        loop {
+            let len = data
+                .cStreamName
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(data.cStreamName.len());
+            let raw = String::from_utf16_lossy(&data.cStreamName[..len]);
+            if let Some(name) = parse_stream_name(&raw) {
+                streams.push(StreamInfo {
+                    name,
+                    size: data.StreamSize.max(0) as u64,
+                });
+            }
+            if FindNextStreamW(handle, &mut data as *mut _ as *mut core::ffi::c_void).is_err() {
+                break;
+            }
+        }
+
+        let _ = FindClose(handle);
+    }
+
+    streams
+}
+
+#[cfg(not(windows))]
+pub fn list_streams(_path: &Path) -> Vec<StreamInfo> {
+    Vec::new()
+}
+
+/// Path that addresses a named stream (`file:stream`)
+fn stream_path(path: &Path, stream: &str) -> PathBuf {
+    let mut os = path.as_os_str().to_os_string();
+    os.push(":");
+    os.push(stream);
+    PathBuf::from(os)
+}
+
+/// Enumerate alternate data streams on files under `root`
+pub fn scan(root: &Path, max_depth: usize) -> AdsReport {
+    let mut report = AdsReport::default();
+
+    for entry in WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| !(e.depth() > 0 && crate::utils::should_skip_entry(e.path())))
+    {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                crate::elevation::note_walkdir_error(&e);
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        report.files_scanned += 1;
+        let streams = list_streams(entry.path());
+        if streams.is_empty() {
+            continue;
+        }
+
+        report.files_with_streams += 1;
+        for stream in streams {
+            report.stream_count += 1;
+            report.total_bytes += stream.size;
+            if stream.name.eq_ignore_ascii_case(ZONE_IDENTIFIER) {
+                report.zone_identifier_files += 1;
+            }
+            if is_suspicious(&stream) {
+                report
+                    .suspicious
+                    .push((entry.path().to_path_buf(), stream.name, stream.size));
+            }
+        }
+    }
+
+    report.suspicious.sort_by_key(|s| std::cmp::Reverse(s.2));
+    report.suspicious.truncate(MAX_SUSPICIOUS);
+    report
+}
+
+/// Files in `downloads_dir` older than `min_age_days` that still carry a Zone.Identifier
+pub fn old_downloads_with_zone_identifier(
+    downloads_dir: &Path,
+    min_age_days: u64,
+) -> Vec<(PathBuf, u64)> {
+    let cutoff = Utc::now() - Duration::days(min_age_days as i64);
+    let mut found = Vec::new();
+
+    for entry in WalkDir::new(downloads_dir)
+        .max_depth(3)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let old_enough = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(|t| chrono::DateTime::<Utc>::from(t) < cutoff)
+            .unwrap_or(false);
+        if !old_enough {
+            continue;
+        }
+        if let Some(zone) = list_streams(entry.path())
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(ZONE_IDENTIFIER))
+        {
+            found.push((entry.path().to_path_buf(), zone.size));
+        }
+    }
+
+    found
+}
+
+/// Remove the Zone.Identifier stream from a file (the file itself is untouched)
+pub fn strip_zone_identifier(path: &Path) -> Result<()> {
+    let stream = stream_path(path, ZONE_IDENTIFIER);
+    std::fs::remove_file(&stream)
+        .with_context(|| format!("Failed to remove Zone.Identifier from {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_raw_stream_names() {
+        assert_eq!(parse_stream_name("::$DATA"), None);
+        assert_eq!(
+            parse_stream_name(":Zone.Identifier:$DATA"),
+            Some("Zone.Identifier".to_string())
+        );
+    }
+
+    #[test]
+    fn flags_large_or_unknown_streams() {
+        let zone = StreamInfo {
+            name: "Zone.Identifier".to_string(),
+            size: 120,
+        };
+        let stash = StreamInfo {
+            name: "payload".to_string(),
+            size: 10,
+        };
+        let huge_zone = StreamInfo {
+            name: "Zone.Identifier".to_string(),
+            size: SUSPICIOUS_STREAM_BYTES,
+        };
+        assert!(!is_suspicious(&zone));
+        assert!(is_suspicious(&stash));
+        assert!(is_suspicious(&huge_zone));
+    }
+
+    #[test]
+    fn stream_path_appends_name() {
+        assert_eq!(
+            stream_path(Path::new("dl/setup.exe"), ZONE_IDENTIFIER),
+            PathBuf::from("dl/setup.exe:Zone.Identifier")
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::cli::ScanOptions;
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::progress;
+use crate::utils::SizeWalk;
 use anyhow::Result;
 use rayon::prelude::*;
 use std::collections::HashMap;
@@ -413,7 +414,12 @@ impl Scanner for CacheScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::cache;
-        let result = cache::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
+        let result = cache::scan(
+            std::path::Path::new(""),
+            config,
+            OutputMode::Normal,
+            &SizeWalk::default(),
+        )?;
         Ok(convert_category_result(
             result,
             Category::Cache,
@@ -440,7 +446,12 @@ impl Scanner for AppCacheScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::app_cache;
-        let result = app_cache::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
+        let result = app_cache::scan(
+            std::path::Path::new(""),
+            config,
+            OutputMode::Normal,
+            &SizeWalk::default(),
+        )?;
         Ok(convert_category_result(
             result,
             Category::AppCache,
@@ -467,7 +478,7 @@ impl Scanner for TempScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::temp;
-        let result = temp::scan(std::path::Path::new(""), config)?;
+        let result = temp::scan(std::path::Path::new(""), config, &SizeWalk::default())?;
         Ok(convert_category_result(
             result,
             Category::Temp,
@@ -494,7 +505,7 @@ impl Scanner for TrashScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::trash;
-        let result = trash::scan()?;
+        let result = trash::scan(0, &SizeWalk::default())?;
         Ok(convert_category_result(
             result,
             Category::Trash,
@@ -527,6 +538,7 @@ impl Scanner for BuildScannerAdapter {
             Some(&config.categories.build),
             config,
             OutputMode::Normal,
+            &SizeWalk::default(),
         )?;
         Ok(convert_category_result(
             result,
@@ -645,7 +657,7 @@ impl Scanner for BrowserScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::browser;
-        let result = browser::scan(path, config)?;
+        let result = browser::scan(path, config, &SizeWalk::default())?;
         Ok(convert_category_result(
             result,
             Category::Browser,
@@ -672,7 +684,7 @@ impl Scanner for SystemScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::system;
-        let result = system::scan(path, config)?;
+        let result = system::scan(path, config, &SizeWalk::default())?;
         Ok(convert_category_result(
             result,
             Category::System,
@@ -755,7 +767,12 @@ impl Scanner for ApplicationsScannerAdapter {
         config: &Config,
     ) -> Result<Vec<CleanableFile>> {
         use crate::categories::applications;
-        let result = applications::scan(std::path::Path::new(""), config, OutputMode::Normal)?;
+        let result = applications::scan(
+            std::path::Path::new(""),
+            config,
+            OutputMode::Normal,
+            &SizeWalk::default(),
+        )?;
         Ok(convert_category_result(
             result,
             Category::Applications,
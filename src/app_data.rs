@@ -0,0 +1,175 @@
+//! Where wole keeps its own files
+//!
+//! Daemon state, the IPC endpoint and scan reports live in the cache
+//! directory: `%LOCALAPPDATA%\wole\cache` on Windows,
+//! `~/.local/share/wole/cache` elsewhere. Small state files are JSON and read
+//! leniently, so a missing or corrupt file is the same as a fresh start.
+//! Secrets (tokens, signing keys) come from OS randomness and are written
+//! with `write_private`, so only the current user can read them.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `%LOCALAPPDATA%\wole` (Windows) or `~/.local/share/wole`
+pub fn data_dir() -> PathBuf {
+    let base_dir = if cfg!(windows) {
+        std::env::var("LOCALAPPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                std::env::var("USERPROFILE")
+                    .map(|p| PathBuf::from(p).join("AppData").join("Local"))
+                    .unwrap_or_else(|_| PathBuf::from("."))
+            })
+    } else {
+        std::env::var("HOME")
+            .map(|h| PathBuf::from(h).join(".local").join("share"))
+            .unwrap_or_else(|_| PathBuf::from("."))
+    };
+    base_dir.join("wole")
+}
+
+/// The cache directory, created if needed
+pub fn cache_dir() -> Result<PathBuf> {
+    let cache_dir = data_dir().join("cache");
+    std::fs::create_dir_all(&cache_dir)
+        .with_context(|| format!("Failed to create cache directory: {}", cache_dir.display()))?;
+    Ok(cache_dir)
+}
+
+/// Read the state file `name` from the cache directory, if it's there and parses
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let text = std::fs::read_to_string(cache_dir().ok()?.join(name)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Write `value` to the state file `name` in the cache directory
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    let path = cache_dir()?.join(name);
+    std::fs::write(&path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Remove the state file `name`; a missing file is fine
+pub fn remove(name: &str) {
+    if let Ok(dir) = cache_dir() {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+}
+
+/// `len` random bytes from the OS, as lowercase hex
+pub fn random_hex(len: usize) -> Result<String> {
+    let mut bytes = vec![0u8; len];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to read OS randomness: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Whether token `sent` is `expected`, compared in constant time so the
+/// answer doesn't leak how much of it matched
+pub fn tokens_match(expected: &str, sent: &str) -> bool {
+    expected.len() == sent.len()
+        && expected
+            .bytes()
+            .zip(sent.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Write `contents` to `path` so that only the current user can read it
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    write_owner_only(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(unix)]
+fn write_owner_only(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies to new files
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents)
+}
+
+/// Replace the inherited ACL with one granting access to the owner only
+#[cfg(windows)]
+fn write_owner_only(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::{LocalFree, BOOL, ERROR_SUCCESS, HLOCAL};
+    use windows::Win32::Security::Authorization::{
+        ConvertStringSecurityDescriptorToSecurityDescriptorW, SetNamedSecurityInfoW,
+        SDDL_REVISION_1, SE_FILE_OBJECT,
+    };
+    use windows::Win32::Security::{
+        GetSecurityDescriptorDacl, ACL, DACL_SECURITY_INFORMATION,
+        PROTECTED_DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID,
+    };
+
+    // Restrict the (empty) file before the secret goes in
+    std::fs::write(path, [])?;
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    unsafe {
+        // Protected DACL with a single ACE: full access for the owner
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            w!("D:P(A;;FA;;;OW)"),
+            SDDL_REVISION_1,
+            &mut descriptor,
+            None,
+        )
+        .map_err(std::io::Error::other)?;
+
+        let mut present = BOOL::default();
+        let mut defaulted = BOOL::default();
+        let mut dacl: *mut ACL = std::ptr::null_mut();
+        let status = GetSecurityDescriptorDacl(descriptor, &mut present, &mut dacl, &mut defaulted)
+            .map_err(std::io::Error::other)
+            .map(|()| {
+                SetNamedSecurityInfoW(
+                    PCWSTR(wide.as_ptr()),
+                    SE_FILE_OBJECT,
+                    DACL_SECURITY_INFORMATION | PROTECTED_DACL_SECURITY_INFORMATION,
+                    PSID::default(),
+                    PSID::default(),
+                    Some(dacl as *const ACL),
+                    None,
+                )
+            });
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        match status? {
+            ERROR_SUCCESS => {}
+            error => return Err(std::io::Error::from_raw_os_error(error.0 as i32)),
+        }
+    }
+    std::fs::write(path, contents)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn write_owner_only(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
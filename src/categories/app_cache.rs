@@ -1,8 +1,9 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
 use bytesize;
 use std::collections::HashSet;
@@ -401,7 +402,12 @@ fn scan_app_caches(
 /// cache-name discovery is disabled for normal scans.
 ///
 /// Optimized to calculate directory sizes in parallel.
-pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
+pub fn scan(
+    _root: &Path,
+    config: &Config,
+    output_mode: OutputMode,
+    walk: &SizeWalk,
+) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
     let mut known_paths = HashSet::new();
     let mut candidates = Vec::new();
@@ -438,12 +444,19 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     let mut paths_with_sizes: Vec<(PathBuf, u64)> = candidates
         .iter()
         .map(|path| {
-            let size = utils::calculate_dir_size(path);
+            let size = utils::calculate_dir_size_in(path, walk);
             (path.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
         .collect();
 
+    // Folders of apps that look uninstalled (off unless configured)
+    paths_with_sizes.extend(
+        super::app_leftovers::find(config, walk)
+            .into_iter()
+            .filter(|(path, _)| !known_paths.contains(path)),
+    );
+
     // Sort by size descending
     paths_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
 
@@ -494,6 +507,7 @@ pub fn scan_with_progress(
     _root: &Path,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     const CATEGORY: &str = "Application Cache";
     let mut result = CategoryResult::default();
@@ -522,7 +536,7 @@ pub fn scan_with_progress(
 
         if let Some(cache_path) = cache_path {
             if cache_path.exists() && !config.is_excluded(&cache_path) {
-                let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
+                let size = utils::calculate_dir_size_with_progress(&cache_path, walk, &on_path);
                 if size > 0 {
                     known_paths.insert(cache_path.clone());
                     files_with_sizes.push((cache_path.clone(), size));
@@ -547,6 +561,13 @@ pub fn scan_with_progress(
         }
     }
 
+    // Folders of apps that look uninstalled (off unless configured)
+    files_with_sizes.extend(
+        super::app_leftovers::find(config, walk)
+            .into_iter()
+            .filter(|(path, _)| !known_paths.contains(path)),
+    );
+
     // Sort by size descending
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
 
@@ -567,38 +588,28 @@ pub fn scan_with_progress(
 }
 
 /// True when any scanned path is state-adjacent and should be reviewed before clean
-/// (Notion roaming `Partitions`, Cursor / Cursor Nightly `snapshots`).
+/// (Notion roaming `Partitions`, Cursor / Cursor Nightly `snapshots`, orphaned app data).
 pub fn scan_includes_review_worthy_paths(paths: &[PathBuf]) -> bool {
-    paths.iter().any(|p| is_review_worthy_app_cache_path(p))
+    paths.iter().any(|p| {
+        wole_core::policy::is_review_worthy_app_cache_path(&p.to_string_lossy())
+            || super::app_leftovers::is_flagged(p)
+    })
 }
 
-fn is_review_worthy_app_cache_path(path: &Path) -> bool {
-    let Some(leaf) = path.file_name().and_then(|n| n.to_str()) else {
-        return false;
-    };
-
-    if leaf.eq_ignore_ascii_case("Partitions") {
-        if let Some(parent) = path
-            .parent()
-            .and_then(|p| p.file_name())
-            .and_then(|n| n.to_str())
-        {
-            return parent.eq_ignore_ascii_case("Notion");
-        }
-    }
-
-    if leaf.eq_ignore_ascii_case("snapshots") {
-        if let Some(parent) = path
-            .parent()
-            .and_then(|p| p.file_name())
-            .and_then(|n| n.to_str())
-        {
-            return parent.eq_ignore_ascii_case("Cursor")
-                || parent.eq_ignore_ascii_case("Cursor Nightly");
-        }
-    }
-
-    false
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(
+        root,
+        &options.config,
+        OutputMode::Quiet,
+        &SizeWalk::default(),
+    )?;
+    Ok(CategoryScan::from_result(
+        "app_cache",
+        Safety::Safe,
+        result,
+        |_| "application cache".to_string(),
+    ))
 }
 
 /// Clean (delete) an application cache directory by moving it to the Recycle Bin
@@ -769,7 +780,13 @@ mod tests {
         std::env::set_var("APPDATA", &roaming);
 
         let config = Config::default();
-        let r = scan(Path::new(""), &config, OutputMode::Quiet).expect("scan");
+        let r = scan(
+            Path::new(""),
+            &config,
+            OutputMode::Quiet,
+            &SizeWalk::default(),
+        )
+        .expect("scan");
 
         let hit = r
             .paths
@@ -0,0 +1,263 @@
+//! AppData folders left behind by uninstalled applications.
+//!
+//! Uninstallers rarely remove what an app wrote to `%LOCALAPPDATA%` and
+//! `%APPDATA%`, so those folders pile up for years. A top-level folder is
+//! reported with Application Cache as "orphaned app data" only when nothing
+//! still claims it: no Uninstall registry entry, no Start Menu shortcut and no
+//! running process has a name that matches it. It must also be outside the
+//! known Windows/runtime folders and untouched for `min_age_days` (180 by
+//! default).
+//!
+//! This is a guess from names, so it's off unless
+//! `[categories.app_leftovers] enabled` is set. When the installed apps can't
+//! be read at all, nothing is reported.
+
+use crate::config::Config;
+use crate::utils::{self, SizeWalk};
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// How deep to look for recent writes inside a candidate folder
+const AGE_CHECK_DEPTH: usize = 4;
+
+/// Names (normalized) shorter than this are too ambiguous to judge
+const MIN_NAME_LEN: usize = 4;
+
+/// Folders that belong to Windows, runtimes or shared tooling rather than an
+/// app that can be uninstalled
+const KNOWN_FOLDERS: &[&str] = &[
+    "application data",
+    "comms",
+    "connecteddevicesplatform",
+    "crashdumps",
+    "d3dscache",
+    "history",
+    "microsoft",
+    "microsoft help",
+    "npm",
+    "npm-cache",
+    "nuget",
+    "packages",
+    "peerdistrepub",
+    "pip",
+    "programs",
+    "publishers",
+    "temp",
+    "temporary internet files",
+    "virtualstore",
+    "wole",
+];
+
+lazy_static! {
+    /// Folders reported by the last `find`, so output can label them
+    static ref FLAGGED: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+}
+
+/// Names that show an app is still around: installed apps and publishers,
+/// Start Menu shortcuts and running processes, all normalized
+#[derive(Debug, Default)]
+pub struct Evidence {
+    names: HashSet<String>,
+}
+
+impl Evidence {
+    pub fn add(&mut self, name: &str) {
+        let normalized = normalize(name);
+        if normalized.len() >= MIN_NAME_LEN {
+            self.names.insert(normalized);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Whether anything still claims a folder called `folder`
+    pub fn claims(&self, folder: &str) -> bool {
+        let folder = normalize(folder);
+        self.names
+            .iter()
+            .any(|name| name.contains(&folder) || folder.contains(name.as_str()))
+    }
+}
+
+/// Lowercase letters and digits only, so `obs-studio` matches `OBS Studio`
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Whether an AppData folder named `folder` looks orphaned. Age is checked
+/// separately; this only answers "does anything still claim it".
+pub fn is_orphaned(folder: &str, evidence: &Evidence) -> bool {
+    let lower = folder.to_lowercase();
+    if lower.starts_with('.') || KNOWN_FOLDERS.contains(&lower.as_str()) {
+        return false;
+    }
+    if normalize(folder).len() < MIN_NAME_LEN {
+        return false;
+    }
+    !evidence.claims(folder)
+}
+
+/// Whether `path` was reported as orphaned app data by the last scan
+pub fn is_flagged(path: &Path) -> bool {
+    FLAGGED
+        .lock()
+        .map(|flagged| flagged.contains(path))
+        .unwrap_or(false)
+}
+
+fn appdata_roots() -> Vec<PathBuf> {
+    ["LOCALAPPDATA", "APPDATA"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn start_menu_dirs() -> Vec<PathBuf> {
+    let programs = |base: PathBuf| {
+        base.join("Microsoft")
+            .join("Windows")
+            .join("Start Menu")
+            .join("Programs")
+    };
+    let mut dirs = Vec::new();
+    if let Ok(appdata) = env::var("APPDATA") {
+        dirs.push(programs(PathBuf::from(appdata)));
+    }
+    if let Ok(program_data) = env::var("ProgramData").or_else(|_| env::var("PROGRAMDATA")) {
+        dirs.push(programs(PathBuf::from(program_data)));
+    }
+    dirs
+}
+
+fn gather_evidence() -> Evidence {
+    let mut evidence = Evidence::default();
+    for name in super::applications::registered_app_names() {
+        evidence.add(&name);
+    }
+    // Without the registry there's nothing to compare against
+    if evidence.is_empty() {
+        return evidence;
+    }
+
+    for dir in start_menu_dirs() {
+        for entry in WalkDir::new(dir)
+            .max_depth(3)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if let Some(stem) = entry.path().file_stem() {
+                evidence.add(&stem.to_string_lossy());
+            }
+        }
+    }
+
+    let mut system = sysinfo::System::new();
+    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    for process in system.processes().values() {
+        if let Some(exe) = process.exe() {
+            if let Some(stem) = exe.file_stem() {
+                evidence.add(&stem.to_string_lossy());
+            }
+            if let Some(folder) = exe.parent().and_then(|p| p.file_name()) {
+                evidence.add(&folder.to_string_lossy());
+            }
+        }
+    }
+
+    evidence
+}
+
+/// Whether nothing under `path` was written since `cutoff`
+fn untouched_since(path: &Path, cutoff: SystemTime) -> bool {
+    for entry in WalkDir::new(path)
+        .max_depth(AGE_CHECK_DEPTH)
+        .follow_links(false)
+    {
+        let Ok(entry) = entry else {
+            // Can't see everything: be safe and keep it
+            return false;
+        };
+        match entry.metadata().ok().and_then(|m| m.modified().ok()) {
+            Some(modified) if modified < cutoff => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Find orphaned AppData folders and their sizes, biggest first.
+///
+/// Does nothing if the sub-category is disabled in config.
+pub fn find(config: &Config, walk: &SizeWalk) -> Vec<(PathBuf, u64)> {
+    let settings = &config.categories.app_leftovers;
+    if !settings.enabled {
+        return Vec::new();
+    }
+    let evidence = gather_evidence();
+    if evidence.is_empty() {
+        return Vec::new();
+    }
+
+    let cutoff = SystemTime::now() - Duration::from_secs(settings.min_age_days * 86400);
+    let mut found: Vec<(PathBuf, u64)> = appdata_roots()
+        .iter()
+        .filter_map(|root| std::fs::read_dir(root).ok())
+        .flat_map(|entries| entries.filter_map(|e| e.ok()))
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .map(|name| is_orphaned(&name.to_string_lossy(), &evidence))
+                .unwrap_or(false)
+        })
+        .filter(|path| !config.is_excluded(path) && !utils::should_skip_entry(path))
+        .filter(|path| untouched_since(path, cutoff))
+        .map(|path| {
+            let size = utils::calculate_dir_size_in(&path, walk);
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    found.sort_by_key(|f| std::cmp::Reverse(f.1));
+
+    if let Ok(mut flagged) = FLAGGED.lock() {
+        flagged.clear();
+        flagged.extend(found.iter().map(|(path, _)| path.clone()));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unclaimed_folders_are_orphaned() {
+        let mut evidence = Evidence::default();
+        evidence.add("OBS Studio");
+        evidence.add("JetBrains s.r.o.");
+        evidence.add("Spotify");
+
+        assert!(!is_orphaned("obs-studio", &evidence));
+        assert!(!is_orphaned("JetBrains", &evidence));
+        assert!(!is_orphaned("Spotify", &evidence));
+        assert!(is_orphaned("OldEditor", &evidence));
+
+        // Windows folders, hidden folders and short names are never guessed at
+        assert!(!is_orphaned("Microsoft", &evidence));
+        assert!(!is_orphaned("Packages", &evidence));
+        assert!(!is_orphaned(".cache", &evidence));
+        assert!(!is_orphaned("qt", &evidence));
+    }
+}
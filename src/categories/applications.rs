@@ -2,6 +2,7 @@ use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
+use crate::utils::SizeWalk;
 use anyhow::{Context, Result};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
@@ -226,9 +227,65 @@ fn read_registry_apps() -> Result<Vec<InstalledApp>> {
     Ok(Vec::new())
 }
 
+/// Display names and publishers of every Uninstall entry, including the ones
+/// without an install folder that `read_registry_apps` leaves out
+#[cfg(windows)]
+pub(crate) fn registered_app_names() -> Vec<String> {
+    let mut names = Vec::new();
+    let registry_paths = [
+        (
+            RegKey::predef(HKEY_LOCAL_MACHINE),
+            "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+        ),
+        (
+            RegKey::predef(HKEY_CURRENT_USER),
+            "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+        ),
+        (
+            RegKey::predef(HKEY_LOCAL_MACHINE),
+            "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+        ),
+    ];
+
+    for (hive, path) in registry_paths {
+        let Ok(key) = hive.open_subkey(path) else {
+            continue;
+        };
+        for subkey_name in key.enum_keys().filter_map(|k| k.ok()) {
+            let Ok(subkey) = key.open_subkey(&subkey_name) else {
+                continue;
+            };
+            for value in ["DisplayName", "Publisher"] {
+                if let Ok(text) = subkey.get_value::<String, _>(value) {
+                    if !text.trim().is_empty() {
+                        names.push(text);
+                    }
+                }
+            }
+            if let Ok(location) = subkey.get_value::<String, _>("InstallLocation") {
+                if let Some(folder) = Path::new(location.trim()).file_name() {
+                    names.push(folder.to_string_lossy().into_owned());
+                }
+            }
+        }
+    }
+
+    names
+}
+
+#[cfg(not(windows))]
+pub(crate) fn registered_app_names() -> Vec<String> {
+    Vec::new()
+}
+
 /// Scan for installed applications
 #[allow(unused_variables)]
-pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
+pub fn scan(
+    _root: &Path,
+    config: &Config,
+    output_mode: OutputMode,
+    walk: &SizeWalk,
+) -> Result<CategoryResult> {
     #[cfg(windows)]
     {
         if output_mode != OutputMode::Quiet {
@@ -267,7 +324,7 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
             } else {
                 // Verify directory still exists before calculating size
                 if app.install_location.exists() && app.install_location.is_dir() {
-                    crate::utils::calculate_dir_size(&app.install_location)
+                    crate::utils::calculate_dir_size_in(&app.install_location, walk)
                 } else {
                     // Directory was deleted/moved since registry read - skip this app
                     continue;
@@ -353,6 +410,7 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
             paths,
             size_bytes,
             items,
+            ..Default::default()
         };
 
         if output_mode != OutputMode::Quiet && !apps_with_sizes.is_empty() {
@@ -404,10 +462,12 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
 }
 
 /// Scan with real-time progress events (for TUI)
+#[allow(unused_variables)]
 pub fn scan_with_progress(
     _root: &Path,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     const CATEGORY: &str = "Installed Applications";
 
@@ -453,7 +513,11 @@ pub fn scan_with_progress(
             } else {
                 // Verify directory still exists before calculating size
                 if app.install_location.exists() && app.install_location.is_dir() {
-                    crate::utils::calculate_dir_size_with_progress(&app.install_location, &on_path)
+                    crate::utils::calculate_dir_size_with_progress(
+                        &app.install_location,
+                        walk,
+                        &on_path,
+                    )
                 } else {
                     // Directory was deleted/moved since registry read - skip this app
                     continue;
@@ -541,6 +605,7 @@ pub fn scan_with_progress(
             paths,
             size_bytes,
             items,
+            ..Default::default()
         };
 
         let _ = tx.send(ScanProgressEvent::CategoryFinished {
@@ -1,47 +1,81 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::CategoryResult;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use lazy_static::lazy_static;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::sync::Mutex;
 
-/// Chromium-based browsers: display name and path under %LOCALAPPDATA% up to and including `User Data`.
+/// Chromium-based browsers: display name, process name (lowercase, without
+/// `.exe`) and path under %LOCALAPPDATA% up to and including `User Data`.
 ///
 /// **Perplexity Comet** is not scanned as a full Chromium profile tree: only narrow
 /// cache folders under `Perplexity\\Comet\\User Data` are collected by
 /// `collect_comet_narrow_caches`. We never target `Comet\\Application`, the whole `Comet`
 /// install directory, or Roaming `Perplexity` (those are excluded from application-cache
 /// heuristics in the `app_cache` module).
-const CHROMIUM_USER_DATA_ROOTS: &[(&str, &[&str])] = &[
-    ("Chrome", &["Google", "Chrome", "User Data"]),
-    ("Chrome (Beta)", &["Google", "Chrome Beta", "User Data"]),
-    ("Chrome (Dev)", &["Google", "Chrome Dev", "User Data"]),
-    ("Edge", &["Microsoft", "Edge", "User Data"]),
-    ("Edge (Beta)", &["Microsoft", "Edge Beta", "User Data"]),
-    ("Edge (Dev)", &["Microsoft", "Edge Dev", "User Data"]),
-    ("Brave", &["BraveSoftware", "Brave-Browser", "User Data"]),
+const CHROMIUM_USER_DATA_ROOTS: &[(&str, &str, &[&str])] = &[
+    ("Chrome", "chrome", &["Google", "Chrome", "User Data"]),
+    (
+        "Chrome (Beta)",
+        "chrome",
+        &["Google", "Chrome Beta", "User Data"],
+    ),
+    (
+        "Chrome (Dev)",
+        "chrome",
+        &["Google", "Chrome Dev", "User Data"],
+    ),
+    ("Edge", "msedge", &["Microsoft", "Edge", "User Data"]),
+    (
+        "Edge (Beta)",
+        "msedge",
+        &["Microsoft", "Edge Beta", "User Data"],
+    ),
+    (
+        "Edge (Dev)",
+        "msedge",
+        &["Microsoft", "Edge Dev", "User Data"],
+    ),
+    (
+        "Brave",
+        "brave",
+        &["BraveSoftware", "Brave-Browser", "User Data"],
+    ),
     (
         "Brave (Beta)",
+        "brave",
         &["BraveSoftware", "Brave-Browser-Beta", "User Data"],
     ),
-    ("Arc", &["The Browser Company", "Arc", "User Data"]),
-    ("Atlas", &["OpenAI", "Atlast", "User Data"]),
-    ("Vivaldi", &["Vivaldi", "User Data"]),
-    ("Chromium", &["Chromium", "User Data"]),
-    ("Sidekick", &["Redundant", "Sidekick", "User Data"]),
-    ("Yandex", &["Yandex", "YandexBrowser", "User Data"]),
+    ("Arc", "arc", &["The Browser Company", "Arc", "User Data"]),
+    ("Atlas", "atlas", &["OpenAI", "Atlast", "User Data"]),
+    ("Vivaldi", "vivaldi", &["Vivaldi", "User Data"]),
+    ("Chromium", "chrome", &["Chromium", "User Data"]),
+    (
+        "Sidekick",
+        "sidekick",
+        &["Redundant", "Sidekick", "User Data"],
+    ),
+    (
+        "Yandex",
+        "browser",
+        &["Yandex", "YandexBrowser", "User Data"],
+    ),
     (
         "Avast Secure Browser",
+        "avastbrowser",
         &["AVAST Software", "Browser", "User Data"],
     ),
     (
         "CCleaner Browser",
+        "ccleanerbrowser",
         &["CCleaner", "CCleaner Browser", "User Data"],
     ),
-    ("Torch", &["Torch", "User Data"]),
-    ("Epic", &["Epic Privacy Browser", "User Data"]),
+    ("Torch", "torch", &["Torch", "User Data"]),
+    ("Epic", "epic", &["Epic Privacy Browser", "User Data"]),
 ];
 
 /// Cache folder names relative to a Chromium profile directory (e.g. `Default`, `Profile 1`).
@@ -68,9 +102,16 @@ const CHROMIUM_USER_DATA_ROOT_CACHE_DIRS: &[&str] = &[
     "Crashpad",
 ];
 
-/// Opera-style single cache root under %LOCALAPPDATA% (not Chromium profile layout).
-const OPERA_STYLE_CACHES: &[(&str, &[&str])] =
-    &[("Opera", &["Opera Software", "Opera Stable", "Cache"])];
+/// Opera keeps its main profile's caches directly in the install's folder
+/// under %LOCALAPPDATA% and extra profiles in `_side_profiles\<id>`.
+const OPERA_ROOTS: &[(&str, &str, &[&str])] = &[
+    ("Opera", "opera", &["Opera Software", "Opera Stable"]),
+    ("Opera GX", "opera", &["Opera Software", "Opera GX Stable"]),
+    ("Opera (Beta)", "opera", &["Opera Software", "Opera Next"]),
+];
+
+/// Profile label for caches shared by all of a browser's profiles
+const SHARED_PROFILE: &str = "(shared)";
 
 /// Comet (`%LOCALAPPDATA%\\Perplexity\\Comet\\User Data`): only these profile subfolders,
 /// plus `Crashpad\\reports` under `User Data` — never `Application` or the `Comet` root.
@@ -83,46 +124,65 @@ const COMET_PROFILE_NARROW_CACHE_DIRS: &[&str] = &[
     "ShaderCache",
 ];
 
+lazy_static! {
+    /// Browser, profile and size of each cache found by the last scan
+    static ref PROFILE_OF: Mutex<HashMap<PathBuf, (String, String, u64)>> =
+        Mutex::new(HashMap::new());
+    /// Browsers left out of the last scan because they were running
+    static ref SKIPPED_RUNNING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+/// Cache size of one browser profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileCache {
+    pub browser: String,
+    pub profile: String,
+    pub items: usize,
+    pub size_bytes: u64,
+}
+
+/// Cache folders found so far, each with the browser and profile it belongs to
+struct Collector<'a> {
+    config: &'a Config,
+    found: Vec<(PathBuf, String, String)>,
+    seen: HashSet<PathBuf>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            found: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn add(&mut self, path: PathBuf, browser: &str, profile: &str) {
+        if path.is_dir() && !self.config.is_excluded(&path) && self.seen.insert(path.clone()) {
+            self.found
+                .push((path, browser.to_string(), profile.to_string()));
+        }
+    }
+}
+
 /// Narrow disk-cache paths for Perplexity Comet only (browser install stays intact).
-fn collect_comet_narrow_caches(
-    local_appdata: &Path,
-    paths: &mut Vec<PathBuf>,
-    seen: &mut HashSet<PathBuf>,
-    config: &Config,
-) {
+fn collect_comet_narrow_caches(local_appdata: &Path, collector: &mut Collector) {
     let user_data_root = join_localappdata(local_appdata, COMET_USER_DATA_SEGMENTS);
     if !user_data_root.is_dir() {
         return;
     }
 
-    let reports = user_data_root.join("Crashpad").join("reports");
-    if reports.is_dir() && !config.is_excluded(&reports) && seen.insert(reports.clone()) {
-        paths.push(reports);
-    }
+    collector.add(
+        user_data_root.join("Crashpad").join("reports"),
+        "Comet",
+        SHARED_PROFILE,
+    );
 
-    let entries = match utils::safe_read_dir(&user_data_root) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
-            continue;
-        };
-        if !is_chromium_profile_dir(name) {
-            continue;
-        }
+    let names = chromium_profile_names(&user_data_root);
+    for (dir, name) in chromium_profiles(&user_data_root) {
+        let profile = names.get(&name).unwrap_or(&name);
         for cache_name in COMET_PROFILE_NARROW_CACHE_DIRS {
-            let cache_path = path.join(cache_name);
-            if cache_path.is_dir()
-                && !config.is_excluded(&cache_path)
-                && seen.insert(cache_path.clone())
-            {
-                paths.push(cache_path);
-            }
+            collector.add(dir.join(cache_name), "Comet", profile);
         }
     }
 }
@@ -135,6 +195,46 @@ fn is_chromium_profile_dir(name: &str) -> bool {
         || name == "System Profile"
 }
 
+/// Profile directories under a Chromium `User Data` root, with their folder names
+fn chromium_profiles(user_data_root: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = utils::safe_read_dir(user_data_root) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<(PathBuf, String)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter_map(|p| {
+            let name = p.file_name()?.to_str()?.to_string();
+            is_chromium_profile_dir(&name).then_some((p, name))
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.1.cmp(&b.1));
+    profiles
+}
+
+/// Names the user gave their profiles (`Profile 1` -> `Work`), from `Local State`
+fn chromium_profile_names(user_data_root: &Path) -> HashMap<String, String> {
+    let Ok(text) = std::fs::read_to_string(user_data_root.join("Local State")) else {
+        return HashMap::new();
+    };
+    let Ok(state) = serde_json::from_str::<serde_json::Value>(&text) else {
+        return HashMap::new();
+    };
+    state["profile"]["info_cache"]
+        .as_object()
+        .map(|cache| {
+            cache
+                .iter()
+                .filter_map(|(dir, info)| {
+                    let name = info["name"].as_str().filter(|n| !n.is_empty())?;
+                    Some((dir.clone(), name.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn join_localappdata(local_appdata: &Path, segments: &[&str]) -> PathBuf {
     let mut p = local_appdata.to_path_buf();
     for s in segments {
@@ -143,13 +243,12 @@ fn join_localappdata(local_appdata: &Path, segments: &[&str]) -> PathBuf {
     p
 }
 
-/// Collect Chromium-family cache paths under one `User Data` root. Deduplicates paths.
+/// Collect Chromium-family cache paths under one `User Data` root, per profile
 fn collect_chromium_family_caches(
     local_appdata: &Path,
+    browser: &str,
     user_data_segments: &[&str],
-    paths: &mut Vec<PathBuf>,
-    seen: &mut HashSet<PathBuf>,
-    config: &Config,
+    collector: &mut Collector,
 ) {
     let user_data_root = join_localappdata(local_appdata, user_data_segments);
     if !user_data_root.is_dir() {
@@ -160,134 +259,203 @@ fn collect_chromium_family_caches(
     for rel in CHROMIUM_USER_DATA_ROOT_CACHE_DIRS {
         let candidate = user_data_root.join(rel);
         if rel == &"Crashpad" {
-            let reports = candidate.join("reports");
-            if reports.is_dir() && !config.is_excluded(&reports) && seen.insert(reports.clone()) {
-                paths.push(reports);
-            }
-        } else if candidate.is_dir()
-            && !config.is_excluded(&candidate)
-            && seen.insert(candidate.clone())
-        {
-            paths.push(candidate);
+            collector.add(candidate.join("reports"), browser, SHARED_PROFILE);
+        } else {
+            collector.add(candidate, browser, SHARED_PROFILE);
         }
     }
 
     // Per-profile cache directories
-    let entries = match utils::safe_read_dir(&user_data_root) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
+    let names = chromium_profile_names(&user_data_root);
+    for (dir, name) in chromium_profiles(&user_data_root) {
+        let profile = names.get(&name).unwrap_or(&name);
+        for cache_name in CHROMIUM_PROFILE_CACHE_DIRS {
+            collector.add(dir.join(cache_name), browser, profile);
+        }
+    }
+}
+
+/// Collect Opera's caches: the main profile lives in the root itself
+fn collect_opera_caches(
+    local_appdata: &Path,
+    browser: &str,
+    segments: &[&str],
+    collector: &mut Collector,
+) {
+    let root = join_localappdata(local_appdata, segments);
+    if !root.is_dir() {
+        return;
+    }
+    for cache_name in CHROMIUM_PROFILE_CACHE_DIRS {
+        collector.add(root.join(cache_name), browser, "Default");
+    }
+    let side_profiles = root.join("_side_profiles");
+    if let Ok(entries) = utils::safe_read_dir(&side_profiles) {
+        for dir in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let Some(id) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                continue;
+            };
+            for cache_name in CHROMIUM_PROFILE_CACHE_DIRS {
+                collector.add(dir.join(cache_name), browser, &id);
+            }
         }
-        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+    }
+}
+
+/// Firefox profiles' `cache2` folders, named after the profile
+fn collect_firefox_caches(local_appdata: &Path, collector: &mut Collector) {
+    let firefox_profiles = local_appdata
+        .join("Mozilla")
+        .join("Firefox")
+        .join("Profiles");
+    let Ok(entries) = utils::safe_read_dir(&firefox_profiles) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    dirs.sort();
+    for dir in dirs {
+        let Some(folder) = dir.file_name().and_then(|n| n.to_str()) else {
             continue;
         };
-        if !is_chromium_profile_dir(name) {
+        // `xxxxxxxx.default-release` -> `default-release`
+        let profile = folder
+            .split_once('.')
+            .map(|(_, name)| name)
+            .unwrap_or(folder);
+        collector.add(dir.join("cache2"), "Firefox", profile);
+    }
+}
+
+/// Lowercase process names without `.exe`, for matching against browsers
+fn running_process_names() -> HashSet<String> {
+    let mut system = sysinfo::System::new();
+    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    system
+        .processes()
+        .values()
+        .map(|p| {
+            let name = p.name().to_string_lossy().to_lowercase();
+            name.strip_suffix(".exe").unwrap_or(&name).to_string()
+        })
+        .collect()
+}
+
+/// Cache sizes per browser profile, for the paths in `result` found by the
+/// last scan, biggest first
+pub fn profile_breakdown(result: &CategoryResult) -> Vec<ProfileCache> {
+    let Ok(profile_of) = PROFILE_OF.lock() else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<ProfileCache> = Vec::new();
+    for path in &result.paths {
+        let Some((browser, profile, size)) = profile_of.get(path) else {
             continue;
-        }
-        for cache_name in CHROMIUM_PROFILE_CACHE_DIRS {
-            let cache_path = path.join(cache_name);
-            if cache_path.is_dir()
-                && !config.is_excluded(&cache_path)
-                && seen.insert(cache_path.clone())
-            {
-                paths.push(cache_path);
+        };
+        match profiles
+            .iter_mut()
+            .find(|p| &p.browser == browser && &p.profile == profile)
+        {
+            Some(entry) => {
+                entry.items += 1;
+                entry.size_bytes += size;
             }
+            None => profiles.push(ProfileCache {
+                browser: browser.clone(),
+                profile: profile.clone(),
+                items: 1,
+                size_bytes: *size,
+            }),
         }
     }
+    profiles.sort_by_key(|p| std::cmp::Reverse(p.size_bytes));
+    profiles
+}
+
+/// Browsers whose caches the last scan left alone because they were running
+pub fn skipped_running() -> Vec<String> {
+    SKIPPED_RUNNING
+        .lock()
+        .map(|skipped| skipped.clone())
+        .unwrap_or_default()
 }
 
 /// Scan for browser cache directories
 ///
 /// Checks well-known Windows cache locations for Chromium-family browsers (disk caches
-/// only, not cookies/history databases), Opera, and Firefox.
-pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
+/// only, not cookies/history databases) in every profile, Opera, and Firefox.
+/// Browsers that are running are skipped: they hold their caches open and
+/// would rebuild them straight away.
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
-    let mut paths = Vec::new();
-    let mut seen = HashSet::new();
-
-    let local_appdata = env::var("LOCALAPPDATA").ok().map(PathBuf::from);
-
-    if let Some(ref local_appdata_path) = local_appdata {
-        for (_name, segments) in CHROMIUM_USER_DATA_ROOTS {
-            collect_chromium_family_caches(
-                local_appdata_path,
-                segments,
-                &mut paths,
-                &mut seen,
-                config,
-            );
-        }
+    let mut collector = Collector::new(config);
+    let mut skipped: Vec<String> = Vec::new();
 
-        for (_name, segments) in OPERA_STYLE_CACHES {
-            let cache_path = join_localappdata(local_appdata_path, segments);
-            if cache_path.exists()
-                && !config.is_excluded(&cache_path)
-                && seen.insert(cache_path.clone())
-            {
-                paths.push(cache_path);
+    if let Some(local_appdata) = env::var("LOCALAPPDATA").ok().map(PathBuf::from) {
+        let running = running_process_names();
+        let mut skip = |browser: &str, process: &str| {
+            let is_running = running.contains(process);
+            if is_running {
+                skipped.push(browser.to_string());
             }
-        }
-
-        collect_comet_narrow_caches(local_appdata_path, &mut paths, &mut seen, config);
-    }
+            is_running
+        };
 
-    // Scan Firefox profiles (need to glob for profile directories)
-    if let Some(ref local_appdata_path) = local_appdata {
-        let firefox_profiles = local_appdata_path
-            .join("Mozilla")
-            .join("Firefox")
-            .join("Profiles");
-        if firefox_profiles.exists() {
-            for entry in WalkDir::new(&firefox_profiles)
-                .max_depth(2)
-                .follow_links(false)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path();
-                if path.is_dir()
-                    && path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|n| n.contains(".default"))
-                        .unwrap_or(false)
-                {
-                    let cache2_path = path.join("cache2");
-                    if cache2_path.exists()
-                        && !config.is_excluded(&cache2_path)
-                        && seen.insert(cache2_path.clone())
-                    {
-                        paths.push(cache2_path);
-                    }
-                }
+        for (browser, process, segments) in CHROMIUM_USER_DATA_ROOTS {
+            if join_localappdata(&local_appdata, segments).is_dir() && !skip(browser, process) {
+                collect_chromium_family_caches(&local_appdata, browser, segments, &mut collector);
             }
         }
+        for (browser, process, segments) in OPERA_ROOTS {
+            if join_localappdata(&local_appdata, segments).is_dir() && !skip(browser, process) {
+                collect_opera_caches(&local_appdata, browser, segments, &mut collector);
+            }
+        }
+        if join_localappdata(&local_appdata, COMET_USER_DATA_SEGMENTS).is_dir()
+            && !skip("Comet", "comet")
+        {
+            collect_comet_narrow_caches(&local_appdata, &mut collector);
+        }
+        if local_appdata.join("Mozilla").join("Firefox").is_dir() && !skip("Firefox", "firefox") {
+            collect_firefox_caches(&local_appdata, &mut collector);
+        }
     }
 
-    let mut paths_with_sizes: Vec<(PathBuf, u64)> = paths
+    let mut paths_with_sizes: Vec<(PathBuf, String, String, u64)> = collector
+        .found
         .into_iter()
-        .filter(|p| p.exists())
-        .map(|p| {
-            let size = utils::calculate_dir_size(&p);
-            (p, size)
+        .map(|(p, browser, profile)| {
+            let size = utils::calculate_dir_size_in(&p, walk);
+            (p, browser, profile, size)
         })
-        .filter(|(_, size)| *size > 0)
+        .filter(|(.., size)| *size > 0)
         .collect();
-    paths_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
+    paths_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.3));
 
-    for (path, size) in paths_with_sizes.iter() {
+    let mut profile_of = PROFILE_OF.lock().unwrap_or_else(|e| e.into_inner());
+    profile_of.clear();
+    for (path, browser, profile, size) in paths_with_sizes {
         result.items += 1;
         result.size_bytes += size;
         result.paths.push(path.clone());
+        profile_of.insert(path, (browser, profile, size));
     }
+    skipped.dedup();
+    *SKIPPED_RUNNING.lock().unwrap_or_else(|e| e.into_inner()) = skipped;
 
     Ok(result)
 }
 
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(root, &options.config, &SizeWalk::default())?;
+    Ok(CategoryScan::from_result(
+        "browser",
+        Safety::Safe,
+        result,
+        |_| "browser cache".to_string(),
+    ))
+}
+
 /// Clean (delete) a browser cache directory by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -319,6 +487,42 @@ mod tests {
         assert!(p.to_string_lossy().contains("Chrome"));
     }
 
+    #[test]
+    fn chromium_caches_are_labelled_by_profile() {
+        let local = tempfile::tempdir().unwrap();
+        let user_data = join_localappdata(local.path(), &["Vivaldi", "User Data"]);
+        for dir in ["Default/Cache", "Profile 1/Code Cache", "Crashpad/reports"] {
+            std::fs::create_dir_all(user_data.join(dir)).unwrap();
+        }
+        std::fs::write(
+            user_data.join("Local State"),
+            r#"{"profile":{"info_cache":{"Profile 1":{"name":"Work"},"Default":{"name":""}}}}"#,
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let mut collector = Collector::new(&config);
+        collect_chromium_family_caches(
+            local.path(),
+            "Vivaldi",
+            &["Vivaldi", "User Data"],
+            &mut collector,
+        );
+        let labels: Vec<(&str, &str)> = collector
+            .found
+            .iter()
+            .map(|(_, browser, profile)| (browser.as_str(), profile.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("Vivaldi", SHARED_PROFILE),
+                ("Vivaldi", "Default"),
+                ("Vivaldi", "Work"),
+            ]
+        );
+    }
+
     #[test]
     fn comet_narrow_cache_allowlist_count() {
         assert_eq!(COMET_PROFILE_NARROW_CACHE_DIRS.len(), 5);
@@ -1,9 +1,11 @@
+use super::typed::{CategoryScan, Safety};
 use crate::config::{CategoryConfig, Config};
+use crate::git::{self, RepoState};
 use crate::output::{CategoryResult, OutputMode};
 use crate::project;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
@@ -55,6 +57,40 @@ fn get_build_artifacts(config: Option<&CategoryConfig>) -> Vec<String> {
     artifacts
 }
 
+/// Project roots under `root`, or under the configured `roots` when set.
+/// A root that is itself a project is the only project scanned there.
+fn discover_projects(
+    root: &Path,
+    config: Option<&CategoryConfig>,
+    global_config: &Config,
+) -> Vec<PathBuf> {
+    let roots = config.map(|c| c.root_paths()).unwrap_or_default();
+    let roots = if roots.is_empty() {
+        vec![root.to_path_buf()]
+    } else {
+        roots
+    };
+    let max_depth = config
+        .and_then(|c| c.max_depth)
+        .unwrap_or(project::DEFAULT_MAX_DEPTH);
+
+    let mut projects = Vec::new();
+    for root in roots.iter().filter(|r| r.is_dir()) {
+        for project in project::find_project_roots_with_depth(root, global_config, max_depth) {
+            if !projects.contains(&project) {
+                projects.push(project);
+            }
+        }
+    }
+    projects
+}
+
+/// Whether `exclude_dirty_repos` leaves out a project whose repository has
+/// work that exists nowhere else
+fn skip_for_local_work(state: &RepoState, config: Option<&CategoryConfig>) -> bool {
+    state.has_local_work() && config.is_some_and(|c| c.exclude_dirty_repos)
+}
+
 /// Project artifact information
 #[derive(Debug, Clone)]
 pub struct ProjectArtifact {
@@ -77,19 +113,14 @@ pub fn scan(
     config: Option<&CategoryConfig>,
     global_config: &Config,
     output_mode: OutputMode,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
 
     // Get the list of artifacts to scan (defaults + custom from config)
     let artifacts_to_scan = get_build_artifacts(config);
 
-    // Check if root itself is a project - if so, only scan that
-    let all_project_roots = if crate::project::detect_project_type(root).is_some() {
-        vec![root.to_path_buf()]
-    } else {
-        // Walk to find projects (with exclusion filtering)
-        project::find_project_roots(root, global_config)
-    };
+    let all_project_roots = discover_projects(root, config, global_config);
 
     // Show discovered projects
     if output_mode != OutputMode::Quiet && !all_project_roots.is_empty() {
@@ -107,20 +138,38 @@ pub fn scan(
             // Check if project is inactive (not recently modified)
             let is_active =
                 project::is_project_active(project_root, project_age_days).unwrap_or(true);
+            let repo = if is_active {
+                RepoState::default()
+            } else {
+                git::repo_state_cached(project_root)
+            };
+            let skip = skip_for_local_work(&repo, config);
 
             // Show project as it's being checked (always show in Normal+ mode)
             if output_mode != OutputMode::Quiet {
                 let relative = utils::to_relative_path(project_root, root);
                 let status = if is_active {
                     Theme::status_safe("active")
+                } else if skip {
+                    Theme::status_safe("skipped")
                 } else {
                     Theme::status_review("inactive")
                 };
-                println!("    {} {} ({})", Theme::muted("•"), relative, status);
+                let local_work = repo
+                    .describe()
+                    .map(|work| format!(", {}", Theme::warning(work)))
+                    .unwrap_or_default();
+                println!(
+                    "    {} {} ({}{})",
+                    Theme::muted("•"),
+                    relative,
+                    status,
+                    local_work
+                );
             }
 
-            if is_active {
-                None // Active - skip it
+            if is_active || skip {
+                None // Active, or holds work that isn't pushed - skip it
             } else {
                 Some(project_root.clone()) // Inactive - include it
             }
@@ -168,7 +217,7 @@ pub fn scan(
     let mut artifacts_with_sizes: Vec<(PathBuf, u64)> = all_artifact_paths
         .iter()
         .map(|path| {
-            let size = utils::calculate_dir_size(path);
+            let size = utils::calculate_dir_size_in(path, walk);
             (path.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
@@ -195,24 +244,21 @@ pub fn scan_with_progress(
     global_config: &Config,
     output_mode: OutputMode,
     tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     let reporter = Arc::new(ScanPathReporter::new("Build Artifacts", tx.clone(), 75));
 
     let mut result = CategoryResult::default();
     let artifacts_to_scan = get_build_artifacts(config);
 
-    let all_project_roots = if crate::project::detect_project_type(root).is_some() {
-        vec![root.to_path_buf()]
-    } else {
-        project::find_project_roots(root, global_config)
-    };
+    let all_project_roots = discover_projects(root, config, global_config);
 
     let inactive_project_roots: Vec<PathBuf> = all_project_roots
         .par_iter()
         .filter_map(|project_root| {
             let is_active =
                 project::is_project_active(project_root, project_age_days).unwrap_or(true);
-            if is_active {
+            if is_active || skip_for_local_work(&git::repo_state_cached(project_root), config) {
                 None
             } else {
                 Some(project_root.clone())
@@ -230,7 +276,7 @@ pub fn scan_with_progress(
         .iter()
         .map(|path| {
             let rep = Arc::clone(&reporter);
-            let size = utils::calculate_dir_size_with_progress(path, &|p| rep.emit_path(p));
+            let size = utils::calculate_dir_size_with_progress(path, walk, &|p| rep.emit_path(p));
             (path.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
@@ -262,6 +308,66 @@ fn find_build_artifacts(project_path: &Path, artifacts_to_scan: &[String]) -> Ve
     artifacts
 }
 
+/// Build artifact folders present directly in `project_root` (defaults plus
+/// `custom_artifacts` from config)
+pub fn project_artifacts(project_root: &Path, config: Option<&CategoryConfig>) -> Vec<PathBuf> {
+    find_build_artifacts(project_root, &get_build_artifacts(config))
+}
+
+/// Typed options for scanning build artifacts on their own
+#[derive(Debug, Clone)]
+pub struct BuildScanOptions {
+    /// Projects touched within this many days count as active and are skipped
+    pub project_age_days: u64,
+    /// Artifact folder names to look for in addition to the defaults
+    pub custom_artifacts: Vec<String>,
+    pub config: Config,
+}
+
+impl Default for BuildScanOptions {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            project_age_days: config.thresholds.project_age_days,
+            custom_artifacts: Vec::new(),
+            config,
+        }
+    }
+}
+
+/// Scan build artifacts of inactive projects under `root`
+pub fn scan_with_options(root: &Path, options: &BuildScanOptions) -> Result<CategoryScan> {
+    let category_config = CategoryConfig {
+        custom_artifacts: options.custom_artifacts.clone(),
+        ..Default::default()
+    };
+    let result = scan(
+        root,
+        options.project_age_days,
+        Some(&category_config),
+        &options.config,
+        OutputMode::Quiet,
+        &SizeWalk::default(),
+    )?;
+    Ok(CategoryScan::from_result(
+        "build",
+        Safety::Safe,
+        result,
+        |path| {
+            let artifact = path.file_name().unwrap_or_default().to_string_lossy();
+            let project = path
+                .parent()
+                .and_then(|p| p.file_name())
+                .unwrap_or_default()
+                .to_string_lossy();
+            format!(
+                "{} in project {} (inactive for {}+ days)",
+                artifact, project, options.project_age_days
+            )
+        },
+    ))
+}
+
 /// Clean (delete) a build artifact directory by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
@@ -1,8 +1,9 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
 use crate::theme::Theme;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
 use bytesize;
 use std::env;
@@ -90,6 +91,101 @@ const CACHE_LOCATIONS: &[(&str, CacheLocation)] = &[
     ),
 ];
 
+/// Machine-wide caches shared by every user on the PC. They're reported apart
+/// from the per-user caches above, never added to this user's totals, and only
+/// cleaned when wole runs elevated.
+///
+/// `%ProgramData%\Package Cache` is deliberately absent: installers keep the
+/// MSI/bundle payloads there that repair and uninstall need.
+const MACHINE_CACHE_LOCATIONS: &[(&str, MachineLocation)] = &[
+    (
+        "Chocolatey HTTP cache",
+        MachineLocation::ProgramData(&["ChocolateyHttpCache"]),
+    ),
+    (
+        "Chocolatey package backups",
+        MachineLocation::ProgramData(&["chocolatey", "lib-bkp"]),
+    ),
+    (
+        "Scoop global cache",
+        MachineLocation::ScoopGlobal(&["cache"]),
+    ),
+    (
+        "NuGet fallback folder",
+        MachineLocation::ProgramFiles(&["dotnet", "sdk", "NuGetFallbackFolder"]),
+    ),
+    (
+        "NuGet offline packages",
+        MachineLocation::ProgramFilesX86(&["Microsoft SDKs", "NuGetPackages"]),
+    ),
+];
+
+enum MachineLocation {
+    ProgramData(&'static [&'static str]),
+    ProgramFiles(&'static [&'static str]),
+    ProgramFilesX86(&'static [&'static str]),
+    /// `%SCOOP_GLOBAL%`, or `%ProgramData%\scoop` when unset
+    ScoopGlobal(&'static [&'static str]),
+}
+
+impl MachineLocation {
+    fn resolve(&self) -> Option<PathBuf> {
+        let (base, subpaths) = match self {
+            MachineLocation::ProgramData(subpaths) => (env::var("ProgramData").ok()?, subpaths),
+            MachineLocation::ProgramFiles(subpaths) => (env::var("ProgramFiles").ok()?, subpaths),
+            MachineLocation::ProgramFilesX86(subpaths) => {
+                (env::var("ProgramFiles(x86)").ok()?, subpaths)
+            }
+            MachineLocation::ScoopGlobal(subpaths) => (
+                env::var("SCOOP_GLOBAL")
+                    .or_else(|_| {
+                        env::var("ProgramData")
+                            .map(|p| PathBuf::from(p).join("scoop").to_string_lossy().to_string())
+                    })
+                    .ok()?,
+                subpaths,
+            ),
+        };
+        let mut path = PathBuf::from(base);
+        for subpath in *subpaths {
+            path = path.join(subpath);
+        }
+        Some(path)
+    }
+}
+
+/// Where machine-wide caches live on this machine
+pub fn machine_cache_roots() -> Vec<PathBuf> {
+    MACHINE_CACHE_LOCATIONS
+        .iter()
+        .filter_map(|(_name, location)| location.resolve())
+        .collect()
+}
+
+/// Find machine-wide caches and their sizes, biggest first
+fn scan_machine_caches<F>(config: &Config, walk: &SizeWalk, on_path: &F) -> Vec<(PathBuf, u64)>
+where
+    F: Fn(&Path) + Sync,
+{
+    let mut found: Vec<(PathBuf, u64)> = machine_cache_roots()
+        .into_iter()
+        .filter(|path| path.exists() && !config.is_excluded(path))
+        .map(|path| {
+            let size = utils::calculate_dir_size_with_progress(&path, walk, on_path);
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    found.sort_by_key(|b| std::cmp::Reverse(b.1));
+    found
+}
+
+/// Record machine-wide caches on `result` without touching its per-user totals
+fn set_machine_caches(result: &mut CategoryResult, machine: Vec<(PathBuf, u64)>) {
+    result.machine_bytes = machine.iter().map(|(_, size)| size).sum();
+    result.machine_paths = machine.into_iter().map(|(path, _)| path).collect();
+}
+
 enum CacheLocation {
     LocalAppData(&'static str),
     LocalAppDataNested(&'static [&'static str]),
@@ -101,7 +197,12 @@ enum CacheLocation {
 /// Checks well-known Windows cache locations for package managers, dev tools, and
 /// Claude Code session data paths under `%USERPROFILE%\.claude`.
 /// Uses shared calculate_dir_size for consistent size calculation.
-pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<CategoryResult> {
+pub fn scan(
+    _root: &Path,
+    config: &Config,
+    output_mode: OutputMode,
+    walk: &SizeWalk,
+) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
     let mut candidates = Vec::new();
 
@@ -150,7 +251,7 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
     let mut paths_with_sizes: Vec<(PathBuf, u64)> = candidates
         .iter()
         .map(|(_name, p)| {
-            let size = utils::calculate_dir_size(p);
+            let size = utils::calculate_dir_size_in(p, walk);
             (p.clone(), size)
         })
         .filter(|(_, size)| *size > 0)
@@ -199,6 +300,24 @@ pub fn scan(_root: &Path, config: &Config, output_mode: OutputMode) -> Result<Ca
         result.paths.push(path);
     }
 
+    let machine = scan_machine_caches(config, walk, &|_: &Path| {});
+    if output_mode != OutputMode::Quiet && !machine.is_empty() {
+        println!(
+            "  {} Found {} machine-wide caches (shared by all users, need admin to clean):",
+            Theme::muted("→"),
+            machine.len()
+        );
+        for (path, size) in &machine {
+            println!(
+                "      {} {} ({})",
+                Theme::muted("→"),
+                path.display(),
+                Theme::size(&bytesize::to_string(*size, false))
+            );
+        }
+    }
+    set_machine_caches(&mut result, machine);
+
     Ok(result)
 }
 
@@ -208,6 +327,7 @@ pub fn scan_with_progress(
     _root: &Path,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     const CATEGORY: &str = "Package Cache";
     let total = CACHE_LOCATIONS.len() as u64;
@@ -249,7 +369,7 @@ pub fn scan_with_progress(
 
         if let Some(cache_path) = cache_path {
             if cache_path.exists() && !config.is_excluded(&cache_path) {
-                let size = utils::calculate_dir_size_with_progress(&cache_path, &on_path);
+                let size = utils::calculate_dir_size_with_progress(&cache_path, walk, &on_path);
                 if size > 0 {
                     files_with_sizes.push((cache_path.clone(), size));
                 }
@@ -281,6 +401,8 @@ pub fn scan_with_progress(
         result.paths.push(path);
     }
 
+    set_machine_caches(&mut result, scan_machine_caches(config, walk, &on_path));
+
     let _ = tx.send(ScanProgressEvent::CategoryFinished {
         category: CATEGORY.to_string(),
         items: result.items,
@@ -290,6 +412,22 @@ pub fn scan_with_progress(
     Ok(result)
 }
 
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(
+        root,
+        &options.config,
+        OutputMode::Quiet,
+        &SizeWalk::default(),
+    )?;
+    Ok(CategoryScan::from_result(
+        "cache",
+        Safety::Safe,
+        result,
+        |_| "package manager cache".to_string(),
+    ))
+}
+
 /// Clean (delete) a package cache directory by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -319,4 +457,21 @@ mod tests {
             .iter()
             .any(|(n, _)| *n == "Claude Code projects"));
     }
+
+    #[test]
+    fn machine_caches_stay_out_of_user_totals() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("cache")).unwrap();
+        std::fs::write(dir.path().join("cache/pkg.zip"), b"12345").unwrap();
+        env::set_var("SCOOP_GLOBAL", dir.path());
+        let resolved = MachineLocation::ScoopGlobal(&["cache"]).resolve();
+        env::remove_var("SCOOP_GLOBAL");
+        assert_eq!(resolved, Some(dir.path().join("cache")));
+
+        let mut result = CategoryResult::default();
+        set_machine_caches(&mut result, vec![(dir.path().join("cache"), 5)]);
+        assert_eq!(result.machine_bytes, 5);
+        assert_eq!(result.items, 0);
+        assert_eq!(result.size_bytes, 0);
+    }
 }
@@ -0,0 +1,163 @@
+//! Downloaded cloud files that could be made online-only again
+//!
+//! OneDrive's Files On-Demand keeps each synced file either online-only (a
+//! placeholder that takes no local space) or downloaded. Downloaded files
+//! that aren't pinned to the device ("Always keep on this device") can be
+//! turned back into placeholders with "Free up space" without losing them,
+//! which is what Storage Sense does for files left unopened for a month.
+//!
+//! With `[categories.large] cloud_files = true`, the Large Files scan lists
+//! those files. They're review only and never cleaned: freeing them is
+//! OneDrive's job, not a deletion.
+
+use crate::categories::old::{self, FileAge};
+use crate::config::{AgeBasis, Config};
+use crate::utils;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Environment variables pointing at the synced OneDrive folders
+const ONEDRIVE_VARS: &[&str] = &["OneDrive", "OneDriveConsumer", "OneDriveCommercial"];
+
+/// Smaller files free too little to be worth listing
+const MIN_SIZE_BYTES: u64 = 1024 * 1024;
+
+/// Storage Sense's default: files not opened for this long go online-only
+const MIN_UNUSED_DAYS: u64 = 30;
+
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0400;
+const FILE_ATTRIBUTE_PINNED: u32 = 0x0008_0000;
+const FILE_ATTRIBUTE_UNPINNED: u32 = 0x0010_0000;
+
+/// How a synced file is kept on this device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudState {
+    /// A placeholder; the content is only in the cloud
+    OnlineOnly,
+    /// Downloaded, and OneDrive may free it again
+    Downloaded,
+    /// Downloaded and pinned with "Always keep on this device"
+    Pinned,
+}
+
+impl CloudState {
+    /// State of a file from its Windows attributes; `None` for plain files
+    /// that a sync provider doesn't manage
+    pub fn from_attributes(attributes: u32) -> Option<Self> {
+        // Unpinned files are already on their way to online-only
+        if utils::is_placeholder_attributes(attributes) || attributes & FILE_ATTRIBUTE_UNPINNED != 0
+        {
+            Some(CloudState::OnlineOnly)
+        } else if attributes & FILE_ATTRIBUTE_PINNED != 0 {
+            Some(CloudState::Pinned)
+        } else if attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+            Some(CloudState::Downloaded)
+        } else {
+            None
+        }
+    }
+}
+
+/// A downloaded cloud file that could be freed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudFile {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// How long it has gone unused
+    pub age: FileAge,
+}
+
+/// The synced OneDrive folders on this machine
+pub fn onedrive_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = ONEDRIVE_VARS
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir())
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// Downloaded, unpinned files under the OneDrive folders that have gone
+/// unused for a month, largest first
+pub fn scan(config: &Config) -> Vec<CloudFile> {
+    let now = SystemTime::now();
+    let access_tracked = old::access_times_tracked();
+    let mut files: Vec<CloudFile> = onedrive_roots()
+        .iter()
+        .flat_map(|root| {
+            WalkDir::new(root)
+                .follow_links(false)
+                .into_iter()
+                .filter_entry(|e| !config.is_excluded(e.path()))
+                .filter_map(|e| e.ok())
+        })
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            if meta.len() < MIN_SIZE_BYTES || state_of(&meta) != Some(CloudState::Downloaded) {
+                return None;
+            }
+            let times = utils::FileTimes::from_metadata(&meta);
+            let age = FileAge::of(&times, AgeBasis::Best, access_tracked, now)?;
+            (age.days >= MIN_UNUSED_DAYS).then(|| CloudFile {
+                path: e.into_path(),
+                size_bytes: meta.len(),
+                age,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    files
+}
+
+#[cfg(windows)]
+fn state_of(meta: &std::fs::Metadata) -> Option<CloudState> {
+    use std::os::windows::fs::MetadataExt;
+    CloudState::from_attributes(meta.file_attributes())
+}
+
+#[cfg(not(windows))]
+fn state_of(_meta: &std::fs::Metadata) -> Option<CloudState> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unpinned_downloads_can_be_freed() {
+        const RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+        const ARCHIVE: u32 = 0x20;
+
+        assert_eq!(CloudState::from_attributes(ARCHIVE), None);
+        assert_eq!(
+            CloudState::from_attributes(ARCHIVE | FILE_ATTRIBUTE_REPARSE_POINT),
+            Some(CloudState::Downloaded)
+        );
+        assert_eq!(
+            CloudState::from_attributes(FILE_ATTRIBUTE_REPARSE_POINT | FILE_ATTRIBUTE_PINNED),
+            Some(CloudState::Pinned)
+        );
+        assert_eq!(
+            CloudState::from_attributes(FILE_ATTRIBUTE_REPARSE_POINT | RECALL_ON_DATA_ACCESS),
+            Some(CloudState::OnlineOnly)
+        );
+        assert_eq!(
+            CloudState::from_attributes(FILE_ATTRIBUTE_REPARSE_POINT | FILE_ATTRIBUTE_UNPINNED),
+            Some(CloudState::OnlineOnly)
+        );
+        assert!(utils::is_placeholder_attributes(RECALL_ON_DATA_ACCESS));
+        assert!(!utils::is_placeholder_attributes(
+            ARCHIVE | FILE_ATTRIBUTE_PINNED
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+//! User-defined cleanup rules ("Custom Rules" category)
+//!
+//! Rules live in `[[custom_categories]]` in the config file, either written by
+//! hand or imported from BleachBit/CCleaner definitions (see `rules_import`).
+//! Each rule names a file or folder (with %VAR% expansion and `*` wildcards in
+//! path components) plus optional file name globs. Rules that resolve to a
+//! drive root, the user profile or a system folder are ignored.
+
+use crate::config::{Config, CustomRule};
+use crate::output::CategoryResult;
+use crate::utils;
+use anyhow::Result;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Maximum directory depth for recursive rules
+const MAX_DEPTH: usize = 12;
+
+/// Scan all enabled custom categories
+pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
+    let mut result = CategoryResult::default();
+    let mut seen = HashSet::new();
+
+    for category in config.custom_categories.iter().filter(|c| c.enabled) {
+        for rule in &category.rules {
+            for (path, size) in scan_rule(rule, config) {
+                if seen.insert(path.clone()) {
+                    result.size_bytes += size;
+                    result.paths.push(path);
+                }
+            }
+        }
+    }
+
+    result.items = result.paths.len();
+    Ok(result)
+}
+
+/// Files matched by a single rule
+pub fn scan_rule(rule: &CustomRule, config: &Config) -> Vec<(PathBuf, u64)> {
+    let patterns = match compile_patterns(&rule.patterns) {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+    let mut found = Vec::new();
+
+    for root in expand_path(&expand_vars(&rule.path)) {
+        if is_too_broad(&root) || utils::is_system_path(&root) || config.is_excluded(&root) {
+            continue;
+        }
+        let Ok(meta) = std::fs::symlink_metadata(&root) else {
+            continue;
+        };
+        if meta.is_file() {
+            found.push((root, meta.len()));
+            continue;
+        }
+        if !meta.is_dir() {
+            continue;
+        }
+
+        let max_depth = if rule.recursive { MAX_DEPTH } else { 1 };
+        for entry in WalkDir::new(&root)
+            .max_depth(max_depth)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !utils::should_skip_entry(e.path()))
+        {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    crate::elevation::note_walkdir_error(&e);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() || config.is_excluded(entry.path()) {
+                continue;
+            }
+            if let Some(ref globs) = patterns {
+                if !globs.is_match(entry.file_name()) {
+                    continue;
+                }
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            found.push((entry.into_path(), size));
+        }
+    }
+
+    found
+}
+
+/// Compile file name globs. Outer None = invalid patterns (rule is skipped),
+/// inner None = no patterns (match everything).
+fn compile_patterns(patterns: &[String]) -> Option<Option<GlobSet>> {
+    if patterns.is_empty() || patterns.iter().any(|p| p == "*" || p == "*.*") {
+        return Some(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .case_insensitive(true)
+            .literal_separator(true)
+            .build()
+            .ok()?;
+        builder.add(glob);
+    }
+    builder.build().ok().map(Some)
+}
+
+/// Expand `%VAR%` references. Unknown variables are left as-is so the path
+/// simply doesn't exist instead of collapsing to something broader.
+pub fn expand_vars(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(end) if end > 0 => {
+                let name = &after[..end];
+                match std::env::var(name) {
+                    Ok(value) => out.push_str(&value),
+                    Err(_) => {
+                        out.push('%');
+                        out.push_str(name);
+                        out.push('%');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('%');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Expand `*`/`?` wildcards in path components into existing paths
+pub fn expand_path(path: &str) -> Vec<PathBuf> {
+    let path = PathBuf::from(path.replace('\\', std::path::MAIN_SEPARATOR_STR));
+    let mut current = vec![PathBuf::new()];
+
+    for component in path.components() {
+        let name = component.as_os_str().to_string_lossy();
+        let is_wildcard =
+            matches!(component, Component::Normal(_)) && (name.contains('*') || name.contains('?'));
+
+        if !is_wildcard {
+            for base in &mut current {
+                base.push(component.as_os_str());
+            }
+            continue;
+        }
+
+        let Ok(glob) = GlobBuilder::new(&name)
+            .case_insensitive(true)
+            .build()
+            .map(|g| g.compile_matcher())
+        else {
+            return Vec::new();
+        };
+        let mut next = Vec::new();
+        for base in &current {
+            let Ok(entries) = std::fs::read_dir(base) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if glob.is_match(entry.file_name()) {
+                    next.push(entry.path());
+                }
+            }
+        }
+        next.sort();
+        current = next;
+        if current.is_empty() {
+            break;
+        }
+    }
+
+    current.retain(|p| !p.as_os_str().is_empty());
+    current
+}
+
+/// Paths a rule must never cover wholesale: drive roots and the user profile
+fn is_too_broad(path: &Path) -> bool {
+    let normal = path
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .count();
+    if normal == 0 {
+        return true;
+    }
+    ["USERPROFILE", "HOME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .any(|home| Path::new(&home) == path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomCategory;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn expands_known_vars_and_keeps_unknown() {
+        std::env::set_var("WOLE_TEST_CUSTOM_DIR", "/tmp/wole");
+        assert_eq!(
+            expand_vars("%WOLE_TEST_CUSTOM_DIR%/cache"),
+            "/tmp/wole/cache"
+        );
+        assert_eq!(
+            expand_vars("%WOLE_MISSING_VAR%/cache"),
+            "%WOLE_MISSING_VAR%/cache"
+        );
+        assert_eq!(expand_vars("100% sure"), "100% sure");
+    }
+
+    #[test]
+    fn scans_wildcard_paths_with_patterns() {
+        let dir = TempDir::new().unwrap();
+        for profile in ["abc.default", "xyz.dev"] {
+            let cache = dir.path().join("Profiles").join(profile).join("cache2");
+            fs::create_dir_all(cache.join("entries")).unwrap();
+            fs::write(cache.join("entries/blob"), b"12345").unwrap();
+            fs::write(cache.join("index.log"), b"123").unwrap();
+            fs::write(cache.join("keep.db"), b"1").unwrap();
+        }
+
+        let mut config = Config::default();
+        config.custom_categories.push(CustomCategory {
+            name: "Test: Cache".to_string(),
+            description: None,
+            source: None,
+            enabled: true,
+            rules: vec![
+                CustomRule {
+                    path: format!("{}/Profiles/*/cache2", dir.path().display()),
+                    patterns: vec!["*.LOG".to_string()],
+                    recursive: false,
+                },
+                CustomRule {
+                    path: format!("{}/Profiles/*.default/cache2/entries", dir.path().display()),
+                    patterns: Vec::new(),
+                    recursive: true,
+                },
+            ],
+        });
+
+        let result = scan(dir.path(), &config).unwrap();
+        assert_eq!(result.items, 3);
+        assert_eq!(result.size_bytes, 3 + 3 + 5);
+        assert!(!result.paths.iter().any(|p| p.ends_with("keep.db")));
+
+        config.custom_categories[0].enabled = false;
+        assert_eq!(scan(dir.path(), &config).unwrap().items, 0);
+    }
+
+    #[test]
+    fn refuses_overly_broad_roots() {
+        assert!(is_too_broad(Path::new("/")));
+        assert!(!is_too_broad(Path::new("/var/cache/app")));
+    }
+}
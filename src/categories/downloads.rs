@@ -1,3 +1,4 @@
+use super::typed::{self, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::{CategoryResult, OutputMode};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
@@ -226,6 +227,42 @@ pub fn scan_with_progress(
     Ok(result)
 }
 
+/// Typed options for scanning old downloads on their own
+#[derive(Debug, Clone)]
+pub struct DownloadsScanOptions {
+    pub min_age_days: u64,
+    pub config: Config,
+}
+
+impl Default for DownloadsScanOptions {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            min_age_days: config.thresholds.min_age_days,
+            config,
+        }
+    }
+}
+
+/// Scan for downloads not modified in `min_age_days`
+pub fn scan_with_options(root: &Path, options: &DownloadsScanOptions) -> Result<CategoryScan> {
+    let result = scan(
+        root,
+        options.min_age_days,
+        &options.config,
+        OutputMode::Quiet,
+    )?;
+    Ok(CategoryScan::from_result(
+        "downloads",
+        Safety::Review,
+        result,
+        |path| match typed::age_days(path) {
+            Some(days) => format!("download not modified in {} days", days),
+            None => format!("download older than {} days", options.min_age_days),
+        },
+    ))
+}
+
 /// Clean (delete) a file from Downloads by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
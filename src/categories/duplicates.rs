@@ -1,56 +1,68 @@
+use super::similar_images::{self, SimilarImageGroup};
+use crate::cleaner::path_identity;
 use crate::config::{Config, DuplicatesConfig};
 use crate::output::CategoryResult;
+use crate::scan_cache::{FileHashes, ScanCache};
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
+use crate::tasks::CancelToken;
 use crate::utils;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use blake3::Hasher;
 use jwalk::WalkDir;
 use memmap2::MmapOptions;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::Sender;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wole_core::duplicates;
 
 /// Size of partial hash sample (first N bytes)
 const PARTIAL_HASH_SIZE: usize = 4096; // 4KB
 
+/// Files larger than this are hashed one segment at a time, so an
+/// interrupted scan resumes at the first segment not yet hashed
+const HASH_SEGMENT_BYTES: u64 = 256 * 1024 * 1024; // 256MB
+
+/// Hashes are saved to the scan cache in batches of this many files
+const SAVE_BATCH: usize = 256;
+
+/// Minimum time between hashing progress updates sent to the TUI
+const HASH_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Maximum number of duplicate groups to return (prevents overwhelming output)
 const MAX_GROUPS: usize = 50;
 
-/// Extract the number from a filename suffix pattern like " (1)" or " (2)"
-/// Returns u32::MAX if no number is found (to sort files without numbers first)
-fn extract_suffix_number(filename: &str) -> u32 {
-    // Look for pattern like " (1)" or " (123)" before the file extension
-    if let Some(start) = filename.rfind(" (") {
-        if let Some(end) = filename[start + 2..].find(')') {
-            let num_str = &filename[start + 2..start + 2 + end];
-            if let Ok(num) = num_str.parse::<u32>() {
-                return num;
-            }
-        }
-    }
-    u32::MAX // Return max value so files without numbers sort first
-}
-
-/// Check if a filename appears to be a duplicate (has common duplicate patterns)
-fn is_duplicate_filename(filename: &str) -> bool {
-    // Check for patterns like " (1)", " - Copy", " - Copy (2)", etc.
-    filename.contains(" (") && filename.contains(")")
-        || filename.contains(" - Copy")
-        || filename.contains(" - copy")
-        || filename.contains("_copy")
-        || filename.contains("_Copy")
+/// Duplicate file group
+pub type DuplicateGroup = duplicates::DuplicateGroup<PathBuf>;
+
+/// What `wole clean --duplicates` does with the extra copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupeMode {
+    /// Delete them (Recycle Bin unless `--permanent`)
+    #[default]
+    Delete,
+    /// Replace each with a hardlink to the kept copy; frees the space while
+    /// every path keeps working
+    Hardlink,
 }
 
-/// Duplicate file group
-#[derive(Debug, Clone)]
-pub struct DuplicateGroup {
-    pub hash: String,
-    pub size: u64,
-    pub paths: Vec<PathBuf>,
+impl DedupeMode {
+    /// Parse `--dedupe-mode` (`delete` or `hardlink`)
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "delete" => Ok(Self::Delete),
+            "hardlink" => Ok(Self::Hardlink),
+            _ => Err(anyhow::anyhow!(
+                "Unknown dedupe mode: {}. Supported: delete, hardlink",
+                s
+            )),
+        }
+    }
 }
 
 /// Result for duplicate file detection
@@ -58,6 +70,8 @@ pub struct DuplicateGroup {
 pub struct DuplicatesResult {
     pub groups: Vec<DuplicateGroup>,
     pub total_wasted: u64, // Size of all duplicates minus one copy each
+    /// Images that look alike (`similar_images`); review only, never cleaned
+    pub similar_images: Vec<SimilarImageGroup>,
 }
 
 impl DuplicatesResult {
@@ -66,56 +80,25 @@ impl DuplicatesResult {
     pub fn to_category_result(&self) -> CategoryResult {
         let mut paths = Vec::new();
         for group in &self.groups {
-            // Separate files into those with duplicate patterns and those without
-            let mut originals: Vec<&PathBuf> = Vec::new();
-            let mut duplicates: Vec<&PathBuf> = Vec::new();
-
-            for path in &group.paths {
-                let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                if is_duplicate_filename(filename) {
-                    duplicates.push(path);
-                } else {
-                    originals.push(path);
-                }
-            }
-
-            // Sort duplicates by suffix number (lower numbers first, to delete higher copies first)
-            duplicates.sort_by(|a, b| {
-                let a_name = a.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                let b_name = b.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                let a_num = extract_suffix_number(a_name);
-                let b_num = extract_suffix_number(b_name);
-                a_num.cmp(&b_num)
-            });
-
-            // If there are files with duplicate patterns, ONLY flag those as duplicates
-            // (never flag files without patterns like "lto_meeting_isa.ogg")
-            if !duplicates.is_empty() {
-                // Add all files with duplicate patterns
-                for path in &duplicates {
-                    paths.push((*path).clone());
-                }
-            } else {
-                // No files have duplicate patterns - fall back to keeping one and flagging the rest
-                // Sort originals alphabetically and keep the first one
-                originals.sort_by(|a, b| {
-                    let a_name = a.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    let b_name = b.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    a_name.cmp(b_name)
-                });
-
-                // Add all but the first one
-                for path in originals.iter().skip(1) {
-                    paths.push((*path).clone());
-                }
-            }
+            let names: Vec<&str> = group
+                .paths
+                .iter()
+                .map(|p| p.file_name().and_then(|n| n.to_str()).unwrap_or(""))
+                .collect();
+            // Files named like copies ("(1)", " - Copy") when there are any,
+            // otherwise all but one
+            paths.extend(
+                duplicates::copies_to_remove(&names)
+                    .into_iter()
+                    .map(|i| group.paths[i].clone()),
+            );
         }
 
         CategoryResult {
             items: paths.len(),
             size_bytes: self.total_wasted,
             paths,
+            ..Default::default()
         }
     }
 
@@ -147,18 +130,156 @@ pub fn scan_with_config(
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
 ) -> Result<DuplicatesResult> {
-    scan_with_config_internal(root, config, global_config, None)
+    let store = HashStore::open(global_config);
+    scan_with_config_internal(root, config, global_config, None, &store, None)
 }
 
-/// Scan for duplicate files with configuration + TUI progress updates (current file path).
+/// Scan for duplicate files with configuration + TUI progress updates
+/// (current file path, files and bytes hashed). Once `cancel` is set, hashing
+/// stops and the scan returns an error; what was hashed is kept for the next
+/// scan.
 pub fn scan_with_config_with_progress(
     root: &Path,
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
     tx: &Sender<ScanProgressEvent>,
+    cancel: Option<&CancelToken>,
 ) -> Result<DuplicatesResult> {
     let reporter = Arc::new(ScanPathReporter::new("Duplicates", tx.clone(), 75));
-    scan_with_config_internal(root, config, global_config, Some(reporter))
+    let store = HashStore::open(global_config);
+    scan_with_config_internal(root, config, global_config, Some(reporter), &store, cancel)
+}
+
+/// Hashes kept in the scan cache, so an interrupted scan picks up where it
+/// stopped instead of hashing everything again. Entries are only reused while
+/// the file keeps its size and modification time.
+struct HashStore {
+    cache: Option<Mutex<ScanCache>>,
+    pending: Mutex<Vec<FileHashes>>,
+}
+
+impl HashStore {
+    /// The scan cache's store, or one that keeps nothing when the cache is off
+    fn open(config: &Config) -> Self {
+        let cache = if config.cache.enabled {
+            ScanCache::open().ok()
+        } else {
+            None
+        };
+        Self::with_cache(cache)
+    }
+
+    fn with_cache(cache: Option<ScanCache>) -> Self {
+        Self {
+            cache: cache.map(Mutex::new),
+            pending: Mutex::default(),
+        }
+    }
+
+    /// What an earlier scan hashed of `path`, or an empty record. None when
+    /// the file can't be read.
+    fn get(&self, path: &Path) -> Option<FileHashes> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let (size, mtime) = (metadata.len(), metadata.modified().ok()?);
+        let cached = self.cache.as_ref().and_then(|cache| {
+            let cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+            cache.get_file_hashes(path, size, mtime).ok().flatten()
+        });
+        Some(cached.unwrap_or(FileHashes {
+            path: path.to_path_buf(),
+            size,
+            mtime,
+            partial: None,
+            full: None,
+            segments: Vec::new(),
+        }))
+    }
+
+    /// Queue `hashes` for saving; `now` writes the queue right away
+    fn put(&self, hashes: FileHashes, now: bool) {
+        if self.cache.is_none() {
+            return;
+        }
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.push(hashes);
+        if now || pending.len() >= SAVE_BATCH {
+            let batch = std::mem::take(&mut *pending);
+            drop(pending);
+            self.save(&batch);
+        }
+    }
+
+    fn flush(&self) {
+        let batch = std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+        self.save(&batch);
+    }
+
+    fn save(&self, batch: &[FileHashes]) {
+        if let (Some(cache), false) = (&self.cache, batch.is_empty()) {
+            // The cache is optional; a failed write only costs rehashing
+            let _ = cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .save_file_hashes(batch);
+        }
+    }
+}
+
+/// Files and bytes fully hashed, reported to the TUI at most every
+/// `HASH_PROGRESS_INTERVAL`. Hashes found in the cache count as done.
+struct HashingProgress {
+    tx: Option<Mutex<Sender<ScanProgressEvent>>>,
+    files_total: u64,
+    bytes_total: u64,
+    files_hashed: AtomicU64,
+    bytes_hashed: AtomicU64,
+    last_sent: Mutex<Option<Instant>>,
+}
+
+impl HashingProgress {
+    fn new(tx: Option<Sender<ScanProgressEvent>>, files: &[&FileHashes]) -> Self {
+        let done = files.iter().filter(|f| f.full.is_some());
+        let resumed: u64 = files
+            .iter()
+            .filter(|f| f.full.is_none())
+            .map(|f| (f.segments.len() as u64 * HASH_SEGMENT_BYTES).min(f.size))
+            .sum();
+        Self {
+            tx: tx.map(Mutex::new),
+            files_total: files.len() as u64,
+            bytes_total: files.iter().map(|f| f.size).sum(),
+            files_hashed: AtomicU64::new(done.clone().count() as u64),
+            bytes_hashed: AtomicU64::new(done.map(|f| f.size).sum::<u64>() + resumed),
+            last_sent: Mutex::new(None),
+        }
+    }
+
+    fn add(&self, files: u64, bytes: u64) {
+        self.files_hashed.fetch_add(files, Ordering::Relaxed);
+        self.bytes_hashed.fetch_add(bytes, Ordering::Relaxed);
+        self.send(false);
+    }
+
+    fn send(&self, force: bool) {
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        if !force && last_sent.is_some_and(|t| t.elapsed() < HASH_PROGRESS_INTERVAL) {
+            return;
+        }
+        *last_sent = Some(Instant::now());
+        let _ = tx
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .send(ScanProgressEvent::Hashing {
+                category: "Duplicates".to_string(),
+                files_hashed: self.files_hashed.load(Ordering::Relaxed),
+                files_total: self.files_total,
+                bytes_hashed: self.bytes_hashed.load(Ordering::Relaxed),
+                bytes_total: self.bytes_total,
+            });
+    }
 }
 
 fn scan_with_config_internal(
@@ -166,7 +287,10 @@ fn scan_with_config_internal(
     config: Option<&DuplicatesConfig>,
     global_config: &Config,
     reporter: Option<Arc<ScanPathReporter>>,
+    store: &HashStore,
+    cancel: Option<&CancelToken>,
 ) -> Result<DuplicatesResult> {
+    let is_cancelled = || cancel.is_some_and(|c| c.is_cancelled());
     let mut result = DuplicatesResult::default();
 
     // Determine scan roots: use config paths if provided, otherwise use root argument
@@ -207,6 +331,11 @@ fn scan_with_config_internal(
         .map(|c| c.buffer_size_bytes)
         .unwrap_or(8 * 1024 * 1024); // Default 8MB
 
+    // Images for similarity matching are collected during the same walk
+    let images: Option<std::sync::Mutex<Vec<(PathBuf, u64)>>> = config
+        .filter(|c| c.similar_images)
+        .map(|_| Default::default());
+
     // Step 1: Group files by size (using parallel directory traversal)
     let size_groups: HashMap<u64, Vec<PathBuf>> = {
         use std::sync::Mutex;
@@ -292,8 +421,8 @@ fn scan_with_config_internal(
                         return;
                     }
 
-                    // Skip hidden files
-                    if utils::is_hidden(&path) {
+                    // Skip hidden and system files
+                    if utils::is_hidden_or_system(&path) {
                         return;
                     }
 
@@ -305,6 +434,14 @@ fn scan_with_config_internal(
                     // Get file size from cached metadata
                     if let Ok(metadata) = entry.metadata() {
                         let size = metadata.len();
+                        if let Some(ref images) = images {
+                            if (similar_images::MIN_IMAGE_BYTES..=similar_images::MAX_IMAGE_BYTES)
+                                .contains(&size)
+                                && similar_images::is_image(&path)
+                            {
+                                images.lock().unwrap().push((path.clone(), size));
+                            }
+                        }
                         if size > 0 {
                             let mut groups = groups.lock().unwrap();
                             groups.entry(size).or_default().push(path);
@@ -316,8 +453,13 @@ fn scan_with_config_internal(
         groups.into_inner().unwrap()
     };
 
-    // Step 2: For files with same size, compute partial hash (PARALLELIZED)
-    let mut partial_hash_groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    if is_cancelled() {
+        bail!("Scan cancelled");
+    }
+
+    // Step 2: For files with same size, compute partial hash (PARALLELIZED).
+    // Hashes an earlier (possibly interrupted) scan saved are reused.
+    let mut partial_hash_groups: HashMap<String, Vec<FileHashes>> = HashMap::new();
 
     // Collect all paths that need partial hashing
     let paths_to_hash: Vec<(u64, Vec<PathBuf>)> = size_groups
@@ -327,62 +469,114 @@ fn scan_with_config_internal(
 
     // Parallelize partial hash computation
     let reporter_for_partial = reporter.as_ref().map(Arc::clone);
-    let partial_hash_results: Vec<(String, PathBuf)> = paths_to_hash
+    let partial_hash_results: Vec<(String, FileHashes)> = paths_to_hash
         .par_iter()
         .flat_map(|(_size, paths)| {
             paths
                 .par_iter()
                 .filter_map(|path| {
+                    if is_cancelled() {
+                        return None;
+                    }
                     if let Some(ref reporter) = reporter_for_partial {
                         reporter.emit_path(path);
                     }
-                    compute_partial_hash(path, buffer_size)
-                        .ok()
-                        .map(|hash| (hash, path.clone()))
+                    let mut hashes = store.get(path)?;
+                    if hashes.partial.is_none() {
+                        hashes.partial = Some(compute_partial_hash(path, buffer_size).ok()?);
+                        store.put(hashes.clone(), false);
+                    }
+                    Some((hashes.partial.clone()?, hashes))
                 })
                 .collect::<Vec<_>>()
         })
         .collect();
 
+    if is_cancelled() {
+        store.flush();
+        bail!("Scan cancelled");
+    }
+
     // Group by partial hash
-    for (partial_hash, path) in partial_hash_results {
+    for (partial_hash, hashes) in partial_hash_results {
         partial_hash_groups
             .entry(partial_hash)
             .or_default()
-            .push(path);
+            .push(hashes);
     }
 
     // Step 3: For partial hash matches, compute full hash (PARALLELIZED)
     let mut full_hash_groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
 
     // Collect paths that need full hashing
-    let paths_for_full_hash: Vec<Vec<PathBuf>> = partial_hash_groups
+    let paths_for_full_hash: Vec<Vec<FileHashes>> = partial_hash_groups
         .into_iter()
         .filter(|(_, paths)| paths.len() >= 2)
         .map(|(_, paths)| paths)
         .collect();
 
-    // Parallelize full hash computation
+    // Parallelize full hash computation. Large files save each hashed
+    // segment right away, so a cancelled scan loses at most one segment.
     let memmap_threshold_clone = memmap_threshold;
     let buffer_size_clone = buffer_size;
     let reporter_for_full = reporter.as_ref().map(Arc::clone);
+    let progress = HashingProgress::new(
+        reporter.as_ref().map(|r| r.sender()),
+        &paths_for_full_hash.iter().flatten().collect::<Vec<_>>(),
+    );
+    progress.send(true);
     let full_hash_results: Vec<(String, PathBuf)> = paths_for_full_hash
         .par_iter()
         .flat_map(|paths| {
             paths
                 .par_iter()
-                .filter_map(|path| {
+                .filter_map(|hashes| {
+                    if let Some(full) = &hashes.full {
+                        return Some((full.clone(), hashes.path.clone()));
+                    }
+                    if is_cancelled() {
+                        return None;
+                    }
                     if let Some(ref reporter) = reporter_for_full {
-                        reporter.emit_path(path);
+                        reporter.emit_path(&hashes.path);
                     }
-                    compute_full_hash(path, memmap_threshold_clone, buffer_size_clone)
-                        .ok()
-                        .map(|hash| (hash, path.clone()))
+                    let mut saved = hashes.clone();
+                    let full = compute_full_hash_resumable(
+                        &hashes.path,
+                        hashes.size,
+                        HASH_SEGMENT_BYTES,
+                        memmap_threshold_clone,
+                        buffer_size_clone,
+                        hashes.segments.clone(),
+                        |segments, bytes| {
+                            progress.add(0, bytes);
+                            saved.segments = segments.to_vec();
+                            store.put(saved.clone(), true);
+                            !is_cancelled()
+                        },
+                    )
+                    .ok()??;
+                    saved.full = Some(full.clone());
+                    saved.segments.clear();
+                    store.put(saved, false);
+                    let unsegmented = if hashes.size > HASH_SEGMENT_BYTES {
+                        0
+                    } else {
+                        hashes.size
+                    };
+                    progress.add(1, unsegmented);
+                    Some((full, hashes.path.clone()))
                 })
                 .collect::<Vec<_>>()
         })
         .collect();
 
+    progress.send(true);
+    store.flush();
+    if is_cancelled() {
+        bail!("Scan cancelled");
+    }
+
     // Group by full hash
     for (full_hash, path) in full_hash_results {
         full_hash_groups.entry(full_hash).or_default().push(path);
@@ -390,6 +584,14 @@ fn scan_with_config_internal(
 
     // Build duplicate groups
     for (hash, paths) in full_hash_groups {
+        // Hardlinks of the same file take no extra space (e.g. after
+        // `--dedupe-mode hardlink`); keep one path per file
+        let mut seen = HashSet::new();
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|path| path_identity::identify(path).is_none_or(|id| seen.insert(id)))
+            .collect();
+
         // Only include groups with duplicates (2+ files)
         if paths.len() < 2 {
             continue;
@@ -402,20 +604,27 @@ fn scan_with_config_internal(
             .map(|m| m.len())
             .unwrap_or(0);
 
-        // Calculate wasted space: (n-1) * size (keep one copy)
-        let wasted = (paths.len() - 1) as u64 * size;
-
-        result.groups.push(DuplicateGroup { hash, size, paths });
-
-        result.total_wasted += wasted;
+        let group = DuplicateGroup { hash, size, paths };
+        result.total_wasted += group.wasted();
+        result.groups.push(group);
     }
 
-    // Sort groups by wasted space descending
-    result.groups.sort_by(|a, b| {
-        let wasted_a = (a.paths.len() - 1) as u64 * a.size;
-        let wasted_b = (b.paths.len() - 1) as u64 * b.size;
-        wasted_b.cmp(&wasted_a)
-    });
+    duplicates::sort_by_wasted(&mut result.groups);
+
+    // Similar images, before the exact groups are cut down so that every
+    // exact duplicate is recognised
+    if let (Some(images), Some(cfg)) = (images, config) {
+        result.similar_images = similar_images::find_similar(
+            images.into_inner().unwrap(),
+            cfg.similar_images_max_distance,
+            &result.groups,
+            |path| {
+                if let Some(ref reporter) = reporter {
+                    reporter.emit_path(path);
+                }
+            },
+        );
+    }
 
     // Limit to top groups
     result.groups.truncate(MAX_GROUPS);
@@ -482,6 +691,62 @@ fn compute_full_hash(path: &Path, memmap_threshold: u64, buffer_size: usize) ->
     Ok(format!("{}", hash.to_hex()))
 }
 
+/// Full hash of a file that can be stopped and resumed. Files over
+/// `segment_bytes` are hashed one segment at a time and their hash is the
+/// hash of the segment hashes; `segments` holds those an earlier run
+/// finished. After each new segment `on_segment` gets all segment hashes so
+/// far and the segment's length, and stops hashing by returning false.
+/// Returns None when stopped.
+#[allow(clippy::too_many_arguments)]
+fn compute_full_hash_resumable(
+    path: &Path,
+    size: u64,
+    segment_bytes: u64,
+    memmap_threshold: u64,
+    buffer_size: usize,
+    mut segments: Vec<[u8; 32]>,
+    mut on_segment: impl FnMut(&[[u8; 32]], u64) -> bool,
+) -> Result<Option<String>> {
+    if size <= segment_bytes {
+        return compute_full_hash(path, memmap_threshold, buffer_size).map(Some);
+    }
+
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut buffer = vec![0u8; buffer_size.clamp(1, segment_bytes as usize)];
+    let count = size.div_ceil(segment_bytes);
+    while (segments.len() as u64) < count {
+        let start = segments.len() as u64 * segment_bytes;
+        let length = segment_bytes.min(size - start);
+        file.seek(SeekFrom::Start(start))
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+        let mut hasher = Hasher::new();
+        let mut remaining = length;
+        while remaining > 0 {
+            let want = remaining.min(buffer.len() as u64) as usize;
+            let bytes_read = file
+                .read(&mut buffer[..want])
+                .with_context(|| format!("Failed to read file: {}", path.display()))?;
+            if bytes_read == 0 {
+                bail!("File shrank while hashing: {}", path.display());
+            }
+            hasher.update(&buffer[..bytes_read]);
+            remaining -= bytes_read as u64;
+        }
+        segments.push(*hasher.finalize().as_bytes());
+        if !on_segment(&segments, length) {
+            return Ok(None);
+        }
+    }
+
+    let mut hasher = Hasher::new();
+    for segment in &segments {
+        hasher.update(segment);
+    }
+    Ok(Some(format!("{}", hasher.finalize().to_hex())))
+}
+
 /// Compute full hash using memory mapping (faster for large files)
 fn compute_full_hash_memmap(path: &Path, _file_size: u64) -> Result<String> {
     let file =
@@ -502,6 +767,42 @@ fn compute_full_hash_memmap(path: &Path, _file_size: u64) -> Result<String> {
     Ok(format!("{}", hash.to_hex()))
 }
 
+/// Replace `copy` with a hardlink to `original`
+///
+/// Both files are hashed again first, so a copy that changed since the scan
+/// is left alone. The link is created next to the copy and renamed over it,
+/// so the copy's path never goes missing. Fails when the files are on
+/// different volumes.
+pub fn replace_with_hardlink(original: &Path, copy: &Path) -> Result<()> {
+    const BUFFER_SIZE: usize = 1024 * 1024;
+    let original_hash = compute_full_hash(original, u64::MAX, BUFFER_SIZE)?;
+    if compute_full_hash(copy, u64::MAX, BUFFER_SIZE)? != original_hash {
+        return Err(anyhow::anyhow!(
+            "{} changed since the scan and no longer matches {}",
+            copy.display(),
+            original.display()
+        ));
+    }
+
+    let name = copy
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let link = copy.with_file_name(format!(".{}.wole-link", name));
+    std::fs::hard_link(original, &link).with_context(|| {
+        format!(
+            "Failed to link {} to {}",
+            link.display(),
+            original.display()
+        )
+    })?;
+    if let Err(e) = std::fs::rename(&link, copy) {
+        let _ = std::fs::remove_file(&link);
+        return Err(e).with_context(|| format!("Failed to replace {}", copy.display()));
+    }
+    Ok(())
+}
+
 /// Clean (delete) duplicate files by moving them to the Recycle Bin
 /// Keeps the first file in each group, deletes the rest
 pub fn clean(groups: &[DuplicateGroup], permanent: bool) -> Result<()> {
@@ -519,3 +820,94 @@ pub fn clean(groups: &[DuplicateGroup], permanent: bool) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn hardlinked_copies_are_no_longer_duplicates() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("report.pdf");
+        let copy = temp.path().join("report (1).pdf");
+        std::fs::write(&original, vec![7u8; 8192]).unwrap();
+        std::fs::write(&copy, vec![7u8; 8192]).unwrap();
+
+        let config = Config::default();
+        let result = scan_with_config(temp.path(), None, &config).unwrap();
+        assert_eq!(result.to_category_result().paths, vec![copy.clone()]);
+
+        replace_with_hardlink(&original, &copy).unwrap();
+        assert_eq!(std::fs::read(&copy).unwrap(), vec![7u8; 8192]);
+        assert_eq!(
+            path_identity::identify(&copy),
+            path_identity::identify(&original)
+        );
+        assert!(scan_with_config(temp.path(), None, &config)
+            .unwrap()
+            .groups
+            .is_empty());
+
+        // A copy that no longer matches is left alone
+        let other = temp.path().join("other.pdf");
+        std::fs::write(&other, vec![8u8; 8192]).unwrap();
+        assert!(replace_with_hardlink(&original, &other).is_err());
+        assert_eq!(std::fs::read(&other).unwrap(), vec![8u8; 8192]);
+    }
+
+    #[test]
+    fn interrupted_hashing_resumes_from_saved_progress() {
+        let temp = TempDir::new().unwrap();
+        let big = temp.path().join("big.iso");
+        let data: Vec<u8> = (0..3000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&big, &data).unwrap();
+        let hash = |segments: Vec<[u8; 32]>, calls: &mut usize| {
+            compute_full_hash_resumable(&big, 3000, 1024, u64::MAX, 100, segments, |_, _| {
+                *calls += 1;
+                true
+            })
+            .unwrap()
+        };
+
+        let mut calls = 0;
+        let whole = hash(Vec::new(), &mut calls).unwrap();
+        assert_eq!(calls, 3);
+
+        // Stopped after the first segment, then resumed from it
+        let mut saved = Vec::new();
+        let stopped =
+            compute_full_hash_resumable(&big, 3000, 1024, u64::MAX, 100, Vec::new(), |s, len| {
+                saved = s.to_vec();
+                assert_eq!(len, 1024);
+                false
+            })
+            .unwrap();
+        assert_eq!((stopped, saved.len()), (None, 1));
+        let mut calls = 0;
+        assert_eq!(hash(saved, &mut calls), Some(whole));
+        assert_eq!(calls, 2);
+
+        // Hashes saved by one scan are found by the next
+        let a = temp.path().join("tree/a.bin");
+        let b = temp.path().join("tree/b.bin");
+        std::fs::create_dir_all(a.parent().unwrap()).unwrap();
+        std::fs::write(&a, vec![3u8; 5000]).unwrap();
+        std::fs::write(&b, vec![3u8; 5000]).unwrap();
+        let db = temp.path().join("scan_cache.db");
+        let config = Config::default();
+        let store = HashStore::with_cache(Some(ScanCache::open_at(&db).unwrap()));
+        let tree = temp.path().join("tree");
+        let first = scan_with_config_internal(&tree, None, &config, None, &store, None).unwrap();
+        assert_eq!(first.groups.len(), 1);
+
+        let store = HashStore::with_cache(Some(ScanCache::open_at(&db).unwrap()));
+        let cached = store.get(&a).unwrap();
+        assert_eq!(cached.full.as_deref(), Some(first.groups[0].hash.as_str()));
+        assert!(cached.partial.is_some() && cached.segments.is_empty());
+
+        // A changed file is hashed again
+        std::fs::write(&b, vec![4u8; 5001]).unwrap();
+        assert_eq!(store.get(&b).unwrap().full, None);
+    }
+}
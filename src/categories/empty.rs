@@ -1,3 +1,4 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::CategoryResult;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
@@ -76,7 +77,10 @@ fn scan_internal(
         {
             let entry = match entry {
                 Ok(e) => e,
-                Err(_) => continue,
+                Err(e) => {
+                    crate::elevation::note_walkdir_error(&e);
+                    continue;
+                }
             };
 
             let path = entry.path();
@@ -207,6 +211,17 @@ fn should_skip_entry(entry: &walkdir::DirEntry) -> bool {
     false
 }
 
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(root, &options.config)?;
+    Ok(CategoryScan::from_result(
+        "empty",
+        Safety::Safe,
+        result,
+        |_| "empty folder".to_string(),
+    ))
+}
+
 /// Clean (delete) an empty folder by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -5,6 +5,16 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Where event logs are kept: `%SystemRoot%\System32\winevt\Logs`
+pub fn logs_dir() -> PathBuf {
+    std::env::var("SystemRoot")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("C:\\Windows"))
+        .join("System32")
+        .join("winevt")
+        .join("Logs")
+}
+
 /// Scan for Windows Event Log files that can be cleaned
 ///
 /// Includes:
@@ -14,14 +24,7 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
     let mut paths = Vec::new();
 
-    // Get Windows directory
-    let windows_dir = std::env::var("SystemRoot")
-        .ok()
-        .map(PathBuf::from)
-        .unwrap_or_else(|| PathBuf::from("C:\\Windows"));
-
-    // Event logs are in System32\winevt\Logs
-    let event_logs_path = windows_dir.join("System32").join("winevt").join("Logs");
+    let event_logs_path = logs_dir();
 
     if !event_logs_path.exists() {
         return Ok(result);
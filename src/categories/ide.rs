@@ -0,0 +1,491 @@
+//! IDE and toolchain caches.
+//!
+//! Caches IDEs and mobile toolchains rebuild on their own, and which often
+//! reach tens of GB:
+//! - JetBrains IDEs and Android Studio: the `caches` and `index` folders of
+//!   every installed version (`JetBrains\IntelliJIdea2024.1\caches`, ...)
+//! - VS Code, VS Code Insiders and VSCodium: downloaded extension packages
+//!   (`CachedExtensionVSIXs`)
+//! - Gradle and Kotlin daemons: logs of daemons that are no longer running,
+//!   and daemon registries and worker folders left when no daemon has run
+//!   for `DAEMON_STALE_DAYS` (`~/.gradle/daemon`, `~/.gradle/workers`,
+//!   `~/.kotlin/daemon`). Gradle's dependency cache is reported with Package
+//!   Cache.
+//! - Android SDK: emulator system images no AVD uses any more
+//! - Xcode (macOS): per-project `DerivedData`
+//!
+//! Paths are found the same way on every platform: JetBrains and Android
+//! Studio keep their caches under the user cache folder (`%LOCALAPPDATA%`,
+//! `~/Library/Caches`, `~/.cache`), VS Code under the user config folder.
+
+use crate::config::Config;
+use crate::output::CategoryResult;
+use crate::utils::{self, SizeWalk};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Per-version folders IDEs rebuild on the next start
+const JETBRAINS_CACHE_DIRS: &[&str] = &["caches", "index"];
+
+/// VS Code and its forks that keep extension packages in their config folder
+const VSCODE_FLAVORS: &[&str] = &["Code", "Code - Insiders", "VSCodium"];
+
+/// Days a daemon's leftovers must be untouched before they're reported
+const DAEMON_STALE_DAYS: u64 = 7;
+
+/// Where the IDE caches live
+#[derive(Debug, Clone, Default)]
+pub struct Locations {
+    /// `%LOCALAPPDATA%`, `~/Library/Caches` or `~/.cache`
+    pub cache_dir: Option<PathBuf>,
+    /// `%APPDATA%`, `~/Library/Application Support` or `~/.config`
+    pub config_dir: Option<PathBuf>,
+    pub gradle_home: Option<PathBuf>,
+    /// Kotlin compiler daemon run folder (`~/.kotlin/daemon`; under the
+    /// local app data folder on Windows and macOS)
+    pub kotlin_daemon: Option<PathBuf>,
+    pub android_sdk: Option<PathBuf>,
+    pub avd_home: Option<PathBuf>,
+    /// `~/Library/Developer/Xcode/DerivedData` (macOS only)
+    pub derived_data: Option<PathBuf>,
+}
+
+impl Locations {
+    /// Standard locations for the current user, honouring `GRADLE_USER_HOME`,
+    /// `ANDROID_HOME`/`ANDROID_SDK_ROOT` and `ANDROID_AVD_HOME`
+    pub fn detect() -> Self {
+        let base = directories::BaseDirs::new();
+        let home = base.as_ref().map(|b| b.home_dir().to_path_buf());
+        let env_path = |name: &str| env::var_os(name).map(PathBuf::from);
+
+        let default_sdk = base.as_ref().map(|b| {
+            if cfg!(windows) {
+                b.data_local_dir().join("Android").join("Sdk")
+            } else if cfg!(target_os = "macos") {
+                b.home_dir().join("Library").join("Android").join("sdk")
+            } else {
+                b.home_dir().join("Android").join("Sdk")
+            }
+        });
+
+        Self {
+            cache_dir: base.as_ref().map(|b| b.cache_dir().to_path_buf()),
+            config_dir: base.as_ref().map(|b| b.config_dir().to_path_buf()),
+            gradle_home: env_path("GRADLE_USER_HOME")
+                .or_else(|| home.as_ref().map(|h| h.join(".gradle"))),
+            kotlin_daemon: base.as_ref().map(|b| {
+                if cfg!(windows) {
+                    b.data_local_dir().join("kotlin").join("daemon")
+                } else if cfg!(target_os = "macos") {
+                    b.data_dir().join("kotlin").join("daemon")
+                } else {
+                    b.home_dir().join(".kotlin").join("daemon")
+                }
+            }),
+            android_sdk: env_path("ANDROID_HOME")
+                .or_else(|| env_path("ANDROID_SDK_ROOT"))
+                .or(default_sdk),
+            avd_home: env_path("ANDROID_AVD_HOME")
+                .or_else(|| env_path("ANDROID_USER_HOME").map(|h| h.join("avd")))
+                .or_else(|| home.as_ref().map(|h| h.join(".android").join("avd"))),
+            derived_data: home
+                .filter(|_| cfg!(target_os = "macos"))
+                .map(|h| h.join("Library/Developer/Xcode/DerivedData")),
+        }
+    }
+}
+
+/// Gradle and Kotlin daemons currently running
+#[derive(Debug, Clone, Default)]
+pub struct RunningDaemons {
+    /// Process IDs of Gradle daemons (they name their log `daemon-<pid>.out.log`)
+    pub gradle: HashSet<u32>,
+    pub kotlin: bool,
+}
+
+impl RunningDaemons {
+    /// Look for daemon main classes on running Java command lines
+    pub fn detect() -> Self {
+        use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::new().with_cmd(UpdateKind::Always),
+        );
+        let mut running = Self::default();
+        for (pid, process) in system.processes() {
+            let cmd = process.cmd();
+            let has = |class: &str| cmd.iter().any(|arg| arg.to_string_lossy().contains(class));
+            if has("org.gradle.launcher.daemon.bootstrap.GradleDaemon") {
+                running.gradle.insert(pid.as_u32());
+            }
+            if has("KotlinCompileDaemon") {
+                running.kotlin = true;
+            }
+        }
+        running
+    }
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
+
+fn entries(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
+
+/// Latest modification of `path` or, for a folder, anything directly in it
+fn last_modified(path: &Path) -> Option<SystemTime> {
+    let own = fs::metadata(path).and_then(|m| m.modified()).ok();
+    entries(path)
+        .iter()
+        .filter_map(|e| fs::metadata(e).and_then(|m| m.modified()).ok())
+        .chain(own)
+        .max()
+}
+
+fn untouched_since(path: &Path, cutoff: SystemTime) -> bool {
+    last_modified(path).is_some_and(|modified| modified < cutoff)
+}
+
+/// Gradle daemon logs, registries and worker folders, and Kotlin daemon
+/// files, left by daemons that aren't running and untouched since `cutoff`
+fn stale_daemon_files(
+    locations: &Locations,
+    running: &RunningDaemons,
+    cutoff: SystemTime,
+) -> Vec<PathBuf> {
+    let mut stale = Vec::new();
+
+    if let Some(gradle) = &locations.gradle_home {
+        for version in subdirs(&gradle.join("daemon")) {
+            // With no daemon left the whole folder (registry included) goes
+            if running.gradle.is_empty() && untouched_since(&version, cutoff) {
+                stale.push(version);
+                continue;
+            }
+            stale.extend(entries(&version).into_iter().filter(|log| {
+                let pid = file_name(log)
+                    .strip_prefix("daemon-")
+                    .and_then(|rest| rest.strip_suffix(".out.log"))
+                    .and_then(|pid| pid.parse::<u32>().ok());
+                pid.is_some_and(|pid| !running.gradle.contains(&pid))
+                    && untouched_since(log, cutoff)
+            }));
+        }
+        let workers = gradle.join("workers");
+        if running.gradle.is_empty() && workers.is_dir() && untouched_since(&workers, cutoff) {
+            stale.push(workers);
+        }
+    }
+
+    if let Some(kotlin) = &locations.kotlin_daemon {
+        // A running daemon still needs its run files; old logs can go
+        stale.extend(entries(kotlin).into_iter().filter(|file| {
+            (!running.kotlin || file_name(file).ends_with(".log")) && untouched_since(file, cutoff)
+        }));
+    }
+
+    stale
+}
+
+/// `caches`/`index` of each versioned product folder under `root` whose name
+/// passes `is_product`
+fn product_caches(root: &Path, is_product: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    subdirs(root)
+        .into_iter()
+        .filter(|product| is_product(file_name(product)))
+        .flat_map(|product| {
+            JETBRAINS_CACHE_DIRS
+                .iter()
+                .map(move |dir| product.join(dir))
+        })
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Folders (relative to the SDK, `/`-separated, lowercase) of the system
+/// images the AVDs in `avd_home` boot from
+fn system_images_in_use(avd_home: &Path) -> HashSet<String> {
+    subdirs(avd_home)
+        .iter()
+        .filter(|avd| file_name(avd).ends_with(".avd"))
+        .filter_map(|avd| fs::read_to_string(avd.join("config.ini")).ok())
+        .flat_map(|config| {
+            config
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .filter(|(key, _)| key.trim().starts_with("image.sysdir."))
+                .map(|(_, value)| normalize_image_dir(value))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn normalize_image_dir(dir: &str) -> String {
+    dir.trim()
+        .replace('\\', "/")
+        .trim_matches('/')
+        .to_lowercase()
+}
+
+/// `system-images/<api>/<tag>/<abi>` folders no AVD refers to. If the AVD
+/// folder can't be read, every image may be in use and none is returned.
+fn unused_system_images(sdk: &Path, avd_home: Option<&Path>) -> Vec<PathBuf> {
+    let Some(avd_home) = avd_home.filter(|h| h.is_dir()) else {
+        return Vec::new();
+    };
+    let in_use = system_images_in_use(avd_home);
+    let images = sdk.join("system-images");
+    subdirs(&images)
+        .iter()
+        .flat_map(|api| subdirs(api))
+        .flat_map(|tag| subdirs(&tag))
+        .filter(|abi| {
+            let relative = abi.strip_prefix(sdk).unwrap_or(abi);
+            !in_use.contains(&normalize_image_dir(&relative.to_string_lossy()))
+        })
+        .collect()
+}
+
+/// Every IDE and toolchain cache folder under `locations`; daemon leftovers
+/// count when untouched since `daemon_cutoff` and not `running`
+pub fn find_caches(
+    locations: &Locations,
+    running: &RunningDaemons,
+    daemon_cutoff: SystemTime,
+) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+
+    if let Some(cache_dir) = &locations.cache_dir {
+        found.extend(product_caches(&cache_dir.join("JetBrains"), |name| {
+            // Versioned product folders only (IntelliJIdea2024.1), not the
+            // shared Toolbox or plugin folders
+            name.chars().any(|c| c.is_ascii_digit())
+        }));
+        found.extend(product_caches(&cache_dir.join("Google"), |name| {
+            name.starts_with("AndroidStudio")
+        }));
+    }
+
+    if let Some(config_dir) = &locations.config_dir {
+        for flavor in VSCODE_FLAVORS {
+            let vsix = config_dir.join(flavor).join("CachedExtensionVSIXs");
+            if vsix.is_dir() {
+                found.push(vsix);
+            }
+        }
+    }
+
+    found.extend(stale_daemon_files(locations, running, daemon_cutoff));
+
+    if let Some(sdk) = &locations.android_sdk {
+        found.extend(unused_system_images(sdk, locations.avd_home.as_deref()));
+    }
+
+    if let Some(derived_data) = &locations.derived_data {
+        found.extend(subdirs(derived_data));
+    }
+
+    found
+}
+
+/// Scan for IDE and toolchain caches
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
+    let cutoff = SystemTime::now() - Duration::from_secs(DAEMON_STALE_DAYS * 24 * 60 * 60);
+    let found = find_caches(&Locations::detect(), &RunningDaemons::detect(), cutoff);
+    Ok(size_caches(found, config, walk))
+}
+
+fn size_caches(found: Vec<PathBuf>, config: &Config, walk: &SizeWalk) -> CategoryResult {
+    // Sequential: calculate_dir_size_in already walks on the rayon pool
+    let sized: Vec<(PathBuf, u64)> = found
+        .into_iter()
+        .filter(|path| !config.is_excluded(path) && !utils::should_skip_entry(path))
+        .map(|path| {
+            let size = if path.is_file() {
+                fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            } else {
+                utils::calculate_dir_size_in(&path, walk)
+            };
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+
+    CategoryResult {
+        items: sized.len(),
+        size_bytes: sized.iter().map(|(_, size)| size).sum(),
+        paths: sized.into_iter().map(|(path, _)| path).collect(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn finds_ide_caches_and_unused_system_images() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let write = |relative: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![1u8; 512]).unwrap();
+        };
+        write("cache/JetBrains/IntelliJIdea2024.1/caches/content.dat");
+        write("cache/JetBrains/IntelliJIdea2024.1/index/stubs.dat");
+        write("cache/JetBrains/IntelliJIdea2024.1/log/idea.log");
+        write("cache/JetBrains/Toolbox/caches/state.dat");
+        write("cache/Google/AndroidStudio2023.3/caches/content.dat");
+        write("config/Code/CachedExtensionVSIXs/rust-analyzer.vsix");
+        write("config/Code/User/settings.json");
+        write("gradle/daemon/8.5/daemon-1.out.log");
+        write("sdk/system-images/android-34/google_apis/x86_64/system.img");
+        write("sdk/system-images/android-30/default/x86_64/system.img");
+        fs::create_dir_all(root.join("avd/Pixel_7.avd")).unwrap();
+        fs::write(
+            root.join("avd/Pixel_7.avd/config.ini"),
+            "hw.lcd.density=420\nimage.sysdir.1=system-images\\android-34\\google_apis\\x86_64\\\n",
+        )
+        .unwrap();
+
+        let locations = Locations {
+            cache_dir: Some(root.join("cache")),
+            config_dir: Some(root.join("config")),
+            gradle_home: Some(root.join("gradle")),
+            kotlin_daemon: None,
+            android_sdk: Some(root.join("sdk")),
+            avd_home: Some(root.join("avd")),
+            derived_data: None,
+        };
+        // Daemon leftovers count once untouched since the cutoff
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let found = find_caches(&locations, &RunningDaemons::default(), future);
+        let result = size_caches(found, &Config::default(), &SizeWalk::default());
+        let mut found: Vec<String> = result
+            .paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "cache/Google/AndroidStudio2023.3/caches",
+                "cache/JetBrains/IntelliJIdea2024.1/caches",
+                "cache/JetBrains/IntelliJIdea2024.1/index",
+                "config/Code/CachedExtensionVSIXs",
+                "gradle/daemon/8.5",
+                "sdk/system-images/android-30/default/x86_64",
+            ]
+        );
+        assert_eq!(result.items, 6);
+        assert_eq!(result.size_bytes, 6 * 512);
+
+        // Without readable AVDs no system image is reported
+        let no_avds = Locations {
+            avd_home: Some(root.join("missing")),
+            ..locations
+        };
+        assert!(!find_caches(&no_avds, &RunningDaemons::default(), future)
+            .iter()
+            .any(|p| p.starts_with(root.join("sdk"))));
+    }
+
+    #[test]
+    fn daemon_leftovers_of_running_daemons_are_kept() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for file in [
+            "gradle/daemon/8.5/daemon-100.out.log",
+            "gradle/daemon/8.5/daemon-200.out.log",
+            "gradle/daemon/8.5/registry.bin",
+            "gradle/workers/classpath.jar",
+            "kotlin/kotlin-daemon.2024-01-01.log",
+            "kotlin/kotlin-daemon-client-tsmarker.run",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, b"x").unwrap();
+        }
+        let locations = Locations {
+            gradle_home: Some(root.join("gradle")),
+            kotlin_daemon: Some(root.join("kotlin")),
+            ..Default::default()
+        };
+        let relative = |found: Vec<PathBuf>| -> Vec<String> {
+            let mut found: Vec<String> = found
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect();
+            found.sort();
+            found
+        };
+
+        // Everything was just written
+        let past = SystemTime::now() - Duration::from_secs(3600);
+        assert!(stale_daemon_files(&locations, &RunningDaemons::default(), past).is_empty());
+
+        let future = SystemTime::now() + Duration::from_secs(60);
+        assert_eq!(
+            relative(stale_daemon_files(
+                &locations,
+                &RunningDaemons::default(),
+                future
+            )),
+            vec![
+                "gradle/daemon/8.5",
+                "gradle/workers",
+                "kotlin/kotlin-daemon-client-tsmarker.run",
+                "kotlin/kotlin-daemon.2024-01-01.log",
+            ]
+        );
+
+        // Daemon 100 and a Kotlin daemon are running: only the other log goes
+        let running = RunningDaemons {
+            gradle: HashSet::from([100]),
+            kotlin: true,
+        };
+        assert_eq!(
+            relative(stale_daemon_files(&locations, &running, future)),
+            vec![
+                "gradle/daemon/8.5/daemon-200.out.log",
+                "kotlin/kotlin-daemon.2024-01-01.log",
+            ]
+        );
+    }
+}
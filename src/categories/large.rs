@@ -1,5 +1,7 @@
+use super::typed::{CategoryScan, Safety};
 use crate::config::Config;
 use crate::git;
+use crate::open_files::{OpenFiles, OpenReason};
 use crate::output::{CategoryResult, OutputMode};
 use crate::project;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
@@ -16,6 +18,9 @@ use std::sync::{Arc, Mutex};
 /// Maximum number of results to return
 const MAX_RESULTS: usize = 100;
 
+/// How deep to walk below each directory unless `max_depth` is configured
+const DEFAULT_MAX_DEPTH: usize = 20;
+
 /// Scan for large files in user directories
 ///
 /// Optimizations:
@@ -34,7 +39,7 @@ pub fn scan(
     let mut result = CategoryResult::default();
 
     // Get user directories to scan
-    let user_dirs = get_user_directories()?;
+    let user_dirs = get_user_directories(config)?;
 
     if output_mode != OutputMode::Quiet && !user_dirs.is_empty() {
         println!(
@@ -46,6 +51,7 @@ pub fn scan(
 
     // Collect files with sizes for sorting
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut hidden: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         if output_mode != OutputMode::Quiet {
@@ -55,12 +61,17 @@ pub fn scan(
             dir,
             min_size_bytes,
             &mut files_with_sizes,
+            &mut hidden,
             config,
             output_mode,
             None,
         )?;
     }
 
+    if config.safety.include_hidden {
+        files_with_sizes.extend(hidden.iter().cloned());
+    }
+
     // Sort by size descending (biggest first)
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
 
@@ -81,13 +92,15 @@ pub fn scan(
             OutputMode::Quiet => 0,
         };
 
+        let open = OpenFiles::detect();
         for (i, (path, size)) in files_with_sizes.iter().take(show_count).enumerate() {
             let size_str = bytesize::to_string(*size, false);
             println!(
-                "      {} {} ({})",
+                "      {} {} ({})  {}",
                 Theme::muted("→"),
                 path.display(),
-                Theme::size(&size_str)
+                Theme::size(&size_str),
+                Theme::muted(&FileHints::of(path, &open).summary())
             );
 
             if i == 9 && output_mode == OutputMode::Normal && files_with_sizes.len() > 10 {
@@ -107,6 +120,7 @@ pub fn scan(
         result.size_bytes += size;
         result.paths.push(path);
     }
+    result.set_hidden(hidden, MAX_RESULTS);
 
     Ok(result)
 }
@@ -122,20 +136,25 @@ pub fn scan_with_progress(
     let reporter = Arc::new(ScanPathReporter::new("Large Files", tx.clone(), 75));
 
     let mut result = CategoryResult::default();
-    let user_dirs = get_user_directories()?;
+    let user_dirs = get_user_directories(config)?;
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut hidden: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         scan_directory(
             dir,
             min_size_bytes,
             &mut files_with_sizes,
+            &mut hidden,
             config,
             output_mode,
             Some(Arc::clone(&reporter)),
         )?;
     }
 
+    if config.safety.include_hidden {
+        files_with_sizes.extend(hidden.iter().cloned());
+    }
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
     files_with_sizes.truncate(MAX_RESULTS);
     for (path, size) in files_with_sizes {
@@ -143,13 +162,20 @@ pub fn scan_with_progress(
         result.size_bytes += size;
         result.paths.push(path);
     }
+    result.set_hidden(hidden, MAX_RESULTS);
 
     let _ = root;
     Ok(result)
 }
 
-/// Get user directories to scan (Downloads, Documents, Desktop, Pictures, Videos, Music)
-fn get_user_directories() -> Result<Vec<PathBuf>> {
+/// Get user directories to scan (Downloads, Documents, Desktop, Pictures, Videos, Music),
+/// or the `[categories.large] roots` when configured
+fn get_user_directories(config: &Config) -> Result<Vec<PathBuf>> {
+    let roots = config.categories.large.root_paths();
+    if !roots.is_empty() {
+        return Ok(roots);
+    }
+
     let mut dirs = Vec::new();
 
     if let Ok(user_profile) = env::var("USERPROFILE") {
@@ -170,6 +196,7 @@ fn scan_directory(
     dir: &Path,
     min_size_bytes: u64,
     files: &mut Vec<(PathBuf, u64)>,
+    hidden: &mut Vec<(PathBuf, u64)>,
     config: &Config,
     _output_mode: OutputMode,
     reporter: Option<Arc<ScanPathReporter>>,
@@ -178,7 +205,11 @@ fn scan_directory(
         return Ok(());
     }
 
-    const MAX_DEPTH: usize = 20;
+    let max_depth = config
+        .categories
+        .large
+        .max_depth
+        .unwrap_or(DEFAULT_MAX_DEPTH);
 
     // Clone config for thread-safe access (jwalk requires 'static)
     let config_clone = Arc::new(config.clone());
@@ -186,13 +217,14 @@ fn scan_directory(
     let config_clone_for_each = Arc::clone(&config_clone);
 
     // Use Arc<Mutex<>> for thread-safe collection that can be shared
-    let found_files: Arc<Mutex<Vec<(PathBuf, u64)>>> = Arc::new(Mutex::new(Vec::new()));
+    // The flag marks hidden/system files, which are reported separately
+    let found_files: Arc<Mutex<Vec<(PathBuf, u64, bool)>>> = Arc::new(Mutex::new(Vec::new()));
     // Clone Arc for the closure
     let found_files_clone = Arc::clone(&found_files);
 
     // Use jwalk for parallel directory traversal
     WalkDir::new(dir)
-        .max_depth(MAX_DEPTH)
+        .max_depth(max_depth)
         .follow_links(false)
         .parallelism(jwalk::Parallelism::RayonDefaultPool {
             busy_timeout: std::time::Duration::from_secs(1),
@@ -247,7 +279,13 @@ fn scan_directory(
             });
         })
         .into_iter()
-        .filter_map(|e| e.ok())
+        .filter_map(|e| match e {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                crate::elevation::note_jwalk_error(&err);
+                None
+            }
+        })
         .for_each(move |entry| {
             let path = entry.path();
             if let Some(ref reporter) = reporter {
@@ -265,13 +303,10 @@ fn scan_directory(
                 Err(_) => return,
             };
 
-            // Check size threshold first (fast)
-            if metadata.len() < min_size_bytes {
-                return;
-            }
-
-            // Skip hidden files
-            if utils::is_hidden(&path) {
+            // Check size threshold first (fast); online-only cloud files
+            // take no local space and never pass it
+            let size = utils::local_len(&metadata);
+            if size < min_size_bytes {
                 return;
             }
 
@@ -287,14 +322,21 @@ fn scan_directory(
                 }
             }
 
+            let is_hidden = utils::is_hidden_or_system(&path);
             let mut files_guard = found_files_clone.lock().unwrap();
-            files_guard.push((path, metadata.len()));
+            files_guard.push((path, size, is_hidden));
         });
 
     // Move collected files to output
     // Use Arc::try_unwrap to get the inner Mutex, then into_inner to get the Vec
-    let mut collected = Arc::try_unwrap(found_files).unwrap().into_inner().unwrap();
-    files.append(&mut collected);
+    let collected = Arc::try_unwrap(found_files).unwrap().into_inner().unwrap();
+    for (path, size, is_hidden) in collected {
+        if is_hidden {
+            hidden.push((path, size));
+        } else {
+            files.push((path, size));
+        }
+    }
 
     Ok(())
 }
@@ -304,9 +346,145 @@ pub fn get_file_type(path: &Path) -> utils::FileType {
     utils::detect_file_type(path)
 }
 
+/// What helps judge at a glance whether a large file can go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHints {
+    pub file_type: utils::FileType,
+    /// Why the file looks in use, if it does
+    pub open: Option<OpenReason>,
+    /// Editor process that has it open, when that's how it's in use
+    pub editor: Option<String>,
+    /// Days since it was last read, where the filesystem records access times
+    pub accessed_days: Option<u64>,
+}
+
+impl FileHints {
+    /// Hints for `path`; `open` comes from one `OpenFiles::detect()` per listing
+    pub fn of(path: &Path, open: &OpenFiles) -> Self {
+        let reason = open.reason(path);
+        Self {
+            file_type: get_file_type(path),
+            open: reason,
+            editor: reason.and_then(|_| open.editor(path)).map(str::to_string),
+            accessed_days: utils::file_times(path)
+                .accessed
+                .and_then(|t| t.elapsed().ok())
+                .map(|d| d.as_secs() / 86400),
+        }
+    }
+
+    /// "in use by Code.exe", "in use by another process", ... or None
+    pub fn in_use(&self) -> Option<String> {
+        match (&self.editor, self.open) {
+            (Some(editor), _) => Some(format!("in use by {}", editor)),
+            (None, Some(reason)) => Some(reason.label().to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// One line for listings: "Video · used 40d ago · in use by vlc"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.file_type.as_str().to_string()];
+        if let Some(days) = self.accessed_days {
+            parts.push(match days {
+                0 => "used today".to_string(),
+                days => format!("used {}d ago", days),
+            });
+        }
+        parts.extend(self.in_use());
+        parts.join(" · ")
+    }
+}
+
+/// Typed options for scanning large files on their own
+#[derive(Debug, Clone)]
+pub struct LargeScanOptions {
+    pub min_size_bytes: u64,
+    pub config: Config,
+}
+
+impl Default for LargeScanOptions {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+            config,
+        }
+    }
+}
+
+/// Scan user folders for files of at least `min_size_bytes`
+pub fn scan_with_options(root: &Path, options: &LargeScanOptions) -> Result<CategoryScan> {
+    let result = scan(
+        root,
+        options.min_size_bytes,
+        &options.config,
+        OutputMode::Quiet,
+    )?;
+    let min = bytesize::to_string(options.min_size_bytes, false);
+    Ok(CategoryScan::from_result(
+        "large",
+        Safety::Review,
+        result,
+        |path| format!("{} file larger than {}", get_file_type(path).as_str(), min),
+    ))
+}
+
 /// Clean (delete) a large file by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
         .with_context(|| format!("Failed to delete large file: {}", path.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn configured_roots_and_depth_replace_user_directories() {
+        let dir = tempfile::Builder::new()
+            .prefix("wole-large")
+            .tempdir()
+            .unwrap();
+        let shallow = dir.path().join("shallow.iso");
+        let deep = dir.path().join("a").join("b").join("deep.iso");
+        fs::create_dir_all(deep.parent().unwrap()).unwrap();
+        fs::write(&shallow, vec![0u8; 2048]).unwrap();
+        fs::write(&deep, vec![0u8; 2048]).unwrap();
+
+        let mut config = Config::default();
+        config.categories.large.roots = vec![dir.path().to_string_lossy().into_owned()];
+        let result = scan(Path::new("/unused"), 1024, &config, OutputMode::Quiet).unwrap();
+        assert_eq!(result.items, 2);
+
+        config.categories.large.max_depth = Some(2);
+        let result = scan(Path::new("/unused"), 1024, &config, OutputMode::Quiet).unwrap();
+        assert_eq!(result.paths, vec![shallow]);
+    }
+
+    #[test]
+    fn hints_describe_type_access_and_editor() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("take2.mp4");
+        let iso = dir.path().join("win.iso");
+        fs::write(&video, b"x").unwrap();
+        fs::write(&iso, b"x").unwrap();
+        let open = OpenFiles::from_processes([(
+            "resolve".to_string(),
+            vec!["resolve".into(), video.clone().into()],
+            None,
+        )]);
+
+        let hints = FileHints::of(&video, &open);
+        assert_eq!(hints.file_type, utils::FileType::Video);
+        assert_eq!(hints.open, Some(OpenReason::EditorArgument));
+        assert_eq!(hints.in_use().as_deref(), Some("in use by resolve"));
+        assert!(hints.summary().starts_with("Video · used today"));
+
+        let hints = FileHints::of(&iso, &open);
+        assert_eq!(hints.in_use(), None);
+        assert_eq!(hints.summary(), "Disk Image · used today");
+    }
+}
@@ -1,16 +1,26 @@
 pub mod app_cache;
+pub mod app_leftovers;
 pub mod applications;
 pub mod browser;
 pub mod build;
 pub mod cache;
+pub mod cloud_files;
+pub mod custom;
 pub mod downloads;
 pub mod duplicates;
 pub mod empty;
 pub mod event_logs;
+pub mod ide;
 pub mod large;
 pub mod old;
+pub mod orphaned;
+pub mod rust;
+pub mod similar_images;
+pub mod stale_locks;
 pub mod startup;
 pub mod system;
 pub mod temp;
 pub mod trash;
+pub mod typed;
+pub mod vm_leftovers;
 pub mod windows_update;
@@ -1,4 +1,5 @@
-use crate::config::Config;
+use super::typed::{CategoryScan, Safety};
+use crate::config::{AgeBasis, Config};
 use crate::git;
 use crate::output::{CategoryResult, OutputMode};
 use crate::project;
@@ -11,11 +12,15 @@ use chrono::{Duration, Utc};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
 
 /// Maximum number of results to return
 const MAX_RESULTS: usize = 200;
 
+/// How deep to walk below each directory unless `max_depth` is configured
+const DEFAULT_MAX_DEPTH: usize = 20;
+
 /// Minimum file size to consider (skip tiny files that add noise)
 const MIN_FILE_SIZE: u64 = 10 * 1024; // 10 KB
 
@@ -37,21 +42,24 @@ pub fn scan(
     let mut result = CategoryResult::default();
 
     let cutoff = Utc::now() - Duration::days(min_age_days as i64);
+    let basis = config.categories.old.age_basis.unwrap_or_default();
 
     // Get user directories to scan
-    let user_dirs = get_user_directories()?;
+    let user_dirs = get_user_directories(config)?;
 
     if output_mode != OutputMode::Quiet && !user_dirs.is_empty() {
         println!(
-            "  {} Scanning {} directories for old files (older than {} days)...",
+            "  {} Scanning {} directories for old files (older than {} days by {})...",
             Theme::muted("→"),
             user_dirs.len(),
-            min_age_days
+            min_age_days,
+            basis_label(basis)
         );
     }
 
     // Collect files with sizes for sorting
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut hidden: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         if output_mode != OutputMode::Quiet {
@@ -61,12 +69,17 @@ pub fn scan(
             dir,
             &cutoff,
             &mut files_with_sizes,
+            &mut hidden,
             config,
             output_mode,
             None,
         )?;
     }
 
+    if config.safety.include_hidden {
+        files_with_sizes.extend(hidden.iter().cloned());
+    }
+
     // Sort by size descending (biggest first)
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
 
@@ -89,11 +102,15 @@ pub fn scan(
 
         for (i, (path, size)) in files_with_sizes.iter().take(show_count).enumerate() {
             let size_str = bytesize::to_string(*size, false);
+            let age = file_age(path, basis)
+                .map(|age| format!("  {}", Theme::muted(&age.describe())))
+                .unwrap_or_default();
             println!(
-                "      {} {} ({})",
+                "      {} {} ({}){}",
                 Theme::muted("→"),
                 path.display(),
-                Theme::size(&size_str)
+                Theme::size(&size_str),
+                age
             );
 
             if i == 9 && output_mode == OutputMode::Normal && files_with_sizes.len() > 10 {
@@ -113,12 +130,19 @@ pub fn scan(
         result.size_bytes += size;
         result.paths.push(path);
     }
+    result.set_hidden(hidden, MAX_RESULTS);
 
     Ok(result)
 }
 
-/// Get user directories to scan (Downloads, Documents, Desktop, Pictures, Videos, Music)
-fn get_user_directories() -> Result<Vec<PathBuf>> {
+/// Get user directories to scan (Downloads, Documents, Desktop, Pictures, Videos, Music),
+/// or the `[categories.old] roots` when configured
+fn get_user_directories(config: &Config) -> Result<Vec<PathBuf>> {
+    let roots = config.categories.old.root_paths();
+    if !roots.is_empty() {
+        return Ok(roots);
+    }
+
     let mut dirs = Vec::new();
 
     if let Ok(user_profile) = env::var("USERPROFILE") {
@@ -145,20 +169,25 @@ pub fn scan_with_progress(
     let reporter = Arc::new(ScanPathReporter::new("Old Files", tx.clone(), 75));
 
     let cutoff = Utc::now() - Duration::days(min_age_days as i64);
-    let user_dirs = get_user_directories()?;
+    let user_dirs = get_user_directories(config)?;
     let mut files_with_sizes: Vec<(PathBuf, u64)> = Vec::new();
+    let mut hidden: Vec<(PathBuf, u64)> = Vec::new();
 
     for dir in &user_dirs {
         scan_directory(
             dir,
             &cutoff,
             &mut files_with_sizes,
+            &mut hidden,
             config,
             output_mode,
             Some(Arc::clone(&reporter)),
         )?;
     }
 
+    if config.safety.include_hidden {
+        files_with_sizes.extend(hidden.iter().cloned());
+    }
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
     files_with_sizes.truncate(MAX_RESULTS);
 
@@ -168,6 +197,7 @@ pub fn scan_with_progress(
         result.size_bytes += size;
         result.paths.push(path);
     }
+    result.set_hidden(hidden, MAX_RESULTS);
 
     let _ = root;
     Ok(result)
@@ -178,6 +208,7 @@ fn scan_directory(
     dir: &Path,
     cutoff: &chrono::DateTime<Utc>,
     files: &mut Vec<(PathBuf, u64)>,
+    hidden: &mut Vec<(PathBuf, u64)>,
     config: &Config,
     _output_mode: OutputMode,
     reporter: Option<Arc<ScanPathReporter>>,
@@ -188,13 +219,15 @@ fn scan_directory(
 
     use jwalk::WalkDir;
 
-    const MAX_DEPTH: usize = 20;
+    let max_depth = config.categories.old.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+    let basis = config.categories.old.age_basis.unwrap_or_default();
+    let access_tracked = access_times_tracked();
 
     // Clone config for thread-safe access
     let config_arc = Arc::new(config.clone());
 
     let walk = WalkDir::new(dir)
-        .max_depth(MAX_DEPTH)
+        .max_depth(max_depth)
         .follow_links(false)
         .parallelism(jwalk::Parallelism::RayonDefaultPool {
             busy_timeout: std::time::Duration::from_secs(1),
@@ -252,7 +285,14 @@ fn scan_directory(
             });
         });
 
-    for e in walk.into_iter().flatten() {
+    for e in walk.into_iter() {
+        let e = match e {
+            Ok(e) => e,
+            Err(err) => {
+                crate::elevation::note_jwalk_error(&err);
+                continue;
+            }
+        };
         let path = e.path();
         if let Some(ref reporter) = reporter {
             reporter.emit_path(&path);
@@ -274,9 +314,10 @@ fn scan_directory(
         }
 
         // Check age
-        if let Ok(modified) = metadata.modified() {
-            let modified_dt: chrono::DateTime<Utc> = modified.into();
-            if modified_dt < *cutoff {
+        let times = utils::FileTimes::from_metadata(&metadata);
+        if let Some((stamp, _)) = pick_timestamp(&times, basis, access_tracked) {
+            let stamp_dt: chrono::DateTime<Utc> = stamp.into();
+            if stamp_dt < *cutoff {
                 // Skip files in active projects (using CACHED git lookup)
                 // PERFORMANCE: Both find_git_root_cached and is_project_active are now cached
                 if let Some(project_root) = git::find_git_root_cached(&path) {
@@ -287,7 +328,11 @@ fn scan_directory(
                     }
                 }
 
-                files.push((path, metadata.len()));
+                if utils::is_hidden_or_system(&path) {
+                    hidden.push((path, metadata.len()));
+                } else {
+                    files.push((path, metadata.len()));
+                }
             }
         }
     }
@@ -295,9 +340,207 @@ fn scan_directory(
     Ok(())
 }
 
+/// Which timestamp a file's age was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeStamp {
+    Accessed,
+    Modified,
+    Created,
+}
+
+impl AgeStamp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgeStamp::Accessed => "accessed",
+            AgeStamp::Modified => "modified",
+            AgeStamp::Created => "created",
+        }
+    }
+}
+
+/// How old a file is, and by which timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileAge {
+    pub days: u64,
+    pub stamp: AgeStamp,
+}
+
+impl FileAge {
+    pub fn of(
+        times: &utils::FileTimes,
+        basis: AgeBasis,
+        access_tracked: bool,
+        now: SystemTime,
+    ) -> Option<Self> {
+        let (time, stamp) = pick_timestamp(times, basis, access_tracked)?;
+        let days = now
+            .duration_since(time)
+            .map(|age| age.as_secs() / 86_400)
+            .unwrap_or(0);
+        Some(Self { days, stamp })
+    }
+
+    /// "not accessed in 412 days", naming the timestamp so the claim holds up
+    pub fn describe(&self) -> String {
+        match self.stamp {
+            AgeStamp::Accessed => format!("not accessed in {} days", self.days),
+            AgeStamp::Modified => format!("not modified in {} days", self.days),
+            AgeStamp::Created => format!("created {} days ago", self.days),
+        }
+    }
+}
+
+/// Age of `path` by `basis` (`None` if its timestamps can't be read)
+pub fn file_age(path: &Path, basis: AgeBasis) -> Option<FileAge> {
+    FileAge::of(
+        &utils::file_times(path),
+        basis,
+        access_times_tracked(),
+        SystemTime::now(),
+    )
+}
+
+/// The timestamp `basis` judges a file by. Missing timestamps fall back to
+/// the modification time.
+fn pick_timestamp(
+    times: &utils::FileTimes,
+    basis: AgeBasis,
+    access_tracked: bool,
+) -> Option<(SystemTime, AgeStamp)> {
+    let modified = times.modified.map(|t| (t, AgeStamp::Modified));
+    match basis {
+        AgeBasis::Mtime => modified,
+        AgeBasis::Atime => times.accessed.map(|t| (t, AgeStamp::Accessed)).or(modified),
+        AgeBasis::Ctime => times.created.map(|t| (t, AgeStamp::Created)).or(modified),
+        AgeBasis::Best => match (times.accessed, times.modified) {
+            // An access time before the last write means reads aren't recorded
+            (Some(accessed), Some(written)) if access_tracked && accessed >= written => {
+                Some((accessed, AgeStamp::Accessed))
+            }
+            (Some(accessed), None) if access_tracked => Some((accessed, AgeStamp::Accessed)),
+            _ => modified,
+        },
+    }
+}
+
+/// What `basis` judges age by, for the scan banner
+fn basis_label(basis: AgeBasis) -> &'static str {
+    match basis {
+        AgeBasis::Best if access_times_tracked() => "last access",
+        AgeBasis::Best => "last modification (access times aren't recorded)",
+        AgeBasis::Atime => "last access",
+        AgeBasis::Mtime => "last modification",
+        AgeBasis::Ctime => "creation",
+    }
+}
+
+/// Whether the system keeps last-access times up to date. NTFS often doesn't
+/// (`fsutil behavior query disablelastaccess`).
+pub fn access_times_tracked() -> bool {
+    static TRACKED: OnceLock<bool> = OnceLock::new();
+    *TRACKED.get_or_init(query_access_tracking)
+}
+
+#[cfg(windows)]
+fn query_access_tracking() -> bool {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    // The low bit disables updates; the high bit only says whether the user
+    // or the system manages the setting. Missing means disabled (pre-1803).
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SYSTEM\CurrentControlSet\Control\FileSystem")
+        .and_then(|key| key.get_value::<u32, _>("NtfsDisableLastAccessUpdate"))
+        .map(|value| value & 1 == 0)
+        .unwrap_or(false)
+}
+
+/// relatime and friends update access times at least daily
+#[cfg(not(windows))]
+fn query_access_tracking() -> bool {
+    true
+}
+
+/// Typed options for scanning old files on their own
+#[derive(Debug, Clone)]
+pub struct OldScanOptions {
+    pub min_age_days: u64,
+    pub config: Config,
+}
+
+impl Default for OldScanOptions {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            min_age_days: config.thresholds.min_age_days,
+            config,
+        }
+    }
+}
+
+/// Scan for files older than `min_age_days` by the configured `age_basis`
+pub fn scan_with_options(root: &Path, options: &OldScanOptions) -> Result<CategoryScan> {
+    let basis = options.config.categories.old.age_basis.unwrap_or_default();
+    let result = scan(
+        root,
+        options.min_age_days,
+        &options.config,
+        OutputMode::Quiet,
+    )?;
+    Ok(CategoryScan::from_result(
+        "old",
+        Safety::Review,
+        result,
+        |path| match file_age(path, basis) {
+            Some(age) => format!("file {}", age.describe()),
+            None => format!("file older than {} days", options.min_age_days),
+        },
+    ))
+}
+
 /// Clean (delete) an old file by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
         .with_context(|| format!("Failed to delete old file: {}", path.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration as StdDuration;
+
+    #[test]
+    fn best_basis_trusts_access_times_only_when_recorded() {
+        let day = StdDuration::from_secs(86_400);
+        let now = SystemTime::now();
+        let times = utils::FileTimes {
+            created: Some(now - day * 400),
+            modified: Some(now - day * 300),
+            accessed: Some(now - day * 10),
+        };
+
+        let age = |basis, tracked| FileAge::of(&times, basis, tracked, now).unwrap();
+        assert_eq!(age(AgeBasis::Best, true).stamp, AgeStamp::Accessed);
+        assert_eq!(age(AgeBasis::Best, true).days, 10);
+        assert_eq!(age(AgeBasis::Best, false).stamp, AgeStamp::Modified);
+        assert_eq!(
+            age(AgeBasis::Best, false).describe(),
+            "not modified in 300 days"
+        );
+        assert_eq!(
+            age(AgeBasis::Ctime, true).describe(),
+            "created 400 days ago"
+        );
+
+        // A read older than the last write means access times are stale
+        let stale = utils::FileTimes {
+            accessed: Some(now - day * 500),
+            ..times
+        };
+        let best = FileAge::of(&stale, AgeBasis::Best, true, now).unwrap();
+        assert_eq!(best.stamp, AgeStamp::Modified);
+        let atime = FileAge::of(&stale, AgeBasis::Atime, true, now).unwrap();
+        assert_eq!(atime.describe(), "not accessed in 500 days");
+    }
+}
@@ -0,0 +1,478 @@
+use crate::config::Config;
+use crate::output::{CategoryResult, OutputMode};
+use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
+use crate::theme::Theme;
+use crate::utils::{self, SizeWalk};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use walkdir::WalkDir;
+
+/// Maximum number of results to return
+const MAX_RESULTS: usize = 500;
+
+/// Maximum directory depth to walk below the scan root
+const MAX_DEPTH: usize = 12;
+
+/// Ownership state of a file or folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnerStatus {
+    /// Owner SID resolves to an existing user, group or service account
+    Resolved,
+    /// Owner SID no longer maps to any account (deleted user, uninstalled app,
+    /// or a profile carried over from another machine). Holds the string SID.
+    Orphaned(String),
+}
+
+/// Orphaned paths grouped by the SID that owns them
+#[derive(Debug, Clone)]
+pub struct OwnerGroup {
+    pub owner_sid: String,
+    pub paths: Vec<PathBuf>,
+    pub size_bytes: u64,
+}
+
+/// A single orphaned path found during the scan
+#[derive(Debug, Clone)]
+pub struct OrphanedEntry {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub owner_sid: String,
+}
+
+/// Scan for files and folders owned by unknown or deleted SIDs
+///
+/// When a folder itself is owned by an orphaned SID it is reported as a whole
+/// (with its recursive size) and not descended into, so an old profile left
+/// behind by a removed account shows up as a single entry.
+///
+/// Ownership lookups are Windows-only; on other platforms this returns no results.
+pub fn scan(
+    root: &Path,
+    config: &Config,
+    output_mode: OutputMode,
+    walk: &SizeWalk,
+) -> Result<CategoryResult> {
+    if output_mode != OutputMode::Quiet {
+        println!(
+            "  {} Checking file owners under {}...",
+            Theme::muted("→"),
+            root.display()
+        );
+    }
+
+    let entries = scan_internal(root, config, None, walk);
+
+    if output_mode != OutputMode::Quiet && !entries.is_empty() {
+        let groups = group_by_owner(&entries);
+        println!(
+            "  {} Found {} items owned by {} unknown accounts:",
+            Theme::muted("→"),
+            entries.len(),
+            groups.len()
+        );
+
+        let show_count = match output_mode {
+            OutputMode::VeryVerbose | OutputMode::Verbose => groups.len(),
+            OutputMode::Normal => 10.min(groups.len()),
+            OutputMode::Quiet => 0,
+        };
+
+        for group in groups.iter().take(show_count) {
+            println!(
+                "      {} {} ({} items, {})",
+                Theme::muted("→"),
+                group.owner_sid,
+                group.paths.len(),
+                Theme::size(&bytesize::to_string(group.size_bytes, false))
+            );
+            if output_mode == OutputMode::VeryVerbose {
+                for path in &group.paths {
+                    println!("         {}", Theme::muted(&path.display().to_string()));
+                }
+            }
+        }
+
+        if groups.len() > show_count {
+            println!(
+                "      {} ... and {} more owners (use -v to see all)",
+                Theme::muted("→"),
+                groups.len() - show_count
+            );
+        }
+    }
+
+    Ok(to_category_result(entries))
+}
+
+/// Scan for orphaned-owner files with TUI progress updates (current path).
+pub fn scan_with_progress(
+    root: &Path,
+    config: &Config,
+    tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
+) -> Result<CategoryResult> {
+    const CATEGORY: &str = "Orphaned Owners";
+
+    let _ = tx.send(ScanProgressEvent::CategoryStarted {
+        category: CATEGORY.to_string(),
+        total_units: None,
+        current_path: None,
+    });
+
+    let reporter = ScanPathReporter::new(CATEGORY, tx.clone(), 75);
+    let entries = scan_internal(root, config, Some(&reporter), walk);
+    Ok(to_category_result(entries))
+}
+
+fn scan_internal(
+    root: &Path,
+    config: &Config,
+    reporter: Option<&ScanPathReporter>,
+    walk: &SizeWalk,
+) -> Vec<OrphanedEntry> {
+    let mut entries = Vec::new();
+
+    if !root.exists() {
+        return entries;
+    }
+
+    // Cache SID lookups: a migrated machine typically has thousands of files
+    // owned by the same handful of accounts.
+    let mut sid_cache: HashMap<String, bool> = HashMap::new();
+
+    let mut walker = WalkDir::new(root)
+        .max_depth(MAX_DEPTH)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            if e.file_type().is_dir() {
+                if e.depth() > 0 && utils::should_skip_entry(e.path()) {
+                    return false;
+                }
+                if utils::is_system_path(e.path()) || config.is_excluded(e.path()) {
+                    return false;
+                }
+            }
+            true
+        });
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                crate::elevation::note_walkdir_error(&e);
+                continue;
+            }
+        };
+
+        // Never offer the scan root itself for deletion
+        if entry.depth() == 0 {
+            continue;
+        }
+
+        let path = entry.path();
+        let is_dir = entry.file_type().is_dir();
+        if !is_dir && !entry.file_type().is_file() {
+            continue;
+        }
+        if !is_dir && config.is_excluded(path) {
+            continue;
+        }
+
+        if let Some(reporter) = reporter {
+            reporter.emit_path(path);
+        }
+
+        let Some(OwnerStatus::Orphaned(sid)) = owner_status_cached(path, &mut sid_cache) else {
+            continue;
+        };
+
+        let size_bytes = if is_dir {
+            walker.skip_current_dir();
+            utils::calculate_dir_size_in(path, walk)
+        } else {
+            entry.metadata().map(|m| m.len()).unwrap_or(0)
+        };
+
+        entries.push(OrphanedEntry {
+            path: path.to_path_buf(),
+            size_bytes,
+            owner_sid: sid,
+        });
+    }
+
+    // Sort by size descending (biggest first) and limit results
+    entries.sort_by_key(|e| std::cmp::Reverse(e.size_bytes));
+    entries.truncate(MAX_RESULTS);
+    entries
+}
+
+fn to_category_result(entries: Vec<OrphanedEntry>) -> CategoryResult {
+    let mut result = CategoryResult::default();
+    for entry in entries {
+        result.items += 1;
+        result.size_bytes += entry.size_bytes;
+        result.paths.push(entry.path);
+    }
+    result
+}
+
+/// Group orphaned entries by owner SID, largest owner first
+pub fn group_by_owner(entries: &[OrphanedEntry]) -> Vec<OwnerGroup> {
+    let mut by_owner: HashMap<&str, OwnerGroup> = HashMap::new();
+    for entry in entries {
+        let group = by_owner
+            .entry(entry.owner_sid.as_str())
+            .or_insert_with(|| OwnerGroup {
+                owner_sid: entry.owner_sid.clone(),
+                paths: Vec::new(),
+                size_bytes: 0,
+            });
+        group.paths.push(entry.path.clone());
+        group.size_bytes += entry.size_bytes;
+    }
+
+    let mut groups: Vec<OwnerGroup> = by_owner.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.owner_sid.cmp(&b.owner_sid))
+    });
+    groups
+}
+
+/// Resolve the owner of a path, reusing earlier lookups for the same SID
+fn owner_status_cached(path: &Path, cache: &mut HashMap<String, bool>) -> Option<OwnerStatus> {
+    let sid = owner_sid(path)?;
+    let orphaned = *cache
+        .entry(sid.clone())
+        .or_insert_with(|| !sid_resolves(&sid));
+    if orphaned {
+        Some(OwnerStatus::Orphaned(sid))
+    } else {
+        Some(OwnerStatus::Resolved)
+    }
+}
+
+/// Get the owner of a path
+///
+/// Returns `None` when the owner cannot be read (access denied, non-Windows).
+pub fn owner_status(path: &Path) -> Option<OwnerStatus> {
+    owner_status_cached(path, &mut HashMap::new())
+}
+
+/// Read the owner SID of a path as a string (e.g. "S-1-5-21-...")
+#[cfg(windows)]
+fn owner_sid(path: &Path) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{LocalFree, ERROR_SUCCESS, HLOCAL};
+    use windows::Win32::Security::Authorization::{
+        ConvertSidToStringSidW, GetNamedSecurityInfoW, SE_FILE_OBJECT,
+    };
+    use windows::Win32::Security::{OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID};
+
+    let wide: Vec<u16> = utils::to_long_path(path)
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    unsafe {
+        let mut owner = PSID::default();
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        let status = GetNamedSecurityInfoW(
+            PCWSTR(wide.as_ptr()),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            Some(&mut owner as *mut PSID),
+            None,
+            None,
+            None,
+            &mut descriptor,
+        );
+        if status != ERROR_SUCCESS || owner.is_invalid() {
+            return None;
+        }
+
+        let mut sid_string = PWSTR::null();
+        let sid = match ConvertSidToStringSidW(owner, &mut sid_string) {
+            Ok(()) => {
+                let s = sid_string.to_string().ok();
+                let _ = LocalFree(HLOCAL(sid_string.0 as _));
+                s
+            }
+            Err(_) => None,
+        };
+
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        sid
+    }
+}
+
+#[cfg(not(windows))]
+fn owner_sid(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Check whether a string SID still maps to an account on this machine or domain
+#[cfg(windows)]
+fn sid_resolves(sid: &str) -> bool {
+    use windows::core::{HRESULT, PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{LocalFree, ERROR_NONE_MAPPED, HLOCAL};
+    use windows::Win32::Security::Authorization::ConvertStringSidToSidW;
+    use windows::Win32::Security::{LookupAccountSidW, SidTypeDeletedAccount, PSID, SID_NAME_USE};
+
+    let wide: Vec<u16> = sid.encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        let mut psid = PSID::default();
+        if ConvertStringSidToSidW(PCWSTR(wide.as_ptr()), &mut psid).is_err() {
+            // Can't parse it - treat as resolved so we never flag it
+            return true;
+        }
+
+        let mut name = [0u16; 256];
+        let mut name_len = name.len() as u32;
+        let mut domain = [0u16; 256];
+        let mut domain_len = domain.len() as u32;
+        let mut sid_use = SID_NAME_USE::default();
+
+        let lookup = LookupAccountSidW(
+            PCWSTR::null(),
+            psid,
+            PWSTR(name.as_mut_ptr()),
+            &mut name_len,
+            PWSTR(domain.as_mut_ptr()),
+            &mut domain_len,
+            &mut sid_use,
+        );
+        let _ = LocalFree(HLOCAL(psid.0));
+
+        match lookup {
+            Ok(()) => sid_use != SidTypeDeletedAccount,
+            // Only "no mapping" means the account is gone. Other failures (domain
+            // controller unreachable, etc.) must not cause files to be flagged.
+            Err(e) => e.code() != HRESULT::from_win32(ERROR_NONE_MAPPED.0),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn sid_resolves(_sid: &str) -> bool {
+    true
+}
+
+/// Take ownership of a path and grant the current user full control
+///
+/// Only called when the user explicitly passes `--take-ownership`; a normal
+/// clean never changes ACLs.
+pub fn take_ownership(path: &Path) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use anyhow::Context;
+        use std::process::Command;
+
+        let path_str = path.display().to_string();
+        let mut takeown = Command::new("takeown");
+        takeown.args(["/F", path_str.as_str()]);
+        if path.is_dir() {
+            takeown.args(["/R", "/D", "Y"]);
+        }
+        let output = takeown
+            .output()
+            .with_context(|| format!("Failed to run takeown for {}", path_str))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "takeown failed for {} (may require admin privileges)",
+                path_str
+            ));
+        }
+
+        let user = match (std::env::var("USERDOMAIN"), std::env::var("USERNAME")) {
+            (Ok(domain), Ok(name)) => format!("{}\\{}", domain, name),
+            (_, Ok(name)) => name,
+            _ => return Err(anyhow::anyhow!("Cannot determine current user")),
+        };
+        let grant = format!("{}:F", user);
+        let output = Command::new("icacls")
+            .args([
+                path_str.as_str(),
+                "/grant",
+                grant.as_str(),
+                "/T",
+                "/C",
+                "/Q",
+            ])
+            .output()
+            .with_context(|| format!("Failed to run icacls for {}", path_str))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "icacls failed to grant access to {}",
+                path_str
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        Err(anyhow::anyhow!(
+            "Taking ownership is only supported on Windows: {}",
+            path.display()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, size_bytes: u64, owner_sid: &str) -> OrphanedEntry {
+        OrphanedEntry {
+            path: PathBuf::from(path),
+            size_bytes,
+            owner_sid: owner_sid.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_owner_sums_and_sorts() {
+        let entries = vec![
+            entry("a", 100, "S-1-5-21-1"),
+            entry("b", 50, "S-1-5-21-2"),
+            entry("c", 300, "S-1-5-21-2"),
+        ];
+
+        let groups = group_by_owner(&entries);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].owner_sid, "S-1-5-21-2");
+        assert_eq!(groups[0].size_bytes, 350);
+        assert_eq!(groups[0].paths.len(), 2);
+        assert_eq!(groups[1].size_bytes, 100);
+    }
+
+    #[test]
+    fn test_to_category_result() {
+        let result = to_category_result(vec![entry("a", 10, "S-1"), entry("b", 20, "S-2")]);
+        assert_eq!(result.items, 2);
+        assert_eq!(result.size_bytes, 30);
+        assert_eq!(result.paths.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_missing_root() {
+        let config = Config::default();
+        let result = scan(
+            Path::new("/definitely/not/a/real/path"),
+            &config,
+            OutputMode::Quiet,
+            &SizeWalk::default(),
+        )
+        .unwrap();
+        assert_eq!(result.items, 0);
+    }
+}
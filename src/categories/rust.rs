@@ -0,0 +1,326 @@
+//! Rust toolchain caches.
+//!
+//! The parts of a Rust setup that grow without bound and that the generic
+//! Package Cache entry (which reports `~/.cargo/registry` as a whole) can't
+//! break down:
+//! - Registry crates: downloaded `.crate` archives (`registry/cache`) and
+//!   their extracted sources (`registry/src`) no build has used in
+//!   `[categories.rust] unused_days`. Cargo records when each was last used
+//!   in `~/.cargo/.global-cache`; without it, file times are used.
+//! - Rustup toolchains not run in `unused_days`, except the default toolchain
+//!   and directory overrides.
+//! - The sccache cache (`SCCACHE_DIR`).
+//!
+//! Everything here is downloaded or rebuilt again when needed.
+
+use crate::config::Config;
+use crate::output::CategoryResult;
+use crate::utils::{self, SizeWalk};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the Rust caches live
+#[derive(Debug, Clone, Default)]
+pub struct Locations {
+    /// `CARGO_HOME` or `~/.cargo`
+    pub cargo_home: Option<PathBuf>,
+    /// `RUSTUP_HOME` or `~/.rustup`
+    pub rustup_home: Option<PathBuf>,
+    /// `SCCACHE_DIR` or sccache's default cache folder
+    pub sccache_dir: Option<PathBuf>,
+}
+
+impl Locations {
+    /// Standard locations for the current user, honouring `CARGO_HOME`,
+    /// `RUSTUP_HOME` and `SCCACHE_DIR`
+    pub fn detect() -> Self {
+        let base = directories::BaseDirs::new();
+        let home = base.as_ref().map(|b| b.home_dir().to_path_buf());
+        let env_path = |name: &str| env::var_os(name).map(PathBuf::from);
+
+        let default_sccache = base.as_ref().map(|b| {
+            if cfg!(windows) {
+                b.data_local_dir()
+                    .join("Mozilla")
+                    .join("sccache")
+                    .join("cache")
+            } else if cfg!(target_os = "macos") {
+                b.cache_dir().join("Mozilla.sccache")
+            } else {
+                b.cache_dir().join("sccache")
+            }
+        });
+
+        Self {
+            cargo_home: env_path("CARGO_HOME").or_else(|| home.as_ref().map(|h| h.join(".cargo"))),
+            rustup_home: env_path("RUSTUP_HOME")
+                .or_else(|| home.as_ref().map(|h| h.join(".rustup"))),
+            sccache_dir: env_path("SCCACHE_DIR").or(default_sccache),
+        }
+    }
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    entries(path).into_iter().filter(|p| p.is_dir()).collect()
+}
+
+fn entries(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
+
+/// Latest of the access and modification times
+fn last_touched(path: &Path) -> Option<DateTime<Utc>> {
+    let meta = fs::metadata(path).ok()?;
+    let times = [meta.accessed().ok(), meta.modified().ok()];
+    times.into_iter().flatten().max().map(DateTime::<Utc>::from)
+}
+
+/// Cargo's record of when each registry crate and source folder was last
+/// used, keyed by `<index>/<name>` (`index.crates.io-6f17d22bba15001f/serde-1.0.200.crate`)
+struct LastUse {
+    crates: HashMap<String, DateTime<Utc>>,
+    sources: HashMap<String, DateTime<Utc>>,
+}
+
+impl LastUse {
+    fn load(cargo_home: &Path) -> Self {
+        let db = cargo_home.join(".global-cache");
+        let conn = db
+            .is_file()
+            .then(|| {
+                rusqlite::Connection::open_with_flags(
+                    &db,
+                    rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+                )
+                .ok()
+            })
+            .flatten();
+        let query = |table: &str| -> HashMap<String, DateTime<Utc>> {
+            let Some(conn) = &conn else {
+                return HashMap::new();
+            };
+            let sql = format!(
+                "SELECT i.name, t.name, t.timestamp FROM {} t \
+                 JOIN registry_index i ON t.registry_id = i.id",
+                table
+            );
+            let Ok(mut stmt) = conn.prepare(&sql) else {
+                return HashMap::new();
+            };
+            stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .map(|rows| {
+                rows.filter_map(|r| r.ok())
+                    .filter_map(|(index, name, ts)| {
+                        Some((
+                            format!("{}/{}", index, name),
+                            DateTime::from_timestamp(ts, 0)?,
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+        };
+        Self {
+            crates: query("registry_crate"),
+            sources: query("registry_src"),
+        }
+    }
+}
+
+/// Registry `.crate` archives and extracted sources last used before `cutoff`
+fn stale_registry_entries(cargo_home: &Path, cutoff: DateTime<Utc>) -> Vec<PathBuf> {
+    let last_use = LastUse::load(cargo_home);
+    let registry = cargo_home.join("registry");
+    let mut stale = Vec::new();
+
+    for (folder, recorded) in [("cache", &last_use.crates), ("src", &last_use.sources)] {
+        for index in subdirs(&registry.join(folder)) {
+            for entry in entries(&index) {
+                let key = format!("{}/{}", file_name(&index), file_name(&entry));
+                let used = recorded.get(&key).copied().or_else(|| last_touched(&entry));
+                if used.is_some_and(|used| used < cutoff) {
+                    stale.push(entry);
+                }
+            }
+        }
+    }
+    stale
+}
+
+/// Toolchains rustup must keep: the default and any directory overrides
+fn toolchains_in_use(rustup_home: &Path) -> HashSet<String> {
+    let Ok(settings) = fs::read_to_string(rustup_home.join("settings.toml")) else {
+        return HashSet::new();
+    };
+    let Ok(settings) = settings.parse::<toml::Table>() else {
+        return HashSet::new();
+    };
+    let default = settings
+        .get("default_toolchain")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let overrides = settings
+        .get("overrides")
+        .and_then(|v| v.as_table())
+        .into_iter()
+        .flat_map(|table| table.values())
+        .filter_map(|v| v.as_str().map(str::to_string));
+    default.into_iter().chain(overrides).collect()
+}
+
+/// `stable` in settings.toml names the `stable-<host triple>` folder
+fn is_in_use(toolchain: &str, in_use: &HashSet<String>) -> bool {
+    in_use.iter().any(|name| {
+        toolchain == name
+            || toolchain
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.starts_with('-'))
+    })
+}
+
+/// Installed toolchains whose `rustc` and `cargo` weren't run since `cutoff`
+fn unused_toolchains(rustup_home: &Path, cutoff: DateTime<Utc>) -> Vec<PathBuf> {
+    let in_use = toolchains_in_use(rustup_home);
+    subdirs(&rustup_home.join("toolchains"))
+        .into_iter()
+        .filter(|toolchain| !is_in_use(file_name(toolchain), &in_use))
+        .filter(|toolchain| {
+            let bin = toolchain.join("bin");
+            let last_run = ["rustc", "cargo", "rustc.exe", "cargo.exe"]
+                .iter()
+                .filter_map(|exe| last_touched(&bin.join(exe)))
+                .max();
+            last_run.is_some_and(|used| used < cutoff)
+        })
+        .collect()
+}
+
+/// Every Rust cache under `locations` unused since `cutoff`
+pub fn find_caches(locations: &Locations, cutoff: DateTime<Utc>) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    if let Some(cargo_home) = &locations.cargo_home {
+        found.extend(stale_registry_entries(cargo_home, cutoff));
+    }
+    if let Some(rustup_home) = &locations.rustup_home {
+        found.extend(unused_toolchains(rustup_home, cutoff));
+    }
+    if let Some(sccache) = locations.sccache_dir.as_ref().filter(|d| d.is_dir()) {
+        found.push(sccache.clone());
+    }
+    found
+}
+
+/// Scan for stale Rust registry crates, unused toolchains and the sccache cache
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
+    let cutoff = Utc::now() - Duration::days(config.categories.rust.unused_days as i64);
+    Ok(scan_locations(&Locations::detect(), cutoff, config, walk))
+}
+
+fn scan_locations(
+    locations: &Locations,
+    cutoff: DateTime<Utc>,
+    config: &Config,
+    walk: &SizeWalk,
+) -> CategoryResult {
+    // Sequential: calculate_dir_size_in already walks on the rayon pool
+    let sized: Vec<(PathBuf, u64)> = find_caches(locations, cutoff)
+        .into_iter()
+        .filter(|path| !config.is_excluded(path) && !utils::should_skip_entry(path))
+        .map(|path| {
+            let size = if path.is_file() {
+                fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            } else {
+                utils::calculate_dir_size_in(&path, walk)
+            };
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+
+    CategoryResult {
+        items: sized.len(),
+        size_bytes: sized.iter().map(|(_, size)| size).sum(),
+        paths: sized.into_iter().map(|(path, _)| path).collect(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn finds_stale_crates_and_unused_toolchains() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let write = |relative: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![1u8; 256]).unwrap();
+        };
+        write("cargo/registry/cache/index.crates.io-1/serde-1.0.0.crate");
+        write("cargo/registry/src/index.crates.io-1/serde-1.0.0/src/lib.rs");
+        write("rustup/toolchains/stable-x86_64-unknown-linux-gnu/bin/rustc");
+        write("rustup/toolchains/1.70.0-x86_64-unknown-linux-gnu/bin/rustc");
+        write("rustup/toolchains/nightly-x86_64-unknown-linux-gnu/bin/rustc");
+        write("sccache/a/b/object.o");
+        fs::write(
+            root.join("rustup/settings.toml"),
+            "default_toolchain = \"stable\"\n\n[overrides]\n\"/src/app\" = \"nightly-x86_64-unknown-linux-gnu\"\n",
+        )
+        .unwrap();
+
+        let locations = Locations {
+            cargo_home: Some(root.join("cargo")),
+            rustup_home: Some(root.join("rustup")),
+            sccache_dir: Some(root.join("sccache")),
+        };
+        // Everything was just written: only the sccache cache is reported
+        let recent = Utc::now() - Duration::days(1);
+        let found = find_caches(&locations, recent);
+        assert_eq!(found, vec![root.join("sccache")]);
+
+        // With a cutoff in the future everything counts as unused, except
+        // the default and overridden toolchains
+        let future = Utc::now() + Duration::days(1);
+        let result = scan_locations(&locations, future, &Config::default(), &SizeWalk::default());
+        let mut found: Vec<String> = result
+            .paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "cargo/registry/cache/index.crates.io-1/serde-1.0.0.crate",
+                "cargo/registry/src/index.crates.io-1/serde-1.0.0",
+                "rustup/toolchains/1.70.0-x86_64-unknown-linux-gnu",
+                "sccache",
+            ]
+        );
+        assert_eq!(result.size_bytes, 4 * 256);
+    }
+}
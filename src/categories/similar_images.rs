@@ -0,0 +1,717 @@
+//! Similar images
+//!
+//! Optional perceptual matching for the Duplicates category. Exact duplicates
+//! need identical bytes, which photos re-saved at another quality or size, or
+//! converted between JPEG and PNG, never have. With
+//! `[categories.duplicates] similar_images = true`, every JPEG and PNG the duplicate scan
+//! walks past gets a 64-bit difference hash (dHash) of its brightness, and
+//! images whose hashes differ in at most `similar_images_max_distance` bits
+//! are grouped as similar. These groups are for review only: they never
+//! become Duplicates paths, so nothing in them is cleaned.
+//!
+//! Decoding is deliberately minimal. JPEGs are read at 1/8 scale from the DC
+//! coefficients of the luminance channel (baseline files, and the first scan
+//! of progressive ones); non-interlaced PNGs are decoded row by row. Images
+//! that can't be read this way are skipped.
+
+use super::duplicates::DuplicateGroup;
+use flate2::read::ZlibDecoder;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Hash grid: 9 columns compared pairwise give 8 bits per row
+const GRID_W: usize = 9;
+const GRID_H: usize = 8;
+
+/// Images smaller than this (icons, thumbnails) aren't compared (bytes)
+pub const MIN_IMAGE_BYTES: u64 = 8 * 1024;
+/// Images larger than this are skipped (bytes)
+pub const MAX_IMAGE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Maximum number of similar-image groups to return
+const MAX_GROUPS: usize = 50;
+
+/// Images that look alike without being byte-for-byte duplicates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarImageGroup {
+    /// Paths and sizes, largest first (usually the best-quality copy)
+    pub images: Vec<(PathBuf, u64)>,
+}
+
+impl SimilarImageGroup {
+    pub fn total_bytes(&self) -> u64 {
+        self.images.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Whether `path` has an extension the image hasher can decode
+pub fn is_image(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        matches!(
+            e.to_ascii_lowercase().as_str(),
+            "jpg" | "jpeg" | "jpe" | "jfif" | "png"
+        )
+    })
+}
+
+/// Number of differing bits between two hashes
+pub fn distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Perceptual hash of an image file, or None if it can't be decoded
+pub fn image_hash(path: &Path) -> Option<u64> {
+    let data = std::fs::read(path).ok()?;
+    if data.starts_with(PNG_SIGNATURE) {
+        png_hash(&data)
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        jpeg_hash(&data)
+    } else {
+        None
+    }
+}
+
+/// Hash `images` (path and size) and group the ones within `max_distance`
+/// bits of each other. Groups made up of a single exact-duplicate group are
+/// left out, since the Duplicates category already lists them.
+pub fn find_similar(
+    images: Vec<(PathBuf, u64)>,
+    max_distance: u32,
+    exact: &[DuplicateGroup],
+    on_image: impl Fn(&Path) + Sync,
+) -> Vec<SimilarImageGroup> {
+    let mut hashed: Vec<(PathBuf, u64, u64)> = images
+        .into_par_iter()
+        .filter_map(|(path, size)| {
+            on_image(&path);
+            image_hash(&path).map(|hash| (path, size, hash))
+        })
+        .collect();
+    hashed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let exact_group: HashMap<&Path, usize> = exact
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, g)| g.paths.iter().map(move |p| (p.as_path(), idx)))
+        .collect();
+
+    // Each unassigned image seeds a group of everything still unassigned
+    // that is close to it; seeding (rather than chaining neighbours) keeps
+    // every member close to the same picture
+    let mut assigned = vec![false; hashed.len()];
+    let mut groups = Vec::new();
+    for seed in 0..hashed.len() {
+        if assigned[seed] {
+            continue;
+        }
+        let members: Vec<usize> = (seed..hashed.len())
+            .filter(|&i| !assigned[i] && distance(hashed[seed].2, hashed[i].2) <= max_distance)
+            .collect();
+        if members.len() < 2 {
+            continue;
+        }
+        let first_exact = exact_group.get(hashed[seed].0.as_path());
+        if first_exact.is_some()
+            && members
+                .iter()
+                .all(|&i| exact_group.get(hashed[i].0.as_path()) == first_exact)
+        {
+            continue;
+        }
+        for &i in &members {
+            assigned[i] = true;
+        }
+        let mut images: Vec<(PathBuf, u64)> = members
+            .iter()
+            .map(|&i| (hashed[i].0.clone(), hashed[i].1))
+            .collect();
+        images.sort_by_key(|image| std::cmp::Reverse(image.1));
+        groups.push(SimilarImageGroup { images });
+    }
+
+    groups.sort_by_key(|g| std::cmp::Reverse(g.total_bytes()));
+    groups.truncate(MAX_GROUPS);
+    groups
+}
+
+/// Box-averages an image's brightness down to the hash grid
+struct Grid {
+    width: usize,
+    height: usize,
+    sums: [f64; GRID_W * GRID_H],
+    counts: [u32; GRID_W * GRID_H],
+}
+
+impl Grid {
+    /// None for images too small to fill every cell
+    fn new(width: usize, height: usize) -> Option<Self> {
+        (width >= GRID_W && height >= GRID_H).then_some(Self {
+            width,
+            height,
+            sums: [0.0; GRID_W * GRID_H],
+            counts: [0; GRID_W * GRID_H],
+        })
+    }
+
+    fn add(&mut self, x: usize, y: usize, value: f64) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let cell = (y * GRID_H / self.height) * GRID_W + x * GRID_W / self.width;
+        self.sums[cell] += value;
+        self.counts[cell] += 1;
+    }
+
+    /// One bit per horizontally adjacent cell pair: set when brightness increases
+    fn hash(&self) -> u64 {
+        let mean = |cell: usize| self.sums[cell] / self.counts[cell].max(1) as f64;
+        let mut hash = 0u64;
+        for row in 0..GRID_H {
+            for col in 0..GRID_W - 1 {
+                let cell = row * GRID_W + col;
+                if mean(cell) < mean(cell + 1) {
+                    hash |= 1 << (row * (GRID_W - 1) + col);
+                }
+            }
+        }
+        hash
+    }
+}
+
+fn luma(r: f64, g: f64, b: f64) -> f64 {
+    0.299 * r + 0.587 * g + 0.114 * b
+}
+
+fn be16(data: &[u8], pos: usize) -> Option<usize> {
+    Some(u16::from_be_bytes([*data.get(pos)?, *data.get(pos + 1)?]) as usize)
+}
+
+fn be32(data: &[u8], pos: usize) -> Option<usize> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?) as usize)
+}
+
+// PNG
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn png_hash(data: &[u8]) -> Option<u64> {
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut idat = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos + 8 <= data.len() {
+        let len = be32(data, pos)?;
+        let body = data.get(pos + 8..pos + 8 + len)?;
+        match &data[pos + 4..pos + 8] {
+            b"IHDR" => header = Some(body),
+            b"PLTE" => palette = body,
+            b"IDAT" => idat.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        pos += len + 12;
+    }
+
+    let header = header.filter(|h| h.len() >= 13)?;
+    let (width, height) = (be32(header, 0)?, be32(header, 4)?);
+    let (depth, color, interlace) = (header[8] as usize, header[9], header[12]);
+    let channels = match (color, depth) {
+        (0, 1 | 2 | 4 | 8 | 16) | (3, 1 | 2 | 4 | 8) => 1,
+        (4, 8 | 16) => 2,
+        (2, 8 | 16) => 3,
+        (6, 8 | 16) => 4,
+        _ => return None,
+    };
+    if interlace != 0 {
+        return None;
+    }
+
+    let bits_per_pixel = channels * depth;
+    let stride = (width * bits_per_pixel).div_ceil(8);
+    let bpp = (bits_per_pixel / 8).max(1);
+    let max = ((1u32 << depth.min(8)) - 1) as f64;
+    // 16-bit samples are read from their high byte
+    let sample = |row: &[u8], x: usize, channel: usize| -> f64 {
+        match depth {
+            8 => row[x * channels + channel] as f64,
+            16 => row[(x * channels + channel) * 2] as f64,
+            _ => {
+                let bit = x * depth;
+                let value = (row[bit / 8] >> (8 - depth - bit % 8)) & (max as u8);
+                value as f64
+            }
+        }
+    };
+
+    let mut grid = Grid::new(width, height)?;
+    let mut decoder = ZlibDecoder::new(&idat[..]);
+    let mut row = vec![0u8; stride];
+    let mut prev = vec![0u8; stride];
+    for y in 0..height {
+        let mut filter = [0u8];
+        decoder.read_exact(&mut filter).ok()?;
+        decoder.read_exact(&mut row).ok()?;
+        unfilter(filter[0], &mut row, &prev, bpp)?;
+        for x in 0..width {
+            let value = match color {
+                3 => {
+                    let idx = sample(&row, x, 0) as usize * 3;
+                    let rgb = palette.get(idx..idx + 3)?;
+                    luma(rgb[0] as f64, rgb[1] as f64, rgb[2] as f64)
+                }
+                2 | 6 => luma(sample(&row, x, 0), sample(&row, x, 1), sample(&row, x, 2)),
+                _ => sample(&row, x, 0) * 255.0 / max,
+            };
+            grid.add(x, y, value);
+        }
+        std::mem::swap(&mut row, &mut prev);
+    }
+    Some(grid.hash())
+}
+
+fn unfilter(filter: u8, row: &mut [u8], prev: &[u8], bpp: usize) -> Option<()> {
+    for i in 0..row.len() {
+        let left = if i >= bpp { row[i - bpp] } else { 0 };
+        let up = prev[i];
+        let up_left = if i >= bpp { prev[i - bpp] } else { 0 };
+        let predicted = match filter {
+            0 => 0,
+            1 => left,
+            2 => up,
+            3 => ((left as u16 + up as u16) / 2) as u8,
+            4 => paeth(left, up, up_left),
+            _ => return None,
+        };
+        row[i] = row[i].wrapping_add(predicted);
+    }
+    Some(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+// JPEG
+
+struct Component {
+    id: u8,
+    h: usize,
+    v: usize,
+}
+
+struct Frame {
+    width: usize,
+    height: usize,
+    progressive: bool,
+    components: Vec<Component>,
+}
+
+/// Canonical Huffman table, decoded bit by bit (JPEG spec F.2.2.3)
+#[derive(Clone)]
+struct Huffman {
+    max_code: [i32; 17],
+    min_code: [i32; 17],
+    val_ptr: [usize; 17],
+    values: Vec<u8>,
+}
+
+impl Huffman {
+    fn new(counts: &[u8], values: &[u8]) -> Self {
+        let mut table = Huffman {
+            max_code: [-1; 17],
+            min_code: [0; 17],
+            val_ptr: [0; 17],
+            values: values.to_vec(),
+        };
+        let (mut code, mut k) = (0i32, 0usize);
+        for len in 1..=16 {
+            let count = counts[len - 1] as i32;
+            table.val_ptr[len] = k;
+            table.min_code[len] = code;
+            code += count;
+            k += count as usize;
+            if count > 0 {
+                table.max_code[len] = code - 1;
+            }
+            code <<= 1;
+        }
+        table
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Option<u8> {
+        let mut code = bits.bit() as i32;
+        for len in 1..=16 {
+            if code <= self.max_code[len] {
+                let idx = self.val_ptr[len] + (code - self.min_code[len]) as usize;
+                return self.values.get(idx).copied();
+            }
+            code = (code << 1) | bits.bit() as i32;
+        }
+        None
+    }
+}
+
+/// Entropy-coded data reader; stops at the next marker, padding with zeros
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    acc: u32,
+    count: u32,
+    padded: usize,
+}
+
+impl Bits<'_> {
+    fn bit(&mut self) -> u32 {
+        if self.count == 0 {
+            self.acc = self.next_byte();
+            self.count = 8;
+        }
+        self.count -= 1;
+        (self.acc >> self.count) & 1
+    }
+
+    fn next_byte(&mut self) -> u32 {
+        match (self.data.get(self.pos), self.data.get(self.pos + 1)) {
+            (Some(0xFF), Some(0x00)) => {
+                self.pos += 2;
+                0xFF
+            }
+            (Some(0xFF), _) | (None, _) => {
+                self.padded += 1;
+                0
+            }
+            (Some(&byte), _) => {
+                self.pos += 1;
+                byte as u32
+            }
+        }
+    }
+
+    fn receive(&mut self, n: u8) -> i32 {
+        (0..n).fold(0, |v, _| (v << 1) | self.bit() as i32)
+    }
+
+    /// Value of an `n`-bit magnitude category (JPEG spec F.2.2.1)
+    fn receive_extend(&mut self, n: u8) -> i32 {
+        if n == 0 {
+            return 0;
+        }
+        let v = self.receive(n);
+        if v < 1 << (n - 1) {
+            v - (1 << n) + 1
+        } else {
+            v
+        }
+    }
+
+    /// Drop buffered bits and skip past the next restart marker
+    fn restart(&mut self) {
+        self.count = 0;
+        while self.pos + 1 < self.data.len() {
+            let (a, b) = (self.data[self.pos], self.data[self.pos + 1]);
+            self.pos += 1;
+            if a == 0xFF && (0xD0..=0xD7).contains(&b) {
+                self.pos += 1;
+                return;
+            }
+        }
+    }
+}
+
+fn jpeg_hash(data: &[u8]) -> Option<u64> {
+    let mut frame = None;
+    let mut dc_tables: [Option<Huffman>; 4] = Default::default();
+    let mut ac_tables: [Option<Huffman>; 4] = Default::default();
+    let mut restart_interval = 0;
+    let mut pos = 2;
+    loop {
+        // Skip entropy-coded data and fill bytes up to the next marker
+        while *data.get(pos)? != 0xFF || matches!(data.get(pos + 1)?, 0x00 | 0xFF | 0xD0..=0xD7) {
+            pos += 1;
+        }
+        let marker = data[pos + 1];
+        pos += 2;
+        if marker == 0xD9 {
+            return None;
+        }
+        let len = be16(data, pos)?;
+        let segment = data.get(pos + 2..pos + len.max(2))?;
+        pos += len;
+        match marker {
+            0xC0..=0xC2 => frame = Some(parse_frame(segment, marker == 0xC2)?),
+            // Lossless, hierarchical and arithmetic-coded files
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => return None,
+            0xC4 => {
+                let mut i = 0;
+                while i < segment.len() {
+                    let (class, id) = (segment[i] >> 4, (segment[i] & 0x0F) as usize);
+                    let counts = segment.get(i + 1..i + 17)?;
+                    let total: usize = counts.iter().map(|&c| c as usize).sum();
+                    let values = segment.get(i + 17..i + 17 + total)?;
+                    let table = Some(Huffman::new(counts, values));
+                    match class {
+                        0 => *dc_tables.get_mut(id)? = table,
+                        _ => *ac_tables.get_mut(id)? = table,
+                    }
+                    i += 17 + total;
+                }
+            }
+            0xDD => restart_interval = be16(segment, 0)?,
+            0xDA => {
+                let frame = frame.as_ref()?;
+                if let Some(hash) = decode_scan(
+                    frame,
+                    segment,
+                    &dc_tables,
+                    &ac_tables,
+                    restart_interval,
+                    &data[pos..],
+                ) {
+                    return Some(hash);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn parse_frame(segment: &[u8], progressive: bool) -> Option<Frame> {
+    if *segment.first()? != 8 {
+        return None;
+    }
+    let (height, width) = (be16(segment, 1)?, be16(segment, 3)?);
+    let count = *segment.get(5)? as usize;
+    let components = (0..count)
+        .map(|i| {
+            let sampling = *segment.get(7 + i * 3)?;
+            let (h, v) = ((sampling >> 4) as usize, (sampling & 0x0F) as usize);
+            ((1..=4).contains(&h) && (1..=4).contains(&v)).then_some(Component {
+                id: segment[6 + i * 3],
+                h,
+                v,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (height > 0 && !components.is_empty()).then_some(Frame {
+        width,
+        height,
+        progressive,
+        components,
+    })
+}
+
+/// Hash the luminance DC coefficients of a scan. None if the scan doesn't
+/// have them or can't be decoded; the caller moves on to the next scan.
+fn decode_scan(
+    frame: &Frame,
+    header: &[u8],
+    dc_tables: &[Option<Huffman>; 4],
+    ac_tables: &[Option<Huffman>; 4],
+    restart_interval: usize,
+    data: &[u8],
+) -> Option<u64> {
+    let count = *header.first()? as usize;
+    // (frame component, DC table, AC table)
+    let mut scan = Vec::new();
+    for i in 0..count {
+        let id = *header.get(1 + i * 2)?;
+        let tables = *header.get(2 + i * 2)?;
+        let component = frame.components.iter().position(|c| c.id == id)?;
+        scan.push((component, (tables >> 4) as usize, (tables & 0x0F) as usize));
+    }
+    let spectral_start = *header.get(1 + count * 2)?;
+    let approximation = *header.get(3 + count * 2)?;
+    // Only the first DC pass of a progressive file holds the DC values
+    let has_dc = spectral_start == 0 && (!frame.progressive || approximation >> 4 == 0);
+    if !has_dc || !scan.iter().any(|s| s.0 == 0) {
+        return None;
+    }
+    let shift = if frame.progressive {
+        approximation & 0x0F
+    } else {
+        0
+    };
+
+    let h_max = frame.components.iter().map(|c| c.h).max()?;
+    let v_max = frame.components.iter().map(|c| c.v).max()?;
+    let luma = &frame.components[0];
+    let blocks_w = (frame.width * luma.h).div_ceil(h_max).div_ceil(8);
+    let blocks_h = (frame.height * luma.v).div_ceil(v_max).div_ceil(8);
+    let mut grid = Grid::new(blocks_w, blocks_h)?;
+
+    // Block positions (in its component's block grid) of each MCU, in order
+    let (mcus_x, mcus_y, blocks): (usize, usize, Vec<(usize, usize, usize)>) = if scan.len() == 1 {
+        (blocks_w, blocks_h, vec![(0, 0, 0)])
+    } else {
+        let blocks = scan
+            .iter()
+            .enumerate()
+            .flat_map(|(s, &(c, _, _))| {
+                let comp = &frame.components[c];
+                (0..comp.v).flat_map(move |v| (0..comp.h).map(move |h| (s, h, v)))
+            })
+            .collect();
+        (
+            frame.width.div_ceil(8 * h_max),
+            frame.height.div_ceil(8 * v_max),
+            blocks,
+        )
+    };
+    let dc = scan
+        .iter()
+        .map(|s| dc_tables[s.1].as_ref())
+        .collect::<Option<Vec<_>>>()?;
+    let ac = if frame.progressive {
+        Vec::new()
+    } else {
+        scan.iter()
+            .map(|s| ac_tables[s.2].as_ref())
+            .collect::<Option<Vec<_>>>()?
+    };
+
+    let mut bits = Bits {
+        data,
+        pos: 0,
+        acc: 0,
+        count: 0,
+        padded: 0,
+    };
+    let mut predictions = vec![0i32; scan.len()];
+    for mcu in 0..mcus_x * mcus_y {
+        if restart_interval > 0 && mcu > 0 && mcu % restart_interval == 0 {
+            bits.restart();
+            predictions.iter_mut().for_each(|p| *p = 0);
+        }
+        let (mx, my) = (mcu % mcus_x, mcu / mcus_x);
+        for &(s, h, v) in &blocks {
+            let size = dc[s].decode(&mut bits)?;
+            predictions[s] += bits.receive_extend(size.min(16));
+            if !frame.progressive {
+                // Skip the AC coefficients
+                let mut k = 1;
+                while k < 64 {
+                    let rs = ac[s].decode(&mut bits)?;
+                    let (run, size) = (rs >> 4, rs & 0x0F);
+                    if size == 0 && run != 15 {
+                        break;
+                    }
+                    bits.receive(size);
+                    k += run as usize + 1;
+                }
+            }
+            if scan[s].0 == 0 {
+                let (x, y) = if blocks.len() == 1 {
+                    (mx, my)
+                } else {
+                    (mx * luma.h + h, my * luma.v + v)
+                };
+                grid.add(x, y, (predictions[s] << shift) as f64);
+            }
+        }
+        // Ran into the end of the data: the file is truncated
+        if bits.padded > 64 {
+            return None;
+        }
+    }
+    Some(grid.hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Grayscale PNG of a diagonal gradient with a bright square, at any size
+    fn write_png(path: &Path, width: u32, height: u32, inverted: bool) {
+        let mut raw = Vec::new();
+        for y in 0..height {
+            raw.push(0);
+            for x in 0..width {
+                let (fx, fy) = (x as f64 / width as f64, y as f64 / height as f64);
+                let mut value = (fx * 0.6 + fy * 0.4) * 200.0;
+                if (0.55..0.8).contains(&fx) && (0.2..0.5).contains(&fy) {
+                    value = 250.0;
+                }
+                if inverted {
+                    value = 255.0 - value;
+                }
+                raw.push(value as u8);
+            }
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&raw).unwrap();
+        let idat = encoder.finish().unwrap();
+
+        let mut png = PNG_SIGNATURE.to_vec();
+        let mut chunk = |kind: &[u8], body: &[u8]| {
+            png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            png.extend_from_slice(kind);
+            png.extend_from_slice(body);
+            png.extend_from_slice(&[0; 4]); // CRC isn't checked
+        };
+        let mut header = Vec::new();
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[8, 0, 0, 0, 0]);
+        chunk(b"IHDR", &header);
+        chunk(b"IDAT", &idat);
+        chunk(b"IEND", &[]);
+        std::fs::write(path, png).unwrap();
+    }
+
+    #[test]
+    fn resized_copies_are_grouped_as_similar() {
+        let dir = TempDir::new().unwrap();
+        let large = dir.path().join("photo.png");
+        let small = dir.path().join("photo-small.png");
+        let other = dir.path().join("other.png");
+        write_png(&large, 640, 480, false);
+        write_png(&small, 160, 120, false);
+        write_png(&other, 640, 480, true);
+
+        let (a, b, c) = (
+            image_hash(&large).unwrap(),
+            image_hash(&small).unwrap(),
+            image_hash(&other).unwrap(),
+        );
+        assert!(distance(a, b) <= 2, "{:064b}\n{:064b}", a, b);
+        assert!(distance(a, c) > 32);
+        assert_eq!(image_hash(&dir.path().join("missing.png")), None);
+
+        let size = |p: &Path| std::fs::metadata(p).unwrap().len();
+        let images = vec![
+            (small.clone(), size(&small)),
+            (other.clone(), size(&other)),
+            (large.clone(), size(&large)),
+        ];
+        let groups = find_similar(images.clone(), 10, &[], |_| {});
+        assert_eq!(groups.len(), 1);
+        let paths: Vec<&PathBuf> = groups[0].images.iter().map(|(p, _)| p).collect();
+        assert_eq!(paths, vec![&large, &small]);
+
+        // Already reported as exact duplicates
+        let exact = DuplicateGroup {
+            hash: String::new(),
+            size: 0,
+            paths: vec![large, small],
+        };
+        assert!(find_similar(images, 10, &[exact], |_| {}).is_empty());
+    }
+}
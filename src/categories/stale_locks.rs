@@ -0,0 +1,302 @@
+//! Stale lock and orphaned temp artifact detection.
+//!
+//! Editors and package managers leave small lock/temp artifacts behind when they
+//! crash or are killed. These are reported as a "stale locks" sub-category of
+//! Temp Files. Defaults are deliberately conservative: only exact, well-known
+//! patterns are matched and only once they are older than the configured age
+//! (7 days by default), so a file that is still open is never a candidate.
+
+use crate::config::Config;
+use crate::scan_events::ScanPathReporter;
+use crate::utils::{self, SizeWalk};
+use chrono::{DateTime, Duration, Utc};
+use std::env;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Maximum directory depth for user folder walks
+const MAX_DEPTH: usize = 8;
+
+/// Kind of stale artifact that was detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleKind {
+    /// Vim swap files (`.name.swp`, `.name.swo`)
+    VimSwap,
+    /// Microsoft Office owner/lock files (`~$name.docx`)
+    OfficeLock,
+    /// Emacs lock links (`.#name`)
+    EmacsLock,
+    /// npm's leftover install staging directories (`node_modules/.staging`)
+    NpmStaging,
+    /// pip build/unpack temp directories (`pip-build-*`, `pip-req-build-*`, ...)
+    PipTemp,
+}
+
+impl StaleKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StaleKind::VimSwap => "Vim swap file",
+            StaleKind::OfficeLock => "Office lock file",
+            StaleKind::EmacsLock => "Emacs lock file",
+            StaleKind::NpmStaging => "npm staging dir",
+            StaleKind::PipTemp => "pip build temp",
+        }
+    }
+}
+
+/// pip temp directory prefixes (created under %TEMP% and normally removed on exit)
+const PIP_TEMP_PREFIXES: &[&str] = &[
+    "pip-build-",
+    "pip-install-",
+    "pip-req-build-",
+    "pip-unpack-",
+    "pip-wheel-",
+    "pip-ephem-wheel-cache-",
+    "pip-modern-metadata-",
+    "pip-record-",
+];
+
+/// Classify a path as a stale artifact candidate based on its name alone.
+///
+/// Age is checked separately; this only answers "does it look like one".
+pub fn classify(path: &Path, is_dir: bool) -> Option<StaleKind> {
+    let name = path.file_name()?.to_str()?;
+
+    if is_dir {
+        if name == ".staging"
+            && path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|p| p.eq_ignore_ascii_case("node_modules"))
+                .unwrap_or(false)
+        {
+            return Some(StaleKind::NpmStaging);
+        }
+        if PIP_TEMP_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            return Some(StaleKind::PipTemp);
+        }
+        return None;
+    }
+
+    let lower = name.to_lowercase();
+    if name.starts_with("~$") && name.len() > 2 {
+        return Some(StaleKind::OfficeLock);
+    }
+    if name.starts_with(".#") && name.len() > 2 {
+        return Some(StaleKind::EmacsLock);
+    }
+    // Vim names swap files ".<file>.swp", falling back to .swo, .swn, ... on collision.
+    // Only the first two are matched so unrelated *.sw? files are left alone.
+    if name.starts_with('.') && (lower.ends_with(".swp") || lower.ends_with(".swo")) {
+        return Some(StaleKind::VimSwap);
+    }
+
+    None
+}
+
+/// Default roots to look for stale artifacts: temp dirs plus the user's document folders
+pub fn default_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Ok(temp_dir) = env::var("TEMP") {
+        roots.push(PathBuf::from(temp_dir));
+    }
+    if let Ok(local_appdata) = env::var("LOCALAPPDATA") {
+        roots.push(PathBuf::from(local_appdata).join("Temp"));
+    }
+    if let Ok(user_profile) = env::var("USERPROFILE") {
+        let profile = PathBuf::from(user_profile);
+        roots.push(profile.join("Documents"));
+        roots.push(profile.join("Desktop"));
+        roots.push(profile.join("Downloads"));
+    }
+
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// Find stale lock/temp artifacts under `roots`.
+///
+/// Returns `(path, size)` pairs; directories are reported as a single entry.
+/// Does nothing if the sub-category is disabled in config.
+pub fn find(
+    roots: &[PathBuf],
+    config: &Config,
+    reporter: Option<&ScanPathReporter>,
+    walk: &SizeWalk,
+) -> Vec<(PathBuf, u64)> {
+    let settings = &config.categories.stale_locks;
+    if !settings.enabled {
+        return Vec::new();
+    }
+
+    let cutoff = Utc::now() - Duration::days(settings.min_age_days as i64);
+    let mut found = Vec::new();
+
+    for root in roots {
+        if root.exists() {
+            find_in(root, &cutoff, config, reporter, walk, &mut found);
+        }
+    }
+
+    found
+}
+
+fn find_in(
+    root: &Path,
+    cutoff: &DateTime<Utc>,
+    config: &Config,
+    reporter: Option<&ScanPathReporter>,
+    walk: &SizeWalk,
+    found: &mut Vec<(PathBuf, u64)>,
+) {
+    let mut walker = WalkDir::new(root)
+        .max_depth(MAX_DEPTH)
+        .follow_links(false)
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                crate::elevation::note_walkdir_error(&e);
+                continue;
+            }
+        };
+        let path = entry.path();
+        let is_dir = entry.file_type().is_dir();
+
+        if is_dir && entry.depth() > 0 {
+            if config.is_excluded(path) || utils::should_skip_entry(path) {
+                walker.skip_current_dir();
+                continue;
+            }
+            // Don't descend into package trees; `.staging` is the only thing we want in there
+            if path
+                .file_name()
+                .map(|n| n.eq_ignore_ascii_case("node_modules"))
+                .unwrap_or(false)
+            {
+                walker.skip_current_dir();
+                let staging = path.join(".staging");
+                if staging.is_dir() && is_older_than(&staging, cutoff) {
+                    found.push((
+                        staging.clone(),
+                        utils::calculate_dir_size_in(&staging, walk),
+                    ));
+                }
+                continue;
+            }
+        }
+
+        if let Some(reporter) = reporter {
+            reporter.emit_path(path);
+        }
+
+        if entry.depth() == 0 {
+            continue;
+        }
+
+        // Emacs lock links are dangling symlinks on some setups; classify by name
+        let is_symlink = entry.file_type().is_symlink();
+        if let Some(kind) = classify(path, is_dir) {
+            if !is_older_than(path, cutoff) {
+                continue;
+            }
+            let size = if is_dir {
+                walker.skip_current_dir();
+                utils::calculate_dir_size_in(path, walk)
+            } else if is_symlink {
+                0
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+            if kind == StaleKind::OfficeLock && !is_symlink && size > OFFICE_LOCK_MAX_BYTES {
+                // Real owner files are tiny; anything bigger isn't one
+                continue;
+            }
+            found.push((path.to_path_buf(), size));
+        }
+    }
+}
+
+/// Office owner files are a fixed 162 bytes; leave headroom but reject real documents
+const OFFICE_LOCK_MAX_BYTES: u64 = 4096;
+
+fn is_older_than(path: &Path, cutoff: &DateTime<Utc>) -> bool {
+    match std::fs::symlink_metadata(path).and_then(|m| m.modified()) {
+        Ok(modified) => {
+            let modified: DateTime<Utc> = modified.into();
+            modified < *cutoff
+        }
+        // Unknown age: be safe and keep it
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn classifies_known_patterns() {
+        assert_eq!(
+            classify(Path::new("/d/.notes.txt.swp"), false),
+            Some(StaleKind::VimSwap)
+        );
+        assert_eq!(
+            classify(Path::new("/d/~$report.docx"), false),
+            Some(StaleKind::OfficeLock)
+        );
+        assert_eq!(
+            classify(Path::new("/d/.#init.el"), false),
+            Some(StaleKind::EmacsLock)
+        );
+        assert_eq!(
+            classify(Path::new("/p/node_modules/.staging"), true),
+            Some(StaleKind::NpmStaging)
+        );
+        assert_eq!(
+            classify(Path::new("/t/pip-req-build-abc123"), true),
+            Some(StaleKind::PipTemp)
+        );
+    }
+
+    #[test]
+    fn ignores_lookalikes() {
+        assert_eq!(classify(Path::new("/d/backup.swp"), false), None);
+        assert_eq!(classify(Path::new("/d/~$"), false), None);
+        assert_eq!(classify(Path::new("/p/.staging"), true), None);
+        assert_eq!(classify(Path::new("/t/pip-build-x"), false), None);
+        assert_eq!(classify(Path::new("/d/report.docx"), false), None);
+    }
+
+    #[test]
+    fn fresh_artifacts_are_not_reported() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("~$open.docx"), b"owner").unwrap();
+        fs::write(dir.path().join(".draft.md.swp"), b"swap").unwrap();
+
+        let config = Config::default();
+        let found = find(
+            &[dir.path().to_path_buf()],
+            &config,
+            None,
+            &SizeWalk::default(),
+        );
+        assert!(found.is_empty());
+
+        let mut config = Config::default();
+        config.categories.stale_locks.enabled = false;
+        assert!(find(
+            &[dir.path().to_path_buf()],
+            &config,
+            None,
+            &SizeWalk::default()
+        )
+        .is_empty());
+    }
+}
@@ -1,6 +1,7 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
 use crate::config::Config;
 use crate::output::CategoryResult;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
 use std::env;
 use std::path::{Path, PathBuf};
@@ -10,7 +11,9 @@ use std::path::{Path, PathBuf};
 /// Includes:
 /// - Thumbnail cache (thumbcache_*.db)
 /// - Icon cache
-pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
+/// - Orphaned Hyper-V checkpoints and Windows Sandbox leftovers, as
+///   machine-wide items (see `vm_leftovers`)
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
     let mut paths = Vec::new();
 
@@ -67,7 +70,7 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
         .into_iter()
         .map(|p| {
             let size = if p.is_dir() {
-                utils::calculate_dir_size(&p)
+                utils::calculate_dir_size_in(&p, walk)
             } else {
                 std::fs::metadata(&p).map(|m| m.len()).unwrap_or(0)
             };
@@ -78,9 +81,26 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
 
     result.paths = paths_with_sizes.into_iter().map(|(p, _)| p).collect();
 
+    // Hyper-V and Sandbox leftovers are machine-wide: kept out of the totals
+    // and only cleaned elevated
+    let leftovers = super::vm_leftovers::find(config, walk);
+    result.machine_bytes = leftovers.iter().map(|(_, size)| size).sum();
+    result.machine_paths = leftovers.into_iter().map(|(path, _)| path).collect();
+
     Ok(result)
 }
 
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(root, &options.config, &SizeWalk::default())?;
+    Ok(CategoryScan::from_result(
+        "system",
+        Safety::Safe,
+        result,
+        |_| "Windows system cache".to_string(),
+    ))
+}
+
 /// Clean (delete) a system cache file/directory by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     // CRITICAL SAFETY CHECK: Never allow deletion of system paths
@@ -1,6 +1,9 @@
+use super::typed::{BasicScanOptions, CategoryScan, Safety};
+use crate::categories::stale_locks;
 use crate::config::Config;
 use crate::output::CategoryResult;
 use crate::scan_events::{ScanPathReporter, ScanProgressEvent};
+use crate::utils::SizeWalk;
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
 use std::env;
@@ -13,13 +16,14 @@ const MAX_RESULTS: usize = 500;
 
 /// Scan for temporary files older than 1 day
 ///
-/// Checks %TEMP% and %LOCALAPPDATA%\Temp directories
+/// Checks %TEMP% and %LOCALAPPDATA%\Temp directories, plus stale editor and
+/// package manager lock artifacts (see `stale_locks`)
 /// Optimizations:
 /// - Limits depth to 3 levels (deep temp files are usually system files)
 /// - Checks config exclusions during traversal (prevents walking excluded trees)
 /// - Sorts by size descending
 /// - Limits to top 500 results
-pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
 
     let cutoff = Utc::now() - Duration::days(1);
@@ -46,6 +50,10 @@ pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
         }
     }
 
+    // Stale locks sub-category
+    let stale = stale_locks::find(&stale_locks::default_roots(), config, None, walk);
+    merge_stale(&mut files_with_sizes, stale);
+
     // Sort by size descending
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
 
@@ -67,6 +75,7 @@ pub fn scan_with_progress(
     _root: &Path,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     const CATEGORY: &str = "Temp Files";
     let cutoff = Utc::now() - Duration::days(1);
@@ -123,6 +132,10 @@ pub fn scan_with_progress(
         });
     }
 
+    // Stale locks sub-category
+    let stale = stale_locks::find(&stale_locks::default_roots(), config, Some(&reporter), walk);
+    merge_stale(&mut files_with_sizes, stale);
+
     // Sort by size descending
     files_with_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
     files_with_sizes.truncate(MAX_RESULTS);
@@ -142,6 +155,22 @@ pub fn scan_with_progress(
     Ok(result)
 }
 
+/// Merge stale lock artifacts into the temp file list.
+///
+/// Stale directories replace any temp files found inside them, and artifacts
+/// that the temp walk already picked up are not counted twice.
+fn merge_stale(files: &mut Vec<(PathBuf, u64)>, stale: Vec<(PathBuf, u64)>) {
+    if stale.is_empty() {
+        return;
+    }
+    files.retain(|(path, _)| {
+        !stale
+            .iter()
+            .any(|(stale_path, _)| path.starts_with(stale_path))
+    });
+    files.extend(stale);
+}
+
 fn scan_temp_dir(
     temp_path: &Path,
     cutoff: &chrono::DateTime<Utc>,
@@ -164,7 +193,10 @@ fn scan_temp_dir(
     {
         let entry = match entry {
             Ok(e) => e,
-            Err(_) => continue,
+            Err(e) => {
+                crate::elevation::note_walkdir_error(&e);
+                continue;
+            }
         };
 
         if let Some(reporter) = reporter {
@@ -185,9 +217,44 @@ fn scan_temp_dir(
     }
 }
 
+/// Scan this category on its own with typed results
+pub fn scan_with_options(root: &Path, options: &BasicScanOptions) -> Result<CategoryScan> {
+    let result = scan(root, &options.config, &SizeWalk::default())?;
+    Ok(CategoryScan::from_result(
+        "temp",
+        Safety::Safe,
+        result,
+        |_| "temporary file".to_string(),
+    ))
+}
+
 /// Clean (delete) a temp file by moving it to the Recycle Bin
 pub fn clean(path: &Path) -> Result<()> {
     crate::trash_ops::delete(path)
         .with_context(|| format!("Failed to delete temp file: {}", path.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_stale_replaces_contained_files() {
+        let mut files = vec![
+            (PathBuf::from("/t/pip-build-1/setup.py"), 10),
+            (PathBuf::from("/t/~$a.docx"), 162),
+            (PathBuf::from("/t/old.log"), 5),
+        ];
+        let stale = vec![
+            (PathBuf::from("/t/pip-build-1"), 100),
+            (PathBuf::from("/t/~$a.docx"), 162),
+        ];
+        merge_stale(&mut files, stale);
+
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&(PathBuf::from("/t/old.log"), 5)));
+        assert!(files.contains(&(PathBuf::from("/t/pip-build-1"), 100)));
+        assert!(files.contains(&(PathBuf::from("/t/~$a.docx"), 162)));
+    }
+}
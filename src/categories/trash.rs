@@ -1,24 +1,153 @@
 use crate::output::CategoryResult;
 use crate::trash_ops;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
 
-/// Scan the Recycle Bin for items
-///
-/// Note: Size calculation is skipped as it would require reading each file,
-/// which is expensive. Only item count is tracked.
-pub fn scan() -> Result<CategoryResult> {
-    let mut result = CategoryResult::default();
+/// Age buckets of the Recycle Bin aging report: upper bound (days) and label
+pub const AGE_BUCKETS: [(u64, &str); 4] = [
+    (7, "under a week"),
+    (30, "1-4 weeks"),
+    (90, "1-3 months"),
+    (u64::MAX, "over 3 months"),
+];
 
-    match trash_ops::list() {
-        Ok(items) => {
-            result.items = items.len();
-            // TrashItem doesn't expose size, so we just count items
-            // Size would require reading each file which is expensive
-            result.size_bytes = 0;
-            result.paths = items
+/// How long the items in the Recycle Bin have been there
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrashAging {
+    /// Days each item has been in the bin
+    pub ages_days: Vec<u64>,
+}
+
+impl TrashAging {
+    /// Ages of items deleted at `deleted` (Unix seconds), as of `now`
+    pub fn of(deleted: impl IntoIterator<Item = i64>, now: i64) -> Self {
+        Self {
+            ages_days: deleted.into_iter().map(|t| age_days(t, now)).collect(),
+        }
+    }
+
+    /// Items per [`AGE_BUCKETS`] entry
+    pub fn buckets(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for age in &self.ages_days {
+            let bucket = AGE_BUCKETS
                 .iter()
-                .map(|i| i.original_parent.join(&i.name))
-                .collect();
+                .position(|(max, _)| age < max)
+                .unwrap_or(AGE_BUCKETS.len() - 1);
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    /// Items that have been in the bin for at least `days`
+    pub fn older_than(&self, days: u64) -> usize {
+        self.ages_days.iter().filter(|age| **age >= days).count()
+    }
+}
+
+fn age_days(time_deleted: i64, now: i64) -> u64 {
+    (now - time_deleted).max(0) as u64 / 86_400
+}
+
+/// Items in the bin for at least `min_age_days` (all of them for 0)
+fn items_older_than(items: Vec<trash::TrashItem>, min_age_days: u64) -> Vec<trash::TrashItem> {
+    if min_age_days == 0 {
+        return items;
+    }
+    let now = chrono::Utc::now().timestamp();
+    items
+        .into_iter()
+        .filter(|item| age_days(item.time_deleted, now) >= min_age_days)
+        .collect()
+}
+
+/// One item in the Recycle Bin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    pub name: String,
+    /// Where the item was deleted from
+    pub original: PathBuf,
+    pub deleted: DateTime<Utc>,
+    pub size_bytes: u64,
+}
+
+impl TrashEntry {
+    fn of(item: &trash::TrashItem, walk: &SizeWalk) -> Self {
+        Self {
+            name: item.name.to_string_lossy().into_owned(),
+            original: item.original_parent.join(&item.name),
+            deleted: DateTime::from_timestamp(item.time_deleted, 0).unwrap_or_default(),
+            size_bytes: item_size(item, walk),
+        }
+    }
+
+    pub fn age_days(&self, now: DateTime<Utc>) -> u64 {
+        age_days(self.deleted.timestamp(), now.timestamp())
+    }
+}
+
+/// Bytes an item takes in the bin. The shell only reports entry counts for
+/// folders, so those are walked where they're stored.
+fn item_size(item: &trash::TrashItem, walk: &SizeWalk) -> u64 {
+    match trash_ops::metadata(item).map(|m| m.size) {
+        Ok(trash::TrashItemSize::Bytes(bytes)) => bytes,
+        _ => stored_path(item)
+            .map(|path| utils::calculate_dir_size_in(&path, walk))
+            .unwrap_or(0),
+    }
+}
+
+/// Where the bin keeps an item: its `$R` file on Windows
+#[cfg(windows)]
+fn stored_path(item: &trash::TrashItem) -> Option<PathBuf> {
+    Some(PathBuf::from(&item.id))
+}
+
+/// Where the bin keeps an item: `files/<name>` next to the `info/<name>.trashinfo`
+/// the id points at (freedesktop trash)
+#[cfg(not(windows))]
+fn stored_path(item: &trash::TrashItem) -> Option<PathBuf> {
+    let info = std::path::Path::new(&item.id);
+    let name = info.file_stem()?;
+    Some(info.parent()?.parent()?.join("files").join(name))
+}
+
+/// Items deleted at least `min_age_days` ago (all of them for 0), largest first
+pub fn entries(min_age_days: u64) -> Result<Vec<TrashEntry>> {
+    entries_in(min_age_days, &SizeWalk::default())
+}
+
+fn entries_in(min_age_days: u64, walk: &SizeWalk) -> Result<Vec<TrashEntry>> {
+    let items = items_older_than(trash_ops::list()?, min_age_days);
+    let mut entries: Vec<TrashEntry> = items
+        .iter()
+        .map(|item| TrashEntry::of(item, walk))
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.size_bytes));
+    Ok(entries)
+}
+
+/// How long each item has been in the Recycle Bin
+pub fn aging() -> Result<TrashAging> {
+    let items = trash_ops::list()?;
+    Ok(TrashAging::of(
+        items.iter().map(|item| item.time_deleted),
+        chrono::Utc::now().timestamp(),
+    ))
+}
+
+/// Scan the Recycle Bin for items deleted at least `min_age_days` ago (all
+/// of them for 0), largest first
+pub fn scan(min_age_days: u64, walk: &SizeWalk) -> Result<CategoryResult> {
+    let mut result = CategoryResult::default();
+
+    match entries_in(min_age_days, walk) {
+        Ok(entries) => {
+            result.items = entries.len();
+            result.size_bytes = entries.iter().map(|e| e.size_bytes).sum();
+            result.paths = entries.into_iter().map(|e| e.original).collect();
         }
         Err(e) => {
             eprintln!("Warning: Could not read Recycle Bin: {}", e);
@@ -28,13 +157,164 @@ pub fn scan() -> Result<CategoryResult> {
     Ok(result)
 }
 
+/// Number of items purged per batch when emptying the Recycle Bin
+const PURGE_BATCH_SIZE: usize = 250;
+
+/// Outcome of emptying the Recycle Bin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmptyOutcome {
+    /// Items purged so far
+    pub purged: usize,
+    /// Items found when the bin was enumerated (0 if it couldn't be enumerated)
+    pub total: usize,
+    /// True if the progress callback asked to stop before the bin was empty
+    pub cancelled: bool,
+}
+
 /// Empty the Recycle Bin by purging all items
 pub fn clean() -> Result<()> {
-    let items = trash_ops::list().context("Failed to list Recycle Bin items")?;
+    clean_with_progress(0, |_, _| true).map(|_| ())
+}
 
-    if !items.is_empty() {
-        trash_ops::purge_all(&items).context("Failed to empty Recycle Bin")?;
+/// Empty the Recycle Bin in batches, reporting progress between batches.
+/// Only items deleted at least `min_age_days` ago are purged (all for 0).
+///
+/// `on_progress(purged, total)` is called once before the first batch and after
+/// every batch; returning `false` stops before the next batch. Items already
+/// purged stay purged.
+///
+/// If the bin can't be enumerated, falls back to emptying it in one shell call
+/// (no progress, can't be cancelled) when everything is to go.
+pub fn clean_with_progress<F>(min_age_days: u64, mut on_progress: F) -> Result<EmptyOutcome>
+where
+    F: FnMut(usize, usize) -> bool,
+{
+    let items = match trash_ops::list() {
+        Ok(items) => items_older_than(items, min_age_days),
+        Err(list_err) if min_age_days > 0 => {
+            return Err(list_err).context("Failed to list the Recycle Bin");
+        }
+        Err(list_err) => {
+            empty_recycle_bin_shell().with_context(|| {
+                format!(
+                    "Failed to empty Recycle Bin (could not list items: {})",
+                    list_err
+                )
+            })?;
+            return Ok(EmptyOutcome::default());
+        }
+    };
+
+    let mut outcome = EmptyOutcome {
+        purged: 0,
+        total: items.len(),
+        cancelled: false,
+    };
+
+    if !on_progress(0, outcome.total) {
+        outcome.cancelled = !items.is_empty();
+        return Ok(outcome);
     }
 
-    Ok(())
+    for batch in items.chunks(PURGE_BATCH_SIZE) {
+        trash_ops::purge_all(batch).with_context(|| {
+            format!(
+                "Failed to empty Recycle Bin ({} of {} items purged)",
+                outcome.purged, outcome.total
+            )
+        })?;
+        outcome.purged += batch.len();
+
+        if !on_progress(outcome.purged, outcome.total) && outcome.purged < outcome.total {
+            outcome.cancelled = true;
+            break;
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Empty the Recycle Bin on all drives via SHEmptyRecycleBinW
+#[cfg(windows)]
+fn empty_recycle_bin_shell() -> Result<()> {
+    const SHERB_NOCONFIRMATION: u32 = 0x0000_0001;
+    const SHERB_NOPROGRESSUI: u32 = 0x0000_0002;
+    const SHERB_NOSOUND: u32 = 0x0000_0004;
+    // Returned when the bin is already empty
+    const E_UNEXPECTED: i32 = 0x8000_FFFFu32 as i32;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn SHEmptyRecycleBinW(hwnd: isize, root_path: *const u16, flags: u32) -> i32;
+    }
+
+    let hr = unsafe {
+        SHEmptyRecycleBinW(
+            0,
+            std::ptr::null(),
+            SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND,
+        )
+    };
+
+    if hr >= 0 || hr == E_UNEXPECTED {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "SHEmptyRecycleBin failed (HRESULT 0x{:08X})",
+            hr as u32
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+fn empty_recycle_bin_shell() -> Result<()> {
+    Err(anyhow::anyhow!(
+        "Recycle Bin fallback is only available on Windows"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aging_buckets_items_by_days_in_bin() {
+        let now = 1_750_000_000;
+        let day = 86_400;
+        let aging = TrashAging::of(
+            [
+                now,
+                now - 3 * day,
+                now - 10 * day,
+                now - 45 * day,
+                now - 400 * day,
+            ],
+            now,
+        );
+        assert_eq!(aging.buckets(), [2, 1, 1, 1]);
+        assert_eq!(aging.older_than(30), 2);
+        assert_eq!(aging.older_than(0), 5);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn stored_items_sit_next_to_their_info_files() {
+        let item = trash::TrashItem {
+            id: "/home/u/.local/share/Trash/info/report.pdf.trashinfo".into(),
+            name: "report.pdf".into(),
+            original_parent: "/home/u/Documents".into(),
+            time_deleted: 1_750_000_000,
+        };
+        assert_eq!(
+            stored_path(&item),
+            Some(PathBuf::from("/home/u/.local/share/Trash/files/report.pdf"))
+        );
+        let entry = TrashEntry::of(&item, &SizeWalk::default());
+        assert_eq!(
+            entry.original,
+            PathBuf::from("/home/u/Documents/report.pdf")
+        );
+        let now = DateTime::from_timestamp(1_750_000_000 + 3 * 86_400, 0).unwrap();
+        assert_eq!(entry.age_days(now), 3);
+    }
 }
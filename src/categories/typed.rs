@@ -0,0 +1,189 @@
+//! Typed single-category scan results for library users
+//!
+//! Each category module exposes `scan_with_options(root, &Options)` next to its
+//! `scan`, taking a typed options struct instead of the CLI's `ScanOptions` and
+//! returning a [`CategoryScan`]: every item with its size, why it was flagged
+//! and how safe the category is to clean. Output is always quiet.
+
+use crate::config::Config;
+use crate::output::CategoryResult;
+use crate::utils;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// How safe a category is to clean without review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Safety {
+    /// Regenerated automatically (caches, temp files); safe to auto-select
+    Safe,
+    /// User data or hard to undo; review before cleaning
+    Review,
+}
+
+/// One flagged file or folder
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedItem {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// Why the item was flagged
+    pub reason: String,
+    /// For folders, their largest files, largest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub largest_files: Vec<(PathBuf, u64)>,
+}
+
+/// Result of scanning a single category
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryScan {
+    /// Category key as used by CLI flags (e.g. `build`)
+    pub category: &'static str,
+    pub safety: Safety,
+    pub items: Vec<ScannedItem>,
+}
+
+impl CategoryScan {
+    /// Build from an untyped result, sizing each path and attaching `reason(path)`.
+    /// Folders the category already walked keep the size and largest files
+    /// that walk found.
+    pub fn from_result(
+        category: &'static str,
+        safety: Safety,
+        result: CategoryResult,
+        reason: impl Fn(&Path) -> String,
+    ) -> Self {
+        let items = result
+            .paths
+            .into_iter()
+            .map(|path| {
+                let sized = path_size(&path);
+                ScannedItem {
+                    size_bytes: sized.size,
+                    reason: reason(&path),
+                    largest_files: sized.largest_files,
+                    path,
+                }
+            })
+            .collect();
+        Self {
+            category,
+            safety,
+            items,
+        }
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.items.iter().map(|i| i.size_bytes).sum()
+    }
+
+    /// Back to the untyped result used by the scanner and cleaner
+    pub fn to_result(&self) -> CategoryResult {
+        CategoryResult {
+            items: self.items.len(),
+            size_bytes: self.total_bytes(),
+            paths: self.items.iter().map(|i| i.path.clone()).collect(),
+            largest_files: self
+                .items
+                .iter()
+                .filter(|i| !i.largest_files.is_empty())
+                .map(|i| (i.path.clone(), i.largest_files.clone()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Options for categories whose only input is the config (exclusions)
+#[derive(Debug, Clone, Default)]
+pub struct BasicScanOptions {
+    pub config: Config,
+}
+
+/// Whole days since `path` was last modified
+pub(crate) fn age_days(path: &Path) -> Option<u64> {
+    let modified = utils::safe_metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    Some(age.as_secs() / 86_400)
+}
+
+fn path_size(path: &Path) -> utils::SizedDir {
+    match utils::safe_symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => utils::take_sized_dir(path).unwrap_or_else(|| {
+            let size = utils::calculate_dir_size(path);
+            utils::take_sized_dir(path).unwrap_or(utils::SizedDir {
+                size,
+                ..Default::default()
+            })
+        }),
+        Ok(meta) => utils::SizedDir {
+            size: meta.len(),
+            ..Default::default()
+        },
+        Err(_) => utils::SizedDir::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sizes_items_and_round_trips_to_result() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.log");
+        fs::write(&file, b"12345").unwrap();
+        let result = CategoryResult {
+            items: 1,
+            size_bytes: 5,
+            paths: vec![file.clone()],
+            ..Default::default()
+        };
+
+        let scan = CategoryScan::from_result("temp", Safety::Safe, result, |_| {
+            "temporary file".to_string()
+        });
+        assert_eq!(scan.items[0].size_bytes, 5);
+        assert_eq!(scan.items[0].reason, "temporary file");
+        assert_eq!(scan.to_result().paths, vec![file]);
+        assert_eq!(age_days(&scan.items[0].path), Some(0));
+    }
+
+    #[test]
+    fn folders_keep_what_their_size_walk_found() {
+        let dir = TempDir::new().unwrap();
+        let folder = dir.path().join("node_modules");
+        fs::create_dir_all(folder.join("pkg")).unwrap();
+        fs::write(folder.join("pkg/index.js"), vec![0u8; 300]).unwrap();
+        fs::write(folder.join("readme.md"), vec![0u8; 20]).unwrap();
+
+        // The category sized the folder while scanning
+        let size = utils::calculate_dir_size(&folder);
+        let result = CategoryResult {
+            items: 1,
+            size_bytes: size,
+            paths: vec![folder.clone()],
+            ..Default::default()
+        };
+        // Files added after the walk aren't seen: the walk isn't repeated
+        fs::write(folder.join("later.bin"), vec![0u8; 5000]).unwrap();
+
+        let scan = CategoryScan::from_result("build", Safety::Safe, result, |_| {
+            "build output".to_string()
+        });
+        assert_eq!(scan.items[0].size_bytes, 320);
+        assert_eq!(
+            scan.items[0].largest_files,
+            vec![
+                (folder.join("pkg/index.js"), 300),
+                (folder.join("readme.md"), 20)
+            ]
+        );
+        assert_eq!(
+            scan.to_result().largest_files[&folder],
+            scan.items[0].largest_files
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! Hyper-V checkpoint and Windows Sandbox leftovers.
+//!
+//! Deleting a VM or a checkpoint in the wrong order, or a crash while merging,
+//! leaves differencing disks (`.avhdx`) that no VM uses any more, and Windows
+//! Sandbox keeps its base layer and per-run folders under
+//! `%ProgramData%\Microsoft\Windows\Containers`. These are machine-wide, often
+//! tens of GB, and invisible in Explorer. They're reported with the System
+//! Cache category as machine-wide items and only cleaned with administrator
+//! rights.
+//!
+//! A checkpoint counts as orphaned only when its file name appears in no VM
+//! configuration and in no parent locator of a disk that is in use, and it
+//! hasn't been written for `MIN_CHECKPOINT_AGE_DAYS`. When the Hyper-V
+//! configuration folder can't be read (it needs elevation), or a disk in use
+//! can't be read, nothing next to it is reported.
+
+use crate::config::Config;
+use crate::utils::{self, SizeWalk};
+use std::collections::HashSet;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Checkpoints written more recently than this are never reported
+pub const MIN_CHECKPOINT_AGE_DAYS: u64 = 7;
+
+/// How much of a differencing disk to read for its parent locator. The VHDX
+/// metadata region usually starts at 1-2 MB.
+const HEADER_READ_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What a leftover is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// `.avhdx` no VM configuration or disk chain refers to
+    OrphanedCheckpoint,
+    /// Per-run Windows Sandbox folder left after the sandbox closed
+    SandboxInstance,
+    /// Windows Sandbox base layer, kept after the feature was turned off
+    SandboxBaseImage,
+}
+
+impl LeftoverKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeftoverKind::OrphanedCheckpoint => "orphaned Hyper-V checkpoint",
+            LeftoverKind::SandboxInstance => "Windows Sandbox leftover",
+            LeftoverKind::SandboxBaseImage => "unused Windows Sandbox base layer",
+        }
+    }
+}
+
+/// Classify a path reported by `find`
+pub fn kind_of(path: &Path) -> Option<LeftoverKind> {
+    let lower = path.to_string_lossy().to_lowercase().replace('/', "\\");
+    if lower.ends_with(".avhdx") {
+        Some(LeftoverKind::OrphanedCheckpoint)
+    } else if lower.contains("\\containers\\sandboxes\\") {
+        Some(LeftoverKind::SandboxInstance)
+    } else if lower.contains("\\containers\\baseimages\\") {
+        Some(LeftoverKind::SandboxBaseImage)
+    } else {
+        None
+    }
+}
+
+fn program_data() -> Option<PathBuf> {
+    env::var("ProgramData")
+        .or_else(|_| env::var("PROGRAMDATA"))
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// Per-user Hyper-V disks folder under `%PUBLIC%`
+fn public_disk_dir() -> Option<PathBuf> {
+    env::var("PUBLIC").ok().map(|public| {
+        PathBuf::from(public)
+            .join("Documents")
+            .join("Hyper-V")
+            .join("Virtual hard disks")
+    })
+}
+
+/// Folders leftovers are found in
+pub fn roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(program_data) = program_data() {
+        let windows = program_data.join("Microsoft").join("Windows");
+        roots.push(windows.join("Hyper-V"));
+        roots.push(windows.join("Virtual Hard Disks"));
+        roots.push(windows.join("Containers"));
+    }
+    roots.extend(public_disk_dir());
+    roots
+}
+
+/// Find leftovers and their sizes, biggest first
+pub fn find(config: &Config, walk: &SizeWalk) -> Vec<(PathBuf, u64)> {
+    let Some(program_data) = program_data() else {
+        return Vec::new();
+    };
+    let windows = program_data.join("Microsoft").join("Windows");
+
+    let mut found: Vec<PathBuf> = Vec::new();
+
+    // Hyper-V keeps registrations (often symlinks to the real files) here
+    let hyperv = windows.join("Hyper-V");
+    let config_dirs = [hyperv.join("Virtual Machines"), hyperv.join("Snapshots")];
+    let mut disk_dirs = vec![windows.join("Virtual Hard Disks")];
+    disk_dirs.extend(public_disk_dir());
+    found.extend(orphaned_checkpoints(
+        &config_dirs,
+        &disk_dirs,
+        Duration::from_secs(MIN_CHECKPOINT_AGE_DAYS * 86400),
+    ));
+
+    let containers = windows.join("Containers");
+    if !sandbox_running() {
+        found.extend(subdirs(&containers.join("Sandboxes")));
+    }
+    if !sandbox_installed() {
+        found.extend(subdirs(&containers.join("BaseImages")));
+    }
+
+    let mut sized: Vec<(PathBuf, u64)> = found
+        .into_iter()
+        .filter(|path| !config.is_excluded(path))
+        .map(|path| {
+            let size = if path.is_dir() {
+                utils::calculate_dir_size_in(&path, walk)
+            } else {
+                std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            };
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    sized.sort_by_key(|b| std::cmp::Reverse(b.1));
+    sized
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn sandbox_running() -> bool {
+    let mut system = sysinfo::System::new();
+    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    system.processes().values().any(|p| {
+        p.name()
+            .to_string_lossy()
+            .to_lowercase()
+            .starts_with("windowssandbox")
+    })
+}
+
+/// Whether Windows Sandbox is still installed, in which case its base layer is in use
+fn sandbox_installed() -> bool {
+    let Some(root) = env::var_os("SystemRoot").map(PathBuf::from) else {
+        // Can't tell; assume the base layer is needed
+        return true;
+    };
+    let system32 = root.join("System32");
+    ["WindowsSandbox.exe", "WindowsSandboxClient.exe"]
+        .iter()
+        .any(|exe| system32.join(exe).exists())
+}
+
+/// Lowercased virtual disk file names mentioned in `bytes`, whether stored as
+/// UTF-16 (`.vmcx`, VHDX parent locators) or UTF-8 (legacy `.xml` configs)
+pub fn disk_names(bytes: &[u8]) -> HashSet<String> {
+    let mut texts: Vec<String> = vec![String::from_utf8_lossy(bytes).into_owned()];
+    for offset in 0..2 {
+        let units: Vec<u16> = bytes[offset.min(bytes.len())..]
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        texts.push(String::from_utf16_lossy(&units));
+    }
+
+    let mut names = HashSet::new();
+    for text in texts {
+        let lower = text.to_lowercase();
+        for token in lower.split(|c: char| c.is_control() || matches!(c, '"' | '<' | '>' | '|')) {
+            for ext in [".avhdx", ".vhdx"] {
+                let mut rest = token;
+                while let Some(pos) = rest.find(ext) {
+                    let end = pos + ext.len();
+                    let candidate = &rest[..end];
+                    let name = candidate
+                        .rsplit(['\\', '/', ':', '\u{fffd}'])
+                        .next()
+                        .unwrap_or(candidate)
+                        .trim();
+                    if name.len() > ext.len() {
+                        names.insert(name.to_string());
+                    }
+                    rest = &rest[end..];
+                }
+            }
+        }
+    }
+    names
+}
+
+fn read_prefix(path: &Path, limit: u64) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+    let mut buf = Vec::new();
+    std::fs::File::open(path)?
+        .take(limit)
+        .read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// `.avhdx` files under `disk_dirs` (and folders holding referenced disks)
+/// that nothing refers to and that are older than `min_age`
+fn orphaned_checkpoints(
+    config_dirs: &[PathBuf],
+    disk_dirs: &[PathBuf],
+    min_age: Duration,
+) -> Vec<PathBuf> {
+    // Names referenced by VM configurations. Without readable configurations
+    // there's no way to tell what's in use.
+    let mut referenced: HashSet<String> = HashSet::new();
+    let mut search_dirs: Vec<PathBuf> = disk_dirs.to_vec();
+    let mut any_config_dir = false;
+    for dir in config_dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            if dir.exists() {
+                return Vec::new();
+            }
+            continue;
+        };
+        any_config_dir = true;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if ext != "vmcx" && ext != "xml" {
+                continue;
+            }
+            // Registrations are often symlinks; the real files sit with the VM
+            if let Ok(target) = std::fs::canonicalize(&path) {
+                if let Some(parent) = target.parent().and_then(|p| p.parent()) {
+                    search_dirs.push(parent.to_path_buf());
+                }
+            }
+            match std::fs::read(&path) {
+                Ok(bytes) => referenced.extend(disk_names(&bytes)),
+                Err(_) => return Vec::new(),
+            }
+        }
+    }
+    if !any_config_dir {
+        return Vec::new();
+    }
+
+    // All virtual disks in the search folders
+    let mut disks: Vec<PathBuf> = Vec::new();
+    for dir in &search_dirs {
+        for entry in WalkDir::new(dir)
+            .max_depth(3)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if (name.ends_with(".avhdx") || name.ends_with(".vhdx"))
+                && !disks.contains(&entry.path().to_path_buf())
+            {
+                disks.push(entry.path().to_path_buf());
+            }
+        }
+    }
+    let name_of = |p: &Path| {
+        p.file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    // Bytes glued in front of a bare file name end up in the extracted name,
+    // so a suffix match counts as a reference
+    let is_referenced =
+        |referenced: &HashSet<String>, name: &str| referenced.iter().any(|r| r.ends_with(name));
+
+    // Follow parent locators from every disk in use; a disk that can't be read
+    // protects every disk in its folder
+    let mut protected_dirs: HashSet<PathBuf> = HashSet::new();
+    let mut queue: Vec<PathBuf> = disks
+        .iter()
+        .filter(|d| is_referenced(&referenced, &name_of(d)))
+        .cloned()
+        .collect();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    while let Some(disk) = queue.pop() {
+        if !visited.insert(disk.clone()) {
+            continue;
+        }
+        match read_prefix(&disk, HEADER_READ_BYTES) {
+            Ok(bytes) => {
+                for parent in disk_names(&bytes) {
+                    if referenced.insert(parent.clone()) {
+                        queue.extend(
+                            disks
+                                .iter()
+                                .filter(|d| parent.ends_with(&name_of(d)))
+                                .cloned(),
+                        );
+                    }
+                }
+            }
+            Err(_) => {
+                if let Some(dir) = disk.parent() {
+                    protected_dirs.insert(dir.to_path_buf());
+                }
+            }
+        }
+    }
+
+    let now = SystemTime::now();
+    disks
+        .into_iter()
+        .filter(|d| name_of(d).ends_with(".avhdx"))
+        .filter(|d| !is_referenced(&referenced, &name_of(d)))
+        .filter(|d| !d.parent().is_some_and(|p| protected_dirs.contains(p)))
+        .filter(|d| {
+            std::fs::metadata(d)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= min_age)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn only_unreferenced_checkpoints_are_orphaned() {
+        let dir = TempDir::new().unwrap();
+        let configs = dir.path().join("Virtual Machines");
+        let disks = dir.path().join("Virtual Hard Disks");
+        fs::create_dir_all(&configs).unwrap();
+        fs::create_dir_all(&disks).unwrap();
+
+        // The VM runs on the newest checkpoint, whose parent is an older one
+        let mut vmcx = vec![0u8; 16];
+        vmcx.extend(utf16(r"D:\VMs\Dev_7F3A.avhdx"));
+        vmcx.extend([0u8; 8]);
+        fs::write(configs.join("1234.vmcx"), vmcx).unwrap();
+
+        let mut header = vec![0u8; 64];
+        header.extend(utf16(r".\Dev_1B2C.avhdx"));
+        fs::write(disks.join("Dev_7F3A.avhdx"), header).unwrap();
+        fs::write(disks.join("Dev_1B2C.avhdx"), utf16("Dev.vhdx")).unwrap();
+        fs::write(disks.join("Dev.vhdx"), b"base").unwrap();
+        fs::write(disks.join("Old_99AA.avhdx"), b"stale").unwrap();
+
+        let orphaned = orphaned_checkpoints(
+            std::slice::from_ref(&configs),
+            std::slice::from_ref(&disks),
+            Duration::ZERO,
+        );
+        assert_eq!(orphaned, vec![disks.join("Old_99AA.avhdx")]);
+        assert_eq!(
+            kind_of(&orphaned[0]),
+            Some(LeftoverKind::OrphanedCheckpoint)
+        );
+
+        // Recent checkpoints are left alone
+        let recent = orphaned_checkpoints(
+            std::slice::from_ref(&configs),
+            std::slice::from_ref(&disks),
+            Duration::from_secs(3600),
+        );
+        assert!(recent.is_empty());
+
+        // No readable configuration: nothing can be called orphaned
+        let none = orphaned_checkpoints(
+            &[dir.path().join("missing")],
+            std::slice::from_ref(&disks),
+            Duration::ZERO,
+        );
+        assert!(none.is_empty());
+    }
+}
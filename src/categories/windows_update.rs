@@ -1,66 +1,64 @@
 use crate::config::Config;
 use crate::output::CategoryResult;
-use crate::utils;
+use crate::utils::{self, SizeWalk};
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// The Windows folder (`%SystemRoot%`)
+fn windows_dir() -> PathBuf {
+    std::env::var("SystemRoot")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("C:\\Windows"))
+}
+
+/// Folders this category cleans: the Windows Update download cache and logs
+pub fn roots() -> Vec<PathBuf> {
+    let windows_path = windows_dir();
+    vec![
+        windows_path.join("SoftwareDistribution").join("Download"),
+        windows_path.join("Logs").join("WindowsUpdate"),
+    ]
+}
+
 /// Scan for Windows Update files that can be cleaned
 ///
 /// Includes:
 /// - Windows Update download cache (C:\Windows\SoftwareDistribution\Download)
 /// - Windows Update logs (C:\Windows\Logs\WindowsUpdate)
 /// - Component Store (WinSxS) - scan only, requires DISM for cleanup
-pub fn scan(_root: &Path, config: &Config) -> Result<CategoryResult> {
+pub fn scan(_root: &Path, config: &Config, walk: &SizeWalk) -> Result<CategoryResult> {
     let mut result = CategoryResult::default();
     let mut paths = Vec::new();
 
-    // Get Windows directory
-    let windows_dir = std::env::var("SystemRoot")
-        .ok()
-        .map(PathBuf::from)
-        .or_else(|| Some(PathBuf::from("C:\\Windows")));
-
-    if let Some(ref windows_path) = windows_dir {
-        // 1. Windows Update download cache
-        let update_download_path = windows_path.join("SoftwareDistribution").join("Download");
-        if update_download_path.exists() && !config.is_excluded(&update_download_path) {
-            match utils::calculate_dir_size(&update_download_path) {
-                size if size > 0 => {
-                    result.items += 1;
-                    result.size_bytes += size;
-                    paths.push(update_download_path);
-                }
-                _ => {}
-            }
-        }
+    let windows_path = windows_dir();
 
-        // 2. Windows Update logs
-        let update_logs_path = windows_path.join("Logs").join("WindowsUpdate");
-        if update_logs_path.exists() && !config.is_excluded(&update_logs_path) {
-            match utils::calculate_dir_size(&update_logs_path) {
+    // 1. Windows Update download cache, 2. Windows Update logs
+    for path in roots() {
+        if path.exists() && !config.is_excluded(&path) {
+            match utils::calculate_dir_size_in(&path, walk) {
                 size if size > 0 => {
                     result.items += 1;
                     result.size_bytes += size;
-                    paths.push(update_logs_path);
+                    paths.push(path);
                 }
                 _ => {}
             }
         }
+    }
 
-        // 3. Component Store (WinSxS) - scan only, show size but note it requires DISM
-        // We scan it but don't add to paths since it requires special handling
-        let winsxs_path = windows_path.join("WinSxS");
-        if winsxs_path.exists() && !config.is_excluded(&winsxs_path) {
-            // Only scan if we can access it (may require admin)
-            match utils::calculate_dir_size(&winsxs_path) {
-                size if size > 0 => {
-                    // Note: We don't add this to paths because cleanup requires DISM
-                    // But we can show it in the scan results
-                    // For now, we'll just note it exists but don't include it in cleanable paths
-                }
-                _ => {}
+    // 3. Component Store (WinSxS) - scan only, show size but note it requires DISM
+    // We scan it but don't add to paths since it requires special handling
+    let winsxs_path = windows_path.join("WinSxS");
+    if winsxs_path.exists() && !config.is_excluded(&winsxs_path) {
+        // Only scan if we can access it (may require admin)
+        match utils::calculate_dir_size_in(&winsxs_path, walk) {
+            size if size > 0 => {
+                // Note: We don't add this to paths because cleanup requires DISM
+                // But we can show it in the scan results
+                // For now, we'll just note it exists but don't include it in cleanable paths
             }
+            _ => {}
         }
     }
 
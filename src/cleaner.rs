@@ -1,10 +1,36 @@
 //! Deletion orchestration for cleaning operations.
 
+pub mod archive;
+pub mod background_delete;
 mod batch_deletion;
 mod category_cleaning;
+mod cross_volume;
+pub mod elevated;
+#[cfg(feature = "failure-injection")]
+pub mod failure_injection;
+mod interlock;
+pub(crate) mod path_identity;
 mod path_precheck;
+mod project_batch;
+mod protected;
+pub mod quarantine;
 mod single_deletion;
+pub mod strategy;
 
-pub use batch_deletion::{clean_paths_batch, BatchDeleteResult};
-pub use category_cleaning::clean_all;
+pub use batch_deletion::{clean_paths_batch, clean_paths_batch_with_progress, BatchDeleteResult};
+pub use category_cleaning::{clean_all, clean_all_with_dedupe, clean_with};
+pub use cross_volume::{recycle_route, split_for_recycle, RecycleRoute, RecycleSplit};
+pub use interlock::{phrase_matches, required_phrase};
+pub use path_identity::{
+    record_scan as record_scanned_identities, reset as reset_scanned_identities, PathChange,
+    PathChangedError,
+};
+pub(crate) use path_precheck::is_path_locked;
+pub use project_batch::{clean_project, group_by_project, ProjectBatch, ProjectOutcome};
+pub use protected::{protection_reason, ProtectedPaths};
+pub(crate) use single_deletion::precheck_and_delete;
 pub use single_deletion::{clean_path, delete_with_precheck, DeleteOutcome};
+pub use strategy::{
+    CategoryCleaner, CategoryOutcome, CleanContext, CleanHooks, CleanOptions, CleanerRegistry,
+    DefaultHooks,
+};
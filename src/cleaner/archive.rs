@@ -0,0 +1,299 @@
+//! Archive-before-delete for build artifacts (`clean --build --archive <dir>`)
+//!
+//! Each build artifact folder (`node_modules`, `target`, ...) is packed into a
+//! zstd-compressed tarball in the archive folder right before it's deleted,
+//! and the deletion log records where, so `wole restore` can unpack it even
+//! after the Recycle Bin was emptied. The archives are plain `.tar.zst` files:
+//! `tar --zstd -xf` unpacks them too. `.tar.gz` archives from older versions
+//! still restore.
+
+use super::strategy::CleanHooks;
+use crate::debug_log;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tar::EntryType;
+use walkdir::WalkDir;
+
+/// Deletion log category whose items are archived
+const ARCHIVED_CATEGORY: &str = "build artifacts";
+
+/// First bytes of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Pack `path` (a folder) into a new archive in `dir` and return its path.
+/// Entries are stored under the folder's own name.
+pub fn archive(path: &Path, dir: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("Nothing to archive at {}", path.display()))?;
+    let project = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let hash = blake3::hash(path.display().to_string().as_bytes());
+    let file_name = format!(
+        "{}-{}-{}-{}.tar.zst",
+        project,
+        name.to_string_lossy(),
+        Utc::now().format("%Y%m%d%H%M%S"),
+        &hash.to_hex()[..8]
+    );
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let target = dir.join(file_name);
+    let partial = target.with_extension("zst.partial");
+
+    let written = File::create(&partial)
+        .map_err(anyhow::Error::from)
+        .and_then(|file| {
+            let encoder = zstd::Encoder::new(BufWriter::new(file), 0)?;
+            let mut builder = tar::Builder::new(encoder);
+            write_tree(&mut builder, path, Path::new(name))?;
+            builder.into_inner()?.finish()?.flush()?;
+            Ok(())
+        });
+    if let Err(e) = written.and_then(|()| Ok(fs::rename(&partial, &target)?)) {
+        let _ = fs::remove_file(&partial);
+        return Err(e).with_context(|| format!("Failed to archive {}", path.display()));
+    }
+    Ok(target)
+}
+
+fn write_tree(builder: &mut tar::Builder<impl Write>, root: &Path, name: &Path) -> Result<()> {
+    for entry in WalkDir::new(root).follow_links(false) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let entry_name = name.join(relative);
+        let meta = entry.path().symlink_metadata()?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(&meta);
+        if meta.file_type().is_symlink() {
+            let link = fs::read_link(entry.path())?;
+            builder.append_link(&mut header, &entry_name, &link)?;
+        } else if meta.is_dir() {
+            builder.append_data(&mut header, &entry_name, io::empty())?;
+        } else {
+            // The header promises a size; pad or cut the data to match it
+            let size = meta.len();
+            let file = File::open(entry.path())?;
+            let data = file.take(size).chain(io::repeat(0)).take(size);
+            builder.append_data(&mut header, &entry_name, data)?;
+        }
+    }
+    Ok(())
+}
+
+/// Unpack an archive made by [`archive`] into `parent`, recreating the
+/// folder it was made from. Fails if that folder exists again.
+pub fn extract(archive: &Path, parent: &Path) -> Result<()> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut input = BufReader::new(file);
+    let unpacked = if input.fill_buf()?.starts_with(&ZSTD_MAGIC) {
+        unpack(zstd::Decoder::with_buffer(input)?, parent)
+    } else {
+        unpack(GzDecoder::new(input), parent)
+    };
+    unpacked.with_context(|| format!("Failed to unpack {}", archive.display()))?;
+    debug_log::cleaning_log(&format!(
+        "unarchived: {} -> {}",
+        archive.display(),
+        parent.display()
+    ));
+    Ok(())
+}
+
+fn unpack(input: impl Read, parent: &Path) -> Result<()> {
+    let mut tarball = tar::Archive::new(input);
+    let mut checked_root = false;
+    for entry in tarball.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let relative = safe_relative(&name)?;
+        let target = parent.join(&relative);
+        if !checked_root {
+            if target.exists() {
+                bail!("{} exists; move it away to restore", target.display());
+            }
+            checked_root = true;
+        }
+
+        match entry.header().entry_type() {
+            EntryType::Directory => fs::create_dir_all(&target)?,
+            EntryType::Regular => {
+                if let Some(dir) = target.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                entry.unpack(&target)?;
+            }
+            EntryType::Symlink => {
+                let link = entry
+                    .link_name()?
+                    .with_context(|| format!("Link {} has no target", name))?;
+                restore_symlink(&link, &target)?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn restore_symlink(link: &Path, target: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(link, target)
+        .with_context(|| format!("Failed to recreate the link {}", target.display()))
+}
+
+/// Windows needs extra rights to create links, so an archive holding one
+/// can't be restored as it was
+#[cfg(not(unix))]
+fn restore_symlink(link: &Path, target: &Path) -> Result<()> {
+    bail!(
+        "Can't recreate the link {} -> {} on this platform",
+        target.display(),
+        link.display()
+    )
+}
+
+/// Reject absolute names and `..`, so an archive can't write outside `parent`
+fn safe_relative(name: &str) -> Result<PathBuf> {
+    let path = Path::new(name.trim_end_matches('/'));
+    if path.as_os_str().is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        bail!("Unsafe path in archive: {}", name);
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Hooks that archive every build artifact before it's deleted. An artifact
+/// that can't be archived is kept.
+pub struct ArchiveHooks {
+    dir: PathBuf,
+    archived: Mutex<HashMap<PathBuf, PathBuf>>,
+}
+
+impl ArchiveHooks {
+    /// Archive into `dir`
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            archived: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl CleanHooks for ArchiveHooks {
+    fn before_delete(&self, path: &Path, category: &str) -> Result<(), String> {
+        if category != ARCHIVED_CATEGORY || !path.is_dir() {
+            return Ok(());
+        }
+        let started = SystemTime::now();
+        let stored = archive(path, &self.dir).map_err(|e| format!("{:#}", e))?;
+        debug_log::cleaning_log(&format!(
+            "archived: {} -> {} ({:.1}s)",
+            path.display(),
+            stored.display(),
+            started.elapsed().unwrap_or_default().as_secs_f64()
+        ));
+        self.archived
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path.to_path_buf(), stored);
+        Ok(())
+    }
+
+    fn archive_of(&self, path: &Path) -> Option<PathBuf> {
+        self.archived
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(path)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archives_round_trip_long_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let project = temp.path().join("app");
+        let deep = project
+            .join("node_modules")
+            .join("a-rather-long-package-name-to-push-past-the-header")
+            .join("and-a-nested-folder-that-keeps-going");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("index.js"), "module.exports = 1;\n").unwrap();
+        fs::write(project.join("node_modules/empty.txt"), "").unwrap();
+        let big = vec![7u8; 512 * 3 + 5];
+        fs::write(project.join("node_modules/blob.bin"), &big).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("../blob.bin", deep.join("blob-link")).unwrap();
+
+        let stored = archive(&project.join("node_modules"), &temp.path().join("archives")).unwrap();
+        assert!(stored.to_string_lossy().ends_with(".tar.zst"));
+
+        // Refuses to overwrite, then restores the tree once it's gone
+        assert!(extract(&stored, &project).is_err());
+        fs::remove_dir_all(project.join("node_modules")).unwrap();
+        extract(&stored, &project).unwrap();
+        assert_eq!(
+            fs::read_to_string(deep.join("index.js")).unwrap(),
+            "module.exports = 1;\n"
+        );
+        assert_eq!(
+            fs::read(project.join("node_modules/blob.bin")).unwrap(),
+            big
+        );
+        assert!(project.join("node_modules/empty.txt").is_file());
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(deep.join("blob-link")).unwrap(),
+            Path::new("../blob.bin")
+        );
+
+        assert!(safe_relative("../evil").is_err());
+        assert!(safe_relative("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn gzip_archives_from_older_versions_still_restore() {
+        let temp = tempfile::tempdir().unwrap();
+        let stored = temp.path().join("app-target.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&stored).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        builder
+            .append_data(&mut header, "target/", io::empty())
+            .unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(3);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "target/out.txt", &b"old"[..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let project = temp.path().join("app");
+        fs::create_dir(&project).unwrap();
+        extract(&stored, &project).unwrap();
+        assert_eq!(
+            fs::read_to_string(project.join("target/out.txt")).unwrap(),
+            "old"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Background deletion of very large folders
+//!
+//! Permanently deleting a folder with hundreds of thousands of files can block
+//! for minutes. Folders with at least `[safety] background_delete_min_entries`
+//! entries are renamed to a hidden sibling first, which is instant on the same
+//! volume and makes the original path disappear, then removed on a worker
+//! thread. Each job is kept in a journal in the history directory until it
+//! completes; a job cut short by exiting is picked up by `resume_pending()`.
+
+use crate::debug_log;
+use crate::history;
+use crate::tasks::{self, CancelToken, TaskKind};
+use crate::utils;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// Name prefix of folders waiting to be deleted
+pub const STAGING_PREFIX: &str = ".wole-deleting-";
+
+/// Completed jobs kept in the journal
+const MAX_COMPLETED_JOBS: usize = 50;
+
+/// A background deletion, as recorded in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundJob {
+    /// Where the folder was before it was staged
+    pub original: PathBuf,
+    /// The renamed folder being deleted
+    pub staged: PathBuf,
+    pub started: DateTime<Utc>,
+    pub completed: Option<DateTime<Utc>>,
+    pub bytes_freed: u64,
+    pub entries_removed: u64,
+}
+
+/// Live progress of a running job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobProgress {
+    pub original: PathBuf,
+    pub bytes_freed: u64,
+    pub entries_removed: u64,
+}
+
+struct Running {
+    original: PathBuf,
+    staged: PathBuf,
+    bytes: Arc<AtomicU64>,
+    entries: Arc<AtomicU64>,
+}
+
+static RUNNING: Mutex<Vec<Running>> = Mutex::new(Vec::new());
+
+/// Serializes journal read-modify-write cycles between jobs
+static JOURNAL: Mutex<()> = Mutex::new(());
+
+/// Whether `path` holds at least `min_entries` files and folders.
+/// Stops walking as soon as the answer is known; 0 means never.
+pub fn has_at_least_entries(path: &Path, min_entries: u64) -> bool {
+    if min_entries == 0 {
+        return false;
+    }
+    WalkDir::new(path)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .take(min_entries as usize)
+        .count() as u64
+        >= min_entries
+}
+
+/// Rename `path` to a hidden sibling so it disappears immediately.
+/// Staying in the same folder keeps it on the same volume, so this is a rename
+/// rather than a copy.
+pub fn stage(path: &Path) -> std::io::Result<PathBuf> {
+    let parent = path.parent().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no parent")
+    })?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let staged = parent.join(format!("{}{}-{}", STAGING_PREFIX, name, stamp));
+    fs::rename(path, &staged)?;
+    Ok(staged)
+}
+
+/// Stage `path` and delete it on a worker thread. Returns the staged path.
+pub fn start(path: &Path) -> Result<PathBuf> {
+    let staged =
+        stage(path).with_context(|| format!("Failed to stage for deletion: {}", path.display()))?;
+    let job = BackgroundJob {
+        original: path.to_path_buf(),
+        staged: staged.clone(),
+        started: Utc::now(),
+        completed: None,
+        bytes_freed: 0,
+        entries_removed: 0,
+    };
+    if let Err(e) = update_journal(|jobs| jobs.push(job.clone())) {
+        debug_log::cleaning_log(&format!("background delete journal write failed: {}", e));
+    }
+    debug_log::cleaning_log(&format!(
+        "background delete start: {} -> {}",
+        path.display(),
+        staged.display()
+    ));
+    spawn(job);
+    Ok(staged)
+}
+
+/// Restart jobs left unfinished by a previous run. Jobs whose staged folder is
+/// already gone are marked completed. Returns the number of jobs restarted.
+pub fn resume_pending() -> usize {
+    let running: Vec<PathBuf> = RUNNING
+        .lock()
+        .map(|r| r.iter().map(|job| job.staged.clone()).collect())
+        .unwrap_or_default();
+
+    let mut resumed = Vec::new();
+    let result = update_journal(|jobs| {
+        for job in jobs.iter_mut() {
+            if job.completed.is_some() || running.contains(&job.staged) {
+                continue;
+            }
+            if job.staged.exists() {
+                resumed.push(job.clone());
+            } else {
+                job.completed = Some(Utc::now());
+            }
+        }
+    });
+    if let Err(e) = result {
+        debug_log::cleaning_log(&format!("background delete journal read failed: {}", e));
+        return 0;
+    }
+
+    let count = resumed.len();
+    for job in resumed {
+        debug_log::cleaning_log(&format!(
+            "background delete resume: {}",
+            job.staged.display()
+        ));
+        spawn(job);
+    }
+    count
+}
+
+/// Progress of the jobs running in this process
+pub fn running() -> Vec<JobProgress> {
+    RUNNING
+        .lock()
+        .map(|r| {
+            r.iter()
+                .map(|job| JobProgress {
+                    original: job.original.clone(),
+                    bytes_freed: job.bytes.load(Ordering::Relaxed),
+                    entries_removed: job.entries.load(Ordering::Relaxed),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Block until every running job finishes, calling `on_tick` with their
+/// progress a few times per second
+pub fn wait(mut on_tick: impl FnMut(&[JobProgress])) {
+    loop {
+        let jobs = running();
+        if jobs.is_empty() {
+            return;
+        }
+        on_tick(&jobs);
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// Jobs recorded in the journal, newest last
+pub fn journal() -> Vec<BackgroundJob> {
+    journal_path()
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn journal_path() -> Result<PathBuf> {
+    Ok(history::get_history_dir()?.join("background_deletes.json"))
+}
+
+fn update_journal(f: impl FnOnce(&mut Vec<BackgroundJob>)) -> Result<()> {
+    let _lock = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    let mut jobs = journal();
+    f(&mut jobs);
+
+    // Keep every pending job but only the most recent completed ones
+    let completed = jobs.iter().filter(|j| j.completed.is_some()).count();
+    let mut excess = completed.saturating_sub(MAX_COMPLETED_JOBS);
+    jobs.retain(|j| {
+        if excess > 0 && j.completed.is_some() {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+
+    let path = journal_path()?;
+    fs::write(&path, serde_json::to_string_pretty(&jobs)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn spawn(job: BackgroundJob) {
+    let bytes = Arc::new(AtomicU64::new(job.bytes_freed));
+    let entries = Arc::new(AtomicU64::new(job.entries_removed));
+    if let Ok(mut r) = RUNNING.lock() {
+        r.push(Running {
+            original: job.original.clone(),
+            staged: job.staged.clone(),
+            bytes: bytes.clone(),
+            entries: entries.clone(),
+        });
+    }
+
+    // A worker: exiting cancels it and the journal entry resumes it next run
+    tasks::spawn("background-delete", TaskKind::Worker, move |cancel| {
+        let finished = remove_tree(&job.staged, &cancel, &bytes, &entries);
+        let bytes_freed = bytes.load(Ordering::Relaxed);
+        let entries_removed = entries.load(Ordering::Relaxed);
+        debug_log::cleaning_log(&format!(
+            "background delete {}: {} ({} bytes, {} entries)",
+            if finished { "done" } else { "paused" },
+            job.staged.display(),
+            bytes_freed,
+            entries_removed
+        ));
+
+        let _ = update_journal(|jobs| {
+            if let Some(entry) = jobs.iter_mut().find(|j| j.staged == job.staged) {
+                entry.bytes_freed = bytes_freed;
+                entry.entries_removed = entries_removed;
+                if finished {
+                    entry.completed = Some(Utc::now());
+                }
+            }
+        });
+        if let Ok(mut r) = RUNNING.lock() {
+            r.retain(|running| running.staged != job.staged);
+        }
+    });
+}
+
+/// Delete `root` bottom-up, counting what was removed. Returns true once
+/// `root` is gone, false when cancelled or when something couldn't be removed.
+fn remove_tree(root: &Path, cancel: &CancelToken, bytes: &AtomicU64, entries: &AtomicU64) -> bool {
+    for entry in WalkDir::new(root)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if cancel.is_cancelled() {
+            return false;
+        }
+        let removed = if entry.file_type().is_dir() {
+            fs::remove_dir(entry.path()).is_ok()
+        } else {
+            let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let ok = utils::safe_remove_file(entry.path()).is_ok();
+            if ok {
+                bytes.fetch_add(len, Ordering::Relaxed);
+            }
+            ok
+        };
+        if removed {
+            entries.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    // Whatever the walk couldn't remove (read-only files, long paths) gets one
+    // more try with the regular recursive delete
+    if root.exists() {
+        let _ = utils::safe_remove_dir_all(root);
+    }
+    !root.exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn staged_folder_is_removed_with_progress() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("node_modules");
+        fs::create_dir_all(target.join("pkg").join("lib")).unwrap();
+        fs::write(target.join("pkg").join("index.js"), vec![0u8; 100]).unwrap();
+        fs::write(target.join("pkg").join("lib").join("a.js"), vec![0u8; 50]).unwrap();
+
+        assert!(has_at_least_entries(&target, 4));
+        assert!(!has_at_least_entries(&target, 5));
+        assert!(!has_at_least_entries(&target, 0));
+
+        let staged = stage(&target).unwrap();
+        assert!(!target.exists());
+        assert_eq!(staged.parent(), Some(dir.path()));
+        assert!(staged
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(".wole-deleting-node_modules-"));
+
+        let bytes = AtomicU64::new(0);
+        let entries = AtomicU64::new(0);
+        assert!(remove_tree(&staged, &CancelToken::new(), &bytes, &entries));
+        assert!(!staged.exists());
+        assert_eq!(bytes.load(Ordering::Relaxed), 150);
+        assert_eq!(entries.load(Ordering::Relaxed), 5);
+
+        // A cancelled job leaves the staged folder to be resumed later
+        let again = dir.path().join("target");
+        fs::create_dir_all(&again).unwrap();
+        fs::write(again.join("x"), b"x").unwrap();
+        let staged = stage(&again).unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(!remove_tree(&staged, &cancel, &bytes, &entries));
+        assert!(staged.exists());
+    }
+}
@@ -1,11 +1,21 @@
 //! Batch deletion feature.
 //!
-//! This module owns batch deletion operations and results.
+//! This module owns batch deletion operations and results. Recycle Bin
+//! batches go to the Shell in one call; permanent deletes and quarantine moves
+//! run per path on a small worker pool.
 
+use super::path_identity::PathChangedError;
 use super::path_precheck::{precheck_path, PrecheckOutcome};
-use super::single_deletion::{classify_anyhow_error, delete_with_precheck, DeleteOutcome};
+use super::quarantine::{self, Quarantine};
+use super::single_deletion::{classify_anyhow_error, precheck_and_delete, DeleteOutcome};
+use crate::config::{Config, PerformanceSettings};
 use crate::debug_log;
-use std::path::PathBuf;
+use crate::history::FailureInfo;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct BatchDeleteResult {
@@ -15,6 +25,8 @@ pub struct BatchDeleteResult {
     pub skipped_paths: Vec<PathBuf>,
     pub locked_paths: Vec<PathBuf>,
     pub permission_denied_paths: Vec<PathBuf>,
+    /// Error code and reason for failed paths where the OS reported an error
+    pub failures: HashMap<PathBuf, FailureInfo>,
 }
 
 impl BatchDeleteResult {
@@ -26,8 +38,136 @@ impl BatchDeleteResult {
             skipped_paths: Vec::new(),
             locked_paths: Vec::new(),
             permission_denied_paths: Vec::new(),
+            failures: HashMap::new(),
         }
     }
+
+    /// Fold another batch's outcome into this one
+    pub fn merge(&mut self, other: BatchDeleteResult) {
+        self.success_count += other.success_count;
+        self.error_count += other.error_count;
+        self.deleted_paths.extend(other.deleted_paths);
+        self.skipped_paths.extend(other.skipped_paths);
+        self.locked_paths.extend(other.locked_paths);
+        self.permission_denied_paths
+            .extend(other.permission_denied_paths);
+        self.failures.extend(other.failures);
+    }
+
+    /// Failure details for every failed path; locked and permission-denied
+    /// paths without a captured OS error get a reason from their classification
+    pub fn failure_details(&self) -> HashMap<PathBuf, FailureInfo> {
+        let mut details = self.failures.clone();
+        for path in &self.locked_paths {
+            details
+                .entry(path.clone())
+                .or_insert_with(|| FailureInfo::from_message("Path is locked by another process"));
+        }
+        for path in &self.permission_denied_paths {
+            details
+                .entry(path.clone())
+                .or_insert_with(|| FailureInfo::from_message("Permission denied"));
+        }
+        details
+    }
+
+    /// Fold the outcome of deleting one path into the result
+    fn record(&mut self, path: &Path, outcome: Result<DeleteOutcome>) {
+        match outcome {
+            Ok(DeleteOutcome::Deleted) => {
+                self.success_count += 1;
+                self.deleted_paths.push(path.to_path_buf());
+            }
+            Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {
+                self.skipped_paths.push(path.to_path_buf());
+            }
+            Ok(DeleteOutcome::SkippedLocked) => {
+                self.error_count += 1;
+                self.locked_paths.push(path.to_path_buf());
+            }
+            Ok(DeleteOutcome::SkippedPermission) => {
+                self.error_count += 1;
+                self.permission_denied_paths.push(path.to_path_buf());
+            }
+            Err(err) => {
+                self.error_count += 1;
+                self.failures
+                    .insert(path.to_path_buf(), FailureInfo::from_error(&err));
+            }
+        }
+    }
+}
+
+/// Deletions that run at once; past this the disk, not the CPU, is the limit
+const MAX_DELETE_WORKERS: usize = 8;
+
+/// Below this many paths starting workers costs more than it saves
+const MIN_PARALLEL_PATHS: usize = 64;
+
+/// How many of `count` paths are deleted at once: `delete_threads` (all cores
+/// when 0), capped at `MAX_DELETE_WORKERS`
+fn delete_workers(settings: &PerformanceSettings, count: usize) -> usize {
+    if count < MIN_PARALLEL_PATHS {
+        return 1;
+    }
+    let threads = match settings.delete_threads {
+        0 => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        n => n as usize,
+    };
+    threads.min(MAX_DELETE_WORKERS).min(count).max(1)
+}
+
+/// Delete `paths` one by one on `workers` threads. Outcomes are folded in
+/// input order once every worker is done, so the result is the same as a
+/// serial run; `on_deleted` is called from the workers as paths go.
+fn delete_each(
+    paths: &[PathBuf],
+    permanent: bool,
+    quarantine: Option<&Quarantine>,
+    workers: usize,
+    on_deleted: &(dyn Fn(u64) + Sync),
+) -> BatchDeleteResult {
+    let delete = |path: &PathBuf| {
+        let outcome = precheck_and_delete(path, permanent, quarantine);
+        if matches!(outcome, Ok(DeleteOutcome::Deleted)) {
+            on_deleted(1);
+        }
+        outcome
+    };
+
+    let mut result = BatchDeleteResult::empty();
+    if workers <= 1 {
+        for path in paths {
+            result.record(path, delete(path));
+        }
+        return result;
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Vec<Mutex<Option<Result<DeleteOutcome>>>> =
+        paths.iter().map(|_| Mutex::new(None)).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(path) = paths.get(i) else {
+                    break;
+                };
+                let outcome = delete(path);
+                *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(outcome);
+            });
+        }
+    });
+    for (path, slot) in paths.iter().zip(slots) {
+        let outcome = slot
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+            .unwrap_or_else(|| Err(anyhow::anyhow!("Deletion did not run")));
+        result.record(path, outcome);
+    }
+    result
 }
 
 fn partition_existing(paths: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
@@ -54,6 +194,20 @@ fn partition_existing(paths: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
 ///
 /// Returns a detailed batch deletion result
 pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResult {
+    let quarantine = quarantine::configured();
+    clean_paths_batch_with_progress(paths, permanent, quarantine.as_ref(), &|_| {})
+}
+
+/// [`clean_paths_batch`], calling `on_deleted` with the number of paths
+/// removed as they go. Non-permanent deletes go to `quarantine` when one is
+/// given. Permanent deletes and quarantine moves run on up to
+/// `[performance] delete_threads` threads, so it may be called from any of them.
+pub fn clean_paths_batch_with_progress(
+    paths: &[PathBuf],
+    permanent: bool,
+    quarantine: Option<&Quarantine>,
+    on_deleted: &(dyn Fn(u64) + Sync),
+) -> BatchDeleteResult {
     if paths.is_empty() {
         return BatchDeleteResult::empty();
     }
@@ -80,30 +234,21 @@ pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResul
     let mut skipped_paths: Vec<PathBuf> = Vec::new();
     let mut locked_paths: Vec<PathBuf> = Vec::new();
     let mut permission_denied_paths: Vec<PathBuf> = Vec::new();
+    let mut failures: HashMap<PathBuf, FailureInfo> = HashMap::new();
 
-    if permanent {
-        // Permanent deletes are already fast (direct filesystem ops)
-        // Delete one-by-one to track individual successes/failures
-        for path in paths {
-            match delete_with_precheck(path, true) {
-                Ok(DeleteOutcome::Deleted) => {
-                    success_count += 1;
-                    deleted_paths.push(path.clone());
-                }
-                Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {
-                    skipped_paths.push(path.clone());
-                }
-                Ok(DeleteOutcome::SkippedLocked) => {
-                    error_count += 1;
-                    locked_paths.push(path.clone());
-                }
-                Ok(DeleteOutcome::SkippedPermission) => {
-                    error_count += 1;
-                    permission_denied_paths.push(path.clone());
-                }
-                Err(_) => error_count += 1,
-            }
-        }
+    if permanent || quarantine.is_some() {
+        // Permanent deletes and moves into quarantine are already fast (direct
+        // filesystem ops). Delete one-by-one to track individual successes/failures,
+        // several at a time
+        let workers = delete_workers(&Config::load().performance, paths.len());
+        let result = delete_each(paths, permanent, quarantine, workers, on_deleted);
+        success_count = result.success_count;
+        error_count = result.error_count;
+        deleted_paths = result.deleted_paths;
+        skipped_paths = result.skipped_paths;
+        locked_paths = result.locked_paths;
+        permission_denied_paths = result.permission_denied_paths;
+        failures = result.failures;
     } else {
         // Batch to Recycle Bin - this is the big performance win
         // First, filter out locked, missing, and system paths (they would cause batch to fail)
@@ -117,6 +262,22 @@ pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResul
                     error_count += 1;
                     locked_paths.push(path.clone());
                 }
+                PrecheckOutcome::Changed(change) => {
+                    error_count += 1;
+                    let err = anyhow::Error::new(PathChangedError {
+                        path: path.clone(),
+                        change,
+                    });
+                    failures.insert(path.clone(), FailureInfo::from_error(&err));
+                }
+                PrecheckOutcome::Protected(reason) => {
+                    error_count += 1;
+                    let err = anyhow::Error::new(crate::Error::Protected {
+                        path: path.clone(),
+                        reason,
+                    });
+                    failures.insert(path.clone(), FailureInfo::from_error(&err));
+                }
                 PrecheckOutcome::Eligible => unlocked.push(path.clone()),
             }
         }
@@ -208,6 +369,8 @@ pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResul
                                         success_count += 1;
                                         deleted_paths.push(path.clone());
                                     } else {
+                                        failures
+                                            .insert(path.clone(), FailureInfo::from_error(&_err));
                                         match classify_anyhow_error(&path, &_err) {
                                             Some(DeleteOutcome::SkippedLocked) => {
                                                 error_count += 1;
@@ -240,6 +403,8 @@ pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResul
                 }
             }
         }
+        // The Shell reports a batch only once it's done
+        on_deleted(success_count as u64);
     }
 
     debug_log::cleaning_log(&format!(
@@ -258,5 +423,38 @@ pub fn clean_paths_batch(paths: &[PathBuf], permanent: bool) -> BatchDeleteResul
         skipped_paths,
         locked_paths,
         permission_denied_paths,
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    #[test]
+    fn parallel_deletes_account_for_every_path_in_order() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut paths: Vec<PathBuf> = (0..200)
+            .map(|i| {
+                let path = temp.path().join(format!("f{}.tmp", i));
+                std::fs::write(&path, "x").unwrap();
+                path
+            })
+            .collect();
+        paths.insert(100, temp.path().join("missing.tmp"));
+
+        let progress = AtomicU64::new(0);
+        let result = delete_each(&paths, true, None, 4, &|n| {
+            progress.fetch_add(n, Ordering::SeqCst);
+        });
+
+        assert_eq!((result.success_count, result.error_count), (200, 0));
+        assert_eq!(progress.load(Ordering::SeqCst), 200);
+        let missing = paths.remove(100);
+        assert_eq!(result.skipped_paths, vec![missing]);
+        // Folded in input order, as a serial run would
+        assert_eq!(result.deleted_paths, paths);
+        assert!(paths.iter().all(|p| !p.exists()));
     }
 }
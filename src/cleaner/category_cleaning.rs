@@ -1,17 +1,24 @@
 //! Category cleaning feature.
 //!
-//! This module owns bulk cleaning across categories using scan results.
-
-use super::batch_deletion::{clean_paths_batch, BatchDeleteResult};
-use super::single_deletion::{delete_with_precheck, DeleteOutcome};
+//! This module owns bulk cleaning across categories using scan results: the
+//! driver that runs a [`CleanerRegistry`](super::CleanerRegistry) and the
+//! deletion helpers the built-in cleaners share.
+
+use super::background_delete;
+use super::batch_deletion::{clean_paths_batch_with_progress, BatchDeleteResult};
+use super::cross_volume::{recycle_route, split_for_recycle};
+use super::project_batch::{clean_project, group_by_project, ProjectOutcome};
+use super::quarantine;
+use super::strategy::{CleanContext, CleanOptions, CleanerRegistry};
 use crate::categories;
-use crate::history::DeletionLog;
+use crate::categories::duplicates::DedupeMode;
+use crate::config::Config;
+use crate::history::{DeletionLog, FailureInfo};
 use crate::output::{OutputMode, ScanResults};
 use crate::progress;
 use crate::theme::Theme;
 use crate::utils;
-use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
@@ -45,31 +52,42 @@ fn read_line_from_stdin() -> io::Result<String> {
     Ok(input)
 }
 
+/// Size of a file or directory tree
+pub(super) fn path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        utils::calculate_dir_size(path)
+    } else {
+        utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
+    }
+}
+
 /// Helper function to batch clean a category (10-50x faster than one-by-one)
-fn batch_clean_category_internal(
+pub(super) fn batch_clean_category_internal(
+    ctx: &CleanContext,
     paths: &[PathBuf],
     category_name: &str,
-    permanent: bool,
-    dry_run: bool,
-    progress: Option<&indicatif::ProgressBar>,
-    history: Option<&mut DeletionLog>,
-    mode: OutputMode,
+    mut history: Option<&mut DeletionLog>,
 ) -> (u64, u64) {
     if paths.is_empty() {
         return (0, 0);
     }
+    let CleanOptions {
+        permanent,
+        dry_run,
+        mode,
+        ..
+    } = ctx.options;
+    let progress = ctx.progress;
 
-    if let Some(pb) = progress {
-        let msg = format!("Cleaning {}...", category_name);
-        pb.set_message(msg);
-    }
+    ctx.set_message(format!("Cleaning {}...", category_name));
+
+    let (admitted, rejected) = ctx.admit(paths, category_name, history.as_deref_mut());
+    let paths = admitted.as_slice();
 
     if dry_run {
         let count = paths.len() as u64;
-        if let Some(pb) = progress {
-            pb.inc(count);
-        }
-        return (count, 0);
+        ctx.inc(count);
+        return (count, rejected);
     }
 
     // Calculate sizes BEFORE deletion (critical for accurate logging)
@@ -78,46 +96,93 @@ fn batch_clean_category_internal(
     // Always calculate sizes if history logging is enabled
     if history.is_some() {
         for path in paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            path_sizes.insert(path.clone(), size);
+            path_sizes.insert(path.clone(), path_size(path));
         }
     }
 
-    // Use batch deletion for much better performance
-    // Batch deletion completes instantly, so progress updates happen after completion
+    // Large items on volumes without a usable Recycle Bin would be copied
+    // across drives; route them per `[cross_volume]` before recycling. The
+    // quarantine takes them as they are.
+    let split = (!permanent && ctx.quarantine.is_none()).then(|| {
+        let settings = Config::load().cross_volume;
+        split_for_recycle(paths, category_name, &settings, recycle_route, |p| {
+            path_sizes.get(p).copied().unwrap_or_else(|| path_size(p))
+        })
+    });
+    if let Some(notice) = split.as_ref().and_then(|s| s.notice(category_name)) {
+        if mode != OutputMode::Quiet {
+            let line = format!("{} {}", Theme::warning("[NOTICE]"), notice);
+            match progress {
+                Some(pb) if !pb.is_hidden() => pb.println(line),
+                _ => println!("{}", line),
+            }
+        }
+    }
+    let permanent_paths: HashSet<&PathBuf> = split
+        .as_ref()
+        .map(|s| s.permanent.iter().collect())
+        .unwrap_or_default();
+
+    // Use batch deletion for much better performance. Permanent deletes run
+    // on several threads and advance the progress bar as they go; logging and
+    // the delete hooks run here once the batch is done.
+    let on_deleted = |n: u64| {
+        if let Some(pb) = progress {
+            pb.inc(n);
+        }
+    };
+    let batch_result = match &split {
+        Some(split) => {
+            let mut result =
+                clean_paths_batch_with_progress(&split.recycle, false, None, &on_deleted);
+            if !split.permanent.is_empty() {
+                result.merge(clean_paths_batch_with_progress(
+                    &split.permanent,
+                    true,
+                    None,
+                    &on_deleted,
+                ));
+            }
+            result
+        }
+        None => {
+            clean_paths_batch_with_progress(paths, permanent, ctx.quarantine.as_ref(), &on_deleted)
+        }
+    };
+    let failures = batch_result.failure_details();
     let BatchDeleteResult {
-        success_count,
-        error_count,
+        mut success_count,
+        mut error_count,
         deleted_paths,
         skipped_paths,
         locked_paths,
         permission_denied_paths,
-    } = clean_paths_batch(paths, permanent);
+        ..
+    } = batch_result;
 
     // Log successes and failures using pre-calculated sizes
-    if let Some(log) = history {
-        for path in &deleted_paths {
-            let size = path_sizes.get(path).copied().unwrap_or(0);
-            log.log_success(path, size, category_name, permanent);
-        }
-        // Log failures (paths that weren't deleted or skipped)
-        for path in &locked_paths {
-            let size = path_sizes.get(path).copied().unwrap_or(0);
-            log.log_failure(
-                path,
-                size,
-                category_name,
-                permanent,
-                "Path is locked by another process",
-            );
+    for path in &deleted_paths {
+        let size = path_sizes.get(path).copied().unwrap_or(0);
+        let permanent = permanent || permanent_paths.contains(path);
+        if !ctx.deleted(path, size, category_name, permanent, history.as_deref_mut()) {
+            success_count -= 1;
+            error_count += 1;
         }
-        for path in &permission_denied_paths {
+    }
+    if let Some(log) = history {
+        // Log failures (paths that weren't deleted or skipped), keeping the OS
+        // error code when the batch captured one
+        let mut log_failed = |path: &PathBuf| {
             let size = path_sizes.get(path).copied().unwrap_or(0);
-            log.log_failure(path, size, category_name, permanent, "Permission denied");
+            let info = failures
+                .get(path)
+                .cloned()
+                .unwrap_or_else(|| FailureInfo::from_message("Batch deletion failed"));
+            let permanent = permanent || permanent_paths.contains(path);
+            log.log_failure_info(path, size, category_name, permanent, &info);
+        };
+        for path in locked_paths.iter().chain(&permission_denied_paths) {
+            log_failed(path);
         }
         for path in paths {
             if deleted_paths.contains(path)
@@ -127,22 +192,10 @@ fn batch_clean_category_internal(
             {
                 continue;
             }
-            let size = path_sizes.get(path).copied().unwrap_or(0);
-            log.log_failure(
-                path,
-                size,
-                category_name,
-                permanent,
-                "Batch deletion failed",
-            );
+            log_failed(path);
         }
     }
 
-    // Update progress
-    if let Some(pb) = progress {
-        pb.inc(success_count as u64);
-    }
-
     // Report errors
     if error_count > 0 && mode != OutputMode::Quiet {
         eprintln!(
@@ -152,53 +205,308 @@ fn batch_clean_category_internal(
         );
     }
 
-    (success_count as u64, error_count as u64)
+    (success_count as u64, error_count as u64 + rejected)
+}
+
+/// Clean build artifacts one project at a time
+///
+/// A project's artifacts (`node_modules`, `dist`, `.turbo`, ...) are removed
+/// together or not at all; a project that can't be fully cleaned is reported
+/// once instead of per folder. Returns (cleaned, errors) like
+/// `batch_clean_category_internal`.
+pub(super) fn clean_build_projects(
+    ctx: &CleanContext,
+    paths: &[PathBuf],
+    mut history: Option<&mut DeletionLog>,
+) -> (u64, u64) {
+    let category_name = "build artifacts";
+    if ctx.options.dry_run {
+        return batch_clean_category_internal(ctx, paths, category_name, history);
+    }
+    let CleanOptions {
+        permanent, mode, ..
+    } = ctx.options;
+    let progress = ctx.progress;
+    ctx.set_message(format!("Cleaning {}...", category_name));
+    let (admitted, rejected) = ctx.admit(paths, category_name, history.as_deref_mut());
+    let paths = admitted.as_slice();
+
+    let path_sizes: HashMap<PathBuf, u64> =
+        paths.iter().map(|p| (p.clone(), path_size(p))).collect();
+    let size_of = |p: &Path| path_sizes.get(p).copied().unwrap_or(0);
+
+    // A project goes to the Recycle Bin only if all of it can
+    let split = (!permanent && ctx.quarantine.is_none()).then(|| {
+        let settings = Config::load().cross_volume;
+        split_for_recycle(paths, category_name, &settings, recycle_route, size_of)
+    });
+    if let Some(notice) = split.as_ref().and_then(|s| s.notice(category_name)) {
+        if mode != OutputMode::Quiet {
+            let line = format!("{} {}", Theme::warning("[NOTICE]"), notice);
+            match progress {
+                Some(pb) if !pb.is_hidden() => pb.println(line),
+                _ => println!("{}", line),
+            }
+        }
+    }
+    let routed_permanent: HashSet<&PathBuf> = split
+        .as_ref()
+        .map(|s| s.permanent.iter().collect())
+        .unwrap_or_default();
+
+    let (mut cleaned, mut errors) = (0u64, rejected);
+    for batch in group_by_project(paths) {
+        let permanent = permanent || batch.paths.iter().any(|p| routed_permanent.contains(p));
+        let outcome = clean_project(&batch, permanent, ctx.quarantine.as_ref());
+        let (deleted, failures) = match outcome {
+            ProjectOutcome::Cleaned { deleted } => (deleted, HashMap::new()),
+            ProjectOutcome::Partial { deleted, failures } => (deleted, failures),
+            ProjectOutcome::RolledBack { blocked, failure } => {
+                let kept = FailureInfo::from_message(&format!(
+                    "not deleted: {} in the same project could not be removed",
+                    blocked.display()
+                ));
+                let failures = batch
+                    .paths
+                    .iter()
+                    .map(|p| {
+                        let info = if *p == blocked {
+                            failure.clone()
+                        } else {
+                            kept.clone()
+                        };
+                        (p.clone(), info)
+                    })
+                    .collect();
+                (Vec::new(), failures)
+            }
+        };
+
+        for path in &deleted {
+            if ctx.deleted(
+                path,
+                size_of(path),
+                category_name,
+                permanent,
+                history.as_deref_mut(),
+            ) {
+                cleaned += 1;
+            } else {
+                errors += 1;
+            }
+        }
+        if let Some(log) = history.as_deref_mut() {
+            for (path, info) in &failures {
+                log.log_failure_info(path, size_of(path), category_name, permanent, info);
+            }
+        }
+        ctx.inc(batch.paths.len() as u64);
+        if !failures.is_empty() {
+            errors += failures.len() as u64;
+            if mode != OutputMode::Quiet {
+                let line = format!(
+                    "[WARNING] Project {} was not fully cleaned ({} of {} items left)",
+                    Theme::error(&batch.project.display().to_string()),
+                    failures.len(),
+                    batch.paths.len()
+                );
+                match progress {
+                    Some(pb) if !pb.is_hidden() => pb.println(line),
+                    _ => eprintln!("{}", line),
+                }
+            }
+        }
+    }
+    (cleaned, errors)
+}
+
+/// Replace duplicate copies with hardlinks to the copy each group keeps
+///
+/// Returns (replaced, errors, bytes freed). Replaced copies are logged as
+/// permanent: the Recycle Bin never sees them, but their content stays
+/// reachable through the kept copy.
+pub(super) fn hardlink_duplicates(
+    ctx: &CleanContext,
+    results: &ScanResults,
+    mut history: Option<&mut DeletionLog>,
+) -> (u64, u64, u64) {
+    let CleanOptions { dry_run, mode, .. } = ctx.options;
+    let progress = ctx.progress;
+    ctx.set_message("Hardlinking duplicate files...");
+    let copies: HashSet<&PathBuf> = results.duplicates.paths.iter().collect();
+    let groups = results.duplicates_groups.as_deref().unwrap_or_default();
+    let (mut linked, mut errors, mut freed) = (0u64, 0u64, 0u64);
+    let mut handled: HashSet<&PathBuf> = HashSet::new();
+
+    for group in groups {
+        // The copy kept is the one the scan didn't flag
+        let Some(original) = group.paths.iter().find(|p| !copies.contains(p)) else {
+            continue;
+        };
+        for copy in group.paths.iter().filter(|p| copies.contains(p)) {
+            handled.insert(copy);
+            ctx.inc(1);
+            if let Err(reason) = ctx.hooks.before_delete(copy, "duplicate files") {
+                errors += 1;
+                ctx.fail(
+                    copy,
+                    group.size,
+                    "duplicate files",
+                    &reason,
+                    history.as_deref_mut(),
+                );
+                continue;
+            }
+            if dry_run {
+                linked += 1;
+                continue;
+            }
+            match categories::duplicates::replace_with_hardlink(original, copy) {
+                Ok(()) => {
+                    if ctx.deleted(
+                        copy,
+                        group.size,
+                        "duplicate files",
+                        true,
+                        history.as_deref_mut(),
+                    ) {
+                        linked += 1;
+                        freed += group.size;
+                    } else {
+                        errors += 1;
+                    }
+                }
+                Err(e) => {
+                    errors += 1;
+                    if let Some(log) = history.as_deref_mut() {
+                        log.log_error(copy, group.size, "duplicate files", true, &e);
+                    }
+                    if mode != OutputMode::Quiet {
+                        let line = format!(
+                            "[WARNING] Failed to hardlink {}: {}",
+                            Theme::secondary(&copy.display().to_string()),
+                            Theme::error(&e.to_string())
+                        );
+                        match progress {
+                            Some(pb) if !pb.is_hidden() => pb.println(line),
+                            _ => eprintln!("{}", line),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Copies without their group (e.g. results loaded without groups) are
+    // left in place rather than deleted
+    let orphans = copies.len() - handled.len();
+    if orphans > 0 && mode != OutputMode::Quiet {
+        eprintln!(
+            "[WARNING] Skipped {} duplicate files whose kept copy is unknown; rescan to hardlink them",
+            orphans
+        );
+    }
+    if let Some(pb) = progress {
+        pb.inc(orphans as u64);
+    }
+    (linked, errors, freed)
 }
 
 /// Clean all categories based on scan results
 ///
-/// Handles confirmation prompts, error tracking, and provides progress feedback
+/// Handles confirmation prompts, error tracking, and provides progress feedback.
+/// `take_ownership` only affects the orphaned-owner category: paths that fail with
+/// permission denied are re-owned and retried instead of being reported as errors.
+/// Steps that need administrator rights are skipped when not elevated.
+/// Returns the session's deletion log, or None when nothing was deleted
+/// (dry run, nothing to clean, or cancelled at the prompt).
 pub fn clean_all(
     results: &ScanResults,
     skip_confirm: bool,
     mode: OutputMode,
     permanent: bool,
     dry_run: bool,
-) -> Result<()> {
-    let total_items = results.cache.items
-        + results.app_cache.items
-        + results.temp.items
-        + results.trash.items
-        + results.build.items
-        + results.downloads.items
-        + results.large.items
-        + results.old.items
-        + results.browser.items
-        + results.system.items
-        + results.empty.items
-        + results.duplicates.items
-        + results.windows_update.items
-        + results.event_logs.items;
-    let total_bytes = results.cache.size_bytes
-        + results.app_cache.size_bytes
-        + results.temp.size_bytes
-        + results.trash.size_bytes
-        + results.build.size_bytes
-        + results.downloads.size_bytes
-        + results.large.size_bytes
-        + results.old.size_bytes
-        + results.browser.size_bytes
-        + results.system.size_bytes
-        + results.empty.size_bytes
-        + results.duplicates.size_bytes
-        + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+    take_ownership: bool,
+) -> crate::Result<Option<DeletionLog>> {
+    clean_all_with_dedupe(
+        results,
+        skip_confirm,
+        mode,
+        permanent,
+        dry_run,
+        take_ownership,
+        DedupeMode::Delete,
+        false,
+    )
+}
+
+/// [`clean_all`], choosing what happens to duplicate copies. With `elevate`,
+/// steps that need administrator rights run in an elevated child process
+/// instead of being skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn clean_all_with_dedupe(
+    results: &ScanResults,
+    skip_confirm: bool,
+    mode: OutputMode,
+    permanent: bool,
+    dry_run: bool,
+    take_ownership: bool,
+    dedupe_mode: DedupeMode,
+    elevate: bool,
+) -> crate::Result<Option<DeletionLog>> {
+    let options = CleanOptions {
+        skip_confirm,
+        mode,
+        permanent,
+        dry_run,
+        take_ownership,
+        dedupe_mode,
+        elevate,
+    };
+    clean_with(results, &CleanerRegistry::default(), &options)
+}
+
+/// Run every cleaner in `registry` over `results`
+///
+/// Like [`clean_all`], for callers that add categories or hooks of their own.
+pub fn clean_with(
+    results: &ScanResults,
+    registry: &CleanerRegistry,
+    options: &CleanOptions,
+) -> crate::Result<Option<DeletionLog>> {
+    if results.summary_only {
+        return Err(crate::Error::SummaryOnly);
+    }
+    let CleanOptions {
+        skip_confirm,
+        mode,
+        permanent,
+        dry_run,
+        ..
+    } = *options;
+
+    let (total_items, total_bytes) = registry
+        .cleaners()
+        .iter()
+        .map(|c| c.planned(results, options))
+        .fold((0, 0), |(items, bytes), (i, b)| (items + i, bytes + b));
 
     if total_items == 0 {
         if mode != OutputMode::Quiet {
-            println!("{}", Theme::success("Nothing to clean."));
+            let skipped: Vec<String> = registry
+                .cleaners()
+                .iter()
+                .filter_map(|c| c.skipped(results, options))
+                .collect();
+            if skipped.is_empty() {
+                println!("{}", Theme::success("Nothing to clean."));
+            } else {
+                for note in skipped {
+                    println!("{}", note);
+                }
+            }
         }
-        return Ok(());
+        return Ok(None);
     }
 
     if dry_run && mode != OutputMode::Quiet {
@@ -216,21 +524,40 @@ pub fn clean_all(
         );
     }
 
+    // The prompt goes to stderr so `--json` output on stdout stays parseable
     if !skip_confirm && !dry_run {
-        print!(
-            "Delete {} items ({})? [yes/no]: ",
-            Theme::value(&total_items.to_string()),
-            Theme::warning(&bytesize::to_string(total_bytes, false))
-        );
+        let phrase =
+            super::required_phrase(total_bytes, total_items as u64, &Config::load().safety);
+        let confirmed = if let Some(phrase) = phrase {
+            eprintln!(
+                "{}",
+                Theme::error(&format!(
+                    "This will delete {} items ({}).",
+                    total_items, phrase
+                ))
+            );
+            eprint!(
+                "Type {} to confirm: ",
+                Theme::warning(&format!("\"{}\"", phrase))
+            );
+            let input = read_line_from_stdin()?;
+            super::phrase_matches(&phrase, &input)
+        } else {
+            eprint!(
+                "Delete {} items ({})? [yes/no]: ",
+                Theme::value(&total_items.to_string()),
+                Theme::warning(&bytesize::to_string(total_bytes, false))
+            );
 
-        let input = read_line_from_stdin()?;
-        let trimmed = input.trim().to_lowercase();
-        // Accept: "y", "yes" (and their uppercase variants)
-        let confirmed = trimmed == "y" || trimmed == "yes";
+            let input = read_line_from_stdin()?;
+            let trimmed = input.trim().to_lowercase();
+            // Accept: "y", "yes" (and their uppercase variants)
+            trimmed == "y" || trimmed == "yes"
+        };
 
         if !confirmed {
-            println!("{}", Theme::muted("Cancelled."));
-            return Ok(());
+            eprintln!("{}", Theme::muted("Cancelled."));
+            return Ok(None);
         }
     }
 
@@ -252,638 +579,27 @@ pub fn clean_all(
         None
     };
 
+    // Finish folders a previous run left staged for deletion, and drop
+    // quarantined items past their retention
+    if !dry_run {
+        background_delete::resume_pending();
+        quarantine::purge_expired();
+    }
+
+    let ctx = CleanContext {
+        options: *options,
+        progress: progress.as_ref(),
+        hooks: registry.hooks(),
+        quarantine: quarantine::configured(),
+    };
     let mut cleaned = 0u64;
     let mut cleaned_bytes = 0u64;
     let mut errors = 0;
-
-    // Clean cache (batch)
-    if results.cache.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.cache.paths,
-            "cache",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.cache.size_bytes;
-    }
-
-    // Clean application cache (batch)
-    if results.app_cache.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.app_cache.paths,
-            "application cache",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.app_cache.size_bytes;
-    }
-
-    // Clean temp (batch)
-    if results.temp.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.temp.paths,
-            "temp files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.temp.size_bytes;
-    }
-
-    // Clean trash
-    if results.trash.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Emptying Recycle Bin...");
-        }
-        if dry_run {
-            cleaned += results.trash.items as u64;
-            if let Some(ref pb) = progress {
-                pb.inc(results.trash.items as u64);
-            }
-            cleaned_bytes += results.trash.size_bytes;
-        } else {
-            match categories::trash::clean() {
-                Ok(()) => {
-                    cleaned += results.trash.items as u64;
-                    if let Some(ref pb) = progress {
-                        pb.inc(results.trash.items as u64);
-                    }
-                    cleaned_bytes += results.trash.size_bytes;
-                    if let Some(ref mut log) = history {
-                        log.log_success(
-                            Path::new("Recycle Bin"),
-                            results.trash.size_bytes,
-                            "trash",
-                            true,
-                        );
-                    }
-                }
-                Err(e) => {
-                    errors += 1;
-                    if let Some(ref mut log) = history {
-                        log.log_failure(
-                            Path::new("Recycle Bin"),
-                            results.trash.size_bytes,
-                            "trash",
-                            true,
-                            &e.to_string(),
-                        );
-                    }
-                    if mode != OutputMode::Quiet {
-                        eprintln!(
-                            "[WARNING] Failed to empty Recycle Bin: {}",
-                            Theme::error(&e.to_string())
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    // Clean build artifacts (batch)
-    if results.build.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.build.paths,
-            "build artifacts",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.build.size_bytes;
-    }
-
-    // Clean downloads (batch)
-    if results.downloads.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.downloads.paths,
-            "old downloads",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.downloads.size_bytes;
-    }
-
-    // Clean large files (batch)
-    if results.large.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.large.paths,
-            "large files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.large.size_bytes;
-    }
-
-    // Clean old files (batch)
-    if results.old.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.old.paths,
-            "old files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.old.size_bytes;
-    }
-
-    // Clean browser caches
-    if results.browser.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning browser caches...");
-        }
-        for path in &results.browser.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "browser", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "browser",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "browser", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "browser", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.browser.size_bytes;
-    }
-
-    // Clean system caches
-    if results.system.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning system caches...");
-        }
-        for path in &results.system.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "system", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "system",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "system", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "system", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.system.size_bytes;
-    }
-
-    // Clean empty folders
-    if results.empty.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning empty folders...");
-        }
-        for path in &results.empty.paths {
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match delete_with_precheck(path, permanent) {
-                    Ok(DeleteOutcome::Deleted) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, 0, "empty", permanent);
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                    Ok(DeleteOutcome::SkippedLocked) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                0,
-                                "empty",
-                                permanent,
-                                "Path is locked by another process",
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Path is locked by another process")
-                            );
-                        }
-                    }
-                    Ok(DeleteOutcome::SkippedPermission) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, 0, "empty", permanent, "Permission denied");
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error("Permission denied")
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, 0, "empty", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.empty.size_bytes;
-    }
-
-    // Clean duplicate files (batch)
-    if results.duplicates.items > 0 {
-        let (success, errs) = batch_clean_category_internal(
-            &results.duplicates.paths,
-            "duplicate files",
-            permanent,
-            dry_run,
-            progress.as_ref(),
-            history.as_mut(),
-            mode,
-        );
-        cleaned += success;
-        errors += errs;
-        cleaned_bytes += results.duplicates.size_bytes;
-    }
-
-    // Clean installed applications (batch)
-    if results.applications.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Uninstalling applications...");
-        }
-
-        // IMPORTANT: uninstalling applications is not safely restorable, even if permanent=false.
-        // We still honor `permanent` for leftover file deletion (Recycle Bin vs permanent),
-        // but we always log these as permanent to avoid offering restore.
-        let log_as_permanent = true;
-
-        for path in &results.applications.paths {
-            let size = categories::applications::get_app_size(path).unwrap_or_else(|| {
-                if path.is_dir() {
-                    utils::calculate_dir_size(path)
-                } else {
-                    utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-                }
-            });
-
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-                cleaned_bytes += size;
-                continue;
-            }
-
-            let display = categories::applications::get_app_display_name(path)
-                .unwrap_or_else(|| path.display().to_string());
-
-            // Tighten: uninstall must succeed before we delete any install/artifact paths.
-            // This avoids leaving the app "installed" but with missing files.
-            let mut had_error = false;
-            let Some(_uninstall_cmd) = categories::applications::get_app_uninstall_string(path)
-            else {
-                // No uninstall command - skip (had_error not set here since we continue)
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] Cannot uninstall {}: {}",
-                        Theme::secondary(&display),
-                        Theme::error("No uninstall command in registry")
-                    );
-                }
-                // Do not delete any files for this app.
-                // It would leave a broken, still-installed entry.
-                if let Some(ref mut log) = history {
-                    log.log_failure(
-                        path,
-                        size,
-                        "applications",
-                        log_as_permanent,
-                        "No uninstall command in registry; skipped to avoid breaking installed app",
-                    );
-                }
-                errors += 1;
-                continue;
-            };
-
-            if let Err(e) = categories::applications::uninstall(path) {
-                had_error = true;
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] Uninstall failed for {}: {}",
-                        Theme::secondary(&display),
-                        Theme::error(&e.to_string())
-                    );
-                }
-            }
-
-            // Post-check: if it's still installed, don't delete artifacts (tight/safe).
-            if !had_error && categories::applications::is_still_installed(path) {
-                had_error = true;
-                if mode != OutputMode::Quiet {
-                    eprintln!(
-                        "[WARNING] {} still appears installed after uninstall (may require reboot). Skipping artifact deletion.",
-                        Theme::secondary(&display)
-                    );
-                }
-            }
-
-            if !had_error {
-                // Only after uninstall succeeds and entry disappears: delete app-specific leftovers.
-                let artifacts = categories::applications::get_app_artifact_paths(path);
-                for artifact in artifacts {
-                    match delete_with_precheck(&artifact, permanent) {
-                        Ok(DeleteOutcome::Deleted) => {}
-                        Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
-                        Ok(DeleteOutcome::SkippedLocked | DeleteOutcome::SkippedPermission) => {
-                            had_error = true;
-                        }
-                        Err(_) => had_error = true,
-                    }
-                }
-            }
-
-            // Update counters/logs.
-            if had_error {
-                errors += 1;
-                if let Some(ref mut log) = history {
-                    log.log_failure(
-                        path,
-                        size,
-                        "applications",
-                        log_as_permanent,
-                        "Application uninstall and/or cleanup did not complete",
-                    );
-                }
-            } else {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-                cleaned_bytes += size;
-                if let Some(ref mut log) = history {
-                    log.log_success(path, size, "applications", log_as_permanent);
-                }
-            }
-        }
-    }
-
-    // Clean Windows Update files
-    if results.windows_update.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning Windows Update files...");
-        }
-        for path in &results.windows_update.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match categories::windows_update::clean(path) {
-                    Ok(()) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "windows_update", permanent);
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(
-                                path,
-                                size,
-                                "windows_update",
-                                permanent,
-                                &e.to_string(),
-                            );
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.windows_update.size_bytes;
-    }
-
-    // Clean Event Logs
-    if results.event_logs.items > 0 {
-        if let Some(ref pb) = progress {
-            pb.set_message("Cleaning Event Logs...");
-        }
-        for path in &results.event_logs.paths {
-            let size = if path.is_dir() {
-                utils::calculate_dir_size(path)
-            } else {
-                utils::safe_metadata(path).map(|m| m.len()).unwrap_or(0)
-            };
-            if dry_run {
-                cleaned += 1;
-                if let Some(ref pb) = progress {
-                    pb.inc(1);
-                }
-            } else {
-                match categories::event_logs::clean(path) {
-                    Ok(()) => {
-                        cleaned += 1;
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        if let Some(ref mut log) = history {
-                            log.log_success(path, size, "event_logs", permanent);
-                        }
-                    }
-                    Err(e) => {
-                        errors += 1;
-                        if let Some(ref mut log) = history {
-                            log.log_failure(path, size, "event_logs", permanent, &e.to_string());
-                        }
-                        if mode != OutputMode::Quiet {
-                            eprintln!(
-                                "[WARNING] Failed to clean {}: {}",
-                                Theme::secondary(&path.display().to_string()),
-                                Theme::error(&e.to_string())
-                            );
-                        }
-                    }
-                }
-            }
-        }
-        cleaned_bytes += results.event_logs.size_bytes;
+    for cleaner in registry.cleaners() {
+        let outcome = cleaner.clean(results, &ctx, history.as_mut());
+        cleaned += outcome.cleaned;
+        errors += outcome.errors;
+        cleaned_bytes += outcome.bytes;
     }
 
     // Finish progress bar
@@ -892,7 +608,7 @@ pub fn clean_all(
     }
 
     // Save history log (if not dry run)
-    let log_path = if let Some(log) = history {
+    let log_path = if let Some(ref log) = history {
         match log.save() {
             Ok(path) => Some(path),
             Err(e) => {
@@ -940,7 +656,34 @@ pub fn clean_all(
         }
     }
 
-    Ok(())
+    // Large folders are already gone from their paths but still being removed;
+    // exiting would pause them until the next run
+    let pending = background_delete::running();
+    if !pending.is_empty() {
+        let spinner = (mode != OutputMode::Quiet).then(|| {
+            progress::create_spinner(&format!(
+                "Reclaiming space from {} large folder{}...",
+                pending.len(),
+                if pending.len() == 1 { "" } else { "s" }
+            ))
+        });
+        background_delete::wait(|jobs| {
+            if let Some(sp) = &spinner {
+                let freed: u64 = jobs.iter().map(|j| j.bytes_freed).sum();
+                sp.set_message(format!(
+                    "Reclaiming space from {} large folder{}... {} freed",
+                    jobs.len(),
+                    if jobs.len() == 1 { "" } else { "s" },
+                    bytesize::to_string(freed, false)
+                ));
+            }
+        });
+        if let Some(sp) = spinner {
+            sp.finish_and_clear();
+        }
+    }
+
+    Ok(history)
 }
 
 #[cfg(test)]
@@ -960,7 +703,7 @@ mod tests {
 
         // Should return Ok without doing anything
         // Use Quiet mode in tests to avoid spinner thread issues
-        let result = clean_all(&results, true, OutputMode::Quiet, false, false);
+        let result = clean_all(&results, true, OutputMode::Quiet, false, false, false);
         assert!(result.is_ok());
     }
 
@@ -977,7 +720,7 @@ mod tests {
 
         // Dry run should not delete the file
         // Use Quiet mode in tests to avoid spinner thread issues
-        let result = clean_all(&results, true, OutputMode::Quiet, false, true);
+        let result = clean_all(&results, true, OutputMode::Quiet, false, true, false);
         assert!(result.is_ok());
         assert!(file.exists()); // File should still exist
     }
@@ -0,0 +1,232 @@
+//! Cross-volume recycle detection.
+//!
+//! The Recycle Bin only takes items cheaply when their volume has a bin of its
+//! own. Items in folders where another volume is mounted, or on removable and
+//! network drives, are copied by the shell or deleted outright, so large ones
+//! are routed by `[cross_volume]` config before recycling.
+
+use crate::config::{CrossVolumePolicy, CrossVolumeSettings};
+use crate::utils;
+use std::path::{Path, PathBuf};
+
+/// Where recycling a path would put it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecycleRoute {
+    /// The path's volume has its own Recycle Bin
+    Local,
+    /// The path lives on `volume`, which the Recycle Bin can't take without copying
+    CrossVolume { volume: PathBuf },
+}
+
+/// Whether the volume mounted at `root` is a fixed local disk
+#[cfg(windows)]
+fn is_fixed_drive(root: &Path) -> bool {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    const DRIVE_FIXED: u32 = 3;
+    let wide: Vec<u16> = root
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    // SAFETY: `wide` is NUL-terminated.
+    unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) == DRIVE_FIXED }
+}
+
+/// Classify how recycling `path` would behave
+#[cfg(windows)]
+pub fn recycle_route(path: &Path) -> RecycleRoute {
+    let Some(volume) = utils::mount_root(path) else {
+        return RecycleRoute::Local;
+    };
+    let drive_root = drive_root(path);
+    if is_fixed_drive(&volume) && same_root(&volume, &drive_root) {
+        RecycleRoute::Local
+    } else {
+        RecycleRoute::CrossVolume { volume }
+    }
+}
+
+#[cfg(not(windows))]
+pub fn recycle_route(_path: &Path) -> RecycleRoute {
+    RecycleRoute::Local
+}
+
+/// Drive prefix and root of `path` (e.g. `C:\`)
+#[cfg_attr(not(windows), allow(dead_code))]
+fn drive_root(path: &Path) -> PathBuf {
+    use std::path::Component;
+    path.components()
+        .take_while(|c| matches!(c, Component::Prefix(_) | Component::RootDir))
+        .collect()
+}
+
+/// Compare volume roots ignoring case and trailing separators
+#[cfg_attr(not(windows), allow(dead_code))]
+fn same_root(a: &Path, b: &Path) -> bool {
+    let norm = |p: &Path| {
+        p.to_string_lossy()
+            .trim_end_matches(['\\', '/'])
+            .to_lowercase()
+    };
+    norm(a) == norm(b)
+}
+
+/// Large cross-volume items routed to permanent deletion
+#[derive(Debug, Default)]
+pub struct RecycleSplit {
+    /// Paths to send to the Recycle Bin
+    pub recycle: Vec<PathBuf>,
+    /// Paths to delete permanently instead
+    pub permanent: Vec<PathBuf>,
+    /// Bytes routed to permanent deletion
+    pub permanent_bytes: u64,
+    /// Estimated extra I/O for cross-volume items still being recycled
+    /// (each is read and written once more by the copy)
+    pub extra_io_bytes: u64,
+    /// Volumes involved, for the notice
+    pub volumes: Vec<PathBuf>,
+}
+
+impl RecycleSplit {
+    /// One-line notice for the user, if anything was rerouted or will be slow
+    pub fn notice(&self, category: &str) -> Option<String> {
+        let volumes = self
+            .volumes
+            .iter()
+            .map(|v| utils::display_path(v))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !self.permanent.is_empty() {
+            Some(format!(
+                "{}: {} large item(s) ({}) on {} deleted permanently; recycling them would copy across volumes",
+                category,
+                self.permanent.len(),
+                bytesize::to_string(self.permanent_bytes, false),
+                volumes
+            ))
+        } else if self.extra_io_bytes > 0 {
+            Some(format!(
+                "{}: recycling items on {} copies them across volumes (~{} extra I/O)",
+                category,
+                volumes,
+                bytesize::to_string(self.extra_io_bytes, false)
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Split paths about to be recycled according to `[cross_volume]` settings.
+///
+/// `route` and `size_of` are injected so callers can reuse known sizes; sizes
+/// are only computed for cross-volume items.
+pub fn split_for_recycle(
+    paths: &[PathBuf],
+    category: &str,
+    settings: &CrossVolumeSettings,
+    route: impl Fn(&Path) -> RecycleRoute,
+    size_of: impl Fn(&Path) -> u64,
+) -> RecycleSplit {
+    let mut split = RecycleSplit::default();
+    if !settings.enabled {
+        split.recycle = paths.to_vec();
+        return split;
+    }
+
+    let policy = settings.policy_for(category);
+    let threshold = settings.large_item_mb * 1024 * 1024;
+
+    for path in paths {
+        let RecycleRoute::CrossVolume { volume } = route(path) else {
+            split.recycle.push(path.clone());
+            continue;
+        };
+        if !split.volumes.contains(&volume) {
+            split.volumes.push(volume);
+        }
+
+        let size = size_of(path);
+        if size >= threshold && policy == CrossVolumePolicy::Permanent {
+            split.permanent_bytes += size;
+            split.permanent.push(path.clone());
+        } else {
+            split.extra_io_bytes += size * 2;
+            split.recycle.push(path.clone());
+        }
+    }
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(path: &Path) -> RecycleRoute {
+        if path.starts_with("/mnt") {
+            RecycleRoute::CrossVolume {
+                volume: PathBuf::from("/mnt"),
+            }
+        } else {
+            RecycleRoute::Local
+        }
+    }
+
+    fn size_of(path: &Path) -> u64 {
+        if path.ends_with("big") {
+            1024 * 1024 * 1024
+        } else {
+            10
+        }
+    }
+
+    #[test]
+    fn routes_large_cross_volume_items_by_category_policy() {
+        // Recycling is the default: nothing is deleted permanently unasked
+        let paths = vec![PathBuf::from("/mnt/cache/big")];
+        let split = split_for_recycle(
+            &paths,
+            "cache",
+            &CrossVolumeSettings::default(),
+            route,
+            size_of,
+        );
+        assert!(split.permanent.is_empty());
+
+        let settings = CrossVolumeSettings {
+            policy: CrossVolumePolicy::Permanent,
+            ..Default::default()
+        };
+        let paths = vec![
+            PathBuf::from("/home/cache/big"),
+            PathBuf::from("/mnt/cache/big"),
+            PathBuf::from("/mnt/cache/small"),
+        ];
+
+        let split = split_for_recycle(&paths, "cache", &settings, route, size_of);
+        assert_eq!(split.permanent, vec![PathBuf::from("/mnt/cache/big")]);
+        assert_eq!(split.recycle.len(), 2);
+        assert_eq!(split.extra_io_bytes, 20);
+        assert!(split.notice("cache").unwrap().contains("permanently"));
+
+        // User files are recycled even under a permanent policy
+        let split = split_for_recycle(&paths, "Large Files", &settings, route, size_of);
+        assert!(split.permanent.is_empty());
+        assert!(split.notice("large").unwrap().contains("extra I/O"));
+    }
+
+    #[test]
+    fn disabled_keeps_everything_in_recycle() {
+        let settings = CrossVolumeSettings {
+            enabled: false,
+            ..Default::default()
+        };
+        let paths = vec![PathBuf::from("/mnt/big")];
+        let split = split_for_recycle(&paths, "cache", &settings, route, size_of);
+        assert_eq!(split.recycle, paths);
+        assert!(split.notice("cache").is_none());
+    }
+}
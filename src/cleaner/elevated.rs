@@ -0,0 +1,372 @@
+//! Cleaning steps that need administrator rights.
+//!
+//! Machine-wide caches, VM leftovers, the Windows Update cache and event logs
+//! can only be removed elevated. When wole isn't, `clean` skips those steps
+//! with one summary warning or, with `--elevate`, hands just those steps to an
+//! elevated copy of itself (a single UAC prompt) and merges what it deleted
+//! into the session's log. The two processes exchange JSON files in the cache
+//! folder: a request listing the steps, readable only by the current user and
+//! under a random name, and an outcome holding the records.
+//!
+//! The child trusts the request no further than it has to: it refuses
+//! categories that don't need administrator rights, paths outside the
+//! category's folders, and paths that changed since the parent's scan
+//! (checked against the identities the scan recorded, see `path_identity`).
+
+use super::category_cleaning::{batch_clean_category_internal, path_size};
+use super::path_identity::{self, FileIdentity};
+use super::strategy::{CleanContext, CleanOptions, DefaultHooks};
+use crate::categories;
+use crate::history::{DeletionLog, DeletionRecord, FailureInfo};
+use crate::output::{OutputMode, ScanResults};
+use crate::theme::Theme;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Hidden argument that runs a request file's steps in the elevated child
+pub const CLEAN_ARG: &str = "--clean-elevated";
+
+/// One category's worth of paths that needs administrator rights
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElevatedStep {
+    /// Category name recorded in the deletion log
+    pub category: String,
+    /// What the step removes, for warnings
+    pub description: String,
+    pub paths: Vec<PathBuf>,
+    pub bytes: u64,
+}
+
+/// Steps handed to the elevated child
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElevatedRequest {
+    pub steps: Vec<ElevatedStep>,
+    pub permanent: bool,
+    /// What the parent's scan recorded for the steps' paths and their folders
+    #[serde(default)]
+    pub(crate) identities: HashMap<PathBuf, FileIdentity>,
+}
+
+/// What the elevated child did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElevatedOutcome {
+    pub cleaned: u64,
+    pub errors: u64,
+    pub records: Vec<DeletionRecord>,
+}
+
+impl ElevatedOutcome {
+    /// Bytes actually removed
+    pub fn cleaned_bytes(&self) -> u64 {
+        self.records
+            .iter()
+            .filter(|r| r.success)
+            .map(|r| r.size_bytes)
+            .sum()
+    }
+}
+
+/// The parts of `results` that only an elevated process can clean
+pub fn pending_steps(results: &ScanResults) -> Vec<ElevatedStep> {
+    [
+        (
+            "machine cache",
+            "machine-wide caches",
+            &results.cache.machine_paths,
+            results.cache.machine_bytes,
+        ),
+        (
+            "vm leftovers",
+            "Hyper-V/Sandbox leftovers",
+            &results.system.machine_paths,
+            results.system.machine_bytes,
+        ),
+        (
+            "windows_update",
+            "Windows Update files",
+            &results.windows_update.paths,
+            results.windows_update.size_bytes,
+        ),
+        (
+            "event_logs",
+            "event logs",
+            &results.event_logs.paths,
+            results.event_logs.size_bytes,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, _, paths, _)| !paths.is_empty())
+    .map(|(category, description, paths, bytes)| ElevatedStep {
+        category: category.to_string(),
+        description: description.to_string(),
+        paths: paths.clone(),
+        bytes,
+    })
+    .collect()
+}
+
+/// The folders an elevated step of `category` may delete in, or None for
+/// categories that don't need administrator rights
+fn admin_roots(category: &str) -> Option<Vec<PathBuf>> {
+    Some(match category {
+        "machine cache" => categories::cache::machine_cache_roots(),
+        "vm leftovers" => categories::vm_leftovers::roots(),
+        "windows_update" => categories::windows_update::roots(),
+        "event_logs" => vec![categories::event_logs::logs_dir()],
+        _ => return None,
+    })
+}
+
+/// Split `paths` into those the elevated child may delete (inside `roots`
+/// and unchanged since the scan) and the refused ones, with why
+fn vet_paths(
+    paths: &[PathBuf],
+    roots: &[PathBuf],
+    identities: &HashMap<PathBuf, FileIdentity>,
+) -> (Vec<PathBuf>, Vec<(PathBuf, anyhow::Error)>) {
+    let mut allowed = Vec::with_capacity(paths.len());
+    let mut refused = Vec::new();
+    for path in paths {
+        let inside = path.is_absolute()
+            && !path
+                .components()
+                .any(|c| matches!(c, Component::ParentDir | Component::CurDir))
+            && roots.iter().any(|root| path.starts_with(root));
+        if !inside {
+            refused.push((
+                path.clone(),
+                anyhow::anyhow!(
+                    "Refusing to delete {}: it is outside the folders of its category",
+                    path.display()
+                ),
+            ));
+        } else if let Err(e) = path_identity::verify_against(identities, path) {
+            refused.push((path.clone(), e.into()));
+        } else {
+            allowed.push(path.clone());
+        }
+    }
+    (allowed, refused)
+}
+
+/// One warning covering every step skipped for lack of administrator rights
+pub fn skipped_warning(steps: &[ElevatedStep]) -> String {
+    let parts: Vec<String> = steps
+        .iter()
+        .map(|s| {
+            format!(
+                "{} {} ({})",
+                s.paths.len(),
+                s.description,
+                bytesize::to_string(s.bytes, false)
+            )
+        })
+        .collect();
+    format!(
+        "{} Skipped {}: administrator rights required (rerun with --elevate)",
+        Theme::warning("[!]"),
+        parts.join(", ")
+    )
+}
+
+/// Clean one step in this process. Returns (cleaned, errors).
+pub(super) fn run_step(
+    ctx: &CleanContext,
+    step: &ElevatedStep,
+    mut history: Option<&mut DeletionLog>,
+) -> (u64, u64) {
+    let clean_one: fn(&Path) -> Result<()> = match step.category.as_str() {
+        "windows_update" => categories::windows_update::clean,
+        "event_logs" => categories::event_logs::clean,
+        _ => return batch_clean_category_internal(ctx, &step.paths, &step.category, history),
+    };
+
+    ctx.set_message(format!("Cleaning {}...", step.description));
+    let permanent = ctx.options.permanent;
+    let (paths, mut errors) = ctx.admit(&step.paths, &step.category, history.as_deref_mut());
+    let mut cleaned = 0;
+    for path in &paths {
+        if ctx.options.dry_run {
+            cleaned += 1;
+            ctx.inc(1);
+            continue;
+        }
+        let size = path_size(path);
+        match clean_one(path) {
+            Ok(()) => {
+                ctx.inc(1);
+                if ctx.deleted(
+                    path,
+                    size,
+                    &step.category,
+                    permanent,
+                    history.as_deref_mut(),
+                ) {
+                    cleaned += 1;
+                } else {
+                    errors += 1;
+                }
+            }
+            Err(e) => {
+                errors += 1;
+                let info = FailureInfo::from_error(&e);
+                ctx.fail_with(path, size, &step.category, &info, history.as_deref_mut());
+            }
+        }
+    }
+    (cleaned, errors)
+}
+
+/// Where the child writes the outcome for `request_path`
+fn outcome_path(request_path: &Path) -> PathBuf {
+    request_path.with_extension("outcome.json")
+}
+
+/// Run `steps` in an elevated child (UAC prompt) and wait for its outcome
+pub fn run_elevated(steps: &[ElevatedStep], permanent: bool) -> Result<ElevatedOutcome> {
+    let paths: Vec<&Path> = steps
+        .iter()
+        .flat_map(|s| &s.paths)
+        .map(PathBuf::as_path)
+        .collect();
+    let request = ElevatedRequest {
+        steps: steps.to_vec(),
+        permanent,
+        identities: path_identity::recorded_for(&paths),
+    };
+    let request_path = crate::app_data::cache_dir()?.join(format!(
+        "clean_elevated_{}.json",
+        crate::app_data::random_hex(16)?
+    ));
+    let json = serde_json::to_string(&request).context("Failed to serialize elevated request")?;
+    crate::app_data::write_private(&request_path, json.as_bytes())?;
+
+    let outcome_path = outcome_path(&request_path);
+    let _ = std::fs::remove_file(&outcome_path);
+    let ran = crate::elevation::run_elevated(CLEAN_ARG, &request_path);
+    let _ = std::fs::remove_file(&request_path);
+    ran?;
+
+    let content =
+        std::fs::read_to_string(&outcome_path).context("The elevated clean reported no results")?;
+    let _ = std::fs::remove_file(&outcome_path);
+    serde_json::from_str(&content).context("Failed to parse elevated clean results")
+}
+
+/// Entry point of the elevated child: clean the request's steps and write
+/// the outcome next to the request file
+pub fn run_request(request_path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(request_path)
+        .with_context(|| format!("Failed to read request file: {}", request_path.display()))?;
+    let request: ElevatedRequest = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse request file: {}", request_path.display()))?;
+    let roots = request
+        .steps
+        .iter()
+        .map(|step| {
+            admin_roots(&step.category).with_context(|| {
+                format!(
+                    "Refusing elevated request: '{}' doesn't need administrator rights",
+                    step.category
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let ctx = CleanContext {
+        options: CleanOptions {
+            skip_confirm: true,
+            mode: OutputMode::Quiet,
+            permanent: request.permanent,
+            ..CleanOptions::default()
+        },
+        progress: None,
+        hooks: &DefaultHooks,
+        quarantine: super::quarantine::configured(),
+    };
+    let mut log = DeletionLog::new();
+    let mut outcome = ElevatedOutcome::default();
+    for (step, roots) in request.steps.iter().zip(&roots) {
+        let (paths, refused) = vet_paths(&step.paths, roots, &request.identities);
+        for (path, e) in refused {
+            outcome.errors += 1;
+            let info = FailureInfo::from_error(&e);
+            ctx.fail_with(&path, 0, &step.category, &info, Some(&mut log));
+        }
+        let step = ElevatedStep {
+            paths,
+            ..step.clone()
+        };
+        let (cleaned, errors) = run_step(&ctx, &step, Some(&mut log));
+        outcome.cleaned += cleaned;
+        outcome.errors += errors;
+    }
+    // Large folders finish deleting in the background; the parent only
+    // learns about them once this process is done
+    super::background_delete::wait(|_| {});
+    outcome.records = log.records;
+
+    let json = serde_json::to_string(&outcome).context("Failed to serialize elevated results")?;
+    let path = outcome_path(request_path);
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write results file: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_steps_cover_admin_only_categories() {
+        let mut results = ScanResults::default();
+        results.cache.machine_paths = vec![PathBuf::from("/ProgramData/pkg")];
+        results.cache.machine_bytes = 2048;
+        results.event_logs.paths = vec![PathBuf::from("/Windows/Logs/a.evtx")];
+        results.event_logs.size_bytes = 1024;
+        // User-level caches don't need elevation
+        results.temp.paths = vec![PathBuf::from("/tmp/x")];
+
+        let steps = pending_steps(&results);
+        let categories: Vec<&str> = steps.iter().map(|s| s.category.as_str()).collect();
+        assert_eq!(categories, ["machine cache", "event_logs"]);
+
+        let warning = skipped_warning(&steps);
+        assert!(warning.contains("1 machine-wide caches"));
+        assert!(warning.contains("1 event logs"));
+        assert!(warning.contains("--elevate"));
+    }
+
+    #[test]
+    fn requests_are_held_to_admin_categories_and_their_folders() {
+        assert!(admin_roots("temp").is_none());
+        assert!(admin_roots("event_logs").is_some());
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("Download");
+        let elsewhere = temp_dir.path().join("Documents");
+        std::fs::create_dir_all(root.join("update")).unwrap();
+        std::fs::create_dir_all(&elsewhere).unwrap();
+        let update = root.join("update");
+        let identities = path_identity::identify(&update)
+            .map(|id| HashMap::from([(update.clone(), id)]))
+            .unwrap();
+
+        let requested = [
+            update.clone(),
+            elsewhere.clone(),
+            root.join("..").join("Documents"),
+        ];
+        let (allowed, refused) = vet_paths(&requested, std::slice::from_ref(&root), &identities);
+        assert_eq!(allowed, vec![update.clone()]);
+        assert_eq!(refused.len(), 2);
+
+        // Swapped for another folder after the scan
+        std::fs::remove_dir(&update).unwrap();
+        std::fs::rename(&elsewhere, &update).unwrap();
+        let (allowed, refused) = vet_paths(std::slice::from_ref(&update), &[root], &identities);
+        assert!(allowed.is_empty());
+        assert!(refused[0].1.is::<path_identity::PathChangedError>());
+    }
+}
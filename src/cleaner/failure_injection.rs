@@ -0,0 +1,154 @@
+//! Simulated deletion failures for robustness tests.
+//!
+//! Built only with the `failure-injection` feature. A [`FaultPlan`] makes
+//! deletions under its `root` fail at the configured rates, the way they fail
+//! in the field: locked by another process, access denied, vanished before
+//! the delete ran, or no Recycle Bin. Which path gets which fault is a pure
+//! function of the path and the seed, so tests can predict the outcome of
+//! every path with [`fault_for`] and assert the cleaner's accounting.
+//!
+//! Plans apply to deletions in every thread, so each test should use its own
+//! root; a plan is removed when its [`FaultGuard`] is dropped.
+
+use lazy_static::lazy_static;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// ERROR_SHARING_VIOLATION, which the cleaner classifies as locked
+const SHARING_VIOLATION: i32 = 32;
+
+lazy_static! {
+    static ref PLANS: Mutex<Vec<(u64, FaultPlan)>> = Mutex::new(Vec::new());
+    static ref NEXT_ID: Mutex<u64> = Mutex::new(0);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// In use by another process
+    Locked,
+    /// The account may not delete it
+    AccessDenied,
+    /// Removed by something else just before the delete
+    Vanished,
+    /// The Recycle Bin can't take it (only affects recycling)
+    TrashUnavailable,
+}
+
+/// Failure rates (0.0-1.0) for deletions under `root`
+#[derive(Debug, Clone, Default)]
+pub struct FaultPlan {
+    pub root: PathBuf,
+    pub seed: u64,
+    pub locked: f64,
+    pub access_denied: f64,
+    pub vanished: f64,
+    pub trash_unavailable: f64,
+}
+
+impl FaultPlan {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Which fault, if any, a deletion of `path` hits
+    pub fn fault_for(&self, path: &Path) -> Option<Fault> {
+        if !path.starts_with(&self.root) {
+            return None;
+        }
+        let mut hasher = DefaultHasher::new();
+        self.seed.hash(&mut hasher);
+        path.hash(&mut hasher);
+        let roll = (hasher.finish() % 1_000_000) as f64 / 1_000_000.0;
+
+        let mut threshold = 0.0;
+        for (rate, fault) in [
+            (self.locked, Fault::Locked),
+            (self.access_denied, Fault::AccessDenied),
+            (self.vanished, Fault::Vanished),
+            (self.trash_unavailable, Fault::TrashUnavailable),
+        ] {
+            threshold += rate;
+            if roll < threshold {
+                return Some(fault);
+            }
+        }
+        None
+    }
+
+    /// Start injecting; faults stop when the guard is dropped
+    pub fn install(self) -> FaultGuard {
+        let mut next = NEXT_ID.lock().unwrap_or_else(|e| e.into_inner());
+        let id = *next;
+        *next += 1;
+        PLANS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((id, self));
+        FaultGuard { id }
+    }
+}
+
+/// Keeps a plan installed
+#[must_use = "the plan is removed when the guard is dropped"]
+pub struct FaultGuard {
+    id: u64,
+}
+
+impl Drop for FaultGuard {
+    fn drop(&mut self) {
+        PLANS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// The fault an installed plan assigns to `path`
+pub fn fault_for(path: &Path) -> Option<Fault> {
+    PLANS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find_map(|(_, plan)| plan.fault_for(path))
+}
+
+/// Fail a deletion of `path` as its fault dictates. `recycle` is whether it's
+/// going to the Recycle Bin; a vanished path is actually removed so the
+/// cleaner sees what it would in the field.
+pub(crate) fn inject(path: &Path, recycle: bool) -> io::Result<()> {
+    match fault_for(path) {
+        None => Ok(()),
+        Some(Fault::Locked) => Err(io::Error::from_raw_os_error(SHARING_VIOLATION)),
+        Some(Fault::AccessDenied) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Access is denied (injected)",
+        )),
+        Some(Fault::Vanished) => {
+            let _ = if path.is_dir() {
+                std::fs::remove_dir_all(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Path vanished before deletion (injected)",
+            ))
+        }
+        Some(Fault::TrashUnavailable) if recycle => Err(io::Error::other(
+            "Recycle Bin is unavailable for this drive (injected)",
+        )),
+        Some(Fault::TrashUnavailable) => Ok(()),
+    }
+}
+
+/// Whether a batch recycle of `paths` should fail as a whole, the way the
+/// Shell refuses a batch when any item in it can't be recycled
+pub(crate) fn batch_fails(paths: &[PathBuf]) -> bool {
+    paths.iter().any(|p| fault_for(p).is_some())
+}
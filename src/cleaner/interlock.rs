@@ -0,0 +1,59 @@
+//! Typed confirmation for very large deletions
+//!
+//! Answering "y" is muscle memory; for deletion sets above the `[safety]`
+//! thresholds the user has to type the total size shown in the prompt
+//! instead, in both the CLI and the TUI.
+
+use crate::config::SafetySettings;
+
+const GB: u64 = 1_000_000_000;
+
+/// The phrase the user must type to confirm, or None when a plain "y" is enough
+pub fn required_phrase(
+    total_bytes: u64,
+    total_items: u64,
+    settings: &SafetySettings,
+) -> Option<String> {
+    let over_size = settings.typed_confirm_gb > 0 && total_bytes >= settings.typed_confirm_gb * GB;
+    let over_items =
+        settings.typed_confirm_items > 0 && total_items >= settings.typed_confirm_items;
+    (over_size || over_items).then(|| bytesize::to_string(total_bytes, false))
+}
+
+/// Whether `input` matches `phrase`, ignoring case and whitespace
+pub fn phrase_matches(phrase: &str, input: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    normalize(phrase) == normalize(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn large_cleans_require_the_size_typed() {
+        let settings = Config::default().safety;
+        assert_eq!(required_phrase(5 * GB, 200, &settings), None);
+
+        let phrase = required_phrase(142_300_000_000, 200, &settings).unwrap();
+        assert_eq!(phrase, "142.3 GB");
+        assert!(phrase_matches(&phrase, "142.3gb"));
+        assert!(!phrase_matches(&phrase, "y"));
+
+        // Item count alone also triggers it
+        assert!(required_phrase(GB, 250_000, &settings).is_some());
+
+        let off = SafetySettings {
+            typed_confirm_gb: 0,
+            typed_confirm_items: 0,
+            ..settings
+        };
+        assert_eq!(required_phrase(500 * GB, 1_000_000, &off), None);
+    }
+}
@@ -0,0 +1,347 @@
+//! Path identity feature.
+//!
+//! This module owns the check that a path about to be deleted is still the
+//! file or folder the scan found. Between scan and clean a path (or one of
+//! its parent folders) could be swapped for a symlink or junction pointing
+//! at data the user never reviewed. Scans record the identity (device and
+//! inode, or volume serial and file index on Windows) of each result and of
+//! every folder above it; before deleting, neither the path nor any of those
+//! folders may have become a link or changed identity. The records are reset
+//! when the next scan starts.
+
+use crate::output::ScanResults;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Which file a path refers to, without following a final link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) struct FileIdentity {
+    device: u64,
+    index: u64,
+    is_link: bool,
+}
+
+/// How a path changed since it was scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathChange {
+    /// It is now a symlink, junction or other reparse point
+    BecameLink,
+    /// It now refers to a different file or folder
+    Replaced,
+    /// A parent folder is now a symlink, junction or other reparse point
+    ParentBecameLink,
+    /// A parent folder now refers to a different folder
+    ParentReplaced,
+}
+
+/// Refusal to delete a path that changed since it was scanned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathChangedError {
+    pub path: PathBuf,
+    pub change: PathChange,
+}
+
+impl fmt::Display for PathChangedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.change {
+            PathChange::BecameLink => write!(
+                f,
+                "Refusing to delete {}: it was replaced by a symlink or junction since the scan",
+                self.path.display()
+            ),
+            PathChange::Replaced => write!(
+                f,
+                "Refusing to delete {}: it is no longer the file or folder that was scanned",
+                self.path.display()
+            ),
+            PathChange::ParentBecameLink => write!(
+                f,
+                "Refusing to delete {}: one of its parent folders was replaced by a symlink or junction since the scan",
+                self.path.display()
+            ),
+            PathChange::ParentReplaced => write!(
+                f,
+                "Refusing to delete {}: one of its parent folders is no longer the folder that was scanned",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathChangedError {}
+
+/// Identities recorded by the current scan
+type Recorded = Mutex<HashMap<PathBuf, FileIdentity>>;
+
+fn recorded() -> &'static Recorded {
+    static RECORDED: OnceLock<Recorded> = OnceLock::new();
+    RECORDED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[cfg(unix)]
+pub(crate) fn identify(path: &Path) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+
+    let meta = std::fs::symlink_metadata(path).ok()?;
+    Some(FileIdentity {
+        device: meta.dev(),
+        index: meta.ino(),
+        is_link: meta.file_type().is_symlink(),
+    })
+}
+
+#[cfg(windows)]
+pub(crate) fn identify(path: &Path) -> Option<FileIdentity> {
+    use std::fs::OpenOptions;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION,
+    };
+
+    const FILE_SHARE_ALL: u32 = 0x1 | 0x2 | 0x4;
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x02000000;
+    const FILE_FLAG_OPEN_REPARSE_POINT: u32 = 0x00200000;
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0400;
+
+    // Open the path itself (not a link's target) without read or write access
+    let file = OpenOptions::new()
+        .access_mode(0)
+        .share_mode(FILE_SHARE_ALL)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS | FILE_FLAG_OPEN_REPARSE_POINT)
+        .open(crate::utils::to_long_path(path))
+        .ok()?;
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &mut info) }.ok()?;
+    Some(FileIdentity {
+        device: info.dwVolumeSerialNumber as u64,
+        index: ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64,
+        is_link: info.dwFileAttributes & FILE_ATTRIBUTE_REPARSE_POINT != 0,
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn identify(path: &Path) -> Option<FileIdentity> {
+    let meta = std::fs::symlink_metadata(path).ok()?;
+    Some(FileIdentity {
+        device: 0,
+        index: 0,
+        is_link: meta.file_type().is_symlink(),
+    })
+}
+
+/// Remember the identity of `paths` and of the folders above them as they are now
+pub fn record(paths: &[&Path]) {
+    record_in(recorded(), paths);
+}
+
+fn record_in(recorded: &Recorded, paths: &[&Path]) {
+    let mut pending: HashSet<&Path> = HashSet::new();
+    {
+        let recorded = recorded.lock().unwrap_or_else(|e| e.into_inner());
+        for path in paths {
+            pending.insert(path);
+            // Siblings share parents; stop at the first one already known
+            for parent in path.ancestors().skip(1) {
+                if parent.as_os_str().is_empty()
+                    || recorded.contains_key(parent)
+                    || !pending.insert(parent)
+                {
+                    break;
+                }
+            }
+        }
+    }
+    let identities: Vec<(PathBuf, FileIdentity)> = pending
+        .into_par_iter()
+        .filter_map(|path| identify(path).map(|id| (path.to_path_buf(), id)))
+        .collect();
+    recorded
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .extend(identities);
+}
+
+/// Forget what earlier scans recorded; called when a new scan starts
+pub fn reset() {
+    recorded().lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Remember the identity of every path a scan found
+pub fn record_scan(results: &ScanResults) {
+    let paths: Vec<&Path> = results
+        .by_category()
+        .iter()
+        .flat_map(|(_, category)| {
+            category
+                .paths
+                .iter()
+                .chain(&category.hidden_paths)
+                .chain(&category.machine_paths)
+        })
+        .map(PathBuf::as_path)
+        .collect();
+    record(&paths);
+}
+
+/// What the scan recorded for `paths` and the folders above them, to hand to
+/// another process (the elevated cleaner) for `verify_against`
+pub(crate) fn recorded_for(paths: &[&Path]) -> HashMap<PathBuf, FileIdentity> {
+    let recorded = recorded().lock().unwrap_or_else(|e| e.into_inner());
+    paths
+        .iter()
+        .flat_map(|path| path.ancestors())
+        .filter_map(|path| Some((path.to_path_buf(), *recorded.get(path)?)))
+        .collect()
+}
+
+/// How `current` differs from the identity recorded by the scan, if at all
+fn compare(scanned: Option<FileIdentity>, current: FileIdentity) -> Option<PathChange> {
+    match scanned {
+        Some(scanned) if scanned == current => None,
+        Some(scanned) if current.is_link && !scanned.is_link => Some(PathChange::BecameLink),
+        Some(_) => Some(PathChange::Replaced),
+        None if current.is_link => Some(PathChange::BecameLink),
+        None => None,
+    }
+}
+
+/// Check that `path` is not a link (unless it already was one when scanned)
+/// and still has the identity recorded by the scan, and that the parent
+/// folders recorded with it are unchanged too. Paths that were never
+/// recorded only get the link check.
+pub(crate) fn verify(path: &Path) -> Result<(), PathChangedError> {
+    verify_in(recorded(), path)
+}
+
+/// `verify` against identities recorded in another process (`recorded_for`)
+pub(crate) fn verify_against(
+    identities: &HashMap<PathBuf, FileIdentity>,
+    path: &Path,
+) -> Result<(), PathChangedError> {
+    let (scanned, parents) = scanned_with_parents(path, |path| identities.get(path).copied());
+    check(path, scanned, parents)
+}
+
+fn verify_in(recorded: &Recorded, path: &Path) -> Result<(), PathChangedError> {
+    let (scanned, parents) = {
+        let recorded = recorded.lock().unwrap_or_else(|e| e.into_inner());
+        scanned_with_parents(path, |path| recorded.get(path).copied())
+    };
+    check(path, scanned, parents)
+}
+
+/// The identity scanned for `path`, and those of the parent folders recorded with it
+fn scanned_with_parents(
+    path: &Path,
+    scanned: impl Fn(&Path) -> Option<FileIdentity>,
+) -> (Option<FileIdentity>, Vec<(&Path, FileIdentity)>) {
+    let parents = path
+        .ancestors()
+        .skip(1)
+        .filter_map(|parent| Some((parent, scanned(parent)?)))
+        .collect();
+    (scanned(path), parents)
+}
+
+fn check(
+    path: &Path,
+    scanned: Option<FileIdentity>,
+    parents: Vec<(&Path, FileIdentity)>,
+) -> Result<(), PathChangedError> {
+    let Some(current) = identify(path) else {
+        // Gone or unreadable: the deletion itself reports that
+        return Ok(());
+    };
+    // Parents first: through a swapped parent the path itself looks replaced
+    let change = parents
+        .into_iter()
+        .find_map(|(parent, scanned)| {
+            // A parent that can't be opened any more was moved away
+            let Some(current) = identify(parent) else {
+                return Some(PathChange::ParentReplaced);
+            };
+            compare(Some(scanned), current).map(|change| match change {
+                PathChange::BecameLink => PathChange::ParentBecameLink,
+                _ => PathChange::ParentReplaced,
+            })
+        })
+        .or_else(|| compare(scanned, current));
+    match change {
+        Some(change) => Err(PathChangedError {
+            path: path.to_path_buf(),
+            change,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn swapped_paths_are_refused() {
+        // A registry of its own: scans in other tests reset the global one
+        let recorded = Recorded::default();
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir.path().join("cache");
+        let important = temp_dir.path().join("important");
+        let file = temp_dir.path().join("file.tmp");
+        let unscanned = temp_dir.path().join("unscanned");
+        fs::create_dir_all(&cache).unwrap();
+        fs::create_dir_all(&important).unwrap();
+        fs::write(&file, b"old").unwrap();
+        record_in(&recorded, &[cache.as_path(), file.as_path()]);
+
+        assert_eq!(verify_in(&recorded, &cache), Ok(()));
+        assert_eq!(verify_in(&recorded, &file), Ok(()));
+
+        // Folder swapped for a symlink to other data
+        fs::remove_dir(&cache).unwrap();
+        std::os::unix::fs::symlink(&important, &cache).unwrap();
+        assert_eq!(
+            verify_in(&recorded, &cache).unwrap_err().change,
+            PathChange::BecameLink
+        );
+
+        // File replaced by a different file under the same name
+        let other = temp_dir.path().join("other");
+        fs::write(&other, b"new").unwrap();
+        fs::rename(&other, &file).unwrap();
+        assert_eq!(
+            verify_in(&recorded, &file).unwrap_err().change,
+            PathChange::Replaced
+        );
+
+        // Never scanned: only links are refused
+        fs::create_dir_all(&unscanned).unwrap();
+        assert_eq!(verify_in(&recorded, &unscanned), Ok(()));
+        fs::remove_dir(&unscanned).unwrap();
+        std::os::unix::fs::symlink(&important, &unscanned).unwrap();
+        assert!(verify_in(&recorded, &unscanned).is_err());
+        assert!(verify_in(&recorded, &temp_dir.path().join("missing")).is_ok());
+
+        // A parent folder swapped for a link to a look-alike tree
+        let project = temp_dir.path().join("project");
+        let target = project.join("target");
+        let elsewhere = temp_dir.path().join("elsewhere");
+        fs::create_dir_all(&target).unwrap();
+        fs::create_dir_all(elsewhere.join("target")).unwrap();
+        record_in(&recorded, &[target.as_path()]);
+        assert_eq!(verify_in(&recorded, &target), Ok(()));
+        fs::rename(&project, temp_dir.path().join("moved")).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, &project).unwrap();
+        assert_eq!(
+            verify_in(&recorded, &target).unwrap_err().change,
+            PathChange::ParentBecameLink
+        );
+    }
+}
@@ -2,15 +2,21 @@
 //!
 //! This module owns path eligibility checks prior to deletion.
 
+use super::path_identity::{self, PathChange};
+use crate::debug_log;
 use crate::utils;
 use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum PrecheckOutcome {
     Eligible,
     Missing,
     Locked,
     BlockedSystem,
+    /// On the protected list, with the reason; reported as a failure
+    Protected(String),
+    /// Became a link or a different file since the scan
+    Changed(PathChange),
 }
 
 /// Check if a path is locked by another process (Windows-specific)
@@ -59,10 +65,23 @@ pub(crate) fn precheck_path(path: &Path) -> PrecheckOutcome {
         return PrecheckOutcome::BlockedSystem;
     }
 
+    if let Some(reason) = super::protected::protection_reason(path) {
+        debug_log::cleaning_log(&format!(
+            "protected path refused: path={} reason={}",
+            path.display(),
+            reason
+        ));
+        return PrecheckOutcome::Protected(reason);
+    }
+
     if !path.exists() {
         return PrecheckOutcome::Missing;
     }
 
+    if let Err(e) = path_identity::verify(path) {
+        return PrecheckOutcome::Changed(e.change);
+    }
+
     if is_path_locked(path) {
         return PrecheckOutcome::Locked;
     }
@@ -108,4 +127,25 @@ mod tests {
         // Non-existent files are not locked
         assert!(!is_path_locked(&nonexistent));
     }
+
+    #[test]
+    fn test_precheck_protected_path_reports_reason() {
+        let temp_dir = create_test_dir();
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+
+        // Refused with its reason, not silently skipped as a system path
+        assert_eq!(
+            precheck_path(&repo),
+            PrecheckOutcome::Protected("is a git worktree".to_string())
+        );
+        let err = anyhow::Error::new(crate::Error::Protected {
+            path: repo,
+            reason: "is a git worktree".to_string(),
+        });
+        assert_eq!(
+            crate::history::FailureInfo::from_error(&err).reason,
+            crate::history::FailureReason::Protected
+        );
+    }
 }
@@ -0,0 +1,224 @@
+//! Per-project deletion batches.
+//!
+//! Cleaning a project's artifacts (`node_modules`, `dist`, `.turbo`) is one
+//! transaction: either every artifact goes, or the project is reported once
+//! with what stopped it. Before anything is deleted each artifact is renamed
+//! to a hidden sibling (see `background_delete::stage`), which fails when a
+//! file inside is in use. If one can't be moved, the ones already moved are
+//! renamed back and the project is left as it was.
+//!
+//! Permanent deletes then remove the staged folders; any that can't be fully
+//! removed are renamed back so nothing is left under a staging name. For the
+//! Recycle Bin the artifacts are moved back first (the bin restores items by
+//! their original path) and recycled together.
+
+use super::background_delete;
+use super::batch_deletion::clean_paths_batch_with_progress;
+use super::path_identity::PathChangedError;
+use super::path_precheck::{precheck_path, PrecheckOutcome};
+use super::quarantine::Quarantine;
+use crate::history::FailureInfo;
+use crate::utils;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One project's artifacts, cleaned together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectBatch {
+    pub project: PathBuf,
+    pub paths: Vec<PathBuf>,
+}
+
+/// How cleaning a project went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectOutcome {
+    /// Every artifact still present was deleted
+    Cleaned { deleted: Vec<PathBuf> },
+    /// Nothing was deleted because `blocked` couldn't be moved
+    RolledBack {
+        blocked: PathBuf,
+        failure: FailureInfo,
+    },
+    /// Deletion started but some artifacts remain (renamed back in place)
+    Partial {
+        deleted: Vec<PathBuf>,
+        failures: HashMap<PathBuf, FailureInfo>,
+    },
+}
+
+/// Group artifact paths by the project folder holding them, in first-seen order
+pub fn group_by_project(paths: &[PathBuf]) -> Vec<ProjectBatch> {
+    let mut batches: Vec<ProjectBatch> = Vec::new();
+    let mut index: HashMap<PathBuf, usize> = HashMap::new();
+    for path in paths {
+        let project = path.parent().unwrap_or(path).to_path_buf();
+        let i = *index.entry(project.clone()).or_insert_with(|| {
+            batches.push(ProjectBatch {
+                project,
+                paths: Vec::new(),
+            });
+            batches.len() - 1
+        });
+        batches[i].paths.push(path.clone());
+    }
+    batches
+}
+
+/// Rename staged artifacts back to where they were
+fn unstage(staged: &[(PathBuf, PathBuf)]) -> Vec<(PathBuf, std::io::Error)> {
+    staged
+        .iter()
+        .rev()
+        .filter_map(|(original, staged)| {
+            fs::rename(staged, original)
+                .err()
+                .map(|e| (original.clone(), e))
+        })
+        .collect()
+}
+
+/// Stage every path, or none: on the first failure the ones already staged
+/// are moved back
+fn stage_all(paths: &[PathBuf]) -> Result<Vec<(PathBuf, PathBuf)>, (PathBuf, FailureInfo)> {
+    let mut staged = Vec::with_capacity(paths.len());
+    for path in paths {
+        let blocked = match precheck_path(path) {
+            PrecheckOutcome::Eligible => match background_delete::stage(path) {
+                Ok(to) => {
+                    staged.push((path.clone(), to));
+                    continue;
+                }
+                Err(e) => FailureInfo::from_io_error(&e),
+            },
+            PrecheckOutcome::Locked => {
+                FailureInfo::from_message("Path is locked by another process")
+            }
+            PrecheckOutcome::Changed(change) => {
+                FailureInfo::from_error(&anyhow::Error::new(PathChangedError {
+                    path: path.clone(),
+                    change,
+                }))
+            }
+            PrecheckOutcome::Protected(reason) => {
+                FailureInfo::from_error(&anyhow::Error::new(crate::Error::Protected {
+                    path: path.clone(),
+                    reason,
+                }))
+            }
+            // Already gone or never deletable: not part of the transaction
+            PrecheckOutcome::Missing | PrecheckOutcome::BlockedSystem => continue,
+        };
+        unstage(&staged);
+        return Err((path.clone(), blocked));
+    }
+    Ok(staged)
+}
+
+fn remove(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        utils::safe_remove_dir_all(path)
+    } else {
+        utils::safe_remove_file(path)
+    }
+}
+
+/// Clean all of a project's artifacts as one unit. Non-permanent deletes go
+/// to `quarantine` when one is given.
+pub fn clean_project(
+    batch: &ProjectBatch,
+    permanent: bool,
+    quarantine: Option<&Quarantine>,
+) -> ProjectOutcome {
+    let staged = match stage_all(&batch.paths) {
+        Ok(staged) => staged,
+        Err((blocked, failure)) => return ProjectOutcome::RolledBack { blocked, failure },
+    };
+
+    let mut deleted = Vec::new();
+    let mut failures = HashMap::new();
+    if permanent {
+        for (original, staged) in staged {
+            match remove(&staged) {
+                Ok(()) => deleted.push(original),
+                Err(e) => {
+                    // Put what's left back where the user expects it
+                    let _ = fs::rename(&staged, &original);
+                    failures.insert(original, FailureInfo::from_io_error(&e));
+                }
+            }
+        }
+    } else {
+        let mut originals: Vec<PathBuf> = staged.iter().map(|(o, _)| o.clone()).collect();
+        for (original, e) in unstage(&staged) {
+            originals.retain(|o| o != &original);
+            failures.insert(original, FailureInfo::from_io_error(&e));
+        }
+        let result = clean_paths_batch_with_progress(&originals, false, quarantine, &|_| {});
+        let details = result.failure_details();
+        for path in originals {
+            if result.deleted_paths.contains(&path) {
+                deleted.push(path);
+            } else if !result.skipped_paths.contains(&path) {
+                let info = details
+                    .get(&path)
+                    .cloned()
+                    .unwrap_or_else(|| FailureInfo::from_message("Recycle Bin deletion failed"));
+                failures.insert(path, info);
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        ProjectOutcome::Cleaned { deleted }
+    } else {
+        ProjectOutcome::Partial { deleted, failures }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn project_is_cleaned_whole_or_rolled_back() {
+        let temp = TempDir::new().unwrap();
+        let app = temp.path().join("app");
+        let web = temp.path().join("web");
+        for dir in ["app/node_modules/pkg", "app/dist", "web/node_modules"] {
+            fs::create_dir_all(temp.path().join(dir)).unwrap();
+        }
+        fs::write(app.join("node_modules/pkg/index.js"), b"x").unwrap();
+        // The staged name of this artifact is too long for the filesystem,
+        // so it can't be moved
+        let blocker = web.join("b".repeat(250));
+        fs::write(&blocker, b"x").unwrap();
+
+        let batches = group_by_project(&[
+            app.join("node_modules"),
+            web.join("node_modules"),
+            app.join("dist"),
+            blocker.clone(),
+        ]);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].project, app);
+        assert_eq!(batches[0].paths.len(), 2);
+
+        assert_eq!(
+            clean_project(&batches[0], true, None),
+            ProjectOutcome::Cleaned {
+                deleted: vec![app.join("node_modules"), app.join("dist")]
+            }
+        );
+        assert_eq!(fs::read_dir(&app).unwrap().count(), 0);
+
+        match clean_project(&batches[1], true, None) {
+            ProjectOutcome::RolledBack { blocked, .. } => assert_eq!(blocked, blocker),
+            other => panic!("expected a rollback, got {:?}", other),
+        }
+        // node_modules was staged first and moved back
+        assert!(web.join("node_modules").is_dir());
+        assert_eq!(fs::read_dir(&web).unwrap().count(), 2);
+    }
+}
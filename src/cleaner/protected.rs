@@ -0,0 +1,205 @@
+//! Protected paths feature.
+//!
+//! This module owns the list of paths the cleaner refuses to delete no matter
+//! which category reported them. Built in: the user's home and its Documents,
+//! Desktop, Pictures, Music, Videos and Downloads folders, cloud-drive roots
+//! (OneDrive, Dropbox, Google Drive, iCloud Drive, Box) and git worktrees.
+//! Those roots are protected themselves, along with every folder above them;
+//! files inside them can still be cleaned. Paths listed in
+//! `[safety] protected_paths` are protected with everything under them.
+//! The list is rebuilt whenever config.toml changes, so edits made in the TUI
+//! apply to the next deletion.
+
+use crate::categories::custom::expand_vars;
+use crate::config::Config;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Folders under the home directory that hold the user's own files
+const USER_FOLDERS: &[&str] = &[
+    "Documents",
+    "Desktop",
+    "Pictures",
+    "Music",
+    "Videos",
+    "Downloads",
+    "OneDrive",
+    "Dropbox",
+    "Google Drive",
+    "iCloudDrive",
+    "Box",
+];
+
+/// Environment variables pointing at cloud-drive roots
+const CLOUD_ROOT_VARS: &[&str] = &["OneDrive", "OneDriveConsumer", "OneDriveCommercial"];
+
+/// The protected list and the config file state it was built from
+type Built = ((Option<SystemTime>, u64), Arc<ProtectedPaths>);
+
+static PROTECTED: Mutex<Option<Built>> = Mutex::new(None);
+
+/// Modification time and length of config.toml, to notice edits
+fn config_stamp() -> (Option<SystemTime>, u64) {
+    Config::config_path()
+        .ok()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map(|meta| (meta.modified().ok(), meta.len()))
+        .unwrap_or((None, 0))
+}
+
+/// The protected list for the current config, rebuilt if the file changed
+fn current() -> Arc<ProtectedPaths> {
+    let stamp = config_stamp();
+    let mut built = PROTECTED.lock().unwrap_or_else(|e| e.into_inner());
+    match built.as_ref() {
+        Some((built_stamp, paths)) if *built_stamp == stamp => paths.clone(),
+        _ => {
+            let paths = Arc::new(ProtectedPaths::from_config(&Config::load()));
+            *built = Some((stamp, paths.clone()));
+            paths
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    path: PathBuf,
+    /// Also protect everything under `path`
+    subtree: bool,
+}
+
+/// Paths the cleaner must never delete
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedPaths {
+    rules: Vec<Rule>,
+}
+
+impl ProtectedPaths {
+    /// Built-in roots plus the user's `[safety] protected_paths`
+    pub fn from_config(config: &Config) -> Self {
+        let mut protected = Self::default();
+        let home = env::var_os("USERPROFILE")
+            .or_else(|| env::var_os("HOME"))
+            .map(PathBuf::from);
+        if let Some(home) = home {
+            for folder in USER_FOLDERS {
+                protected.add_root(home.join(folder));
+            }
+            protected.add_root(home.join("OneDrive").join("Documents"));
+            protected.add_root(home);
+        }
+        for var in CLOUD_ROOT_VARS {
+            if let Some(root) = env::var_os(var) {
+                protected.add_root(PathBuf::from(root));
+            }
+        }
+        for entry in &config.safety.protected_paths {
+            if !entry.trim().is_empty() {
+                protected.add_tree(PathBuf::from(expand_vars(entry.trim())));
+            }
+        }
+        protected
+    }
+
+    /// Protect `path` and the folders above it
+    pub fn add_root(&mut self, path: PathBuf) {
+        self.rules.push(Rule {
+            path,
+            subtree: false,
+        });
+    }
+
+    /// Protect `path`, the folders above it and everything under it
+    pub fn add_tree(&mut self, path: PathBuf) {
+        self.rules.push(Rule {
+            path,
+            subtree: true,
+        });
+    }
+
+    /// Why deleting `path` is refused, if it is
+    pub fn reason(&self, path: &Path) -> Option<String> {
+        for rule in &self.rules {
+            if is_within(&rule.path, path) {
+                return Some(format!("contains {}", rule.path.display()));
+            }
+            if rule.subtree && is_within(path, &rule.path) {
+                return Some(format!("under {}", rule.path.display()));
+            }
+        }
+        if path.components().any(|c| c.as_os_str() == ".git") {
+            return Some("inside a git repository's .git folder".to_string());
+        }
+        if path.join(".git").exists() {
+            return Some("is a git worktree".to_string());
+        }
+        None
+    }
+}
+
+/// Whether `path` is `base` or somewhere under it (case-insensitive on Windows)
+fn is_within(path: &Path, base: &Path) -> bool {
+    let mut path = path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir));
+    for base_part in base
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+    {
+        match path.next() {
+            Some(part) if same_component(part, base_part) => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn same_component(a: Component, b: Component) -> bool {
+    if cfg!(windows) {
+        a.as_os_str()
+            .to_string_lossy()
+            .eq_ignore_ascii_case(&b.as_os_str().to_string_lossy())
+    } else {
+        a == b
+    }
+}
+
+/// Why the cleaner refuses to delete `path`, if it does
+pub fn protection_reason(path: &Path) -> Option<String> {
+    current().reason(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roots_ancestors_and_user_trees_are_protected() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path().join("home");
+        let repo = home.join("src").join("app");
+        std::fs::create_dir_all(repo.join(".git").join("objects")).unwrap();
+        std::fs::create_dir_all(repo.join("node_modules")).unwrap();
+
+        let mut protected = ProtectedPaths::default();
+        protected.add_root(home.join("Documents"));
+        protected.add_tree(home.join("Keep"));
+
+        // The root and everything above it, but not what's inside
+        assert!(protected.reason(&home.join("Documents")).is_some());
+        assert!(protected.reason(&home).is_some());
+        assert!(protected
+            .reason(&home.join("Documents").join("big.iso"))
+            .is_none());
+        // User entries cover their whole tree
+        assert!(protected.reason(&home.join("Keep").join("a.txt")).is_some());
+        // Git worktrees and their .git folder, but not build output inside
+        assert!(protected.reason(&repo).is_some());
+        assert!(protected
+            .reason(&repo.join(".git").join("objects"))
+            .is_some());
+        assert!(protected.reason(&repo.join("node_modules")).is_none());
+    }
+}
@@ -0,0 +1,523 @@
+//! Quarantine: soft delete into a wole-managed folder
+//!
+//! With `[quarantine] enabled`, items that would go to the Recycle Bin are
+//! moved into the quarantine folder instead, each under its own entry folder,
+//! and listed with where they came from. Each move appends one line to
+//! `pending.jsonl`; restores and purges fold those into `manifest.json`, so a
+//! clean of many items never rewrites the whole manifest per item. The
+//! quarantine is opened once per clean and passed down. Restoring is a
+//! rename back, so large trees come back whole, and items on network drives
+//! (which have no Recycle Bin) can be recovered too. Items older than
+//! `retention_days` are purged at the start of the next clean or TUI session.
+//!
+//! An item is renamed into quarantine when it's on the same volume and
+//! copied then removed otherwise. The copy never follows symlinks or
+//! junctions: each link is recreated as a link, relative targets as they were
+//! and absolute targets inside the moved tree rebased onto its new location,
+//! so a restored `node_modules` keeps its `.bin` links working.
+
+use crate::app_data;
+use crate::config::{Config, QuarantineSettings};
+use crate::debug_log;
+use crate::restore::normalize_path_for_comparison;
+use crate::utils;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use walkdir::WalkDir;
+
+const MANIFEST: &str = "manifest.json";
+
+/// Entries added since the manifest was last written, one JSON object a line
+const PENDING: &str = "pending.jsonl";
+
+/// Serializes appends and manifest read-modify-write cycles
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// One quarantined item, as listed in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub id: String,
+    /// Where the item was before it was quarantined
+    pub original: PathBuf,
+    /// Where it's kept now
+    pub stored: PathBuf,
+    pub size_bytes: u64,
+    pub quarantined: DateTime<Utc>,
+}
+
+/// A quarantine folder and its manifest
+#[derive(Debug, Clone)]
+pub struct Quarantine {
+    dir: PathBuf,
+}
+
+/// Where deletes that would recycle go instead, or `None` when
+/// `[quarantine] enabled` is off
+pub fn configured() -> Option<Quarantine> {
+    let settings = Config::load().quarantine;
+    settings.enabled.then(|| Quarantine::open(&settings))
+}
+
+impl Quarantine {
+    /// The quarantine configured in `settings`
+    pub fn open(settings: &QuarantineSettings) -> Self {
+        Self::at(match &settings.path {
+            Some(path) => PathBuf::from(path),
+            None => app_data::data_dir().join("quarantine"),
+        })
+    }
+
+    /// Quarantine kept in `dir`
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST)
+    }
+
+    /// Every quarantined item, oldest first
+    pub fn entries(&self) -> Result<Vec<QuarantineEntry>> {
+        let path = self.manifest_path();
+        let mut entries: Vec<QuarantineEntry> = match read_if_present(&path)? {
+            Some(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            None => Vec::new(),
+        };
+        // A line cut short by a crash mid-append is skipped, and so are lines
+        // already folded in by an update that crashed before clearing them
+        if let Some(pending) = read_if_present(&self.dir.join(PENDING))? {
+            let known: HashSet<String> = entries.iter().map(|e| e.id.clone()).collect();
+            let added: Vec<QuarantineEntry> = pending
+                .lines()
+                .filter_map(|line| serde_json::from_str::<QuarantineEntry>(line).ok())
+                .filter(|entry| !known.contains(&entry.id))
+                .collect();
+            entries.extend(added);
+        }
+        Ok(entries)
+    }
+
+    /// Add `entry` to the pending list
+    fn append(&self, entry: &QuarantineEntry) -> Result<()> {
+        let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = self.dir.join(PENDING);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Rewrite the manifest with `f` applied, folding in the pending list
+    fn update(&self, f: impl FnOnce(&mut Vec<QuarantineEntry>)) -> Result<()> {
+        let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = self.entries()?;
+        f(&mut entries);
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.manifest_path();
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&entries)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))?;
+        match fs::remove_file(self.dir.join(PENDING)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).context("Failed to clear the pending quarantine list")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Move `path` into the quarantine
+    pub fn put(&self, path: &Path) -> Result<QuarantineEntry> {
+        let name = path
+            .file_name()
+            .with_context(|| format!("Nothing to quarantine at {}", path.display()))?;
+        let quarantined = Utc::now();
+        let hash = blake3::hash(path.display().to_string().as_bytes());
+        let id = format!(
+            "{}-{}",
+            quarantined.format("%Y%m%d%H%M%S%9f"),
+            &hash.to_hex()[..8]
+        );
+        let entry_dir = self.dir.join(&id);
+        fs::create_dir_all(&entry_dir)
+            .with_context(|| format!("Failed to create {}", entry_dir.display()))?;
+        let stored = entry_dir.join(name);
+
+        let size_bytes = if is_real_dir(path) {
+            utils::calculate_dir_size(path)
+        } else {
+            fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+        };
+        if let Err(e) = move_item(path, &stored) {
+            let _ = fs::remove_dir_all(&entry_dir);
+            return Err(e).with_context(|| format!("Failed to quarantine {}", path.display()));
+        }
+
+        let entry = QuarantineEntry {
+            id,
+            original: path.to_path_buf(),
+            stored,
+            size_bytes,
+            quarantined,
+        };
+        self.append(&entry)?;
+        debug_log::cleaning_log(&format!(
+            "quarantined: {} -> {}",
+            path.display(),
+            entry.stored.display()
+        ));
+        Ok(entry)
+    }
+
+    /// The latest quarantined copy of `original`, if any
+    pub fn find(&self, original: &Path) -> Result<Option<QuarantineEntry>> {
+        let wanted = normalize_path_for_comparison(&original.display().to_string());
+        Ok(self.entries()?.into_iter().rev().find(|entry| {
+            normalize_path_for_comparison(&entry.original.display().to_string()) == wanted
+        }))
+    }
+
+    /// Move an item back to where it came from. Fails if something is there
+    /// now.
+    pub fn restore(&self, entry: &QuarantineEntry) -> Result<()> {
+        // symlink_metadata, so a dangling link there counts as something
+        if fs::symlink_metadata(&entry.original).is_ok() {
+            bail!("{} already exists", entry.original.display());
+        }
+        if let Some(parent) = entry.original.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        move_item(&entry.stored, &entry.original)
+            .with_context(|| format!("Failed to restore {}", entry.original.display()))?;
+        let _ = fs::remove_dir_all(self.dir.join(&entry.id));
+        self.update(|entries| entries.retain(|e| e.id != entry.id))
+    }
+
+    /// Permanently delete items quarantined more than `retention_days` before
+    /// `now`. Returns how many were purged and their size.
+    pub fn purge_expired(&self, retention_days: u64, now: DateTime<Utc>) -> Result<(usize, u64)> {
+        let cutoff = now - Duration::days(retention_days as i64);
+        let expired: Vec<QuarantineEntry> = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.quarantined < cutoff)
+            .collect();
+
+        let mut purged = Vec::new();
+        let mut bytes = 0;
+        for entry in expired {
+            let entry_dir = self.dir.join(&entry.id);
+            match utils::safe_remove_dir_all(&entry_dir) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    debug_log::cleaning_log(&format!(
+                        "quarantine purge failed: {} {}",
+                        entry_dir.display(),
+                        e
+                    ));
+                    continue;
+                }
+            }
+            bytes += entry.size_bytes;
+            purged.push(entry.id);
+        }
+        if !purged.is_empty() {
+            self.update(|entries| entries.retain(|e| !purged.contains(&e.id)))?;
+        }
+        Ok((purged.len(), bytes))
+    }
+}
+
+/// Purge items past `[quarantine] retention_days`. Returns how many went.
+pub fn purge_expired() -> usize {
+    let settings = Config::load().quarantine;
+    let result = Quarantine::open(&settings).purge_expired(settings.retention_days, Utc::now());
+    match result {
+        Ok((count, _)) => count,
+        Err(e) => {
+            debug_log::cleaning_log(&format!("quarantine purge failed: {}", e));
+            0
+        }
+    }
+}
+
+/// The contents of `path`, or `None` if it doesn't exist
+fn read_if_present(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Rename `from` to `to`, or copy and remove it when they're on different
+/// volumes
+fn move_item(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_then_remove(from, to)
+}
+
+/// The cross-volume move: copy `from` to `to` with its links, then remove it
+fn copy_then_remove(from: &Path, to: &Path) -> Result<()> {
+    if let Err(e) = copy_item(from, to) {
+        let _ = remove_item(to);
+        return Err(e);
+    }
+    remove_item(from).with_context(|| format!("Copied but couldn't remove {}", from.display()))
+}
+
+/// A directory, and not a symlink or junction to one
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|meta| meta.is_dir())
+        .unwrap_or(false)
+}
+
+/// Remove a file, a tree or a link (not what it points to)
+fn remove_item(path: &Path) -> std::io::Result<()> {
+    if is_real_dir(path) {
+        return utils::safe_remove_dir_all(path);
+    }
+    // Links to directories are directories themselves on Windows
+    utils::safe_remove_file(path).or_else(|e| fs::remove_dir(path).map_err(|_| e))
+}
+
+fn copy_item(from: &Path, to: &Path) -> Result<()> {
+    let meta =
+        fs::symlink_metadata(from).with_context(|| format!("Failed to read {}", from.display()))?;
+    if meta.file_type().is_symlink() {
+        return copy_link(from, to, from, to);
+    }
+    if !meta.is_dir() {
+        fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+        return Ok(());
+    }
+    for entry in WalkDir::new(from).follow_links(false) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from)?);
+        if entry.path_is_symlink() {
+            copy_link(entry.path(), &target, from, to)?;
+        } else if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create {}", target.display()))?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Recreate the link `link` at `copy`. An absolute target inside `root` is
+/// rebased onto `new_root`; any other target is kept as is.
+fn copy_link(link: &Path, copy: &Path, root: &Path, new_root: &Path) -> Result<()> {
+    let target =
+        fs::read_link(link).with_context(|| format!("Failed to read link {}", link.display()))?;
+    let target = match target.strip_prefix(root) {
+        Ok(inside) if target.is_absolute() => new_root.join(inside),
+        _ => target,
+    };
+    // Whether the link is to a directory, even when its target is missing
+    let to_dir = fs::metadata(link).map(|m| m.is_dir()).unwrap_or(false);
+    create_link(&target, copy, to_dir)
+        .with_context(|| format!("Failed to recreate link {}", copy.display()))
+}
+
+#[cfg(unix)]
+fn create_link(target: &Path, link: &Path, _to_dir: bool) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+/// Directory links become symlinks when allowed (Developer Mode or admin)
+/// and junctions otherwise; junctions need an absolute target
+#[cfg(windows)]
+fn create_link(target: &Path, link: &Path, to_dir: bool) -> std::io::Result<()> {
+    use std::os::windows::fs::{symlink_dir, symlink_file};
+
+    if !to_dir {
+        return symlink_file(target, link);
+    }
+    if target.is_relative() && symlink_dir(target, link).is_ok() {
+        return Ok(());
+    }
+    let absolute = match link.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target.to_path_buf(),
+    };
+    let status = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(link)
+        .arg(&absolute)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "mklink /J failed for {}",
+            link.display()
+        )))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_link(_target: &Path, link: &Path, _to_dir: bool) -> std::io::Result<()> {
+    Err(std::io::Error::other(format!(
+        "Can't recreate link {} on this platform",
+        link.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn quarantined_items_restore_whole_and_expire() {
+        let temp = TempDir::new().unwrap();
+        let quarantine = Quarantine::at(temp.path().join("q"));
+        let project = temp.path().join("app");
+        fs::create_dir_all(project.join("node_modules/pkg/lib")).unwrap();
+        fs::write(project.join("node_modules/pkg/lib/index.js"), b"12345").unwrap();
+        fs::write(project.join("notes.txt"), b"abc").unwrap();
+
+        let modules = quarantine.put(&project.join("node_modules")).unwrap();
+        let notes = quarantine.put(&project.join("notes.txt")).unwrap();
+        assert!(!project.join("node_modules").exists());
+        assert_eq!(modules.size_bytes, 5);
+        assert_eq!(quarantine.entries().unwrap().len(), 2);
+
+        // The tree comes back as it was
+        let found = quarantine.find(&project.join("node_modules")).unwrap();
+        assert_eq!(found.as_ref(), Some(&modules));
+        quarantine.restore(&modules).unwrap();
+        assert_eq!(
+            fs::read(project.join("node_modules/pkg/lib/index.js")).unwrap(),
+            b"12345"
+        );
+        assert_eq!(quarantine.entries().unwrap(), vec![notes.clone()]);
+
+        // A restore never overwrites
+        fs::write(project.join("notes.txt"), b"new").unwrap();
+        assert!(quarantine.restore(&notes).is_err());
+
+        assert_eq!(quarantine.purge_expired(14, Utc::now()).unwrap(), (0, 0));
+        let later = Utc::now() + Duration::days(15);
+        assert_eq!(quarantine.purge_expired(14, later).unwrap(), (1, 3));
+        assert!(quarantine.entries().unwrap().is_empty());
+        assert!(!notes.stored.exists());
+    }
+
+    #[test]
+    fn puts_append_and_updates_fold_them_into_the_manifest() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("q");
+        let quarantine = Quarantine::at(dir.clone());
+        let mut put = Vec::new();
+        for name in ["a.log", "b.log", "c.log"] {
+            let path = temp.path().join(name);
+            fs::write(&path, b"x").unwrap();
+            put.push(quarantine.put(&path).unwrap());
+        }
+
+        // No manifest rewrite per item, just one line each
+        assert!(!dir.join(MANIFEST).exists());
+        let pending = fs::read_to_string(dir.join(PENDING)).unwrap();
+        assert_eq!(pending.lines().count(), 3);
+        assert_eq!(quarantine.entries().unwrap(), put);
+
+        // A restore writes the manifest once and clears the pending list
+        quarantine.restore(&put[1]).unwrap();
+        assert!(!dir.join(PENDING).exists());
+        assert_eq!(
+            quarantine.entries().unwrap(),
+            vec![put[0].clone(), put[2].clone()]
+        );
+
+        // Leftovers of a crash: a line already in the manifest and a torn one
+        fs::write(
+            dir.join(PENDING),
+            format!("{}\n{{\"id\":", serde_json::to_string(&put[0]).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(
+            quarantine.entries().unwrap(),
+            vec![put[0].clone(), put[2].clone()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_in_quarantined_trees_survive_a_restore() {
+        use std::os::unix::fs::symlink;
+
+        let temp = TempDir::new().unwrap();
+        let modules = temp.path().join("app").join("node_modules");
+        fs::create_dir_all(modules.join("tool").join("bin")).unwrap();
+        fs::create_dir_all(modules.join(".bin")).unwrap();
+        fs::write(modules.join("tool/bin/cli.js"), b"#!/usr/bin/env node").unwrap();
+        symlink("../tool/bin/cli.js", modules.join(".bin/tool")).unwrap();
+        // Absolute link into the tree, like a pnpm junction
+        symlink(modules.join("tool"), modules.join("linked")).unwrap();
+        // Link out of the tree: must stay a link, not pull the target in
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("big.bin"), b"keep").unwrap();
+        symlink(&outside, modules.join("shared")).unwrap();
+
+        let check = |root: &Path| {
+            assert_eq!(
+                fs::read_link(root.join(".bin/tool")).unwrap(),
+                Path::new("../tool/bin/cli.js")
+            );
+            assert_eq!(
+                fs::read(root.join(".bin/tool")).unwrap(),
+                b"#!/usr/bin/env node"
+            );
+            assert_eq!(
+                fs::read_link(root.join("linked")).unwrap(),
+                root.join("tool")
+            );
+            assert_eq!(fs::read_link(root.join("shared")).unwrap(), outside);
+        };
+
+        // Same volume: renamed out and back
+        let quarantine = Quarantine::at(temp.path().join("q"));
+        let entry = quarantine.put(&modules).unwrap();
+        assert!(fs::symlink_metadata(entry.stored.join(".bin/tool"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        quarantine.restore(&entry).unwrap();
+        check(&modules);
+
+        // Other volume: copied with links recreated, both ways
+        let stored = temp.path().join("elsewhere").join("node_modules");
+        fs::create_dir_all(stored.parent().unwrap()).unwrap();
+        copy_then_remove(&modules, &stored).unwrap();
+        assert!(!modules.exists());
+        check(&stored);
+        copy_then_remove(&stored, &modules).unwrap();
+        check(&modules);
+        assert_eq!(fs::read(outside.join("big.bin")).unwrap(), b"keep");
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! This module owns single-path deletion and precheck-based deletion.
 
+use super::background_delete;
+use super::path_identity::{self, PathChangedError};
 use super::path_precheck::{is_path_locked, precheck_path, PrecheckOutcome};
+use super::quarantine::Quarantine;
+use crate::config::Config;
+use crate::debug_log;
 use crate::utils;
 use anyhow::{Context, Result};
 use std::path::Path;
@@ -64,22 +69,74 @@ pub(crate) fn classify_anyhow_error(path: &Path, err: &anyhow::Error) -> Option<
     None
 }
 
-pub fn delete_with_precheck(path: &Path, permanent: bool) -> Result<DeleteOutcome> {
+/// Hand a large folder to a background deletion. Returns false (and leaves
+/// the folder in place) when it's below the threshold or couldn't be staged.
+fn delete_in_background(path: &Path) -> bool {
+    let min_entries = Config::load().safety.background_delete_min_entries;
+    if !path.is_dir() || !background_delete::has_at_least_entries(path, min_entries) {
+        return false;
+    }
+    match background_delete::start(path) {
+        Ok(_) => true,
+        Err(e) => {
+            debug_log::cleaning_log(&format!("background delete unavailable: {}", e));
+            false
+        }
+    }
+}
+
+/// Delete a file or folder, bypassing the Recycle Bin
+fn remove_permanently(path: &Path) -> std::io::Result<()> {
+    #[cfg(feature = "failure-injection")]
+    super::failure_injection::inject(path, false)?;
+    if path.is_dir() {
+        utils::safe_remove_dir_all(path)
+    } else {
+        utils::safe_remove_file(path)
+    }
+}
+
+/// Delete `path` unless it's missing, locked or a system path, or changed
+/// since the scan; those outcomes are reported instead of failing
+pub fn delete_with_precheck(path: &Path, permanent: bool) -> crate::Result<DeleteOutcome> {
+    let quarantine = super::quarantine::configured();
+    Ok(precheck_and_delete(path, permanent, quarantine.as_ref())?)
+}
+
+/// [`delete_with_precheck`] for the cleaner's own callers, which classify the
+/// `anyhow` error themselves. Non-permanent deletes go to `quarantine` when
+/// one is given and to the Recycle Bin otherwise.
+pub(crate) fn precheck_and_delete(
+    path: &Path,
+    permanent: bool,
+    quarantine: Option<&Quarantine>,
+) -> Result<DeleteOutcome> {
     match precheck_path(path) {
         PrecheckOutcome::Missing => return Ok(DeleteOutcome::SkippedMissing),
         PrecheckOutcome::Locked => return Ok(DeleteOutcome::SkippedLocked),
         PrecheckOutcome::BlockedSystem => return Ok(DeleteOutcome::SkippedSystem),
+        PrecheckOutcome::Changed(change) => {
+            return Err(PathChangedError {
+                path: path.to_path_buf(),
+                change,
+            }
+            .into())
+        }
+        PrecheckOutcome::Protected(reason) => {
+            return Err(crate::Error::Protected {
+                path: path.to_path_buf(),
+                reason,
+            }
+            .into())
+        }
         PrecheckOutcome::Eligible => {}
     }
 
     if permanent {
-        let result = if path.is_dir() {
-            utils::safe_remove_dir_all(path)
-        } else {
-            utils::safe_remove_file(path)
-        };
-
-        match result {
+        if delete_in_background(path) {
+            return Ok(DeleteOutcome::Deleted);
+        }
+        match remove_permanently(path) {
             Ok(()) => Ok(DeleteOutcome::Deleted),
             Err(err) => match classify_io_error(path, &err) {
                 Some(outcome) => Ok(outcome),
@@ -95,7 +152,11 @@ pub fn delete_with_precheck(path: &Path, permanent: bool) -> Result<DeleteOutcom
             },
         }
     } else {
-        match crate::trash_ops::delete(path) {
+        let result = match quarantine {
+            Some(quarantine) => quarantine.put(path).map(|_| ()),
+            None => crate::trash_ops::delete(path),
+        };
+        match result {
             Ok(()) => Ok(DeleteOutcome::Deleted),
             Err(err) => match classify_anyhow_error(path, &err) {
                 Some(outcome) => Ok(outcome),
@@ -115,27 +176,46 @@ pub fn delete_with_precheck(path: &Path, permanent: bool) -> Result<DeleteOutcom
 ///
 /// Features:
 /// - Checks for locked files before deletion (Windows)
+/// - Refuses paths that became a symlink/junction or a different file since the scan
 /// - Uses long path support for paths > 260 characters
 /// - Provides clear error messages
 /// - **CRITICAL**: Blocks deletion of system directories for safety
-pub fn clean_path(path: &Path, permanent: bool) -> Result<()> {
+pub fn clean_path(path: &Path, permanent: bool) -> crate::Result<()> {
     // CRITICAL SAFETY CHECK: Never allow deletion of system paths
     // This provides defense-in-depth even if a system path somehow gets into the deletion list
     if utils::is_system_path(path) {
-        return Err(anyhow::anyhow!(
-            "Cannot delete system path: {}. System directories are protected from deletion.",
-            path.display()
-        ));
+        return Err(crate::Error::Protected {
+            path: path.to_path_buf(),
+            reason: "system directory".to_string(),
+        });
+    }
+    if let Some(reason) = super::protected::protection_reason(path) {
+        return Err(crate::Error::Protected {
+            path: path.to_path_buf(),
+            reason,
+        });
     }
 
+    // Never follow a path swapped for a link (or another file) since the scan
+    path_identity::verify(path)?;
+
     // Check if file is locked (Windows only)
     if is_path_locked(path) {
-        return Err(anyhow::anyhow!("Path is locked by another process"));
+        return Err(crate::Error::Locked(format!(
+            "{} is locked by another process",
+            path.display()
+        )));
     }
 
     if permanent {
         // Permanent delete - bypass Recycle Bin
         // Use safe_* functions for long path support
+        if delete_in_background(path) {
+            return Ok(());
+        }
+        #[cfg(feature = "failure-injection")]
+        super::failure_injection::inject(path, false)
+            .with_context(|| format!("Failed to permanently delete: {}", path.display()))?;
         if path.is_dir() {
             utils::safe_remove_dir_all(path).with_context(|| {
                 format!("Failed to permanently delete directory: {}", path.display())
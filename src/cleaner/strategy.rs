@@ -0,0 +1,928 @@
+//! Per-category deletion strategies.
+//!
+//! Each category's cleaning lives behind [`CategoryCleaner`]; a
+//! [`CleanerRegistry`] lists them in the order they run, and
+//! [`clean_with`](super::clean_with) drives any registry through the usual
+//! confirmation, progress, deletion log and summary. A new category plugs in
+//! by registering one cleaner.
+//!
+//! [`CleanHooks`] let library users validate each path right before it's
+//! deleted and verify it afterwards. Built-in cleaners call them for every
+//! path they delete; steps that run in an elevated child process (see
+//! `elevated`) use the default hooks.
+
+use super::category_cleaning::{
+    batch_clean_category_internal, clean_build_projects, hardlink_duplicates, path_size,
+};
+use super::elevated;
+use super::quarantine::Quarantine;
+use super::single_deletion::{precheck_and_delete, DeleteOutcome};
+use crate::categories;
+use crate::categories::duplicates::DedupeMode;
+use crate::history::{DeletionLog, DeletionRecord, FailureInfo};
+use crate::output::{CategoryResult, OutputMode, ScanResults};
+use crate::theme::Theme;
+use std::path::{Path, PathBuf};
+
+/// How a clean runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CleanOptions {
+    /// Skip the confirmation prompt
+    pub skip_confirm: bool,
+    pub mode: OutputMode,
+    /// Bypass the Recycle Bin
+    pub permanent: bool,
+    pub dry_run: bool,
+    /// Re-own orphaned-owner files that fail with permission denied
+    pub take_ownership: bool,
+    pub dedupe_mode: DedupeMode,
+    /// Run admin-only steps in an elevated child instead of skipping them
+    pub elevate: bool,
+}
+
+impl Default for CleanOptions {
+    fn default() -> Self {
+        Self {
+            skip_confirm: false,
+            mode: OutputMode::Normal,
+            permanent: false,
+            dry_run: false,
+            take_ownership: false,
+            dedupe_mode: DedupeMode::Delete,
+            elevate: false,
+        }
+    }
+}
+
+/// Checks around each deletion. Both default to allowing everything.
+pub trait CleanHooks {
+    /// Called before `path` is deleted; an `Err` keeps it and is logged as the
+    /// reason it failed
+    fn before_delete(&self, _path: &Path, _category: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called after `path` was deleted; an `Err` turns the deletion into a
+    /// failure with that reason
+    fn after_delete(&self, _path: &Path, _category: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Archive `path` was packed into before it was deleted, recorded in the
+    /// deletion log so restore can unpack it
+    fn archive_of(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Hooks that accept every deletion
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHooks;
+
+impl CleanHooks for DefaultHooks {}
+
+/// What a cleaner gets besides the scan results
+pub struct CleanContext<'a> {
+    pub options: CleanOptions,
+    pub progress: Option<&'a indicatif::ProgressBar>,
+    pub hooks: &'a dyn CleanHooks,
+    /// Where non-permanent deletes go instead of the Recycle Bin, loaded once
+    /// per clean
+    pub quarantine: Option<Quarantine>,
+}
+
+impl CleanContext<'_> {
+    /// Show a line without breaking the progress bar
+    pub fn notice(&self, line: String) {
+        if self.options.mode == OutputMode::Quiet {
+            return;
+        }
+        match self.progress {
+            Some(pb) if !pb.is_hidden() => pb.println(line),
+            _ => eprintln!("{}", line),
+        }
+    }
+
+    pub fn set_message(&self, message: impl Into<String>) {
+        if let Some(pb) = self.progress {
+            pb.set_message(message.into());
+        }
+    }
+
+    pub fn inc(&self, delta: u64) {
+        if let Some(pb) = self.progress {
+            pb.inc(delta);
+        }
+    }
+
+    /// The paths `before_delete` lets through. Rejected paths are logged as
+    /// failures; the second value is how many there were.
+    pub fn admit(
+        &self,
+        paths: &[PathBuf],
+        category: &str,
+        mut history: Option<&mut DeletionLog>,
+    ) -> (Vec<PathBuf>, u64) {
+        let mut admitted = Vec::with_capacity(paths.len());
+        let mut rejected = 0;
+        for path in paths {
+            match self.hooks.before_delete(path, category) {
+                Ok(()) => admitted.push(path.clone()),
+                Err(reason) => {
+                    rejected += 1;
+                    self.inc(1);
+                    self.fail(path, 0, category, &reason, history.as_deref_mut());
+                }
+            }
+        }
+        (admitted, rejected)
+    }
+
+    /// Log a deletion once `after_delete` confirms it. Returns whether it
+    /// counts as cleaned.
+    pub fn deleted(
+        &self,
+        path: &Path,
+        size: u64,
+        category: &str,
+        permanent: bool,
+        history: Option<&mut DeletionLog>,
+    ) -> bool {
+        match self.hooks.after_delete(path, category) {
+            Ok(()) => {
+                if let Some(log) = history {
+                    let mut record = DeletionRecord::success(path, size, category, permanent);
+                    record.archive = self
+                        .hooks
+                        .archive_of(path)
+                        .map(|archive| archive.display().to_string());
+                    log.add_record(record);
+                }
+                true
+            }
+            Err(reason) => {
+                self.fail(path, size, category, &reason, history);
+                false
+            }
+        }
+    }
+
+    /// Log and report a path that wasn't deleted
+    pub fn fail(
+        &self,
+        path: &Path,
+        size: u64,
+        category: &str,
+        reason: &str,
+        history: Option<&mut DeletionLog>,
+    ) {
+        self.fail_with(
+            path,
+            size,
+            category,
+            &FailureInfo::from_message(reason),
+            history,
+        );
+    }
+
+    /// [`fail`](Self::fail) with a classified reason
+    pub fn fail_with(
+        &self,
+        path: &Path,
+        size: u64,
+        category: &str,
+        info: &FailureInfo,
+        history: Option<&mut DeletionLog>,
+    ) {
+        if let Some(log) = history {
+            log.log_failure_info(path, size, category, self.options.permanent, info);
+        }
+        self.notice(format!(
+            "[WARNING] Failed to clean {}: {}",
+            Theme::secondary(&path.display().to_string()),
+            Theme::error(&info.message)
+        ));
+    }
+}
+
+/// What one cleaner did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CategoryOutcome {
+    pub cleaned: u64,
+    pub errors: u64,
+    /// Bytes counted as freed
+    pub bytes: u64,
+}
+
+/// How one category is cleaned
+pub trait CategoryCleaner {
+    /// Name recorded in the deletion log
+    fn name(&self) -> &str;
+
+    /// Items and bytes this clean will act on, for the prompt and progress bar
+    fn planned(&self, results: &ScanResults, options: &CleanOptions) -> (usize, u64);
+
+    /// Why part of the category will be skipped, if it will be
+    fn skipped(&self, _results: &ScanResults, _options: &CleanOptions) -> Option<String> {
+        None
+    }
+
+    /// Delete the category's items
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome;
+}
+
+/// Selects a category's results
+pub type ResultFn = fn(&ScanResults) -> &CategoryResult;
+
+fn planned_from(result: &CategoryResult) -> (usize, u64) {
+    (result.items, result.size_bytes)
+}
+
+/// Deletes a category's paths in one batch (Recycle Bin or permanent)
+pub struct BatchCleaner {
+    pub name: &'static str,
+    pub result: ResultFn,
+}
+
+impl CategoryCleaner for BatchCleaner {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from((self.result)(results))
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        let result = (self.result)(results);
+        if result.items == 0 {
+            return CategoryOutcome::default();
+        }
+        let (cleaned, errors) =
+            batch_clean_category_internal(ctx, &result.paths, self.name, history);
+        CategoryOutcome {
+            cleaned,
+            errors,
+            bytes: result.size_bytes,
+        }
+    }
+}
+
+/// Deletes a category's paths one at a time, checking each for locks and
+/// permissions first
+pub struct PerPathCleaner {
+    pub name: &'static str,
+    /// Progress message, e.g. "Cleaning browser caches..."
+    pub message: &'static str,
+    pub result: ResultFn,
+}
+
+impl CategoryCleaner for PerPathCleaner {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from((self.result)(results))
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        mut history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        let result = (self.result)(results);
+        if result.items == 0 {
+            return CategoryOutcome::default();
+        }
+        ctx.set_message(self.message);
+        let permanent = ctx.options.permanent;
+        let (paths, rejected) = ctx.admit(&result.paths, self.name, history.as_deref_mut());
+        let mut outcome = CategoryOutcome {
+            errors: rejected,
+            bytes: result.size_bytes,
+            ..Default::default()
+        };
+        for path in &paths {
+            if ctx.options.dry_run {
+                outcome.cleaned += 1;
+                ctx.inc(1);
+                continue;
+            }
+            let size = path_size(path);
+            let failure = match precheck_and_delete(path, permanent, ctx.quarantine.as_ref()) {
+                Ok(DeleteOutcome::Deleted) => {
+                    ctx.inc(1);
+                    if ctx.deleted(path, size, self.name, permanent, history.as_deref_mut()) {
+                        outcome.cleaned += 1;
+                    } else {
+                        outcome.errors += 1;
+                    }
+                    None
+                }
+                Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => None,
+                Ok(DeleteOutcome::SkippedLocked) => Some(FailureInfo::from_message(
+                    "Path is locked by another process",
+                )),
+                Ok(DeleteOutcome::SkippedPermission) => {
+                    Some(FailureInfo::from_message("Permission denied"))
+                }
+                Err(e) => Some(FailureInfo::from_error(&e)),
+            };
+            if let Some(info) = failure {
+                outcome.errors += 1;
+                ctx.fail_with(path, size, self.name, &info, history.as_deref_mut());
+            }
+        }
+        outcome
+    }
+}
+
+/// Empties the Recycle Bin (items older than the scan's age limit)
+pub struct TrashCleaner;
+
+impl CategoryCleaner for TrashCleaner {
+    fn name(&self) -> &str {
+        "trash"
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from(&results.trash)
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        let trash = &results.trash;
+        if trash.items == 0 {
+            return CategoryOutcome::default();
+        }
+        ctx.set_message("Emptying Recycle Bin...");
+        let bin = Path::new("Recycle Bin");
+        if let Err(reason) = ctx.hooks.before_delete(bin, self.name()) {
+            ctx.inc(trash.items as u64);
+            ctx.fail(bin, trash.size_bytes, self.name(), &reason, history);
+            return CategoryOutcome {
+                errors: 1,
+                ..Default::default()
+            };
+        }
+        let emptied = CategoryOutcome {
+            cleaned: trash.items as u64,
+            errors: 0,
+            bytes: trash.size_bytes,
+        };
+        if ctx.options.dry_run {
+            ctx.inc(trash.items as u64);
+            return emptied;
+        }
+
+        let mut reported = 0usize;
+        let result =
+            categories::trash::clean_with_progress(results.trash_min_age_days, |purged, total| {
+                ctx.set_message(format!("Emptying Recycle Bin ({}/{})...", purged, total));
+                // Scan count and live count can differ; never advance past the scan count
+                let capped = purged.min(trash.items);
+                ctx.inc((capped - reported) as u64);
+                reported = capped;
+                true
+            });
+        ctx.inc((trash.items - reported) as u64);
+        match result {
+            Ok(_) => {
+                if ctx.deleted(bin, trash.size_bytes, self.name(), true, history) {
+                    emptied
+                } else {
+                    CategoryOutcome {
+                        errors: 1,
+                        ..Default::default()
+                    }
+                }
+            }
+            Err(e) => {
+                if let Some(log) = history {
+                    log.log_error(bin, trash.size_bytes, self.name(), true, &e);
+                }
+                if ctx.options.mode != OutputMode::Quiet {
+                    eprintln!(
+                        "[WARNING] Failed to empty Recycle Bin: {}",
+                        Theme::error(&e.to_string())
+                    );
+                }
+                CategoryOutcome {
+                    errors: 1,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+}
+
+/// Removes build artifacts a whole project at a time
+pub struct BuildCleaner;
+
+impl CategoryCleaner for BuildCleaner {
+    fn name(&self) -> &str {
+        "build artifacts"
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from(&results.build)
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        if results.build.items == 0 {
+            return CategoryOutcome::default();
+        }
+        let (cleaned, errors) = clean_build_projects(ctx, &results.build.paths, history);
+        CategoryOutcome {
+            cleaned,
+            errors,
+            bytes: results.build.size_bytes,
+        }
+    }
+}
+
+/// Deletes duplicate copies or replaces them with hardlinks, per `--dedupe-mode`
+pub struct DuplicatesCleaner;
+
+impl CategoryCleaner for DuplicatesCleaner {
+    fn name(&self) -> &str {
+        "duplicate files"
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from(&results.duplicates)
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        if results.duplicates.items == 0 {
+            return CategoryOutcome::default();
+        }
+        match ctx.options.dedupe_mode {
+            DedupeMode::Hardlink => {
+                let (cleaned, errors, bytes) = hardlink_duplicates(ctx, results, history);
+                CategoryOutcome {
+                    cleaned,
+                    errors,
+                    bytes,
+                }
+            }
+            DedupeMode::Delete => {
+                let (cleaned, errors) = batch_clean_category_internal(
+                    ctx,
+                    &results.duplicates.paths,
+                    self.name(),
+                    history,
+                );
+                CategoryOutcome {
+                    cleaned,
+                    errors,
+                    bytes: results.duplicates.size_bytes,
+                }
+            }
+        }
+    }
+}
+
+/// Uninstalls applications, then removes their leftovers
+pub struct ApplicationsCleaner;
+
+impl CategoryCleaner for ApplicationsCleaner {
+    fn name(&self) -> &str {
+        "applications"
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from(&results.applications)
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        mut history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        if results.applications.items == 0 {
+            return CategoryOutcome::default();
+        }
+        ctx.set_message("Uninstalling applications...");
+
+        // IMPORTANT: uninstalling applications is not safely restorable, even if permanent=false.
+        // We still honor `permanent` for leftover file deletion (Recycle Bin vs permanent),
+        // but we always log these as permanent to avoid offering restore.
+        let log_as_permanent = true;
+        let permanent = ctx.options.permanent;
+        let (paths, rejected) = ctx.admit(
+            &results.applications.paths,
+            self.name(),
+            history.as_deref_mut(),
+        );
+        let mut outcome = CategoryOutcome {
+            errors: rejected,
+            ..Default::default()
+        };
+
+        for path in &paths {
+            let size =
+                categories::applications::get_app_size(path).unwrap_or_else(|| path_size(path));
+
+            if ctx.options.dry_run {
+                outcome.cleaned += 1;
+                ctx.inc(1);
+                outcome.bytes += size;
+                continue;
+            }
+
+            let display = categories::applications::get_app_display_name(path)
+                .unwrap_or_else(|| path.display().to_string());
+
+            // Tighten: uninstall must succeed before we delete any install/artifact paths.
+            // This avoids leaving the app "installed" but with missing files.
+            let mut had_error = false;
+            let Some(_uninstall_cmd) = categories::applications::get_app_uninstall_string(path)
+            else {
+                // No uninstall command - skip (had_error not set here since we continue)
+                if ctx.options.mode != OutputMode::Quiet {
+                    eprintln!(
+                        "[WARNING] Cannot uninstall {}: {}",
+                        Theme::secondary(&display),
+                        Theme::error("No uninstall command in registry")
+                    );
+                }
+                // Do not delete any files for this app.
+                // It would leave a broken, still-installed entry.
+                if let Some(log) = history.as_deref_mut() {
+                    log.log_failure(
+                        path,
+                        size,
+                        self.name(),
+                        log_as_permanent,
+                        "No uninstall command in registry; skipped to avoid breaking installed app",
+                    );
+                }
+                outcome.errors += 1;
+                continue;
+            };
+
+            if let Err(e) = categories::applications::uninstall(path) {
+                had_error = true;
+                if ctx.options.mode != OutputMode::Quiet {
+                    eprintln!(
+                        "[WARNING] Uninstall failed for {}: {}",
+                        Theme::secondary(&display),
+                        Theme::error(&e.to_string())
+                    );
+                }
+            }
+
+            // Post-check: if it's still installed, don't delete artifacts (tight/safe).
+            if !had_error && categories::applications::is_still_installed(path) {
+                had_error = true;
+                if ctx.options.mode != OutputMode::Quiet {
+                    eprintln!(
+                        "[WARNING] {} still appears installed after uninstall (may require reboot). Skipping artifact deletion.",
+                        Theme::secondary(&display)
+                    );
+                }
+            }
+
+            if !had_error {
+                // Only after uninstall succeeds and entry disappears: delete app-specific leftovers.
+                let artifacts = categories::applications::get_app_artifact_paths(path);
+                for artifact in artifacts {
+                    match precheck_and_delete(&artifact, permanent, ctx.quarantine.as_ref()) {
+                        Ok(DeleteOutcome::Deleted) => {}
+                        Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
+                        Ok(DeleteOutcome::SkippedLocked | DeleteOutcome::SkippedPermission) => {
+                            had_error = true;
+                        }
+                        Err(_) => had_error = true,
+                    }
+                }
+            }
+
+            // Update counters/logs.
+            if had_error {
+                outcome.errors += 1;
+                if let Some(log) = history.as_deref_mut() {
+                    log.log_failure(
+                        path,
+                        size,
+                        self.name(),
+                        log_as_permanent,
+                        "Application uninstall and/or cleanup did not complete",
+                    );
+                }
+            } else {
+                ctx.inc(1);
+                if ctx.deleted(
+                    path,
+                    size,
+                    self.name(),
+                    log_as_permanent,
+                    history.as_deref_mut(),
+                ) {
+                    outcome.cleaned += 1;
+                    outcome.bytes += size;
+                } else {
+                    outcome.errors += 1;
+                }
+            }
+        }
+        outcome
+    }
+}
+
+/// Deletes files owned by deleted or unknown accounts, re-owning them first
+/// with `--take-ownership`
+pub struct OrphanedCleaner;
+
+impl CategoryCleaner for OrphanedCleaner {
+    fn name(&self) -> &str {
+        "orphaned"
+    }
+
+    fn planned(&self, results: &ScanResults, _options: &CleanOptions) -> (usize, u64) {
+        planned_from(&results.orphaned)
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        mut history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        if results.orphaned.items == 0 {
+            return CategoryOutcome::default();
+        }
+        ctx.set_message("Cleaning orphaned-owner files...");
+        let permanent = ctx.options.permanent;
+        let take_ownership = ctx.options.take_ownership;
+        let (paths, rejected) =
+            ctx.admit(&results.orphaned.paths, self.name(), history.as_deref_mut());
+        let mut outcome = CategoryOutcome {
+            errors: rejected,
+            bytes: results.orphaned.size_bytes,
+            ..Default::default()
+        };
+
+        for path in &paths {
+            if ctx.options.dry_run {
+                outcome.cleaned += 1;
+                ctx.inc(1);
+                continue;
+            }
+            let size = path_size(path);
+
+            let mut result = precheck_and_delete(path, permanent, ctx.quarantine.as_ref());
+            // Only touch ACLs when explicitly requested via --take-ownership
+            if take_ownership && matches!(result, Ok(DeleteOutcome::SkippedPermission)) {
+                result = categories::orphaned::take_ownership(path)
+                    .and_then(|()| precheck_and_delete(path, permanent, ctx.quarantine.as_ref()));
+            }
+
+            let failure = match result {
+                Ok(DeleteOutcome::Deleted) => {
+                    ctx.inc(1);
+                    if ctx.deleted(path, size, self.name(), permanent, history.as_deref_mut()) {
+                        outcome.cleaned += 1;
+                    } else {
+                        outcome.errors += 1;
+                    }
+                    None
+                }
+                Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => None,
+                Ok(DeleteOutcome::SkippedLocked) => {
+                    Some("Path is locked by another process".to_string())
+                }
+                Ok(DeleteOutcome::SkippedPermission) if take_ownership => {
+                    Some("Permission denied".to_string())
+                }
+                Ok(DeleteOutcome::SkippedPermission) => {
+                    Some("Permission denied (retry with --take-ownership)".to_string())
+                }
+                Err(e) => Some(e.to_string()),
+            };
+
+            if let Some(reason) = failure {
+                outcome.errors += 1;
+                ctx.fail(path, size, self.name(), &reason, history.as_deref_mut());
+            }
+        }
+        outcome
+    }
+}
+
+/// Machine-wide caches, VM leftovers, Windows Update files and event logs:
+/// cleaned here when elevated, in an elevated child with `--elevate`, and
+/// skipped otherwise
+pub struct AdminCleaner;
+
+impl AdminCleaner {
+    fn will_run(options: &CleanOptions) -> bool {
+        options.elevate || crate::optimize::is_admin()
+    }
+}
+
+impl CategoryCleaner for AdminCleaner {
+    fn name(&self) -> &str {
+        "admin steps"
+    }
+
+    fn planned(&self, results: &ScanResults, options: &CleanOptions) -> (usize, u64) {
+        if !Self::will_run(options) {
+            return (0, 0);
+        }
+        elevated::pending_steps(results)
+            .iter()
+            .fold((0, 0), |(items, bytes), step| {
+                (items + step.paths.len(), bytes + step.bytes)
+            })
+    }
+
+    fn skipped(&self, results: &ScanResults, options: &CleanOptions) -> Option<String> {
+        let steps = elevated::pending_steps(results);
+        (!steps.is_empty() && !Self::will_run(options)).then(|| elevated::skipped_warning(&steps))
+    }
+
+    fn clean(
+        &self,
+        results: &ScanResults,
+        ctx: &CleanContext,
+        mut history: Option<&mut DeletionLog>,
+    ) -> CategoryOutcome {
+        let steps = elevated::pending_steps(results);
+        let mut outcome = CategoryOutcome::default();
+        if steps.is_empty() {
+            return outcome;
+        }
+        let options = &ctx.options;
+
+        if crate::optimize::is_admin() || (options.elevate && options.dry_run) {
+            for step in &steps {
+                let (cleaned, errors) = elevated::run_step(ctx, step, history.as_deref_mut());
+                outcome.cleaned += cleaned;
+                outcome.errors += errors;
+                outcome.bytes += step.bytes;
+            }
+        } else if options.elevate {
+            ctx.set_message("Waiting for the elevated clean...");
+            match elevated::run_elevated(&steps, options.permanent) {
+                Ok(child) => {
+                    outcome.cleaned = child.cleaned;
+                    outcome.errors = child.errors;
+                    outcome.bytes = child.cleaned_bytes();
+                    if let Some(log) = history {
+                        for record in child.records {
+                            log.add_record(record);
+                        }
+                    }
+                }
+                Err(e) => {
+                    outcome.errors = steps.len() as u64;
+                    if options.mode != OutputMode::Quiet {
+                        eprintln!(
+                            "[WARNING] Elevated clean failed: {}",
+                            Theme::error(&e.to_string())
+                        );
+                    }
+                }
+            }
+            ctx.inc(steps.iter().map(|s| s.paths.len() as u64).sum());
+        } else if let Some(note) = self.skipped(results, options) {
+            match ctx.progress {
+                Some(pb) if !pb.is_hidden() => pb.println(note),
+                _ if options.mode != OutputMode::Quiet => println!("{}", note),
+                _ => {}
+            }
+        }
+        outcome
+    }
+}
+
+/// The cleaners a clean runs, in order, and the hooks they call
+pub struct CleanerRegistry {
+    cleaners: Vec<Box<dyn CategoryCleaner>>,
+    hooks: Box<dyn CleanHooks>,
+}
+
+impl Default for CleanerRegistry {
+    /// Every built-in category
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(BatchCleaner {
+                name: "cache",
+                result: |r| &r.cache,
+            })
+            .register(AdminCleaner)
+            .register(BatchCleaner {
+                name: "application cache",
+                result: |r| &r.app_cache,
+            })
+            .register(BatchCleaner {
+                name: "temp files",
+                result: |r| &r.temp,
+            })
+            .register(TrashCleaner)
+            .register(BuildCleaner)
+            .register(BatchCleaner {
+                name: "old downloads",
+                result: |r| &r.downloads,
+            })
+            .register(BatchCleaner {
+                name: "large files",
+                result: |r| &r.large,
+            })
+            .register(BatchCleaner {
+                name: "old files",
+                result: |r| &r.old,
+            })
+            .register(PerPathCleaner {
+                name: "browser",
+                message: "Cleaning browser caches...",
+                result: |r| &r.browser,
+            })
+            .register(PerPathCleaner {
+                name: "system",
+                message: "Cleaning system caches...",
+                result: |r| &r.system,
+            })
+            .register(PerPathCleaner {
+                name: "empty",
+                message: "Cleaning empty folders...",
+                result: |r| &r.empty,
+            })
+            .register(DuplicatesCleaner)
+            .register(ApplicationsCleaner)
+            .register(OrphanedCleaner)
+            .register(BatchCleaner {
+                name: "custom rules",
+                result: |r| &r.custom,
+            })
+            .register(BatchCleaner {
+                name: "ide caches",
+                result: |r| &r.ide,
+            })
+            .register(BatchCleaner {
+                name: "rust toolchain",
+                result: |r| &r.rust,
+            });
+        registry
+    }
+}
+
+impl CleanerRegistry {
+    /// No cleaners, default hooks
+    pub fn empty() -> Self {
+        Self {
+            cleaners: Vec::new(),
+            hooks: Box::new(DefaultHooks),
+        }
+    }
+
+    /// Run `cleaner` after the ones already registered
+    pub fn register(&mut self, cleaner: impl CategoryCleaner + 'static) -> &mut Self {
+        self.cleaners.push(Box::new(cleaner));
+        self
+    }
+
+    /// Replace the hooks every cleaner calls
+    pub fn with_hooks(mut self, hooks: impl CleanHooks + 'static) -> Self {
+        self.hooks = Box::new(hooks);
+        self
+    }
+
+    pub fn cleaners(&self) -> &[Box<dyn CategoryCleaner>] {
+        &self.cleaners
+    }
+
+    pub fn hooks(&self) -> &dyn CleanHooks {
+        self.hooks.as_ref()
+    }
+}
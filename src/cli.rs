@@ -34,6 +34,11 @@ pub struct Cli {
     /// Suppress all output except errors
     #[arg(short = 'q', long, global = true, conflicts_with = "verbose")]
     pub quiet: bool,
+
+    /// Progress display: redrawn bars, or plain log lines every few seconds
+    /// (auto: plain when stderr isn't a terminal)
+    #[arg(long, global = true, default_value = "auto", value_name = "MODE", value_parser = ["auto", "bar", "plain"])]
+    pub progress: String,
 }
 
 #[derive(Subcommand)]
@@ -89,14 +94,46 @@ pub enum Commands {
         #[arg(long)]
         event_logs: bool,
 
-        /// Root path to scan (default: home directory)
+        /// Scan for files owned by deleted or unknown accounts (Windows only; not part of --all)
+        #[arg(long)]
+        orphaned: bool,
+
+        /// Scan custom rules from config (see `wole config --import-rules`; not part of --all)
+        #[arg(long)]
+        custom: bool,
+
+        /// Scan IDE and toolchain caches (JetBrains, VS Code, Android SDK, Xcode)
+        #[arg(long)]
+        ide: bool,
+
+        /// Scan stale Cargo registry crates, unused rustup toolchains and sccache
+        #[arg(long)]
+        rust: bool,
+
+        /// Root path to scan (repeatable; several are scanned in parallel) [default: current directory]
         #[arg(long, value_name = "PATH")]
-        path: Option<PathBuf>,
+        path: Vec<PathBuf>,
+
+        /// Scan every fixed drive in parallel, with results broken down per drive
+        #[arg(long, conflicts_with_all = ["path", "diff", "record", "json_stream"])]
+        all_drives: bool,
 
         /// Output results as JSON for scripting
         #[arg(long)]
         json: bool,
 
+        /// Print newline-delimited JSON events as they happen (scan progress, items, deletions)
+        #[arg(long, conflicts_with = "json")]
+        json_stream: bool,
+
+        /// Report item counts and sizes per category only, without any paths
+        #[arg(long, conflicts_with_all = ["json_stream", "record"])]
+        summary_only: bool,
+
+        /// Compare against the previous `--diff` scan of this folder: what's new, grew or disappeared
+        #[arg(long, conflicts_with_all = ["json_stream", "summary_only", "quick", "replay"])]
+        diff: bool,
+
         /// Project inactivity threshold in days [default: 14]
         #[arg(long, default_value = "14", value_name = "DAYS")]
         project_age: u64,
@@ -109,10 +146,22 @@ pub enum Commands {
         #[arg(long, default_value = "100MB", value_name = "SIZE")]
         min_size: String,
 
-        /// Exclude paths matching pattern (repeatable)
+        /// Only include Recycle Bin items deleted at least this many days ago (with --trash)
+        #[arg(long, value_name = "DAYS")]
+        trash_older_than: Option<u64>,
+
+        /// Exclude paths matching a glob (`**/cache/**`, `*.iso`) or a regex prefixed with `re:` (repeatable)
         #[arg(long, value_name = "PATTERN")]
         exclude: Vec<String>,
 
+        /// Include hidden and system files in --large/--old results (reported separately otherwise)
+        #[arg(long)]
+        include_hidden: bool,
+
+        /// Only show results on this drive (e.g. D:)
+        #[arg(long, value_name = "VOLUME")]
+        volume: Option<String>,
+
         /// Force full rescan (ignore cache)
         #[arg(long)]
         force_full: bool,
@@ -124,6 +173,26 @@ pub enum Commands {
         /// Clear scan cache before running
         #[arg(long)]
         clear_cache: bool,
+
+        /// Estimate folder sizes from a sample of their subfolders (faster, approximate)
+        #[arg(long)]
+        quick: bool,
+
+        /// Seed for --quick sampling; the same seed samples the same folders [default: random]
+        #[arg(long, value_name = "N", requires = "quick")]
+        seed: Option<u64>,
+
+        /// Record scan inputs, traversal decisions and results to FILE (e.g. session.wole)
+        #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+        record: Option<PathBuf>,
+
+        /// Hash path names in the recording (keeps well-known folders and extensions)
+        #[arg(long, requires = "record")]
+        anonymize: bool,
+
+        /// Show a recorded scan and re-check its decisions against this build
+        #[arg(long, value_name = "FILE")]
+        replay: Option<PathBuf>,
     },
 
     /// Delete files found by scan (with confirmation)
@@ -193,6 +262,36 @@ pub enum Commands {
         #[arg(long)]
         event_logs: bool,
 
+        /// Clean files owned by deleted or unknown accounts (Windows only; not part of --all)
+        #[arg(long)]
+        orphaned: bool,
+
+        /// Clean files matched by custom rules from config (not part of --all)
+        #[arg(long)]
+        custom: bool,
+
+        /// Clean IDE and toolchain caches (JetBrains, VS Code, Android SDK, Xcode)
+        #[arg(long)]
+        ide: bool,
+
+        /// Clean stale Cargo registry crates, unused rustup toolchains and sccache
+        #[arg(long)]
+        rust: bool,
+
+        /// Take ownership of orphaned files that can't otherwise be deleted (requires admin)
+        #[arg(long)]
+        take_ownership: bool,
+
+        /// Run steps that need administrator rights (machine-wide caches, Windows
+        /// Update files, event logs) in an elevated process instead of skipping them
+        #[arg(long)]
+        elevate: bool,
+
+        /// What to do with duplicate copies: delete them, or replace each with a
+        /// hardlink to the kept copy (same volume only) [default: delete]
+        #[arg(long, default_value = "delete", value_name = "MODE", value_parser = ["delete", "hardlink"])]
+        dedupe_mode: String,
+
         /// Root path to scan (default: home directory)
         #[arg(long, value_name = "PATH")]
         path: Option<PathBuf>,
@@ -201,10 +300,18 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
 
+        /// Print newline-delimited JSON events as they happen (scan progress, items, deletions)
+        #[arg(long, conflicts_with = "json")]
+        json_stream: bool,
+
         /// Skip confirmation prompt (use with caution!)
         #[arg(short = 'y', long = "yes")]
         yes: bool,
 
+        /// List every item and choose which ones to skip before deleting
+        #[arg(short = 'i', long)]
+        interactive: bool,
+
         /// Project inactivity threshold in days [default: 14]
         #[arg(long, default_value = "14", value_name = "DAYS")]
         project_age: u64,
@@ -217,19 +324,187 @@ pub enum Commands {
         #[arg(long, default_value = "100MB", value_name = "SIZE")]
         min_size: String,
 
-        /// Exclude paths matching pattern (repeatable)
+        /// Only include Recycle Bin items deleted at least this many days ago (with --trash)
+        #[arg(long, value_name = "DAYS")]
+        trash_older_than: Option<u64>,
+
+        /// Exclude paths matching a glob (`**/cache/**`, `*.iso`) or a regex prefixed with `re:` (repeatable)
         #[arg(long, value_name = "PATTERN")]
         exclude: Vec<String>,
 
+        /// Include hidden and system files in --large/--old results (reported separately otherwise)
+        #[arg(long)]
+        include_hidden: bool,
+
+        /// Only show results on this drive (e.g. D:)
+        #[arg(long, value_name = "VOLUME")]
+        volume: Option<String>,
+
         /// Permanently delete (bypass Recycle Bin)
         #[arg(long)]
         permanent: bool,
 
+        /// Pack each build artifact folder into a .tar.zst in DIR before deleting
+        /// it, so `wole restore` can unpack it later (with --build)
+        #[arg(long, value_name = "DIR")]
+        archive: Option<PathBuf>,
+
         /// Preview only, don't delete
         #[arg(long)]
         dry_run: bool,
     },
 
+    /// Write what a clean would delete to a signed plan file for review
+    #[command(after_help = "EXAMPLES:
+    wole plan --all -o cleanup.wole          Plan a full clean without deleting anything
+    wole plan --build --permanent -o b.wole  Plan permanent deletion of build artifacts
+    wole apply cleanup.wole                  Delete exactly what the plan lists")]
+    Plan {
+        /// File to write the plan to (e.g. cleanup.wole)
+        #[arg(short = 'o', long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Plan all clean categories (installed applications are never planned)
+        #[arg(short = 'a', long)]
+        all: bool,
+
+        /// Plan package manager cache directories (npm, pip, nuget, etc.)
+        #[arg(long)]
+        cache: bool,
+
+        /// Plan application cache directories (Discord, VS Code, Slack, etc.)
+        #[arg(long)]
+        app_cache: bool,
+
+        /// Plan temporary files (system temp folders)
+        #[arg(long)]
+        temp: bool,
+
+        /// Plan emptying the Recycle Bin
+        #[arg(long)]
+        trash: bool,
+
+        /// Plan build artifacts from inactive projects (node_modules, target, etc.)
+        #[arg(long)]
+        build: bool,
+
+        /// Plan old files in Downloads folder
+        #[arg(long)]
+        downloads: bool,
+
+        /// Plan files over size threshold
+        #[arg(long)]
+        large: bool,
+
+        /// Plan files not accessed in N days
+        #[arg(long)]
+        old: bool,
+
+        /// Plan browser caches (Chrome, Edge, Firefox)
+        #[arg(long)]
+        browser: bool,
+
+        /// Plan Windows system caches (thumbnails, updates, icons)
+        #[arg(long)]
+        system: bool,
+
+        /// Plan empty folders
+        #[arg(long)]
+        empty: bool,
+
+        /// Plan duplicate files (keeps one copy)
+        #[arg(long)]
+        duplicates: bool,
+
+        /// Plan Windows Update files (download cache, logs) - requires admin
+        #[arg(long)]
+        windows_update: bool,
+
+        /// Plan Windows Event Log files (old .evtx files) - requires admin
+        #[arg(long)]
+        event_logs: bool,
+
+        /// Plan files owned by deleted or unknown accounts (Windows only; not part of --all)
+        #[arg(long)]
+        orphaned: bool,
+
+        /// Plan files matched by custom rules from config (not part of --all)
+        #[arg(long)]
+        custom: bool,
+
+        /// Plan IDE and toolchain caches (JetBrains, VS Code, Android SDK, Xcode)
+        #[arg(long)]
+        ide: bool,
+
+        /// Plan stale Cargo registry crates, unused rustup toolchains and sccache
+        #[arg(long)]
+        rust: bool,
+
+        /// Take ownership of orphaned files that can't otherwise be deleted (requires admin)
+        #[arg(long)]
+        take_ownership: bool,
+
+        /// What to do with duplicate copies: delete them, or replace each with a
+        /// hardlink to the kept copy (same volume only) [default: delete]
+        #[arg(long, default_value = "delete", value_name = "MODE", value_parser = ["delete", "hardlink"])]
+        dedupe_mode: String,
+
+        /// Root path to scan (default: home directory)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+
+        /// List every item and choose which ones to leave out of the plan
+        #[arg(short = 'i', long)]
+        interactive: bool,
+
+        /// Project inactivity threshold in days [default: 14]
+        #[arg(long, default_value = "14", value_name = "DAYS")]
+        project_age: u64,
+
+        /// Minimum file age in days for --downloads and --old [default: 30]
+        #[arg(long, default_value = "30", value_name = "DAYS")]
+        min_age: u64,
+
+        /// Minimum file size for --large (e.g., 100MB, 1GB) [default: 100MB]
+        #[arg(long, default_value = "100MB", value_name = "SIZE")]
+        min_size: String,
+
+        /// Only include Recycle Bin items deleted at least this many days ago (with --trash)
+        #[arg(long, value_name = "DAYS")]
+        trash_older_than: Option<u64>,
+
+        /// Exclude paths matching a glob (`**/cache/**`, `*.iso`) or a regex prefixed with `re:` (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+
+        /// Include hidden and system files in --large/--old results (reported separately otherwise)
+        #[arg(long)]
+        include_hidden: bool,
+
+        /// Only plan items on this drive (e.g. D:)
+        #[arg(long, value_name = "VOLUME")]
+        volume: Option<String>,
+
+        /// Plan permanent deletion (bypass Recycle Bin)
+        #[arg(long)]
+        permanent: bool,
+    },
+
+    /// Delete what a plan file lists, only if none of it changed since
+    Apply {
+        /// Plan written by `wole plan`
+        #[arg(value_name = "FILE")]
+        plan: PathBuf,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+
+        /// Check the plan and show what it would delete, without deleting
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Show detailed analysis with file lists
     #[command(visible_alias = "a")]
     Analyze {
@@ -257,6 +532,14 @@ pub enum Commands {
         #[arg(long, value_name = "SORT")]
         sort: Option<String>,
 
+        /// Report NTFS alternate data streams (hidden per-file streams) instead of folder sizes
+        #[arg(long)]
+        ads: bool,
+
+        /// With --ads: remove Zone.Identifier streams from downloads older than --min-age
+        #[arg(long, requires = "ads")]
+        strip_zone_ids: bool,
+
         /// Enable all scan categories (legacy cleanable file analysis)
         #[arg(short = 'a', long)]
         all: bool,
@@ -313,6 +596,14 @@ pub enum Commands {
         #[arg(long)]
         applications: bool,
 
+        /// Scan IDE and toolchain caches (JetBrains, VS Code, Android SDK, Xcode)
+        #[arg(long)]
+        ide: bool,
+
+        /// Scan stale Cargo registry crates, unused rustup toolchains and sccache
+        #[arg(long)]
+        rust: bool,
+
         /// Root path to scan (default: user profile)
         #[arg(long, value_name = "PATH")]
         path: Option<PathBuf>,
@@ -329,7 +620,7 @@ pub enum Commands {
         #[arg(long, default_value = "100MB", value_name = "SIZE")]
         min_size: String,
 
-        /// Exclude paths matching pattern (repeatable)
+        /// Exclude paths matching a glob (`**/cache/**`, `*.iso`) or a regex prefixed with `re:` (repeatable)
         #[arg(long, value_name = "PATTERN")]
         exclude: Vec<String>,
     },
@@ -351,6 +642,10 @@ pub enum Commands {
         /// Clear scan cache
         #[arg(long)]
         clear_cache: bool,
+
+        /// Import BleachBit cleaner XML or CCleaner winapp2.ini rules as custom categories
+        #[arg(long, value_name = "FILE")]
+        import_rules: Option<PathBuf>,
     },
 
     /// Restore files from the last deletion session
@@ -371,6 +666,163 @@ pub enum Commands {
         /// Restore all contents of the Recycle Bin in bulk (faster on Windows)
         #[arg(long)]
         all: bool,
+
+        /// List past deletion sessions and how much of each is still in the Recycle Bin
+        #[arg(long)]
+        list: bool,
+
+        /// Restore a session listed by --list
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Show what --session would restore and which files are in the way, without restoring
+        #[arg(long, requires = "session")]
+        preview: bool,
+
+        /// Only restore entries of these categories (e.g. build,cache)
+        #[arg(long, value_name = "CATEGORY", value_delimiter = ',', conflicts_with_all = ["all", "path"])]
+        category: Vec<String>,
+
+        /// Only restore entries whose file name matches GLOB (e.g. "*.pdf")
+        #[arg(long = "match", value_name = "GLOB", conflicts_with_all = ["all", "path"])]
+        pattern: Option<String>,
+
+        /// Only restore entries deleted within AGE (e.g. 30m, 2h, 3d, 1w)
+        #[arg(long, value_name = "AGE", conflicts_with_all = ["all", "path"])]
+        since: Option<String>,
+    },
+
+    /// Write a markdown summary of recent cleanups
+    Report {
+        /// Write the weekly report to FILE ("-" for stdout)
+        #[arg(long, value_name = "FILE")]
+        weekly: PathBuf,
+
+        /// Skip the scan for current cleanable estimates
+        #[arg(long)]
+        no_scan: bool,
+
+        /// Path to scan for current estimates (default: current directory)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
+
+    /// Show lifetime totals of everything cleaned
+    #[command(after_help = "EXAMPLES:
+    wole stats                   Space reclaimed, items and sessions so far
+    wole stats --json            The same totals as JSON
+    wole stats --rebuild         Recount the totals from the deletion history")]
+    Stats {
+        /// Recount the totals from the deletion history logs
+        #[arg(long)]
+        rebuild: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show, prune or clear the incremental scan cache
+    #[command(after_help = "EXAMPLES:
+    wole cache                   What the cache holds and its size on disk
+    wole cache --prune           Drop entries for missing paths, expired ones and any over [cache] max_size_mb
+    wole cache --clear           Empty the cache; the next scan starts from scratch")]
+    Cache {
+        /// Show what the cache holds (the default)
+        #[arg(long, conflicts_with_all = ["prune", "clear"])]
+        stats: bool,
+
+        /// Remove entries for paths that no longer exist, entries older than
+        /// [cache] max_age_days, and the oldest ones past max_size_mb
+        #[arg(long, conflicts_with = "clear")]
+        prune: bool,
+
+        /// Remove everything in the cache
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// List every category with its id, safety level and default thresholds
+    #[command(after_help = "EXAMPLES:
+    wole categories              Ids, descriptions and defaults as a table
+    wole categories --json       The same list as JSON, for scripts and docs")]
+    Categories {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Maintain the deletion history and disk usage history
+    #[command(
+        args_conflicts_with_subcommands = true,
+        after_help = "EXAMPLES:
+    wole history --trend             How reclaimable space per category grew over 8 weeks
+    wole history --trend --weeks 12  ...over 12 weeks
+    wole history list                Past cleaning sessions, newest first
+    wole history show <id>           What a session deleted and what failed
+    wole history purge --keep 50     Remove all but the newest 50 sessions
+    wole history compact             Apply the [history] retention settings"
+    )]
+    History {
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+
+        /// Show how reclaimable space per category changed across recent scans
+        #[arg(long)]
+        trend: bool,
+
+        /// Weeks covered by --trend
+        #[arg(long, default_value = "8", value_name = "N", requires = "trend")]
+        weeks: u32,
+
+        /// Output --trend as JSON
+        #[arg(long, requires = "trend")]
+        json: bool,
+    },
+
+    /// Free space fast when the system drive is nearly full
+    Emergency {
+        /// Run the plan without asking for confirmation
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Run even if free space is above the critical threshold
+        #[arg(long)]
+        force: bool,
+
+        /// Show the plan without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Keep scan and Disk Insights caches warm while the system is idle
+    #[command(after_help = "EXAMPLES:
+    wole index                   Show when the caches were last refreshed
+    wole index --now             Refresh the caches right away
+    wole index --daemon          Keep running and refresh whenever the system is idle")]
+    Index {
+        /// Keep running in the foreground, indexing whenever the system is idle
+        #[arg(long, conflicts_with = "now")]
+        daemon: bool,
+
+        /// Run one index pass now, regardless of system activity
+        #[arg(long)]
+        now: bool,
+    },
+
+    /// Scan on a schedule and keep the latest report
+    #[command(after_help = "EXAMPLES:
+    wole daemon                  Scan every [daemon] interval_hours (default: daily)
+    wole daemon --interval 6     Scan every 6 hours
+    wole daemon --once           Run one scheduled scan now and exit")]
+    Daemon {
+        /// Hours between scans (overrides `interval_hours` under [daemon])
+        #[arg(long, value_name = "HOURS")]
+        interval: Option<u64>,
+
+        /// Run one scan, hook and notification, then exit
+        #[arg(long)]
+        once: bool,
     },
 
     /// Uninstall wole from your system
@@ -466,13 +918,18 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
 
-        /// Continuous refresh mode (updates every second)
-        #[arg(short = 'w', long)]
-        watch: bool,
+        /// Print the status every SECS seconds (default: 1) instead of opening
+        /// the dashboard; with --json, one JSON document per line
+        #[arg(short = 'w', long, value_name = "SECS", num_args = 0..=1, default_missing_value = "1")]
+        watch: Option<u64>,
 
         /// Use new experimental display format
         #[arg(long)]
         new: bool,
+
+        /// Skip the slower WMI queries (process handles and I/O, boot info)
+        #[arg(long)]
+        fast: bool,
     },
 
     /// Manage Windows startup programs
@@ -502,6 +959,7 @@ impl Cli {
     }
 
     pub fn run(self) -> anyhow::Result<()> {
+        crate::progress::set_mode(&self.progress);
         let output_mode = if self.quiet {
             OutputMode::Quiet
         } else if self.verbose >= 2 {
@@ -532,15 +990,31 @@ impl Cli {
                     applications,
                     windows_update,
                     event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
                     path,
+                    all_drives,
                     json,
+                    json_stream,
+                    summary_only,
+                    diff,
                     project_age,
                     min_age,
                     min_size,
+                    trash_older_than,
                     exclude,
+                    include_hidden,
+                    volume,
                     force_full,
                     no_cache,
                     clear_cache,
+                    quick,
+                    seed,
+                    record,
+                    anonymize,
+                    replay,
                 } => commands::scan_command::handle_scan(
                     all,
                     cache,
@@ -554,15 +1028,31 @@ impl Cli {
                     applications,
                     windows_update,
                     event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
                     path,
+                    all_drives,
                     json,
+                    json_stream,
+                    summary_only,
+                    diff,
                     project_age,
                     min_age,
                     min_size,
+                    trash_older_than,
                     exclude,
+                    include_hidden,
+                    volume,
                     force_full,
                     no_cache,
                     clear_cache,
+                    quick,
+                    seed,
+                    record,
+                    anonymize,
+                    replay,
                     output_mode,
                 ),
                 Commands::Clean {
@@ -582,14 +1072,27 @@ impl Cli {
                     applications,
                     windows_update,
                     event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
+                    take_ownership,
+                    elevate,
+                    dedupe_mode,
                     path,
                     json,
+                    json_stream,
                     yes,
+                    interactive,
                     project_age,
                     min_age,
                     min_size,
+                    trash_older_than,
                     exclude,
+                    include_hidden,
+                    volume,
                     permanent,
+                    archive,
                     dry_run,
                 } => commands::clean_command::handle_clean(
                     all,
@@ -608,17 +1111,109 @@ impl Cli {
                     applications,
                     windows_update,
                     event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
+                    take_ownership,
+                    elevate,
+                    dedupe_mode,
                     path,
                     json,
+                    json_stream,
                     yes,
+                    interactive,
                     project_age,
                     min_age,
                     min_size,
+                    trash_older_than,
                     exclude,
+                    include_hidden,
+                    volume,
                     permanent,
+                    archive,
                     dry_run,
+                    None,
                     output_mode,
                 ),
+                Commands::Plan {
+                    output,
+                    all,
+                    cache,
+                    app_cache,
+                    temp,
+                    trash,
+                    build,
+                    downloads,
+                    large,
+                    old,
+                    browser,
+                    system,
+                    empty,
+                    duplicates,
+                    windows_update,
+                    event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
+                    take_ownership,
+                    dedupe_mode,
+                    path,
+                    interactive,
+                    project_age,
+                    min_age,
+                    min_size,
+                    trash_older_than,
+                    exclude,
+                    include_hidden,
+                    volume,
+                    permanent,
+                } => commands::clean_command::handle_clean(
+                    all,
+                    cache,
+                    app_cache,
+                    temp,
+                    trash,
+                    build,
+                    downloads,
+                    large,
+                    old,
+                    browser,
+                    system,
+                    empty,
+                    duplicates,
+                    false,
+                    windows_update,
+                    event_logs,
+                    orphaned,
+                    custom,
+                    ide,
+                    rust,
+                    take_ownership,
+                    false,
+                    dedupe_mode,
+                    path,
+                    false,
+                    false,
+                    false,
+                    interactive,
+                    project_age,
+                    min_age,
+                    min_size,
+                    trash_older_than,
+                    exclude,
+                    include_hidden,
+                    volume,
+                    permanent,
+                    None,
+                    false,
+                    Some(output),
+                    output_mode,
+                ),
+                Commands::Apply { plan, yes, dry_run } => {
+                    commands::apply_command::handle_apply(plan, yes, dry_run, output_mode)
+                }
                 Commands::Analyze {
                     disk,
                     entire_disk,
@@ -626,6 +1221,8 @@ impl Cli {
                     depth,
                     top,
                     sort,
+                    ads,
+                    strip_zone_ids,
                     all,
                     cache,
                     app_cache,
@@ -640,6 +1237,8 @@ impl Cli {
                     empty,
                     duplicates,
                     applications,
+                    ide,
+                    rust,
                     path,
                     project_age,
                     min_age,
@@ -652,6 +1251,8 @@ impl Cli {
                     depth,
                     top,
                     sort,
+                    ads,
+                    strip_zone_ids,
                     all,
                     cache,
                     app_cache,
@@ -666,6 +1267,8 @@ impl Cli {
                     empty,
                     duplicates,
                     applications,
+                    ide,
+                    rust,
                     path,
                     project_age,
                     min_age,
@@ -678,20 +1281,106 @@ impl Cli {
                     reset,
                     edit,
                     clear_cache,
-                } => commands::config_command::handle_config(show, reset, edit, clear_cache),
+                    import_rules,
+                } => commands::config_command::handle_config(
+                    show,
+                    reset,
+                    edit,
+                    clear_cache,
+                    import_rules,
+                ),
                 Commands::Restore {
                     last,
                     path,
                     from,
                     all,
+                    list,
+                    session,
+                    preview,
+                    category,
+                    pattern,
+                    since,
                 } => commands::restore_command::handle_restore(
                     last,
                     path,
                     from,
                     all,
+                    list,
+                    session,
+                    preview,
+                    crate::restore::RestoreFilter::new(
+                        &category,
+                        pattern.as_deref(),
+                        since.as_deref(),
+                    )?,
                     self.quiet,
                     self.verbose,
                 ),
+                Commands::Report {
+                    weekly,
+                    no_scan,
+                    path,
+                } => commands::report_command::handle_report(weekly, no_scan, path, output_mode),
+                Commands::Stats { rebuild, json } => {
+                    commands::stats_command::handle_stats(rebuild, json, output_mode)
+                }
+                Commands::Cache {
+                    stats: _,
+                    prune,
+                    clear,
+                } => commands::cache_command::handle_cache(prune, clear, output_mode),
+                Commands::History {
+                    action,
+                    trend,
+                    weeks,
+                    json,
+                } => match action {
+                    Some(HistoryAction::List { limit, json }) => {
+                        commands::history_command::handle_history_list(limit, json, output_mode)
+                    }
+                    Some(HistoryAction::Show { id, items, json }) => {
+                        commands::history_command::handle_history_show(
+                            &id,
+                            items,
+                            json,
+                            output_mode,
+                        )
+                    }
+                    Some(HistoryAction::Purge { keep, older_than }) => {
+                        commands::history_command::handle_history_purge(
+                            keep,
+                            older_than,
+                            output_mode,
+                        )
+                    }
+                    Some(HistoryAction::Compact) => {
+                        commands::history_command::handle_history_compact(output_mode)
+                    }
+                    None if trend => {
+                        commands::history_command::handle_history_trend(weeks, json, output_mode)
+                    }
+                    None => {
+                        eprintln!("Nothing to do. Use --trend or a subcommand such as 'list'.");
+                        eprintln!("Run 'wole history --help' for more information.");
+                        Ok(())
+                    }
+                },
+                Commands::Categories { json } => {
+                    commands::categories_command::handle_categories(json, output_mode)
+                }
+                Commands::Emergency {
+                    yes,
+                    force,
+                    dry_run,
+                } => {
+                    commands::emergency_command::handle_emergency(yes, force, dry_run, output_mode)
+                }
+                Commands::Index { daemon, now } => {
+                    commands::index_command::handle_index(daemon, now, output_mode)
+                }
+                Commands::Daemon { interval, once } => {
+                    commands::daemon_command::handle_daemon(interval, once, output_mode)
+                }
                 Commands::Remove { config, data, yes } => commands::remove_command::handle_remove(
                     config,
                     data,
@@ -769,9 +1458,12 @@ impl Cli {
                 //     }
                 //     Ok(())
                 // }
-                Commands::Status { json, watch, new } => {
-                    commands::status_command::handle_status(json, watch, new)
-                }
+                Commands::Status {
+                    json,
+                    watch,
+                    new,
+                    fast,
+                } => commands::status_command::handle_status(json, watch, new, fast),
                 Commands::Startup {
                     list,
                     disable,
@@ -783,6 +1475,50 @@ impl Cli {
     }
 }
 
+#[derive(Subcommand)]
+pub enum HistoryAction {
+    /// List past cleaning sessions, newest first
+    List {
+        /// Show at most N sessions
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// What a session deleted, how much it recovered and what failed
+    Show {
+        /// Session id, as shown by `wole history list`
+        id: String,
+
+        /// List every deleted item, not only the failures
+        #[arg(long)]
+        items: bool,
+
+        /// Output the session's log as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove logs past [history] max_entries / max_age_days; removed
+    /// sessions can no longer be restored
+    Purge {
+        /// Keep only the newest N sessions (overrides [history] max_entries)
+        #[arg(long, value_name = "N")]
+        keep: Option<u64>,
+
+        /// Remove sessions older than DAYS (overrides [history] max_age_days)
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+    },
+
+    /// Remove logs past [history] max_entries / max_age_days and, with
+    /// [history] compress, compress the rest
+    Compact,
+}
+
 #[derive(Clone)]
 pub struct ScanOptions {
     pub cache: bool,
@@ -800,7 +1536,79 @@ pub struct ScanOptions {
     pub duplicates: bool,
     pub windows_update: bool,
     pub event_logs: bool,
+    pub orphaned: bool,
+    pub custom: bool,
+    pub ide: bool,
+    pub rust: bool,
     pub project_age_days: u64,
     pub min_age_days: u64,
     pub min_size_bytes: u64,
+    /// Keep only counts and sizes: results come back without paths and the
+    /// scan cache is neither read nor written
+    pub summary_only: bool,
+    /// Quick estimate (`--quick`) whose seed the size walks sample with
+    pub estimate: Option<std::sync::Arc<crate::estimate::Estimate>>,
+}
+
+impl ScanOptions {
+    /// Options scanning the categories named in `keys` (as in CLI flags, e.g.
+    /// `cache`, `app_cache`), with thresholds from `config`. Installed
+    /// applications are never included.
+    pub fn from_keys(keys: &[String], config: &crate::config::Config) -> Self {
+        let has = |key: &str| keys.iter().any(|k| k.trim().eq_ignore_ascii_case(key));
+        Self {
+            cache: has("cache"),
+            app_cache: has("app_cache"),
+            temp: has("temp"),
+            trash: has("trash"),
+            build: has("build"),
+            downloads: has("downloads"),
+            large: has("large"),
+            old: has("old"),
+            applications: false,
+            browser: has("browser"),
+            system: has("system"),
+            empty: has("empty"),
+            duplicates: has("duplicates"),
+            windows_update: has("windows_update"),
+            event_logs: has("event_logs"),
+            orphaned: has("orphaned"),
+            custom: has("custom"),
+            ide: has("ide"),
+            rust: has("rust"),
+            project_age_days: config.thresholds.project_age_days,
+            min_age_days: config.thresholds.min_age_days,
+            min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+            summary_only: false,
+            estimate: None,
+        }
+    }
+
+    /// CLI keys of the enabled categories, in report order
+    pub fn keys(&self) -> Vec<&'static str> {
+        [
+            ("cache", self.cache),
+            ("app_cache", self.app_cache),
+            ("temp", self.temp),
+            ("trash", self.trash),
+            ("build", self.build),
+            ("downloads", self.downloads),
+            ("large", self.large),
+            ("old", self.old),
+            ("applications", self.applications),
+            ("browser", self.browser),
+            ("system", self.system),
+            ("empty", self.empty),
+            ("duplicates", self.duplicates),
+            ("windows_update", self.windows_update),
+            ("event_logs", self.event_logs),
+            ("orphaned", self.orphaned),
+            ("custom", self.custom),
+            ("ide", self.ide),
+            ("rust", self.rust),
+        ]
+        .into_iter()
+        .filter_map(|(key, enabled)| enabled.then_some(key))
+        .collect()
+    }
 }
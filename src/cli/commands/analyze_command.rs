@@ -17,6 +17,8 @@ pub(crate) fn handle_analyze(
     depth: u8,
     top: Option<usize>,
     sort: Option<String>,
+    ads: bool,
+    strip_zone_ids: bool,
     all: bool,
     cache: bool,
     app_cache: bool,
@@ -31,6 +33,8 @@ pub(crate) fn handle_analyze(
     empty: bool,
     duplicates: bool,
     applications: bool,
+    ide: bool,
+    rust: bool,
     path: Option<PathBuf>,
     project_age: u64,
     min_age: u64,
@@ -55,6 +59,8 @@ pub(crate) fn handle_analyze(
         || empty
         || duplicates
         || applications
+        || ide
+        || rust
         || all;
     let disk_mode = disk || (!has_category_flags); // Default to disk mode if no category flags
 
@@ -107,6 +113,19 @@ pub(crate) fn handle_analyze(
             depth
         };
 
+        if ads {
+            return handle_ads(
+                &scan_path,
+                effective_depth,
+                strip_zone_ids,
+                min_age,
+                output_mode,
+            );
+        }
+
+        // Full-tree walk: check battery before starting
+        crate::power::guard_cli(&config.power, output_mode == OutputMode::Quiet)?;
+
         // Scan directory
         let spinner = if output_mode != OutputMode::Quiet {
             Some(crate::progress::create_spinner(&format!(
@@ -132,7 +151,7 @@ pub(crate) fn handle_analyze(
                 insights: insights.clone(),
                 current_path: scan_path.clone(),
                 cursor: 0,
-                sort_by,
+                sort_by: sort_by.into(),
                 selected_paths: std::collections::HashSet::new(),
             };
             tui::run(Some(app_state))?;
@@ -164,9 +183,12 @@ pub(crate) fn handle_analyze(
             system,
             empty,
             duplicates,
+            ide,
+            rust,
         ) = if all {
             (
-                true, true, true, true, true, true, true, true, true, true, true, true, true,
+                true, true, true, true, true, true, true, true, true, true, true, true, true, true,
+                true,
             )
         } else {
             (
@@ -183,6 +205,8 @@ pub(crate) fn handle_analyze(
                 system,
                 empty,
                 duplicates,
+                ide,
+                rust,
             )
         };
 
@@ -208,11 +232,16 @@ pub(crate) fn handle_analyze(
         );
 
         // Merge CLI exclusions
-        config.exclusions.patterns.extend(exclude.iter().cloned());
+        crate::scanner::exclusions::validate(&exclude)?;
+        config.exclusions.add_patterns(&exclude);
 
         // Use config values (after CLI overrides) for scan options
         let min_size_bytes = config.thresholds.min_size_mb * 1024 * 1024;
 
+        if duplicates {
+            crate::power::guard_cli(&config.power, output_mode == OutputMode::Quiet)?;
+        }
+
         // Open scan cache if enabled
         let use_cache = config.cache.enabled;
         let mut scan_cache = if use_cache {
@@ -250,9 +279,15 @@ pub(crate) fn handle_analyze(
                 duplicates,
                 windows_update: false,
                 event_logs: false,
+                orphaned: false,
+                custom: false,
+                ide,
+                rust,
                 project_age_days: config.thresholds.project_age_days,
                 min_age_days: config.thresholds.min_age_days,
                 min_size_bytes,
+                summary_only: false,
+                estimate: None,
             },
             output_mode,
             &config,
@@ -277,3 +312,71 @@ pub(crate) fn handle_analyze(
         Ok(())
     }
 }
+
+/// `wole analyze --ads`: report alternate data streams under `scan_path`
+fn handle_ads(
+    scan_path: &std::path::Path,
+    depth: u8,
+    strip_zone_ids: bool,
+    min_age: u64,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    use crate::ads;
+
+    let spinner = if output_mode != OutputMode::Quiet {
+        Some(crate::progress::create_spinner(&format!(
+            "Reading alternate data streams in {} (depth: {})...",
+            scan_path.display(),
+            depth
+        )))
+    } else {
+        None
+    };
+
+    let report = ads::scan(scan_path, depth as usize);
+
+    // Old downloads still marked as "from the internet"
+    let downloads_dir = std::env::var("USERPROFILE")
+        .map(|p| PathBuf::from(p).join("Downloads"))
+        .ok()
+        .filter(|d| d.exists());
+    let zoned_downloads = downloads_dir
+        .as_deref()
+        .map(|d| ads::old_downloads_with_zone_identifier(d, min_age))
+        .unwrap_or_default();
+
+    if let Some(sp) = spinner {
+        crate::progress::finish_and_clear(&sp);
+    }
+
+    output::print_ads_report(&report, scan_path, &zoned_downloads, min_age, output_mode);
+
+    if strip_zone_ids && !zoned_downloads.is_empty() {
+        let mut stripped = 0usize;
+        let mut failed = 0usize;
+        for (path, _) in &zoned_downloads {
+            match ads::strip_zone_identifier(path) {
+                Ok(()) => stripped += 1,
+                Err(e) => {
+                    failed += 1;
+                    if output_mode == OutputMode::VeryVerbose {
+                        eprintln!("  {}", e);
+                    }
+                }
+            }
+        }
+        if output_mode != OutputMode::Quiet {
+            println!(
+                "  Removed Zone.Identifier from {} file(s){}",
+                stripped,
+                if failed > 0 {
+                    format!(", {} failed", failed)
+                } else {
+                    String::new()
+                }
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Apply command feature.
+//!
+//! This module owns and handles the "wole apply" command behavior.
+
+use crate::cleaner;
+use crate::output::render;
+use crate::output::OutputMode;
+use crate::theme::Theme;
+use crate::utils;
+use std::path::PathBuf;
+
+/// Drifted paths listed before giving up
+const DRIFT_SHOWN: usize = 20;
+
+pub(crate) fn handle_apply(
+    plan_path: PathBuf,
+    yes: bool,
+    dry_run: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let plan = crate::plan::load(&plan_path)?;
+    let quiet = output_mode == OutputMode::Quiet;
+
+    if !quiet {
+        println!();
+        println!(
+            "Plan {} from {}: {} items, {}",
+            Theme::primary(&plan_path.display().to_string()),
+            plan.created_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+            render::format_number(plan.entries.len() as u64),
+            Theme::size(&render::format_size(plan.total_bytes()))
+        );
+    }
+
+    let drift = plan.drift();
+    if !drift.is_empty() {
+        if !quiet {
+            for d in drift.iter().take(DRIFT_SHOWN) {
+                println!(
+                    "  {} {} ({})",
+                    Theme::error("✗"),
+                    utils::display_path(&d.path),
+                    d.reason
+                );
+            }
+            if drift.len() > DRIFT_SHOWN {
+                println!(
+                    "  {}",
+                    Theme::muted(&format!("...and {} more", drift.len() - DRIFT_SHOWN))
+                );
+            }
+        }
+        anyhow::bail!(
+            "{} planned path(s) changed since the plan was written; nothing was deleted. Run `wole plan` again.",
+            drift.len()
+        );
+    }
+    if !quiet {
+        println!("{} Every planned path is unchanged", Theme::success("[OK]"));
+    }
+
+    cleaner::clean_all_with_dedupe(
+        &plan.scan_results(),
+        yes,
+        output_mode,
+        plan.permanent,
+        dry_run,
+        plan.take_ownership,
+        plan.dedupe_mode(),
+        false,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Cache command feature.
+//!
+//! This module owns and handles the "wole cache" command behavior.
+
+use crate::config::Config;
+use crate::output::render;
+use crate::output::OutputMode;
+use crate::scan_cache::ScanCache;
+use crate::theme::Theme;
+use anyhow::Context;
+
+pub(crate) fn handle_cache(
+    prune: bool,
+    clear: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let mut cache = ScanCache::open().context("Failed to open scan cache")?;
+    let quiet = output_mode == OutputMode::Quiet;
+
+    if clear {
+        cache.clear_all()?;
+        if !quiet {
+            println!("{} Scan cache cleared.", Theme::success("[OK]"));
+        }
+        return Ok(());
+    }
+
+    if prune {
+        let settings = Config::load().cache;
+        let stats = cache.prune(settings.max_age_days, settings.max_size_mb * 1024 * 1024)?;
+        if !quiet {
+            println!(
+                "{} Pruned {} entries: {} for missing paths, {} older than {} days, {} over the {} MB limit",
+                Theme::success("[OK]"),
+                render::format_number(stats.removed() as u64),
+                render::format_number(stats.missing as u64),
+                render::format_number(stats.expired as u64),
+                settings.max_age_days,
+                render::format_number(stats.evicted as u64),
+                settings.max_size_mb
+            );
+            println!(
+                "  {} Database: {} → {}",
+                Theme::muted("→"),
+                render::format_size(stats.bytes_before),
+                render::format_size(stats.bytes_after)
+            );
+        }
+        return Ok(());
+    }
+
+    let stats = cache.stats()?;
+    if quiet {
+        println!("{}", stats.db_bytes);
+        return Ok(());
+    }
+    let local = |t: chrono::DateTime<chrono::Utc>| {
+        t.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    println!();
+    println!("{}", Theme::header("Scan Cache"));
+    println!("{}", Theme::divider_bold(60));
+    println!(
+        "  Database size:    {}",
+        Theme::size(&render::format_size(stats.db_bytes))
+    );
+    println!(
+        "  Files tracked:    {} ({})",
+        render::format_number(stats.files as u64),
+        render::format_size(stats.scanned_bytes)
+    );
+    println!(
+        "  Hashed files:     {}",
+        render::format_number(stats.file_hashes as u64)
+    );
+    println!(
+        "  Saved results:    {}",
+        render::format_number(stats.category_results as u64)
+    );
+    println!(
+        "  Scan sessions:    {}",
+        render::format_number(stats.sessions as u64)
+    );
+    if let Some(oldest) = stats.oldest_entry {
+        println!("  Oldest entry:     {}", local(oldest));
+    }
+    println!(
+        "  Last pruned:      {}",
+        stats
+            .last_pruned
+            .map(local)
+            .unwrap_or_else(|| "never".to_string())
+    );
+    println!();
+    println!(
+        "Run {} to drop stale entries or {} to start over.",
+        Theme::command("wole cache --prune"),
+        Theme::command("wole cache --clear")
+    );
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Categories command feature.
+//!
+//! This module owns and handles the "wole categories" command behavior.
+
+use crate::config::Config;
+use crate::output::OutputMode;
+use crate::theme::Theme;
+use wole_core::policy::{CategoryPolicy, Safety, CATEGORIES};
+
+/// Settings that decide what a category reports, with their default values
+fn default_thresholds(key: &str, config: &Config) -> Vec<(&'static str, u64)> {
+    let t = &config.thresholds;
+    let c = &config.categories;
+    match key {
+        "temp" => vec![(
+            "categories.stale_locks.min_age_days",
+            c.stale_locks.min_age_days,
+        )],
+        "trash" => vec![("thresholds.trash_min_age_days", t.trash_min_age_days)],
+        "build" => vec![("thresholds.project_age_days", t.project_age_days)],
+        "downloads" | "old" => vec![("thresholds.min_age_days", t.min_age_days)],
+        "large" => vec![("thresholds.min_size_mb", t.min_size_mb)],
+        "app_cache" => vec![(
+            "categories.app_leftovers.min_age_days",
+            c.app_leftovers.min_age_days,
+        )],
+        "duplicates" => vec![(
+            "categories.duplicates.similar_images_max_distance",
+            c.duplicates.similar_images_max_distance as u64,
+        )],
+        "rust" => vec![("categories.rust.unused_days", c.rust.unused_days)],
+        _ => Vec::new(),
+    }
+}
+
+fn safety_name(safety: Safety) -> &'static str {
+    match safety {
+        Safety::Safe => "safe",
+        Safety::Review => "review",
+        Safety::Admin => "admin",
+    }
+}
+
+fn platforms(policy: &CategoryPolicy) -> &'static [&'static str] {
+    if policy.windows_only {
+        &["windows"]
+    } else {
+        &["windows", "macos", "linux"]
+    }
+}
+
+pub(crate) fn handle_categories(json: bool, output_mode: OutputMode) -> anyhow::Result<()> {
+    let config = Config::default();
+
+    if json {
+        let categories: Vec<serde_json::Value> = CATEGORIES
+            .iter()
+            .map(|policy| {
+                let thresholds: serde_json::Map<String, serde_json::Value> =
+                    default_thresholds(policy.key, &config)
+                        .into_iter()
+                        .map(|(name, value)| (name.to_string(), value.into()))
+                        .collect();
+                serde_json::json!({
+                    "id": policy.key,
+                    "name": policy.label,
+                    "description": policy.description,
+                    "safety": safety_name(policy.safety),
+                    "flag": format!("--{}", policy.key.replace('_', "-")),
+                    "platforms": platforms(policy),
+                    "default_thresholds": thresholds,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&categories)?);
+        return Ok(());
+    }
+    if output_mode == OutputMode::Quiet {
+        for policy in CATEGORIES.iter() {
+            println!("{}", policy.key);
+        }
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", Theme::header("Categories"));
+    println!("{}", Theme::divider_bold(60));
+    for policy in CATEGORIES.iter() {
+        let mut notes = vec![safety_name(policy.safety).to_string()];
+        if policy.windows_only {
+            notes.push("Windows only".to_string());
+        }
+        println!(
+            "  {} {}  {}",
+            Theme::command(&format!("{:<16}", policy.key)),
+            policy.description,
+            Theme::muted(&format!("({})", notes.join(", ")))
+        );
+        for (name, value) in default_thresholds(policy.key, &config) {
+            println!(
+                "  {:<16} {}",
+                "",
+                Theme::muted(&format!("{} = {}", name, value))
+            );
+        }
+    }
+    println!();
+    println!(
+        "Use an id as a flag ({}) or in config ({}).",
+        Theme::command("wole scan --app-cache"),
+        Theme::command("[daemon] categories = [\"app_cache\"]")
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::ScanResults;
+
+    #[test]
+    fn registry_matches_scan_results() {
+        let results = ScanResults::default();
+        let keys: Vec<&str> = results.by_category().iter().map(|(k, _)| *k).collect();
+        let ids: Vec<&str> = CATEGORIES.iter().map(|p| p.key).collect();
+        assert_eq!(keys, ids);
+        assert!(CATEGORIES.iter().all(|p| !p.description.is_empty()));
+    }
+}
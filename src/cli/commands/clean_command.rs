@@ -2,6 +2,7 @@
 //!
 //! This module owns and handles the "wole clean" command behavior.
 
+use crate::categories::duplicates::DedupeMode;
 use crate::cleaner;
 use crate::cli::ScanOptions;
 use crate::config::Config;
@@ -65,15 +66,29 @@ pub(crate) fn handle_clean(
     applications: bool,
     windows_update: bool,
     event_logs: bool,
+    orphaned: bool,
+    custom: bool,
+    ide: bool,
+    rust: bool,
+    take_ownership: bool,
+    elevate: bool,
+    dedupe_mode: String,
     path: Option<PathBuf>,
     json: bool,
+    json_stream: bool,
     yes: bool,
+    interactive: bool,
     project_age: u64,
     min_age: u64,
     min_size: String,
+    trash_older_than: Option<u64>,
     exclude: Vec<String>,
+    include_hidden: bool,
+    volume: Option<String>,
     permanent: bool,
+    archive: Option<PathBuf>,
     dry_run: bool,
+    plan: Option<PathBuf>,
     output_mode: OutputMode,
 ) -> anyhow::Result<()> {
     // --all enables all categories
@@ -93,10 +108,16 @@ pub(crate) fn handle_clean(
         duplicates,
         windows_update,
         event_logs,
+        orphaned,
+        custom,
+        ide,
+        rust,
     ) = if all {
+        // Orphaned files are user data and custom rules may be unreviewed
+        // imports: both only when asked for by name
         (
             true, true, true, true, true, true, true, true, true, true, true, true, true, true,
-            true,
+            true, orphaned, custom, true, true,
         )
     } else if !cache
         && !app_cache
@@ -113,6 +134,10 @@ pub(crate) fn handle_clean(
         && !applications
         && !windows_update
         && !event_logs
+        && !orphaned
+        && !custom
+        && !ide
+        && !rust
     {
         // No categories specified - show help message
         eprintln!("No categories specified. Use --all or specify categories like --cache, --app-cache, --temp, --build");
@@ -135,8 +160,31 @@ pub(crate) fn handle_clean(
             duplicates,
             windows_update,
             event_logs,
+            orphaned,
+            custom,
+            ide,
+            rust,
         )
     };
+    // Uninstalls can't be replayed from a plan file
+    let applications = applications && plan.is_none();
+    if archive.is_some() && !build {
+        anyhow::bail!("--archive only applies to build artifacts; add --build");
+    }
+
+    if interactive && (json || json_stream) {
+        anyhow::bail!("--interactive can't be combined with --json or --json-stream");
+    }
+    // Nothing but events goes to stdout, so there's no prompt to answer
+    let output_mode = if json_stream {
+        if !yes && !dry_run {
+            anyhow::bail!("--json-stream needs --yes (or --dry-run)");
+        }
+        crate::json_stream::enable();
+        OutputMode::Quiet
+    } else {
+        output_mode
+    };
 
     let scan_path = path.unwrap_or_else(|| {
         directories::UserDirs::new()
@@ -158,9 +206,21 @@ pub(crate) fn handle_clean(
                 / (1024 * 1024),
         ), // Convert bytes to MB for config
     );
+    if let Some(days) = trash_older_than {
+        config.thresholds.trash_min_age_days = days;
+    }
 
     // Merge CLI exclusions
-    config.exclusions.patterns.extend(exclude.iter().cloned());
+    crate::scanner::exclusions::validate(&exclude)?;
+    config.exclusions.add_patterns(&exclude);
+    if include_hidden {
+        config.safety.include_hidden = true;
+    }
+
+    // Duplicate detection hashes file contents; check battery before starting
+    if duplicates {
+        crate::power::guard_cli(&config.power, output_mode == OutputMode::Quiet)?;
+    }
 
     let mut scan_cache = if config.cache.enabled {
         match crate::scan_cache::ScanCache::open() {
@@ -254,22 +314,46 @@ pub(crate) fn handle_clean(
         duplicates,
         windows_update,
         event_logs,
+        orphaned,
+        custom,
+        ide,
+        rust,
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
+        summary_only: false,
+        estimate: None,
     };
 
-    let results = scanner::scan_all(
-        &scan_path,
-        scan_options.clone(),
-        output_mode,
-        &config,
-        scan_cache.as_mut(),
-    )?;
-
-    if json {
-        output::print_json(&results)?;
+    let mut results = if json_stream {
+        scanner::scan_with_events(
+            &scan_path,
+            scan_options.clone(),
+            &config,
+            scan_cache.as_mut(),
+            None,
+            &crate::json_stream::emit_scan,
+        )?
     } else {
+        // Scan chatter would land in front of the JSON on stdout
+        scanner::scan_all(
+            &scan_path,
+            scan_options.clone(),
+            if json { OutputMode::Quiet } else { output_mode },
+            &config,
+            scan_cache.as_mut(),
+        )?
+    };
+    if let Some(ref volume) = volume {
+        crate::volumes::filter_results(&mut results, volume);
+    }
+    if json_stream {
+        crate::json_stream::emit(&crate::json_stream::Event::scan_finished(&results));
+    }
+
+    // With --json, results are printed once cleaning is done so the clean
+    // outcome (with per-path failure reasons) lands in the same document
+    if !json && !json_stream {
         output::print_human_with_options(&results, output_mode, Some(&scan_options));
     }
 
@@ -317,7 +401,67 @@ pub(crate) fn handle_clean(
         }
     }
 
-    cleaner::clean_all(&results, yes, output_mode, permanent, dry_run)?;
+    if interactive {
+        crate::selection::prompt(&mut results)?;
+    }
+
+    if let Some(out) = plan {
+        return super::plan_command::handle_plan(
+            &results,
+            &scan_path,
+            &out,
+            permanent,
+            DedupeMode::parse(&dedupe_mode)?,
+            take_ownership,
+            output_mode,
+        );
+    }
+
+    let options = cleaner::CleanOptions {
+        skip_confirm: yes,
+        mode: if json { OutputMode::Quiet } else { output_mode },
+        permanent,
+        dry_run,
+        take_ownership,
+        dedupe_mode: DedupeMode::parse(&dedupe_mode)?,
+        elevate,
+    };
+    // A dry run deletes nothing, so there's nothing to archive
+    let registry = match archive.filter(|_| !dry_run) {
+        Some(dir) => {
+            cleaner::CleanerRegistry::default().with_hooks(cleaner::archive::ArchiveHooks::new(dir))
+        }
+        None => cleaner::CleanerRegistry::default(),
+    };
+    let log = cleaner::clean_with(&results, &registry, &options)?;
+
+    if json {
+        output::print_json_with_clean(&results, log.as_ref())?;
+    }
+    if json_stream {
+        let (cleaned, cleaned_bytes, errors) = log
+            .as_ref()
+            .map(|log| {
+                (
+                    log.total_items - log.errors,
+                    log.total_bytes_cleaned,
+                    log.errors,
+                )
+            })
+            .unwrap_or_default();
+        crate::json_stream::emit(&crate::json_stream::Event::CleanFinished {
+            cleaned,
+            cleaned_bytes,
+            errors,
+        });
+    }
+
+    // Unattended cleans (e.g. on build agents) report where someone will see them
+    if let Some(log) = log.as_ref().filter(|_| !dry_run) {
+        if let Err(e) = crate::notify::notify_clean_finished(&config.notifications, log) {
+            eprintln!("{}", Theme::warning_msg(&e.to_string()));
+        }
+    }
 
     Ok(())
 }
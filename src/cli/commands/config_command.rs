@@ -5,13 +5,19 @@
 use crate::config::Config;
 use crate::theme::Theme;
 use bytesize;
+use std::path::{Path, PathBuf};
 
 pub(crate) fn handle_config(
     show: bool,
     reset: bool,
     edit: bool,
     clear_cache: bool,
+    import_rules: Option<PathBuf>,
 ) -> anyhow::Result<()> {
+    if let Some(rules_path) = import_rules {
+        return handle_import_rules(&rules_path);
+    }
+
     if show {
         let config = Config::load_or_create();
         println!("{}", Theme::header("Current Configuration"));
@@ -58,6 +64,10 @@ pub(crate) fn handle_config(
             "  Max no-confirm size: {} MB",
             config.safety.max_size_no_confirm_mb
         );
+        println!(
+            "  Typed confirm at: {} GB / {} items",
+            config.safety.typed_confirm_gb, config.safety.typed_confirm_items
+        );
         println!("  Skip locked files: {}", config.safety.skip_locked_files);
         println!("  Dry run default: {}", config.safety.dry_run_default);
         println!();
@@ -66,6 +76,10 @@ pub(crate) fn handle_config(
             "  Scan threads: {} (0 = auto)",
             config.performance.scan_threads
         );
+        println!(
+            "  Delete threads: {} (0 = auto)",
+            config.performance.delete_threads
+        );
         println!("  Batch size: {}", config.performance.batch_size);
         println!(
             "  Parallel scanning: {}",
@@ -82,11 +96,13 @@ pub(crate) fn handle_config(
             "  Max age: {} days (0 = forever)",
             config.history.max_age_days
         );
+        println!("  Compress: {}", config.history.compress);
         println!();
         println!("Cache Settings:");
         println!("  Enabled: {}", config.cache.enabled);
         println!("  Full disk baseline: {}", config.cache.full_disk_baseline);
         println!("  Max age: {} days", config.cache.max_age_days);
+        println!("  Max size: {} MB (0 = no limit)", config.cache.max_size_mb);
         println!(
             "  Content hash threshold: {}",
             bytesize::to_string(config.cache.content_hash_threshold_bytes, false)
@@ -171,6 +187,10 @@ pub(crate) fn handle_config(
             "  Max no-confirm size: {} MB",
             config.safety.max_size_no_confirm_mb
         );
+        println!(
+            "  Typed confirm at: {} GB / {} items",
+            config.safety.typed_confirm_gb, config.safety.typed_confirm_items
+        );
         println!("  Skip locked files: {}", config.safety.skip_locked_files);
         println!("  Dry run default: {}", config.safety.dry_run_default);
         println!();
@@ -179,6 +199,10 @@ pub(crate) fn handle_config(
             "  Scan threads: {} (0 = auto)",
             config.performance.scan_threads
         );
+        println!(
+            "  Delete threads: {} (0 = auto)",
+            config.performance.delete_threads
+        );
         println!("  Batch size: {}", config.performance.batch_size);
         println!(
             "  Parallel scanning: {}",
@@ -195,11 +219,13 @@ pub(crate) fn handle_config(
             "  Max age: {} days (0 = forever)",
             config.history.max_age_days
         );
+        println!("  Compress: {}", config.history.compress);
         println!();
         println!("Cache Settings:");
         println!("  Enabled: {}", config.cache.enabled);
         println!("  Full disk baseline: {}", config.cache.full_disk_baseline);
         println!("  Max age: {} days", config.cache.max_age_days);
+        println!("  Max size: {} MB (0 = no limit)", config.cache.max_size_mb);
         println!(
             "  Content hash threshold: {}",
             bytesize::to_string(config.cache.content_hash_threshold_bytes, false)
@@ -211,3 +237,49 @@ pub(crate) fn handle_config(
     }
     Ok(())
 }
+
+/// `wole config --import-rules <FILE>`: convert BleachBit/CCleaner rules into custom categories
+fn handle_import_rules(rules_path: &Path) -> anyhow::Result<()> {
+    let report = crate::rules_import::import_file(rules_path)?;
+
+    for note in &report.skipped {
+        println!("  {} {}", Theme::muted("skipped:"), Theme::muted(note));
+    }
+
+    if report.categories.is_empty() {
+        println!(
+            "{}",
+            Theme::warning_msg(&format!(
+                "No importable file rules found in {}",
+                rules_path.display()
+            ))
+        );
+        return Ok(());
+    }
+
+    for category in &report.categories {
+        println!(
+            "  {} {} ({} rule{})",
+            Theme::success("+"),
+            category.name,
+            category.rules.len(),
+            if category.rules.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    let mut config = Config::load_or_create();
+    let (added, replaced) =
+        crate::rules_import::merge_into(&mut config.custom_categories, report.categories);
+    config.save()?;
+
+    println!();
+    println!(
+        "{} Imported {} custom categor{} ({} new, {} updated). Scan them with --custom.",
+        Theme::success("OK"),
+        added + replaced,
+        if added + replaced == 1 { "y" } else { "ies" },
+        added,
+        replaced
+    );
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Daemon command feature.
+//!
+//! This module owns and handles the "wole daemon" command behavior.
+
+use crate::config::Config;
+use crate::output::OutputMode;
+use crate::scheduler::{self, ScheduleState};
+use crate::tasks::CancelToken;
+use crate::theme::Theme;
+
+pub(crate) fn handle_daemon(
+    interval: Option<u64>,
+    once: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let config = Config::load();
+    let quiet = output_mode == OutputMode::Quiet;
+    let mut log = |msg: &str| {
+        if !quiet {
+            println!(
+                "{} {} {}",
+                Theme::muted(&chrono::Local::now().format("%Y-%m-%d %H:%M").to_string()),
+                Theme::muted("→"),
+                msg
+            );
+        }
+    };
+
+    if once {
+        scheduler::run_scan(&config, &mut log)?;
+        if !quiet {
+            println!(
+                "{} Report written to {}",
+                Theme::success("[OK]"),
+                crate::utils::display_path(&scheduler::report_path()?)
+            );
+        }
+        return Ok(());
+    }
+
+    let interval = interval.unwrap_or(config.daemon.interval_hours).max(1);
+    if !quiet {
+        let next =
+            ScheduleState::load().due_in(chrono::Utc::now().timestamp().max(0) as u64, interval);
+        println!(
+            "{}",
+            Theme::muted(&format!(
+                "Scanning every {}h (next scan in {}); press Ctrl+C to stop.",
+                interval,
+                crate::output::render::format_age(next)
+            ))
+        );
+    }
+    scheduler::run(&config, interval, &CancelToken::new(), log)
+}
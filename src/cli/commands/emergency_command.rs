@@ -0,0 +1,151 @@
+//! Emergency command feature.
+//!
+//! This module owns and handles the "wole emergency" command behavior.
+
+use crate::config::Config;
+use crate::emergency;
+use crate::output::OutputMode;
+use crate::quota::QuotaKind;
+use crate::theme::Theme;
+use std::io::{self, BufRead, Write};
+
+pub(crate) fn handle_emergency(
+    yes: bool,
+    force: bool,
+    dry_run: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let config = Config::load();
+    let quiet = output_mode == OutputMode::Quiet;
+
+    let space = emergency::system_drive_space(&config.quota);
+    if let Some(ref space) = space {
+        let critical = config.emergency.is_critical(space.total, space.available);
+        if !quiet {
+            println!(
+                "{} {} free of {} on {}{}",
+                if critical {
+                    Theme::error("[CRITICAL]")
+                } else {
+                    Theme::success("[OK]")
+                },
+                Theme::size(&bytesize::to_string(space.available, false)),
+                bytesize::to_string(space.total, false),
+                crate::utils::display_path(&space.mount),
+                space
+                    .quota
+                    .as_ref()
+                    .map(|q| format!(" (limited by your {})", q.kind.label()))
+                    .unwrap_or_default()
+            );
+            if critical && space.quota.as_ref().map(|q| q.kind) == Some(QuotaKind::OneDrive) {
+                println!(
+                    "{}",
+                    Theme::warning("Caches and temp files don't count against OneDrive: this plan frees local disk space only. Move or delete large files in the OneDrive folder to get under the plan limit.")
+                );
+            }
+        }
+        if !critical && !force {
+            if !quiet {
+                println!(
+                    "{}",
+                    Theme::muted("Free space is above the critical threshold; use `wole clean` or pass --force.")
+                );
+            }
+            return Ok(());
+        }
+    }
+
+    if !quiet {
+        println!(
+            "{}",
+            Theme::muted("Estimating temp files, caches and Recycle Bin...")
+        );
+    }
+    let plan = emergency::build_plan(&config);
+    if plan.is_empty() {
+        if !quiet {
+            println!("{}", Theme::muted("Nothing to free in safe categories."));
+        }
+        return Ok(());
+    }
+
+    let total_bytes: u64 = plan.iter().map(|s| s.result.size_bytes).sum();
+    let total_items: usize = plan.iter().map(|s| s.result.items).sum();
+    if !quiet {
+        println!();
+        println!("{}", Theme::header("Emergency Cleanup Plan"));
+        println!("{}", Theme::divider(60));
+        for (i, step) in plan.iter().enumerate() {
+            let size = if step.category == "Trash" {
+                "empty bin".to_string()
+            } else {
+                bytesize::to_string(step.result.size_bytes, false)
+            };
+            println!(
+                "  {}. {:<20} {:>8} items  {}",
+                i + 1,
+                step.category,
+                step.result.items,
+                Theme::size(&format!("{:>10}", size))
+            );
+        }
+        println!("{}", Theme::divider(60));
+        println!(
+            "  {} items, about {} (plus the Recycle Bin)",
+            total_items,
+            Theme::size(&bytesize::to_string(total_bytes, false))
+        );
+        println!(
+            "{}",
+            Theme::warning(
+                "Items are deleted permanently: recycling them would not free any space."
+            )
+        );
+    }
+
+    if dry_run {
+        if !quiet {
+            println!("{}", Theme::muted("Dry run: nothing was deleted."));
+        }
+        return Ok(());
+    }
+
+    if !yes {
+        print!("Run this plan? [y/N]: ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
+        if input != "y" && input != "yes" {
+            println!("{}", Theme::muted("Cancelled."));
+            return Ok(());
+        }
+    }
+
+    let outcome = emergency::execute(&plan);
+    if !quiet {
+        println!();
+        println!(
+            "{} Deleted {} items, freed {}",
+            Theme::success("[OK]"),
+            outcome.deleted,
+            Theme::size(&bytesize::to_string(outcome.freed_bytes, false))
+        );
+        if outcome.errors > 0 {
+            println!(
+                "{} {} items could not be deleted (in use or protected)",
+                Theme::warning("[WARNING]"),
+                outcome.errors
+            );
+        }
+        if let Some(after) = emergency::system_drive_space(&config.quota) {
+            println!(
+                "  {} Now {} free",
+                Theme::muted("→"),
+                bytesize::to_string(after.available, false)
+            );
+        }
+    }
+    Ok(())
+}
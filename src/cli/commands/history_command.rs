@@ -0,0 +1,359 @@
+//! History command feature.
+//!
+//! This module owns and handles the "wole history" command behavior.
+
+use crate::config::Config;
+use crate::history::{self, DeletionLog};
+use crate::output::render;
+use crate::output::OutputMode;
+use crate::theme::Theme;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Widest sparkline `history --trend` prints (one point per day up to this)
+const MAX_TREND_POINTS: usize = 56;
+
+/// Enforce the `[history]` retention settings and, with `compress` set,
+/// compress the deletion logs and disk usage history
+pub(crate) fn handle_history_compact(output_mode: OutputMode) -> anyhow::Result<()> {
+    let settings = Config::load().history;
+    let now = chrono::Utc::now();
+
+    let logs = crate::history::compact_logs(&crate::history::get_history_dir()?, &settings, now)?;
+    let (usage_logs, usage_before, usage_after) =
+        crate::usage_history::compact_all(settings.compress, now)?;
+
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    println!(
+        "{} Deletion logs: {} removed by retention, {} compressed ({} -> {})",
+        Theme::success("[OK]"),
+        render::format_number(logs.removed as u64),
+        render::format_number(logs.compressed as u64),
+        render::format_size(logs.bytes_before),
+        render::format_size(logs.bytes_after)
+    );
+    println!(
+        "{} Disk usage history: {} logs compacted ({} -> {})",
+        Theme::success("[OK]"),
+        render::format_number(usage_logs as u64),
+        render::format_size(usage_before),
+        render::format_size(usage_after)
+    );
+    if !settings.compress {
+        println!(
+            "    {}",
+            Theme::muted("Set [history] compress = true to store history compressed")
+        );
+    }
+    Ok(())
+}
+
+/// Print how reclaimable space per category changed over the last `weeks`
+pub(crate) fn handle_history_trend(
+    weeks: u32,
+    json: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let samples = crate::history::load_trend()?;
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::weeks(weeks.max(1) as i64);
+    let points = (weeks.max(1) as usize * 7).min(MAX_TREND_POINTS);
+    let trend = crate::history::size_trend(&samples, since, now, points);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&trend)?);
+        return Ok(());
+    }
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    if trend.times.is_empty() {
+        println!(
+            "{} No scans recorded yet; run {} to start the trend",
+            Theme::muted("→"),
+            Theme::command("wole scan --all")
+        );
+        return Ok(());
+    }
+
+    let scans = samples.iter().filter(|s| s.time >= since).count();
+    println!();
+    println!(
+        "{}",
+        Theme::header(&format!(
+            "Reclaimable space, last {} weeks ({} scans)",
+            weeks.max(1),
+            render::format_number(scans as u64)
+        ))
+    );
+    println!("{}", Theme::divider(60));
+
+    let row = |name: &str, values: &[u64]| {
+        let (first, last) = (values[0], values[values.len() - 1]);
+        let sign = if last >= first { "+" } else { "-" };
+        println!(
+            "  {} {}  {} -> {} ({}{})",
+            render::pad_right(name, 16),
+            Theme::size(&render::sparkline(values)),
+            render::format_size(first),
+            render::format_size(last),
+            sign,
+            render::format_size(last.abs_diff(first))
+        );
+    };
+    row("Total", &trend.totals);
+    let mut categories: Vec<_> = trend
+        .categories
+        .iter()
+        .filter(|(_, values)| values.iter().any(|&v| v > 0))
+        .collect();
+    categories.sort_by_key(|(_, values)| std::cmp::Reverse(values[values.len() - 1]));
+    for (name, values) in categories {
+        row(name, values);
+    }
+    Ok(())
+}
+
+/// One row of `history list`
+#[derive(Serialize)]
+struct SessionSummary {
+    id: String,
+    started: chrono::DateTime<chrono::Utc>,
+    deleted: usize,
+    bytes_cleaned: u64,
+    failed: usize,
+    categories: Vec<String>,
+}
+
+impl SessionSummary {
+    fn of(id: String, log: &DeletionLog) -> Self {
+        let mut categories: Vec<String> = Vec::new();
+        for record in &log.records {
+            if !categories.contains(&record.category) {
+                categories.push(record.category.clone());
+            }
+        }
+        Self {
+            id,
+            started: log.session_start,
+            deleted: log.records.iter().filter(|r| r.success).count(),
+            bytes_cleaned: log.total_bytes_cleaned,
+            failed: log.errors,
+            categories,
+        }
+    }
+}
+
+fn local_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// List past cleaning sessions, newest first
+pub(crate) fn handle_history_list(
+    limit: Option<usize>,
+    json: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let sessions: Vec<SessionSummary> = history::list_logs()?
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .filter_map(|path| {
+            let log = history::load_log(&path).ok()?;
+            Some(SessionSummary::of(history::session_id(&path), &log))
+        })
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    if sessions.is_empty() {
+        println!("No deletion history found.");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", Theme::header("Cleaning Sessions"));
+    println!("{}", Theme::divider_bold(72));
+    for session in &sessions {
+        let failed = if session.failed > 0 {
+            Theme::error(&format!(
+                ", {} failed",
+                render::format_number(session.failed as u64)
+            ))
+        } else {
+            String::new()
+        };
+        println!(
+            "  {}  {}  {} items, {}{}",
+            Theme::value(&session.id),
+            local_time(session.started),
+            render::format_number(session.deleted as u64),
+            render::format_size(session.bytes_cleaned),
+            failed
+        );
+        if !session.categories.is_empty() {
+            println!("    {}", Theme::muted(&session.categories.join(", ")));
+        }
+    }
+    println!();
+    println!(
+        "Show a session with {}.",
+        Theme::command("wole history show <id>")
+    );
+    println!();
+    Ok(())
+}
+
+/// What session `id` deleted, how much it recovered and what failed
+pub(crate) fn handle_history_show(
+    id: &str,
+    items: bool,
+    json: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let log = history::load_log(&crate::restore::session_log(id)?)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&log)?);
+        return Ok(());
+    }
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+
+    let deleted: Vec<_> = log.records.iter().filter(|r| r.success).collect();
+    let permanent = deleted.iter().filter(|r| r.permanent).count();
+    let failures: Vec<_> = log.failures().collect();
+
+    println!();
+    println!(
+        "{}",
+        Theme::header(&format!(
+            "Session {} ({})",
+            id,
+            local_time(log.session_start)
+        ))
+    );
+    println!("{}", Theme::divider_bold(72));
+    println!(
+        "  Deleted: {} items, {} freed ({} permanently)",
+        render::format_number(deleted.len() as u64),
+        render::format_size(log.total_bytes_cleaned),
+        render::format_number(permanent as u64)
+    );
+    if !failures.is_empty() {
+        println!(
+            "  Failed:  {}",
+            Theme::error(&format!(
+                "{} items",
+                render::format_number(failures.len() as u64)
+            ))
+        );
+    }
+
+    // (items, bytes) per category, in the order they were cleaned
+    let mut order: Vec<&str> = Vec::new();
+    let mut totals: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for record in &deleted {
+        if !order.contains(&record.category.as_str()) {
+            order.push(&record.category);
+        }
+        let total = totals.entry(&record.category).or_default();
+        total.0 += 1;
+        total.1 += record.size_bytes;
+    }
+    if !order.is_empty() {
+        println!();
+        println!("  {}", Theme::primary("By category"));
+        for category in order {
+            let (count, bytes) = totals[category];
+            println!(
+                "    {}  {} items, {}",
+                render::pad_right(category, 20),
+                render::format_number(count),
+                render::format_size(bytes)
+            );
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+        println!("  {}", Theme::primary("Failures"));
+        for record in &failures {
+            println!("    {}", record.path);
+            println!(
+                "      {}",
+                Theme::muted(record.error.as_deref().unwrap_or("unknown error"))
+            );
+        }
+    }
+
+    if items && !deleted.is_empty() {
+        println!();
+        println!("  {}", Theme::primary("Deleted items"));
+        for record in &deleted {
+            let permanent = if record.permanent {
+                Theme::muted(" (permanent)")
+            } else {
+                String::new()
+            };
+            println!(
+                "    {}  {}{}",
+                record.path,
+                Theme::size(&render::format_size(record.size_bytes)),
+                permanent
+            );
+        }
+    }
+    println!();
+    if !items && !deleted.is_empty() {
+        println!(
+            "List every deleted item with {}; restore with {}.",
+            Theme::command("--items"),
+            Theme::command(&format!("wole restore --session {}", id))
+        );
+        println!();
+    }
+    Ok(())
+}
+
+/// Remove sessions past the `[history]` limits (or `keep` / `older_than`)
+pub(crate) fn handle_history_purge(
+    keep: Option<u64>,
+    older_than: Option<u64>,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let mut settings = Config::load().history;
+    settings.max_entries = keep.unwrap_or(settings.max_entries);
+    settings.max_age_days = older_than.unwrap_or(settings.max_age_days);
+    // Purging only removes; compressing is `history compact`'s job
+    settings.compress = false;
+
+    if settings.max_entries == 0 && settings.max_age_days == 0 {
+        if output_mode != OutputMode::Quiet {
+            println!(
+                "{} No limits set: [history] max_entries and max_age_days are both 0 (use --keep or --older-than)",
+                Theme::muted("→")
+            );
+        }
+        return Ok(());
+    }
+    let stats = history::compact_logs(&history::get_history_dir()?, &settings, chrono::Utc::now())?;
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    println!(
+        "{} Removed {} sessions ({} freed)",
+        Theme::success("[OK]"),
+        render::format_number(stats.removed as u64),
+        render::format_size(stats.bytes_before.saturating_sub(stats.bytes_after))
+    );
+    Ok(())
+}
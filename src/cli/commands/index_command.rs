@@ -0,0 +1,101 @@
+//! Index command feature.
+//!
+//! This module owns and handles the "wole index" command behavior.
+
+use crate::config::Config;
+use crate::indexer::{self, IndexState, PassOutcome};
+use crate::output::{render, OutputMode};
+use crate::tasks::CancelToken;
+use crate::theme::Theme;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn handle_index(daemon: bool, now: bool, output_mode: OutputMode) -> anyhow::Result<()> {
+    let config = Config::load();
+    let quiet = output_mode == OutputMode::Quiet;
+    let log = |msg: &str| {
+        if !quiet {
+            println!("{} {}", Theme::muted("→"), msg);
+        }
+    };
+
+    if now {
+        match indexer::run_pass(&config, || true, log)? {
+            PassOutcome::Completed => {
+                if !quiet {
+                    println!("{} Caches refreshed", Theme::success("[OK]"));
+                }
+            }
+            PassOutcome::Interrupted(step) => {
+                println!("{} Stopped before {}", Theme::warning("[WARNING]"), step);
+            }
+        }
+        return Ok(());
+    }
+
+    if daemon {
+        if !config.indexer.enabled {
+            println!(
+                "{}",
+                Theme::warning(
+                    "Background indexing is disabled. Set `enabled = true` under [indexer] in the config file."
+                )
+            );
+            return Ok(());
+        }
+        if !quiet {
+            println!(
+                "{}",
+                Theme::muted(&format!(
+                    "Indexing when idle (CPU < {:.0}%, disk < {:.1} MB/s); press Ctrl+C to stop.",
+                    config.indexer.idle_cpu_percent, config.indexer.idle_disk_mb_per_sec
+                ))
+            );
+        }
+        return indexer::run_daemon(&config, &CancelToken::new(), log);
+    }
+
+    // No flags: report the index state
+    let state = IndexState::load();
+    println!("{}", Theme::header("Background Index"));
+    println!("{}", Theme::divider(60));
+    println!(
+        "  Daemon:        {}",
+        if config.indexer.enabled {
+            Theme::success("enabled")
+        } else {
+            Theme::muted("disabled")
+        }
+    );
+    match state.last_run {
+        Some(last) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(last);
+            println!(
+                "  Last pass:     {} ago ({}s)",
+                render::format_age(now.saturating_sub(last)),
+                state.last_duration_secs
+            );
+        }
+        None => println!("  Last pass:     {}", Theme::muted("never")),
+    }
+    println!(
+        "  Refresh every: {}h when idle",
+        config.indexer.refresh_hours
+    );
+    if state.interrupted_passes > 0 {
+        println!(
+            "  Interrupted:   {} passes (system became busy)",
+            state.interrupted_passes
+        );
+    }
+    for path in indexer::index_paths(&config.indexer) {
+        println!(
+            "  {} {}",
+            Theme::muted("•"),
+            crate::utils::display_path(&path)
+        );
+    }
+    Ok(())
+}
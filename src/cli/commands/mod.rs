@@ -3,12 +3,22 @@
 //! Each module owns one command feature.
 
 pub mod analyze_command;
+pub mod apply_command;
+pub mod cache_command;
+pub mod categories_command;
 pub mod clean_command;
 pub mod config_command;
+pub mod daemon_command;
+pub mod emergency_command;
+pub mod history_command;
+pub mod index_command;
 pub mod optimize_command;
+pub mod plan_command;
 pub mod remove_command;
+pub mod report_command;
 pub mod restore_command;
 pub mod scan_command;
 pub mod startup_command;
+pub mod stats_command;
 pub mod status_command;
 pub mod update_command;
@@ -0,0 +1,72 @@
+//! Plan command feature.
+//!
+//! This module owns and handles the "wole plan" command behavior. The scan
+//! itself is shared with "wole clean"; this writes the results to a plan file
+//! instead of deleting them.
+
+use crate::categories::duplicates::DedupeMode;
+use crate::output::render;
+use crate::output::{OutputMode, ScanResults};
+use crate::plan::{self, Plan, Strategy};
+use crate::theme::Theme;
+use std::path::Path;
+
+pub(crate) fn handle_plan(
+    results: &ScanResults,
+    root: &Path,
+    out: &Path,
+    permanent: bool,
+    dedupe_mode: DedupeMode,
+    take_ownership: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let mut plan = Plan::build(results, root, permanent, dedupe_mode, take_ownership);
+    plan::save(&mut plan, out)?;
+
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    println!();
+    println!(
+        "{} Plan written to {}: {} items, {}",
+        Theme::success("[OK]"),
+        Theme::primary(&out.display().to_string()),
+        render::format_number(plan.entries.len() as u64),
+        Theme::size(&render::format_size(plan.total_bytes()))
+    );
+    for (strategy, label) in [
+        (Strategy::RecycleBin, "to the Recycle Bin"),
+        (Strategy::Permanent, "deleted permanently"),
+        (Strategy::Hardlink, "replaced with hardlinks"),
+        (Strategy::EmptyRecycleBin, "purged from the Recycle Bin"),
+    ] {
+        let entries: Vec<_> = plan
+            .entries
+            .iter()
+            .filter(|e| e.strategy == strategy)
+            .collect();
+        if !entries.is_empty() {
+            println!(
+                "  {} {} {} ({})",
+                Theme::muted("→"),
+                render::format_number(entries.len() as u64),
+                label,
+                render::format_size(entries.iter().map(|e| e.size).sum())
+            );
+        }
+    }
+    if results.applications.items > 0 {
+        println!(
+            "  {} Installed applications are not included in plans",
+            Theme::muted("→")
+        );
+    }
+    println!(
+        "{}",
+        Theme::muted(&format!(
+            "Review it, then run: wole apply {}",
+            out.display()
+        ))
+    );
+    Ok(())
+}
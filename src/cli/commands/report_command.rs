@@ -0,0 +1,84 @@
+//! Report command feature.
+//!
+//! This module owns and handles the "wole report" command behavior.
+
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::history;
+use crate::output::OutputMode;
+use crate::report;
+use crate::scanner;
+use crate::theme::Theme;
+use anyhow::Context;
+use chrono::{Duration, Utc};
+use std::path::PathBuf;
+
+pub(crate) fn handle_report(
+    weekly: PathBuf,
+    no_scan: bool,
+    path: Option<PathBuf>,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    // The report compares against the week before, so load two weeks of sessions
+    let since = now - Duration::days(report::REPORT_DAYS * 2 + 1);
+    let logs: Vec<history::DeletionLog> = history::list_logs()?
+        .iter()
+        .filter_map(|log_path| history::load_log(log_path).ok())
+        .filter(|log| log.session_start >= since)
+        .collect();
+
+    let mut weekly_report = report::build_weekly(&logs, now);
+
+    if !no_scan {
+        let config = Config::load();
+        let scan_path =
+            path.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        let options = ScanOptions {
+            cache: true,
+            app_cache: true,
+            temp: true,
+            trash: true,
+            build: false,
+            downloads: false,
+            large: false,
+            old: false,
+            applications: false,
+            browser: true,
+            system: true,
+            empty: false,
+            duplicates: false,
+            windows_update: false,
+            event_logs: false,
+            orphaned: false,
+            custom: false,
+            ide: false,
+            rust: false,
+            project_age_days: config.thresholds.project_age_days,
+            min_age_days: config.thresholds.min_age_days,
+            min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+            summary_only: false,
+            estimate: None,
+        };
+        let results = scanner::scan_all(&scan_path, options, OutputMode::Quiet, &config, None)
+            .context("Failed to scan for current cleanable estimates")?;
+        weekly_report.set_cleanable(&results);
+    }
+
+    let markdown = report::render_markdown(&weekly_report);
+    if weekly.as_os_str() == "-" {
+        print!("{}", markdown);
+        return Ok(());
+    }
+
+    std::fs::write(&weekly, markdown)
+        .with_context(|| format!("Failed to write report to {}", weekly.display()))?;
+    if output_mode != OutputMode::Quiet {
+        println!(
+            "{} Weekly report written to {}",
+            Theme::success("OK"),
+            Theme::primary(&weekly.display().to_string())
+        );
+    }
+    Ok(())
+}
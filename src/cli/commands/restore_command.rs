@@ -3,17 +3,23 @@
 //! This module owns and handles the "wole restore" command behavior.
 
 use crate::history;
+use crate::output::render;
 use crate::output::OutputMode;
 use crate::restore;
 use crate::theme::Theme;
 use anyhow::Context;
 use std::path::PathBuf;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_restore(
     last: bool,
     path: Option<PathBuf>,
     from: Option<PathBuf>,
     all: bool,
+    list: bool,
+    session: Option<String>,
+    preview: bool,
+    filter: restore::RestoreFilter,
     quiet: bool,
     verbose: u8,
 ) -> anyhow::Result<()> {
@@ -27,7 +33,33 @@ pub(crate) fn handle_restore(
         OutputMode::Normal
     };
 
-    if all {
+    if list {
+        return print_restore_points(output_mode);
+    }
+
+    if let Some(id) = session {
+        let log_path = restore::session_log(&id)?;
+        let log = history::load_log(&log_path)
+            .with_context(|| format!("Failed to load log file: {}", log_path.display()))?;
+        if preview {
+            return print_preview(&id, &filter.apply(&log), output_mode);
+        }
+        match restore::restore_from_log(&log, &filter, output_mode) {
+            Ok(result) => {
+                if output_mode != OutputMode::Quiet {
+                    println!();
+                    println!(
+                        "{} {}",
+                        Theme::success("OK"),
+                        Theme::success(&result.summary())
+                    );
+                }
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!("Failed to restore: {}", e));
+            }
+        }
+    } else if all {
         // Restore all contents of Recycle Bin in bulk
         match restore::restore_all_bin(output_mode, None) {
             Ok(result) => {
@@ -46,7 +78,7 @@ pub(crate) fn handle_restore(
         }
     } else if last {
         // Restore from last deletion session
-        match restore::restore_last(output_mode) {
+        match restore::restore_last(&filter, output_mode) {
             Ok(result) => {
                 if output_mode != OutputMode::Quiet {
                     println!();
@@ -82,7 +114,7 @@ pub(crate) fn handle_restore(
         // Restore from specific log file
         let log = history::load_log(log_path)
             .with_context(|| format!("Failed to load log file: {}", log_path.display()))?;
-        match restore::restore_from_log(&log, output_mode) {
+        match restore::restore_from_log(&log, &filter, output_mode) {
             Ok(result) => {
                 if output_mode != OutputMode::Quiet {
                     println!();
@@ -99,7 +131,7 @@ pub(crate) fn handle_restore(
         }
     } else {
         // Default: restore from last session
-        match restore::restore_last(output_mode) {
+        match restore::restore_last(&filter, output_mode) {
             Ok(result) => {
                 if output_mode != OutputMode::Quiet {
                     println!();
@@ -118,3 +150,154 @@ pub(crate) fn handle_restore(
 
     Ok(())
 }
+
+/// List past sessions with what `restore` can still bring back from each
+fn print_restore_points(output_mode: OutputMode) -> anyhow::Result<()> {
+    let points = restore::restore_points()?;
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    if points.is_empty() {
+        println!("No deletion history found. Nothing to restore.");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", Theme::header("Restore Points"));
+    println!("{}", Theme::divider_bold(72));
+    for (i, point) in points.iter().enumerate() {
+        let started = point
+            .session_start
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string();
+        let latest = if i == 0 { " (--last)" } else { "" };
+        println!(
+            "  {}  {}  {} items, {}{}",
+            Theme::value(&point.id),
+            started,
+            render::format_number((point.items + point.permanent_items) as u64),
+            render::format_size(point.bytes),
+            Theme::muted(latest)
+        );
+        if !point.categories.is_empty() {
+            println!("    {}", Theme::muted(&point.categories.join(", ")));
+        }
+        let recoverable = format!(
+            "{}/{} items ({}) in Recycle Bin",
+            render::format_number(point.recoverable_items as u64),
+            render::format_number(point.items as u64),
+            render::format_size(point.recoverable_bytes)
+        );
+        let recoverable = if point.items == 0 {
+            Theme::muted("nothing moved to the Recycle Bin")
+        } else if point.fully_recoverable() {
+            Theme::success(&recoverable)
+        } else if point.recoverable_items == 0 {
+            Theme::error(&format!(
+                "purged, {} no longer recoverable",
+                render::format_size(point.bytes)
+            ))
+        } else {
+            Theme::warning(&recoverable)
+        };
+        println!("    {}", recoverable);
+        if point.permanent_items > 0 && output_mode != OutputMode::Normal {
+            println!(
+                "    {}",
+                Theme::muted(&format!(
+                    "{} deleted permanently",
+                    render::format_number(point.permanent_items as u64)
+                ))
+            );
+        }
+        if output_mode == OutputMode::VeryVerbose {
+            println!(
+                "    {}",
+                Theme::muted(&point.log_path.display().to_string())
+            );
+        }
+    }
+    println!();
+    println!(
+        "Preview a session with {}, then restore it without {}.",
+        Theme::command("wole restore --session <id> --preview"),
+        Theme::command("--preview")
+    );
+    println!();
+    Ok(())
+}
+
+/// What `wole restore --session <id>` would bring back, and what's in its way
+fn print_preview(
+    id: &str,
+    log: &history::DeletionLog,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let items = restore::preview_log(log)?;
+    if output_mode == OutputMode::Quiet {
+        return Ok(());
+    }
+    let permanent = log
+        .records
+        .iter()
+        .filter(|r| r.success && r.permanent)
+        .count();
+
+    println!();
+    println!("{}", Theme::header(&format!("Restore preview: {}", id)));
+    println!("{}", Theme::divider_bold(72));
+    for item in &items {
+        let (mark, note) = if !item.in_bin {
+            (
+                Theme::error("x"),
+                Theme::error("no longer in the Recycle Bin"),
+            )
+        } else if item.conflict {
+            (
+                Theme::warning("!"),
+                Theme::warning("conflicts with an existing file"),
+            )
+        } else {
+            (Theme::success("+"), String::new())
+        };
+        let line = format!(
+            "  {} {:>10}  {}  {}",
+            mark,
+            render::format_size(item.size_bytes),
+            crate::utils::display_path(&item.path),
+            note
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let restorable: Vec<_> = items.iter().filter(|i| i.in_bin).collect();
+    let conflicts = restorable.iter().filter(|i| i.conflict).count();
+    println!();
+    println!(
+        "Would restore {} of {} items ({}).",
+        render::format_number(restorable.len() as u64),
+        render::format_number(items.len() as u64),
+        render::format_size(restorable.iter().map(|i| i.size_bytes).sum())
+    );
+    if conflicts > 0 {
+        println!(
+            "{}",
+            Theme::warning(&format!(
+                "{} target(s) already exist and would conflict.",
+                render::format_number(conflicts as u64)
+            ))
+        );
+    }
+    if permanent > 0 {
+        println!(
+            "{}",
+            Theme::muted(&format!(
+                "{} item(s) were deleted permanently and can't be restored.",
+                render::format_number(permanent as u64)
+            ))
+        );
+    }
+    println!();
+    Ok(())
+}
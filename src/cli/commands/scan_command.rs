@@ -4,7 +4,9 @@
 
 use crate::cli::ScanOptions;
 use crate::config::Config;
-use crate::output::{self, OutputMode};
+use crate::output::{self, OutputMode, ScanResults};
+use crate::recording;
+use crate::scan_diff::{self, Snapshot};
 use crate::scanner;
 use crate::size;
 use crate::theme::Theme;
@@ -46,6 +48,183 @@ fn get_disk_space_for_path(path: &Path) -> Option<(u64, u64)> {
     best.map(|(_, total, avail)| (total, avail))
 }
 
+/// Report repositories cloned more than once under `root` with duplicated build artifacts
+fn print_clone_groups(root: &Path, config: &Config) {
+    let groups: Vec<_> =
+        crate::clones::find_clone_groups(root, config, config.thresholds.project_age_days)
+            .into_iter()
+            .filter(|g| g.duplicated_bytes() > 0)
+            .collect();
+    if groups.is_empty() {
+        return;
+    }
+
+    let total: u64 = groups.iter().map(|g| g.duplicated_bytes()).sum();
+    println!();
+    println!(
+        "{} {} of build artifacts duplicated across clones",
+        Theme::header("Duplicate Clones"),
+        Theme::size(&bytesize::to_string(total, false))
+    );
+    println!("{}", Theme::divider(60));
+    for group in &groups {
+        println!(
+            "  {} ({} copies, {} duplicated)",
+            Theme::primary(&group.origin),
+            group.clones.len(),
+            bytesize::to_string(group.duplicated_bytes(), false)
+        );
+        for (i, clone) in group.clones.iter().enumerate() {
+            let status = if i == 0 {
+                Theme::status_safe("most recent")
+            } else if clone.active {
+                Theme::status_safe("active")
+            } else {
+                Theme::status_review("stale: consider removing this clone")
+            };
+            println!(
+                "    {} {} ({}, {})",
+                Theme::muted("→"),
+                crate::utils::to_relative_path(&clone.path, root),
+                bytesize::to_string(clone.artifact_bytes, false),
+                status
+            );
+        }
+    }
+}
+
+/// Show a recording made with `--record` and re-check its decisions
+fn replay_recording(path: &Path, json: bool, output_mode: OutputMode) -> anyhow::Result<()> {
+    let recording = recording::load(path)?;
+    let results = recording.scan_results();
+    if json {
+        return output::print_json(&results);
+    }
+
+    println!("{}", Theme::header("Recorded Scan"));
+    println!("{}", Theme::divider(60));
+    println!(
+        "  {} wole {} on {}, {}{}",
+        Theme::muted("→"),
+        recording.version,
+        recording.platform,
+        recording.recorded_at.format("%Y-%m-%d %H:%M UTC"),
+        if recording.anonymized {
+            " (anonymized)"
+        } else {
+            ""
+        }
+    );
+    println!("  {} Root: {}", Theme::muted("→"), recording.root);
+    println!(
+        "  {} Categories: {}",
+        Theme::muted("→"),
+        recording.inputs.categories.join(", ")
+    );
+    println!(
+        "  {} Thresholds: project age {}d, min age {}d, min size {}",
+        Theme::muted("→"),
+        recording.inputs.project_age_days,
+        recording.inputs.min_age_days,
+        bytesize::to_string(recording.inputs.min_size_bytes, false)
+    );
+    if !recording.inputs.exclusions.is_empty() {
+        println!(
+            "  {} Exclusions: {}",
+            Theme::muted("→"),
+            recording.inputs.exclusions.join(", ")
+        );
+    }
+    let counts = recording.decision_counts();
+    if !counts.is_empty() {
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect();
+        println!(
+            "  {} Decisions: {}{}",
+            Theme::muted("→"),
+            summary.join(", "),
+            if recording.dropped_decisions > 0 {
+                format!(" (+{} not kept)", recording.dropped_decisions)
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    output::print_human(&results, output_mode);
+
+    if recording.anonymized {
+        println!();
+        println!(
+            "{}",
+            Theme::muted("Anonymized recording: decisions can't be re-checked.")
+        );
+        return Ok(());
+    }
+    let mismatches = recording.recheck();
+    println!();
+    if mismatches.is_empty() {
+        println!(
+            "{} All recorded exclusion and system-path decisions reproduce with this build",
+            Theme::success("[OK]")
+        );
+    } else {
+        println!(
+            "{} {} recorded decisions differ with this build:",
+            Theme::warning("[DIFF]"),
+            mismatches.len()
+        );
+        for mismatch in mismatches.iter().take(50) {
+            println!(
+                "    {} {:?} no longer applies: {}",
+                Theme::muted("→"),
+                mismatch.kind,
+                mismatch.path
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Compare `results` with the last `--diff` scan of `scan_path` and save them
+/// as the new baseline
+fn print_diff(
+    scan_path: &Path,
+    scan_options: &ScanOptions,
+    results: &ScanResults,
+    json: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    // The baseline lives in the scan cache even when --no-cache skips reuse
+    let mut cache = crate::scan_cache::ScanCache::open()?;
+    let current = Snapshot::of(results, &scan_options.keys());
+    let previous = cache.get_snapshot(scan_path)?;
+    let diff = previous
+        .as_ref()
+        .map(|prev| scan_diff::diff(prev, &current));
+    let baseline = match previous {
+        Some(prev) => prev.updated_with(current),
+        None => current,
+    };
+    cache.save_snapshot(scan_path, &baseline)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else if output_mode != OutputMode::Quiet {
+        match diff {
+            Some(diff) => scan_diff::print_human(&diff),
+            None => println!(
+                "{} No previous --diff scan of {}; saved this one as the baseline",
+                Theme::muted("→"),
+                scan_path.display()
+            ),
+        }
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_scan(
     all: bool,
@@ -60,17 +239,45 @@ pub(crate) fn handle_scan(
     applications: bool,
     windows_update: bool,
     event_logs: bool,
-    path: Option<PathBuf>,
+    orphaned: bool,
+    custom: bool,
+    ide: bool,
+    rust: bool,
+    path: Vec<PathBuf>,
+    all_drives: bool,
     json: bool,
+    json_stream: bool,
+    summary_only: bool,
+    diff: bool,
     project_age: u64,
     min_age: u64,
     min_size: String,
+    trash_older_than: Option<u64>,
     exclude: Vec<String>,
+    include_hidden: bool,
+    volume: Option<String>,
     force_full: bool,
     no_cache: bool,
     clear_cache: bool,
+    quick: bool,
+    seed: Option<u64>,
+    record: Option<PathBuf>,
+    anonymize: bool,
+    replay: Option<PathBuf>,
     output_mode: OutputMode,
 ) -> anyhow::Result<()> {
+    if let Some(replay) = replay {
+        return replay_recording(&replay, json, output_mode);
+    }
+
+    // Nothing but events goes to stdout
+    let output_mode = if json_stream {
+        crate::json_stream::enable();
+        OutputMode::Quiet
+    } else {
+        output_mode
+    };
+
     // --all enables all categories
     let (
         cache,
@@ -88,10 +295,16 @@ pub(crate) fn handle_scan(
         duplicates,
         windows_update,
         event_logs,
+        orphaned,
+        custom,
+        ide,
+        rust,
     ) = if all {
+        // Orphaned files are user data and custom rules may be unreviewed
+        // imports: both only when asked for by name
         (
             true, true, true, true, true, true, true, true, true, true, true, true, true, true,
-            true,
+            true, orphaned, custom, true, true,
         )
     } else if !cache
         && !app_cache
@@ -104,6 +317,10 @@ pub(crate) fn handle_scan(
         && !applications
         && !windows_update
         && !event_logs
+        && !orphaned
+        && !custom
+        && !ide
+        && !rust
     {
         // No categories specified - show help message
         eprintln!("No categories specified. Use --all or specify categories like --cache, --app-cache, --temp, --build");
@@ -127,16 +344,35 @@ pub(crate) fn handle_scan(
             false,
             windows_update,
             event_logs,
+            orphaned,
+            custom,
+            ide,
+            rust,
         )
     };
 
     // Default to current directory to avoid stack overflow from OneDrive/UserDirs
     // PERFORMANCE FIX: Avoid OneDrive paths which are very slow to scan on Windows
     // Use current directory instead, which is faster and more predictable
-    let scan_path = path.unwrap_or_else(|| {
+    let mut scan_paths = if all_drives {
+        let drives = crate::volumes::fixed_drives();
+        if drives.is_empty() {
+            anyhow::bail!("No fixed drives found to scan");
+        }
+        drives
+    } else {
+        path
+    };
+    let mut seen = std::collections::HashSet::new();
+    scan_paths.retain(|p| seen.insert(p.clone()));
+    if scan_paths.is_empty() {
         // Use current directory as default - faster and avoids OneDrive sync issues
-        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-    });
+        scan_paths.push(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    }
+    if scan_paths.len() > 1 && (diff || record.is_some() || json_stream) {
+        anyhow::bail!("--diff, --record and --json-stream scan a single --path");
+    }
+    let scan_path = scan_paths[0].clone();
 
     // Load config first
     let mut config = Config::load();
@@ -151,12 +387,20 @@ pub(crate) fn handle_scan(
                 / (1024 * 1024),
         ), // Convert bytes to MB for config
     );
+    if let Some(days) = trash_older_than {
+        config.thresholds.trash_min_age_days = days;
+    }
 
     // Merge CLI exclusions
-    config.exclusions.patterns.extend(exclude.iter().cloned());
+    crate::scanner::exclusions::validate(&exclude)?;
+    config.exclusions.add_patterns(&exclude);
+    if include_hidden {
+        config.safety.include_hidden = true;
+    }
 
-    // Handle cache flags
-    let use_cache = !no_cache && config.cache.enabled && !force_full;
+    // Handle cache flags; estimated sizes are never cached
+    // Summary scans have no paths to cache
+    let use_cache = !no_cache && config.cache.enabled && !force_full && !quick && !summary_only;
 
     if clear_cache {
         if let Ok(mut scan_cache) = crate::scan_cache::ScanCache::open() {
@@ -174,6 +418,10 @@ pub(crate) fn handle_scan(
                     "applications",
                     "windows_update",
                     "event_logs",
+                    "orphaned",
+                    "custom",
+                    "ide",
+                    "rust",
                 ]
             } else {
                 let mut cats = Vec::new();
@@ -210,6 +458,18 @@ pub(crate) fn handle_scan(
                 if event_logs {
                     cats.push("event_logs");
                 }
+                if orphaned {
+                    cats.push("orphaned");
+                }
+                if custom {
+                    cats.push("custom");
+                }
+                if ide {
+                    cats.push("ide");
+                }
+                if rust {
+                    cats.push("rust");
+                }
                 cats
             };
 
@@ -245,9 +505,19 @@ pub(crate) fn handle_scan(
         duplicates,
         windows_update,
         event_logs,
+        orphaned,
+        custom,
+        ide,
+        rust,
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
+        // The volume filter needs paths; those are dropped after it instead
+        summary_only: summary_only && volume.is_none(),
+        estimate: quick.then(|| {
+            let seed = seed.unwrap_or_else(crate::estimate::random_seed);
+            std::sync::Arc::new(crate::estimate::Estimate::new(seed))
+        }),
     };
 
     // Open scan cache if enabled
@@ -284,6 +554,11 @@ pub(crate) fn handle_scan(
         }
     }
 
+    // The deep baseline walks the whole disk; check battery before starting
+    if first_scan_detected && config.cache.full_disk_baseline {
+        crate::power::guard_cli(&config.power, output_mode == OutputMode::Quiet)?;
+    }
+
     if first_scan_detected && output_mode != OutputMode::Quiet {
         if config.cache.full_disk_baseline {
             // Deep baseline will be handled by scanner (full-disk traversal + category scans).
@@ -326,18 +601,110 @@ pub(crate) fn handle_scan(
         }
     }
 
-    let results = scanner::scan_all(
-        &scan_path,
-        scan_options.clone(),
-        output_mode,
-        &config,
-        scan_cache.as_mut(),
-    )?;
+    if record.is_some() {
+        recording::start(anonymize);
+    }
 
-    if json {
+    let scanned = if scan_paths.len() > 1 {
+        if output_mode != OutputMode::Quiet && !json {
+            println!(
+                "{} Scanning {} in parallel...",
+                Theme::muted("→"),
+                scan_paths
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        scanner::scan_roots(&scan_paths, scan_options.clone(), &config, use_cache)
+    } else if json_stream {
+        scanner::scan_with_events(
+            &scan_path,
+            scan_options.clone(),
+            &config,
+            scan_cache.as_mut(),
+            None,
+            &crate::json_stream::emit_scan,
+        )
+    } else {
+        scanner::scan_all(
+            &scan_path,
+            scan_options.clone(),
+            output_mode,
+            &config,
+            scan_cache.as_mut(),
+        )
+    };
+    let sampling = scan_options.estimate.as_ref().map(|e| e.sampling());
+    let mut results = scanned?;
+    results.sampling = sampling;
+    if let Some(ref volume) = volume {
+        crate::volumes::filter_results(&mut results, volume);
+    }
+    // With --volume, paths are dropped once the filter is done with them
+    if summary_only {
+        results.summarize();
+    }
+
+    // Estimates and single-drive views would skew the size trend
+    if !quick && volume.is_none() {
+        let _ = crate::history::record_sizes(&crate::history::SizeSample::of(
+            &results,
+            &scan_options.keys(),
+        ));
+    }
+
+    if let Some(ref record) = record {
+        recording::finish(record, &scan_path, &scan_options, &config, &results)?;
+        if output_mode != OutputMode::Quiet {
+            eprintln!(
+                "{} Recorded scan to {}",
+                Theme::muted("→"),
+                record.display()
+            );
+        }
+    }
+
+    if diff {
+        print_diff(&scan_path, &scan_options, &results, json, output_mode)?;
+    } else if json_stream {
+        crate::json_stream::emit(&crate::json_stream::Event::scan_finished(&results));
+    } else if json {
         output::print_json(&results)?;
     } else {
         output::print_human_with_options(&results, output_mode, Some(&scan_options));
+
+        if let Some(sampling) = results.sampling {
+            if output_mode != OutputMode::Quiet {
+                println!();
+                println!(
+                    "{} Quick estimate: walked {:.0}% of folders; repeat with {} to compare runs",
+                    Theme::muted("→"),
+                    sampling.coverage_percent,
+                    Theme::command(&format!("--quick --seed {}", sampling.seed))
+                );
+            }
+        }
+
+        // Several clones of one repository each carry their own build artifacts
+        if build && !summary_only && output_mode != OutputMode::Quiet {
+            for root in &scan_paths {
+                print_clone_groups(root, &config);
+            }
+        }
+
+        // Protected folders were skipped; say how much an elevated scan would add
+        if output_mode != OutputMode::Quiet {
+            if let Some(hint) = crate::elevation::hint() {
+                println!();
+                println!("{}", Theme::warning_msg(&hint.summary()));
+                println!(
+                    "  {} Run from an elevated terminal to include them",
+                    Theme::muted("→")
+                );
+            }
+        }
     }
 
     // After first scan, show cache statistics
@@ -0,0 +1,66 @@
+//! Stats command feature.
+//!
+//! This module owns and handles the "wole stats" command behavior.
+
+use crate::output::render;
+use crate::output::OutputMode;
+use crate::stats::LifetimeStats;
+use crate::theme::Theme;
+
+pub(crate) fn handle_stats(
+    rebuild: bool,
+    json: bool,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let stats = if rebuild {
+        let stats = LifetimeStats::from_history();
+        stats.save()?;
+        stats
+    } else {
+        LifetimeStats::load()
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    if output_mode == OutputMode::Quiet {
+        println!("{}", stats.bytes_cleaned);
+        return Ok(());
+    }
+
+    if stats.sessions == 0 {
+        println!(
+            "Nothing cleaned yet. Run {} to get started.",
+            Theme::command("wole clean")
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", Theme::header("Lifetime Statistics"));
+    println!("{}", Theme::divider_bold(60));
+    println!(
+        "  Space reclaimed:  {}",
+        Theme::size(&render::format_size(stats.bytes_cleaned))
+    );
+    println!(
+        "  Items cleaned:    {}",
+        render::format_number(stats.items_cleaned)
+    );
+    println!(
+        "  Cleanup sessions: {}",
+        render::format_number(stats.sessions)
+    );
+    if let (Some(first), Some(last)) = (stats.first_session, stats.last_session) {
+        let local = |t: chrono::DateTime<chrono::Utc>| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string()
+        };
+        println!("  First cleanup:    {}", local(first));
+        println!("  Latest cleanup:   {}", local(last));
+    }
+    println!();
+    Ok(())
+}
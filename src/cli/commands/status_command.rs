@@ -2,58 +2,82 @@
 //!
 //! This module owns and handles the "wole status" command behavior.
 
-pub(crate) fn handle_status(json: bool, _watch: bool, new_format: bool) -> anyhow::Result<()> {
-    if json {
-        // JSON output mode - use text output
-        use sysinfo::System;
+use crate::status::{StatusGatherOptions, SystemStatus};
+use sysinfo::System;
+
+fn gather(system: &mut System, fast: bool) -> anyhow::Result<SystemStatus> {
+    let options = if fast {
+        StatusGatherOptions::fast()
+    } else {
+        StatusGatherOptions::full()
+    };
+    crate::status::gather_status_with_options(system, options)
+        .map_err(|e| anyhow::anyhow!("Failed to gather system status: {}", e))
+}
 
+pub(crate) fn handle_status(
+    json: bool,
+    watch: Option<u64>,
+    new_format: bool,
+    fast: bool,
+) -> anyhow::Result<()> {
+    if let Some(secs) = watch {
+        return watch_status(json, secs.max(1), fast);
+    }
+
+    if json {
         let mut system = System::new();
         system.refresh_all();
 
-        match crate::status::gather_status(&mut system) {
-            Ok(status) => {
-                let json_output = serde_json::to_string_pretty(&status)?;
-                println!("{}", json_output);
-                Ok(())
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to gather system status: {}", e)),
-        }
+        let status = gather(&mut system, fast)?;
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        Ok(())
     } else if new_format {
-        // New experimental CLI format
-        use sysinfo::System;
-
         let mut system = System::new();
         system.refresh_all();
 
-        match crate::status::gather_status(&mut system) {
-            Ok(status) => {
-                println!("{}", crate::status::format_cli_output_new(&status));
-                Ok(())
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to gather system status: {}", e)),
-        }
+        let status = gather(&mut system, fast)?;
+        println!("{}", crate::status::format_cli_output_new(&status));
+        Ok(())
     } else {
         // Launch interactive TUI for real-time status dashboard
-        // Ignore watch flag - TUI always auto-refreshes
-        use crate::status::gather_status_fast;
-        use sysinfo::System;
-
         // Don't call refresh_all() - gather_status will refresh what it needs
         // This avoids blocking on expensive full system refresh
         let mut system = System::new();
+        let status = gather(&mut system, true)?;
+
+        let mut app_state = crate::tui::state::AppState::new();
+        app_state.screen = crate::tui::state::Screen::Status {
+            status: Box::new(status),
+            last_refresh: std::time::Instant::now(),
+            status_receiver: None,
+        };
+        crate::tui::run(Some(app_state))?;
+        Ok(())
+    }
+}
+
+/// Redraw the status every `secs` seconds until interrupted. With `--json`,
+/// print one compact JSON document per line instead, for logging.
+fn watch_status(json: bool, secs: u64, fast: bool) -> anyhow::Result<()> {
+    use crossterm::{cursor::MoveTo, execute, terminal::Clear, terminal::ClearType};
+    use std::io::Write;
+
+    // One System for the whole loop so CPU, disk and network rates are deltas
+    let mut system = System::new();
+    system.refresh_all();
 
-        match gather_status_fast(&mut system) {
-            Ok(status) => {
-                let mut app_state = crate::tui::state::AppState::new();
-                app_state.screen = crate::tui::state::Screen::Status {
-                    status: Box::new(status),
-                    last_refresh: std::time::Instant::now(),
-                    status_receiver: None,
-                };
-                crate::tui::run(Some(app_state))?;
-                Ok(())
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to gather system status: {}", e)),
+    loop {
+        let status = gather(&mut system, fast)?;
+        let mut stdout = std::io::stdout();
+        if json {
+            writeln!(stdout, "{}", serde_json::to_string(&status)?)?;
+        } else {
+            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+            writeln!(stdout, "{}", crate::status::format_cli_output_new(&status))?;
+            writeln!(stdout, "Refreshing every {}s. Press Ctrl+C to stop.", secs)?;
         }
+        stdout.flush()?;
+        std::thread::sleep(std::time::Duration::from_secs(secs));
     }
 }
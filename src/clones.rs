@@ -0,0 +1,165 @@
+//! Duplicate clone detection
+//!
+//! Dev machines often hold several working copies of the same repository
+//! (extra clones, worktrees), each with its own `node_modules`/`target`.
+//! Working copies are grouped by normalized `origin` URL; build artifacts in
+//! every copy but the most recently used one count as duplicated, and copies
+//! that are also inactive are suggested for removal as a whole.
+
+use crate::categories::build;
+use crate::config::Config;
+use crate::git;
+use crate::project;
+use crate::utils;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One working copy of a repository
+#[derive(Debug, Clone)]
+pub struct CloneInfo {
+    pub path: PathBuf,
+    /// Build artifact folders found in the copy's projects
+    pub artifacts: Vec<PathBuf>,
+    pub artifact_bytes: u64,
+    /// Last modification of the git index/HEAD (or the folder itself)
+    pub last_activity: Option<SystemTime>,
+    /// Touched within the project age threshold
+    pub active: bool,
+}
+
+/// Working copies sharing the same origin
+#[derive(Debug, Clone)]
+pub struct CloneGroup {
+    /// Normalized origin URL (e.g. `github.com/org/repo`)
+    pub origin: String,
+    /// Most recently used first
+    pub clones: Vec<CloneInfo>,
+}
+
+impl CloneGroup {
+    /// Artifact bytes in every copy except the most recently used one
+    pub fn duplicated_bytes(&self) -> u64 {
+        self.clones.iter().skip(1).map(|c| c.artifact_bytes).sum()
+    }
+
+    /// Copies other than the most recently used one that are inactive
+    pub fn stale_clones(&self) -> impl Iterator<Item = &CloneInfo> {
+        self.clones.iter().skip(1).filter(|c| !c.active)
+    }
+}
+
+/// Find repositories with more than one working copy under `root`. Groups
+/// are sorted by duplicated bytes, largest first.
+pub fn find_clone_groups(root: &Path, config: &Config, project_age_days: u64) -> Vec<CloneGroup> {
+    // Projects can be nested inside a repository (monorepos); collect their
+    // artifacts per repository root
+    let mut repos: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for project_root in project::find_project_roots(root, config) {
+        if let Some(repo_root) = git::find_git_root_cached(&project_root) {
+            repos
+                .entry(repo_root)
+                .or_default()
+                .extend(build::project_artifacts(
+                    &project_root,
+                    Some(&config.categories.build),
+                ));
+        }
+    }
+
+    let mut by_origin: HashMap<String, Vec<(PathBuf, Vec<PathBuf>)>> = HashMap::new();
+    for (repo_root, artifacts) in repos {
+        if let Some(url) = git::origin_url(&repo_root) {
+            by_origin
+                .entry(git::normalize_remote_url(&url))
+                .or_default()
+                .push((repo_root, artifacts));
+        }
+    }
+
+    let mut groups: Vec<CloneGroup> = by_origin
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(origin, copies)| {
+            let mut clones: Vec<CloneInfo> = copies
+                .into_iter()
+                .map(|(path, mut artifacts)| {
+                    artifacts.sort();
+                    artifacts.dedup();
+                    let artifact_bytes =
+                        artifacts.iter().map(|a| utils::calculate_dir_size(a)).sum();
+                    let active =
+                        project::is_project_active(&path, project_age_days).unwrap_or(true);
+                    CloneInfo {
+                        last_activity: last_activity(&path),
+                        path,
+                        artifacts,
+                        artifact_bytes,
+                        active,
+                    }
+                })
+                .collect();
+            clones.sort_by_key(|c| std::cmp::Reverse(c.last_activity));
+            CloneGroup { origin, clones }
+        })
+        .collect();
+
+    groups.sort_by_key(|g| std::cmp::Reverse(g.duplicated_bytes()));
+    groups
+}
+
+/// When a working copy was last used, judged by git metadata
+fn last_activity(repo_root: &Path) -> Option<SystemTime> {
+    let git_dir = git::git_dir(repo_root);
+    let candidates = git_dir
+        .iter()
+        .flat_map(|dir| [dir.join("index"), dir.join("HEAD")])
+        .chain(std::iter::once(repo_root.to_path_buf()));
+    candidates
+        .filter_map(|p| std::fs::metadata(p).ok()?.modified().ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_clone(dir: &Path, name: &str, url: &str, artifact_bytes: usize) -> PathBuf {
+        let clone = dir.join(name);
+        fs::create_dir_all(clone.join(".git")).unwrap();
+        fs::write(
+            clone.join(".git/config"),
+            format!("[remote \"origin\"]\n\turl = {}\n", url),
+        )
+        .unwrap();
+        fs::write(clone.join("package.json"), "{}").unwrap();
+        fs::create_dir_all(clone.join("node_modules/dep")).unwrap();
+        fs::write(
+            clone.join("node_modules/dep/index.js"),
+            vec![b'x'; artifact_bytes],
+        )
+        .unwrap();
+        clone
+    }
+
+    #[test]
+    fn groups_clones_by_origin() {
+        let dir = TempDir::new().unwrap();
+        make_clone(dir.path(), "app", "git@github.com:org/app.git", 100);
+        make_clone(dir.path(), "app-copy", "https://github.com/org/app", 200);
+        make_clone(dir.path(), "other", "https://github.com/org/other", 50);
+
+        let groups = find_clone_groups(dir.path(), &Config::default(), 14);
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.origin, "github.com/org/app");
+        assert_eq!(group.clones.len(), 2);
+        let total: u64 = group.clones.iter().map(|c| c.artifact_bytes).sum();
+        assert_eq!(total, 300);
+        assert_eq!(group.duplicated_bytes(), group.clones[1].artifact_bytes);
+        // Both were just created, so neither is stale
+        assert_eq!(group.stale_clones().count(), 0);
+    }
+}
@@ -1,5 +1,5 @@
+use crate::scanner::exclusions::ExclusionSet;
 use anyhow::{Context, Result};
-use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -33,6 +33,34 @@ pub struct Config {
 
     #[serde(default)]
     pub cache: CacheSettings,
+
+    #[serde(default)]
+    pub power: PowerSettings,
+
+    #[serde(default)]
+    pub cross_volume: CrossVolumeSettings,
+
+    #[serde(default)]
+    pub emergency: EmergencySettings,
+
+    #[serde(default)]
+    pub quota: QuotaSettings,
+
+    #[serde(default)]
+    pub quarantine: QuarantineSettings,
+
+    #[serde(default)]
+    pub indexer: IndexerSettings,
+
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+
+    #[serde(default)]
+    pub daemon: DaemonSettings,
+
+    /// User-defined cleanup rules (hand-written or imported from BleachBit/CCleaner)
+    #[serde(default)]
+    pub custom_categories: Vec<CustomCategory>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +73,11 @@ pub struct Thresholds {
 
     #[serde(default = "default_min_size_mb")]
     pub min_size_mb: u64,
+
+    /// Recycle Bin items deleted fewer than this many days ago are left
+    /// alone (0 = the whole bin)
+    #[serde(default)]
+    pub trash_min_age_days: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -58,10 +91,9 @@ pub struct Exclusions {
     #[serde(default)]
     pub patterns: Vec<String>,
 
-    /// Compiled glob patterns for fast matching (lazily initialized)
+    /// Compiled glob and regex patterns for fast matching (lazily initialized)
     #[serde(skip)]
-    #[allow(dead_code)]
-    compiled: OnceLock<Option<GlobSet>>,
+    compiled: OnceLock<Option<ExclusionSet>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +127,10 @@ pub struct UiSettings {
     /// Higher values scan deeper but take longer
     #[serde(default = "default_scan_depth_entire_disk")]
     pub scan_depth_entire_disk: u8,
+
+    /// Number of largest files listed by disk insights (default: 10)
+    #[serde(default = "default_largest_files")]
+    pub largest_files: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +151,16 @@ pub struct SafetySettings {
     #[serde(default = "default_max_size_no_confirm")]
     pub max_size_no_confirm_mb: u64,
 
+    /// Require typing the total size instead of "y" when a clean reaches this many GB (0 = off)
+    /// Default: 100
+    #[serde(default = "default_typed_confirm_gb")]
+    pub typed_confirm_gb: u64,
+
+    /// Require typed confirmation when a clean reaches this many items (0 = off)
+    /// Default: 100000
+    #[serde(default = "default_typed_confirm_items")]
+    pub typed_confirm_items: u64,
+
     /// Skip locked files (files in use by other processes)
     #[serde(default = "default_true")]
     pub skip_locked_files: bool,
@@ -122,6 +168,30 @@ pub struct SafetySettings {
     /// Dry run by default (don't actually delete, just show what would be deleted)
     #[serde(default = "default_false")]
     pub dry_run_default: bool,
+
+    /// Include hidden and system-attribute files in Large/Old Files results
+    /// (otherwise they're only reported separately)
+    #[serde(default = "default_false")]
+    pub include_hidden: bool,
+
+    /// Permanently delete folders with at least this many entries in the background:
+    /// the folder is renamed away at once and removed on a worker thread (0 = off)
+    /// Default: 20000
+    #[serde(default = "default_background_delete_min_entries")]
+    pub background_delete_min_entries: u64,
+
+    /// Leave Large, Old and Downloads results out while they're open in an
+    /// editor (on an editor's command line, in an open workspace, next to an
+    /// editor lock file, or held by another process)
+    #[serde(default = "default_true")]
+    pub skip_open_in_editors: bool,
+
+    /// Paths never deleted by any category, with everything under them, on
+    /// top of the built-in list (home folders, cloud-drive roots, git
+    /// worktrees). `%VAR%` references are expanded.
+    /// Example: ["D:\\Archive", "%USERPROFILE%\\Projects\\keep"]
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,6 +200,10 @@ pub struct PerformanceSettings {
     #[serde(default = "default_threads")]
     pub scan_threads: u32,
 
+    /// Number of files deleted at once when cleaning (0 = auto-detect)
+    #[serde(default = "default_threads")]
+    pub delete_threads: u32,
+
     /// Batch size for file operations
     #[serde(default = "default_batch_size")]
     pub batch_size: usize,
@@ -137,6 +211,11 @@ pub struct PerformanceSettings {
     /// Enable parallel scanning (can be disabled for debugging)
     #[serde(default = "default_true")]
     pub parallel_scanning: bool,
+
+    /// Read the NTFS Master File Table for Disk Insights instead of walking
+    /// folders (Windows, elevated; falls back to walking otherwise)
+    #[serde(default = "default_false")]
+    pub use_mft: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,6 +231,10 @@ pub struct HistorySettings {
     /// Maximum age of history entries in days (0 = keep forever)
     #[serde(default = "default_history_age_days")]
     pub max_age_days: u64,
+
+    /// Store deletion logs and disk usage history zstd-compressed
+    #[serde(default)]
+    pub compress: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -175,6 +258,26 @@ pub struct CacheSettings {
     /// Smaller files use mtime+size only (default: 10MB)
     #[serde(default = "default_hash_threshold")]
     pub content_hash_threshold_bytes: u64,
+
+    /// Read the NTFS change journal so categories whose folders saw no
+    /// changes since the last scan reuse its result (Windows, elevated)
+    #[serde(default = "default_true")]
+    pub usn_journal: bool,
+
+    /// Rescan categories whose result is older than this, journal or not
+    /// (hours, default: 24)
+    #[serde(default = "default_usn_max_age_hours")]
+    pub usn_max_age_hours: u64,
+
+    /// Evict the oldest entries once the cache database grows past this
+    /// (MB, 0 = no limit, default: 512)
+    #[serde(default = "default_cache_max_size_mb")]
+    pub max_size_mb: u64,
+
+    /// Prune the cache after a scan when the last prune is this old
+    /// (days, 0 = only with `wole cache --prune`, default: 7)
+    #[serde(default = "default_prune_interval_days")]
+    pub prune_interval_days: u64,
 }
 
 impl Default for CacheSettings {
@@ -184,10 +287,352 @@ impl Default for CacheSettings {
             full_disk_baseline: default_false(),
             max_age_days: default_cache_age(),
             content_hash_threshold_bytes: default_hash_threshold(),
+            usn_journal: default_true(),
+            usn_max_age_hours: default_usn_max_age_hours(),
+            max_size_mb: default_cache_max_size_mb(),
+            prune_interval_days: default_prune_interval_days(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomCategory {
+    /// Display name, e.g. "Firefox: Cache"
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Where the rule came from (e.g. "bleachbit:firefox.xml"), for reference only
+    #[serde(default)]
+    pub source: Option<String>,
+
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub rules: Vec<CustomRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomRule {
+    /// File or folder to clean. Supports %VAR% environment variables and `*`/`?`
+    /// wildcards in path components (e.g. `%LOCALAPPDATA%\App\Profiles\*\cache2`)
+    pub path: String,
+
+    /// File name globs to match inside `path` (empty = every file)
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Also match files in subfolders
+    #[serde(default = "default_false")]
+    pub recursive: bool,
+}
+
+/// What to do with a large item whose volume has no usable Recycle Bin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrossVolumePolicy {
+    /// Recycle anyway (slow: the shell copies or deletes outright)
+    Recycle,
+    /// Delete permanently, with a notice
+    Permanent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossVolumeSettings {
+    /// Detect items on volumes the Recycle Bin can't take cheaply (mounted
+    /// folders, removable and network drives) before recycling them
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Items at least this large (MB) follow `policy`; smaller ones are recycled
+    /// Default: 256
+    #[serde(default = "default_cross_volume_large_mb")]
+    pub large_item_mb: u64,
+
+    /// Policy for large cross-volume items. "permanent" skips the slow copy
+    /// but deletes without a way back, so it has to be chosen explicitly.
+    /// Default: "recycle"
+    #[serde(default = "default_cross_volume_policy")]
+    pub policy: CrossVolumePolicy,
+
+    /// Per-category overrides (keys as in CLI flags, e.g. `large`, `downloads`)
+    #[serde(default = "default_cross_volume_categories")]
+    pub categories: std::collections::HashMap<String, CrossVolumePolicy>,
+}
+
+impl CrossVolumeSettings {
+    /// Policy for `category` (CLI key or display name)
+    pub fn policy_for(&self, category: &str) -> CrossVolumePolicy {
+        let key = category_key(category);
+        self.categories.get(&key).copied().unwrap_or(self.policy)
+    }
+}
+
+/// Map a category display name ("Old Downloads") or clean label to its CLI key ("downloads")
+pub(crate) fn category_key(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    let key = match lower.as_str() {
+        "package cache" => "cache",
+        "application cache" => "app_cache",
+        "temp files" => "temp",
+        "build artifacts" => "build",
+        "old downloads" => "downloads",
+        "large files" => "large",
+        "old files" => "old",
+        "installed applications" => "applications",
+        "browser cache" => "browser",
+        "system cache" => "system",
+        "empty folders" => "empty",
+        "custom rules" => "custom",
+        "ide caches" => "ide",
+        "rust toolchain" => "rust",
+        other => other,
+    };
+    key.replace([' ', '-'], "_")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineSettings {
+    /// Move deleted items into wole's quarantine folder instead of the
+    /// Recycle Bin (permanent deletes are unaffected)
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+
+    /// Days an item stays in quarantine before it's purged
+    /// Default: 14
+    #[serde(default = "default_quarantine_retention_days")]
+    pub retention_days: u64,
+
+    /// Quarantine folder (default: `quarantine` next to the deletion history)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl Default for QuarantineSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            retention_days: default_quarantine_retention_days(),
+            path: None,
+        }
+    }
+}
+
+impl Default for CrossVolumeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            large_item_mb: default_cross_volume_large_mb(),
+            policy: default_cross_volume_policy(),
+            categories: default_cross_volume_categories(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSettings {
+    /// Check battery level before IO-heavy scans (duplicates, disk insights, deep baseline)
+    #[serde(default = "default_true")]
+    pub check_battery: bool,
+
+    /// Battery level (percent) below which heavy scans are flagged while unplugged
+    /// Default: 30
+    #[serde(default = "default_min_battery_percent")]
+    pub min_battery_percent: u8,
+
+    /// Defer heavy scans on low battery instead of only warning
+    #[serde(default = "default_false")]
+    pub defer_on_low_battery: bool,
+
+    /// Mention metered connections (phone tethering, mobile data) in the warning
+    #[serde(default = "default_true")]
+    pub warn_on_metered: bool,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            check_battery: default_true(),
+            min_battery_percent: default_min_battery_percent(),
+            defer_on_low_battery: default_false(),
+            warn_on_metered: default_true(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerSettings {
+    /// Allow `wole index --daemon` to refresh caches in the background
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+
+    /// Total CPU usage (percent) below which the system counts as idle
+    /// Default: 15
+    #[serde(default = "default_idle_cpu_percent")]
+    pub idle_cpu_percent: f32,
+
+    /// Combined disk read+write (MB/s) below which the system counts as idle
+    /// Default: 5
+    #[serde(default = "default_idle_disk_mb_per_sec")]
+    pub idle_disk_mb_per_sec: f64,
+
+    /// Consecutive idle samples required before indexing starts
+    /// Default: 3
+    #[serde(default = "default_idle_samples")]
+    pub idle_samples: u32,
+
+    /// Seconds between idle checks
+    /// Default: 60
+    #[serde(default = "default_idle_check_interval")]
+    pub check_interval_secs: u64,
+
+    /// Re-index once the last pass is older than this many hours
+    /// Default: 6
+    #[serde(default = "default_reindex_hours")]
+    pub refresh_hours: u64,
+
+    /// Folders to pre-compute Disk Insights for (empty = home folder and system drive)
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+impl Default for IndexerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            idle_cpu_percent: default_idle_cpu_percent(),
+            idle_disk_mb_per_sec: default_idle_disk_mb_per_sec(),
+            idle_samples: default_idle_samples(),
+            check_interval_secs: default_idle_check_interval(),
+            refresh_hours: default_reindex_hours(),
+            paths: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Notify from `wole daemon` and `wole index --daemon` when a lot of space
+    /// is cleanable
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+
+    /// Cleanable space (GB) found by an index pass before a notification is shown
+    /// Default: 10
+    #[serde(default = "default_notify_min_cleanable_gb")]
+    pub min_cleanable_gb: u64,
+
+    /// Hours to wait before notifying again
+    /// Default: 24
+    #[serde(default = "default_notify_interval_hours")]
+    pub min_interval_hours: u64,
+
+    /// Categories the notification's "Clean now" action may clean (keys as in
+    /// CLI flags). Items go to the Recycle Bin.
+    /// Default: ["cache", "temp", "browser"]
+    #[serde(default = "default_notify_clean_categories")]
+    pub clean_categories: Vec<String>,
+
+    /// Show a desktop notification when `wole clean` finishes
+    #[serde(default = "default_false")]
+    pub after_clean: bool,
+
+    /// URL to POST a JSON summary to when `wole clean` finishes (empty = off)
+    #[serde(default)]
+    pub webhook_url: String,
+
+    /// Payload shape for `webhook_url`: "generic", "slack" or "teams"
+    /// Default: "generic"
+    #[serde(default = "default_webhook_format")]
+    pub webhook_format: String,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            min_cleanable_gb: default_notify_min_cleanable_gb(),
+            min_interval_hours: default_notify_interval_hours(),
+            clean_categories: default_notify_clean_categories(),
+            after_clean: default_false(),
+            webhook_url: String::new(),
+            webhook_format: default_webhook_format(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonSettings {
+    /// Hours between scheduled scans (`wole daemon`)
+    /// Default: 24
+    #[serde(default = "default_daemon_interval_hours")]
+    pub interval_hours: u64,
+
+    /// Categories to scan (keys as in CLI flags)
+    #[serde(default = "default_daemon_categories")]
+    pub categories: Vec<String>,
+
+    /// Command run after each scheduled scan, with `WOLE_REPORT` (path of the
+    /// JSON report) and `WOLE_CLEANABLE_BYTES` set (empty = none)
+    #[serde(default)]
+    pub hook: String,
+}
+
+impl Default for DaemonSettings {
+    fn default() -> Self {
+        Self {
+            interval_hours: default_daemon_interval_hours(),
+            categories: default_daemon_categories(),
+            hook: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencySettings {
+    /// Free space (MB) on the system drive below which it counts as critical
+    /// Default: 2048
+    #[serde(default = "default_critical_free_mb")]
+    pub critical_free_mb: u64,
+
+    /// Free space (percent of the drive) below which it counts as critical
+    /// Default: 5
+    #[serde(default = "default_critical_free_percent")]
+    pub critical_free_percent: u8,
+}
+
+impl Default for EmergencySettings {
+    fn default() -> Self {
+        Self {
+            critical_free_mb: default_critical_free_mb(),
+            critical_free_percent: default_critical_free_percent(),
+        }
+    }
+}
+
+impl EmergencySettings {
+    /// Whether `available` of `total` bytes is below either threshold
+    pub fn is_critical(&self, total: u64, available: u64) -> bool {
+        available < self.critical_free_mb * 1024 * 1024
+            || (total > 0 && available * 100 / total < self.critical_free_percent as u64)
+    }
+}
+
+/// Storage limits below the physical disk size (NTFS quotas are detected)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct QuotaSettings {
+    /// Size of the OneDrive plan in GB (0 = don't consider OneDrive)
+    /// Default: 0
+    #[serde(default)]
+    pub onedrive_limit_gb: u64,
+
+    /// Synced OneDrive folder (empty = `%OneDrive%`)
+    #[serde(default)]
+    pub onedrive_path: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CategorySettings {
     /// Default enabled categories for TUI (empty = use hardcoded defaults)
@@ -209,6 +654,15 @@ pub struct CategorySettings {
 
     #[serde(default)]
     pub duplicates: DuplicatesConfig,
+
+    #[serde(default)]
+    pub stale_locks: StaleLocksConfig,
+
+    #[serde(default)]
+    pub rust: RustConfig,
+
+    #[serde(default)]
+    pub app_leftovers: AppLeftoversConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -225,6 +679,56 @@ pub struct CategoryConfig {
     /// Merged with default artifacts
     #[serde(default)]
     pub custom_artifacts: Vec<String>,
+
+    /// Folders to scan instead of the default locations (build, large and
+    /// old only). `%VAR%` references are expanded.
+    /// Example: ["D:\\work", "%USERPROFILE%\\src"]
+    #[serde(default)]
+    pub roots: Vec<String>,
+
+    /// How deep to walk below each root (build, large and old only)
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Leave build artifacts of repositories with uncommitted changes or
+    /// unpushed commits out of the results (build only)
+    #[serde(default)]
+    pub exclude_dirty_repos: bool,
+
+    /// Also list downloaded OneDrive files unused for a month that "Free up
+    /// space" could make online-only (large only). Never cleaned.
+    #[serde(default)]
+    pub cloud_files: bool,
+
+    /// Which timestamp decides a file's age (old only). Default: "best"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_basis: Option<AgeBasis>,
+}
+
+/// Timestamp the old-files scan judges age by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeBasis {
+    /// Last access where the system keeps it up to date, last modification otherwise
+    #[default]
+    Best,
+    /// Last access
+    Atime,
+    /// Last modification
+    Mtime,
+    /// Creation
+    Ctime,
+}
+
+impl CategoryConfig {
+    /// Configured `roots` with environment variables expanded
+    pub fn root_paths(&self) -> Vec<PathBuf> {
+        self.roots
+            .iter()
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| PathBuf::from(crate::categories::custom::expand_vars(r.trim())))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -244,6 +748,51 @@ pub struct DuplicatesConfig {
     /// Default: 8MB for optimal performance on modern NVMe SSDs
     #[serde(default = "default_duplicate_buffer_size")]
     pub buffer_size_bytes: usize,
+
+    /// Also group images that look alike (JPEG/PNG copies re-saved at another
+    /// size or quality) for review. Similar images are never cleaned.
+    /// Default: false
+    #[serde(default)]
+    pub similar_images: bool,
+
+    /// How many of the 64 perceptual hash bits two images may differ in and
+    /// still count as similar. Lower is stricter.
+    /// Default: 10
+    #[serde(default = "default_similar_images_max_distance")]
+    pub similar_images_max_distance: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleLocksConfig {
+    /// Report leftover editor/package manager lock and temp artifacts under Temp Files
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Minimum age (days) before a lock/temp artifact is considered stale
+    /// Default: 7
+    #[serde(default = "default_stale_lock_age")]
+    pub min_age_days: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLeftoversConfig {
+    /// Report AppData folders of apps that look uninstalled under Application Cache
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Days a folder must be untouched before it's reported
+    /// Default: 180
+    #[serde(default = "default_app_leftovers_age")]
+    pub min_age_days: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustConfig {
+    /// Days without use before a registry crate or rustup toolchain is reported
+    /// Default: 90
+    #[serde(default = "default_rust_unused_days")]
+    pub unused_days: u64,
 }
 
 impl Default for Thresholds {
@@ -252,6 +801,7 @@ impl Default for Thresholds {
             project_age_days: default_project_age(),
             min_age_days: default_min_age(),
             min_size_mb: default_min_size_mb(),
+            trash_min_age_days: 0,
         }
     }
 }
@@ -266,29 +816,21 @@ impl Default for Exclusions {
 }
 
 impl Exclusions {
-    /// Get or compile the glob set for fast matching
-    fn get_compiled(&self) -> Option<&GlobSet> {
+    /// Add patterns (e.g. from `--exclude`), recompiling on next use
+    pub fn add_patterns(&mut self, patterns: &[String]) {
+        self.patterns.extend(patterns.iter().cloned());
+        self.compiled = OnceLock::new();
+    }
+
+    /// Get or compile the exclusion set for fast matching
+    fn get_compiled(&self) -> Option<&ExclusionSet> {
         self.compiled
             .get_or_init(|| {
                 if self.patterns.is_empty() {
                     return None;
                 }
-
-                let mut builder = GlobSetBuilder::new();
-                for pattern in &self.patterns {
-                    // Normalize pattern for globset
-                    let normalized = if pattern.starts_with("**/") || pattern.starts_with("/") {
-                        pattern.clone()
-                    } else {
-                        format!("**/{}", pattern)
-                    };
-
-                    if let Ok(glob) = Glob::new(&normalized) {
-                        builder.add(glob);
-                    }
-                }
-
-                builder.build().ok()
+                // Invalid patterns are skipped; the CLI rejects them up front
+                Some(ExclusionSet::new(&self.patterns).0)
             })
             .as_ref()
     }
@@ -304,6 +846,7 @@ impl Default for UiSettings {
             show_storage_info: default_false(),
             scan_depth_user: default_scan_depth_user(),
             scan_depth_entire_disk: default_scan_depth_entire_disk(),
+            largest_files: default_largest_files(),
         }
     }
 }
@@ -315,8 +858,14 @@ impl Default for SafetySettings {
             default_permanent: default_false(),
             max_no_confirm: default_max_no_confirm(),
             max_size_no_confirm_mb: default_max_size_no_confirm(),
+            typed_confirm_gb: default_typed_confirm_gb(),
+            typed_confirm_items: default_typed_confirm_items(),
             skip_locked_files: default_true(),
             dry_run_default: default_false(),
+            include_hidden: default_false(),
+            background_delete_min_entries: default_background_delete_min_entries(),
+            skip_open_in_editors: default_true(),
+            protected_paths: Vec::new(),
         }
     }
 }
@@ -325,8 +874,10 @@ impl Default for PerformanceSettings {
     fn default() -> Self {
         Self {
             scan_threads: default_threads(),
+            delete_threads: default_threads(),
             batch_size: default_batch_size(),
             parallel_scanning: default_true(),
+            use_mft: default_false(),
         }
     }
 }
@@ -337,6 +888,7 @@ impl Default for HistorySettings {
             enabled: default_true(),
             max_entries: default_max_history(),
             max_age_days: default_history_age_days(),
+            compress: false,
         }
     }
 }
@@ -347,6 +899,34 @@ impl Default for DuplicatesConfig {
             scan_paths: Vec::new(),
             memmap_threshold_bytes: default_memmap_threshold(),
             buffer_size_bytes: default_duplicate_buffer_size(),
+            similar_images: false,
+            similar_images_max_distance: default_similar_images_max_distance(),
+        }
+    }
+}
+
+impl Default for StaleLocksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_age_days: default_stale_lock_age(),
+        }
+    }
+}
+
+impl Default for AppLeftoversConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_age_days: default_app_leftovers_age(),
+        }
+    }
+}
+
+impl Default for RustConfig {
+    fn default() -> Self {
+        Self {
+            unused_days: default_rust_unused_days(),
         }
     }
 }
@@ -370,6 +950,15 @@ fn default_max_no_confirm() -> u64 {
 fn default_max_size_no_confirm() -> u64 {
     100
 } // 100 MB
+fn default_typed_confirm_gb() -> u64 {
+    100
+}
+fn default_typed_confirm_items() -> u64 {
+    100_000
+}
+fn default_background_delete_min_entries() -> u64 {
+    20_000
+}
 fn default_threads() -> u32 {
     0
 } // 0 = auto-detect
@@ -398,12 +987,99 @@ fn default_memmap_threshold() -> u64 {
 fn default_duplicate_buffer_size() -> usize {
     8 * 1024 * 1024
 } // 8MB
+fn default_similar_images_max_distance() -> u32 {
+    10
+}
+fn default_stale_lock_age() -> u64 {
+    7
+}
+fn default_rust_unused_days() -> u64 {
+    90
+}
+fn default_app_leftovers_age() -> u64 {
+    180
+}
+fn default_min_battery_percent() -> u8 {
+    30
+}
+fn default_idle_cpu_percent() -> f32 {
+    15.0
+}
+fn default_idle_disk_mb_per_sec() -> f64 {
+    5.0
+}
+fn default_idle_samples() -> u32 {
+    3
+}
+fn default_idle_check_interval() -> u64 {
+    60
+}
+fn default_reindex_hours() -> u64 {
+    6
+}
+fn default_daemon_interval_hours() -> u64 {
+    24
+}
+fn default_daemon_categories() -> Vec<String> {
+    [
+        "cache",
+        "app_cache",
+        "temp",
+        "trash",
+        "build",
+        "downloads",
+        "browser",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+fn default_notify_min_cleanable_gb() -> u64 {
+    10
+}
+fn default_notify_interval_hours() -> u64 {
+    24
+}
+fn default_notify_clean_categories() -> Vec<String> {
+    ["cache", "temp", "browser"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+fn default_webhook_format() -> String {
+    "generic".to_string()
+}
+fn default_critical_free_mb() -> u64 {
+    2048
+}
+fn default_critical_free_percent() -> u8 {
+    5
+}
+fn default_cross_volume_large_mb() -> u64 {
+    256
+}
+fn default_quarantine_retention_days() -> u64 {
+    14
+}
+fn default_cross_volume_policy() -> CrossVolumePolicy {
+    CrossVolumePolicy::Recycle
+}
+fn default_cross_volume_categories() -> std::collections::HashMap<String, CrossVolumePolicy> {
+    // User files stay recoverable even when `policy = "permanent"`
+    ["downloads", "large", "old", "duplicates"]
+        .into_iter()
+        .map(|c| (c.to_string(), CrossVolumePolicy::Recycle))
+        .collect()
+}
 fn default_scan_depth_user() -> u8 {
     8
 }
 fn default_scan_depth_entire_disk() -> u8 {
     10
 }
+fn default_largest_files() -> usize {
+    10
+}
 fn default_cache_age() -> u64 {
     30
 }
@@ -411,6 +1087,18 @@ fn default_hash_threshold() -> u64 {
     10 * 1024 * 1024 // 10MB
 }
 
+fn default_usn_max_age_hours() -> u64 {
+    24
+}
+
+fn default_cache_max_size_mb() -> u64 {
+    512
+}
+
+fn default_prune_interval_days() -> u64 {
+    7
+}
+
 impl Config {
     /// Get the config file path: %APPDATA%\wole\config.toml
     pub fn config_path() -> Result<PathBuf> {
@@ -519,14 +1207,22 @@ impl Config {
     ///
     /// Uses pre-compiled glob patterns for O(1) matching instead of O(patterns)
     pub fn is_excluded(&self, path: &Path) -> bool {
+        let excluded = self.matches_exclusion(path);
+        if excluded {
+            crate::recording::note(crate::recording::DecisionKind::Excluded, path);
+        }
+        excluded
+    }
+
+    fn matches_exclusion(&self, path: &Path) -> bool {
         // Fast path: no patterns
         if self.exclusions.patterns.is_empty() {
             return false;
         }
 
-        // Use compiled glob set for fast matching
-        if let Some(glob_set) = self.exclusions.get_compiled() {
-            return glob_set.is_match(path);
+        // Use compiled patterns for fast matching
+        if let Some(set) = self.exclusions.get_compiled() {
+            return set.is_match(path);
         }
 
         // Fallback to old logic if compilation failed
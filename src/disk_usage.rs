@@ -1,5 +1,6 @@
 //! Disk usage analysis - scan filesystem and calculate folder sizes
 
+use crate::tasks::CancelToken;
 use crate::utils;
 use anyhow::Result;
 use jwalk::WalkDir;
@@ -27,6 +28,30 @@ pub struct FolderNode {
     pub children: Vec<FolderNode>,
     pub files: Vec<FileInfo>, // Files directly in this directory (not in subdirectories)
     pub percentage: f64,      // % of parent's total size
+    /// What the size is made of, largest first: file types (`FileType`
+    /// names), or dependency and build folders such as `node_modules` below
+    /// this one
+    #[serde(default)]
+    pub breakdown: Vec<(String, u64)>,
+}
+
+impl FolderNode {
+    /// The `parts` biggest shares of this folder's size, like
+    /// "60% Video, 25% node_modules"; shares under 1% are left out
+    pub fn breakdown_summary(&self, parts: usize) -> Option<String> {
+        if self.size == 0 {
+            return None;
+        }
+        let shares: Vec<String> = self
+            .breakdown
+            .iter()
+            .map(|(kind, bytes)| (kind, *bytes as f64 / self.size as f64 * 100.0))
+            .filter(|(_, percent)| *percent >= 1.0)
+            .take(parts)
+            .map(|(kind, percent)| format!("{:.0}% {}", percent, kind))
+            .collect();
+        (!shares.is_empty()).then(|| shares.join(", "))
+    }
 }
 
 /// Complete disk insights data
@@ -35,9 +60,17 @@ pub struct DiskInsights {
     pub root: FolderNode,
     pub total_size: u64,
     pub total_files: u64,
-    pub largest_files: Vec<(PathBuf, u64)>, // Top 10 largest files
+    /// Largest files under the root, largest first (`[ui] largest_files` of them)
+    pub largest_files: Vec<(PathBuf, u64)>,
     #[serde(with = "duration_serde")]
     pub scan_duration: Duration,
+    /// Folders where another volume is mounted. Their contents live on that
+    /// volume, so they're not walked or counted here.
+    #[serde(default)]
+    pub mounted_volumes: Vec<PathBuf>,
+    /// The scan was cancelled and only covers the folders read until then
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 /// Serialize/Deserialize Duration as seconds (f64)
@@ -77,21 +110,32 @@ pub type ProgressCallback = Box<dyn Fn(&Path) + Send + Sync>;
 /// Scan a directory and build a folder tree with sizes
 /// If progress_callback is provided, it will be called for each file being read
 pub fn scan_directory(path: &Path, max_depth: u8) -> Result<DiskInsights> {
-    scan_directory_with_progress(path, max_depth, None)
+    scan_directory_with_progress(path, max_depth, None, None)
 }
 
 /// Scan a directory with progress reporting
-/// Checks cache first, and saves results to cache after scanning
+/// Checks cache first, and saves results to cache after scanning.
+/// Once `cancel` is set the walk stops and the folders read so far are
+/// returned with `cancelled` set; those partial insights aren't cached.
 pub fn scan_directory_with_progress(
     path: &Path,
     max_depth: u8,
     progress_callback: Option<ProgressCallback>,
+    cancel: Option<&CancelToken>,
 ) -> Result<DiskInsights> {
-    // Check cache first
+    let is_cancelled = || cancel.is_some_and(CancelToken::is_cancelled);
+
+    let config = crate::config::Config::load();
+    let largest_count = config.ui.largest_files;
+
+    // Check cache first; it's stale if it lists fewer largest files than asked for
     if let Ok(Some(cached_insights)) =
         crate::disk_usage_cache::load_cached_insights(path, max_depth)
     {
-        return Ok(cached_insights);
+        let listed = largest_count.min(cached_insights.total_files as usize);
+        if cached_insights.largest_files.len() >= listed {
+            return Ok(cached_insights);
+        }
     }
 
     let start_time = Instant::now();
@@ -103,6 +147,7 @@ pub fn scan_directory_with_progress(
     let dir_file_counts: Mutex<HashMap<PathBuf, u64>> = Mutex::new(HashMap::new());
     let dir_files: Mutex<HashMap<PathBuf, Vec<(PathBuf, u64)>>> = Mutex::new(HashMap::new()); // Files per directory
     let dir_children: Mutex<HashMap<PathBuf, Vec<PathBuf>>> = Mutex::new(HashMap::new()); // Track directory structure
+    let dir_kinds: Mutex<HashMap<PathBuf, HashMap<&'static str, u64>>> = Mutex::new(HashMap::new());
     let file_sizes: Mutex<Vec<(PathBuf, u64)>> = Mutex::new(Vec::new());
 
     let total_size = AtomicU64::new(0);
@@ -112,117 +157,163 @@ pub fn scan_directory_with_progress(
     use std::sync::atomic::AtomicUsize;
     let error_count = AtomicUsize::new(0);
 
+    let mounted_volumes: std::sync::Arc<Mutex<Vec<PathBuf>>> = Default::default();
+    let mounted_in_walk = std::sync::Arc::clone(&mounted_volumes);
+
+    // Track directory structure for all directories we encounter
+    let add_dir = |entry_path: &Path| {
+        if let Some(parent) = entry_path.parent() {
+            // Only track if parent is within our scan root
+            if parent.starts_with(path) || parent == path {
+                let mut children = dir_children.lock().unwrap();
+                let child_list = children.entry(parent.to_path_buf()).or_default();
+                // Avoid duplicates
+                if !child_list.contains(&entry_path.to_path_buf()) {
+                    child_list.push(entry_path.to_path_buf());
+                }
+            }
+        }
+    };
+
+    let add_file = |entry_path: &Path, size: u64| {
+        // Report progress for this file
+        if let Some(ref callback) = progress_callback {
+            callback(entry_path);
+        }
+
+        total_size.fetch_add(size, Ordering::Relaxed);
+        total_files.fetch_add(1, Ordering::Relaxed);
+
+        // Track file size for largest files list
+        file_sizes
+            .lock()
+            .unwrap()
+            .push((entry_path.to_path_buf(), size));
+
+        // Add file to its parent directory's file list
+        if let Some(parent) = entry_path.parent() {
+            let mut files = dir_files.lock().unwrap();
+            files
+                .entry(parent.to_path_buf())
+                .or_default()
+                .push((entry_path.to_path_buf(), size));
+
+            let mut sizes = dir_sizes.lock().unwrap();
+            *sizes.entry(parent.to_path_buf()).or_insert(0) += size;
+
+            let mut counts = dir_file_counts.lock().unwrap();
+            *counts.entry(parent.to_path_buf()).or_insert(0) += 1;
+
+            // The file counts as its type, and above a dependency or build
+            // folder as that folder
+            let mut kinds = dir_kinds.lock().unwrap();
+            let mut kind = utils::detect_file_type(entry_path).as_str();
+            *kinds
+                .entry(parent.to_path_buf())
+                .or_default()
+                .entry(kind)
+                .or_insert(0) += size;
+
+            // Also add to all ancestor directories
+            let mut current = parent;
+            while let Some(ancestor) = current.parent() {
+                // Ensure we're still within the scan root
+                if !ancestor.starts_with(path) && ancestor != path {
+                    break;
+                }
+
+                kind = dependency_folder(current).unwrap_or(kind);
+                *sizes.entry(ancestor.to_path_buf()).or_insert(0) += size;
+                *counts.entry(ancestor.to_path_buf()).or_insert(0) += 1;
+                *kinds
+                    .entry(ancestor.to_path_buf())
+                    .or_default()
+                    .entry(kind)
+                    .or_insert(0) += size;
+                current = ancestor;
+
+                // Stop if we've reached the root
+                if current == path {
+                    break;
+                }
+            }
+        }
+    };
+
+    // Reading the MFT lists the whole volume at once; without elevation or
+    // on other filesystems, walk the folders instead
+    let from_mft =
+        config.performance.use_mft && scan_mft(path, max_depth, &is_cancelled, &add_dir, &add_file);
+
     // Use jwalk for parallel traversal
-    WalkDir::new(path)
-        .max_depth(max_depth as usize)
-        .follow_links(false)
-        .parallelism(jwalk::Parallelism::RayonDefaultPool {
-            busy_timeout: Duration::from_secs(1),
-        })
-        .process_read_dir(|_depth, _path, _state, children| {
-            // Filter out entries we want to skip
-            children.retain(|entry| {
-                if let Ok(ref e) = entry {
-                    // Skip symlinks
-                    if e.file_type().is_symlink() {
-                        return false;
-                    }
-                    // Skip reparse points on Windows
-                    if utils::is_windows_reparse_point(&e.path()) {
-                        return false;
-                    }
-                    // Skip system directories - but be more careful for user directories
-                    // Only skip if it's actually a system directory, not just containing the word
-                    if utils::is_system_path(&e.path()) {
-                        return false;
-                    }
+    if !from_mft {
+        let walk_cancel = cancel.cloned();
+        WalkDir::new(path)
+            .max_depth(max_depth as usize)
+            .follow_links(false)
+            .parallelism(jwalk::Parallelism::RayonDefaultPool {
+                busy_timeout: Duration::from_secs(1),
+            })
+            .process_read_dir(move |_depth, _path, _state, children| {
+                if walk_cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+                    children.clear();
+                    return;
                 }
-                true
-            });
-        })
-        .into_iter()
-        .for_each(|entry| {
-            match entry {
-                Ok(e) => {
-                    let entry_path = e.path();
-
-                    // Track directory structure for all directories we encounter
-                    if e.file_type().is_dir() {
-                        if let Some(parent) = entry_path.parent() {
-                            // Only track if parent is within our scan root
-                            if parent.starts_with(path) || parent == path {
-                                let mut children = dir_children.lock().unwrap();
-                                let child_list = children.entry(parent.to_path_buf()).or_default();
-                                // Avoid duplicates
-                                if !child_list.contains(&entry_path) {
-                                    child_list.push(entry_path.to_path_buf());
-                                }
-                            }
+                // Filter out entries we want to skip
+                children.retain(|entry| {
+                    if let Ok(ref e) = entry {
+                        // Another volume mounted into a folder: note it, but its
+                        // size belongs to that volume
+                        if e.depth() > 0
+                            && (e.file_type().is_dir() || e.file_type().is_symlink())
+                            && utils::is_mount_point(&e.path())
+                        {
+                            mounted_in_walk.lock().unwrap().push(e.path());
+                            return false;
+                        }
+                        // Skip symlinks
+                        if e.file_type().is_symlink() {
+                            return false;
+                        }
+                        // Skip reparse points on Windows
+                        if utils::is_windows_reparse_point(&e.path()) {
+                            return false;
+                        }
+                        // Skip system directories - but be more careful for user directories
+                        // Only skip if it's actually a system directory, not just containing the word
+                        if utils::is_system_path(&e.path()) {
+                            return false;
                         }
                     }
-
-                    if e.file_type().is_file() {
-                        // Report progress for this file
-                        if let Some(ref callback) = progress_callback {
-                            callback(&entry_path);
+                    true
+                });
+            })
+            .into_iter()
+            .take_while(|_| !is_cancelled())
+            .for_each(|entry| {
+                match entry {
+                    Ok(e) => {
+                        let entry_path = e.path();
+                        if e.file_type().is_dir() {
+                            add_dir(&entry_path);
                         }
-
-                        if let Ok(meta) = e.metadata() {
-                            let size = meta.len();
-                            total_size.fetch_add(size, Ordering::Relaxed);
-                            total_files.fetch_add(1, Ordering::Relaxed);
-
-                            // Track file size for largest files list
-                            file_sizes
-                                .lock()
-                                .unwrap()
-                                .push((entry_path.to_path_buf(), size));
-
-                            // Add file to its parent directory's file list
-                            if let Some(parent) = entry_path.parent() {
-                                let mut files = dir_files.lock().unwrap();
-                                files
-                                    .entry(parent.to_path_buf())
-                                    .or_default()
-                                    .push((entry_path.to_path_buf(), size));
-
-                                let mut sizes = dir_sizes.lock().unwrap();
-                                *sizes.entry(parent.to_path_buf()).or_insert(0) += size;
-
-                                let mut counts = dir_file_counts.lock().unwrap();
-                                *counts.entry(parent.to_path_buf()).or_insert(0) += 1;
-
-                                // Also add to all ancestor directories
-                                let mut current = parent;
-                                while let Some(ancestor) = current.parent() {
-                                    // Ensure we're still within the scan root
-                                    if !ancestor.starts_with(path) && ancestor != path {
-                                        break;
-                                    }
-
-                                    *sizes.entry(ancestor.to_path_buf()).or_insert(0) += size;
-                                    *counts.entry(ancestor.to_path_buf()).or_insert(0) += 1;
-                                    current = ancestor;
-
-                                    // Stop if we've reached the root
-                                    if current == path {
-                                        break;
-                                    }
-                                }
+                        if e.file_type().is_file() {
+                            if let Ok(meta) = e.metadata() {
+                                add_file(&entry_path, meta.len());
                             }
                         }
                     }
+                    Err(e) => {
+                        // Track errors but continue scanning
+                        // jwalk will continue even if some directories can't be accessed
+                        error_count.fetch_add(1, Ordering::Relaxed);
+                        // Silently skip to avoid cluttering output
+                        // Errors are typically permission denied, which is expected for system directories
+                        crate::elevation::note_jwalk_error(&e);
+                    }
                 }
-                Err(e) => {
-                    // Track errors but continue scanning
-                    // jwalk will continue even if some directories can't be accessed
-                    error_count.fetch_add(1, Ordering::Relaxed);
-                    // Silently skip to avoid cluttering output
-                    // Errors are typically permission denied, which is expected for system directories
-                    let _ = e;
-                }
-            }
-        });
+            });
+    }
 
     let total_size = total_size.load(Ordering::Relaxed);
     let total_files = total_files.load(Ordering::Relaxed);
@@ -240,12 +331,12 @@ pub fn scan_directory_with_progress(
         eprintln!("Warning: {} directories could not be accessed (likely permission denied). Results may be incomplete.", errors_encountered);
     }
 
-    // Get top 10 largest files
+    // Largest files of the whole walk, not just of the top folders
     file_sizes.sort_by_key(|b| std::cmp::Reverse(b.1));
-    let largest_files = file_sizes.into_iter().take(10).collect();
+    let largest_files = file_sizes.into_iter().take(largest_count).collect();
 
     // Build folder tree starting from root
-    let root = build_folder_tree(
+    let mut root = build_folder_tree(
         path,
         &dir_sizes,
         &dir_file_counts,
@@ -254,6 +345,7 @@ pub fn scan_directory_with_progress(
         total_size,
         max_depth,
     )?;
+    attach_breakdowns(&mut root, &dir_kinds.into_inner().unwrap());
 
     let insights = DiskInsights {
         root,
@@ -261,14 +353,79 @@ pub fn scan_directory_with_progress(
         total_files,
         largest_files,
         scan_duration: start_time.elapsed(),
+        mounted_volumes: {
+            let mut mounted = std::mem::take(&mut *mounted_volumes.lock().unwrap());
+            mounted.sort();
+            mounted
+        },
+        cancelled: is_cancelled(),
     };
+    if insights.cancelled {
+        return Ok(insights);
+    }
 
     // Save to cache (ignore errors - cache is optional)
     let _ = crate::disk_usage_cache::save_cached_insights(path, max_depth, &insights);
+    // Fresh scans feed the usage history (cached results were recorded already)
+    let _ = crate::usage_history::record_insights(path, &insights);
 
     Ok(insights)
 }
 
+/// Feed a scan from the NTFS MFT, skipping what the folder walk skips.
+/// False when the MFT can't be read.
+fn scan_mft(
+    path: &Path,
+    max_depth: u8,
+    is_cancelled: &impl Fn() -> bool,
+    add_dir: &impl Fn(&Path),
+    add_file: &impl Fn(&Path, u64),
+) -> bool {
+    crate::mft::walk(path, max_depth as usize, |entry| {
+        if is_cancelled() || utils::is_system_path(&entry.path) {
+            return false;
+        }
+        if entry.is_dir {
+            add_dir(&entry.path);
+        } else {
+            add_file(&entry.path, entry.size);
+        }
+        true
+    })
+    .is_ok()
+}
+
+/// The name of `dir` if it's a dependency or build folder (`node_modules`,
+/// `target`, ...) whose size is worth naming on its own
+fn dependency_folder(dir: &Path) -> Option<&'static str> {
+    let name = dir.file_name()?.to_str()?;
+    utils::SKIP_WALK_DIRS
+        .iter()
+        .find(|d| d.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Fill in each node's `breakdown` from the per-folder totals of the scan
+fn attach_breakdowns(
+    node: &mut FolderNode,
+    dir_kinds: &HashMap<PathBuf, HashMap<&'static str, u64>>,
+) {
+    if let Some(kinds) = dir_kinds.get(&node.path) {
+        node.breakdown = kinds
+            .iter()
+            .map(|(kind, bytes)| (kind.to_string(), *bytes))
+            .collect();
+        sort_breakdown(&mut node.breakdown);
+    }
+    for child in &mut node.children {
+        attach_breakdowns(child, dir_kinds);
+    }
+}
+
+fn sort_breakdown(breakdown: &mut [(String, u64)]) {
+    breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
+
 /// Build a folder tree from directory size map
 fn build_folder_tree(
     path: &Path,
@@ -428,6 +585,7 @@ fn build_folder_tree(
         children,
         files,
         percentage,
+        breakdown: Vec::new(),
     })
 }
 
@@ -505,6 +663,77 @@ pub fn find_folder_by_path<'a>(node: &'a FolderNode, target_path: &Path) -> Opti
     None
 }
 
+/// Remove a file or folder from the tree after it has been deleted from disk.
+///
+/// Sizes, file counts and percentages of every ancestor are updated. Returns the
+/// (bytes, files) taken out, or `None` if `target` isn't below `node`.
+pub fn remove_path(node: &mut FolderNode, target: &Path) -> Option<(u64, u64)> {
+    remove_from(node, target).map(|(bytes, files, _)| (bytes, files))
+}
+
+/// Bytes, files and breakdown taken out of a node
+type Removed = (u64, u64, Vec<(String, u64)>);
+
+/// `remove_path`, also returning the breakdown taken out of `node`
+fn remove_from(node: &mut FolderNode, target: &Path) -> Option<Removed> {
+    if node.path == target || !target.starts_with(&node.path) {
+        return None;
+    }
+
+    let (bytes, files, kinds) = if let Some(pos) = node.files.iter().position(|f| f.path == target)
+    {
+        let file = node.files.remove(pos);
+        let kind = utils::detect_file_type(&file.path).as_str().to_string();
+        (file.size, 1, vec![(kind, file.size)])
+    } else if let Some(pos) = node.children.iter().position(|c| c.path == target) {
+        let child = node.children.remove(pos);
+        let kinds = match dependency_folder(&child.path) {
+            Some(name) => vec![(name.to_string(), child.size)],
+            None => child.breakdown,
+        };
+        (child.size, child.file_count, kinds)
+    } else {
+        let (child, removed) = node
+            .children
+            .iter_mut()
+            .find_map(|child| Some((child.path.clone(), remove_from(child, target)?)))?;
+        let (bytes, files, kinds) = removed;
+        // Seen from here, everything below a dependency folder is that folder
+        match dependency_folder(&child) {
+            Some(name) => (bytes, files, vec![(name.to_string(), bytes)]),
+            None => (bytes, files, kinds),
+        }
+    };
+
+    node.size = node.size.saturating_sub(bytes);
+    node.file_count = node.file_count.saturating_sub(files);
+    if node.size > 0 {
+        for child in &mut node.children {
+            child.percentage = (child.size as f64 / node.size as f64) * 100.0;
+        }
+    }
+    for (kind, removed) in &kinds {
+        if let Some(entry) = node.breakdown.iter_mut().find(|(k, _)| k == kind) {
+            entry.1 = entry.1.saturating_sub(*removed);
+        }
+    }
+    node.breakdown.retain(|(_, bytes)| *bytes > 0);
+    sort_breakdown(&mut node.breakdown);
+    Some((bytes, files, kinds))
+}
+
+impl DiskInsights {
+    /// Drop a deleted path from the tree, the totals and the largest files list
+    pub fn remove_path(&mut self, target: &Path) {
+        if let Some((bytes, files)) = remove_path(&mut self.root, target) {
+            self.total_size = self.total_size.saturating_sub(bytes);
+            self.total_files = self.total_files.saturating_sub(files);
+            self.largest_files
+                .retain(|(path, _)| !path.starts_with(target));
+        }
+    }
+}
+
 /// Get breadcrumb path from root to target
 pub fn get_breadcrumb(root: &FolderNode, target: &Path) -> Vec<String> {
     let mut breadcrumb = Vec::new();
@@ -531,3 +760,55 @@ pub fn get_breadcrumb(root: &FolderNode, target: &Path) -> Vec<String> {
 
     breadcrumb
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn breakdown_names_file_types_and_dependency_folders() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let modules = root.join("app").join("node_modules");
+        fs::create_dir_all(modules.join("lib")).unwrap();
+        fs::write(modules.join("lib").join("bundle.zip"), vec![0u8; 3000]).unwrap();
+        fs::write(root.join("app").join("intro.mp4"), vec![0u8; 6000]).unwrap();
+        fs::write(root.join("notes.txt"), vec![0u8; 1000]).unwrap();
+
+        let mut insights = scan_directory_with_progress(root, 4, None, None).unwrap();
+        assert_eq!(
+            insights.root.breakdown_summary(2).as_deref(),
+            Some("60% Video, 30% node_modules")
+        );
+        // Inside the dependency folder, its own contents are broken down
+        let node = find_folder_by_path(&insights.root, &modules).unwrap();
+        assert_eq!(node.breakdown_summary(3).as_deref(), Some("100% Archive"));
+
+        insights.remove_path(&modules);
+        assert_eq!(
+            insights.root.breakdown,
+            vec![("Video".to_string(), 6000), ("Text".to_string(), 1000)]
+        );
+    }
+
+    #[test]
+    fn largest_files_come_from_nested_folders() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let deep = temp_dir.path().join("a").join("b").join("c");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("huge.iso"), vec![0u8; 5000]).unwrap();
+        for i in 0..12 {
+            fs::write(
+                temp_dir.path().join(format!("{}.txt", i)),
+                vec![0u8; 10 + i],
+            )
+            .unwrap();
+        }
+
+        let insights = scan_directory_with_progress(temp_dir.path(), 5, None, None).unwrap();
+        assert_eq!(insights.largest_files.len(), 10);
+        assert_eq!(insights.largest_files[0], (deep.join("huge.iso"), 5000));
+        assert_eq!(insights.largest_files[1].1, 21);
+    }
+}
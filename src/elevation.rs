@@ -0,0 +1,261 @@
+//! Detection of scans limited by missing administrator rights
+//!
+//! Walkers report "access denied" errors here instead of dropping them silently.
+//! After a scan, `hint()` summarizes how much was skipped (grouped by top-level
+//! folder such as `C:\ProgramData`) so the UI can suggest running elevated.
+//! The TUI relaunches itself elevated through a small JSON handoff file that
+//! carries the current scan settings over to the new process; `clean
+//! --elevate` runs its admin-only steps the same way and waits for them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Minimum number of unreadable folders before elevation is suggested
+const MIN_DENIED: u64 = 20;
+
+/// Number of top-level folders listed in the summary
+const SUMMARY_ROOTS: usize = 3;
+
+/// Access-denied counts per top-level folder for the current scan
+static DENIED: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+
+/// Clear recorded denials (call at the start of each scan)
+pub fn reset() {
+    if let Ok(mut denied) = DENIED.lock() {
+        denied.clear();
+    }
+}
+
+/// Record a path that could not be read due to missing permissions
+pub fn record_denied(path: &Path) {
+    let root = top_level_folder(path);
+    if let Ok(mut denied) = DENIED.lock() {
+        *denied.entry(root).or_insert(0) += 1;
+    }
+}
+
+/// Record `path` if `err` is a permission error
+pub fn note_io_error(path: &Path, err: &io::Error) {
+    if err.kind() == io::ErrorKind::PermissionDenied {
+        record_denied(path);
+    }
+}
+
+/// Record a `walkdir` error if it was caused by missing permissions
+pub fn note_walkdir_error(err: &walkdir::Error) {
+    if let (Some(path), Some(io_err)) = (err.path(), err.io_error()) {
+        note_io_error(path, io_err);
+    }
+}
+
+/// Record a `jwalk` error if it was caused by missing permissions
+pub fn note_jwalk_error(err: &jwalk::Error) {
+    if let (Some(path), Some(io_err)) = (err.path(), err.io_error()) {
+        note_io_error(path, io_err);
+    }
+}
+
+/// Group a path under its drive + first folder (e.g. `C:\ProgramData`)
+fn top_level_folder(path: &Path) -> PathBuf {
+    let mut root = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => root.push(component.as_os_str()),
+            Component::Normal(name) => {
+                root.push(name);
+                break;
+            }
+            _ => {}
+        }
+    }
+    if root.as_os_str().is_empty() {
+        path.to_path_buf()
+    } else {
+        root
+    }
+}
+
+/// Summary of what a scan could not see without elevation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElevationHint {
+    /// Total number of unreadable folders/files
+    pub denied: u64,
+    /// Top-level folders with the most denials, largest first
+    pub roots: Vec<(PathBuf, u64)>,
+}
+
+impl ElevationHint {
+    /// One-line summary, e.g. "312 folders unreadable (C:\ProgramData: 280, C:\Windows: 32)"
+    pub fn summary(&self) -> String {
+        let top: Vec<String> = self
+            .roots
+            .iter()
+            .take(SUMMARY_ROOTS)
+            .map(|(root, count)| format!("{}: {}", root.display(), count))
+            .collect();
+        let more = self.roots.len().saturating_sub(SUMMARY_ROOTS);
+        let more = if more > 0 {
+            format!(", +{} more", more)
+        } else {
+            String::new()
+        };
+        format!(
+            "{} folders unreadable without admin ({}{})",
+            self.denied,
+            top.join(", "),
+            more
+        )
+    }
+}
+
+/// Build a hint from recorded denials, if elevation would make a difference
+fn hint_from(denied: &BTreeMap<PathBuf, u64>) -> Option<ElevationHint> {
+    let total: u64 = denied.values().sum();
+    if total < MIN_DENIED {
+        return None;
+    }
+    let mut roots: Vec<(PathBuf, u64)> = denied.iter().map(|(p, c)| (p.clone(), *c)).collect();
+    roots.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Some(ElevationHint {
+        denied: total,
+        roots,
+    })
+}
+
+/// Elevation hint for the last scan, or None when already elevated or nothing
+/// significant was skipped
+pub fn hint() -> Option<ElevationHint> {
+    if crate::optimize::is_admin() {
+        return None;
+    }
+    let denied = DENIED.lock().ok()?;
+    hint_from(&denied)
+}
+
+/// State carried over when the TUI relaunches itself elevated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ElevationHandoff {
+    /// Path being scanned
+    pub scan_path: PathBuf,
+    /// Display names of the enabled categories
+    pub categories: Vec<String>,
+    /// Active search filter on the Results screen
+    #[serde(default)]
+    pub search_query: String,
+    /// Start a new scan immediately after relaunching
+    #[serde(default)]
+    pub rescan: bool,
+}
+
+/// Hidden argument used to pass the handoff file to the elevated process
+pub const RESUME_ARG: &str = "--resume-elevated";
+
+/// Write the handoff file and return its path
+pub fn write_handoff(handoff: &ElevationHandoff) -> Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("wole_elevate_{}.json", std::process::id()));
+    let json =
+        serde_json::to_string_pretty(handoff).context("Failed to serialize elevation handoff")?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write handoff file: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Read and remove a handoff file written by `write_handoff`
+pub fn take_handoff(path: &Path) -> Result<ElevationHandoff> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read handoff file: {}", path.display()))?;
+    let _ = std::fs::remove_file(path);
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse handoff file: {}", path.display()))
+}
+
+/// Relaunch wole elevated (UAC prompt) and resume from `handoff_path`
+pub fn relaunch_elevated(handoff_path: &Path) -> Result<()> {
+    start_elevated(RESUME_ARG, handoff_path, false)
+}
+
+/// Run `wole <arg> <file>` elevated (UAC prompt) in a hidden window and wait
+/// for it to exit
+pub fn run_elevated(arg: &str, file: &Path) -> Result<()> {
+    start_elevated(arg, file, true)
+}
+
+#[cfg(windows)]
+fn start_elevated(arg: &str, file: &Path, wait: bool) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate wole executable")?;
+    // Single quotes are escaped by doubling them in PowerShell literals
+    let quote = |p: &Path| p.display().to_string().replace('\'', "''");
+    let command = format!(
+        "Start-Process -FilePath '{}' -ArgumentList '{}','\"{}\"' -Verb RunAs{}",
+        quote(&exe),
+        arg,
+        quote(file),
+        if wait {
+            " -Wait -WindowStyle Hidden"
+        } else {
+            ""
+        }
+    );
+    let status = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            command.as_str(),
+        ])
+        .status()
+        .context("Failed to start PowerShell")?;
+    if !status.success() {
+        anyhow::bail!("Elevation was cancelled or failed");
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn start_elevated(_arg: &str, _file: &Path, _wait: bool) -> Result<()> {
+    anyhow::bail!("Relaunching elevated is only supported on Windows")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_by_top_level_folder() {
+        assert_eq!(
+            top_level_folder(Path::new("/ProgramData/Microsoft/Crypto")),
+            PathBuf::from("/ProgramData")
+        );
+        assert_eq!(top_level_folder(Path::new("/")), PathBuf::from("/"));
+    }
+
+    #[test]
+    fn hint_requires_enough_denials() {
+        let mut denied = BTreeMap::new();
+        denied.insert(PathBuf::from("/ProgramData"), 5);
+        assert!(hint_from(&denied).is_none());
+
+        denied.insert(PathBuf::from("/Windows"), 30);
+        let hint = hint_from(&denied).unwrap();
+        assert_eq!(hint.denied, 35);
+        assert_eq!(hint.roots[0], (PathBuf::from("/Windows"), 30));
+        assert!(hint.summary().starts_with("35 folders unreadable"));
+    }
+
+    #[test]
+    fn handoff_round_trips() {
+        let handoff = ElevationHandoff {
+            scan_path: PathBuf::from("/data"),
+            categories: vec!["Temp Files".to_string()],
+            search_query: "log".to_string(),
+            rescan: true,
+        };
+        let path = write_handoff(&handoff).unwrap();
+        assert_eq!(take_handoff(&path).unwrap(), handoff);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,214 @@
+//! Free-space emergency mode
+//!
+//! When the system drive is nearly full, `wole emergency` builds a one-screen
+//! plan from the fastest safe scans (temp files, caches, Recycle Bin), asks
+//! once and executes it. Everything here is chosen to keep working with
+//! almost no free space: items are deleted permanently (moving them to the
+//! Recycle Bin frees nothing), the scan cache isn't opened or written, and the
+//! deletion history is only saved after space has been freed.
+
+use crate::categories;
+use crate::cleaner;
+use crate::config::{Config, QuotaSettings};
+use crate::history::DeletionLog;
+use crate::output::{CategoryResult, OutputMode};
+use crate::quota::{self, Quota, QuotaKind};
+use crate::utils::{self, SizeWalk};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Total and available bytes of a drive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriveSpace {
+    pub mount: PathBuf,
+    pub total: u64,
+    pub available: u64,
+    /// Set when a quota leaves less room than the disk; `total` and
+    /// `available` are then the quota's
+    pub quota: Option<Quota>,
+}
+
+/// Space on the system drive (`C:\` on Windows, `/` elsewhere), limited by
+/// the tightest quota that applies to it
+pub fn system_drive_space(settings: &QuotaSettings) -> Option<DriveSpace> {
+    let mut space = physical_drive_space()?;
+    let quotas = quota::for_volume(&space.mount, settings);
+    if let Some(tight) = quota::tightest(quotas, space.available) {
+        space.total = tight.limit;
+        space.available = tight.available();
+        space.quota = Some(tight);
+    }
+    Some(space)
+}
+
+/// Space on the system drive as the disk reports it
+fn physical_drive_space() -> Option<DriveSpace> {
+    use sysinfo::Disks;
+
+    let root = utils::get_root_disk_path();
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| root.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| DriveSpace {
+            mount: d.mount_point().to_path_buf(),
+            total: d.total_space(),
+            available: d.available_space(),
+            quota: None,
+        })
+}
+
+/// Dashboard banner text when the system drive is below the critical threshold
+pub fn banner(config: &Config) -> Option<String> {
+    let space = system_drive_space(&config.quota)?;
+    if !config.emergency.is_critical(space.total, space.available) {
+        return None;
+    }
+    let free = bytesize::to_string(space.available, false);
+    Some(match space.quota.as_ref().map(|q| q.kind) {
+        Some(QuotaKind::OneDrive) => format!(
+            "OneDrive almost full: {} left in your plan. Move or delete large files in the OneDrive folder.",
+            free
+        ),
+        Some(QuotaKind::Ntfs) => format!(
+            "Disk quota almost used: {} left for your account on {}. Run `wole emergency` for a one-step cleanup plan.",
+            free,
+            utils::display_path(&space.mount)
+        ),
+        None => format!(
+            "Disk almost full: {} free on {}. Run `wole emergency` for a one-step cleanup plan.",
+            free,
+            utils::display_path(&space.mount)
+        ),
+    })
+}
+
+/// One category in the emergency plan
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub category: &'static str,
+    pub result: CategoryResult,
+}
+
+impl PlanStep {
+    fn is_trash(&self) -> bool {
+        self.category == "Trash"
+    }
+}
+
+/// Scan the safe, fast categories and order them by estimated size. The
+/// Recycle Bin has no size estimate and goes last.
+pub fn build_plan(config: &Config) -> Vec<PlanStep> {
+    let root = utils::get_root_disk_path();
+    let quiet = OutputMode::Quiet;
+    let walk = &SizeWalk::default();
+    let scans: [(&'static str, anyhow::Result<CategoryResult>); 4] = [
+        ("Temp Files", categories::temp::scan(&root, config, walk)),
+        (
+            "Browser Cache",
+            categories::browser::scan(&root, config, walk),
+        ),
+        (
+            "Application Cache",
+            categories::app_cache::scan(&root, config, quiet, walk),
+        ),
+        (
+            "Package Cache",
+            categories::cache::scan(&root, config, quiet, walk),
+        ),
+    ];
+
+    let mut plan: Vec<PlanStep> = scans
+        .into_iter()
+        .filter_map(|(category, result)| result.ok().map(|result| PlanStep { category, result }))
+        .filter(|step| step.result.items > 0)
+        .collect();
+    plan.sort_by_key(|step| std::cmp::Reverse(step.result.size_bytes));
+
+    if let Ok(trash) = categories::trash::scan(0, walk) {
+        if trash.items > 0 {
+            plan.push(PlanStep {
+                category: "Trash",
+                result: trash,
+            });
+        }
+    }
+    plan
+}
+
+/// Outcome of running the plan
+#[derive(Debug, Clone, Default)]
+pub struct EmergencyOutcome {
+    pub deleted: usize,
+    pub errors: usize,
+    /// Free space gained on the system drive, measured before and after
+    pub freed_bytes: u64,
+    /// Where the deletion log was saved, if saving succeeded
+    pub log_path: Option<PathBuf>,
+}
+
+/// Delete everything in `plan` permanently and empty the Recycle Bin
+pub fn execute(plan: &[PlanStep]) -> EmergencyOutcome {
+    let before = physical_drive_space().map(|s| s.available);
+    let mut outcome = EmergencyOutcome::default();
+    let mut log = DeletionLog::new();
+
+    for step in plan {
+        if step.is_trash() {
+            match categories::trash::clean() {
+                Ok(()) => outcome.deleted += step.result.items,
+                Err(_) => outcome.errors += 1,
+            }
+            continue;
+        }
+
+        // Only file sizes are recorded; sizing folders would walk them again
+        let sizes: Vec<u64> = step
+            .result
+            .paths
+            .iter()
+            .map(|p| {
+                utils::safe_symlink_metadata(p)
+                    .ok()
+                    .filter(|m| m.is_file())
+                    .map(|m| m.len())
+                    .unwrap_or(0)
+            })
+            .collect();
+        let result = cleaner::clean_paths_batch(&step.result.paths, true);
+        outcome.deleted += result.success_count;
+        outcome.errors += result.error_count;
+        let deleted: HashSet<&PathBuf> = result.deleted_paths.iter().collect();
+        for (path, size) in step.result.paths.iter().zip(sizes) {
+            if deleted.contains(path) {
+                log.log_success(path, size, step.category, true);
+            }
+        }
+    }
+
+    let after = physical_drive_space().map(|s| s.available);
+    if let (Some(before), Some(after)) = (before, after) {
+        outcome.freed_bytes = after.saturating_sub(before);
+    }
+    // Saved last: with space freed the log write can't be what fills the disk
+    if !log.records.is_empty() {
+        outcome.log_path = log.save().ok();
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::EmergencySettings;
+
+    #[test]
+    fn critical_below_either_threshold() {
+        let settings = EmergencySettings::default();
+        let gb = 1024 * 1024 * 1024;
+        assert!(settings.is_critical(500 * gb, gb));
+        assert!(settings.is_critical(1000 * gb, 40 * gb));
+        assert!(!settings.is_critical(500 * gb, 100 * gb));
+    }
+}
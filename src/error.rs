@@ -0,0 +1,141 @@
+//! Errors returned by the library API
+//!
+//! The scan, clean and restore entry points return [`Result`], so callers can
+//! tell a locked file from a denied one or a cancelled scan without parsing
+//! messages. Internally most code still uses `anyhow`; its errors are
+//! classified into an [`Error`] variant where they leave the library, keeping
+//! the full context chain in the message.
+
+use crate::cleaner::PathChangedError;
+use std::io;
+use std::path::PathBuf;
+
+/// Windows "file in use" errors: sharing and lock violations
+const LOCKED_OS_ERRORS: [i32; 2] = [32, 33];
+
+/// Windows "access denied"
+const ACCESS_DENIED_OS_ERROR: i32 = 5;
+
+/// `Result` with the library's [`Error`]
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// What went wrong in a scan, clean or restore
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A file operation failed for a reason not covered below
+    #[error(transparent)]
+    Io(io::Error),
+
+    /// Access was denied; elevation or ownership may be needed
+    #[error("{0}")]
+    PermissionDenied(String),
+
+    /// A file is open in another process
+    #[error("{0}")]
+    Locked(String),
+
+    /// A path is a system or protected folder and is never deleted
+    #[error("Cannot delete protected path: {} ({reason})", path.display())]
+    Protected { path: PathBuf, reason: String },
+
+    /// A path was replaced by a link or another file since it was scanned
+    #[error(transparent)]
+    PathChanged(#[from] PathChangedError),
+
+    /// Nothing matched: no deletion history, session or Recycle Bin entry
+    #[error("{0}")]
+    NotFound(String),
+
+    /// The user or a cancel token stopped the operation
+    #[error("Cancelled")]
+    CancelledByUser,
+
+    /// A setting or pattern is invalid
+    #[error("{0}")]
+    Config(String),
+
+    /// Summary-only results carry no paths to clean
+    #[error("Summary-only results have no paths to clean; scan without --summary-only")]
+    SummaryOnly,
+
+    /// Anything else, with its full context
+    #[error("{0}")]
+    Other(String),
+}
+
+impl Error {
+    /// An error for `message` classified by the I/O error that caused it
+    pub(crate) fn io(message: String, err: &io::Error) -> Self {
+        match err.raw_os_error() {
+            Some(code) if cfg!(windows) && LOCKED_OS_ERRORS.contains(&code) => {
+                return Error::Locked(message)
+            }
+            Some(ACCESS_DENIED_OS_ERROR) if cfg!(windows) => {
+                return Error::PermissionDenied(message)
+            }
+            _ => {}
+        }
+        match err.kind() {
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied(message),
+            kind => Error::Io(io::Error::new(kind, message)),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::io(err.to_string(), &err)
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<Error>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+        if let Some(changed) = err.downcast_ref::<PathChangedError>() {
+            return Error::PathChanged(changed.clone());
+        }
+        let message = format!("{:#}", err);
+        match err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<io::Error>())
+        {
+            Some(io_err) => Error::io(message, io_err),
+            None => Error::Other(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn anyhow_errors_are_classified_by_their_cause() {
+        #[cfg(windows)]
+        {
+            let locked: anyhow::Error = io::Error::from_raw_os_error(32).into();
+            assert!(matches!(Error::from(locked), Error::Locked(_)));
+        }
+
+        let denied = Err::<(), _>(io::Error::from(io::ErrorKind::PermissionDenied))
+            .context("Failed to delete: C:\\x")
+            .unwrap_err();
+        match Error::from(denied) {
+            Error::PermissionDenied(message) => assert!(message.starts_with("Failed to delete")),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let cancelled = anyhow::Error::from(Error::CancelledByUser).context("Scanning cache");
+        assert!(matches!(Error::from(cancelled), Error::CancelledByUser));
+
+        assert!(matches!(
+            Error::from(anyhow::anyhow!("no such session")),
+            Error::Other(_)
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Quick estimates (`wole scan --quick`)
+//!
+//! A quick scan carries an `Estimate` in its `ScanOptions`; its folder sizes
+//! are extrapolated from a sample of each folder's subfolders instead of
+//! walking all of them. The
+//! sample is picked by hashing each subfolder's name with the scan's seed,
+//! so runs with the same seed walk the same folders and differences between
+//! their estimates reflect real changes on disk. Folders with only a few
+//! subfolders are always walked in full.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Share of subfolders walked
+const SAMPLE_RATE: f64 = 0.25;
+
+/// Folders with fewer subfolders than this are walked in full
+const MIN_SAMPLED_DIRS: usize = 8;
+
+/// How a quick estimate sampled the disk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampling {
+    pub seed: u64,
+    /// Share of subfolders actually walked, 0-100
+    pub coverage_percent: f64,
+}
+
+/// Seed for a run without `--seed`
+pub fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    nanos ^ ((std::process::id() as u64) << 32)
+}
+
+/// One quick estimate: its seed and how many subfolders its walks sampled
+#[derive(Debug, Default)]
+pub struct Estimate {
+    seed: u64,
+    dirs_seen: AtomicU64,
+    dirs_walked: AtomicU64,
+}
+
+impl Estimate {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::default()
+        }
+    }
+
+    /// Which of a folder's `subdirs` to walk: the ones whose names rank first
+    /// for the seed. Returns a keep-flag per subfolder and records the coverage.
+    pub(crate) fn sample(&self, subdirs: &[&Path]) -> Vec<bool> {
+        let keep = sample(self.seed, subdirs);
+        self.dirs_seen
+            .fetch_add(subdirs.len() as u64, Ordering::Relaxed);
+        self.dirs_walked.fetch_add(
+            keep.iter().filter(|k| **k).count() as u64,
+            Ordering::Relaxed,
+        );
+        keep
+    }
+
+    /// The coverage of the estimate so far
+    pub fn sampling(&self) -> Sampling {
+        let seen = self.dirs_seen.load(Ordering::Relaxed);
+        let walked = self.dirs_walked.load(Ordering::Relaxed);
+        Sampling {
+            seed: self.seed,
+            coverage_percent: if seen == 0 {
+                100.0
+            } else {
+                walked as f64 * 100.0 / seen as f64
+            },
+        }
+    }
+}
+
+fn rank(seed: u64, name: &str) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&seed.to_le_bytes());
+    hasher.update(name.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Keep-flags for `subdirs`: the ones whose names rank first for `seed`
+fn sample(seed: u64, subdirs: &[&Path]) -> Vec<bool> {
+    let total = subdirs.len();
+    let keep_count = if total < MIN_SAMPLED_DIRS {
+        total
+    } else {
+        ((total as f64 * SAMPLE_RATE).ceil() as usize).max(1)
+    };
+    let mut order: Vec<(usize, [u8; 32])> = subdirs
+        .iter()
+        .enumerate()
+        .map(|(i, dir)| {
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            (i, rank(seed, &name))
+        })
+        .collect();
+    order.sort_by_key(|&(_, rank)| rank);
+
+    let mut keep = vec![false; total];
+    for (i, _) in order.into_iter().take(keep_count) {
+        keep[i] = true;
+    }
+    keep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn same_seed_samples_same_folders() {
+        let dirs: Vec<PathBuf> = (0..20)
+            .map(|i| PathBuf::from(format!("/p/dir{}", i)))
+            .collect();
+        let refs: Vec<&Path> = dirs.iter().map(PathBuf::as_path).collect();
+
+        let first = sample(42, &refs);
+        assert_eq!(first, sample(42, &refs));
+        assert_eq!(first.iter().filter(|k| **k).count(), 5);
+        assert_ne!(first, sample(7, &refs));
+
+        // Small folders are walked in full
+        assert!(sample(42, &refs[..3]).iter().all(|k| *k));
+    }
+
+    #[test]
+    fn estimates_count_their_own_coverage() {
+        let dirs: Vec<PathBuf> = (0..20)
+            .map(|i| PathBuf::from(format!("/p/dir{}", i)))
+            .collect();
+        let refs: Vec<&Path> = dirs.iter().map(PathBuf::as_path).collect();
+
+        let quick = Estimate::new(42);
+        let other = Estimate::new(7);
+        assert_eq!(quick.sample(&refs), sample(42, &refs));
+        other.sample(&refs[..3]);
+
+        assert_eq!(quick.sampling().seed, 42);
+        assert_eq!(quick.sampling().coverage_percent, 25.0);
+        assert_eq!(other.sampling().coverage_percent, 100.0);
+        assert_eq!(Estimate::new(1).sampling().coverage_percent, 100.0);
+    }
+}
@@ -0,0 +1,431 @@
+//! Batched directory listings with file sizes
+//!
+//! Size calculations spend most of their time on one metadata call per file.
+//! Where the platform can return a whole directory's names, types and sizes
+//! in a few calls, [`list`] does so:
+//!
+//! - Windows (x64 and ARM64): `GetFileInformationByHandleEx` with
+//!   `FileFullDirectoryInfo`, 64 KB of entries per call
+//! - macOS (Intel and Apple Silicon): `getattrlistbulk`
+//!
+//! Support is detected at runtime. When a volume or filesystem rejects the
+//! batch call (some network shares and FAT volumes), that volume falls back
+//! to per-file metadata for the rest of the process. Other platforms, and
+//! runs with `WOLE_FAST_DIR=0` (handy for comparing timings), always fall
+//! back.
+//!
+//! To compare both paths on a machine (e.g. Windows on ARM64), run the
+//! ignored timing test in release mode:
+//! `cargo test --release --lib fast_dir -- --ignored --nocapture`.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// One entry of a directory listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: OsString,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    /// Logical size in bytes (0 for folders)
+    pub len: u64,
+}
+
+/// Why a batched listing wasn't produced
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+enum Failure {
+    /// The volume doesn't support batched listings; don't try it again
+    Unsupported,
+    /// This directory couldn't be read (access denied, vanished, ...)
+    Other,
+}
+
+fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("WOLE_FAST_DIR").map_or(true, |v| v != "0"))
+}
+
+fn unsupported_volumes() -> &'static Mutex<HashSet<String>> {
+    static UNSUPPORTED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    UNSUPPORTED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// List `dir` with sizes in batches, or `None` when the platform or volume
+/// has no fast path (callers then read metadata per file).
+/// `.` and `..` are never returned.
+pub fn list(dir: &Path) -> Option<Vec<Entry>> {
+    if !enabled() {
+        return None;
+    }
+    let volume = platform::volume_key(dir)?;
+    if unsupported_volumes()
+        .lock()
+        .is_ok_and(|set| set.contains(&volume))
+    {
+        return None;
+    }
+    match platform::list(dir) {
+        Ok(entries) => Some(entries),
+        Err(Failure::Unsupported) => {
+            if let Ok(mut set) = unsupported_volumes().lock() {
+                set.insert(volume);
+            }
+            None
+        }
+        Err(Failure::Other) => None,
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{Entry, Failure};
+    use std::ffi::{c_void, OsString};
+    use std::mem::offset_of;
+    use std::os::windows::ffi::OsStringExt;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::Path;
+    use windows::Win32::Foundation::{
+        ERROR_INVALID_FUNCTION, ERROR_INVALID_PARAMETER, ERROR_NOT_SUPPORTED, ERROR_NO_MORE_FILES,
+        HANDLE, WIN32_ERROR,
+    };
+    use windows::Win32::Storage::FileSystem::{
+        FileFullDirectoryInfo, GetFileInformationByHandleEx, FILE_ATTRIBUTE_DIRECTORY,
+        FILE_ATTRIBUTE_REPARSE_POINT, FILE_FLAG_BACKUP_SEMANTICS, FILE_FULL_DIR_INFO,
+        FILE_LIST_DIRECTORY, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+    };
+
+    // FILE_FULL_DIR_INFO field offsets
+    const NEXT_ENTRY_OFFSET: usize = offset_of!(FILE_FULL_DIR_INFO, NextEntryOffset);
+    const END_OF_FILE: usize = offset_of!(FILE_FULL_DIR_INFO, EndOfFile);
+    const FILE_ATTRIBUTES: usize = offset_of!(FILE_FULL_DIR_INFO, FileAttributes);
+    const FILE_NAME_LENGTH: usize = offset_of!(FILE_FULL_DIR_INFO, FileNameLength);
+    const FILE_NAME: usize = offset_of!(FILE_FULL_DIR_INFO, FileName);
+
+    pub(super) fn volume_key(dir: &Path) -> Option<String> {
+        use std::path::{Component, Prefix};
+        match dir.components().next()? {
+            Component::Prefix(prefix) => Some(match prefix.kind() {
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                    format!("{}:", (letter as char).to_ascii_uppercase())
+                }
+                _ => prefix.as_os_str().to_string_lossy().to_lowercase(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn read_u32(buf: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+    }
+
+    pub(super) fn list(dir: &Path) -> Result<Vec<Entry>, Failure> {
+        let handle = std::fs::OpenOptions::new()
+            .access_mode(FILE_LIST_DIRECTORY.0)
+            .share_mode((FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE).0)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+            .open(crate::utils::to_long_path(dir))
+            .map_err(|_| Failure::Other)?;
+
+        // u64 storage keeps the buffer 8-byte aligned, as the API requires
+        let mut storage = vec![0u64; 64 * 1024 / 8];
+        let mut entries = Vec::new();
+        loop {
+            // SAFETY: the handle is open for listing and `storage` is a
+            // writable buffer of the size passed.
+            let listed = unsafe {
+                GetFileInformationByHandleEx(
+                    HANDLE(handle.as_raw_handle()),
+                    FileFullDirectoryInfo,
+                    storage.as_mut_ptr() as *mut c_void,
+                    (storage.len() * 8) as u32,
+                )
+            };
+            if let Err(e) = listed {
+                return match WIN32_ERROR::from_error(&e) {
+                    Some(ERROR_NO_MORE_FILES) => Ok(entries),
+                    Some(
+                        ERROR_INVALID_FUNCTION | ERROR_NOT_SUPPORTED | ERROR_INVALID_PARAMETER,
+                    ) => Err(Failure::Unsupported),
+                    _ => Err(Failure::Other),
+                };
+            }
+
+            // SAFETY: plain bytes viewed over the u64 storage
+            let buf: &[u8] = unsafe {
+                std::slice::from_raw_parts(storage.as_ptr() as *const u8, storage.len() * 8)
+            };
+            let mut offset = 0usize;
+            loop {
+                if offset + FILE_NAME > buf.len() {
+                    return Err(Failure::Other);
+                }
+                let name_len = read_u32(buf, offset + FILE_NAME_LENGTH) as usize;
+                let name_start = offset + FILE_NAME;
+                if name_start + name_len > buf.len() {
+                    return Err(Failure::Other);
+                }
+                let wide: Vec<u16> = buf[name_start..name_start + name_len]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                let name = OsString::from_wide(&wide);
+                if name != "." && name != ".." {
+                    let attributes = read_u32(buf, offset + FILE_ATTRIBUTES);
+                    let is_dir = attributes & FILE_ATTRIBUTE_DIRECTORY.0 != 0;
+                    let len = u64::from_le_bytes(
+                        buf[offset + END_OF_FILE..offset + END_OF_FILE + 8]
+                            .try_into()
+                            .unwrap(),
+                    );
+                    entries.push(Entry {
+                        name,
+                        is_dir,
+                        is_symlink: attributes & FILE_ATTRIBUTE_REPARSE_POINT.0 != 0,
+                        len: if is_dir { 0 } else { len },
+                    });
+                }
+                let next = read_u32(buf, offset + NEXT_ENTRY_OFFSET) as usize;
+                if next == 0 {
+                    break;
+                }
+                offset += next;
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{Entry, Failure};
+    use std::ffi::{c_void, OsStr, OsString};
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    const ATTR_BIT_MAP_COUNT: u16 = 5;
+    const ATTR_CMN_NAME: u32 = 0x0000_0001;
+    const ATTR_CMN_OBJTYPE: u32 = 0x0000_0008;
+    const ATTR_CMN_ERROR: u32 = 0x2000_0000;
+    const ATTR_CMN_RETURNED_ATTRS: u32 = 0x8000_0000;
+    const ATTR_FILE_DATALENGTH: u32 = 0x0000_0200;
+    const VDIR: u32 = 2;
+    const VLNK: u32 = 5;
+    const EINVAL: i32 = 22;
+    const ENOTSUP: i32 = 45;
+
+    #[repr(C)]
+    struct AttrList {
+        bitmapcount: u16,
+        reserved: u16,
+        commonattr: u32,
+        volattr: u32,
+        dirattr: u32,
+        fileattr: u32,
+        forkattr: u32,
+    }
+
+    extern "C" {
+        fn getattrlistbulk(
+            dirfd: i32,
+            attr_list: *mut AttrList,
+            attr_buf: *mut c_void,
+            attr_buf_size: usize,
+            options: u64,
+        ) -> i32;
+    }
+
+    pub(super) fn volume_key(dir: &Path) -> Option<String> {
+        std::fs::metadata(dir).ok().map(|m| m.dev().to_string())
+    }
+
+    fn read_u32(buf: &[u8], at: usize) -> Option<u32> {
+        Some(u32::from_ne_bytes(buf.get(at..at + 4)?.try_into().ok()?))
+    }
+
+    pub(super) fn list(dir: &Path) -> Result<Vec<Entry>, Failure> {
+        let handle = std::fs::File::open(dir).map_err(|_| Failure::Other)?;
+        let mut attrs = AttrList {
+            bitmapcount: ATTR_BIT_MAP_COUNT,
+            reserved: 0,
+            commonattr: ATTR_CMN_RETURNED_ATTRS | ATTR_CMN_NAME | ATTR_CMN_ERROR | ATTR_CMN_OBJTYPE,
+            volattr: 0,
+            dirattr: 0,
+            fileattr: ATTR_FILE_DATALENGTH,
+            forkattr: 0,
+        };
+        let mut storage = vec![0u64; 64 * 1024 / 8];
+        let mut entries = Vec::new();
+        loop {
+            // SAFETY: the descriptor is an open directory and `storage` is a
+            // writable buffer of the size passed.
+            let count = unsafe {
+                getattrlistbulk(
+                    handle.as_raw_fd(),
+                    &mut attrs,
+                    storage.as_mut_ptr() as *mut c_void,
+                    storage.len() * 8,
+                    0,
+                )
+            };
+            if count == 0 {
+                return Ok(entries);
+            }
+            if count < 0 {
+                return match std::io::Error::last_os_error().raw_os_error() {
+                    Some(ENOTSUP | EINVAL) => Err(Failure::Unsupported),
+                    _ => Err(Failure::Other),
+                };
+            }
+
+            // SAFETY: plain bytes viewed over the u64 storage
+            let buf: &[u8] = unsafe {
+                std::slice::from_raw_parts(storage.as_ptr() as *const u8, storage.len() * 8)
+            };
+            let mut offset = 0usize;
+            for _ in 0..count {
+                let length = read_u32(buf, offset).ok_or(Failure::Other)? as usize;
+                // Returned attribute_set_t: common, vol, dir, file, fork
+                let returned_common = read_u32(buf, offset + 4).ok_or(Failure::Other)?;
+                let returned_file = read_u32(buf, offset + 16).ok_or(Failure::Other)?;
+                let mut field = offset + 24;
+
+                let mut error = 0;
+                if returned_common & ATTR_CMN_ERROR != 0 {
+                    error = read_u32(buf, field).ok_or(Failure::Other)?;
+                    field += 4;
+                }
+                let mut name = OsString::new();
+                if returned_common & ATTR_CMN_NAME != 0 {
+                    let data_offset = read_u32(buf, field).ok_or(Failure::Other)? as usize;
+                    let data_len = read_u32(buf, field + 4).ok_or(Failure::Other)? as usize;
+                    let start = field + data_offset;
+                    let bytes = buf
+                        .get(start..start + data_len)
+                        .ok_or(Failure::Other)?
+                        .split(|&b| b == 0)
+                        .next()
+                        .unwrap_or_default();
+                    name = OsStr::from_bytes(bytes).to_os_string();
+                    field += 8;
+                }
+                let mut obj_type = 0;
+                if returned_common & ATTR_CMN_OBJTYPE != 0 {
+                    obj_type = read_u32(buf, field).ok_or(Failure::Other)?;
+                    field += 4;
+                }
+                let mut len = 0;
+                if returned_file & ATTR_FILE_DATALENGTH != 0 {
+                    let bytes = buf.get(field..field + 8).ok_or(Failure::Other)?;
+                    len = u64::from_ne_bytes(bytes.try_into().map_err(|_| Failure::Other)?);
+                }
+
+                if error == 0 && !name.is_empty() {
+                    entries.push(Entry {
+                        name,
+                        is_dir: obj_type == VDIR,
+                        is_symlink: obj_type == VLNK,
+                        len: if obj_type == VDIR { 0 } else { len },
+                    });
+                }
+                offset += length;
+            }
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use super::{Entry, Failure};
+    use std::path::Path;
+
+    pub(super) fn volume_key(_dir: &Path) -> Option<String> {
+        None
+    }
+
+    pub(super) fn list(_dir: &Path) -> Result<Vec<Entry>, Failure> {
+        Err(Failure::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batched_listing_matches_per_file_metadata() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.bin"), vec![0u8; 1234]).unwrap();
+        std::fs::write(temp_dir.path().join("empty"), b"").unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+
+        // Platforms without a fast path fall back to per-file metadata
+        let Some(mut entries) = list(temp_dir.path()) else {
+            assert!(cfg!(not(any(windows, target_os = "macos"))) || !enabled());
+            return;
+        };
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        let expected = [
+            ("a.bin", false, 1234),
+            ("empty", false, 0),
+            ("sub", true, 0),
+        ];
+        assert_eq!(entries.len(), expected.len());
+        for (entry, (name, is_dir, len)) in entries.iter().zip(expected) {
+            assert_eq!(entry.name, name);
+            assert_eq!((entry.is_dir, entry.len), (is_dir, len));
+            assert!(!entry.is_symlink);
+        }
+    }
+
+    /// Timings of the batched listing against per-file metadata, for
+    /// checking the fast path pays off on a given machine
+    #[test]
+    #[ignore = "timing run; use --ignored --nocapture in release mode"]
+    fn batched_listing_timing() {
+        use std::time::Instant;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dirs: Vec<_> = (0..50)
+            .map(|i| temp_dir.path().join(i.to_string()))
+            .collect();
+        for dir in &dirs {
+            std::fs::create_dir(dir).unwrap();
+            for i in 0..400 {
+                std::fs::write(dir.join(format!("{}.bin", i)), vec![0u8; i]).unwrap();
+            }
+        }
+
+        let started = Instant::now();
+        let mut per_file = 0u64;
+        for dir in &dirs {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                per_file += entry.unwrap().metadata().unwrap().len();
+            }
+        }
+        let per_file_time = started.elapsed();
+
+        let started = Instant::now();
+        let mut batched = 0u64;
+        for dir in &dirs {
+            let Some(entries) = list(dir) else {
+                println!("no batched listing here; per-file: {:?}", per_file_time);
+                return;
+            };
+            batched += entries.iter().map(|e| e.len).sum::<u64>();
+        }
+        let batched_time = started.elapsed();
+
+        assert_eq!(batched, per_file);
+        println!(
+            "{} files on {}: per-file {:?}, batched {:?}",
+            dirs.len() * 400,
+            std::env::consts::ARCH,
+            per_file_time,
+            batched_time
+        );
+    }
+}
@@ -4,13 +4,85 @@
 //! - Audit trails
 //! - Undo information (path records)
 //! - Statistics tracking
+//!
+//! With `[history] compress` set, logs are written zstd-compressed
+//! (`cleanup_<time>.json.zst`); readers detect the format from the content,
+//! so plain logs and the gzip logs of older versions load the same way.
+//!
+//! Scans also append the reclaimable bytes of each category to
+//! `size_trend.jsonl`, a small time series behind `wole history --trend` and
+//! the dashboard sparkline.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
+/// First bytes of every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// First bytes of every gzip stream, as older versions compressed
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How stored history bytes are encoded, told apart by their first bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Plain,
+    Gzip,
+    Zstd,
+}
+
+impl Encoding {
+    pub fn of(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else if bytes.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// Whether stored history bytes are compressed, in either format
+pub fn is_compressed(bytes: &[u8]) -> bool {
+    Encoding::of(bytes) != Encoding::Plain
+}
+
+/// `text` as it's stored: zstd-compressed when `compress` is set
+pub fn encode_text(text: &str, compress: bool) -> std::io::Result<Vec<u8>> {
+    if !compress {
+        return Ok(text.as_bytes().to_vec());
+    }
+    zstd::encode_all(text.as_bytes(), 0)
+}
+
+/// Read a history file, decompressing it if it was stored compressed.
+/// Compressed files may hold several frames (or gzip members) one after
+/// another; when the last one was cut short by a crash, what came before it
+/// is returned.
+pub fn read_text(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    let mut text = Vec::new();
+    let decoded = match Encoding::of(&bytes) {
+        Encoding::Plain => {
+            return String::from_utf8(bytes)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        }
+        Encoding::Gzip => flate2::read::MultiGzDecoder::new(&bytes[..]).read_to_end(&mut text),
+        Encoding::Zstd => zstd::Decoder::with_buffer(&bytes[..])?.read_to_end(&mut text),
+    };
+    if let Err(e) = decoded {
+        if text.is_empty() {
+            return Err(e);
+        }
+    }
+    Ok(String::from_utf8_lossy(&text).into_owned())
+}
+
 /// Record of a single deletion operation
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DeletionRecord {
@@ -29,6 +101,92 @@ pub struct DeletionRecord {
     pub success: bool,
     /// Error message if deletion failed
     pub error: Option<String>,
+    /// OS error code (Win32 error or errno) if deletion failed with one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<i32>,
+    /// Classified failure reason, for retry policies and triage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<FailureReason>,
+    /// Archive the item was packed into before it was deleted (`clean --archive`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+}
+
+pub use wole_core::report::FailureReason;
+
+/// Details of a failed deletion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureInfo {
+    pub reason: FailureReason,
+    pub code: Option<i32>,
+    pub message: String,
+}
+
+impl FailureInfo {
+    /// Failure described only by a message (no OS error available)
+    pub fn from_message(message: &str) -> Self {
+        Self {
+            reason: FailureReason::from_message(message),
+            code: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn from_io_error(err: &std::io::Error) -> Self {
+        let code = err.raw_os_error();
+        let reason = match (code, err.kind()) {
+            (Some(code), _) => FailureReason::from_os_code(code),
+            (None, std::io::ErrorKind::NotFound) => FailureReason::NotFound,
+            (None, std::io::ErrorKind::PermissionDenied) => FailureReason::AccessDenied,
+            (None, _) => FailureReason::from_message(&err.to_string()),
+        };
+        Self {
+            reason,
+            code,
+            message: err.to_string(),
+        }
+    }
+
+    /// Use the first `io::Error` in the chain for code and reason, keeping the full message
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let message = format!("{:#}", err);
+        if err
+            .chain()
+            .any(|cause| cause.is::<crate::cleaner::PathChangedError>())
+        {
+            return Self {
+                reason: FailureReason::Changed,
+                code: None,
+                message,
+            };
+        }
+        if err.chain().any(|cause| {
+            matches!(
+                cause.downcast_ref::<crate::Error>(),
+                Some(crate::Error::Protected { .. })
+            )
+        }) {
+            return Self {
+                reason: FailureReason::Protected,
+                code: None,
+                message,
+            };
+        }
+        match err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<std::io::Error>())
+        {
+            Some(io_err) => Self {
+                message,
+                ..Self::from_io_error(io_err)
+            },
+            None => Self {
+                reason: FailureReason::from_message(&message),
+                code: None,
+                message,
+            },
+        }
+    }
 }
 
 impl DeletionRecord {
@@ -42,9 +200,18 @@ impl DeletionRecord {
             permanent,
             success: true,
             error: None,
+            error_code: None,
+            reason: None,
+            archive: None,
         }
     }
 
+    /// Whether restore can bring the item back: it went to the Recycle Bin
+    /// or quarantine, or was archived first
+    pub fn is_restorable(&self) -> bool {
+        self.success && (!self.permanent || self.archive.is_some())
+    }
+
     /// Create a new failed deletion record
     pub fn failure(
         path: &Path,
@@ -52,6 +219,23 @@ impl DeletionRecord {
         category: &str,
         permanent: bool,
         error: &str,
+    ) -> Self {
+        Self::failure_info(
+            path,
+            size_bytes,
+            category,
+            permanent,
+            &FailureInfo::from_message(error),
+        )
+    }
+
+    /// Create a failed deletion record carrying an error code and reason
+    pub fn failure_info(
+        path: &Path,
+        size_bytes: u64,
+        category: &str,
+        permanent: bool,
+        info: &FailureInfo,
     ) -> Self {
         Self {
             timestamp: Utc::now(),
@@ -60,13 +244,16 @@ impl DeletionRecord {
             category: category.to_string(),
             permanent,
             success: false,
-            error: Some(error.to_string()),
+            error: Some(info.message.clone()),
+            error_code: info.code,
+            reason: Some(info.reason),
+            archive: None,
         }
     }
 }
 
 /// Log of all deletions in a session
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DeletionLog {
     /// When this session started
     #[serde(with = "chrono::serde::ts_seconds")]
@@ -95,6 +282,8 @@ impl DeletionLog {
 
     /// Add a deletion record to the log
     pub fn add_record(&mut self, record: DeletionRecord) {
+        crate::json_stream::emit(&crate::json_stream::Event::Deletion(&record));
+        crate::progress::record_deletion(record.size_bytes, record.success);
         self.total_items += 1;
         if record.success {
             self.total_bytes_cleaned += record.size_bytes;
@@ -125,16 +314,92 @@ impl DeletionLog {
         ));
     }
 
-    /// Save the log to the history directory
+    /// Add a failed deletion with its code and classified reason
+    pub fn log_failure_info(
+        &mut self,
+        path: &Path,
+        size_bytes: u64,
+        category: &str,
+        permanent: bool,
+        info: &FailureInfo,
+    ) {
+        self.add_record(DeletionRecord::failure_info(
+            path, size_bytes, category, permanent, info,
+        ));
+    }
+
+    /// Add a failed deletion from the error that caused it
+    pub fn log_error(
+        &mut self,
+        path: &Path,
+        size_bytes: u64,
+        category: &str,
+        permanent: bool,
+        err: &anyhow::Error,
+    ) {
+        self.log_failure_info(
+            path,
+            size_bytes,
+            category,
+            permanent,
+            &FailureInfo::from_error(err),
+        );
+    }
+
+    /// Failed records
+    pub fn failures(&self) -> impl Iterator<Item = &DeletionRecord> {
+        self.records.iter().filter(|r| !r.success)
+    }
+
+    /// Try the failed deletions of this session again, the same way
+    /// (Recycle Bin or permanent) they were first attempted
+    ///
+    /// Returns a new session log with the outcome of each retry. Paths that
+    /// are gone by now, or have become protected, are left out of it.
+    pub fn retry_failures(&self) -> DeletionLog {
+        use crate::cleaner::DeleteOutcome;
+
+        let mut retry = DeletionLog::new();
+        let quarantine = crate::cleaner::quarantine::configured();
+        for record in self.failures() {
+            let path = Path::new(&record.path);
+            let (size, category, permanent) = (
+                record.size_bytes,
+                record.category.as_str(),
+                record.permanent,
+            );
+            match crate::cleaner::precheck_and_delete(path, permanent, quarantine.as_ref()) {
+                Ok(DeleteOutcome::Deleted) => retry.log_success(path, size, category, permanent),
+                Ok(DeleteOutcome::SkippedMissing | DeleteOutcome::SkippedSystem) => {}
+                Ok(DeleteOutcome::SkippedLocked) => retry.log_failure(
+                    path,
+                    size,
+                    category,
+                    permanent,
+                    "Path is locked by another process",
+                ),
+                Ok(DeleteOutcome::SkippedPermission) => {
+                    retry.log_failure(path, size, category, permanent, "Permission denied")
+                }
+                Err(e) => retry.log_error(path, size, category, permanent, &e),
+            }
+        }
+        retry
+    }
+
+    /// Save the log to the history directory and count it in the lifetime
+    /// statistics
     ///
     /// Returns the path to the saved log file
     pub fn save(&self) -> Result<PathBuf> {
         let history_dir = get_history_dir()?;
+        let compress = crate::config::Config::load().history.compress;
 
         // Create filename with timestamp
         let filename = format!(
-            "cleanup_{}.json",
-            self.session_start.format("%Y%m%d_%H%M%S")
+            "cleanup_{}.json{}",
+            self.session_start.format("%Y%m%d_%H%M%S"),
+            if compress { ".zst" } else { "" }
         );
         let log_path = history_dir.join(filename);
 
@@ -142,9 +407,12 @@ impl DeletionLog {
         let json =
             serde_json::to_string_pretty(self).context("Failed to serialize deletion log")?;
 
-        fs::write(&log_path, json)
+        fs::write(&log_path, encode_text(&json, compress)?)
             .with_context(|| format!("Failed to write deletion log to {}", log_path.display()))?;
 
+        // The lifetime totals are a convenience; the log itself is what matters
+        let _ = crate::stats::record_session(self);
+
         Ok(log_path)
     }
 
@@ -198,9 +466,12 @@ pub fn get_history_dir() -> Result<PathBuf> {
 
 /// List all history log files
 pub fn list_logs() -> Result<Vec<PathBuf>> {
-    let history_dir = get_history_dir()?;
+    list_logs_in(&get_history_dir()?)
+}
 
-    let mut logs: Vec<PathBuf> = fs::read_dir(&history_dir)
+/// Session logs in `history_dir`, newest first
+fn list_logs_in(history_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut logs: Vec<PathBuf> = fs::read_dir(history_dir)
         .with_context(|| {
             format!(
                 "Failed to read history directory: {}",
@@ -209,7 +480,8 @@ pub fn list_logs() -> Result<Vec<PathBuf>> {
         })?
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
-        .filter(|path| path.extension().map(|ext| ext == "json").unwrap_or(false))
+        // Other state kept here (e.g. the background deletion journal) isn't a session log
+        .filter(|path| is_session_log(path))
         .collect();
 
     // Sort by filename (which includes timestamp) in reverse order (newest first)
@@ -219,10 +491,103 @@ pub fn list_logs() -> Result<Vec<PathBuf>> {
     Ok(logs)
 }
 
+/// Whether `path` names a session log, compressed or not
+fn is_session_log(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    name.starts_with("cleanup_")
+        && [".json", ".json.gz", ".json.zst"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+}
+
+/// Session id of a history log: its file name without `cleanup_` and
+/// `.json[.zst|.gz]` (`20250114_093012`)
+pub fn session_id(log_path: &Path) -> String {
+    let name = log_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = name.strip_prefix("cleanup_").unwrap_or(&name);
+    let name = name
+        .strip_suffix(".zst")
+        .or_else(|| name.strip_suffix(".gz"))
+        .unwrap_or(name);
+    name.strip_suffix(".json").unwrap_or(name).to_string()
+}
+
+/// When the session in a log named `cleanup_<time>.json[.zst|.gz]` started
+fn session_time(path: &Path) -> Option<DateTime<Utc>> {
+    let name = path.file_name()?.to_str()?;
+    let stamp = name.strip_prefix("cleanup_")?.split('.').next()?;
+    chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M%S")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// What `compact_logs` did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactStats {
+    /// Logs deleted by the retention settings
+    pub removed: usize,
+    /// Plain logs rewritten compressed
+    pub compressed: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// Enforce `[history] max_entries` and `max_age_days` on the session logs in
+/// `history_dir` and, with `compress` set, rewrite the plain logs that are
+/// left compressed. Removed sessions can no longer be restored.
+pub fn compact_logs(
+    history_dir: &Path,
+    settings: &crate::config::HistorySettings,
+    now: DateTime<Utc>,
+) -> Result<CompactStats> {
+    let mut stats = CompactStats::default();
+    let size = |path: &Path| fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    for (index, path) in list_logs_in(history_dir)?.into_iter().enumerate() {
+        stats.bytes_before += size(&path);
+
+        let too_many = settings.max_entries > 0 && index as u64 >= settings.max_entries;
+        let too_old = settings.max_age_days > 0
+            && session_time(&path)
+                .is_some_and(|t| now - t > chrono::Duration::days(settings.max_age_days as i64));
+        if too_many || too_old {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            stats.removed += 1;
+            continue;
+        }
+
+        let bytes =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        if !settings.compress || is_compressed(&bytes) {
+            stats.bytes_after += bytes.len() as u64;
+            continue;
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        let compressed = encode_text(&text, true)?;
+        let mut target = path.clone().into_os_string();
+        target.push(".zst");
+        let target = PathBuf::from(target);
+        fs::write(&target, &compressed)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        stats.compressed += 1;
+        stats.bytes_after += compressed.len() as u64;
+    }
+
+    Ok(stats)
+}
+
 /// Load a deletion log from a file
 pub fn load_log(path: &Path) -> Result<DeletionLog> {
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read log file: {}", path.display()))?;
+    let content =
+        read_text(path).with_context(|| format!("Failed to read log file: {}", path.display()))?;
 
     let log: DeletionLog = serde_json::from_str(&content)
         .with_context(|| format!("Failed to parse log file: {}", path.display()))?;
@@ -230,11 +595,206 @@ pub fn load_log(path: &Path) -> Result<DeletionLog> {
     Ok(log)
 }
 
+const TREND_FILE: &str = "size_trend.jsonl";
+
+/// Samples kept in the size trend; older ones are dropped
+const MAX_TREND_SAMPLES: usize = 2000;
+
+/// Reclaimable bytes per category (CLI keys) found by one scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeSample {
+    pub time: DateTime<Utc>,
+    pub categories: BTreeMap<String, u64>,
+}
+
+impl SizeSample {
+    /// The `categories` (CLI keys) scanned in `results`
+    pub fn of(results: &crate::output::ScanResults, categories: &[&str]) -> Self {
+        Self {
+            time: Utc::now(),
+            categories: results
+                .by_category()
+                .into_iter()
+                .filter(|(key, _)| categories.contains(key))
+                .map(|(key, result)| (key.to_string(), result.size_bytes))
+                .collect(),
+        }
+    }
+}
+
+fn trend_path() -> Result<PathBuf> {
+    Ok(get_history_dir()?.join(TREND_FILE))
+}
+
+/// Append `sample` to the size trend
+pub fn record_sizes(sample: &SizeSample) -> Result<()> {
+    append_sample(&trend_path()?, sample, MAX_TREND_SAMPLES)
+}
+
+fn append_sample(path: &Path, sample: &SizeSample, max_samples: usize) -> Result<()> {
+    if sample.categories.is_empty() {
+        return Ok(());
+    }
+    let mut samples = read_samples(path);
+    samples.push(sample.clone());
+    let keep = samples.len().saturating_sub(max_samples);
+    let mut text = String::new();
+    for sample in &samples[keep..] {
+        text.push_str(&serde_json::to_string(sample)?);
+        text.push('\n');
+    }
+    fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every recorded sample, oldest first; unreadable lines are skipped
+pub fn load_trend() -> Result<Vec<SizeSample>> {
+    Ok(read_samples(&trend_path()?))
+}
+
+fn read_samples(path: &Path) -> Vec<SizeSample> {
+    let mut samples: Vec<SizeSample> = fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    samples.sort_by_key(|s| s.time);
+    samples
+}
+
+/// Total reclaimable bytes over the last `weeks` as `points` values, for
+/// sparklines; empty when nothing was recorded
+pub fn recent_totals(weeks: i64, points: usize) -> Vec<u64> {
+    let now = Utc::now();
+    let samples = load_trend().unwrap_or_default();
+    size_trend(&samples, now - chrono::Duration::weeks(weeks), now, points).totals
+}
+
+/// Reclaimable bytes at evenly spaced points in time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SizeTrend {
+    /// Each point, oldest first
+    pub times: Vec<DateTime<Utc>>,
+    /// All categories together at each point
+    pub totals: Vec<u64>,
+    /// Each category at each point (0 before it was first scanned)
+    pub categories: BTreeMap<String, Vec<u64>>,
+}
+
+/// `samples` as `points` values between `since` (or the first sample, if
+/// later) and `until`. Each point holds every category's latest size at that
+/// time, so scans of a few categories don't reset the others.
+pub fn size_trend(
+    samples: &[SizeSample],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    points: usize,
+) -> SizeTrend {
+    let Some(first) = samples.first() else {
+        return SizeTrend::default();
+    };
+    let start = since.max(first.time);
+    if start > until || points == 0 {
+        return SizeTrend::default();
+    }
+
+    let mut trend = SizeTrend::default();
+    let names: std::collections::BTreeSet<&String> =
+        samples.iter().flat_map(|s| s.categories.keys()).collect();
+    for i in 1..=points {
+        let at = start + (until - start) * i as i32 / points as i32;
+        let mut latest: BTreeMap<&String, u64> = BTreeMap::new();
+        for sample in samples.iter().take_while(|s| s.time <= at) {
+            latest.extend(sample.categories.iter().map(|(k, v)| (k, *v)));
+        }
+        trend.times.push(at);
+        trend.totals.push(latest.values().sum());
+        for name in &names {
+            trend
+                .categories
+                .entry((*name).clone())
+                .or_default()
+                .push(latest.get(name).copied().unwrap_or(0));
+        }
+    }
+    trend
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::Path;
 
+    #[test]
+    fn session_ids_ignore_compression() {
+        assert_eq!(
+            session_id(Path::new("h/cleanup_20250114_093012.json")),
+            "20250114_093012"
+        );
+        assert_eq!(
+            session_id(Path::new("h/cleanup_20250114_093012.json.gz")),
+            "20250114_093012"
+        );
+        assert_eq!(
+            session_id(Path::new("h/cleanup_20250114_093012.json.zst")),
+            "20250114_093012"
+        );
+    }
+
+    #[test]
+    fn plain_gzip_and_zstd_logs_load_alike() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let mut log = DeletionLog::new();
+        log.log_success(Path::new("/t/a.tmp"), 5, "temp", false);
+        let json = serde_json::to_string_pretty(&log).unwrap();
+
+        fs::write(dir.join("cleanup_20250101_000000.json"), &json).unwrap();
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(json.as_bytes()).unwrap();
+        fs::write(
+            dir.join("cleanup_20250102_000000.json.gz"),
+            gzip.finish().unwrap(),
+        )
+        .unwrap();
+        let zstd = encode_text(&json, true).unwrap();
+        assert_eq!(Encoding::of(&zstd), Encoding::Zstd);
+        fs::write(dir.join("cleanup_20250103_000000.json.zst"), zstd).unwrap();
+
+        let logs = list_logs_in(dir).unwrap();
+        assert_eq!(logs.len(), 3);
+        for path in logs {
+            let loaded = load_log(&path).unwrap();
+            assert_eq!(loaded.total_bytes_cleaned, 5);
+            assert_eq!(loaded.records[0].path, "/t/a.tmp");
+        }
+    }
+
+    #[test]
+    fn size_trend_carries_each_category_forward() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TREND_FILE);
+        let start = Utc::now() - chrono::Duration::days(4);
+        let sample = |days: i64, categories: &[(&str, u64)]| SizeSample {
+            time: start + chrono::Duration::days(days),
+            categories: categories
+                .iter()
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect(),
+        };
+        append_sample(&path, &sample(0, &[("cache", 100), ("temp", 10)]), 3).unwrap();
+        append_sample(&path, &sample(1, &[("cache", 300)]), 3).unwrap();
+        append_sample(&path, &sample(2, &[("temp", 40)]), 3).unwrap();
+        append_sample(&path, &sample(3, &[("cache", 500), ("temp", 50)]), 3).unwrap();
+        // Only the newest three samples are kept
+        let samples = read_samples(&path);
+        assert_eq!(samples.len(), 3);
+
+        let trend = size_trend(&samples, start, start + chrono::Duration::days(3), 3);
+        assert_eq!(trend.categories["cache"], [300, 300, 500]);
+        assert_eq!(trend.categories["temp"], [0, 40, 50]);
+        assert_eq!(trend.totals, [300, 340, 550]);
+    }
+
     #[test]
     fn test_deletion_record_success() {
         let record = DeletionRecord::success(Path::new("/test/file.txt"), 1024, "cache", false);
@@ -334,4 +894,102 @@ mod tests {
         assert!(summary.contains("1000 bytes"));
         assert!(summary.contains("1 errors"));
     }
+
+    #[test]
+    fn test_failure_classification() {
+        let not_found = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert_eq!(
+            FailureInfo::from_io_error(&not_found).reason,
+            FailureReason::NotFound
+        );
+
+        #[cfg(windows)]
+        let sharing = std::io::Error::from_raw_os_error(32);
+        #[cfg(not(windows))]
+        let sharing = std::io::Error::from_raw_os_error(16);
+        let err = anyhow::Error::new(sharing).context("Failed to delete: C:\\x.log");
+        let info = FailureInfo::from_error(&err);
+        assert_eq!(info.reason, FailureReason::Locked);
+        assert!(info.code.is_some());
+        assert!(info.message.starts_with("Failed to delete"));
+
+        assert_eq!(
+            FailureReason::from_message("Path is locked by another process"),
+            FailureReason::Locked
+        );
+    }
+
+    #[test]
+    fn test_failure_fields_round_trip() {
+        let mut log = DeletionLog::new();
+        log.log_failure_info(
+            Path::new("/test/deep"),
+            10,
+            "temp",
+            true,
+            &FailureInfo {
+                reason: FailureReason::PathTooLong,
+                code: Some(206),
+                message: "The filename or extension is too long".to_string(),
+            },
+        );
+        let json = serde_json::to_string(&log).unwrap();
+        assert!(json.contains("\"reason\":\"path-too-long\""));
+        assert!(json.contains("\"error_code\":206"));
+
+        // Logs written before these fields existed still load
+        let old = r#"{"timestamp":0,"path":"/a","size_bytes":1,"category":"temp","permanent":false,"success":false,"error":"x"}"#;
+        let record: DeletionRecord = serde_json::from_str(old).unwrap();
+        assert_eq!(record.reason, None);
+    }
+
+    #[test]
+    fn test_retry_failures() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let stuck = temp_dir.path().join("stuck.log");
+        std::fs::write(&stuck, b"data").unwrap();
+
+        let mut log = DeletionLog::new();
+        log.log_success(&temp_dir.path().join("gone.tmp"), 5, "temp", true);
+        log.log_failure(&stuck, 4, "temp", true, "Path is locked by another process");
+        log.log_failure(&temp_dir.path().join("vanished"), 1, "temp", true, "x");
+
+        let retry = log.retry_failures();
+        assert!(!stuck.exists());
+        assert_eq!(retry.records.len(), 1);
+        assert!(retry.records[0].success);
+        assert_eq!(retry.records[0].path, stuck.display().to_string());
+        assert_eq!(retry.total_bytes_cleaned, 4);
+    }
+
+    #[test]
+    fn test_compact_logs_enforces_retention_and_compresses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let now = Utc::now();
+        for days_ago in [1, 2, 3, 200] {
+            let mut log = DeletionLog::new();
+            log.session_start = now - chrono::Duration::days(days_ago);
+            log.log_success(Path::new("/t/a.tmp"), 5, "temp", false);
+            let name = format!("cleanup_{}.json", log.session_start.format("%Y%m%d_%H%M%S"));
+            fs::write(dir.join(name), serde_json::to_string_pretty(&log).unwrap()).unwrap();
+        }
+
+        let settings = crate::config::HistorySettings {
+            max_entries: 2,
+            max_age_days: 90,
+            compress: true,
+            ..Default::default()
+        };
+        let stats = compact_logs(dir, &settings, now).unwrap();
+        assert_eq!((stats.removed, stats.compressed), (2, 2));
+
+        let logs = list_logs_in(dir).unwrap();
+        assert_eq!(logs.len(), 2);
+        for path in logs {
+            assert!(path.to_string_lossy().ends_with(".json.zst"));
+            assert!(is_compressed(&fs::read(&path).unwrap()));
+            assert_eq!(load_log(&path).unwrap().total_bytes_cleaned, 5);
+        }
+    }
 }
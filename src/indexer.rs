@@ -0,0 +1,342 @@
+//! Idle-time background indexing
+//!
+//! `wole index --daemon` keeps the scan cache and the Disk Insights cache
+//! warm so interactive scans start from fresh data. It only does work while
+//! the machine is idle: CPU and disk throughput have to stay below the
+//! `[indexer]` thresholds for several consecutive samples, and the pass stops
+//! between steps as soon as the machine gets busy again. Nothing runs on
+//! battery. With `[notifications]` enabled, a pass that finds a lot of
+//! cleanable space ends with a desktop notification (see `notify`).
+
+use crate::app_data::{self, unix_now};
+use crate::cli::ScanOptions;
+use crate::config::{Config, IndexerSettings};
+use crate::disk_usage;
+use crate::notify::{self, ActionListener};
+use crate::output::OutputMode;
+use crate::scan_cache::ScanCache;
+use crate::scanner;
+use crate::tasks::CancelToken;
+use crate::utils;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessesToUpdate, System};
+
+/// One measurement of how busy the machine is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdleSample {
+    pub cpu_percent: f32,
+    /// Combined read+write throughput of all processes
+    pub disk_mb_per_sec: f64,
+    pub on_battery: bool,
+}
+
+impl IdleSample {
+    pub fn is_idle(&self, settings: &IndexerSettings) -> bool {
+        !self.on_battery
+            && self.cpu_percent < settings.idle_cpu_percent
+            && self.disk_mb_per_sec < settings.idle_disk_mb_per_sec
+    }
+}
+
+/// Counts consecutive idle samples so a single quiet moment doesn't start a pass
+#[derive(Debug, Default)]
+pub struct IdleTracker {
+    streak: u32,
+}
+
+impl IdleTracker {
+    /// Record a sample; returns true once `required` idle samples in a row were seen
+    pub fn observe(&mut self, idle: bool, required: u32) -> bool {
+        self.streak = if idle { self.streak + 1 } else { 0 };
+        self.streak >= required.max(1)
+    }
+}
+
+/// Measure CPU and disk activity over about a second
+pub fn sample(system: &mut System) -> IdleSample {
+    system.refresh_cpu_all();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    let started = Instant::now();
+    std::thread::sleep(Duration::from_secs(1));
+    system.refresh_cpu_all();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let bytes: u64 = system
+        .processes()
+        .values()
+        .map(|p| {
+            let usage = p.disk_usage();
+            usage.read_bytes + usage.written_bytes
+        })
+        .sum();
+    let secs = started.elapsed().as_secs_f64().max(0.001);
+    IdleSample {
+        cpu_percent: system.global_cpu_usage(),
+        disk_mb_per_sec: bytes as f64 / (1024.0 * 1024.0) / secs,
+        on_battery: crate::power::current().on_battery,
+    }
+}
+
+/// State file in the cache directory
+const STATE_FILE: &str = "indexer.json";
+
+/// What the last completed pass did, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexState {
+    /// Unix timestamp of the last completed pass
+    pub last_run: Option<u64>,
+    pub last_duration_secs: u64,
+    pub paths_indexed: Vec<PathBuf>,
+    /// Passes cut short because the machine became busy
+    pub interrupted_passes: u64,
+    /// Cleanable bytes per category (CLI keys) found by the last pass
+    #[serde(default)]
+    pub cleanable_bytes: BTreeMap<String, u64>,
+    /// Unix timestamp of the last cleanable-space notification
+    #[serde(default)]
+    pub last_notified: Option<u64>,
+}
+
+impl IndexState {
+    pub fn load() -> Self {
+        app_data::load(STATE_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        app_data::save(STATE_FILE, self)
+    }
+
+    /// Whether a new pass is due `refresh_hours` after the last one
+    pub fn is_due(&self, now: u64, refresh_hours: u64) -> bool {
+        match self.last_run {
+            Some(last) => now.saturating_sub(last) >= refresh_hours * 3600,
+            None => true,
+        }
+    }
+}
+
+/// Folders to pre-compute Disk Insights for
+pub fn index_paths(settings: &IndexerSettings) -> Vec<PathBuf> {
+    if !settings.paths.is_empty() {
+        return settings.paths.iter().map(PathBuf::from).collect();
+    }
+    let mut paths = Vec::new();
+    if let Some(dirs) = directories::UserDirs::new() {
+        paths.push(dirs.home_dir().to_path_buf());
+    }
+    paths.push(utils::get_root_disk_path());
+    paths
+}
+
+/// Categories refreshed in the scan cache: the ones interactive scans use
+/// most and that are cheap to keep current
+fn index_scan_options(config: &Config) -> ScanOptions {
+    ScanOptions {
+        cache: true,
+        app_cache: true,
+        temp: true,
+        trash: false,
+        build: true,
+        downloads: true,
+        large: true,
+        old: true,
+        applications: false,
+        browser: true,
+        system: false,
+        empty: false,
+        duplicates: false,
+        windows_update: false,
+        event_logs: false,
+        orphaned: false,
+        custom: false,
+        ide: false,
+        rust: false,
+        project_age_days: config.thresholds.project_age_days,
+        min_age_days: config.thresholds.min_age_days,
+        min_size_bytes: config.thresholds.min_size_mb * 1024 * 1024,
+        summary_only: false,
+        estimate: None,
+    }
+}
+
+/// How an index pass ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassOutcome {
+    Completed,
+    /// The machine became busy (or the pass was cancelled) before the named step
+    Interrupted(String),
+}
+
+/// Refresh the scan cache, then the Disk Insights cache for each path.
+/// `keep_going` is asked before every step; returning false stops the pass.
+pub fn run_pass(
+    config: &Config,
+    mut keep_going: impl FnMut() -> bool,
+    mut log: impl FnMut(&str),
+) -> Result<PassOutcome> {
+    let started = Instant::now();
+    let mut state = IndexState::load();
+
+    if !keep_going() {
+        state.interrupted_passes += 1;
+        state.save()?;
+        return Ok(PassOutcome::Interrupted("scan cache".to_string()));
+    }
+    log("Refreshing scan cache...");
+    let mut cache = ScanCache::open().context("Failed to open scan cache")?;
+    let root = directories::UserDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(utils::get_root_disk_path);
+    let results = scanner::scan_all(
+        &root,
+        index_scan_options(config),
+        OutputMode::Quiet,
+        config,
+        Some(&mut cache),
+    )?;
+    state.cleanable_bytes = results
+        .by_category()
+        .into_iter()
+        .filter(|(_, category)| category.size_bytes > 0)
+        .map(|(key, category)| (key.to_string(), category.size_bytes))
+        .collect();
+
+    let root_disk = utils::get_root_disk_path();
+    let mut indexed = Vec::new();
+    for path in index_paths(&config.indexer) {
+        if !keep_going() {
+            state.interrupted_passes += 1;
+            state.save()?;
+            return Ok(PassOutcome::Interrupted(utils::display_path(&path)));
+        }
+        log(&format!("Indexing {}...", utils::display_path(&path)));
+        let depth = if path == root_disk {
+            config.ui.scan_depth_entire_disk
+        } else {
+            config.ui.scan_depth_user
+        };
+        // A missing or unreadable folder shouldn't stop the rest of the pass
+        if disk_usage::scan_directory(&path, depth).is_ok() {
+            indexed.push(path);
+        }
+    }
+
+    state.last_run = Some(unix_now());
+    state.last_duration_secs = started.elapsed().as_secs();
+    state.paths_indexed = indexed;
+    state.save()?;
+    Ok(PassOutcome::Completed)
+}
+
+/// Notify about the last pass's cleanable space if it's worth it
+fn notify_cleanable(config: &Config, log: &mut impl FnMut(&str)) -> Result<()> {
+    let mut state = IndexState::load();
+    let mut last_notified = state.last_notified;
+    let notified = notify::notify_cleanable(
+        &config.notifications,
+        &state.cleanable_bytes,
+        &mut last_notified,
+        unix_now(),
+    )?;
+    if let Some(title) = notified {
+        log(&format!("Notified: {}", title));
+        state.last_notified = last_notified;
+        state.save()?;
+    }
+    Ok(())
+}
+
+/// Sample idleness every `check_interval_secs` and run a pass whenever one is
+/// due and the machine has been idle long enough. Returns when cancelled.
+pub fn run_daemon(config: &Config, cancel: &CancelToken, mut log: impl FnMut(&str)) -> Result<()> {
+    let settings = &config.indexer;
+    let mut system = System::new();
+    let mut tracker = IdleTracker::default();
+
+    // Notification buttons reach the daemon through the IPC endpoint
+    let listener = if config.notifications.enabled {
+        ActionListener::start()
+            .map_err(|e| log(&format!("Notification actions unavailable: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    while !cancel.is_cancelled() {
+        let idle = sample(&mut system).is_idle(settings);
+        let ready = tracker.observe(idle, settings.idle_samples);
+        if ready && IndexState::load().is_due(unix_now(), settings.refresh_hours) {
+            let keep_going =
+                || !cancel.is_cancelled() && sample(&mut System::new()).is_idle(settings);
+            match run_pass(config, keep_going, &mut log) {
+                Ok(PassOutcome::Completed) => {
+                    log("Index pass completed");
+                    if config.notifications.enabled {
+                        if let Err(e) = notify_cleanable(config, &mut log) {
+                            log(&format!("Notification failed: {}", e));
+                        }
+                    }
+                }
+                Ok(PassOutcome::Interrupted(step)) => {
+                    log(&format!("System busy, paused before {}", step));
+                    tracker = IdleTracker::default();
+                }
+                Err(e) => log(&format!("Index pass failed: {}", e)),
+            }
+        }
+
+        // Sleep in short slices so cancellation is noticed quickly
+        let wake = Instant::now() + Duration::from_secs(settings.check_interval_secs.max(1));
+        while Instant::now() < wake && !cancel.is_cancelled() {
+            if let Some(listener) = &listener {
+                listener.poll(config, &mut log);
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_requires_consecutive_quiet_samples() {
+        let settings = IndexerSettings::default();
+        let quiet = IdleSample {
+            cpu_percent: 3.0,
+            disk_mb_per_sec: 0.5,
+            on_battery: false,
+        };
+        let busy = IdleSample {
+            cpu_percent: 60.0,
+            ..quiet
+        };
+        let battery = IdleSample {
+            on_battery: true,
+            ..quiet
+        };
+        assert!(quiet.is_idle(&settings));
+        assert!(!busy.is_idle(&settings));
+        assert!(!battery.is_idle(&settings));
+
+        let mut tracker = IdleTracker::default();
+        assert!(!tracker.observe(true, 3));
+        assert!(!tracker.observe(true, 3));
+        assert!(!tracker.observe(false, 3));
+        assert!(!tracker.observe(true, 3));
+        assert!(!tracker.observe(true, 3));
+        assert!(tracker.observe(true, 3));
+
+        let mut state = IndexState::default();
+        assert!(state.is_due(10_000, 6));
+        state.last_run = Some(10_000);
+        assert!(!state.is_due(10_000 + 3600, 6));
+        assert!(state.is_due(10_000 + 6 * 3600, 6));
+    }
+}
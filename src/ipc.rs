@@ -0,0 +1,155 @@
+//! Local IPC with a running daemon
+//!
+//! The daemon listens on a loopback TCP port and writes the port plus a random
+//! token to `daemon.json` in the cache directory. A client (a notification
+//! action, for instance) reads that file, connects and sends one line,
+//! `<token> <command>`; the daemon answers `ok` or `error <reason>`. The token
+//! comes from OS randomness and `daemon.json` is readable only by the current
+//! user, so other local programs can't drive the daemon.
+
+use crate::app_data;
+use crate::tasks::{self, CancelToken, TaskKind};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Endpoint file in the cache directory
+const ENDPOINT_FILE: &str = "daemon.json";
+
+/// Where a running daemon can be reached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub port: u16,
+    pub token: String,
+    pub pid: u32,
+}
+
+impl Endpoint {
+    /// The endpoint of the running daemon, if it wrote one
+    pub fn load() -> Option<Self> {
+        app_data::load(ENDPOINT_FILE)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = app_data::cache_dir()?.join(ENDPOINT_FILE);
+        app_data::write_private(&path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+}
+
+/// A random token from the OS
+fn new_token() -> Result<String> {
+    app_data::random_hex(16)
+}
+
+/// Parse a request line into its command if the token matches
+fn parse_request<'a>(line: &'a str, token: &str) -> Option<&'a str> {
+    let (sent, command) = line.trim().split_once(' ')?;
+    (app_data::tokens_match(token, sent) && !command.is_empty()).then_some(command)
+}
+
+/// Listens for commands until dropped; removes `daemon.json` on drop
+pub struct Server {
+    pub endpoint: Endpoint,
+    cancel: CancelToken,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+        if Endpoint::load().as_ref() == Some(&self.endpoint) {
+            app_data::remove(ENDPOINT_FILE);
+        }
+    }
+}
+
+/// Start listening on a loopback port. Commands arrive on the returned channel.
+pub fn serve() -> Result<(Server, Receiver<String>)> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .context("Failed to open a local port for the daemon")?;
+    listener.set_nonblocking(true)?;
+    let endpoint = Endpoint {
+        port: listener.local_addr()?.port(),
+        token: new_token()?,
+        pid: std::process::id(),
+    };
+    endpoint.save()?;
+
+    let (tx, rx) = mpsc::channel();
+    let token = endpoint.token.clone();
+    let cancel = tasks::spawn("daemon-ipc", TaskKind::Worker, move |cancel| {
+        while !cancel.is_cancelled() {
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+                Err(_) => continue,
+            };
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_read_timeout(Some(CONNECT_TIMEOUT));
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            if reader.read_line(&mut line).is_err() {
+                continue;
+            }
+            let reply = match parse_request(&line, &token) {
+                Some(command) if tx.send(command.to_string()).is_ok() => "ok\n",
+                Some(_) => "error daemon is stopping\n",
+                None => "error bad token\n",
+            };
+            let _ = (&stream).write_all(reply.as_bytes());
+        }
+    });
+    Ok((Server { endpoint, cancel }, rx))
+}
+
+/// Send `command` to the running daemon. Ok(false) when no daemon is listening.
+pub fn send(command: &str) -> Result<bool> {
+    let Some(endpoint) = Endpoint::load() else {
+        return Ok(false);
+    };
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, endpoint.port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) else {
+        // Left behind by a daemon that didn't exit cleanly
+        return Ok(false);
+    };
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    writeln!(stream, "{} {}", endpoint.token, command)?;
+    let mut reply = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut reply)
+        .context("The daemon didn't answer")?;
+    match reply.trim() {
+        "ok" => Ok(true),
+        other => anyhow::bail!(
+            "The daemon refused the request: {}",
+            other.trim_start_matches("error ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_need_the_token() {
+        let token = new_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, new_token().unwrap());
+
+        assert_eq!(
+            parse_request(&format!("{} clean\n", token), &token),
+            Some("clean")
+        );
+        assert_eq!(parse_request("0123 clean", &token), None);
+        assert_eq!(parse_request(&token, &token), None);
+        assert_eq!(parse_request(&format!("{} ", token), &token), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! `--json-stream`: newline-delimited JSON events on stdout
+//!
+//! `wole scan --json-stream` and `wole clean --json-stream` print one JSON
+//! object per line as things happen, so wrappers can follow a long scan
+//! instead of waiting for the final `--json` document. Every object has an
+//! `event` field:
+//!
+//! - `category_started` `{category}`
+//! - `item_found` `{category, path}`, sent when the item's category finishes
+//! - `category_finished` `{category, items, size_bytes}`
+//! - `error` `{category?, message}`
+//! - `scan_finished` `{total_items, total_bytes}`
+//! - `deletion` - one deletion history record (`path`, `success`, `reason`, ...)
+//! - `clean_finished` `{cleaned, cleaned_bytes, errors}`
+//!
+//! Categories are named by their CLI key (`cache`, `app_cache`, ...).
+
+use crate::history::DeletionRecord;
+use crate::output::ScanResults;
+use crate::scanner::ScanEvent;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// One line of the stream
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    CategoryStarted {
+        category: &'a str,
+    },
+    ItemFound {
+        category: &'a str,
+        path: &'a Path,
+    },
+    CategoryFinished {
+        category: &'a str,
+        items: usize,
+        size_bytes: u64,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        category: Option<&'a str>,
+        message: &'a str,
+    },
+    ScanFinished {
+        total_items: usize,
+        total_bytes: u64,
+    },
+    Deletion(&'a DeletionRecord),
+    CleanFinished {
+        cleaned: usize,
+        cleaned_bytes: u64,
+        errors: usize,
+    },
+}
+
+impl<'a> Event<'a> {
+    /// The stream event for a scan event; the final results have none
+    /// (see `scan_finished`)
+    pub fn from_scan(event: &'a ScanEvent) -> Option<Self> {
+        Some(match event {
+            ScanEvent::CategoryStarted { category } => Self::CategoryStarted { category },
+            ScanEvent::ItemFound { category, path } => Self::ItemFound { category, path },
+            ScanEvent::CategoryFinished {
+                category,
+                items,
+                size_bytes,
+            } => Self::CategoryFinished {
+                category,
+                items: *items,
+                size_bytes: *size_bytes,
+            },
+            ScanEvent::Error { category, message } => Self::Error {
+                category: *category,
+                message,
+            },
+            ScanEvent::Finished(_) => return None,
+        })
+    }
+
+    /// Totals of a finished scan
+    pub fn scan_finished(results: &ScanResults) -> Self {
+        let (total_items, total_bytes) = results
+            .by_category()
+            .iter()
+            .fold((0, 0), |(items, bytes), (_, r)| {
+                (items + r.items, bytes + r.size_bytes)
+            });
+        Self::ScanFinished {
+            total_items,
+            total_bytes,
+        }
+    }
+}
+
+/// Print events from now on
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Print `event` as one line, if the stream is enabled
+pub fn emit(event: &Event) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(line) = serde_json::to_string(event) {
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", line);
+        let _ = stdout.flush();
+    }
+}
+
+/// `emit` for a scan event, for `scanner::scan_with_events`
+pub fn emit_scan(event: ScanEvent) {
+    if let Some(event) = Event::from_scan(&event) {
+        emit(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::DeletionRecord;
+
+    #[test]
+    fn events_are_tagged_json_objects() {
+        let found = ScanEvent::ItemFound {
+            category: "build",
+            path: "p/node_modules".into(),
+        };
+        let line = serde_json::to_value(Event::from_scan(&found).unwrap()).unwrap();
+        assert_eq!(
+            line,
+            serde_json::json!({"event": "item_found", "category": "build", "path": "p/node_modules"})
+        );
+        assert!(Event::from_scan(&ScanEvent::Finished(Default::default())).is_none());
+
+        let record = DeletionRecord::success(Path::new("p/dist"), 10, "build", false);
+        let line = serde_json::to_value(Event::Deletion(&record)).unwrap();
+        assert_eq!(line["event"], "deletion");
+        assert_eq!(line["path"], "p/dist");
+        assert_eq!(line["success"], true);
+    }
+}
@@ -2,31 +2,61 @@
 //!
 //! This crate provides both a CLI binary and a library API for programmatic use
 
+pub mod ads;
 pub mod analyzer;
+mod app_data;
 pub mod categories;
 pub mod cleaner;
 pub mod cli;
+pub mod clones;
 pub mod config;
 pub mod debug_log;
 pub mod disk_usage;
 mod disk_usage_cache;
+pub mod elevation;
+pub mod emergency;
+pub mod error;
+pub mod estimate;
+pub mod fast_dir;
 pub mod git;
 pub mod history;
+pub mod indexer;
+pub mod ipc;
+pub mod json_stream;
+pub mod mft;
+pub mod notify;
+pub mod open_files;
 pub mod optimize;
 pub mod output;
+pub mod plan;
+pub mod power;
 pub mod progress;
 pub mod project;
+pub mod quota;
+pub mod recording;
+pub mod recreate_cost;
+pub mod report;
 pub mod restore;
+pub mod rules_import;
 pub mod scan_cache;
+pub mod scan_diff;
 pub mod scan_events;
 pub mod scanner;
+pub mod scheduler;
+pub mod selection;
 pub mod size;
 pub mod spinner;
+pub mod stats;
 pub mod status;
+pub mod tasks;
 pub mod theme;
 pub mod tui;
 pub mod uninstall;
 pub mod update;
+pub mod usage_history;
 pub mod utils;
+pub mod volumes;
 
 pub(crate) mod trash_ops;
+
+pub use error::{Error, Result};
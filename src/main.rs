@@ -11,9 +11,31 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Elevated relaunch from the TUI: resume with the handed-over state
+    if args.len() == 3 && args[1] == wole::elevation::RESUME_ARG {
+        wole::tui::resume_elevated(std::path::Path::new(&args[2]))?;
+        return Ok(());
+    }
+
+    // Elevated child of `wole clean --elevate`: run the handed-over steps
+    if args.len() == 3 && args[1] == wole::cleaner::elevated::CLEAN_ARG {
+        return wole::cleaner::elevated::run_request(std::path::Path::new(&args[2]));
+    }
+
+    // A button on a daemon notification (the `wole:` protocol handler)
+    if args.len() == 3 && args[1] == wole::notify::ACTIVATE_ARG {
+        let result = wole::notify::handle_activation(&args[2]);
+        wole::tasks::shutdown();
+        return result;
+    }
+
     let cli = Cli::parse();
 
     // If command is None, it means --version or --help was used, or no command
     // Clap handles --version and --help automatically, so we just run
-    cli.run()
+    let result = cli.run();
+
+    // Scans leave cache writes running in the background; let them finish
+    wole::tasks::shutdown();
+    result
 }
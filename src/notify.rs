@@ -0,0 +1,607 @@
+//! Desktop notifications from the daemon
+//!
+//! After an index pass finds more cleanable space than
+//! `[notifications] min_cleanable_gb`, the daemon shows a notification. On
+//! Windows it's an Action Center toast with two buttons that launch the
+//! `wole:` URL protocol: "Clean now" asks the running daemon (over `ipc`) to
+//! clean the categories pre-approved in `clean_categories`, "Review" opens the
+//! TUI on a fresh scan. Elsewhere the notification is informational.
+//!
+//! Any web page or email can open a `wole:` link, so "Clean now" only cleans
+//! when its URL carries the random token issued for the toast. The token is
+//! kept in a file only the user can read, expires after a day and works once.
+//! Both daemons (`wole daemon` and `wole index --daemon`) notify this way.
+//!
+//! When `wole clean` finishes, `after_clean` shows a summary notification and
+//! `webhook_url` receives the same summary as JSON (generic, Slack or Teams
+//! shaped), for scheduled cleans on build agents.
+
+use crate::cli::ScanOptions;
+use crate::config::{Config, NotificationSettings};
+use crate::history::DeletionLog;
+use crate::output::OutputMode;
+use crate::{app_data, cleaner, ipc, scanner, utils};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Command;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+/// How long a webhook may take to answer
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hidden argument the `wole:` protocol handler is registered with
+pub const ACTIVATE_ARG: &str = "--notify-action";
+
+/// Token file in the cache directory
+const TOKEN_FILE: &str = "notify_token.json";
+
+/// How long a toast's "Clean now" button keeps working
+const TOKEN_LIFETIME_SECS: u64 = 24 * 3600;
+
+/// What a notification button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyAction {
+    /// Clean the pre-approved categories
+    CleanNow,
+    /// Open the TUI and scan
+    Review,
+}
+
+impl NotifyAction {
+    /// Command name, also used in the `wole:` URL and over IPC
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotifyAction::CleanNow => "clean",
+            NotifyAction::Review => "review",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotifyAction::CleanNow => "Clean now",
+            NotifyAction::Review => "Review",
+        }
+    }
+
+    /// `clean` or `review`, in any case
+    pub fn parse(command: &str) -> Option<Self> {
+        match command.trim().to_lowercase().as_str() {
+            "clean" => Some(NotifyAction::CleanNow),
+            "review" => Some(NotifyAction::Review),
+            _ => None,
+        }
+    }
+}
+
+/// A `wole:` URL: the action and, for "Clean now", the toast's token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activation {
+    pub action: NotifyAction,
+    pub token: Option<String>,
+}
+
+impl Activation {
+    pub fn review() -> Self {
+        Self {
+            action: NotifyAction::Review,
+            token: None,
+        }
+    }
+
+    pub fn clean_now(token: String) -> Self {
+        Self {
+            action: NotifyAction::CleanNow,
+            token: Some(token),
+        }
+    }
+
+    /// `wole:clean/<token>`, `wole://review/` or plain `review`
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let rest = match url.get(..5) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("wole:") => &url[5..],
+            _ => url,
+        };
+        let mut parts = rest.trim_matches('/').splitn(2, '/');
+        let action = NotifyAction::parse(parts.next()?)?;
+        let token = parts
+            .next()
+            .map(|t| t.trim_matches('/'))
+            .filter(|t| !t.is_empty())
+            .map(String::from);
+        Some(Self { action, token })
+    }
+
+    pub fn url(&self) -> String {
+        match &self.token {
+            Some(token) => format!("wole:{}/{}", self.action.as_str(), token),
+            None => format!("wole:{}", self.action.as_str()),
+        }
+    }
+}
+
+/// The token the last "Clean now" toast was issued with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionToken {
+    pub token: String,
+    /// Unix timestamp after which the token no longer works
+    pub expires: u64,
+}
+
+impl ActionToken {
+    /// Whether `token` is this one and hasn't expired
+    pub fn accepts(&self, token: &str, now: u64) -> bool {
+        app_data::tokens_match(&self.token, token) && now < self.expires
+    }
+}
+
+/// Create the token for a new "Clean now" toast, replacing any earlier one
+fn issue_token(now: u64) -> Result<String> {
+    let issued = ActionToken {
+        token: app_data::random_hex(16)?,
+        expires: now + TOKEN_LIFETIME_SECS,
+    };
+    let path = app_data::cache_dir()?.join(TOKEN_FILE);
+    app_data::write_private(&path, serde_json::to_string(&issued)?.as_bytes())?;
+    Ok(issued.token)
+}
+
+/// Check `token` against the issued one and use it up
+fn redeem_token(token: Option<&str>, now: u64) -> Result<()> {
+    let token = token.context("This link has no notification token; open wole to clean")?;
+    let issued: Option<ActionToken> = app_data::load(TOKEN_FILE);
+    if !issued.is_some_and(|issued| issued.accepts(token, now)) {
+        bail!("This notification has expired or was already used; open wole to clean");
+    }
+    app_data::remove(TOKEN_FILE);
+    Ok(())
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Toast XML; clicking the toast body reviews, buttons run their action
+pub fn toast_xml(title: &str, body: &str, actions: &[Activation]) -> String {
+    let buttons: String = actions
+        .iter()
+        .map(|a| {
+            format!(
+                r#"<action content="{}" activationType="protocol" arguments="{}"/>"#,
+                xml_escape(a.action.label()),
+                xml_escape(&a.url())
+            )
+        })
+        .collect();
+    let actions_xml = if buttons.is_empty() {
+        String::new()
+    } else {
+        format!("<actions>{}</actions>", buttons)
+    };
+    format!(
+        r#"<toast activationType="protocol" launch="{}"><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual>{}</toast>"#,
+        Activation::review().url(),
+        xml_escape(title),
+        xml_escape(body),
+        actions_xml
+    )
+}
+
+/// Show a notification. Actions are only offered where the platform supports them.
+#[cfg(windows)]
+pub fn send(title: &str, body: &str, actions: &[Activation]) -> Result<()> {
+    // Unpackaged apps have no app ID of their own; PowerShell's is always registered
+    const APP_ID: &str =
+        r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+    let xml = toast_xml(title, body, actions).replace('\'', "''");
+    let script = format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+         [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null; \
+         $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; $xml.LoadXml('{}'); \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+        xml, APP_ID
+    );
+    run_quietly(Command::new("powershell").args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        script.as_str(),
+    ]))
+}
+
+#[cfg(target_os = "macos")]
+pub fn send(title: &str, body: &str, _actions: &[Activation]) -> Result<()> {
+    let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!(
+        "display notification \"{}\" with title \"{}\"",
+        quote(body),
+        quote(title)
+    );
+    run_quietly(Command::new("osascript").args(["-e", script.as_str()]))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn send(title: &str, body: &str, _actions: &[Activation]) -> Result<()> {
+    run_quietly(Command::new("notify-send").args(["--app-name=wole", title, body]))
+}
+
+fn run_quietly(command: &mut Command) -> Result<()> {
+    let output = command.output().context("Failed to show notification")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to show notification: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Register the `wole:` URL protocol for the current user so toast buttons
+/// launch this executable
+#[cfg(windows)]
+pub fn register_protocol() -> Result<()> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let exe = std::env::current_exe().context("Failed to locate wole executable")?;
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let (key, _) = hkcu
+        .create_subkey(r"Software\Classes\wole")
+        .context("Failed to register the wole: protocol")?;
+    key.set_value("", &"URL:wole")?;
+    key.set_value("URL Protocol", &"")?;
+    let (command, _) = key.create_subkey(r"shell\open\command")?;
+    command.set_value(
+        "",
+        &format!("\"{}\" {} \"%1\"", exe.display(), ACTIVATE_ARG),
+    )?;
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn register_protocol() -> Result<()> {
+    Ok(())
+}
+
+/// Scan and clean the pre-approved categories without prompting. Items go to
+/// the Recycle Bin. Returns the bytes freed.
+pub fn clean_approved(config: &Config) -> Result<u64> {
+    let root = directories::UserDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(utils::get_root_disk_path);
+    let results = scanner::scan_all(
+        &root,
+        ScanOptions::from_keys(&config.notifications.clean_categories, config),
+        OutputMode::Quiet,
+        config,
+        None,
+    )?;
+    let log = cleaner::clean_all(&results, true, OutputMode::Quiet, false, false, false)?;
+    Ok(log.map(|l| l.total_bytes_cleaned).unwrap_or(0))
+}
+
+/// Run an activated action, then report the result as a notification.
+/// "Clean now" first redeems the toast's token and refuses without it.
+pub fn run_action(activation: &Activation, config: &Config) -> Result<()> {
+    match activation.action {
+        NotifyAction::CleanNow => {
+            redeem_token(activation.token.as_deref(), app_data::unix_now())?;
+            let freed = clean_approved(config)?;
+            send(
+                "wole cleanup finished",
+                &format!(
+                    "Moved {} to the Recycle Bin",
+                    bytesize::to_string(freed, false)
+                ),
+                &[],
+            )
+        }
+        NotifyAction::Review => crate::tui::review(),
+    }
+}
+
+/// Cleanable bytes in total and in the `approved` categories
+pub fn cleanable_totals(cleanable: &BTreeMap<String, u64>, approved: &[String]) -> (u64, u64) {
+    let total = cleanable.values().sum();
+    let approved = cleanable
+        .iter()
+        .filter(|(key, _)| approved.iter().any(|a| a.trim().eq_ignore_ascii_case(key)))
+        .map(|(_, bytes)| bytes)
+        .sum();
+    (total, approved)
+}
+
+/// Notify about `cleanable` bytes per category if they cross the threshold
+/// and `last_notified` is old enough. Updates `last_notified` and returns the
+/// notification title when one was shown.
+pub fn notify_cleanable(
+    settings: &NotificationSettings,
+    cleanable: &BTreeMap<String, u64>,
+    last_notified: &mut Option<u64>,
+    now: u64,
+) -> Result<Option<String>> {
+    let (total, approved) = cleanable_totals(cleanable, &settings.clean_categories);
+    let recently = last_notified
+        .is_some_and(|last| now.saturating_sub(last) < settings.min_interval_hours * 3600);
+    if total < settings.min_cleanable_gb * 1_000_000_000 || recently {
+        return Ok(None);
+    }
+
+    let title = format!("{} cleanable", bytesize::to_string(total, false));
+    let (body, actions) = if approved > 0 {
+        (
+            format!(
+                "Clean now moves {} from {} to the Recycle Bin.",
+                bytesize::to_string(approved, false),
+                settings.clean_categories.join(", ")
+            ),
+            vec![
+                Activation::clean_now(issue_token(now)?),
+                Activation::review(),
+            ],
+        )
+    } else {
+        (
+            "Open wole to review what can go.".to_string(),
+            vec![Activation::review()],
+        )
+    };
+    send(&title, &body, &actions)?;
+    *last_notified = Some(now);
+    Ok(Some(title))
+}
+
+/// What a finished `wole clean` did, as sent to `webhook_url`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CleanSummary {
+    pub host: String,
+    pub started: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub cleaned: usize,
+    pub cleaned_bytes: u64,
+    pub errors: usize,
+    /// Bytes cleaned per category
+    pub categories: BTreeMap<String, u64>,
+}
+
+impl CleanSummary {
+    pub fn of(log: &DeletionLog) -> Self {
+        let mut categories = BTreeMap::new();
+        for record in log.records.iter().filter(|r| r.success) {
+            *categories.entry(record.category.clone()).or_insert(0) += record.size_bytes;
+        }
+        Self {
+            host: sysinfo::System::host_name().unwrap_or_default(),
+            started: log.session_start,
+            duration_secs: (Utc::now() - log.session_start).num_seconds().max(0) as u64,
+            cleaned: log.total_items - log.errors,
+            cleaned_bytes: log.total_bytes_cleaned,
+            errors: log.errors,
+            categories,
+        }
+    }
+
+    /// One-line summary for chat messages and notifications
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "wole clean on {}: {} freed, {} items cleaned",
+            if self.host.is_empty() {
+                "this machine"
+            } else {
+                &self.host
+            },
+            bytesize::to_string(self.cleaned_bytes, false),
+            self.cleaned
+        );
+        if self.errors > 0 {
+            text.push_str(&format!(", {} failed", self.errors));
+        }
+        text
+    }
+}
+
+/// JSON body for a webhook in `format` ("generic", "slack" or "teams")
+pub fn webhook_payload(summary: &CleanSummary, format: &str) -> Result<serde_json::Value> {
+    Ok(match format.trim().to_lowercase().as_str() {
+        "generic" | "" => serde_json::json!({
+            "event": "clean_finished",
+            "text": summary.text(),
+            "summary": summary,
+        }),
+        "slack" => serde_json::json!({ "text": summary.text() }),
+        "teams" => serde_json::json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": "wole clean finished",
+            "text": summary.text(),
+        }),
+        other => bail!(
+            "Unknown [notifications] webhook_format '{}' (expected generic, slack or teams)",
+            other
+        ),
+    })
+}
+
+/// Tell `[notifications]` that a clean finished: a desktop notification with
+/// `after_clean`, a POST to `webhook_url` when set. Both are attempted; the
+/// first failure is returned.
+pub fn notify_clean_finished(settings: &NotificationSettings, log: &DeletionLog) -> Result<()> {
+    let summary = CleanSummary::of(log);
+    let shown = if settings.after_clean {
+        send(
+            &format!(
+                "{} freed",
+                bytesize::to_string(summary.cleaned_bytes, false)
+            ),
+            &summary.text(),
+            &[],
+        )
+    } else {
+        Ok(())
+    };
+    let posted = if settings.webhook_url.trim().is_empty() {
+        Ok(())
+    } else {
+        post_webhook(
+            settings.webhook_url.trim(),
+            &summary,
+            &settings.webhook_format,
+        )
+    };
+    shown.and(posted)
+}
+
+fn post_webhook(url: &str, summary: &CleanSummary, format: &str) -> Result<()> {
+    let payload = webhook_payload(summary, format)?;
+    ureq::post(url)
+        .set("User-Agent", "wole")
+        .timeout(WEBHOOK_TIMEOUT)
+        .send_json(payload)
+        .with_context(|| format!("Failed to post clean summary to {}", url))?;
+    Ok(())
+}
+
+/// Receives notification actions in a daemon. Stops listening when dropped.
+pub struct ActionListener {
+    _server: ipc::Server,
+    commands: Receiver<String>,
+}
+
+impl ActionListener {
+    /// Register the `wole:` protocol and listen for actions
+    pub fn start() -> Result<Self> {
+        register_protocol()?;
+        let (server, commands) = ipc::serve()?;
+        Ok(Self {
+            _server: server,
+            commands,
+        })
+    }
+
+    /// Run an action that arrived since the last call, if any
+    pub fn poll(&self, config: &Config, log: &mut impl FnMut(&str)) {
+        let Ok(command) = self.commands.try_recv() else {
+            return;
+        };
+        match Activation::parse(&command) {
+            Some(activation) if activation.action == NotifyAction::CleanNow => {
+                log("Cleaning pre-approved categories...");
+                if let Err(e) = run_action(&activation, config) {
+                    log(&format!("Clean failed: {}", e));
+                }
+            }
+            // Review opens a terminal, which the activation does itself
+            _ => log(&format!("Ignored daemon command: {}", command)),
+        }
+    }
+}
+
+/// Entry point of the `wole:` protocol handler. "Clean now" goes to the
+/// running daemon and only runs here when no daemon answers; either way it
+/// needs the toast's token.
+pub fn handle_activation(url: &str) -> Result<()> {
+    let activation =
+        Activation::parse(url).with_context(|| format!("Unknown notification action: {}", url))?;
+    if activation.action == NotifyAction::CleanNow && ipc::send(&activation.url())? {
+        return Ok(());
+    }
+    run_action(&activation, &Config::load())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_round_trip_through_urls_and_toast_xml() {
+        let clean = Activation::clean_now("3f9a".to_string());
+        assert_eq!(Activation::parse("wole:clean/3f9a"), Some(clean.clone()));
+        assert_eq!(Activation::parse("WOLE://clean/3f9a/"), Some(clean.clone()));
+        assert_eq!(Activation::parse(&clean.url()), Some(clean.clone()));
+        assert_eq!(Activation::parse("wole:clean").unwrap().token, None);
+        assert_eq!(
+            Activation::parse("WOLE://review/"),
+            Some(Activation::review())
+        );
+        assert_eq!(Activation::parse("review"), Some(Activation::review()));
+        assert_eq!(Activation::parse("wole:format-c"), None);
+
+        // "Clean now" needs the issued token, unexpired
+        let issued = ActionToken {
+            token: "3f9a".to_string(),
+            expires: 2_000,
+        };
+        assert!(issued.accepts("3f9a", 1_000));
+        assert!(!issued.accepts("3f9b", 1_000));
+        assert!(!issued.accepts("3f9", 1_000));
+        assert!(!issued.accepts("3f9a", 2_000));
+
+        let xml = toast_xml(
+            "34 GB cleanable",
+            "Cache & temp <safe>",
+            &[clean, Activation::review()],
+        );
+        assert!(xml.contains("<text>Cache &amp; temp &lt;safe&gt;</text>"));
+        assert!(xml.contains(
+            r#"content="Clean now" activationType="protocol" arguments="wole:clean/3f9a""#
+        ));
+        assert!(xml.contains(r#"launch="wole:review""#));
+        assert!(!toast_xml("t", "b", &[]).contains("<actions>"));
+
+        let approved = vec!["Temp".to_string(), "browser".to_string()];
+        let options = ScanOptions::from_keys(&approved, &Config::default());
+        assert!(options.temp && options.browser);
+        assert!(!options.cache && !options.large && !options.applications);
+
+        let cleanable = [("browser", 5), ("temp", 2), ("large", 10)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        assert_eq!(cleanable_totals(&cleanable, &approved), (17, 7));
+
+        // Below the threshold nothing is shown
+        let mut last = None;
+        let settings = NotificationSettings::default();
+        assert_eq!(
+            notify_cleanable(&settings, &cleanable, &mut last, 1_000).unwrap(),
+            None
+        );
+        assert_eq!(last, None);
+    }
+
+    #[test]
+    fn clean_summaries_fit_each_webhook_format() {
+        let mut log = DeletionLog::new();
+        log.log_success(std::path::Path::new("/tmp/a"), 3_000_000, "cache", false);
+        log.log_success(std::path::Path::new("/tmp/b"), 1_000_000, "temp", false);
+        log.log_failure(std::path::Path::new("/tmp/c"), 5, "temp", false, "locked");
+        let mut summary = CleanSummary::of(&log);
+        summary.host = "agent-7".to_string();
+        assert_eq!((summary.cleaned, summary.errors), (2, 1));
+        assert_eq!(summary.categories["cache"], 3_000_000);
+        assert_eq!(
+            summary.text(),
+            "wole clean on agent-7: 4.0 MB freed, 2 items cleaned, 1 failed"
+        );
+
+        let generic = webhook_payload(&summary, "generic").unwrap();
+        assert_eq!(generic["event"], "clean_finished");
+        assert_eq!(generic["summary"]["cleaned_bytes"], 4_000_000);
+        assert_eq!(
+            webhook_payload(&summary, "Slack").unwrap(),
+            serde_json::json!({ "text": summary.text() })
+        );
+        assert_eq!(
+            webhook_payload(&summary, "teams").unwrap()["@type"],
+            "MessageCard"
+        );
+        assert!(webhook_payload(&summary, "discord").is_err());
+    }
+}
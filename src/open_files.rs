@@ -0,0 +1,293 @@
+//! Files open in editors
+//!
+//! Large and old files a user is editing right now shouldn't be offered for
+//! cleanup just because they haven't been saved in a while. A candidate counts
+//! as open when an editor has it on its command line, when it sits inside a
+//! folder an editor opened as a workspace, when the editor's lock or swap file
+//! is next to it (`~$name`, `.name.swp`, `.#name`, `.~lock.name#`), or, on
+//! Windows, when another process holds it without delete sharing. Handles are
+//! only tested on the candidate paths themselves; the system's open-handle
+//! table is never enumerated.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// Editor and IDE process names, lowercase without `.exe`
+const EDITOR_PROCESSES: &[&str] = &[
+    "code",
+    "code - insiders",
+    "cursor",
+    "windsurf",
+    "zed",
+    "devenv",
+    "idea64",
+    "idea",
+    "pycharm64",
+    "webstorm64",
+    "rider64",
+    "clion64",
+    "goland64",
+    "phpstorm64",
+    "rustrover64",
+    "studio64",
+    "sublime_text",
+    "notepad",
+    "notepad++",
+    "vim",
+    "gvim",
+    "nvim",
+    "nvim-qt",
+    "emacs",
+    "runemacs",
+    "nano",
+    "winword",
+    "excel",
+    "powerpnt",
+    "soffice.bin",
+    "photoshop",
+    "illustrator",
+    "afterfx",
+    "adobe premiere pro",
+    "resolve",
+    "blender",
+];
+
+/// Why a candidate is considered open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenReason {
+    /// Passed to an editor on its command line
+    EditorArgument,
+    /// Inside a folder an editor opened
+    EditorWorkspace,
+    /// An editor's lock or swap file sits next to it
+    LockFile,
+    /// Another process holds it open
+    Locked,
+}
+
+impl OpenReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OpenReason::EditorArgument => "open in an editor",
+            OpenReason::EditorWorkspace => "in an open editor workspace",
+            OpenReason::LockFile => "has an editor lock file",
+            OpenReason::Locked => "in use by another process",
+        }
+    }
+}
+
+/// Files and folders running editors have open, with the editor's process name
+#[derive(Debug, Default)]
+pub struct OpenFiles {
+    files: HashMap<PathBuf, String>,
+    workspaces: Vec<(PathBuf, String)>,
+}
+
+fn is_editor(process_name: &str) -> bool {
+    let lower = process_name.to_lowercase();
+    let name = lower.strip_suffix(".exe").unwrap_or(&lower);
+    EDITOR_PROCESSES.contains(&name)
+}
+
+/// Too broad to treat as a workspace: everything the user owns is below it
+fn is_broad_folder(path: &Path) -> bool {
+    path.parent().is_none() || directories::UserDirs::new().is_some_and(|d| d.home_dir() == path)
+}
+
+impl OpenFiles {
+    /// Look at the command lines of running editors
+    pub fn detect() -> Self {
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::new()
+                .with_cmd(UpdateKind::Always)
+                .with_cwd(UpdateKind::Always),
+        );
+        Self::from_processes(system.processes().values().map(|p| {
+            (
+                p.name().to_string_lossy().into_owned(),
+                p.cmd().to_vec(),
+                p.cwd().map(Path::to_path_buf),
+            )
+        }))
+    }
+
+    /// Collect the paths named on editor command lines. Relative arguments are
+    /// resolved against the process's working directory.
+    pub fn from_processes(
+        processes: impl IntoIterator<Item = (String, Vec<OsString>, Option<PathBuf>)>,
+    ) -> Self {
+        let mut open = OpenFiles::default();
+        for (name, cmd, cwd) in processes {
+            if !is_editor(&name) {
+                continue;
+            }
+            // The first argument is the executable itself
+            for arg in cmd.iter().skip(1) {
+                let arg = PathBuf::from(arg);
+                if arg.to_string_lossy().starts_with('-') {
+                    continue;
+                }
+                let path = match (&cwd, arg.is_relative()) {
+                    (Some(cwd), true) => cwd.join(&arg),
+                    _ => arg,
+                };
+                let Ok(meta) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let path = path.canonicalize().unwrap_or(path);
+                if meta.is_dir() {
+                    if !is_broad_folder(&path) {
+                        open.workspaces.push((path, name.clone()));
+                    }
+                } else {
+                    open.files.insert(path, name.clone());
+                }
+            }
+        }
+        open
+    }
+
+    /// The editor process that has `path` (or a workspace around it) open
+    pub fn editor(&self, path: &Path) -> Option<&str> {
+        if self.files.is_empty() && self.workspaces.is_empty() {
+            return None;
+        }
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.files.get(&canonical).map(String::as_str).or_else(|| {
+            self.workspaces
+                .iter()
+                .find(|(w, _)| canonical.starts_with(w))
+                .map(|(_, editor)| editor.as_str())
+        })
+    }
+
+    /// Why `path` looks open, if it does
+    pub fn reason(&self, path: &Path) -> Option<OpenReason> {
+        if !self.files.is_empty() || !self.workspaces.is_empty() {
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            if self.files.contains_key(&canonical) {
+                return Some(OpenReason::EditorArgument);
+            }
+            if self
+                .workspaces
+                .iter()
+                .any(|(w, _)| canonical.starts_with(w))
+            {
+                return Some(OpenReason::EditorWorkspace);
+            }
+        }
+        if has_lock_file(path) {
+            return Some(OpenReason::LockFile);
+        }
+        if path.is_file() && crate::cleaner::is_path_locked(path) {
+            return Some(OpenReason::Locked);
+        }
+        None
+    }
+}
+
+/// Whether an editor's lock or swap file for `path` exists next to it
+fn has_lock_file(path: &Path) -> bool {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str()))
+    else {
+        return false;
+    };
+    let mut candidates = vec![
+        format!("~${}", name),
+        format!(".{}.swp", name),
+        format!(".{}.swo", name),
+        format!(".#{}", name),
+        format!(".~lock.{}#", name),
+    ];
+    // Office replaces the first two characters of longer names
+    if let Some(rest) = name.get(2..).filter(|r| !r.is_empty()) {
+        candidates.push(format!("~${}", rest));
+    }
+    candidates
+        .iter()
+        .any(|c| std::fs::symlink_metadata(parent.join(c)).is_ok())
+}
+
+/// Remove open paths from `paths`, subtracting their size from `size_bytes`.
+/// Returns the paths removed.
+pub fn retain_closed(
+    paths: &mut Vec<PathBuf>,
+    size_bytes: &mut u64,
+    open: &OpenFiles,
+) -> Vec<(PathBuf, OpenReason)> {
+    let mut removed = Vec::new();
+    paths.retain(|path| match open.reason(path) {
+        Some(reason) => {
+            let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            *size_bytes = size_bytes.saturating_sub(size);
+            removed.push((path.clone(), reason));
+            false
+        }
+        None => true,
+    });
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn editor_arguments_and_lock_files_mark_files_open() {
+        let dir = TempDir::new().unwrap();
+        let project = dir.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        let draft = dir.path().join("draft.mp4");
+        let report = dir.path().join("report.docx");
+        let notes = dir.path().join("notes.txt");
+        let inside = project.join("data.bin");
+        let other = dir.path().join("other.iso");
+        for file in [&draft, &report, &notes, &inside, &other] {
+            fs::write(file, vec![0u8; 10]).unwrap();
+        }
+        fs::write(dir.path().join("~$port.docx"), b"lock").unwrap();
+        fs::write(dir.path().join(".notes.txt.swp"), b"swap").unwrap();
+
+        let open = OpenFiles::from_processes([
+            (
+                "Code.exe".to_string(),
+                vec!["code".into(), "--reuse-window".into(), "project".into()],
+                Some(dir.path().to_path_buf()),
+            ),
+            (
+                "vlc".to_string(),
+                vec!["vlc".into(), other.clone().into()],
+                None,
+            ),
+            (
+                "resolve".to_string(),
+                vec!["resolve".into(), draft.clone().into()],
+                None,
+            ),
+        ]);
+
+        assert_eq!(open.reason(&draft), Some(OpenReason::EditorArgument));
+        assert_eq!(open.reason(&inside), Some(OpenReason::EditorWorkspace));
+        assert_eq!(open.reason(&report), Some(OpenReason::LockFile));
+        assert_eq!(open.reason(&notes), Some(OpenReason::LockFile));
+        // Not an editor
+        assert_eq!(open.reason(&other), None);
+        assert_eq!(open.editor(&draft), Some("resolve"));
+        assert_eq!(open.editor(&inside), Some("Code.exe"));
+        assert_eq!(open.editor(&report), None);
+
+        let mut paths = vec![draft.clone(), other.clone()];
+        let mut size = 20;
+        let removed = retain_closed(&mut paths, &mut size, &open);
+        assert_eq!(paths, vec![other]);
+        assert_eq!(size, 10);
+        assert_eq!(removed, vec![(draft, OpenReason::EditorArgument)]);
+    }
+}
@@ -1,11 +1,23 @@
 use crate::cli::ScanOptions;
+use crate::history::DeletionLog;
 use crate::theme::Theme;
-use serde::Serialize;
-use std::path::PathBuf;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use render::Rule;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use wole_core::policy;
+use wole_core::report::{
+    Categories, CategoryReport, CleanReport, CloudFileItem, Estimate, Failure, FileHint, FileSize,
+    GitFlags, Image, ItemAge, ItemTimes, LargestFiles, RecreateCost, RecycleBinItem, ScanReport,
+    Summary, VolumeTotal,
+};
+
+pub mod render;
 
 // Forward declaration for duplicate groups
+pub use crate::categories::cloud_files::CloudFile;
 pub use crate::categories::duplicates::DuplicateGroup;
+pub use crate::categories::similar_images::SimilarImageGroup;
 
 /// Get emoji for a category name in CLI output
 fn category_emoji(category_name: &str) -> &'static str {
@@ -25,68 +37,14 @@ fn category_emoji(category_name: &str) -> &'static str {
         "Duplicates" => "📋",
         "Windows Update" => "🔄",
         "Event Logs" => "📋",
+        "Orphaned Owners" | "Orphaned" => "👤",
+        "Custom Rules" | "Custom" => "📝",
+        "IDE Caches" | "IDE" => "🧰",
+        "Rust Toolchain" | "Rust" => "🦀",
         _ => "📁", // Default folder emoji
     }
 }
 
-/// Truncate a string to a maximum display width (adds ellipsis if needed).
-fn truncate_to_width(s: &str, max_width: usize) -> String {
-    if UnicodeWidthStr::width(s) <= max_width {
-        return s.to_string();
-    }
-
-    let ellipsis = "…";
-    let ellipsis_w = UnicodeWidthStr::width(ellipsis);
-    let target = max_width.saturating_sub(ellipsis_w);
-
-    let mut out = String::new();
-    let mut w = 0usize;
-    for ch in s.chars() {
-        let cw = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if w + cw > target {
-            break;
-        }
-        out.push(ch);
-        w += cw;
-    }
-    out.push_str(ellipsis);
-    out
-}
-
-/// Pad/truncate content to a specific display width (Unicode-aware).
-fn pad_right_to_width(s: &str, width: usize) -> String {
-    let truncated = truncate_to_width(s, width);
-    let w = UnicodeWidthStr::width(truncated.as_str());
-    format!("{}{}", truncated, " ".repeat(width.saturating_sub(w)))
-}
-
-/// Print a table row with borders and 1-space cell padding.
-fn print_table_row(cols: &[(String, usize)]) {
-    let mut row = String::from("│");
-    for (content, width) in cols {
-        row.push(' ');
-        row.push_str(&pad_right_to_width(content, *width));
-        row.push(' ');
-        row.push('│');
-    }
-    println!("{}", row);
-}
-
-/// Print a horizontal separator line (Unicode box drawing).
-/// Widths are content widths (excluding the 1-space left/right padding).
-fn print_table_separator(widths: &[usize], left: &str, mid: &str, right: &str) {
-    let mut sep = left.to_string();
-    for (i, width) in widths.iter().enumerate() {
-        if i > 0 {
-            sep.push_str(mid);
-        }
-        // +2 for the 1-space padding on each side of the cell
-        sep.push_str(&"─".repeat(width + 2));
-    }
-    sep.push_str(right);
-    println!("{}", sep);
-}
-
 /// Output verbosity mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputMode {
@@ -113,63 +71,474 @@ pub struct ScanResults {
     pub duplicates: CategoryResult,
     pub windows_update: CategoryResult,
     pub event_logs: CategoryResult,
+    pub orphaned: CategoryResult,
+    pub custom: CategoryResult,
+    pub ide: CategoryResult,
+    pub rust: CategoryResult,
     /// Optional duplicate groups for enhanced display (only populated for duplicates category)
     pub duplicates_groups: Option<Vec<DuplicateGroup>>,
+    /// Images that look alike (`[categories.duplicates] similar_images`); review only, never cleaned
+    pub similar_image_groups: Vec<SimilarImageGroup>,
+    /// Downloaded OneDrive files that could go online-only
+    /// (`[categories.large] cloud_files`); review only, never cleaned
+    pub cloud_files: Vec<CloudFile>,
+    /// Set when sizes are a quick estimate (`wole scan --quick`)
+    pub sampling: Option<crate::estimate::Sampling>,
+    /// Only counts and sizes were kept (`wole scan --summary-only`); there
+    /// are no paths, so these results can't be cleaned
+    pub summary_only: bool,
+    /// The scan was cancelled: categories after the cancel are empty and the
+    /// one running then may be incomplete
+    pub cancelled: bool,
+    /// Folder the scan was started from, if known
+    pub root: Option<PathBuf>,
+    /// Recycle Bin items newer than this (days) were left out and are never
+    /// purged (`--trash-older-than`)
+    pub trash_min_age_days: u64,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CategoryResult {
     pub items: usize,
     pub size_bytes: u64,
     pub paths: Vec<PathBuf>,
+    /// Hidden or system-attribute matches (Large/Old Files), reported on their
+    /// own. They're only part of `paths` when `[safety] include_hidden` is set.
+    #[serde(default)]
+    pub hidden_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub hidden_bytes: u64,
+    /// Machine-wide caches shared by all users (Package Cache). Never counted in
+    /// `items`/`size_bytes`; only cleaned with administrator rights.
+    #[serde(default)]
+    pub machine_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub machine_bytes: u64,
+    /// Largest files inside each folder in `paths`, largest first, as found by
+    /// the walk that sized the folder
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub largest_files: HashMap<PathBuf, Vec<(PathBuf, u64)>>,
+}
+
+impl ScanResults {
+    /// Every category with its CLI key (`cache`, `app_cache`, ...)
+    pub fn by_category(&self) -> [(&'static str, &CategoryResult); 19] {
+        [
+            ("cache", &self.cache),
+            ("app_cache", &self.app_cache),
+            ("temp", &self.temp),
+            ("trash", &self.trash),
+            ("build", &self.build),
+            ("downloads", &self.downloads),
+            ("large", &self.large),
+            ("old", &self.old),
+            ("applications", &self.applications),
+            ("browser", &self.browser),
+            ("system", &self.system),
+            ("empty", &self.empty),
+            ("duplicates", &self.duplicates),
+            ("windows_update", &self.windows_update),
+            ("event_logs", &self.event_logs),
+            ("orphaned", &self.orphaned),
+            ("custom", &self.custom),
+            ("ide", &self.ide),
+            ("rust", &self.rust),
+        ]
+    }
+
+    /// Attach the largest files found inside every flagged folder
+    pub fn collect_largest_files(&mut self) {
+        for key in self.by_category().map(|(key, _)| key) {
+            if let Some(category) = self.category_mut(key) {
+                category.collect_largest_files();
+            }
+        }
+    }
+
+    /// The largest files inside flagged folder `dir`, largest first
+    pub fn largest_files_in(&self, dir: &Path) -> &[(PathBuf, u64)] {
+        self.by_category()
+            .into_iter()
+            .find_map(|(_, category)| category.largest_files.get(dir))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The category with CLI key `key`
+    pub fn category_mut(&mut self, key: &str) -> Option<&mut CategoryResult> {
+        Some(match key {
+            "cache" => &mut self.cache,
+            "app_cache" => &mut self.app_cache,
+            "temp" => &mut self.temp,
+            "trash" => &mut self.trash,
+            "build" => &mut self.build,
+            "downloads" => &mut self.downloads,
+            "large" => &mut self.large,
+            "old" => &mut self.old,
+            "applications" => &mut self.applications,
+            "browser" => &mut self.browser,
+            "system" => &mut self.system,
+            "empty" => &mut self.empty,
+            "duplicates" => &mut self.duplicates,
+            "windows_update" => &mut self.windows_update,
+            "event_logs" => &mut self.event_logs,
+            "orphaned" => &mut self.orphaned,
+            "custom" => &mut self.custom,
+            "ide" => &mut self.ide,
+            "rust" => &mut self.rust,
+            _ => return None,
+        })
+    }
+
+    /// Drop every path, keeping each category's counts and sizes
+    pub fn summarize(&mut self) {
+        for key in self.by_category().map(|(key, _)| key) {
+            if let Some(category) = self.category_mut(key) {
+                category.paths = Vec::new();
+                category.hidden_paths = Vec::new();
+                category.machine_paths = Vec::new();
+                category.largest_files = HashMap::new();
+            }
+        }
+        self.duplicates_groups = None;
+        self.similar_image_groups = Vec::new();
+        self.cloud_files = Vec::new();
+        self.summary_only = true;
+    }
+
+    /// Fold the results of a scan of another root into these.
+    ///
+    /// Categories that don't depend on the root (caches, Recycle Bin, ...)
+    /// come back identical from every root and are counted once; paths found
+    /// under both roots are kept once.
+    pub fn merge(&mut self, other: ScanResults) {
+        for (key, theirs) in other.by_category() {
+            if let Some(ours) = self.category_mut(key) {
+                ours.merge(theirs);
+            }
+        }
+
+        if let Some(theirs) = other.duplicates_groups {
+            let groups = self.duplicates_groups.get_or_insert_with(Vec::new);
+            for group in theirs {
+                match groups.iter_mut().find(|g| g.hash == group.hash) {
+                    Some(existing) => {
+                        for path in group.paths {
+                            if !existing.paths.contains(&path) {
+                                existing.paths.push(path);
+                            }
+                        }
+                    }
+                    None => groups.push(group),
+                }
+            }
+        }
+        for group in other.similar_image_groups {
+            if !self.similar_image_groups.contains(&group) {
+                self.similar_image_groups.push(group);
+            }
+        }
+        // The OneDrive folders are the same whichever root was scanned
+        if self.cloud_files.is_empty() {
+            self.cloud_files = other.cloud_files;
+        }
+        self.summary_only |= other.summary_only;
+        self.cancelled |= other.cancelled;
+    }
 }
 
 impl CategoryResult {
     pub fn size_human(&self) -> String {
         bytesize::to_string(self.size_bytes, false)
     }
+
+    /// Add the paths of `other` that aren't already here, with their sizes
+    fn merge(&mut self, other: &CategoryResult) {
+        if other.paths.is_empty() {
+            // Counts only (summary results): take them when there are none yet
+            if self.items == 0 {
+                self.items = other.items;
+                self.size_bytes = other.size_bytes;
+            }
+        } else {
+            let (added, bytes) = merge_paths(&mut self.paths, &other.paths, other.size_bytes);
+            self.items += if added == other.paths.len() {
+                other.items
+            } else {
+                added
+            };
+            self.size_bytes += bytes;
+        }
+        self.hidden_bytes += merge_paths(
+            &mut self.hidden_paths,
+            &other.hidden_paths,
+            other.hidden_bytes,
+        )
+        .1;
+        self.machine_bytes += merge_paths(
+            &mut self.machine_paths,
+            &other.machine_paths,
+            other.machine_bytes,
+        )
+        .1;
+        for (dir, files) in &other.largest_files {
+            self.largest_files
+                .entry(dir.clone())
+                .or_insert_with(|| files.clone());
+        }
+    }
+
+    /// Attach what the size walks found inside each folder in `paths`
+    pub fn collect_largest_files(&mut self) {
+        for path in &self.paths {
+            if let Some(sized) = crate::utils::take_sized_dir(path) {
+                if !sized.largest_files.is_empty() {
+                    self.largest_files.insert(path.clone(), sized.largest_files);
+                }
+            }
+        }
+    }
+
+    /// Record the hidden/system bucket, biggest first, keeping at most `max`
+    pub fn set_hidden(&mut self, mut hidden: Vec<(PathBuf, u64)>, max: usize) {
+        hidden.sort_by_key(|h| std::cmp::Reverse(h.1));
+        hidden.truncate(max);
+        self.hidden_bytes = hidden.iter().map(|(_, size)| size).sum();
+        self.hidden_paths = hidden.into_iter().map(|(path, _)| path).collect();
+    }
+}
+
+/// Add the paths of `theirs` missing from `ours`. Returns how many were
+/// added and their size: `their_bytes` when nothing overlapped, otherwise
+/// measured from disk.
+fn merge_paths(ours: &mut Vec<PathBuf>, theirs: &[PathBuf], their_bytes: u64) -> (usize, u64) {
+    let known: std::collections::HashSet<PathBuf> = ours.iter().cloned().collect();
+    let new: Vec<PathBuf> = theirs
+        .iter()
+        .filter(|p| !known.contains(*p))
+        .cloned()
+        .collect();
+    let bytes = if new.len() == theirs.len() {
+        their_bytes
+    } else {
+        new.iter()
+            .map(|p| match std::fs::symlink_metadata(p) {
+                Ok(meta) if meta.is_dir() => crate::utils::calculate_dir_size(p),
+                Ok(meta) => meta.len(),
+                Err(_) => 0,
+            })
+            .sum()
+    };
+    let added = new.len();
+    ours.extend(new);
+    (added, bytes)
+}
+
+fn clean_report(log: &DeletionLog) -> CleanReport {
+    CleanReport {
+        cleaned: log.total_items - log.errors,
+        cleaned_bytes: log.total_bytes_cleaned,
+        errors: log.errors,
+        failures: log
+            .failures()
+            .map(|r| Failure {
+                path: r.path.clone(),
+                category: r.category.clone(),
+                reason: r.reason,
+                error_code: r.error_code,
+                error: r.error.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn category_report(result: &CategoryResult) -> CategoryReport {
+    let largest_files = result
+        .paths
+        .iter()
+        .filter_map(|dir| {
+            let files = result.largest_files.get(dir)?;
+            Some(LargestFiles {
+                path: dir.to_string_lossy().to_string(),
+                files: files
+                    .iter()
+                    .map(|(path, size)| FileSize {
+                        name: path
+                            .strip_prefix(dir)
+                            .unwrap_or(path)
+                            .to_string_lossy()
+                            .to_string(),
+                        size_bytes: *size,
+                        size_human: bytesize::to_string(*size, false),
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    CategoryReport {
+        items: result.items,
+        size_bytes: result.size_bytes,
+        size_human: result.size_human(),
+        paths: result
+            .paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+        hidden_paths: result
+            .hidden_paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+        hidden_size_bytes: result.hidden_bytes,
+        machine_paths: result
+            .machine_paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+        machine_size_bytes: result.machine_bytes,
+        recreate_costs: Vec::new(),
+        largest_files,
+        item_times: result.paths.iter().map(|p| item_times(p)).collect(),
+        file_hints: Vec::new(),
+        ages: Vec::new(),
+        git_flags: Vec::new(),
+        recycle_bin: Vec::new(),
+    }
+}
+
+fn item_times(path: &std::path::Path) -> ItemTimes {
+    let rfc3339 = |t: Option<std::time::SystemTime>| {
+        t.map(|t| {
+            chrono::DateTime::<chrono::Utc>::from(t)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        })
+    };
+    let times = crate::utils::file_times(path);
+    ItemTimes {
+        path: path.to_string_lossy().to_string(),
+        created: rfc3339(times.created),
+        modified: rfc3339(times.modified),
+        accessed: rfc3339(times.accessed),
+    }
 }
 
-#[derive(Serialize)]
-struct JsonResults {
-    version: String,
-    timestamp: String,
-    categories: JsonCategories,
-    summary: JsonSummary,
+/// Like `category_report`, plus a recreate-cost estimate for each folder
+fn category_report_with_recreate_costs(result: &CategoryResult) -> CategoryReport {
+    let mut category = category_report(result);
+    category.recreate_costs = result
+        .paths
+        .iter()
+        .filter(|p| p.is_dir())
+        .filter_map(|path| {
+            let size = crate::utils::calculate_dir_size(path);
+            let cost = crate::recreate_cost::estimate(path, size)?;
+            Some(RecreateCost {
+                path: path.to_string_lossy().to_string(),
+                kind: match cost.kind {
+                    crate::recreate_cost::RecreateKind::Redownload => "redownload",
+                    crate::recreate_cost::RecreateKind::Rebuild => "rebuild",
+                    crate::recreate_cost::RecreateKind::Regenerated => "regenerated",
+                }
+                .to_string(),
+                description: cost.describe(),
+                download_bytes: cost.download_bytes,
+            })
+        })
+        .collect();
+    category
 }
 
-#[derive(Serialize)]
-struct JsonCategories {
-    cache: JsonCategory,
-    app_cache: JsonCategory,
-    temp: JsonCategory,
-    trash: JsonCategory,
-    build: JsonCategory,
-    downloads: JsonCategory,
-    large: JsonCategory,
-    old: JsonCategory,
-    applications: JsonCategory,
-    browser: JsonCategory,
-    system: JsonCategory,
-    empty: JsonCategory,
-    duplicates: JsonCategory,
-    windows_update: JsonCategory,
-    event_logs: JsonCategory,
+/// Like `category_report_with_recreate_costs`, plus which artifacts belong to
+/// a repository with uncommitted changes or unpushed commits
+fn category_report_with_git_flags(result: &CategoryResult) -> CategoryReport {
+    let mut category = category_report_with_recreate_costs(result);
+    category.git_flags = result
+        .paths
+        .iter()
+        .filter_map(|path| {
+            let state = crate::git::repo_state_cached(path);
+            state.has_local_work().then(|| GitFlags {
+                path: path.to_string_lossy().to_string(),
+                dirty: state.dirty,
+                unpushed: state.unpushed,
+            })
+        })
+        .collect();
+    category
 }
 
-#[derive(Serialize)]
-struct JsonCategory {
-    items: usize,
-    size_bytes: u64,
-    size_human: String,
-    paths: Vec<String>,
+/// Like `category_report`, plus name, deletion date and size of each
+/// Recycle Bin entry
+fn category_report_with_bin_items(result: &CategoryResult, min_age_days: u64) -> CategoryReport {
+    let mut category = category_report(result);
+    if result.items == 0 {
+        return category;
+    }
+    let now = chrono::Utc::now();
+    category.recycle_bin = crate::categories::trash::entries(min_age_days)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| RecycleBinItem {
+            age_days: entry.age_days(now),
+            name: entry.name,
+            original_path: entry.original.to_string_lossy().to_string(),
+            deleted: entry
+                .deleted
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            size_bytes: entry.size_bytes,
+        })
+        .collect();
+    category
 }
 
-#[derive(Serialize)]
-struct JsonSummary {
-    total_items: usize,
-    total_bytes: u64,
-    total_human: String,
+/// Like `category_report`, plus each file's age by the configured `age_basis`
+fn category_report_with_ages(result: &CategoryResult) -> CategoryReport {
+    let mut category = category_report(result);
+    if result.paths.is_empty() {
+        return category;
+    }
+    let basis = crate::config::Config::load()
+        .categories
+        .old
+        .age_basis
+        .unwrap_or_default();
+    category.ages = result
+        .paths
+        .iter()
+        .filter_map(|path| {
+            let age = crate::categories::old::file_age(path, basis)?;
+            Some(ItemAge {
+                path: path.to_string_lossy().to_string(),
+                days: age.days,
+                basis: age.stamp.as_str().to_string(),
+            })
+        })
+        .collect();
+    category
+}
+
+/// Like `category_report`, plus type, access age and in-use status of each file
+fn category_report_with_file_hints(result: &CategoryResult) -> CategoryReport {
+    let mut category = category_report(result);
+    if result.paths.is_empty() {
+        return category;
+    }
+    let open = crate::open_files::OpenFiles::detect();
+    category.file_hints = result
+        .paths
+        .iter()
+        .map(|path| {
+            let hints = crate::categories::large::FileHints::of(path, &open);
+            FileHint {
+                path: path.to_string_lossy().to_string(),
+                file_type: hints.file_type.as_str().to_string(),
+                accessed_days: hints.accessed_days,
+                in_use: hints.in_use(),
+            }
+        })
+        .collect();
+    category
 }
 
 pub fn print_human(results: &ScanResults, mode: OutputMode) {
@@ -195,71 +564,41 @@ pub fn print_human_with_options(
     let col_widths = [26, 7, 12, 24];
 
     // Print table header with borders
-    print_table_separator(&col_widths, "┌", "┬", "┐");
-    print_table_row(&[
-        (Theme::primary("Category"), col_widths[0]),
-        (Theme::primary("Items"), col_widths[1]),
-        (Theme::primary("Size"), col_widths[2]),
-        (Theme::primary("Status"), col_widths[3]),
-    ]);
-    print_table_separator(&col_widths, "├", "┼", "┤");
-
-    let categories = [
-        ("Package cache", &results.cache, "[OK] Safe to clean"),
-        (
-            "Application cache",
-            &results.app_cache,
-            "[OK] Safe to clean",
-        ),
-        ("Temp", &results.temp, "[OK] Safe to clean"),
-        ("Trash", &results.trash, "[OK] Safe to clean"),
-        ("Build", &results.build, "[OK] Inactive projects"),
-        ("Downloads", &results.downloads, "[OK] Old files"),
-        ("Large", &results.large, "[!] Review suggested"),
-        ("Old", &results.old, "[!] Review suggested"),
-        (
-            "Applications",
-            &results.applications,
-            "[!] Review suggested",
-        ),
-        ("Browser", &results.browser, "[OK] Safe to clean"),
-        ("System", &results.system, "[OK] Safe to clean"),
-        ("Empty", &results.empty, "[OK] Safe to clean"),
-        ("Duplicates", &results.duplicates, "[!] Review suggested"),
-        (
-            "Windows Update",
-            &results.windows_update,
-            "[!] Requires admin",
-        ),
-        ("Event Logs", &results.event_logs, "[!] Requires admin"),
-    ];
+    println!("{}", render::table_rule(&col_widths, Rule::Top));
+    println!(
+        "{}",
+        render::table_row(&[
+            (Theme::primary("Category"), col_widths[0]),
+            (Theme::primary("Items"), col_widths[1]),
+            (Theme::primary("Size"), col_widths[2]),
+            (Theme::primary("Status"), col_widths[3]),
+        ])
+    );
+    println!("{}", render::table_rule(&col_widths, Rule::Middle));
 
-    for (name, result, status) in categories {
+    for (key, result) in results.by_category() {
         if result.items > 0 {
-            let status = if name == "Application cache" {
-                if crate::categories::app_cache::scan_includes_review_worthy_paths(
-                    &results.app_cache.paths,
-                ) {
-                    "[!] Review suggested"
-                } else {
-                    "[OK] Safe to clean"
-                }
-            } else {
-                status
+            let Some(category) = policy::category(key) else {
+                continue;
             };
-            let status_colored = if status.starts_with("[OK]") {
-                Theme::status_safe(status)
+            let category = category.for_paths(result.paths.iter().map(|p| p.to_string_lossy()));
+            let name = category.label;
+            let status_colored = if category.safety == policy::Safety::Safe {
+                Theme::status_safe(category.status)
             } else {
-                Theme::status_review(status)
+                Theme::status_review(category.status)
             };
             let emoji = category_emoji(name);
             let category_display = format!("{} {}", emoji, name);
-            print_table_row(&[
-                (Theme::category(&category_display), col_widths[0]),
-                (Theme::value(&result.items.to_string()), col_widths[1]),
-                (Theme::size(&result.size_human()), col_widths[2]),
-                (status_colored, col_widths[3]),
-            ]);
+            println!(
+                "{}",
+                render::table_row(&[
+                    (Theme::category(&category_display), col_widths[0]),
+                    (Theme::value(&result.items.to_string()), col_widths[1]),
+                    (Theme::size(&result.size_human()), col_widths[2]),
+                    (status_colored, col_widths[3]),
+                ])
+            );
 
             // Special handling for duplicates: show groups in verbose mode
             if name == "Duplicates"
@@ -322,6 +661,14 @@ pub fn print_human_with_options(
                         }
                     }
                 }
+            } else if key == "trash" {
+                if mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose {
+                    print_trash_entries(results.trash_min_age_days, mode);
+                }
+            } else if key == "large" || key == "old" {
+                if mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose {
+                    print_grouped_paths(result, results.root.as_deref(), mode, key == "large");
+                }
             } else {
                 // Regular path display for other categories
                 // In verbose mode, show first few paths
@@ -367,7 +714,11 @@ pub fn print_human_with_options(
         + results.empty.items
         + results.duplicates.items
         + results.windows_update.items
-        + results.event_logs.items;
+        + results.event_logs.items
+        + results.orphaned.items
+        + results.custom.items
+        + results.ide.items
+        + results.rust.items;
     let total_bytes = results.cache.size_bytes
         + results.app_cache.size_bytes
         + results.temp.size_bytes
@@ -382,10 +733,14 @@ pub fn print_human_with_options(
         + results.empty.size_bytes
         + results.duplicates.size_bytes
         + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+        + results.event_logs.size_bytes
+        + results.orphaned.size_bytes
+        + results.custom.size_bytes
+        + results.ide.size_bytes
+        + results.rust.size_bytes;
 
     if total_items == 0 {
-        print_table_separator(&col_widths, "└", "┴", "┘");
+        println!("{}", render::table_rule(&col_widths, Rule::Bottom));
         println!();
         println!(
             "{}",
@@ -393,27 +748,567 @@ pub fn print_human_with_options(
         );
     } else {
         // Total row inside the same table box
-        print_table_separator(&col_widths, "├", "┼", "┤");
-        print_table_row(&[
-            (Theme::header("Total"), col_widths[0]),
-            (Theme::value(&total_items.to_string()), col_widths[1]),
-            (
-                Theme::size(&bytesize::to_string(total_bytes, false)),
-                col_widths[2],
-            ),
-            (Theme::success("Reclaimable"), col_widths[3]),
-        ]);
-        print_table_separator(&col_widths, "└", "┴", "┘");
+        println!("{}", render::table_rule(&col_widths, Rule::Middle));
+        println!(
+            "{}",
+            render::table_row(&[
+                (Theme::header("Total"), col_widths[0]),
+                (Theme::value(&total_items.to_string()), col_widths[1]),
+                (
+                    Theme::size(&bytesize::to_string(total_bytes, false)),
+                    col_widths[2],
+                ),
+                (Theme::success("Reclaimable"), col_widths[3]),
+            ])
+        );
+        println!("{}", render::table_rule(&col_widths, Rule::Bottom));
+        print_volume_subtotals(results);
         println!();
-        let clean_command = build_clean_command(options);
+        let mut clean_command = build_clean_command(options);
+        if results.trash_min_age_days > 0 && results.trash.items > 0 {
+            clean_command.push_str(&format!(
+                " --trash-older-than {}",
+                results.trash_min_age_days
+            ));
+        }
         println!(
             "Run {} to remove these files.",
             Theme::command(&clean_command)
         );
     }
+    print_hidden_buckets(results);
+    print_trash_aging(results, options);
+    print_machine_caches(&results.cache);
+    print_vm_leftovers(&results.system);
+    print_app_leftovers(&results.app_cache);
+    print_browser_profiles(&results.browser);
+    print_similar_images(results, mode);
+    print_cloud_files(results, mode);
     println!();
 }
 
+/// Break the total down per drive when results span more than one
+/// Per-drive totals for `--json`, when results span several drives
+fn volume_totals(results: &ScanResults) -> Vec<VolumeTotal> {
+    let subtotals = crate::volumes::subtotals(results);
+    if subtotals.len() < 2 {
+        return Vec::new();
+    }
+    subtotals
+        .into_iter()
+        .map(|subtotal| VolumeTotal {
+            volume: subtotal.volume,
+            items: subtotal.items,
+            size_bytes: subtotal.size_bytes,
+            size_human: bytesize::to_string(subtotal.size_bytes, false),
+        })
+        .collect()
+}
+
+fn print_volume_subtotals(results: &ScanResults) {
+    let subtotals = crate::volumes::subtotals(results);
+    if subtotals.len() < 2 {
+        return;
+    }
+    for subtotal in subtotals {
+        println!(
+            "  {} {:<6} {:>7} items  {}",
+            Theme::muted("└─"),
+            subtotal.volume,
+            subtotal.items,
+            Theme::size(&bytesize::to_string(subtotal.size_bytes, false))
+        );
+    }
+}
+
+/// Note machine-wide package caches; they're shared by every user on the PC,
+/// so they're kept out of this user's totals and need elevation to clean
+fn print_machine_caches(cache: &CategoryResult) {
+    if cache.machine_paths.is_empty() {
+        return;
+    }
+    println!(
+        "{} Package cache: {} machine-wide caches ({}) shared by all users, not counted above",
+        Theme::muted("[i]"),
+        cache.machine_paths.len(),
+        bytesize::to_string(cache.machine_bytes, false)
+    );
+    if !crate::optimize::is_admin() {
+        println!(
+            "    {}",
+            Theme::muted("Run wole as administrator to clean them with --cache")
+        );
+    }
+}
+
+/// Note Hyper-V checkpoint and Windows Sandbox leftovers found with System
+/// Cache. Like machine-wide caches they need elevation to clean.
+fn print_vm_leftovers(system: &CategoryResult) {
+    use crate::categories::vm_leftovers;
+
+    if system.machine_paths.is_empty() {
+        return;
+    }
+    println!(
+        "{} System: {} Hyper-V/Sandbox leftovers ({}) not counted above",
+        Theme::muted("[i]"),
+        system.machine_paths.len(),
+        bytesize::to_string(system.machine_bytes, false)
+    );
+    for path in &system.machine_paths {
+        let label = vm_leftovers::kind_of(path)
+            .map(|k| k.label())
+            .unwrap_or("leftover");
+        println!(
+            "    {} {} ({})",
+            Theme::muted("→"),
+            crate::utils::display_path(path),
+            label
+        );
+    }
+    if system
+        .machine_paths
+        .iter()
+        .any(|p| vm_leftovers::kind_of(p) == Some(vm_leftovers::LeftoverKind::OrphanedCheckpoint))
+    {
+        println!(
+            "    {}",
+            Theme::muted(
+                "Check Hyper-V Manager first: no VM or checkpoint should list these disks"
+            )
+        );
+    }
+    if !crate::optimize::is_admin() {
+        println!(
+            "    {}",
+            Theme::muted("Run wole as administrator to clean them with --system")
+        );
+    }
+}
+
+/// Paths listed per folder group with `-v` before the rest are collapsed
+const GROUP_PREVIEW: usize = 3;
+
+/// Folder groups listed with `-v` before the rest are collapsed
+const GROUP_LIMIT: usize = 10;
+
+/// Paths under one top-level folder, biggest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PathGroup {
+    folder: PathBuf,
+    bytes: u64,
+    paths: Vec<(PathBuf, u64)>,
+}
+
+/// Group `paths` by their top-level folder under the scan root, or under the
+/// folder they all share when they aren't all below the root (Large and Old
+/// Files search the user folders wherever the scan started). Biggest group
+/// first.
+fn group_by_top_folder(
+    paths: &[PathBuf],
+    root: Option<&Path>,
+    size_of: impl Fn(&Path) -> u64,
+) -> Vec<PathGroup> {
+    let Some(first) = paths.first() else {
+        return Vec::new();
+    };
+    let base = match root {
+        Some(root) if paths.iter().all(|p| p.starts_with(root) && p != root) => root.to_path_buf(),
+        _ => {
+            let mut base = first.parent().unwrap_or(first).to_path_buf();
+            while !paths.iter().all(|p| p.starts_with(&base)) {
+                if !base.pop() {
+                    break;
+                }
+            }
+            base
+        }
+    };
+
+    let mut groups: Vec<PathGroup> = Vec::new();
+    for path in paths {
+        let relative = path.strip_prefix(&base).unwrap_or(path);
+        let mut components = relative.components();
+        let folder = match (components.next(), components.next()) {
+            (Some(top), Some(_)) => base.join(top),
+            _ => base.clone(),
+        };
+        let size = size_of(path);
+        match groups.iter_mut().find(|g| g.folder == folder) {
+            Some(group) => {
+                group.bytes += size;
+                group.paths.push((path.clone(), size));
+            }
+            None => groups.push(PathGroup {
+                folder,
+                bytes: size,
+                paths: vec![(path.clone(), size)],
+            }),
+        }
+    }
+    for group in &mut groups {
+        group
+            .paths
+            .sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    }
+    groups.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.folder.cmp(&b.folder)));
+    groups
+}
+
+/// List Large/Old Files paths grouped by top-level folder with subtotals.
+/// With `-v` each group shows its biggest few and collapses the rest; with
+/// `-vv` everything is listed.
+/// With `hints`, each file also shows its type, last use and whether it's in use
+fn print_grouped_paths(
+    result: &CategoryResult,
+    root: Option<&Path>,
+    mode: OutputMode,
+    hints: bool,
+) {
+    let open = hints.then(crate::open_files::OpenFiles::detect);
+    let groups = group_by_top_folder(&result.paths, root, |path| {
+        std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    });
+    let collapse = mode != OutputMode::VeryVerbose;
+    let shown_groups = if collapse {
+        groups.len().min(GROUP_LIMIT)
+    } else {
+        groups.len()
+    };
+
+    for group in groups.iter().take(shown_groups) {
+        println!(
+            "  {} {} ({} files, {})",
+            Theme::muted("└─"),
+            Theme::primary(&group.folder.display().to_string()),
+            render::format_number(group.paths.len() as u64),
+            bytesize::to_string(group.bytes, false)
+        );
+        let shown = if collapse {
+            group.paths.len().min(GROUP_PREVIEW)
+        } else {
+            group.paths.len()
+        };
+        for (path, size) in group.paths.iter().take(shown) {
+            let emoji = crate::utils::detect_file_type(path).emoji();
+            let hint = open
+                .as_ref()
+                .map(|open| {
+                    let hints = crate::categories::large::FileHints::of(path, open);
+                    let summary = format!("  {}", hints.summary());
+                    if hints.open.is_some() {
+                        Theme::warning(&summary)
+                    } else {
+                        Theme::muted(&summary)
+                    }
+                })
+                .unwrap_or_default();
+            println!(
+                "     {} {}  {}{}",
+                emoji,
+                Theme::muted(&path.display().to_string()),
+                Theme::size(&bytesize::to_string(*size, false)),
+                hint
+            );
+        }
+        if group.paths.len() > shown {
+            println!(
+                "     {}",
+                Theme::muted(&format!(
+                    "…and {} more in {}",
+                    group.paths.len() - shown,
+                    group.folder.display()
+                ))
+            );
+        }
+    }
+    if groups.len() > shown_groups {
+        let rest = &groups[shown_groups..];
+        println!(
+            "  {}",
+            Theme::muted(&format!(
+                "…and {} more folders ({} files, {})",
+                rest.len(),
+                rest.iter().map(|g| g.paths.len()).sum::<usize>(),
+                bytesize::to_string(rest.iter().map(|g| g.bytes).sum(), false)
+            ))
+        );
+    }
+}
+
+/// List Recycle Bin entries, largest first: what, when it was deleted, how
+/// big, and where it came from. `-v` shows the top 10, `-vv` all of them.
+fn print_trash_entries(min_age_days: u64, mode: OutputMode) {
+    let Ok(entries) = crate::categories::trash::entries(min_age_days) else {
+        return;
+    };
+    let shown = if mode == OutputMode::VeryVerbose {
+        entries.len()
+    } else {
+        entries.len().min(10)
+    };
+    let now = chrono::Utc::now();
+    for entry in entries.iter().take(shown) {
+        println!(
+            "  {} {}  {}  {}",
+            crate::utils::detect_file_type(&entry.original).emoji(),
+            entry.name,
+            Theme::size(&bytesize::to_string(entry.size_bytes, false)),
+            Theme::muted(&format!(
+                "deleted {} ({} days ago) from {}",
+                entry
+                    .deleted
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d"),
+                entry.age_days(now),
+                entry
+                    .original
+                    .parent()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default()
+            ))
+        );
+    }
+    if entries.len() > shown {
+        println!(
+            "  {}",
+            Theme::muted(&format!(
+                "...and {} more (use -vv to see all)",
+                entries.len() - shown
+            ))
+        );
+    }
+}
+
+/// Break the Recycle Bin down by how long items have been there, so the old
+/// ones can be emptied on their own with `--trash-older-than`
+fn print_trash_aging(results: &ScanResults, options: Option<&ScanOptions>) {
+    let trash_scanned = options.map(|o| o.trash).unwrap_or(false);
+    if results.trash.items == 0 && !(trash_scanned && results.trash_min_age_days > 0) {
+        return;
+    }
+    let Ok(aging) = crate::categories::trash::aging() else {
+        return;
+    };
+    if aging.ages_days.is_empty() {
+        return;
+    }
+    println!(
+        "{} Recycle Bin by time since deletion:",
+        Theme::muted("[i]")
+    );
+    for ((_, label), count) in crate::categories::trash::AGE_BUCKETS
+        .iter()
+        .zip(aging.buckets())
+    {
+        if count > 0 {
+            println!("    {:<14} {}", label, render::format_number(count as u64));
+        }
+    }
+    let days = results.trash_min_age_days;
+    if days > 0 {
+        let newer = aging.ages_days.len() - aging.older_than(days);
+        if newer > 0 {
+            println!(
+                "    {}",
+                Theme::muted(&format!(
+                    "{} items deleted in the last {} days are left out",
+                    newer, days
+                ))
+            );
+        }
+    } else {
+        let older = aging.older_than(30);
+        if older > 0 && older < aging.ages_days.len() {
+            println!(
+                "    Use {} to empty only the {} items deleted over 30 days ago",
+                Theme::command("--trash-older-than 30"),
+                older
+            );
+        }
+    }
+}
+
+/// List the AppData folders Application Cache guessed belong to uninstalled
+/// apps. They're part of the totals, so say why each one is there.
+fn print_app_leftovers(app_cache: &CategoryResult) {
+    use crate::categories::app_leftovers;
+
+    let flagged: Vec<&PathBuf> = app_cache
+        .paths
+        .iter()
+        .filter(|p| app_leftovers::is_flagged(p))
+        .collect();
+    if flagged.is_empty() {
+        return;
+    }
+    println!(
+        "{} Application Cache includes {} orphaned app data folders - review before deleting",
+        Theme::warning("[!]"),
+        flagged.len()
+    );
+    for path in flagged {
+        println!(
+            "    {} {} (no installed app, shortcut or process matches)",
+            Theme::muted("→"),
+            crate::utils::display_path(path)
+        );
+    }
+}
+
+/// Break Browser Cache down per browser profile, and name browsers that were
+/// skipped because they were running
+fn print_browser_profiles(browser: &CategoryResult) {
+    use crate::categories::browser;
+
+    let profiles = browser::profile_breakdown(browser);
+    if profiles.len() > 1 {
+        println!("{} Browser Cache by profile:", Theme::muted("[i]"));
+        for profile in profiles {
+            println!(
+                "    {} {:<28} {:>4} items  {}",
+                Theme::muted("→"),
+                format!("{} · {}", profile.browser, profile.profile),
+                profile.items,
+                Theme::size(&bytesize::to_string(profile.size_bytes, false))
+            );
+        }
+    }
+    let skipped = browser::skipped_running();
+    if !skipped.is_empty() {
+        println!(
+            "{} Skipped {} cache: close the browser and scan again to include it",
+            Theme::muted("[i]"),
+            skipped.join(", ")
+        );
+    }
+}
+
+/// Note the hidden/system files found by Large and Old Files. They're riskier
+/// to delete, so they stay out of the totals unless `--include-hidden` is used.
+fn print_hidden_buckets(results: &ScanResults) {
+    for (name, result) in [("Large", &results.large), ("Old", &results.old)] {
+        if result.hidden_paths.is_empty() {
+            continue;
+        }
+        let include_hidden = result.hidden_paths.iter().any(|p| result.paths.contains(p));
+        let summary = format!(
+            "{} hidden/system files ({})",
+            result.hidden_paths.len(),
+            bytesize::to_string(result.hidden_bytes, false)
+        );
+        if include_hidden {
+            println!(
+                "{} {} includes {} - review before deleting",
+                Theme::warning("[!]"),
+                name,
+                summary
+            );
+        } else {
+            println!(
+                "{} {}: {} not included; use {} to review them",
+                Theme::muted("[i]"),
+                name,
+                summary,
+                Theme::command("--include-hidden")
+            );
+        }
+    }
+}
+
+/// List images that look alike. They're never part of the Duplicates
+/// totals and never cleaned; the groups are only shown with `-v`.
+fn print_similar_images(results: &ScanResults, mode: OutputMode) {
+    let groups = &results.similar_image_groups;
+    if groups.is_empty() {
+        return;
+    }
+    let total: u64 = groups.iter().map(|g| g.total_bytes()).sum();
+    println!(
+        "{} {} {} of similar images ({}) - review only, never cleaned",
+        Theme::muted("[i]"),
+        groups.len(),
+        if groups.len() == 1 { "group" } else { "groups" },
+        bytesize::to_string(total, false)
+    );
+    let show_groups = match mode {
+        OutputMode::Verbose => groups.len().min(5),
+        OutputMode::VeryVerbose => groups.len(),
+        _ => {
+            println!("    {}", Theme::muted("Use -v to list them"));
+            return;
+        }
+    };
+    for (idx, group) in groups.iter().take(show_groups).enumerate() {
+        println!(
+            "  {} Similar {} ({} images):",
+            Theme::muted("└─"),
+            idx + 1,
+            group.images.len()
+        );
+        for (path, size) in &group.images {
+            println!(
+                "     {} {} {}",
+                crate::utils::detect_file_type(path).emoji(),
+                Theme::muted(&path.display().to_string()),
+                Theme::muted(&format!("({})", bytesize::to_string(*size, false)))
+            );
+        }
+    }
+    if groups.len() > show_groups {
+        println!(
+            "  {} ... and {} more groups",
+            Theme::muted(""),
+            Theme::muted(&(groups.len() - show_groups).to_string())
+        );
+    }
+}
+
+/// List downloaded OneDrive files that "Free up space" could make
+/// online-only. They're never cleaned; the files are only shown with `-v`.
+fn print_cloud_files(results: &ScanResults, mode: OutputMode) {
+    let files = &results.cloud_files;
+    if files.is_empty() {
+        return;
+    }
+    let total: u64 = files.iter().map(|f| f.size_bytes).sum();
+    println!(
+        "{} {} downloaded OneDrive {} unused for a month ({}) - review only, never cleaned",
+        Theme::muted("[i]"),
+        files.len(),
+        if files.len() == 1 { "file" } else { "files" },
+        bytesize::to_string(total, false)
+    );
+    let shown = match mode {
+        OutputMode::Verbose => files.len().min(10),
+        OutputMode::VeryVerbose => files.len(),
+        _ => {
+            println!("    {}", Theme::muted("Use -v to list them"));
+            return;
+        }
+    };
+    for file in files.iter().take(shown) {
+        println!(
+            "  {} {} {} {}",
+            Theme::muted("└─"),
+            file.path.display(),
+            Theme::size(&bytesize::to_string(file.size_bytes, false)),
+            Theme::muted(&format!("({})", file.age.describe()))
+        );
+    }
+    if files.len() > shown {
+        println!(
+            "  {} ... and {} more",
+            Theme::muted(""),
+            Theme::muted(&(files.len() - shown).to_string())
+        );
+    }
+    println!(
+        "    {}",
+        Theme::muted("Right-click > Free up space in Explorer keeps them in OneDrive only")
+    );
+}
+
 /// Build a clean command based on the scan options used
 fn build_clean_command(options: Option<&ScanOptions>) -> String {
     let Some(opts) = options else {
@@ -437,13 +1332,17 @@ fn build_clean_command(options: Option<&ScanOptions>) -> String {
         opts.duplicates,
         opts.windows_update,
         opts.event_logs,
+        opts.orphaned,
+        opts.custom,
+        opts.ide,
+        opts.rust,
     ]
     .iter()
     .filter(|&&x| x)
     .count();
 
     // If all categories are enabled, use --all
-    if enabled_count == 15 {
+    if enabled_count == 19 {
         return "wole clean --all".to_string();
     }
 
@@ -494,6 +1393,18 @@ fn build_clean_command(options: Option<&ScanOptions>) -> String {
     if opts.event_logs {
         flags.push("--event-logs");
     }
+    if opts.orphaned {
+        flags.push("--orphaned");
+    }
+    if opts.custom {
+        flags.push("--custom");
+    }
+    if opts.ide {
+        flags.push("--ide");
+    }
+    if opts.rust {
+        flags.push("--rust");
+    }
 
     // If no flags (shouldn't happen, but be safe), fall back to --all
     if flags.is_empty() {
@@ -504,230 +1415,83 @@ fn build_clean_command(options: Option<&ScanOptions>) -> String {
 }
 
 pub fn print_json(results: &ScanResults) -> anyhow::Result<()> {
-    let json_results = JsonResults {
+    print_json_with_clean(results, None)
+}
+
+/// Print scan results as JSON, with the clean outcome (including per-path
+/// failure reasons and OS error codes) when a deletion log is given
+pub fn print_json_with_clean(
+    results: &ScanResults,
+    clean: Option<&DeletionLog>,
+) -> anyhow::Result<()> {
+    println!("{}", to_json(results, clean)?);
+    Ok(())
+}
+
+/// Scan results (and the clean outcome, if any) in the `--json` format
+pub fn to_json(results: &ScanResults, clean: Option<&DeletionLog>) -> anyhow::Result<String> {
+    let categories = Categories {
+        cache: category_report_with_recreate_costs(&results.cache),
+        app_cache: category_report(&results.app_cache),
+        temp: category_report(&results.temp),
+        trash: category_report_with_bin_items(&results.trash, results.trash_min_age_days),
+        build: category_report_with_git_flags(&results.build),
+        downloads: category_report(&results.downloads),
+        large: category_report_with_file_hints(&results.large),
+        old: category_report_with_ages(&results.old),
+        applications: category_report(&results.applications),
+        browser: category_report(&results.browser),
+        system: category_report(&results.system),
+        empty: category_report(&results.empty),
+        duplicates: category_report(&results.duplicates),
+        windows_update: category_report(&results.windows_update),
+        event_logs: category_report(&results.event_logs),
+        orphaned: category_report(&results.orphaned),
+        custom: category_report(&results.custom),
+        ide: category_report(&results.ide),
+        rust: category_report(&results.rust),
+    };
+    let report = ScanReport {
         version: "1.0".to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
-        categories: JsonCategories {
-            cache: JsonCategory {
-                items: results.cache.items,
-                size_bytes: results.cache.size_bytes,
-                size_human: results.cache.size_human(),
-                paths: results
-                    .cache
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            app_cache: JsonCategory {
-                items: results.app_cache.items,
-                size_bytes: results.app_cache.size_bytes,
-                size_human: results.app_cache.size_human(),
-                paths: results
-                    .app_cache
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            temp: JsonCategory {
-                items: results.temp.items,
-                size_bytes: results.temp.size_bytes,
-                size_human: results.temp.size_human(),
-                paths: results
-                    .temp
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            trash: JsonCategory {
-                items: results.trash.items,
-                size_bytes: results.trash.size_bytes,
-                size_human: results.trash.size_human(),
-                paths: results
-                    .trash
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            build: JsonCategory {
-                items: results.build.items,
-                size_bytes: results.build.size_bytes,
-                size_human: results.build.size_human(),
-                paths: results
-                    .build
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            downloads: JsonCategory {
-                items: results.downloads.items,
-                size_bytes: results.downloads.size_bytes,
-                size_human: results.downloads.size_human(),
-                paths: results
-                    .downloads
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            large: JsonCategory {
-                items: results.large.items,
-                size_bytes: results.large.size_bytes,
-                size_human: results.large.size_human(),
-                paths: results
-                    .large
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            old: JsonCategory {
-                items: results.old.items,
-                size_bytes: results.old.size_bytes,
-                size_human: results.old.size_human(),
-                paths: results
-                    .old
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            applications: JsonCategory {
-                items: results.applications.items,
-                size_bytes: results.applications.size_bytes,
-                size_human: results.applications.size_human(),
-                paths: results
-                    .applications
-                    .paths
+        summary: Summary::of(&categories),
+        categories,
+        volumes: volume_totals(results),
+        similar_images: results
+            .similar_image_groups
+            .iter()
+            .map(|group| {
+                group
+                    .images
                     .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            browser: JsonCategory {
-                items: results.browser.items,
-                size_bytes: results.browser.size_bytes,
-                size_human: results.browser.size_human(),
-                paths: results
-                    .browser
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            system: JsonCategory {
-                items: results.system.items,
-                size_bytes: results.system.size_bytes,
-                size_human: results.system.size_human(),
-                paths: results
-                    .system
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            empty: JsonCategory {
-                items: results.empty.items,
-                size_bytes: results.empty.size_bytes,
-                size_human: results.empty.size_human(),
-                paths: results
-                    .empty
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            duplicates: JsonCategory {
-                items: results.duplicates.items,
-                size_bytes: results.duplicates.size_bytes,
-                size_human: results.duplicates.size_human(),
-                paths: results
-                    .duplicates
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            windows_update: JsonCategory {
-                items: results.windows_update.items,
-                size_bytes: results.windows_update.size_bytes,
-                size_human: results.windows_update.size_human(),
-                paths: results
-                    .windows_update
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-            event_logs: JsonCategory {
-                items: results.event_logs.items,
-                size_bytes: results.event_logs.size_bytes,
-                size_human: results.event_logs.size_human(),
-                paths: results
-                    .event_logs
-                    .paths
-                    .iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect(),
-            },
-        },
-        summary: JsonSummary {
-            total_items: results.cache.items
-                + results.app_cache.items
-                + results.temp.items
-                + results.trash.items
-                + results.build.items
-                + results.downloads.items
-                + results.large.items
-                + results.old.items
-                + results.applications.items
-                + results.browser.items
-                + results.system.items
-                + results.empty.items
-                + results.duplicates.items
-                + results.windows_update.items
-                + results.event_logs.items,
-            total_bytes: results.cache.size_bytes
-                + results.app_cache.size_bytes
-                + results.temp.size_bytes
-                + results.trash.size_bytes
-                + results.build.size_bytes
-                + results.downloads.size_bytes
-                + results.large.size_bytes
-                + results.old.size_bytes
-                + results.applications.size_bytes
-                + results.browser.size_bytes
-                + results.system.size_bytes
-                + results.empty.size_bytes
-                + results.duplicates.size_bytes
-                + results.windows_update.size_bytes
-                + results.event_logs.size_bytes,
-            total_human: bytesize::to_string(
-                results.cache.size_bytes
-                    + results.app_cache.size_bytes
-                    + results.temp.size_bytes
-                    + results.trash.size_bytes
-                    + results.build.size_bytes
-                    + results.downloads.size_bytes
-                    + results.large.size_bytes
-                    + results.old.size_bytes
-                    + results.applications.size_bytes
-                    + results.browser.size_bytes
-                    + results.system.size_bytes
-                    + results.empty.size_bytes
-                    + results.duplicates.size_bytes
-                    + results.windows_update.size_bytes
-                    + results.event_logs.size_bytes,
-                true,
-            ),
-        },
+                    .map(|(path, size)| Image {
+                        path: path.display().to_string(),
+                        size_bytes: *size,
+                        size_human: bytesize::to_string(*size, false),
+                    })
+                    .collect()
+            })
+            .collect(),
+        cloud_files: results
+            .cloud_files
+            .iter()
+            .map(|file| CloudFileItem {
+                path: file.path.display().to_string(),
+                size_bytes: file.size_bytes,
+                size_human: bytesize::to_string(file.size_bytes, false),
+                unused_days: file.age.days,
+                basis: file.age.stamp.as_str().to_string(),
+            })
+            .collect(),
+        clean: clean.map(clean_report),
+        estimate: results.sampling.map(|sampling| Estimate {
+            seed: sampling.seed,
+            coverage_percent: (sampling.coverage_percent * 10.0).round() / 10.0,
+        }),
+        summary_only: results.summary_only,
     };
 
-    println!("{}", serde_json::to_string_pretty(&json_results)?);
-    Ok(())
+    Ok(report.to_json()?)
 }
 
 pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
@@ -756,6 +1520,10 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
         ("Application Cache", &results.app_cache),
         ("Browser Cache", &results.browser),
         ("Empty Folders", &results.empty),
+        ("Orphaned Owners", &results.orphaned),
+        ("Custom Rules", &results.custom),
+        ("IDE Caches", &results.ide),
+        ("Rust Toolchain", &results.rust),
     ];
 
     // Filter out categories with no items and sort by size descending
@@ -767,23 +1535,29 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
     let col_widths = [30, 10, 12];
 
     // Print table header with borders
-    print_table_separator(&col_widths, "┌", "┬", "┐");
-    print_table_row(&[
-        ("Category".to_string(), col_widths[0]),
-        ("Files".to_string(), col_widths[1]),
-        ("Size".to_string(), col_widths[2]),
-    ]);
-    print_table_separator(&col_widths, "├", "┼", "┤");
+    println!("{}", render::table_rule(&col_widths, Rule::Top));
+    println!(
+        "{}",
+        render::table_row(&[
+            ("Category".to_string(), col_widths[0]),
+            ("Files".to_string(), col_widths[1]),
+            ("Size".to_string(), col_widths[2]),
+        ])
+    );
+    println!("{}", render::table_rule(&col_widths, Rule::Middle));
 
     // Print category rows
     for (name, result) in &categories {
         let emoji = category_emoji(name);
         let category_display = format!("{} {}", emoji, name);
-        print_table_row(&[
-            (category_display, col_widths[0]),
-            (format_number(result.items as u64), col_widths[1]),
-            (result.size_human(), col_widths[2]),
-        ]);
+        println!(
+            "{}",
+            render::table_row(&[
+                (category_display, col_widths[0]),
+                (render::format_number(result.items as u64), col_widths[1]),
+                (result.size_human(), col_widths[2]),
+            ])
+        );
 
         // Special handling for duplicates: show groups in verbose mode
         if *name == "Duplicates" && (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
@@ -845,6 +1619,15 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
                     }
                 }
             }
+        } else if (*name == "Large Files" || *name == "Old Files")
+            && (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
+        {
+            print_grouped_paths(
+                result,
+                results.root.as_deref(),
+                mode,
+                *name == "Large Files",
+            );
         } else if (mode == OutputMode::Verbose || mode == OutputMode::VeryVerbose)
             && !result.paths.is_empty()
         {
@@ -854,10 +1637,37 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
             } else {
                 result.paths.len()
             };
+            let with_costs = *name == "Build Artifacts" || *name == "Package Cache";
+            let mut costs = Vec::new();
             for path in result.paths.iter().take(show_count) {
                 let file_type = crate::utils::detect_file_type(path);
                 let emoji = file_type.emoji();
-                println!("  {} {}", emoji, Theme::muted(&path.display().to_string()));
+                let cost = if with_costs {
+                    crate::recreate_cost::estimate(path, crate::utils::calculate_dir_size(path))
+                } else {
+                    None
+                };
+                match cost {
+                    Some(cost) => {
+                        println!(
+                            "  {} {}  {}",
+                            emoji,
+                            Theme::muted(&path.display().to_string()),
+                            Theme::warning(&format!("↻ {}", cost.describe()))
+                        );
+                        costs.push(cost);
+                    }
+                    None => {
+                        println!("  {} {}", emoji, Theme::muted(&path.display().to_string()))
+                    }
+                }
+            }
+            if let Some(summary) = crate::recreate_cost::summarize(&costs) {
+                println!(
+                    "  {} Cost to recreate the items above: {}",
+                    Theme::muted("└─"),
+                    summary
+                );
             }
             if result.paths.len() > show_count && mode == OutputMode::Verbose {
                 println!(
@@ -884,7 +1694,11 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
         + results.empty.items
         + results.duplicates.items
         + results.windows_update.items
-        + results.event_logs.items;
+        + results.event_logs.items
+        + results.orphaned.items
+        + results.custom.items
+        + results.ide.items
+        + results.rust.items;
     let total_bytes = results.cache.size_bytes
         + results.app_cache.size_bytes
         + results.temp.size_bytes
@@ -899,16 +1713,26 @@ pub fn print_analyze(results: &ScanResults, mode: OutputMode) {
         + results.empty.size_bytes
         + results.duplicates.size_bytes
         + results.windows_update.size_bytes
-        + results.event_logs.size_bytes;
+        + results.event_logs.size_bytes
+        + results.orphaned.size_bytes
+        + results.custom.size_bytes
+        + results.ide.size_bytes
+        + results.rust.size_bytes;
 
     // Print separator and total
-    print_table_separator(&col_widths, "├", "┼", "┤");
-    print_table_row(&[
-        ("Total".to_string(), col_widths[0]),
-        (format_number(total_items as u64), col_widths[1]),
-        (bytesize::to_string(total_bytes, false), col_widths[2]),
-    ]);
-    print_table_separator(&col_widths, "└", "┴", "┘");
+    println!("{}", render::table_rule(&col_widths, Rule::Middle));
+    println!(
+        "{}",
+        render::table_row(&[
+            ("Total".to_string(), col_widths[0]),
+            (render::format_number(total_items as u64), col_widths[1]),
+            (bytesize::to_string(total_bytes, false), col_widths[2]),
+        ])
+    );
+    println!("{}", render::table_rule(&col_widths, Rule::Bottom));
+    print_volume_subtotals(results);
+    print_similar_images(results, mode);
+    print_cloud_files(results, mode);
     println!();
 }
 
@@ -935,12 +1759,12 @@ pub fn print_disk_insights(
         Theme::header("Disk Insights"),
         Theme::primary(&root_path.display().to_string()),
         Theme::size(&bytesize::to_string(insights.total_size, false)),
-        Theme::value(&format_number(insights.total_files))
+        Theme::value(&render::format_number(insights.total_files))
     );
     println!();
 
     // Show root with 100% bar
-    let root_bar = render_progress_bar(100.0, 20);
+    let root_bar = render::progress_bar(100.0, 20);
     println!(
         "{}  {}  {}  {}",
         Theme::secondary("#"),
@@ -949,13 +1773,20 @@ pub fn print_disk_insights(
         Theme::size(&bytesize::to_string(insights.total_size, false))
     );
     println!("   {}", Theme::muted(&root_path.display().to_string()));
+    if let Some(summary) = insights.root.breakdown_summary(4) {
+        println!(
+            "   {} {}",
+            Theme::secondary("What's taking space here:"),
+            Theme::value(&summary)
+        );
+    }
     println!();
 
     // Show top folders
     for (i, folder) in top_folders.iter().enumerate() {
         let num = i + 1;
         let size_str = bytesize::to_string(folder.size, false);
-        let files_str = format_number(folder.file_count);
+        let files_str = render::format_number(folder.file_count);
 
         // Get display name - use relative path from root if it's deeper than one level
         let display_name = if folder.path != root_path && folder.path.starts_with(root_path) {
@@ -982,7 +1813,7 @@ pub fn print_disk_insights(
         } else {
             0.0
         };
-        let bar = render_progress_bar(root_percentage, 20);
+        let bar = render::progress_bar(root_percentage, 20);
 
         println!(
             "{}  {}  {}  {}  {}  {}",
@@ -993,6 +1824,9 @@ pub fn print_disk_insights(
             Theme::category(&display_name),
             Theme::muted(&format!("({} files)", files_str))
         );
+        if let Some(summary) = folder.breakdown_summary(3) {
+            println!("   {}", Theme::muted(&summary));
+        }
     }
 
     // Show largest files if available
@@ -1001,7 +1835,7 @@ pub fn print_disk_insights(
         println!("{}", Theme::divider(60));
         println!();
         println!("{}", Theme::primary("Largest Files:"));
-        for (file_path, size) in insights.largest_files.iter().take(5) {
+        for (file_path, size) in &insights.largest_files {
             let relative = crate::utils::to_relative_path(file_path, root_path);
             println!(
                 "  {}  {}",
@@ -1011,6 +1845,26 @@ pub fn print_disk_insights(
         }
     }
 
+    if !insights.mounted_volumes.is_empty() {
+        println!();
+        println!("{}", Theme::divider(60));
+        println!();
+        println!(
+            "{}",
+            Theme::primary("Mounted Volumes (other disks, not counted above):")
+        );
+        for mount in &insights.mounted_volumes {
+            println!(
+                "  {}  {}",
+                Theme::category(&crate::utils::to_relative_path(mount, root_path)),
+                Theme::muted(&format!(
+                    "-> run wole analyze --disk --path \"{}\"",
+                    crate::utils::display_path(mount)
+                ))
+            );
+        }
+    }
+
     println!();
     if mode == OutputMode::Normal || mode == OutputMode::Verbose {
         println!(
@@ -1021,27 +1875,161 @@ pub fn print_disk_insights(
     println!();
 }
 
-/// Render a progress bar with filled and empty blocks
-fn render_progress_bar(percentage: f64, width: usize) -> String {
-    let filled = (percentage / 100.0 * width as f64).round() as usize;
-    let filled = filled.min(width);
-    let empty = width.saturating_sub(filled);
-    format!(
-        "{}{}",
-        Theme::size(&"█".repeat(filled)),
-        Theme::muted(&"░".repeat(empty))
-    )
-}
+/// Print an alternate data stream report (`wole analyze --ads`)
+pub fn print_ads_report(
+    report: &crate::ads::AdsReport,
+    root_path: &std::path::Path,
+    zoned_downloads: &[(std::path::PathBuf, u64)],
+    min_age_days: u64,
+    mode: OutputMode,
+) {
+    if mode == OutputMode::Quiet {
+        return;
+    }
 
-/// Format number with commas
-fn format_number(n: u64) -> String {
-    let s = n.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
+    println!();
+    println!(
+        "{}  {}  |  {} files  |  {} with streams",
+        Theme::header("Alternate Data Streams"),
+        Theme::primary(&root_path.display().to_string()),
+        Theme::value(&render::format_number(report.files_scanned)),
+        Theme::value(&render::format_number(report.files_with_streams))
+    );
+    println!();
+    println!(
+        "  {} {} streams, {} total",
+        Theme::muted("→"),
+        Theme::value(&render::format_number(report.stream_count)),
+        Theme::size(&bytesize::to_string(report.total_bytes, false))
+    );
+    println!(
+        "  {} {} files marked as downloaded from the internet (Zone.Identifier)",
+        Theme::muted("→"),
+        Theme::value(&render::format_number(report.zone_identifier_files))
+    );
+
+    if !report.suspicious.is_empty() {
+        println!();
+        println!("{}", Theme::divider(60));
+        println!();
+        println!("{}", Theme::primary("Large or unusual streams:"));
+        let show = match mode {
+            OutputMode::Verbose | OutputMode::VeryVerbose => report.suspicious.len(),
+            _ => 10.min(report.suspicious.len()),
+        };
+        for (path, name, size) in report.suspicious.iter().take(show) {
+            let relative = crate::utils::to_relative_path(path, root_path);
+            println!(
+                "  {}  {}{}",
+                Theme::size(&bytesize::to_string(*size, false)),
+                Theme::muted(&relative),
+                Theme::warning(&format!(":{}", name))
+            );
         }
-        result.push(c);
+        if show < report.suspicious.len() {
+            println!(
+                "  {}",
+                Theme::muted(&format!(
+                    "... and {} more (use -v to show all)",
+                    report.suspicious.len() - show
+                ))
+            );
+        }
+    }
+
+    if !zoned_downloads.is_empty() {
+        let bytes: u64 = zoned_downloads.iter().map(|(_, size)| size).sum();
+        println!();
+        println!(
+            "  {} {} downloads older than {} days still carry Zone.Identifier ({})",
+            Theme::muted("→"),
+            Theme::value(&render::format_number(zoned_downloads.len() as u64)),
+            min_age_days,
+            Theme::size(&bytesize::to_string(bytes, false))
+        );
+        println!(
+            "    Run {} to remove the mark (files are kept)",
+            Theme::command("wole analyze --ads --strip-zone-ids")
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_and_old_paths_group_by_top_level_folder() {
+        let root = Path::new("/d");
+        let paths: Vec<PathBuf> = [
+            "/d/media/a.mkv",
+            "/d/media/show/b.mkv",
+            "/d/games/c.pak",
+            "/d/notes.txt",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        let size = |p: &Path| match p.file_name().and_then(|n| n.to_str()) {
+            Some("a.mkv") => 10,
+            Some("b.mkv") => 30,
+            Some("c.pak") => 25,
+            _ => 1,
+        };
+
+        let groups = group_by_top_folder(&paths, Some(root), size);
+        let summary: Vec<(PathBuf, u64, usize)> = groups
+            .iter()
+            .map(|g| (g.folder.clone(), g.bytes, g.paths.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (PathBuf::from("/d/media"), 40, 2),
+                (PathBuf::from("/d/games"), 25, 1),
+                (PathBuf::from("/d"), 1, 1),
+            ]
+        );
+        assert_eq!(groups[0].paths[0].0, PathBuf::from("/d/media/show/b.mkv"));
+
+        // Outside the scan root the folder they share is used instead
+        let groups = group_by_top_folder(&paths[..3], Some(Path::new("/e")), size);
+        assert_eq!(groups[0].folder, PathBuf::from("/d/media"));
+        assert_eq!(groups[1].folder, PathBuf::from("/d/games"));
+    }
+
+    #[test]
+    fn merging_roots_counts_shared_paths_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared.bin");
+        let extra = temp_dir.path().join("extra.bin");
+        std::fs::write(&shared, vec![0u8; 10]).unwrap();
+        std::fs::write(&extra, vec![0u8; 7]).unwrap();
+
+        let category = |paths: &[&Path], size_bytes| CategoryResult {
+            items: paths.len(),
+            size_bytes,
+            paths: paths.iter().map(|p| p.to_path_buf()).collect(),
+            ..Default::default()
+        };
+        let mut merged = ScanResults {
+            temp: category(&[Path::new("/tmp/a")], 5),
+            build: category(&[Path::new("/c/dev/target")], 100),
+            large: category(&[&shared], 10),
+            ..Default::default()
+        };
+        merged.merge(ScanResults {
+            temp: category(&[Path::new("/tmp/a")], 5),
+            build: category(&[Path::new("/d/projects/target")], 40),
+            large: category(&[&shared, &extra], 17),
+            ..Default::default()
+        });
+
+        // Root-independent results aren't doubled
+        assert_eq!((merged.temp.items, merged.temp.size_bytes), (1, 5));
+        assert_eq!((merged.build.items, merged.build.size_bytes), (2, 140));
+        // Partial overlap: only the new path is added, measured from disk
+        assert_eq!((merged.large.items, merged.large.size_bytes), (2, 17));
     }
-    result.chars().rev().collect()
 }
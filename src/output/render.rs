@@ -0,0 +1,243 @@
+//! Formatting helpers behind the CLI output
+//!
+//! These build strings and never print, so tools embedding the library can
+//! produce the same tables, bars and sizes as `wole` itself. Styling comes
+//! from `Theme`. Width calculations count terminal columns: ANSI color codes
+//! (from a caller's own coloring) take none and wide characters such as emoji
+//! take two.
+//!
+//! ```
+//! use wole::output::render::{self, Rule};
+//!
+//! let widths = [10, 8];
+//! let table = [
+//!     render::table_rule(&widths, Rule::Top),
+//!     render::table_row(&[("Cache".to_string(), 10), (render::format_size(1_500_000), 8)]),
+//!     render::table_rule(&widths, Rule::Bottom),
+//! ]
+//! .join("\n");
+//! assert!(table.starts_with("┌────────────┬──────────┐"));
+//! ```
+
+use crate::theme::Theme;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSIS: &str = "…";
+const RESET: &str = "\x1b[0m";
+
+/// Remove ANSI color sequences (`ESC [ ... m`)
+pub fn strip_ansi(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// Terminal columns `s` takes, ignoring color codes
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(strip_ansi(s).as_str())
+}
+
+/// Cut `s` to at most `max_width` columns, ending with "…" when shortened.
+/// Color codes are kept, and a cut colored string is reset so the color
+/// doesn't leak.
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    let target = max_width.saturating_sub(UnicodeWidthStr::width(ELLIPSIS));
+
+    let mut out = String::new();
+    let mut width = 0usize;
+    let mut colored = false;
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            colored = true;
+            out.push(ch);
+            if chars.peek() == Some(&'[') {
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        let cw = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if width + cw > target {
+            break;
+        }
+        out.push(ch);
+        width += cw;
+    }
+    out.push_str(ELLIPSIS);
+    if colored {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Truncate or pad `s` with spaces on the right to exactly `width` columns
+pub fn pad_right(s: &str, width: usize) -> String {
+    let truncated = truncate_to_width(s, width);
+    let w = display_width(&truncated);
+    format!("{}{}", truncated, " ".repeat(width.saturating_sub(w)))
+}
+
+/// Truncate or pad `s` with spaces on the left to exactly `width` columns
+pub fn pad_left(s: &str, width: usize) -> String {
+    let truncated = truncate_to_width(s, width);
+    let w = display_width(&truncated);
+    format!("{}{}", " ".repeat(width.saturating_sub(w)), truncated)
+}
+
+/// Which horizontal rule of a table to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// A horizontal table rule in box-drawing characters. `widths` are content
+/// widths, as passed to `table_row`.
+pub fn table_rule(widths: &[usize], rule: Rule) -> String {
+    let (left, mid, right) = match rule {
+        Rule::Top => ("┌", "┬", "┐"),
+        Rule::Middle => ("├", "┼", "┤"),
+        Rule::Bottom => ("└", "┴", "┘"),
+    };
+    let mut line = left.to_string();
+    for (i, width) in widths.iter().enumerate() {
+        if i > 0 {
+            line.push_str(mid);
+        }
+        // +2 for the 1-space padding on each side of the cell
+        line.push_str(&"─".repeat(width + 2));
+    }
+    line.push_str(right);
+    line
+}
+
+/// A bordered table row; each cell is (content, width) and gets 1 space of
+/// padding on each side
+pub fn table_row(cells: &[(String, usize)]) -> String {
+    let mut row = String::from("│");
+    for (content, width) in cells {
+        row.push(' ');
+        row.push_str(&pad_right(content, *width));
+        row.push(' ');
+        row.push('│');
+    }
+    row
+}
+
+/// A complete bordered table: header, a rule, then `rows`, one line each
+pub fn table(widths: &[usize], header: &[String], rows: &[Vec<String>]) -> String {
+    let cells = |row: &[String]| -> Vec<(String, usize)> {
+        row.iter().cloned().zip(widths.iter().copied()).collect()
+    };
+    let mut lines = vec![table_rule(widths, Rule::Top), table_row(&cells(header))];
+    lines.push(table_rule(widths, Rule::Middle));
+    lines.extend(rows.iter().map(|row| table_row(&cells(row))));
+    lines.push(table_rule(widths, Rule::Bottom));
+    lines.join("\n")
+}
+
+/// A bar of `width` blocks, `percentage` of them filled
+pub fn progress_bar(percentage: f64, width: usize) -> String {
+    let filled = (percentage / 100.0 * width as f64).round() as usize;
+    let filled = filled.min(width);
+    let empty = width.saturating_sub(filled);
+    format!(
+        "{}{}",
+        Theme::size(&"█".repeat(filled)),
+        Theme::muted(&"░".repeat(empty))
+    )
+}
+
+/// One block per value, scaled between the smallest and largest: "▁▃▅█"
+pub fn sparkline(values: &[u64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let span = (max - min).max(1) as f64;
+    values
+        .iter()
+        .map(|&v| BLOCKS[(((v - min) as f64 / span) * 7.0).round() as usize])
+        .collect()
+}
+
+/// Number with thousands separators, e.g. "1,234,567"
+pub fn format_number(n: u64) -> String {
+    let s = n.to_string();
+    let mut result = String::new();
+    for (i, c) in s.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    result.chars().rev().collect()
+}
+
+/// Size in decimal units, e.g. "1.5 MB", as used throughout the CLI
+pub fn format_size(bytes: u64) -> String {
+    bytesize::to_string(bytes, false)
+}
+
+/// Coarse age of `secs` seconds: "45m", "6h", "12d"
+pub fn format_age(secs: u64) -> String {
+    if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / 86400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_ignore_color_codes() {
+        let red = "\x1b[31mhello\x1b[0m";
+        assert_eq!(strip_ansi(red), "hello");
+        assert_eq!(display_width(red), 5);
+        assert_eq!(display_width("📦 x"), 4);
+
+        assert_eq!(truncate_to_width("hello world", 6), "hello…");
+        assert_eq!(truncate_to_width(red, 3), "\x1b[31mhe…\x1b[0m");
+        assert_eq!(pad_right(red, 7), format!("{}  ", red));
+        assert_eq!(pad_left("42", 4), "  42");
+
+        let row = table_row(&[(red.to_string(), 6), ("1".to_string(), 2)]);
+        assert_eq!(
+            display_width(&row),
+            display_width(&table_rule(&[6, 2], Rule::Top))
+        );
+        assert_eq!(table(&[3], &["a".to_string()], &[]).lines().count(), 4);
+
+        assert_eq!(format_number(1_234_567), "1,234,567");
+        assert_eq!(format_size(1_500_000), "1.5 MB");
+        assert_eq!(format_age(7200), "2h");
+    }
+}
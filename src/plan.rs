@@ -0,0 +1,421 @@
+//! Reviewable cleanup plans
+//!
+//! `wole plan --all -o cleanup.wole` writes down exactly what a clean would
+//! delete: each path with its category, size and how it goes away (Recycle
+//! Bin, permanent, hardlink, Recycle Bin purge), plus a fingerprint of the
+//! path's metadata. Once the plan has been reviewed, `wole apply
+//! cleanup.wole` re-reads every fingerprint and deletes only if all of them
+//! still match. A single changed or missing path aborts the whole plan
+//! before anything is touched.
+//!
+//! Plans are signed with a key kept in wole's data folder, so edits made to
+//! the file after it was written are caught, and a plan only applies for the
+//! account that wrote it.
+
+use crate::app_data;
+use crate::categories::duplicates::DedupeMode;
+use crate::output::{DuplicateGroup, ScanResults};
+use crate::selection;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+/// Bumped when the plan format changes
+pub const PLAN_VERSION: u32 = 1;
+
+/// How a planned path is removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// Moved to the Recycle Bin
+    RecycleBin,
+    /// Deleted without going through the Recycle Bin
+    Permanent,
+    /// Replaced by a hardlink to the copy in `keep`
+    Hardlink,
+    /// Purged from the Recycle Bin
+    EmptyRecycleBin,
+}
+
+/// One path a plan deletes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanEntry {
+    /// CLI key of the category (`build`, `cache`, ...)
+    pub category: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub strategy: Strategy,
+    /// Hash of the path's metadata (and of everything under it for folders)
+    pub fingerprint: String,
+    /// Copy the path is hardlinked to (`Strategy::Hardlink` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep: Option<PathBuf>,
+}
+
+/// A signed list of deletions written by `wole plan`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    pub version: u32,
+    pub wole_version: String,
+    pub created_at: DateTime<Utc>,
+    pub root: PathBuf,
+    pub permanent: bool,
+    pub take_ownership: bool,
+    pub trash_min_age_days: u64,
+    pub entries: Vec<PlanEntry>,
+    /// Keyed hash over everything above
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// A planned path that no longer matches the filesystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    pub path: PathBuf,
+    pub reason: &'static str,
+}
+
+/// Hash a path's metadata: type, size and modification time, and for a
+/// folder the same for every entry below it (by relative path)
+pub fn fingerprint(path: &Path) -> Result<String> {
+    let meta = std::fs::symlink_metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut hasher = blake3::Hasher::new();
+    hash_metadata(&mut hasher, Path::new(""), &meta);
+    if meta.is_dir() {
+        for entry in WalkDir::new(path)
+            .min_depth(1)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if let Ok(meta) = entry.metadata() {
+                let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+                hash_metadata(&mut hasher, relative, &meta);
+            }
+        }
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn hash_metadata(hasher: &mut blake3::Hasher, relative: &Path, meta: &std::fs::Metadata) {
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    hasher.update(relative.as_os_str().as_encoded_bytes());
+    hasher.update(&[0, meta.is_dir() as u8, meta.file_type().is_symlink() as u8]);
+    hasher.update(&meta.len().to_le_bytes());
+    hasher.update(&modified.to_le_bytes());
+}
+
+impl Plan {
+    /// Plan the deletion of everything in `results`.
+    ///
+    /// Installed applications are left out: uninstalling needs details only
+    /// the scan that found them has. Paths that vanished since the scan are
+    /// skipped.
+    pub fn build(
+        results: &ScanResults,
+        root: &Path,
+        permanent: bool,
+        dedupe_mode: DedupeMode,
+        take_ownership: bool,
+    ) -> Self {
+        let copies: HashSet<&PathBuf> = results.duplicates.paths.iter().collect();
+        let kept: BTreeMap<&PathBuf, &PathBuf> = results
+            .duplicates_groups
+            .iter()
+            .flatten()
+            .filter_map(|group| {
+                let original = group.paths.iter().find(|p| !copies.contains(p))?;
+                Some(
+                    group
+                        .paths
+                        .iter()
+                        .filter(|p| copies.contains(p))
+                        .map(move |copy| (copy, original)),
+                )
+            })
+            .flatten()
+            .collect();
+
+        let entries = selection::items(results)
+            .into_iter()
+            .filter(|item| item.category != "applications")
+            .filter_map(|item| {
+                let (strategy, keep) = match item.category {
+                    "trash" => (Strategy::EmptyRecycleBin, None),
+                    "duplicates" if dedupe_mode == DedupeMode::Hardlink => {
+                        (Strategy::Hardlink, Some((*kept.get(&item.path)?).clone()))
+                    }
+                    _ if permanent => (Strategy::Permanent, None),
+                    _ => (Strategy::RecycleBin, None),
+                };
+                Some(PlanEntry {
+                    category: item.category.to_string(),
+                    fingerprint: fingerprint(&item.path).ok()?,
+                    path: item.path,
+                    size: item.size,
+                    strategy,
+                    keep,
+                })
+            })
+            .collect();
+
+        Self {
+            version: PLAN_VERSION,
+            wole_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            root: root.to_path_buf(),
+            permanent,
+            take_ownership,
+            trash_min_age_days: results.trash_min_age_days,
+            entries,
+            signature: String::new(),
+        }
+    }
+
+    /// Total bytes the plan frees
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    fn signature_with(&self, key: &[u8; 32]) -> Result<String> {
+        let unsigned = Plan {
+            signature: String::new(),
+            ..self.clone()
+        };
+        let bytes = serde_json::to_vec(&unsigned)?;
+        Ok(blake3::keyed_hash(key, &bytes).to_hex().to_string())
+    }
+
+    fn sign_with(&mut self, key: &[u8; 32]) -> Result<()> {
+        self.signature = self.signature_with(key)?;
+        Ok(())
+    }
+
+    fn verify_with(&self, key: &[u8; 32]) -> Result<()> {
+        if self.version != PLAN_VERSION {
+            bail!(
+                "Plan format {} isn't supported by this version of wole (expected {})",
+                self.version,
+                PLAN_VERSION
+            );
+        }
+        if self.signature.is_empty() || self.signature != self.signature_with(key)? {
+            bail!("Plan signature doesn't match: the file was edited, or written by another account or machine");
+        }
+        Ok(())
+    }
+
+    /// Planned paths that are missing or changed since the plan was written.
+    /// The Recycle Bin counts as changed when its contents differ.
+    pub fn drift(&self) -> Vec<Drift> {
+        let mut drift = Vec::new();
+        for entry in &self.entries {
+            let reason = match fingerprint(&entry.path) {
+                Err(_) => Some("missing"),
+                Ok(current) if current != entry.fingerprint => Some("changed"),
+                Ok(_) => None,
+            };
+            if let Some(reason) = reason {
+                drift.push(Drift {
+                    path: entry.path.clone(),
+                    reason,
+                });
+            }
+            if let Some(ref keep) = entry.keep {
+                if !keep.exists() {
+                    drift.push(Drift {
+                        path: keep.clone(),
+                        reason: "kept copy missing",
+                    });
+                }
+            }
+        }
+
+        let planned: HashSet<&PathBuf> = self
+            .entries
+            .iter()
+            .filter(|e| e.strategy == Strategy::EmptyRecycleBin)
+            .map(|e| &e.path)
+            .collect();
+        if !planned.is_empty() {
+            let current = crate::categories::trash::scan(
+                self.trash_min_age_days,
+                &crate::utils::SizeWalk::default(),
+            )
+            .map(|r| r.paths)
+            .unwrap_or_default();
+            if current.iter().any(|p| !planned.contains(p)) {
+                drift.push(Drift {
+                    path: PathBuf::from("Recycle Bin"),
+                    reason: "has items the plan doesn't list",
+                });
+            }
+        }
+        drift
+    }
+
+    /// What the duplicate copies are deduplicated with
+    pub fn dedupe_mode(&self) -> DedupeMode {
+        if self
+            .entries
+            .iter()
+            .any(|e| e.strategy == Strategy::Hardlink)
+        {
+            DedupeMode::Hardlink
+        } else {
+            DedupeMode::Delete
+        }
+    }
+
+    /// The planned entries in the shape the cleaner works from
+    pub fn scan_results(&self) -> ScanResults {
+        let mut results = ScanResults {
+            root: Some(self.root.clone()),
+            trash_min_age_days: self.trash_min_age_days,
+            ..Default::default()
+        };
+        let mut groups: BTreeMap<&PathBuf, DuplicateGroup> = BTreeMap::new();
+        for entry in &self.entries {
+            let Some(category) = results.category_mut(&entry.category) else {
+                continue;
+            };
+            category.paths.push(entry.path.clone());
+            category.items += 1;
+            category.size_bytes += entry.size;
+            if let Some(ref keep) = entry.keep {
+                groups
+                    .entry(keep)
+                    .or_insert_with(|| DuplicateGroup {
+                        hash: String::new(),
+                        size: entry.size,
+                        paths: vec![keep.clone()],
+                    })
+                    .paths
+                    .push(entry.path.clone());
+            }
+        }
+        if !groups.is_empty() {
+            results.duplicates_groups = Some(groups.into_values().collect());
+        }
+        results
+    }
+}
+
+/// Where the plan signing key lives: %LOCALAPPDATA%\wole\plan.key
+fn key_path() -> PathBuf {
+    app_data::data_dir().join("plan.key")
+}
+
+/// The signing key: 32 bytes of OS randomness, created on first use and
+/// readable only by the current user
+fn signing_key() -> Result<[u8; 32]> {
+    let path = key_path();
+    if let Ok(bytes) = std::fs::read(&path) {
+        if let Ok(key) = <[u8; 32]>::try_from(bytes.as_slice()) {
+            return Ok(key);
+        }
+    }
+
+    let mut key = [0u8; 32];
+    getrandom::getrandom(&mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to generate a plan key: {}", e))?;
+    app_data::write_private(&path, &key).context("Failed to save the plan key")?;
+    Ok(key)
+}
+
+/// Sign `plan` and write it to `out`
+pub fn save(plan: &mut Plan, out: &Path) -> Result<()> {
+    plan.sign_with(&signing_key()?)?;
+    let json = serde_json::to_string_pretty(plan)?;
+    std::fs::write(out, json).with_context(|| format!("Failed to write plan to {}", out.display()))
+}
+
+/// Load a plan written by `save`, checking its signature
+pub fn load(path: &Path) -> Result<Plan> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read plan {}", path.display()))?;
+    let plan: Plan = serde_json::from_str(&content).context("Not a valid wole plan")?;
+    plan.verify_with(&signing_key()?)?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::CategoryResult;
+
+    #[test]
+    fn plan_is_signed_and_detects_drift() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let build = temp_dir.path().join("target");
+        let log = temp_dir.path().join("old.log");
+        std::fs::create_dir(&build).unwrap();
+        std::fs::write(build.join("lib.rlib"), b"artifact").unwrap();
+        std::fs::write(&log, b"log").unwrap();
+
+        let results = ScanResults {
+            build: CategoryResult {
+                items: 1,
+                size_bytes: 8,
+                paths: vec![build.clone()],
+                ..Default::default()
+            },
+            temp: CategoryResult {
+                items: 2,
+                size_bytes: 3,
+                paths: vec![log.clone(), temp_dir.path().join("gone.tmp")],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let key = [7u8; 32];
+        let mut plan = Plan::build(&results, temp_dir.path(), true, DedupeMode::Delete, false);
+        plan.sign_with(&key).unwrap();
+
+        // The path that no longer exists is left out
+        assert_eq!(plan.entries.len(), 2);
+        assert!(plan
+            .entries
+            .iter()
+            .all(|e| e.strategy == Strategy::Permanent));
+        assert!(plan.verify_with(&key).is_ok());
+        assert!(plan.verify_with(&[8u8; 32]).is_err());
+        assert!(plan.drift().is_empty());
+
+        let rebuilt = plan.scan_results();
+        assert_eq!(rebuilt.build.paths, vec![build.clone()]);
+        assert_eq!((rebuilt.temp.items, rebuilt.temp.size_bytes), (1, 3));
+
+        let mut edited = plan.clone();
+        edited.entries[0].path = temp_dir.path().join("elsewhere");
+        assert!(edited.verify_with(&key).is_err());
+
+        std::fs::write(build.join("new.rlib"), b"x").unwrap();
+        std::fs::remove_file(&log).unwrap();
+        let drift = plan.drift();
+        assert_eq!(
+            drift,
+            vec![
+                Drift {
+                    path: log,
+                    reason: "missing"
+                },
+                Drift {
+                    path: build,
+                    reason: "changed"
+                },
+            ]
+        );
+    }
+}
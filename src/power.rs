@@ -0,0 +1,173 @@
+//! Power and connection checks before IO-heavy scans
+//!
+//! Duplicate detection, disk insights and the deep first-scan baseline read a
+//! lot of data. On a laptop running on battery that can drain it noticeably,
+//! so these scans first call `check()`; depending on `[power]` config the
+//! result is either a warning or a request to defer the scan.
+
+use crate::config::PowerSettings;
+use crate::theme::Theme;
+use anyhow::Result;
+
+/// Snapshot of the machine's power and connection state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PowerState {
+    /// Running on battery (not plugged in)
+    pub on_battery: bool,
+    /// Battery charge, if a battery is present
+    pub battery_percent: Option<f32>,
+    /// The active internet connection is metered (mobile data, tethering)
+    pub metered: bool,
+}
+
+/// What to tell the user before a heavy scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerAdvice {
+    pub message: String,
+    /// Config asks to postpone the scan rather than only warn
+    pub defer: bool,
+}
+
+/// Read the current power state
+pub fn current() -> PowerState {
+    let power = crate::status::gather_power_metrics();
+    PowerState {
+        // battery crate states: Unknown, Charging, Discharging, Empty, Full
+        on_battery: power
+            .as_ref()
+            .map(|p| p.status == "Discharging" || p.status == "Empty")
+            .unwrap_or(false),
+        battery_percent: power.map(|p| p.level_percent),
+        metered: is_metered(),
+    }
+}
+
+/// Whether the active internet connection is metered (best effort)
+#[cfg(windows)]
+pub fn is_metered() -> bool {
+    use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+
+    let cost = NetworkInformation::GetInternetConnectionProfile()
+        .and_then(|profile| profile.GetConnectionCost());
+    match cost {
+        Ok(cost) => {
+            let cost_type = cost.NetworkCostType().unwrap_or(NetworkCostType::Unknown);
+            cost_type == NetworkCostType::Fixed
+                || cost_type == NetworkCostType::Variable
+                || cost.Roaming().unwrap_or(false)
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(windows))]
+pub fn is_metered() -> bool {
+    false
+}
+
+/// Decide whether a heavy scan deserves a warning given `state`
+pub fn advise(settings: &PowerSettings, state: &PowerState) -> Option<PowerAdvice> {
+    let low_battery = match state.battery_percent {
+        Some(percent) if settings.check_battery && state.on_battery => {
+            (percent < settings.min_battery_percent as f32).then_some(percent)
+        }
+        _ => None,
+    };
+    let metered = settings.warn_on_metered && state.metered;
+
+    let mut reasons = Vec::new();
+    if let Some(percent) = low_battery {
+        reasons.push(format!("on battery at {:.0}%", percent));
+    }
+    if metered {
+        reasons.push("on a metered connection".to_string());
+    }
+    if reasons.is_empty() {
+        return None;
+    }
+
+    Some(PowerAdvice {
+        message: format!("This scan is disk-heavy and you're {}", reasons.join(", ")),
+        defer: low_battery.is_some() && settings.defer_on_low_battery,
+    })
+}
+
+/// Check the current state before a heavy scan
+pub fn check(settings: &PowerSettings) -> Option<PowerAdvice> {
+    if !settings.check_battery && !settings.warn_on_metered {
+        return None;
+    }
+    advise(settings, &current())
+}
+
+/// CLI guard for heavy scans: prints a warning, or fails if config says to defer
+pub fn guard_cli(settings: &PowerSettings, quiet: bool) -> Result<()> {
+    let Some(advice) = check(settings) else {
+        return Ok(());
+    };
+    if advice.defer {
+        anyhow::bail!(
+            "{}. Deferring; plug in and retry, or set power.defer_on_low_battery = false",
+            advice.message
+        );
+    }
+    if !quiet {
+        eprintln!("{}", Theme::warning_msg(&advice.message));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_only_when_unplugged_below_threshold() {
+        let settings = PowerSettings::default();
+        let plugged = PowerState {
+            on_battery: false,
+            battery_percent: Some(10.0),
+            metered: false,
+        };
+        assert!(advise(&settings, &plugged).is_none());
+
+        let low = PowerState {
+            on_battery: true,
+            battery_percent: Some(12.0),
+            metered: false,
+        };
+        let advice = advise(&settings, &low).unwrap();
+        assert!(advice.message.contains("12%"));
+        assert!(!advice.defer);
+
+        let charged = PowerState {
+            battery_percent: Some(80.0),
+            ..low.clone()
+        };
+        assert!(advise(&settings, &charged).is_none());
+    }
+
+    #[test]
+    fn defers_only_when_configured() {
+        let settings = PowerSettings {
+            defer_on_low_battery: true,
+            ..PowerSettings::default()
+        };
+        let low = PowerState {
+            on_battery: true,
+            battery_percent: Some(5.0),
+            metered: true,
+        };
+        let advice = advise(&settings, &low).unwrap();
+        assert!(advice.defer);
+        assert!(advice.message.contains("metered"));
+
+        // Metered alone is only ever a warning
+        let metered_only = PowerState {
+            on_battery: false,
+            battery_percent: None,
+            metered: true,
+        };
+        assert!(!advise(&settings, &metered_only).unwrap().defer);
+    }
+}
@@ -1,8 +1,155 @@
-use indicatif::{ProgressBar, ProgressStyle};
-use std::time::Duration;
+//! Progress bars and spinners
+//!
+//! With `--progress plain` (the default when stderr isn't a terminal) bars
+//! aren't drawn; instead a line such as
+//! `[00:42] Cleaning cache: 12,400/51,200 items, 8.2 GB freed, 3 errors`
+//! is printed to stderr every `PLAIN_INTERVAL` or `PLAIN_STEP_PERCENT` of
+//! progress, so CI logs stay readable.
 
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::output::render::format_number;
 use crate::spinner;
 
+/// Longest gap between plain progress lines
+const PLAIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Progress (in percent of the total) that prints a plain line early
+const PLAIN_STEP_PERCENT: u64 = 10;
+
+/// How often plain progress is checked
+const PLAIN_POLL: Duration = Duration::from_millis(250);
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Bytes freed and failed deletions of the running clean; None until the
+/// first deletion is recorded
+static TALLY: Mutex<Option<(u64, u64)>> = Mutex::new(None);
+
+/// Pick how progress is shown: `bar`, `plain`, or `auto` (plain when
+/// stderr isn't a terminal)
+pub fn set_mode(mode: &str) {
+    let plain = match mode {
+        "plain" => true,
+        "bar" => false,
+        _ => !std::io::stderr().is_terminal(),
+    };
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether progress is printed as plain lines
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Count a deletion toward the freed/errors shown in plain progress lines
+pub fn record_deletion(size_bytes: u64, success: bool) {
+    if let Ok(mut tally) = TALLY.lock() {
+        let (freed, errors) = tally.get_or_insert((0, 0));
+        if success {
+            *freed += size_bytes;
+        } else {
+            *errors += 1;
+        }
+    }
+}
+
+/// What a bar counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Items,
+    Bytes,
+}
+
+/// Set the message and start drawing: a steady tick for bars, or a
+/// reporter thread in plain mode
+fn start(pb: ProgressBar, msg: &str, tick_ms: u64, unit: Unit) -> ProgressBar {
+    pb.set_message(msg.to_string());
+    if is_plain() {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+        if let Ok(mut tally) = TALLY.lock() {
+            *tally = None;
+        }
+        report_plain(&pb, unit);
+    } else {
+        pb.enable_steady_tick(Duration::from_millis(tick_ms));
+    }
+    pb
+}
+
+/// Print plain progress lines until the bar finishes or is dropped
+fn report_plain(pb: &ProgressBar, unit: Unit) {
+    let weak = pb.downgrade();
+    std::thread::spawn(move || {
+        let mut last_line = Instant::now();
+        let mut last_pos = 0;
+        loop {
+            std::thread::sleep(PLAIN_POLL);
+            let Some(pb) = weak.upgrade() else {
+                break;
+            };
+            let finished = pb.is_finished();
+            let pos = pb.position();
+            let stepped = pb.length().is_some_and(|len| {
+                len > 0 && pos.saturating_sub(last_pos) * 100 / len >= PLAIN_STEP_PERCENT
+            });
+            if (finished && pos != last_pos) || stepped || last_line.elapsed() >= PLAIN_INTERVAL {
+                let tally = TALLY.lock().ok().and_then(|t| *t);
+                eprintln!(
+                    "{}",
+                    plain_line(pos, pb.length(), unit, &pb.message(), pb.elapsed(), tally)
+                );
+                last_line = Instant::now();
+                last_pos = pos;
+            }
+            if finished {
+                break;
+            }
+        }
+    });
+}
+
+/// `[mm:ss] <message>: <pos>/<len> items[, <freed> freed, <errors> errors]`
+fn plain_line(
+    pos: u64,
+    len: Option<u64>,
+    unit: Unit,
+    msg: &str,
+    elapsed: Duration,
+    tally: Option<(u64, u64)>,
+) -> String {
+    let secs = elapsed.as_secs();
+    let mut line = format!(
+        "[{:02}:{:02}] {}",
+        secs / 60,
+        secs % 60,
+        msg.trim_end_matches('.')
+    );
+    if let Some(len) = len {
+        let count = match unit {
+            Unit::Items => format!("{}/{} items", format_number(pos), format_number(len)),
+            Unit::Bytes => format!(
+                "{}/{}",
+                bytesize::to_string(pos, false),
+                bytesize::to_string(len, false)
+            ),
+        };
+        line.push_str(&format!(": {}", count));
+    }
+    if let Some((freed, errors)) = tally {
+        line.push_str(&format!(
+            ", {} freed, {} errors",
+            bytesize::to_string(freed, false),
+            format_number(errors)
+        ));
+    }
+    line
+}
+
 /// Create a spinner for indeterminate progress
 pub fn create_spinner(msg: &str) -> ProgressBar {
     let pb = ProgressBar::new_spinner();
@@ -12,9 +159,7 @@ pub fn create_spinner(msg: &str) -> ProgressBar {
             .template("{spinner:.cyan} {msg}")
             .unwrap(),
     );
-    pb.set_message(msg.to_string());
-    pb.enable_steady_tick(Duration::from_millis(80));
-    pb
+    start(pb, msg, 80, Unit::Items)
 }
 
 /// Create a progress bar for determinate progress
@@ -26,9 +171,7 @@ pub fn create_progress_bar(total: u64, msg: &str) -> ProgressBar {
             .unwrap()
             .progress_chars("█▓░"),
     );
-    pb.set_message(msg.to_string());
-    pb.enable_steady_tick(Duration::from_millis(100));
-    pb
+    start(pb, msg, 100, Unit::Items)
 }
 
 /// Create a progress bar with ETA display
@@ -44,9 +187,7 @@ pub fn create_progress_bar_with_eta(total: u64, msg: &str) -> ProgressBar {
             .unwrap()
             .progress_chars("█▓░"),
     );
-    pb.set_message(msg.to_string());
-    pb.enable_steady_tick(Duration::from_millis(100));
-    pb
+    start(pb, msg, 100, Unit::Items)
 }
 
 /// Create a bytes-based progress bar with ETA (for cleaning operations)
@@ -60,9 +201,7 @@ pub fn create_bytes_progress_bar(total_bytes: u64, msg: &str) -> ProgressBar {
             .unwrap()
             .progress_chars("█▓░")
     );
-    pb.set_message(msg.to_string());
-    pb.enable_steady_tick(Duration::from_millis(100));
-    pb
+    start(pb, msg, 100, Unit::Bytes)
 }
 
 /// Create a scanning progress bar (for multi-category scans)
@@ -76,9 +215,7 @@ pub fn create_scan_progress_bar(total: u64, msg: &str) -> ProgressBar {
             .unwrap()
             .progress_chars("█▓░"),
     );
-    pb.set_message(msg.to_string());
-    pb.enable_steady_tick(Duration::from_millis(100));
-    pb
+    start(pb, msg, 100, Unit::Items)
 }
 
 /// Finish progress bar with a success message
@@ -139,4 +276,35 @@ mod tests {
         assert_eq!(pb.position(), 5);
         pb.finish();
     }
+
+    #[test]
+    fn plain_lines_show_counts_and_clean_totals() {
+        let elapsed = Duration::from_secs(75);
+        assert_eq!(
+            plain_line(
+                12_400,
+                Some(51_200),
+                Unit::Items,
+                "Cleaning cache...",
+                elapsed,
+                None
+            ),
+            "[01:15] Cleaning cache: 12,400/51,200 items"
+        );
+        assert_eq!(
+            plain_line(
+                3,
+                Some(10),
+                Unit::Items,
+                "Cleaning",
+                elapsed,
+                Some((2048, 3))
+            ),
+            "[01:15] Cleaning: 3/10 items, 2.0 KB freed, 3 errors"
+        );
+        assert_eq!(
+            plain_line(0, None, Unit::Items, "Scanning...", elapsed, None),
+            "[01:15] Scanning"
+        );
+    }
 }
@@ -100,6 +100,10 @@ fn get_marker_file(path: &Path, project_type: ProjectType) -> Option<PathBuf> {
 /// Check if a project is active (recently modified or has uncommitted changes)
 /// Uses smart heuristics to check multiple indicators of recent activity
 ///
+/// A project inside a workspace (Cargo, npm/yarn/pnpm, Nx, Turbo, Lerna) is
+/// judged with the whole workspace: activity at its root or in any member
+/// keeps every member active.
+///
 /// PERFORMANCE: Caches results per (project_path, age_days) to avoid repeated
 /// expensive file system checks when scanning many files in the same project.
 pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
@@ -114,18 +118,43 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
     };
 
     // Check cache first
-    let cached_result = PROJECT_ACTIVE_CACHE.with(|cache| {
-        let cache_ref = cache.borrow();
-        cache_ref.get(&(cache_key.clone(), age_days)).copied()
-    });
-
-    if let Some(cached) = cached_result {
+    let cached = |key: &PathBuf| {
+        PROJECT_ACTIVE_CACHE.with(|cache| cache.borrow().get(&(key.clone(), age_days)).copied())
+    };
+    if let Some(cached) = cached(&cache_key) {
         return Ok(cached);
     }
 
-    // Not in cache - compute result
-    let cutoff = Utc::now() - Duration::days(age_days as i64);
+    // Not in cache - compute result, once per workspace
+    let workspace = workspace_root(&cache_key);
+    let active = match workspace.as_ref().and_then(cached) {
+        Some(active) => active,
+        None => {
+            let cutoff = Utc::now() - Duration::days(age_days as i64);
+            match &workspace {
+                Some(root) => {
+                    is_dir_active(root, cutoff)
+                        || workspace_members(root)
+                            .iter()
+                            .any(|member| is_dir_active(member, cutoff))
+                }
+                None => is_dir_active(&cache_key, cutoff),
+            }
+        }
+    };
 
+    PROJECT_ACTIVE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(root) = workspace {
+            cache.insert((root, age_days), active);
+        }
+        cache.insert((cache_key, age_days), active);
+    });
+    Ok(active)
+}
+
+/// Whether `path` itself shows activity since `cutoff`
+fn is_dir_active(path: &Path, cutoff: DateTime<Utc>) -> bool {
     // Helper to check if file was modified within cutoff
     let was_modified_recently = |file_path: &Path| -> bool {
         if let Ok(meta) = std::fs::metadata(file_path) {
@@ -137,20 +166,11 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
         false
     };
 
-    // Check git index (file-based, no git2 needed)
-    if was_modified_recently(&path.join(".git").join("index")) {
-        PROJECT_ACTIVE_CACHE.with(|cache| {
-            cache.borrow_mut().insert((cache_key, age_days), true);
-        });
-        return Ok(true);
-    }
-
-    // Check git HEAD
-    if was_modified_recently(&path.join(".git").join("HEAD")) {
-        PROJECT_ACTIVE_CACHE.with(|cache| {
-            cache.borrow_mut().insert((cache_key, age_days), true);
-        });
-        return Ok(true);
+    // Check git index and HEAD (file-based, no git2 needed)
+    if was_modified_recently(&path.join(".git").join("index"))
+        || was_modified_recently(&path.join(".git").join("HEAD"))
+    {
+        return true;
     }
 
     // Check common project files and lock files
@@ -174,13 +194,11 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
         "Gemfile.lock",
     ];
 
-    for file in &project_files {
-        if was_modified_recently(&path.join(file)) {
-            PROJECT_ACTIVE_CACHE.with(|cache| {
-                cache.borrow_mut().insert((cache_key, age_days), true);
-            });
-            return Ok(true);
-        }
+    if project_files
+        .iter()
+        .any(|file| was_modified_recently(&path.join(file)))
+    {
+        return true;
     }
 
     // Check if any source files were modified recently
@@ -196,33 +214,139 @@ pub fn is_project_active(path: &Path, age_days: u64) -> Result<bool> {
                 if source_extensions.contains(&ext.to_string_lossy().as_ref())
                     && was_modified_recently(&entry_path)
                 {
-                    PROJECT_ACTIVE_CACHE.with(|cache| {
-                        cache.borrow_mut().insert((cache_key, age_days), true);
-                    });
-                    return Ok(true);
+                    return true;
                 }
             }
         }
     }
 
-    // Project is inactive - cache and return
-    PROJECT_ACTIVE_CACHE.with(|cache| {
-        cache.borrow_mut().insert((cache_key, age_days), false);
-    });
-    Ok(false) // Inactive
+    false
+}
+
+/// How many folders above a project are searched for its workspace root
+const MAX_WORKSPACE_DEPTH: usize = 8;
+
+/// How deep below a workspace root its members are searched
+const MAX_MEMBER_DEPTH: usize = 4;
+
+/// The nearest folder at or above `path` that declares a workspace. The
+/// search stops at the repository root.
+pub fn workspace_root(path: &Path) -> Option<PathBuf> {
+    for dir in path.ancestors().take(MAX_WORKSPACE_DEPTH) {
+        if declares_workspace(dir) {
+            return Some(dir.to_path_buf());
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Whether `dir` is the root of a Cargo, npm/yarn, pnpm, Nx, Turbo or Lerna workspace
+fn declares_workspace(dir: &Path) -> bool {
+    if ["pnpm-workspace.yaml", "nx.json", "turbo.json", "lerna.json"]
+        .iter()
+        .any(|marker| dir.join(marker).is_file())
+    {
+        return true;
+    }
+
+    let cargo_workspace = std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"));
+    if cargo_workspace {
+        return true;
+    }
+
+    std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|manifest| manifest.get("workspaces").is_some())
+}
+
+/// Projects below the workspace `root` (not including it)
+fn workspace_members(root: &Path) -> Vec<PathBuf> {
+    let mut members = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        if depth >= MAX_MEMBER_DEPTH {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if !file_type.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if is_skipped_dir(&name) {
+                continue;
+            }
+            let path = entry.path();
+            if detect_project_type(&path).is_some() {
+                members.push(path.clone());
+            }
+            pending.push((path, depth + 1));
+        }
+    }
+    members
+}
+
+/// Deep or generated folders that never hold project roots
+fn is_skipped_dir(name_lower: &str) -> bool {
+    matches!(
+        name_lower,
+        "node_modules"
+            | ".git"
+            | ".hg"
+            | ".svn"
+            | "target"
+            | ".gradle"
+            | "__pycache__"
+            | ".venv"
+            | "venv"
+            | ".next"
+            | ".nuxt"
+            | ".turbo"
+            | ".parcel-cache"
+            | ".vite"
+            | "$recycle.bin"
+            | "system volume information"
+            | "windows"
+            | "program files"
+            | "program files (x86)"
+            | "programdata"
+            | "appdata"
+    )
 }
 
 /// Find all project roots in a directory tree
 ///
 /// Uses jwalk for parallel directory traversal (2-4x faster than sequential).
 pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
+    find_project_roots_with_depth(root, config, DEFAULT_MAX_DEPTH)
+}
+
+/// Default depth below the scan root searched for projects
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// Find project roots at most `max_depth` levels below `root`
+pub fn find_project_roots_with_depth(
+    root: &Path,
+    config: &Config,
+    max_depth: usize,
+) -> Vec<PathBuf> {
     // Skip if root itself is a project (avoid scanning into it)
     if detect_project_type(root).is_some() {
         return vec![root.to_path_buf()];
     }
 
-    const MAX_DEPTH: usize = 5;
-
     let projects: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
     let seen: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
 
@@ -230,7 +354,7 @@ pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
     let config_arc = Arc::new(config.clone());
 
     WalkDir::new(root)
-        .max_depth(MAX_DEPTH)
+        .max_depth(max_depth)
         .follow_links(false)
         .parallelism(jwalk::Parallelism::RayonDefaultPool {
             busy_timeout: std::time::Duration::from_secs(1),
@@ -249,31 +373,7 @@ pub fn find_project_roots(root: &Path, config: &Config) -> Vec<PathBuf> {
                     if e.file_type().is_dir() {
                         // Skip known deep/large directories that aren't project roots
                         if let Some(name) = path.file_name() {
-                            let name_lower = name.to_string_lossy().to_lowercase();
-                            if matches!(
-                                name_lower.as_str(),
-                                "node_modules"
-                                    | ".git"
-                                    | ".hg"
-                                    | ".svn"
-                                    | "target"
-                                    | ".gradle"
-                                    | "__pycache__"
-                                    | ".venv"
-                                    | "venv"
-                                    | ".next"
-                                    | ".nuxt"
-                                    | ".turbo"
-                                    | ".parcel-cache"
-                                    | ".vite"
-                                    | "$recycle.bin"
-                                    | "system volume information"
-                                    | "windows"
-                                    | "program files"
-                                    | "program files (x86)"
-                                    | "programdata"
-                                    | "appdata"
-                            ) {
+                            if is_skipped_dir(&name.to_string_lossy().to_lowercase()) {
                                 return false;
                             }
                         }
@@ -393,4 +493,41 @@ mod tests {
         let roots = find_project_roots(temp_dir.path(), &config);
         assert_eq!(roots.len(), 2);
     }
+
+    fn write_old(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+        let year_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(365 * 86_400);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(year_ago)
+            .unwrap();
+    }
+
+    #[test]
+    fn activity_in_any_workspace_member_keeps_the_workspace_active() {
+        let temp_dir = create_test_dir();
+        let repo = temp_dir.path().join("monorepo");
+        write_old(
+            &repo.join("package.json"),
+            r#"{"workspaces": ["packages/*"]}"#,
+        );
+        write_old(&repo.join("packages/a/package.json"), "{}");
+        write_old(&repo.join("packages/b/package.json"), "{}");
+        fs::write(repo.join("packages/b/index.ts"), "export {}").unwrap();
+
+        let member = repo.join("packages/a");
+        assert_eq!(workspace_root(&member), Some(repo.clone()));
+        assert!(is_project_active(&member, 14).unwrap());
+
+        // Without the workspace declaration each package stands alone
+        let standalone = temp_dir.path().join("standalone");
+        write_old(&standalone.join("a/package.json"), "{}");
+        write_old(&standalone.join("b/package.json"), "{}");
+        fs::write(standalone.join("b/index.ts"), "export {}").unwrap();
+        assert_eq!(workspace_root(&standalone.join("a")), None);
+        assert!(!is_project_active(&standalone.join("a"), 14).unwrap());
+    }
 }
@@ -0,0 +1,191 @@
+//! Storage quotas that limit free space below what the disk has
+//!
+//! A physical drive can have plenty of room while the user can't use it:
+//! an NTFS disk quota caps what their account may write, and a OneDrive plan
+//! caps what the synced folder may hold. Health scoring and emergency mode
+//! use the quota with the least room left instead of the raw disk numbers,
+//! so "free up N GB" targets the limit the user will actually hit.
+//!
+//! NTFS quotas are read from `GetDiskFreeSpaceExW`, which reports the
+//! caller's limit. Windows doesn't expose the OneDrive plan size locally, so
+//! it comes from `[quota] onedrive_limit_gb`; usage is the size of the synced
+//! folder, counting online-only files at their full size.
+
+use crate::config::QuotaSettings;
+use crate::utils;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// Measuring the OneDrive folder walks it, so reuse the result this long
+const ONEDRIVE_USAGE_TTL: Duration = Duration::from_secs(600);
+
+lazy_static! {
+    static ref ONEDRIVE_USAGE: Mutex<Option<(PathBuf, u64, Instant)>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaKind {
+    Ntfs,
+    OneDrive,
+}
+
+impl QuotaKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            QuotaKind::Ntfs => "NTFS quota",
+            QuotaKind::OneDrive => "OneDrive plan",
+        }
+    }
+}
+
+/// A limit on how much the user may store, in bytes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quota {
+    pub kind: QuotaKind,
+    pub limit: u64,
+    pub used: u64,
+}
+
+impl Quota {
+    pub fn available(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+/// The quota with the least room left, if it has less than `disk_available`.
+/// A quota with more room than the disk doesn't constrain anything.
+pub fn tightest(quotas: Vec<Quota>, disk_available: u64) -> Option<Quota> {
+    quotas
+        .into_iter()
+        .filter(|q| q.available() < disk_available)
+        .min_by_key(|q| q.available())
+}
+
+/// The user's NTFS quota on the volume holding `path`, when one is enforced
+#[cfg(windows)]
+pub fn ntfs(path: &Path) -> Option<Quota> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut caller_free = 0u64;
+    let mut caller_total = 0u64;
+    let mut volume_free = 0u64;
+    unsafe {
+        GetDiskFreeSpaceExW(
+            PCWSTR(wide.as_ptr()),
+            Some(&mut caller_free),
+            Some(&mut caller_total),
+            Some(&mut volume_free),
+        )
+        .ok()?;
+    }
+    // Without a quota the caller sees the whole volume
+    (caller_free < volume_free).then(|| Quota {
+        kind: QuotaKind::Ntfs,
+        limit: caller_total,
+        used: caller_total.saturating_sub(caller_free),
+    })
+}
+
+#[cfg(not(windows))]
+pub fn ntfs(_path: &Path) -> Option<Quota> {
+    None
+}
+
+/// The synced OneDrive folder: the configured path, else `%OneDrive%`
+fn onedrive_folder(settings: &QuotaSettings) -> Option<PathBuf> {
+    if !settings.onedrive_path.is_empty() {
+        return Some(PathBuf::from(&settings.onedrive_path));
+    }
+    std::env::var_os("OneDrive")
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir())
+}
+
+/// Logical size of every file under `folder`; online-only placeholders report
+/// their full size, which is what counts against the plan
+fn synced_size(folder: &Path) -> u64 {
+    WalkDir::new(folder)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// The OneDrive plan, when a limit is configured and the folder exists
+pub fn onedrive(settings: &QuotaSettings) -> Option<Quota> {
+    if settings.onedrive_limit_gb == 0 {
+        return None;
+    }
+    let folder = onedrive_folder(settings)?;
+
+    let mut cached = ONEDRIVE_USAGE.lock().ok()?;
+    let used = match cached.as_ref() {
+        Some((path, used, at)) if *path == folder && at.elapsed() < ONEDRIVE_USAGE_TTL => *used,
+        _ => {
+            let used = synced_size(&folder);
+            *cached = Some((folder, used, Instant::now()));
+            used
+        }
+    };
+    Some(Quota {
+        kind: QuotaKind::OneDrive,
+        limit: settings.onedrive_limit_gb * 1024 * 1024 * 1024,
+        used,
+    })
+}
+
+/// Every quota that applies to the volume holding `path`
+pub fn for_volume(path: &Path, settings: &QuotaSettings) -> Vec<Quota> {
+    let mut quotas: Vec<Quota> = ntfs(path).into_iter().collect();
+    if let Some(onedrive) = onedrive(settings) {
+        let on_volume = onedrive_folder(settings)
+            .map(|folder| utils::is_on_volume(&folder, &utils::volume_of(path)))
+            .unwrap_or(false);
+        if on_volume {
+            quotas.push(onedrive);
+        }
+    }
+    quotas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tightest_quota_constrains_only_below_the_disk() {
+        let gb = 1024 * 1024 * 1024;
+        let ntfs = Quota {
+            kind: QuotaKind::Ntfs,
+            limit: 100 * gb,
+            used: 90 * gb,
+        };
+        let onedrive = Quota {
+            kind: QuotaKind::OneDrive,
+            limit: 1024 * gb,
+            used: 1020 * gb,
+        };
+
+        let tight = tightest(vec![ntfs.clone(), onedrive.clone()], 500 * gb).unwrap();
+        assert_eq!(tight.kind, QuotaKind::OneDrive);
+        assert_eq!(tight.available(), 4 * gb);
+
+        // The disk itself is fuller than any quota
+        assert_eq!(tightest(vec![ntfs, onedrive], 2 * gb), None);
+    }
+}
@@ -0,0 +1,467 @@
+//! Scan recording and replay
+//!
+//! `wole scan --record session.wole` captures what went into a scan (enabled
+//! categories, thresholds, exclusions), the traversal decisions made along the
+//! way (excluded paths, skipped links/junctions, blocked system paths) and the
+//! results. Users attach the file to a bug report; `wole scan --replay
+//! session.wole` shows it on a maintainer's machine and re-checks the
+//! recorded decisions against the current code to reproduce mis-detections.
+//!
+//! With `--anonymize`, path components are replaced by salted hashes, except
+//! for well-known folder names and file extensions that detection depends on.
+//! Anonymized decisions can't be re-checked.
+
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::output::{CategoryResult, ScanResults};
+use crate::utils;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Decisions kept per recording; later ones are counted but dropped
+const MAX_DECISIONS: usize = 100_000;
+
+/// Folder names kept as-is when anonymizing (detection keys off them)
+const KEEP_COMPONENTS: &[&str] = &[
+    "users",
+    "appdata",
+    "local",
+    "locallow",
+    "roaming",
+    "temp",
+    "tmp",
+    "cache",
+    "caches",
+    "downloads",
+    "documents",
+    "desktop",
+    "programdata",
+    "program files",
+    "program files (x86)",
+    "windows",
+    "microsoft",
+    "packages",
+    "google",
+    "chrome",
+    "mozilla",
+    "firefox",
+    "user data",
+    "default",
+    "code cache",
+    "gpucache",
+    "cache_data",
+    "npm-cache",
+    "pip",
+    "nuget",
+    "cargo",
+    "registry",
+    "bin",
+    "obj",
+    "dist",
+    "build",
+    "coverage",
+];
+
+/// A traversal decision taken while scanning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DecisionKind {
+    /// Matched a config exclusion pattern
+    Excluded,
+    /// Symlink, junction or reparse point that wasn't followed
+    SkippedLink,
+    /// Blocked as a system directory
+    SystemPath,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decision {
+    pub kind: DecisionKind,
+    pub path: String,
+}
+
+/// Scan inputs as recorded
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedInputs {
+    pub categories: Vec<String>,
+    pub project_age_days: u64,
+    pub min_age_days: u64,
+    pub min_size_bytes: u64,
+    pub exclusions: Vec<String>,
+}
+
+/// Contents of a `.wole` recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub version: String,
+    pub recorded_at: DateTime<Utc>,
+    pub platform: String,
+    pub anonymized: bool,
+    pub root: String,
+    pub inputs: RecordedInputs,
+    pub decisions: Vec<Decision>,
+    /// Decisions beyond `MAX_DECISIONS` that were not kept
+    #[serde(default)]
+    pub dropped_decisions: usize,
+    /// Results keyed by category (CLI flag names)
+    pub results: BTreeMap<String, CategoryResult>,
+}
+
+/// A recorded decision the current code no longer makes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub kind: DecisionKind,
+    pub path: String,
+}
+
+struct Session {
+    anonymize: bool,
+    salt: [u8; 32],
+    decisions: Vec<Decision>,
+    dropped: usize,
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Start capturing decisions for the next scan
+pub fn start(anonymize: bool) {
+    let seed = format!(
+        "{}-{}",
+        std::process::id(),
+        Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    );
+    if let Ok(mut session) = SESSION.lock() {
+        *session = Some(Session {
+            anonymize,
+            salt: *blake3::hash(seed.as_bytes()).as_bytes(),
+            decisions: Vec::new(),
+            dropped: 0,
+        });
+        ACTIVE.store(true, Ordering::Release);
+    }
+}
+
+/// Record a traversal decision (no-op unless recording)
+pub fn note(kind: DecisionKind, path: &Path) {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return;
+    }
+    if let Ok(mut guard) = SESSION.lock() {
+        if let Some(session) = guard.as_mut() {
+            if session.decisions.len() >= MAX_DECISIONS {
+                session.dropped += 1;
+                return;
+            }
+            let path = session.render(path);
+            session.decisions.push(Decision { kind, path });
+        }
+    }
+}
+
+/// Stop capturing and write the recording for `results` to `out`
+pub fn finish(
+    out: &Path,
+    root: &Path,
+    options: &ScanOptions,
+    config: &Config,
+    results: &ScanResults,
+) -> Result<()> {
+    ACTIVE.store(false, Ordering::Release);
+    let session = SESSION
+        .lock()
+        .ok()
+        .and_then(|mut s| s.take())
+        .context("Recording was not started")?;
+
+    let results = categories(results)
+        .into_iter()
+        .filter(|(_, result)| result.items > 0)
+        .map(|(name, result)| {
+            let mut result = result.clone();
+            if session.anonymize {
+                result.paths = result
+                    .paths
+                    .iter()
+                    .map(|p| PathBuf::from(session.render(p)))
+                    .collect();
+            }
+            (name.to_string(), result)
+        })
+        .collect();
+
+    let recording = Recording {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        recorded_at: Utc::now(),
+        platform: std::env::consts::OS.to_string(),
+        anonymized: session.anonymize,
+        root: session.render(root),
+        inputs: RecordedInputs {
+            categories: enabled_categories(options),
+            project_age_days: options.project_age_days,
+            min_age_days: options.min_age_days,
+            min_size_bytes: options.min_size_bytes,
+            exclusions: config.exclusions.patterns.clone(),
+        },
+        decisions: session.decisions,
+        dropped_decisions: session.dropped,
+        results,
+    };
+
+    let json = serde_json::to_string_pretty(&recording)?;
+    std::fs::write(out, json)
+        .with_context(|| format!("Failed to write recording to {}", out.display()))
+}
+
+/// Load a recording written by `finish`
+pub fn load(path: &Path) -> Result<Recording> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read recording {}", path.display()))?;
+    serde_json::from_str(&content).context("Not a valid wole recording")
+}
+
+impl Recording {
+    /// Results in the shape the output module prints
+    pub fn scan_results(&self) -> ScanResults {
+        let mut results = ScanResults {
+            root: Some(PathBuf::from(&self.root)),
+            ..Default::default()
+        };
+        for (name, slot) in categories_mut(&mut results) {
+            if let Some(recorded) = self.results.get(name) {
+                *slot = recorded.clone();
+            }
+        }
+        results
+    }
+
+    /// Decisions counted by kind
+    pub fn decision_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for decision in &self.decisions {
+            let key = serde_json::to_value(decision.kind)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            *counts.entry(key).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Re-check recorded exclusion and system-path decisions with the current
+    /// code and the recorded exclusions. Empty for anonymized recordings.
+    pub fn recheck(&self) -> Vec<Mismatch> {
+        if self.anonymized {
+            return Vec::new();
+        }
+        let mut config = Config::default();
+        config.exclusions.patterns = self.inputs.exclusions.clone();
+
+        self.decisions
+            .iter()
+            .filter(|d| {
+                let path = Path::new(&d.path);
+                match d.kind {
+                    DecisionKind::Excluded => !config.is_excluded(path),
+                    DecisionKind::SystemPath => !utils::is_system_path(path),
+                    // Depends on the user's filesystem
+                    DecisionKind::SkippedLink => false,
+                }
+            })
+            .map(|d| Mismatch {
+                kind: d.kind,
+                path: d.path.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Session {
+    fn render(&self, path: &Path) -> String {
+        if !self.anonymize {
+            return path.display().to_string();
+        }
+        anonymize_path(path, &self.salt)
+    }
+}
+
+/// Replace identifying path components with short salted hashes
+fn anonymize_path(path: &Path, salt: &[u8; 32]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(p) => parts.push(p.as_os_str().to_string_lossy().into_owned()),
+            Component::RootDir => parts.push(String::new()),
+            Component::CurDir => parts.push(".".to_string()),
+            Component::ParentDir => parts.push("..".to_string()),
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                let lower = name.to_lowercase();
+                if KEEP_COMPONENTS.contains(&lower.as_str())
+                    || utils::SKIP_WALK_DIRS.contains(&lower.as_str())
+                {
+                    parts.push(name.into_owned());
+                    continue;
+                }
+                let (stem, ext) = match name.rsplit_once('.') {
+                    Some((stem, ext)) if !stem.is_empty() && ext.len() <= 8 => (stem, Some(ext)),
+                    _ => (name.as_ref(), None),
+                };
+                let mut hasher = blake3::Hasher::new_keyed(salt);
+                hasher.update(stem.as_bytes());
+                let hash = hasher.finalize().to_hex();
+                let hashed = match ext {
+                    Some(ext) => format!("h{}.{}", &hash[..8], ext),
+                    None => format!("h{}", &hash[..8]),
+                };
+                parts.push(hashed);
+            }
+        }
+    }
+    let sep = std::path::MAIN_SEPARATOR.to_string();
+    match parts.as_slice() {
+        [root] if root.is_empty() => sep,
+        _ => parts.join(&sep),
+    }
+}
+
+fn enabled_categories(options: &ScanOptions) -> Vec<String> {
+    [
+        ("cache", options.cache),
+        ("app_cache", options.app_cache),
+        ("temp", options.temp),
+        ("trash", options.trash),
+        ("build", options.build),
+        ("downloads", options.downloads),
+        ("large", options.large),
+        ("old", options.old),
+        ("applications", options.applications),
+        ("browser", options.browser),
+        ("system", options.system),
+        ("empty", options.empty),
+        ("duplicates", options.duplicates),
+        ("windows_update", options.windows_update),
+        ("event_logs", options.event_logs),
+        ("orphaned", options.orphaned),
+        ("custom", options.custom),
+        ("ide", options.ide),
+        ("rust", options.rust),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name.to_string())
+    .collect()
+}
+
+fn categories(results: &ScanResults) -> Vec<(&'static str, &CategoryResult)> {
+    vec![
+        ("cache", &results.cache),
+        ("app_cache", &results.app_cache),
+        ("temp", &results.temp),
+        ("trash", &results.trash),
+        ("build", &results.build),
+        ("downloads", &results.downloads),
+        ("large", &results.large),
+        ("old", &results.old),
+        ("applications", &results.applications),
+        ("browser", &results.browser),
+        ("system", &results.system),
+        ("empty", &results.empty),
+        ("duplicates", &results.duplicates),
+        ("windows_update", &results.windows_update),
+        ("event_logs", &results.event_logs),
+        ("orphaned", &results.orphaned),
+        ("custom", &results.custom),
+        ("ide", &results.ide),
+        ("rust", &results.rust),
+    ]
+}
+
+fn categories_mut(results: &mut ScanResults) -> Vec<(&'static str, &mut CategoryResult)> {
+    vec![
+        ("cache", &mut results.cache),
+        ("app_cache", &mut results.app_cache),
+        ("temp", &mut results.temp),
+        ("trash", &mut results.trash),
+        ("build", &mut results.build),
+        ("downloads", &mut results.downloads),
+        ("large", &mut results.large),
+        ("old", &mut results.old),
+        ("applications", &mut results.applications),
+        ("browser", &mut results.browser),
+        ("system", &mut results.system),
+        ("empty", &mut results.empty),
+        ("duplicates", &mut results.duplicates),
+        ("windows_update", &mut results.windows_update),
+        ("event_logs", &mut results.event_logs),
+        ("orphaned", &mut results.orphaned),
+        ("custom", &mut results.custom),
+        ("ide", &mut results.ide),
+        ("rust", &mut results.rust),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anonymizes_names_but_keeps_known_folders_and_extensions() {
+        let salt = [7u8; 32];
+        let path = Path::new("/home/alice/AppData/Local/Temp/secret-report.docx");
+        let anon = anonymize_path(path, &salt);
+        assert!(!anon.contains("alice"));
+        assert!(!anon.contains("secret"));
+        assert!(anon.contains("AppData"));
+        assert!(anon.contains("Temp"));
+        assert!(anon.ends_with(".docx"));
+        // Stable within a session
+        assert_eq!(anon, anonymize_path(path, &salt));
+    }
+
+    #[test]
+    fn recheck_flags_decisions_current_code_no_longer_makes() {
+        let recording = Recording {
+            version: "0.0.0".to_string(),
+            recorded_at: Utc::now(),
+            platform: "windows".to_string(),
+            anonymized: false,
+            root: "/".to_string(),
+            inputs: RecordedInputs {
+                exclusions: vec!["**/keep/**".to_string()],
+                ..Default::default()
+            },
+            decisions: vec![
+                Decision {
+                    kind: DecisionKind::Excluded,
+                    path: "/data/keep/file.txt".to_string(),
+                },
+                Decision {
+                    kind: DecisionKind::Excluded,
+                    path: "/data/other/file.txt".to_string(),
+                },
+                Decision {
+                    kind: DecisionKind::SystemPath,
+                    path: "/Windows/System32".to_string(),
+                },
+            ],
+            dropped_decisions: 0,
+            results: BTreeMap::new(),
+        };
+
+        let mismatches = recording.recheck();
+        assert_eq!(
+            mismatches,
+            vec![Mismatch {
+                kind: DecisionKind::Excluded,
+                path: "/data/other/file.txt".to_string(),
+            }]
+        );
+        assert_eq!(recording.decision_counts().get("excluded"), Some(&2));
+    }
+}
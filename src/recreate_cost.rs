@@ -0,0 +1,240 @@
+//! Cost to recreate deleted caches and build artifacts
+//!
+//! Deleting `node_modules` or a package cache frees space now but costs a
+//! re-download later; deleting `target/` costs a rebuild. These estimates come
+//! from what each folder is (its name, its place in a known cache, marker
+//! files next to it) and how big it is, so results can show what a deletion
+//! will cost in time and bandwidth.
+
+use std::path::Path;
+
+/// What happens when the folder is needed again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecreateKind {
+    /// Fetched again from the network
+    Redownload,
+    /// Produced again by a build
+    Rebuild,
+    /// Recreated automatically on the next run, at negligible cost
+    Regenerated,
+}
+
+/// Estimated cost of recreating one deleted folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecreateCost {
+    pub kind: RecreateKind,
+    /// The command or tool that recreates it
+    pub tool: &'static str,
+    /// Estimated download size (`Redownload` only)
+    pub download_bytes: u64,
+    /// Size of the folder itself
+    pub size_bytes: u64,
+}
+
+impl RecreateCost {
+    /// Short description, e.g. "re-download ~1.2 GB (npm install)"
+    pub fn describe(&self) -> String {
+        match self.kind {
+            RecreateKind::Redownload => format!(
+                "re-download ~{} ({})",
+                bytesize::to_string(self.download_bytes, false),
+                self.tool
+            ),
+            RecreateKind::Rebuild => {
+                let scale = if self.size_bytes < 200 * MB {
+                    "quick"
+                } else if self.size_bytes < 2 * GB {
+                    "full"
+                } else {
+                    "long full"
+                };
+                format!("{} rebuild ({})", scale, self.tool)
+            }
+            RecreateKind::Regenerated => format!("regenerated automatically ({})", self.tool),
+        }
+    }
+}
+
+const MB: u64 = 1_000_000;
+const GB: u64 = 1_000_000_000;
+
+/// Build artifact folders: (name, kind, tool, downloaded/on-disk ratio).
+/// Installed packages are unpacked, so they download at roughly a third of
+/// their size on disk.
+const ARTIFACT_RULES: &[(&str, RecreateKind, &str, f64)] = &[
+    (
+        "node_modules",
+        RecreateKind::Redownload,
+        "npm install",
+        0.35,
+    ),
+    (".venv", RecreateKind::Redownload, "pip install", 0.4),
+    ("venv", RecreateKind::Redownload, "pip install", 0.4),
+    ("dist", RecreateKind::Rebuild, "project build", 0.0),
+    ("build", RecreateKind::Rebuild, "project build", 0.0),
+    (".next", RecreateKind::Rebuild, "next build", 0.0),
+    (".nuxt", RecreateKind::Rebuild, "nuxt build", 0.0),
+    (".output", RecreateKind::Rebuild, "project build", 0.0),
+    (".angular", RecreateKind::Rebuild, "ng build", 0.0),
+    (".svelte-kit", RecreateKind::Rebuild, "vite build", 0.0),
+    (".gradle", RecreateKind::Rebuild, "gradle build", 0.0),
+    ("bin", RecreateKind::Rebuild, "dotnet build", 0.0),
+    ("obj", RecreateKind::Rebuild, "dotnet build", 0.0),
+    (
+        "__pycache__",
+        RecreateKind::Regenerated,
+        "next Python run",
+        0.0,
+    ),
+    (
+        ".pytest_cache",
+        RecreateKind::Regenerated,
+        "next pytest run",
+        0.0,
+    ),
+    (
+        ".mypy_cache",
+        RecreateKind::Regenerated,
+        "next mypy run",
+        0.0,
+    ),
+    (
+        ".parcel-cache",
+        RecreateKind::Regenerated,
+        "next parcel build",
+        0.0,
+    ),
+    (".vite", RecreateKind::Regenerated, "next vite run", 0.0),
+    (".turbo", RecreateKind::Regenerated, "next turbo run", 0.0),
+    ("coverage", RecreateKind::Regenerated, "next test run", 0.0),
+    (
+        ".nyc_output",
+        RecreateKind::Regenerated,
+        "next test run",
+        0.0,
+    ),
+];
+
+/// Package caches, matched on a lowercase path fragment. Caches hold
+/// compressed downloads, so they cost about their own size to refill.
+const CACHE_RULES: &[(&str, &str)] = &[
+    ("npm-cache", "npm install"),
+    ("yarn/cache", "yarn install"),
+    ("pnpm", "pnpm install"),
+    ("pip/cache", "pip install"),
+    (".cargo/registry", "cargo build"),
+    ("nuget/v3-cache", "dotnet restore"),
+    ("go/pkg/mod", "go build"),
+    (".m2/repository", "mvn install"),
+    (".gradle/caches", "gradle build"),
+    (".gradle/wrapper", "gradle wrapper"),
+    ("ms-playwright", "playwright install"),
+    ("huggingface", "model downloads"),
+    ("puppeteer", "puppeteer install"),
+];
+
+/// Estimate the cost of recreating `path` (a build artifact or package cache
+/// folder of `size_bytes`). None when the folder isn't a known kind.
+pub fn estimate(path: &Path, size_bytes: u64) -> Option<RecreateCost> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+    // `target` depends on the toolchain that produced it
+    if name == "target" {
+        let parent = path.parent()?;
+        let tool = if parent.join("Cargo.toml").exists() {
+            "cargo build"
+        } else if parent.join("pom.xml").exists() {
+            "mvn package"
+        } else {
+            "project build"
+        };
+        return Some(RecreateCost {
+            kind: RecreateKind::Rebuild,
+            tool,
+            download_bytes: 0,
+            size_bytes,
+        });
+    }
+
+    if let Some((_, kind, tool, ratio)) = ARTIFACT_RULES.iter().find(|(n, ..)| *n == name) {
+        return Some(RecreateCost {
+            kind: *kind,
+            tool,
+            download_bytes: (size_bytes as f64 * ratio) as u64,
+            size_bytes,
+        });
+    }
+
+    let normalized = path.to_string_lossy().to_lowercase().replace('\\', "/");
+    CACHE_RULES
+        .iter()
+        .find(|(fragment, _)| normalized.contains(fragment))
+        .map(|(_, tool)| RecreateCost {
+            kind: RecreateKind::Redownload,
+            tool,
+            download_bytes: size_bytes,
+            size_bytes,
+        })
+}
+
+/// One-line total for several estimates, e.g. "re-download ~3.4 GB, 2 rebuilds"
+pub fn summarize(costs: &[RecreateCost]) -> Option<String> {
+    let download: u64 = costs
+        .iter()
+        .filter(|c| c.kind == RecreateKind::Redownload)
+        .map(|c| c.download_bytes)
+        .sum();
+    let rebuilds = costs
+        .iter()
+        .filter(|c| c.kind == RecreateKind::Rebuild)
+        .count();
+
+    let mut parts = Vec::new();
+    if download > 0 {
+        parts.push(format!(
+            "re-download ~{}",
+            bytesize::to_string(download, false)
+        ));
+    }
+    if rebuilds > 0 {
+        parts.push(format!(
+            "{} rebuild{}",
+            rebuilds,
+            if rebuilds == 1 { "" } else { "s" }
+        ));
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn estimates_by_folder_kind() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+
+        let target = estimate(&dir.path().join("target"), 3 * GB).unwrap();
+        assert_eq!(target.describe(), "long full rebuild (cargo build)");
+
+        let modules = estimate(&dir.path().join("node_modules"), 1_000 * MB).unwrap();
+        assert_eq!(modules.kind, RecreateKind::Redownload);
+        assert_eq!(modules.download_bytes, 350 * MB);
+
+        let npm = estimate(Path::new("C:/Users/me/AppData/Local/npm-cache"), 1_200 * MB);
+        assert_eq!(
+            npm.as_ref().map(RecreateCost::describe).as_deref(),
+            Some("re-download ~1.2 GB (npm install)")
+        );
+
+        let pycache = estimate(&dir.path().join("__pycache__"), MB).unwrap();
+        assert_eq!(pycache.kind, RecreateKind::Regenerated);
+        assert_eq!(estimate(&dir.path().join("photos"), GB), None);
+
+        let total = summarize(&[target, modules, npm.unwrap(), pycache]).unwrap();
+        assert_eq!(total, "re-download ~1.6 GB, 1 rebuild");
+    }
+}
@@ -0,0 +1,369 @@
+//! Weekly summary report
+//!
+//! Composes the last seven days of deletion history, the trend against the
+//! week before and (optionally) what is cleanable right now into a markdown
+//! document for `wole report --weekly`. Charts are plain text (sparklines and
+//! ASCII bars) so the report renders anywhere markdown does.
+
+use crate::history::{DeletionLog, FailureReason};
+use crate::output::ScanResults;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Days covered by the weekly report
+pub const REPORT_DAYS: i64 = 7;
+
+/// Rows shown in the "top" tables
+const TOP_COUNT: usize = 5;
+
+/// Width of ASCII bars
+const BAR_WIDTH: usize = 20;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Totals for one category in the report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryTotal {
+    pub category: String,
+    pub items: usize,
+    pub bytes: u64,
+}
+
+/// Data for the weekly report
+#[derive(Debug, Clone, Default)]
+pub struct WeeklyReport {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub sessions: usize,
+    pub items_cleaned: usize,
+    pub bytes_cleaned: u64,
+    pub errors: usize,
+    /// Bytes cleaned per day, oldest first
+    pub daily_bytes: Vec<u64>,
+    /// Bytes cleaned in the seven days before the period
+    pub previous_bytes: u64,
+    /// Categories by bytes cleaned, largest first
+    pub by_category: Vec<CategoryTotal>,
+    /// Largest single deletions: (path, category, bytes)
+    pub top_items: Vec<(String, String, u64)>,
+    /// Failed deletions grouped by reason
+    pub failures: Vec<(FailureReason, usize)>,
+    /// What a scan of safe categories finds right now
+    pub cleanable: Vec<CategoryTotal>,
+}
+
+/// Build the report from deletion logs (any order, any age) for the week ending at `now`
+pub fn build_weekly(logs: &[DeletionLog], now: DateTime<Utc>) -> WeeklyReport {
+    let period_start = now - Duration::days(REPORT_DAYS);
+    let previous_start = period_start - Duration::days(REPORT_DAYS);
+
+    let mut report = WeeklyReport {
+        period_start,
+        period_end: now,
+        daily_bytes: vec![0; REPORT_DAYS as usize],
+        ..Default::default()
+    };
+    let mut categories: HashMap<String, CategoryTotal> = HashMap::new();
+    let mut failures: HashMap<FailureReason, usize> = HashMap::new();
+
+    for log in logs {
+        let mut in_period = false;
+        for record in &log.records {
+            if record.timestamp > now {
+                continue;
+            }
+            if record.timestamp < period_start {
+                if record.timestamp >= previous_start && record.success {
+                    report.previous_bytes += record.size_bytes;
+                }
+                continue;
+            }
+            in_period = true;
+
+            if !record.success {
+                report.errors += 1;
+                *failures
+                    .entry(record.reason.unwrap_or(FailureReason::Other))
+                    .or_insert(0) += 1;
+                continue;
+            }
+
+            report.items_cleaned += 1;
+            report.bytes_cleaned += record.size_bytes;
+            let day = (record.timestamp - period_start).num_days() as usize;
+            if let Some(slot) = report.daily_bytes.get_mut(day) {
+                *slot += record.size_bytes;
+            }
+            let total = categories
+                .entry(record.category.clone())
+                .or_insert_with(|| CategoryTotal {
+                    category: record.category.clone(),
+                    items: 0,
+                    bytes: 0,
+                });
+            total.items += 1;
+            total.bytes += record.size_bytes;
+            report.top_items.push((
+                record.path.clone(),
+                record.category.clone(),
+                record.size_bytes,
+            ));
+        }
+        if in_period {
+            report.sessions += 1;
+        }
+    }
+
+    report.by_category = categories.into_values().collect();
+    report.by_category.sort_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    report
+        .top_items
+        .sort_by_key(|item| std::cmp::Reverse(item.2));
+    report.top_items.truncate(TOP_COUNT);
+    report.failures = failures.into_iter().collect();
+    report.failures.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| reason_label(a.0).cmp(reason_label(b.0)))
+    });
+    report
+}
+
+impl WeeklyReport {
+    /// Add current cleanable estimates from a scan
+    pub fn set_cleanable(&mut self, results: &ScanResults) {
+        let categories = [
+            ("Package Cache", &results.cache),
+            ("Application Cache", &results.app_cache),
+            ("Temp Files", &results.temp),
+            ("Trash", &results.trash),
+            ("Browser Cache", &results.browser),
+            ("System Cache", &results.system),
+        ];
+        self.cleanable = categories
+            .iter()
+            .filter(|(_, r)| r.items > 0)
+            .map(|(name, r)| CategoryTotal {
+                category: name.to_string(),
+                items: r.items,
+                bytes: r.size_bytes,
+            })
+            .collect();
+        self.cleanable.sort_by_key(|c| std::cmp::Reverse(c.bytes));
+    }
+}
+
+fn reason_label(reason: FailureReason) -> &'static str {
+    match reason {
+        FailureReason::Locked => "locked",
+        FailureReason::AccessDenied => "access-denied",
+        FailureReason::NotFound => "not-found",
+        FailureReason::PathTooLong => "path-too-long",
+        FailureReason::Changed => "changed",
+        FailureReason::Protected => "protected",
+        FailureReason::Other => "other",
+    }
+}
+
+/// Unicode sparkline scaled to the largest value
+pub fn sparkline(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&v| {
+            if max == 0 {
+                SPARK_CHARS[0]
+            } else {
+                let idx = (v as f64 / max as f64 * (SPARK_CHARS.len() - 1) as f64).round();
+                SPARK_CHARS[idx as usize]
+            }
+        })
+        .collect()
+}
+
+/// ASCII bar for `value` relative to `max`
+fn bar(value: u64, max: u64) -> String {
+    let filled = if max == 0 {
+        0
+    } else {
+        ((value as f64 / max as f64) * BAR_WIDTH as f64).round() as usize
+    };
+    format!("{}{}", "#".repeat(filled), ".".repeat(BAR_WIDTH - filled))
+}
+
+fn size(bytes: u64) -> String {
+    bytesize::to_string(bytes, false)
+}
+
+/// Render the report as markdown
+pub fn render_markdown(report: &WeeklyReport) -> String {
+    let mut md = String::new();
+    let _ = writeln!(
+        md,
+        "# wole weekly report: {} to {}",
+        report.period_start.format("%Y-%m-%d"),
+        report.period_end.format("%Y-%m-%d")
+    );
+    let _ = writeln!(md);
+
+    let _ = writeln!(md, "## Summary");
+    let _ = writeln!(md);
+    let _ = writeln!(md, "| | |");
+    let _ = writeln!(md, "|---|---|");
+    let _ = writeln!(md, "| Space freed | {} |", size(report.bytes_cleaned));
+    let _ = writeln!(md, "| Items cleaned | {} |", report.items_cleaned);
+    let _ = writeln!(md, "| Cleanup sessions | {} |", report.sessions);
+    let _ = writeln!(md, "| Failed deletions | {} |", report.errors);
+    let trend = if report.previous_bytes == 0 {
+        "no cleanups the week before".to_string()
+    } else {
+        let change = (report.bytes_cleaned as f64 - report.previous_bytes as f64)
+            / report.previous_bytes as f64
+            * 100.0;
+        format!(
+            "{:+.0}% vs previous week ({})",
+            change,
+            size(report.previous_bytes)
+        )
+    };
+    let _ = writeln!(md, "| Trend | {} |", trend);
+    let _ = writeln!(md);
+
+    let _ = writeln!(md, "## Daily activity");
+    let _ = writeln!(md);
+    let _ = writeln!(md, "```");
+    let _ = writeln!(md, "{}", sparkline(&report.daily_bytes));
+    let max_day = report.daily_bytes.iter().copied().max().unwrap_or(0);
+    for (i, bytes) in report.daily_bytes.iter().enumerate() {
+        let day = report.period_start + Duration::days(i as i64);
+        let _ = writeln!(
+            md,
+            "{} {} {:>10}",
+            day.format("%a %m-%d"),
+            bar(*bytes, max_day),
+            size(*bytes)
+        );
+    }
+    let _ = writeln!(md, "```");
+    let _ = writeln!(md);
+
+    if !report.by_category.is_empty() {
+        let _ = writeln!(md, "## Freed by category");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "| Category | Items | Size | |");
+        let _ = writeln!(md, "|---|---:|---:|---|");
+        let max = report.by_category.first().map(|c| c.bytes).unwrap_or(0);
+        for total in &report.by_category {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | `{}` |",
+                total.category,
+                total.items,
+                size(total.bytes),
+                bar(total.bytes, max)
+            );
+        }
+        let _ = writeln!(md);
+    }
+
+    if !report.top_items.is_empty() {
+        let _ = writeln!(md, "## Top offenders");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "| Path | Category | Size |");
+        let _ = writeln!(md, "|---|---|---:|");
+        for (path, category, bytes) in &report.top_items {
+            let _ = writeln!(md, "| `{}` | {} | {} |", path, category, size(*bytes));
+        }
+        let _ = writeln!(md);
+    }
+
+    if !report.failures.is_empty() {
+        let _ = writeln!(md, "## Failed deletions");
+        let _ = writeln!(md);
+        for (reason, count) in &report.failures {
+            let _ = writeln!(md, "- {}: {}", reason_label(*reason), count);
+        }
+        let _ = writeln!(md);
+    }
+
+    if !report.cleanable.is_empty() {
+        let total: u64 = report.cleanable.iter().map(|c| c.bytes).sum();
+        let _ = writeln!(md, "## Cleanable now");
+        let _ = writeln!(md);
+        let _ = writeln!(
+            md,
+            "{} could be freed from safe categories (`wole clean` to review).",
+            size(total)
+        );
+        let _ = writeln!(md);
+        let _ = writeln!(md, "| Category | Items | Size |");
+        let _ = writeln!(md, "|---|---:|---:|");
+        for total in &report.cleanable {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} |",
+                total.category,
+                total.items,
+                size(total.bytes)
+            );
+        }
+        let _ = writeln!(md);
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{DeletionRecord, FailureInfo};
+    use std::path::Path;
+
+    fn record_at(days_ago: i64, now: DateTime<Utc>, bytes: u64, category: &str) -> DeletionRecord {
+        let mut record = DeletionRecord::success(Path::new("/x"), bytes, category, false);
+        record.timestamp = now - Duration::days(days_ago) + Duration::hours(1);
+        record
+    }
+
+    #[test]
+    fn summarizes_week_and_previous_week() {
+        let now = Utc::now();
+        let mut log = DeletionLog::new();
+        log.add_record(record_at(1, now, 300, "cache"));
+        log.add_record(record_at(3, now, 100, "temp"));
+        log.add_record(record_at(10, now, 1000, "cache"));
+        let mut failed = DeletionRecord::failure_info(
+            Path::new("/y"),
+            5,
+            "temp",
+            false,
+            &FailureInfo::from_message("Path is locked by another process"),
+        );
+        failed.timestamp = now - Duration::days(2);
+        log.add_record(failed);
+
+        let report = build_weekly(&[log], now);
+        assert_eq!(report.sessions, 1);
+        assert_eq!(report.items_cleaned, 2);
+        assert_eq!(report.bytes_cleaned, 400);
+        assert_eq!(report.previous_bytes, 1000);
+        assert_eq!(report.by_category[0].category, "cache");
+        assert_eq!(report.failures, vec![(FailureReason::Locked, 1)]);
+        assert_eq!(report.daily_bytes.iter().sum::<u64>(), 400);
+
+        let md = render_markdown(&report);
+        assert!(md.starts_with("# wole weekly report"));
+        assert!(md.contains("## Top offenders"));
+        assert!(md.contains("-60% vs previous week"));
+    }
+
+    #[test]
+    fn sparkline_scales_to_max() {
+        assert_eq!(sparkline(&[0, 4, 8]), "▁▅█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+    }
+}
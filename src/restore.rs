@@ -1,11 +1,17 @@
 //! Restore functionality for recovering deleted files
 //!
-//! Provides ability to restore files from Recycle Bin using deletion history logs
+//! Provides ability to restore files from Recycle Bin using deletion history logs.
+//! Items cleaned with `[quarantine] enabled` are moved back from the
+//! quarantine folder instead, and build artifacts cleaned with `--archive`
+//! are unpacked from their archive.
 
-use crate::history::{list_logs, load_log, DeletionLog, DeletionRecord};
+use crate::cleaner::quarantine::Quarantine;
+use crate::history::{list_logs, load_log, session_id, DeletionLog, DeletionRecord};
 use crate::theme::Theme;
 use crate::trash_ops;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use globset::{GlobBuilder, GlobMatcher};
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::io::Write;
@@ -13,10 +19,10 @@ use std::path::{Path, PathBuf};
 
 /// Callback function type for progress updates during restoration
 pub type RestoreProgressCallback =
-    Box<dyn FnMut(Option<&Path>, usize, usize, usize, usize) -> Result<()>>;
+    Box<dyn FnMut(Option<&Path>, usize, usize, usize, usize) -> crate::Result<()>>;
 
 /// Get the count of files that can be restored from the most recent deletion session
-pub fn get_restore_count() -> Result<usize> {
+pub fn get_restore_count() -> crate::Result<usize> {
     let logs = list_logs()?;
 
     if logs.is_empty() {
@@ -26,37 +32,131 @@ pub fn get_restore_count() -> Result<usize> {
     // Get the most recent log
     let latest_log = load_log(&logs[0])?;
 
-    // Count restorable items (successful, non-permanent deletions)
+    // Count restorable items (Recycle Bin, quarantine or archive)
     let count = latest_log
         .records
         .iter()
-        .filter(|r| r.success && !r.permanent)
+        .filter(|r| r.is_restorable())
         .count();
 
     Ok(count)
 }
 
+/// Which entries of a deletion log to restore (everything by default)
+#[derive(Debug, Clone, Default)]
+pub struct RestoreFilter {
+    /// Category keys (`build`, `cache`, ...)
+    categories: Vec<String>,
+    pattern: Option<GlobMatcher>,
+    since: Option<DateTime<Utc>>,
+}
+
+impl RestoreFilter {
+    /// Filter on categories (CLI keys or display names), a glob on the file
+    /// name (`*.pdf`; patterns with a separator match the whole path), and
+    /// how recently the entry was deleted (`30m`, `2h`, `3d`, `1w`)
+    pub fn new(
+        categories: &[String],
+        pattern: Option<&str>,
+        since: Option<&str>,
+    ) -> crate::Result<Self> {
+        let pattern = pattern
+            .map(|p| {
+                GlobBuilder::new(p)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .map(|glob| glob.compile_matcher())
+                    .with_context(|| format!("Invalid pattern '{}'", p))
+            })
+            .transpose()?;
+        let since = since
+            .map(parse_age)
+            .transpose()?
+            .map(|age| Utc::now() - age);
+        Ok(Self {
+            categories: categories
+                .iter()
+                .map(|c| crate::config::category_key(c))
+                .collect(),
+            pattern,
+            since,
+        })
+    }
+
+    /// Whether `record` passes every filter given
+    pub fn matches(&self, record: &DeletionRecord) -> bool {
+        if !self.categories.is_empty()
+            && !self
+                .categories
+                .contains(&crate::config::category_key(&record.category))
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.pattern {
+            let path = Path::new(&record.path);
+            let matched = if pattern.glob().glob().contains(['/', '\\']) {
+                pattern.is_match(path)
+            } else {
+                path.file_name().is_some_and(|name| pattern.is_match(name))
+            };
+            if !matched {
+                return false;
+            }
+        }
+        self.since.is_none_or(|since| record.timestamp >= since)
+    }
+
+    /// `log` with only the entries that match
+    pub fn apply(&self, log: &DeletionLog) -> DeletionLog {
+        let mut filtered = log.clone();
+        filtered.records.retain(|r| self.matches(r));
+        filtered
+    }
+}
+
+/// Parse an age like `30m`, `2h`, `3d` or `1w`
+fn parse_age(age: &str) -> Result<Duration> {
+    let age = age.trim();
+    let split = age.len() - age.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (number, unit) = age.split_at(split);
+    let Ok(number) = number.parse::<i64>() else {
+        bail!("'{}' is not an age like 30m, 2h, 3d or 1w", age);
+    };
+    Ok(match unit.trim().to_lowercase().as_str() {
+        "m" | "min" => Duration::minutes(number),
+        "h" => Duration::hours(number),
+        "d" => Duration::days(number),
+        "w" => Duration::weeks(number),
+        _ => bail!("'{}' is not an age like 30m, 2h, 3d or 1w", age),
+    })
+}
+
 /// Restore files from the most recent deletion session
-pub fn restore_last(output_mode: crate::output::OutputMode) -> Result<RestoreResult> {
-    restore_last_with_progress(output_mode, None)
+pub fn restore_last(
+    filter: &RestoreFilter,
+    output_mode: crate::output::OutputMode,
+) -> crate::Result<RestoreResult> {
+    restore_last_with_progress(filter, output_mode, None)
 }
 
 /// Restore files from the most recent deletion session with progress callback
 pub fn restore_last_with_progress(
+    filter: &RestoreFilter,
     output_mode: crate::output::OutputMode,
     progress_callback: Option<RestoreProgressCallback>,
-) -> Result<RestoreResult> {
+) -> crate::Result<RestoreResult> {
     let logs = list_logs()?;
 
     if logs.is_empty() {
-        return Err(anyhow::anyhow!(
-            "No deletion history found. Nothing to restore."
+        return Err(crate::Error::NotFound(
+            "No deletion history found. Nothing to restore.".to_string(),
         ));
     }
 
     // Get the most recent log
     let latest_log = load_log(&logs[0])?;
-    restore_from_log_with_progress(&latest_log, output_mode, progress_callback)
+    restore_from_log_with_progress(&filter.apply(&latest_log), output_mode, progress_callback)
 }
 
 /// Normalize a path for comparison (handles case-insensitive matching on Windows)
@@ -100,12 +200,13 @@ fn is_temp_directory(path: &Path) -> bool {
     path_normalized.contains("/temp/") || path_normalized.contains("/tmp/")
 }
 
-/// Restore files from a specific deletion log
+/// Restore the entries of a deletion log that `filter` matches
 pub fn restore_from_log(
     log: &DeletionLog,
+    filter: &RestoreFilter,
     output_mode: crate::output::OutputMode,
-) -> Result<RestoreResult> {
-    restore_from_log_with_progress(log, output_mode, None)
+) -> crate::Result<RestoreResult> {
+    restore_from_log_with_progress(&filter.apply(log), output_mode, None)
 }
 
 /// Restore files from a specific deletion log with progress callback
@@ -114,19 +215,20 @@ pub fn restore_from_log_with_progress(
     log: &DeletionLog,
     output_mode: crate::output::OutputMode,
     mut progress_callback: Option<RestoreProgressCallback>,
-) -> Result<RestoreResult> {
+) -> crate::Result<RestoreResult> {
     let mut result = RestoreResult::default();
 
+    // Count total items to restore
+    let total_to_restore = log.records.iter().filter(|r| r.is_restorable()).count();
+
+    // Archived items are unpacked and quarantined items come back by a
+    // rename; the rest are looked up in the bin
+    let log = &restore_archived(log, &mut result, output_mode);
+    let log = &restore_quarantined(log, &mut result, output_mode)?;
+
     // Get current Recycle Bin contents
     let recycle_bin_items = trash_ops::list().context("Failed to list Recycle Bin contents")?;
 
-    // Count total items to restore
-    let total_to_restore = log
-        .records
-        .iter()
-        .filter(|r| r.success && !r.permanent)
-        .count();
-
     // Create a map of Recycle Bin items by original path
     // Windows Recycle Bin stores files with their original paths in metadata
     // Use normalized paths for better matching
@@ -400,8 +502,108 @@ pub fn restore_from_log_with_progress(
     Ok(result)
 }
 
+/// Unpack every record of `log` whose archive is still there. Returns the
+/// log of what's left to restore some other way.
+fn restore_archived(
+    log: &DeletionLog,
+    result: &mut RestoreResult,
+    output_mode: crate::output::OutputMode,
+) -> DeletionLog {
+    let mut remaining = log.clone();
+    remaining.records.clear();
+    for record in &log.records {
+        let archive = record
+            .archive
+            .as_deref()
+            .map(Path::new)
+            .filter(|archive| record.success && archive.is_file());
+        let (Some(archive), Some(parent)) = (archive, Path::new(&record.path).parent()) else {
+            remaining.records.push(record.clone());
+            continue;
+        };
+        match crate::cleaner::archive::extract(archive, parent) {
+            Ok(()) => {
+                result.restored += 1;
+                result.restored_bytes += record.size_bytes;
+                if output_mode != crate::output::OutputMode::Quiet {
+                    println!(
+                        "{} Unpacked: {}",
+                        Theme::success("✓"),
+                        Theme::secondary(&record.path)
+                    );
+                }
+            }
+            Err(err) => {
+                result.errors += 1;
+                result.error_reasons.push(format!("{:#}", err));
+                if output_mode != crate::output::OutputMode::Quiet {
+                    eprintln!(
+                        "{} Failed to unpack {}: {}",
+                        Theme::error("✗"),
+                        Theme::secondary(&record.path),
+                        Theme::error(&format!("{:#}", err))
+                    );
+                }
+            }
+        }
+    }
+    remaining
+}
+
+/// Move back every record of `log` found in the quarantine. Returns the log
+/// of what's left to look up in the Recycle Bin.
+fn restore_quarantined(
+    log: &DeletionLog,
+    result: &mut RestoreResult,
+    output_mode: crate::output::OutputMode,
+) -> Result<DeletionLog> {
+    let quarantine = Quarantine::open(&crate::config::Config::load().quarantine);
+    let mut remaining = log.clone();
+    remaining.records.clear();
+    for record in &log.records {
+        let entry = if record.success && !record.permanent {
+            quarantine.find(Path::new(&record.path))?
+        } else {
+            None
+        };
+        let Some(entry) = entry else {
+            remaining.records.push(record.clone());
+            continue;
+        };
+        match quarantine.restore(&entry) {
+            Ok(()) => {
+                result.restored += 1;
+                result.restored_bytes += record.size_bytes;
+                if output_mode != crate::output::OutputMode::Quiet {
+                    println!(
+                        "{} Restored: {}",
+                        Theme::success("✓"),
+                        Theme::secondary(&record.path)
+                    );
+                }
+            }
+            Err(err) => {
+                result.errors += 1;
+                result.error_reasons.push(format!("{:#}", err));
+                if output_mode != crate::output::OutputMode::Quiet {
+                    eprintln!(
+                        "{} Failed to restore {}: {}",
+                        Theme::error("✗"),
+                        Theme::secondary(&record.path),
+                        Theme::error(&format!("{:#}", err))
+                    );
+                }
+            }
+        }
+    }
+    Ok(remaining)
+}
+
 /// Restore a specific file by path
-pub fn restore_path(path: &Path, output_mode: crate::output::OutputMode) -> Result<RestoreResult> {
+pub fn restore_path(
+    path: &Path,
+    output_mode: crate::output::OutputMode,
+) -> crate::Result<RestoreResult> {
     let mut result = RestoreResult::default();
 
     // Get current Recycle Bin contents
@@ -438,13 +640,7 @@ pub fn restore_path(path: &Path, output_mode: crate::output::OutputMode) -> Resu
                     }
                     return Ok(result);
                 }
-                Err(e) => {
-                    return Err(anyhow::anyhow!(
-                        "Failed to restore {}: {}",
-                        path.display(),
-                        e
-                    ));
-                }
+                Err(e) => return Err(e),
             }
         }
     }
@@ -511,10 +707,10 @@ pub fn restore_path(path: &Path, output_mode: crate::output::OutputMode) -> Resu
 
         Ok(result)
     } else {
-        Err(anyhow::anyhow!(
+        Err(crate::Error::NotFound(format!(
             "File or directory not found in Recycle Bin: {}",
             path.display()
-        ))
+        )))
     }
 }
 
@@ -522,7 +718,7 @@ pub fn restore_path(path: &Path, output_mode: crate::output::OutputMode) -> Resu
 pub fn restore_all_bin(
     output_mode: crate::output::OutputMode,
     mut progress_callback: Option<RestoreProgressCallback>,
-) -> Result<RestoreResult> {
+) -> crate::Result<RestoreResult> {
     let mut result = RestoreResult::default();
 
     // Get current Recycle Bin contents
@@ -694,7 +890,7 @@ pub fn restore_all_bin(
 }
 
 /// Restore a single file from Recycle Bin
-pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
+pub fn restore_file(item: &trash::TrashItem) -> crate::Result<()> {
     let dest = item.original_parent.join(&item.name);
 
     // Check if parent directory exists and is accessible
@@ -705,17 +901,20 @@ pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
                 Ok(()) => {
                     // Verify parent directory was actually created
                     if !parent.exists() {
-                        return Err(anyhow::anyhow!(
+                        return Err(crate::Error::Other(format!(
                             "Parent directory does not exist and could not be created: {}",
                             parent.display()
-                        ));
+                        )));
                     }
                 }
                 Err(e) => {
-                    return Err(anyhow::anyhow!(
-                        "Failed to create parent directory {}: {}",
-                        parent.display(),
-                        e
+                    return Err(crate::Error::io(
+                        format!(
+                            "Failed to create parent directory {}: {}",
+                            parent.display(),
+                            e
+                        ),
+                        &e,
                     ));
                 }
             }
@@ -724,17 +923,16 @@ pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
             match std::fs::metadata(parent) {
                 Ok(metadata) => {
                     if !metadata.is_dir() {
-                        return Err(anyhow::anyhow!(
+                        return Err(crate::Error::Other(format!(
                             "Parent path exists but is not a directory: {}",
                             parent.display()
-                        ));
+                        )));
                     }
                 }
                 Err(e) => {
-                    return Err(anyhow::anyhow!(
-                        "Cannot access parent directory {}: {}",
-                        parent.display(),
-                        e
+                    return Err(crate::Error::io(
+                        format!("Cannot access parent directory {}: {}", parent.display(), e),
+                        &e,
                     ));
                 }
             }
@@ -743,10 +941,10 @@ pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
 
     // Check if destination already exists
     if dest.exists() {
-        return Err(anyhow::anyhow!(
-            "Destination already exists: {}",
-            dest.display()
-        ));
+        return Err(crate::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("Destination already exists: {}", dest.display()),
+        )));
     }
 
     // Move file back from Recycle Bin to original location
@@ -761,22 +959,165 @@ pub fn restore_file(item: &trash::TrashItem) -> Result<()> {
             if is_temp_directory(&dest) {
                 // Check if the error is the Windows Recycle Bin error code
                 if error_msg.contains("0x80270022") || error_msg.contains("-2144927710") {
-                    return Err(anyhow::anyhow!(
+                    return Err(crate::Error::Other(format!(
                         "Cannot restore to temp directory (likely cleaned up): {}\n\
                         The original temp directory may have been deleted by Windows.\n\
                         Temp files are typically safe to leave in the Recycle Bin.",
                         dest.display()
-                    ));
+                    )));
                 }
             }
 
-            Err(anyhow::anyhow!(
+            Err(crate::Error::Other(format!(
                 "Failed to restore file to {}: {}",
                 dest.display(),
                 error_msg
-            ))
+            )))
+        }
+    }
+}
+
+/// A past deletion session and how much of it is still in the Recycle Bin
+#[derive(Debug, Clone)]
+pub struct RestorePoint {
+    pub log_path: PathBuf,
+    /// Session id for `wole restore --session` (the log's timestamp, `20250114_093012`)
+    pub id: String,
+    pub session_start: DateTime<Utc>,
+    /// Categories cleaned in the session, in the order they were cleaned
+    pub categories: Vec<String>,
+    /// Items moved to the Recycle Bin in this session
+    pub items: usize,
+    pub bytes: u64,
+    /// Of those, items the Recycle Bin still holds (not emptied or purged by Windows)
+    pub recoverable_items: usize,
+    pub recoverable_bytes: u64,
+    /// Items deleted permanently, which can't be restored
+    pub permanent_items: usize,
+}
+
+impl RestorePoint {
+    fn from_log(log_path: PathBuf, log: &DeletionLog, bin_paths: &HashSet<String>) -> Self {
+        let mut point = Self {
+            id: session_id(&log_path),
+            log_path,
+            session_start: log.session_start,
+            categories: Vec::new(),
+            items: 0,
+            bytes: 0,
+            recoverable_items: 0,
+            recoverable_bytes: 0,
+            permanent_items: 0,
+        };
+        for record in log.records.iter().filter(|r| r.success) {
+            if !point.categories.contains(&record.category) {
+                point.categories.push(record.category.clone());
+            }
+            if record.permanent {
+                point.permanent_items += 1;
+                continue;
+            }
+            point.items += 1;
+            point.bytes += record.size_bytes;
+            if is_in_bin(&normalize_path_for_comparison(&record.path), bin_paths) {
+                point.recoverable_items += 1;
+                point.recoverable_bytes += record.size_bytes;
+            }
         }
+        point
+    }
+
+    /// Whether every trashed item of the session can still be restored
+    pub fn fully_recoverable(&self) -> bool {
+        self.recoverable_items == self.items
+    }
+}
+
+/// Whether the Recycle Bin holds `path` itself or, for a deleted directory,
+/// any file from inside it
+fn is_in_bin(path: &str, bin_paths: &HashSet<String>) -> bool {
+    if bin_paths.contains(path) {
+        return true;
     }
+    let prefix = format!("{}/", path.trim_end_matches('/'));
+    bin_paths
+        .iter()
+        .any(|bin_path| bin_path.starts_with(&prefix))
+}
+
+/// The history log of the session `id` (as shown by `wole history list` or
+/// `wole restore --list`)
+pub fn session_log(id: &str) -> crate::Result<PathBuf> {
+    list_logs()?
+        .into_iter()
+        .find(|path| session_id(path) == id)
+        .ok_or_else(|| {
+            crate::Error::NotFound(format!(
+                "No deletion session '{}'. Run 'wole history list' to see sessions.",
+                id
+            ))
+        })
+}
+
+/// Original paths of everything in the Recycle Bin, normalized for comparison
+/// Original paths of everything recoverable: Recycle Bin items and
+/// quarantined ones
+fn bin_paths() -> Result<HashSet<String>> {
+    let quarantined = Quarantine::open(&crate::config::Config::load().quarantine).entries()?;
+    Ok(trash_ops::list()
+        .context("Failed to list Recycle Bin contents")?
+        .iter()
+        .map(|item| item.original_parent.join(&item.name))
+        .chain(quarantined.into_iter().map(|entry| entry.original))
+        .map(|path| normalize_path_for_comparison(&path.display().to_string()))
+        .collect())
+}
+
+/// Every past deletion session, newest first, with what's still recoverable
+pub fn restore_points() -> crate::Result<Vec<RestorePoint>> {
+    let bin_paths = bin_paths()?;
+    Ok(list_logs()?
+        .into_iter()
+        .filter_map(|path| {
+            let log = load_log(&path).ok()?;
+            Some(RestorePoint::from_log(path, &log, &bin_paths))
+        })
+        .collect())
+}
+
+/// One item of a session as a restore would treat it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewItem {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub category: String,
+    /// Still in the Recycle Bin, so it can be restored
+    pub in_bin: bool,
+    /// Something already exists at the original path
+    pub conflict: bool,
+}
+
+/// What restoring `log` would do, item by item. Permanent deletions are left
+/// out: they can't be restored.
+pub fn preview_log(log: &DeletionLog) -> crate::Result<Vec<PreviewItem>> {
+    Ok(preview_items(log, &bin_paths()?))
+}
+
+fn preview_items(log: &DeletionLog, bin_paths: &HashSet<String>) -> Vec<PreviewItem> {
+    log.records
+        .iter()
+        .filter(|r| r.success && !r.permanent)
+        .map(|record| {
+            let path = PathBuf::from(&record.path);
+            PreviewItem {
+                in_bin: is_in_bin(&normalize_path_for_comparison(&record.path), bin_paths),
+                conflict: path.symlink_metadata().is_ok(),
+                path,
+                size_bytes: record.size_bytes,
+                category: record.category.clone(),
+            }
+        })
+        .collect()
 }
 
 /// Result of a restore operation
@@ -805,6 +1146,113 @@ impl RestoreResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn restore_point_counts_items_still_in_bin() {
+        let mut log = DeletionLog::new();
+        log.log_success(Path::new("/home/u/cache/a.bin"), 100, "cache", false);
+        log.log_success(Path::new("/home/u/build/target"), 400, "build", false);
+        log.log_success(Path::new("/home/u/cache/purged.bin"), 50, "cache", false);
+        log.log_success(Path::new("/home/u/temp/gone.tmp"), 10, "temp", true);
+        log.log_failure(Path::new("/home/u/locked"), 5, "temp", false, "locked");
+
+        let bin_paths: HashSet<String> = ["/home/u/cache/a.bin", "/home/u/build/target/debug/app"]
+            .iter()
+            .map(|p| normalize_path_for_comparison(p))
+            .collect();
+        let point = RestorePoint::from_log(PathBuf::from("log.json"), &log, &bin_paths);
+
+        assert_eq!((point.items, point.bytes), (3, 550));
+        assert_eq!((point.recoverable_items, point.recoverable_bytes), (2, 500));
+        assert_eq!(point.permanent_items, 1);
+        assert_eq!(point.categories, vec!["cache", "build", "temp"]);
+        assert!(!point.fully_recoverable());
+    }
+
+    #[test]
+    fn filter_selects_by_category_pattern_and_age() {
+        let mut log = DeletionLog::new();
+        log.log_success(
+            Path::new("/home/u/Downloads/paper.PDF"),
+            1,
+            "Old Downloads",
+            false,
+        );
+        log.log_success(
+            Path::new("/home/u/app/node_modules"),
+            2,
+            "build artifacts",
+            false,
+        );
+        log.log_success(Path::new("/home/u/app/dist"), 3, "build artifacts", false);
+        log.records[2].timestamp = Utc::now() - Duration::hours(3);
+
+        let kept = |filter: RestoreFilter| -> Vec<u64> {
+            filter
+                .apply(&log)
+                .records
+                .iter()
+                .map(|r| r.size_bytes)
+                .collect()
+        };
+        assert_eq!(kept(RestoreFilter::default()), vec![1, 2, 3]);
+        let build = ["build".to_string()];
+        assert_eq!(
+            kept(RestoreFilter::new(&build, None, None).unwrap()),
+            vec![2, 3]
+        );
+        assert_eq!(
+            kept(RestoreFilter::new(&build, None, Some("2h")).unwrap()),
+            vec![2]
+        );
+        assert_eq!(
+            kept(RestoreFilter::new(&[], Some("*.pdf"), None).unwrap()),
+            vec![1]
+        );
+        assert_eq!(
+            kept(RestoreFilter::new(&[], Some("/home/u/app/*"), None).unwrap()),
+            vec![2, 3]
+        );
+        assert!(RestoreFilter::new(&[], None, Some("2 days")).is_err());
+    }
+
+    #[test]
+    fn preview_flags_purged_items_and_conflicts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let back = temp.path().join("back.log");
+        std::fs::write(&back, b"recreated since").unwrap();
+        let free = temp.path().join("free.log");
+        let purged = temp.path().join("purged.log");
+
+        let mut log = DeletionLog::new();
+        log.log_success(&back, 10, "temp", false);
+        log.log_success(&free, 20, "temp", false);
+        log.log_success(&purged, 30, "temp", false);
+        log.log_success(&temp.path().join("gone"), 40, "temp", true);
+
+        let bin_paths: HashSet<String> = [&back, &free]
+            .iter()
+            .map(|p| normalize_path_for_comparison(&p.display().to_string()))
+            .collect();
+        let preview = preview_items(&log, &bin_paths);
+
+        let flags: Vec<(PathBuf, bool, bool)> = preview
+            .into_iter()
+            .map(|item| (item.path, item.in_bin, item.conflict))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                (back, true, true),
+                (free, true, false),
+                (purged, false, false)
+            ]
+        );
+        assert_eq!(
+            session_id(Path::new("h/cleanup_20250114_093012.json")),
+            "20250114_093012"
+        );
+    }
+
     #[test]
     fn test_restore_result_default() {
         let result = RestoreResult::default();
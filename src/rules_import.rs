@@ -0,0 +1,624 @@
+//! Import cleaning rules from BleachBit and CCleaner
+//!
+//! Converts BleachBit cleaner definitions (`*.xml`) and CCleaner `winapp2.ini`
+//! style sections into `[[custom_categories]]` entries, so rules curated for
+//! other tools carry over. Only plain file deletion is imported; registry,
+//! SQLite vacuum, JSON/INI editing, Linux/macOS-only actions and winapp2
+//! sections with exclusions are reported as skipped.
+
+use crate::config::{CustomCategory, CustomRule};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Outcome of an import
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub categories: Vec<CustomCategory>,
+    /// Human-readable notes about rules that could not be converted
+    pub skipped: Vec<String>,
+}
+
+/// Import a rules file, detecting the format from its extension or content
+pub fn import_file(path: &Path) -> Result<ImportReport> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read rules file: {}", path.display()))?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let is_xml = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("xml"))
+        .unwrap_or(false)
+        || content.trim_start().starts_with('<');
+
+    if is_xml {
+        import_bleachbit(&content, &format!("bleachbit:{}", file_name))
+    } else {
+        Ok(import_winapp2(&content, &format!("ccleaner:{}", file_name)))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BleachBit
+// ---------------------------------------------------------------------------
+
+/// Minimal XML element, enough for BleachBit cleaner files
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: HashMap<String, String>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(|s| s.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    fn child_text(&self, name: &str) -> Option<String> {
+        self.child(name).map(|c| c.text.trim().to_string())
+    }
+}
+
+/// Convert a BleachBit cleaner XML document into custom categories
+pub fn import_bleachbit(xml: &str, source: &str) -> Result<ImportReport> {
+    let root = parse_xml(xml)?;
+    let cleaner = if root.name == "cleaner" {
+        &root
+    } else {
+        root.child("cleaner")
+            .context("Not a BleachBit cleaner file (no <cleaner> element)")?
+    };
+
+    let mut report = ImportReport::default();
+    let cleaner_id = cleaner.attr("id").unwrap_or("cleaner");
+    let cleaner_label = cleaner
+        .child_text("label")
+        .unwrap_or_else(|| cleaner_id.to_string());
+
+    if !os_matches(cleaner.attr("os")) {
+        report
+            .skipped
+            .push(format!("{}: not a Windows cleaner", cleaner_label));
+        return Ok(report);
+    }
+
+    // <var name="profile"><value>...</value></var> is referenced as $$profile$$
+    let mut vars: HashMap<String, Vec<String>> = HashMap::new();
+    for var in cleaner.children_named("var") {
+        if let Some(name) = var.attr("name") {
+            let values = var
+                .children_named("value")
+                .filter(|v| os_matches(v.attr("os")))
+                .map(|v| v.text.trim().to_string())
+                .collect();
+            vars.insert(name.to_string(), values);
+        }
+    }
+
+    for option in cleaner.children_named("option") {
+        let option_label = option
+            .child_text("label")
+            .or_else(|| option.attr("id").map(String::from))
+            .unwrap_or_default();
+        let name = format!("{}: {}", cleaner_label, option_label);
+        let mut rules = Vec::new();
+
+        for action in option.children_named("action") {
+            match convert_bleachbit_action(action, &vars) {
+                Ok(mut converted) => rules.append(&mut converted),
+                Err(reason) => report.skipped.push(format!("{}: {}", name, reason)),
+            }
+        }
+
+        if rules.is_empty() {
+            continue;
+        }
+        report.categories.push(CustomCategory {
+            name,
+            description: option.child_text("description"),
+            source: Some(source.to_string()),
+            enabled: true,
+            rules,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Convert one `<action>`; Err carries the reason it was skipped
+fn convert_bleachbit_action(
+    action: &Element,
+    vars: &HashMap<String, Vec<String>>,
+) -> std::result::Result<Vec<CustomRule>, String> {
+    if !os_matches(action.attr("os")) {
+        return Ok(Vec::new());
+    }
+    let command = action.attr("command").unwrap_or("");
+    if command != "delete" {
+        return Err(format!("'{}' actions are not supported", command));
+    }
+    if action.attr("nregex").is_some() || action.attr("wholeregex").is_some() {
+        return Err("negated/whole-path regex filters are not supported".to_string());
+    }
+    let raw_path = action.attr("path").ok_or("action has no path")?;
+
+    let pattern = match action.attr("regex") {
+        Some(regex) => {
+            Some(regex_to_glob(regex).ok_or_else(|| format!("regex '{}' is too complex", regex))?)
+        }
+        None => None,
+    };
+
+    let search = action.attr("search").unwrap_or("file");
+    let recursive = match search {
+        "file" | "glob" | "walk.top" => false,
+        "walk.files" | "walk.all" => true,
+        other => return Err(format!("search type '{}' is not supported", other)),
+    };
+
+    let mut rules = Vec::new();
+    for path in substitute_bleachbit_vars(raw_path, vars) {
+        let path = convert_bleachbit_path(&path).ok_or("path is not a Windows path")?;
+        rules.push(CustomRule {
+            path,
+            patterns: pattern.iter().cloned().collect(),
+            recursive,
+        });
+    }
+    Ok(rules)
+}
+
+/// Expand `$$name$$` references to `<var>` values (one path per value)
+fn substitute_bleachbit_vars(path: &str, vars: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut paths = vec![path.to_string()];
+    for (name, values) in vars {
+        let token = format!("$${}$$", name);
+        if !path.contains(&token) {
+            continue;
+        }
+        paths = paths
+            .iter()
+            .flat_map(|p| values.iter().map(|v| p.replace(&token, v)))
+            .collect();
+    }
+    paths
+}
+
+/// Translate BleachBit's `$var` environment syntax to `%VAR%`; None for Unix paths
+fn convert_bleachbit_path(path: &str) -> Option<String> {
+    if path.starts_with('/') || path.starts_with('~') {
+        return None;
+    }
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '$' && chars.peek().is_some_and(|n| n.is_ascii_alphabetic()) {
+            let mut name = String::new();
+            while let Some(&n) = chars.peek() {
+                if n.is_ascii_alphanumeric() || n == '_' {
+                    name.push(n);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            out.push('%');
+            out.push_str(&name.to_uppercase());
+            out.push('%');
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// Whether an `os` attribute applies to Windows
+fn os_matches(os: Option<&str>) -> bool {
+    match os {
+        None => true,
+        Some(os) => os.eq_ignore_ascii_case("windows"),
+    }
+}
+
+/// Best-effort conversion of simple file name regexes (`^.*\.log$`) to globs
+fn regex_to_glob(regex: &str) -> Option<String> {
+    let body = regex.strip_prefix('^').unwrap_or(regex);
+    let body = body.strip_suffix('$').unwrap_or(body);
+    let mut glob = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' if chars.peek() == Some(&'*') => {
+                chars.next();
+                glob.push('*');
+            }
+            '.' => glob.push('?'),
+            '\\' => match chars.next()? {
+                c @ ('.' | '-' | '_' | '$' | '~' | '(' | ')' | '[' | ']') => glob.push(c),
+                _ => return None,
+            },
+            c if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ' | '~' | '#') => glob.push(c),
+            _ => return None,
+        }
+    }
+    // An unanchored regex matches anywhere in the name
+    if !regex.starts_with('^') && !glob.starts_with('*') {
+        glob.insert(0, '*');
+    }
+    if !regex.ends_with('$') && !glob.ends_with('*') {
+        glob.push('*');
+    }
+    Some(glob)
+}
+
+/// Parse an XML document into an element tree (no DTDs, namespaces or CDATA)
+fn parse_xml(xml: &str) -> Result<Element> {
+    let mut stack: Vec<Element> = vec![Element::default()];
+    let mut rest = xml;
+
+    while let Some(lt) = rest.find('<') {
+        let text = &rest[..lt];
+        if let Some(top) = stack.last_mut() {
+            top.text.push_str(&decode_entities(text));
+        }
+        rest = &rest[lt..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").context("Unterminated XML comment")?;
+            rest = &after[end + 3..];
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').context("Unterminated XML declaration")?;
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        let end = find_tag_end(rest).context("Unterminated XML tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let element = stack.pop().context("Unbalanced XML")?;
+            if element.name != name.trim() {
+                anyhow::bail!("Mismatched closing tag </{}>", name.trim());
+            }
+            stack
+                .last_mut()
+                .context("Unbalanced XML")?
+                .children
+                .push(element);
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let element = parse_tag(tag);
+        if self_closing {
+            stack
+                .last_mut()
+                .context("Unbalanced XML")?
+                .children
+                .push(element);
+        } else {
+            stack.push(element);
+        }
+    }
+
+    if stack.len() != 1 {
+        anyhow::bail!("Unclosed XML element");
+    }
+    let mut document = stack.pop().unwrap_or_default();
+    if document.children.len() == 1 {
+        Ok(document.children.remove(0))
+    } else {
+        Ok(document)
+    }
+}
+
+/// Index of the `>` closing a tag, skipping quoted attribute values
+fn find_tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_tag(tag: &str) -> Element {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = Element {
+        name: tag[..name_end].to_string(),
+        ..Element::default()
+    };
+
+    let mut rest = tag[name_end..].trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let value_part = rest[eq + 1..].trim_start();
+        let Some(quote) = value_part
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+        else {
+            break;
+        };
+        let Some(close) = value_part[1..].find(quote) else {
+            break;
+        };
+        element
+            .attrs
+            .insert(key, decode_entities(&value_part[1..1 + close]));
+        rest = value_part[close + 2..].trim_start();
+    }
+    element
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// ---------------------------------------------------------------------------
+// CCleaner (winapp2.ini)
+// ---------------------------------------------------------------------------
+
+/// Convert CCleaner `winapp2.ini` style sections into custom categories.
+///
+/// `FileKeyN=path|patterns|RECURSE` entries become rules; registry keys are
+/// skipped. Sections with `ExcludeKeyN` entries are skipped whole, since
+/// their file keys alone would delete what the exclusions protect. Section
+/// names drop the trailing ` *` marker.
+pub fn import_winapp2(ini: &str, source: &str) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut current: Option<CustomCategory> = None;
+    // First ExcludeKey of the current section, if any
+    let mut excluded: Option<String> = None;
+
+    let finish =
+        |category: Option<CustomCategory>, excluded: Option<String>, report: &mut ImportReport| {
+            let Some(category) = category else {
+                return;
+            };
+            if let Some(key) = excluded {
+                report.skipped.push(format!(
+                    "{}: skipped, {} excludes files its rules would delete",
+                    category.name, key
+                ));
+            } else if !category.rules.is_empty() {
+                report.categories.push(category);
+            }
+        };
+
+    for line in ini.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            finish(current.take(), excluded.take(), &mut report);
+            let name = section.trim().trim_end_matches('*').trim().to_string();
+            current = Some(CustomCategory {
+                name,
+                description: None,
+                source: Some(source.to_string()),
+                enabled: true,
+                rules: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(category) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let lower_key = key.to_ascii_lowercase();
+
+        if lower_key.starts_with("filekey") {
+            match convert_file_key(value.trim()) {
+                Some(rule) => category.rules.push(rule),
+                None => report
+                    .skipped
+                    .push(format!("{}: could not parse {}", category.name, key)),
+            }
+        } else if lower_key.starts_with("regkey") {
+            report.skipped.push(format!(
+                "{}: registry keys are not supported",
+                category.name
+            ));
+        } else if lower_key.starts_with("excludekey") && excluded.is_none() {
+            excluded = Some(key.to_string());
+        }
+    }
+    finish(current.take(), excluded.take(), &mut report);
+
+    report
+}
+
+/// Parse `path|pattern1;pattern2|RECURSE`
+fn convert_file_key(value: &str) -> Option<CustomRule> {
+    let mut parts = value.split('|');
+    let path = parts.next()?.trim();
+    if path.is_empty() {
+        return None;
+    }
+    let patterns: Vec<String> = parts
+        .next()
+        .map(|p| {
+            p.split(';')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let recursive = parts.any(|flag| {
+        flag.trim().eq_ignore_ascii_case("RECURSE")
+            || flag.trim().eq_ignore_ascii_case("REMOVESELF")
+    });
+
+    Some(CustomRule {
+        path: path.trim_end_matches('\\').to_string(),
+        patterns,
+        recursive,
+    })
+}
+
+/// Add imported categories to `existing`, replacing any with the same name.
+/// Returns (added, replaced).
+pub fn merge_into(
+    existing: &mut Vec<CustomCategory>,
+    imported: Vec<CustomCategory>,
+) -> (usize, usize) {
+    let mut added = 0;
+    let mut replaced = 0;
+    for category in imported {
+        match existing.iter_mut().find(|c| c.name == category.name) {
+            Some(slot) => {
+                *slot = category;
+                replaced += 1;
+            }
+            None => {
+                existing.push(category);
+                added += 1;
+            }
+        }
+    }
+    (added, replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREFOX_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- sample -->
+<cleaner id="firefox">
+  <label>Firefox</label>
+  <var name="profile">
+    <value os="windows">$APPDATA\Mozilla\Firefox\Profiles\*</value>
+    <value os="linux">~/.mozilla/firefox/*</value>
+  </var>
+  <option id="cache">
+    <label>Cache</label>
+    <description>Delete the web cache</description>
+    <action command="delete" search="walk.files" path="$localappdata\Mozilla\Firefox\Profiles\*\cache2"/>
+    <action command="delete" search="walk.files" path="~/.cache/mozilla" os="linux"/>
+  </option>
+  <option id="logs">
+    <label>Logs</label>
+    <action command="delete" search="glob" path="$$profile$$" regex="^.*\.log$"/>
+    <action command="sqlite.vacuum" search="file" path="$$profile$$\places.sqlite"/>
+  </option>
+</cleaner>"#;
+
+    #[test]
+    fn imports_bleachbit_options() {
+        let report = import_bleachbit(FIREFOX_XML, "bleachbit:firefox.xml").unwrap();
+        assert_eq!(report.categories.len(), 2);
+
+        let cache = &report.categories[0];
+        assert_eq!(cache.name, "Firefox: Cache");
+        assert_eq!(cache.description.as_deref(), Some("Delete the web cache"));
+        assert_eq!(
+            cache.rules,
+            vec![CustomRule {
+                path: r"%LOCALAPPDATA%\Mozilla\Firefox\Profiles\*\cache2".to_string(),
+                patterns: Vec::new(),
+                recursive: true,
+            }]
+        );
+
+        let logs = &report.categories[1];
+        assert_eq!(logs.rules[0].path, r"%APPDATA%\Mozilla\Firefox\Profiles\*");
+        assert_eq!(logs.rules[0].patterns, vec!["*.log".to_string()]);
+        assert!(report.skipped.iter().any(|s| s.contains("sqlite.vacuum")));
+    }
+
+    #[test]
+    fn imports_winapp2_sections() {
+        let ini = "; comment\n\
+                   [Example App *]\n\
+                   LangSecRef=3021\n\
+                   Detect=HKCU\\Software\\Example\n\
+                   FileKey1=%LocalAppData%\\Example\\Cache|*.*|RECURSE\n\
+                   FileKey2=%AppData%\\Example|*.log;*.tmp\n\
+                   RegKey1=HKCU\\Software\\Example\\MRU\n\
+                   \n\
+                   [Empty]\n\
+                   Detect=HKCU\\Software\\Nothing\n";
+        let report = import_winapp2(ini, "ccleaner:winapp2.ini");
+        assert_eq!(report.categories.len(), 1);
+        let app = &report.categories[0];
+        assert_eq!(app.name, "Example App");
+        assert_eq!(app.rules.len(), 2);
+        assert!(app.rules[0].recursive);
+        assert_eq!(app.rules[1].patterns, vec!["*.log", "*.tmp"]);
+        assert!(!app.rules[1].recursive);
+        assert_eq!(report.skipped.len(), 1);
+    }
+
+    #[test]
+    fn winapp2_sections_with_exclusions_are_skipped() {
+        let ini = "[Example Logs *]\n\
+                   FileKey1=%AppData%\\Example|*.*|RECURSE\n\
+                   ExcludeKey1=FILE|%AppData%\\Example\\|settings.ini\n\
+                   \n\
+                   [Other]\n\
+                   FileKey1=%Temp%\\Other|*.tmp\n";
+        let report = import_winapp2(ini, "ccleaner:winapp2.ini");
+        assert_eq!(report.categories.len(), 1);
+        assert_eq!(report.categories[0].name, "Other");
+        assert!(report
+            .skipped
+            .iter()
+            .any(|s| s.starts_with("Example Logs: skipped") && s.contains("ExcludeKey1")));
+    }
+
+    #[test]
+    fn converts_simple_regexes_only() {
+        assert_eq!(regex_to_glob(r"^.*\.log$").as_deref(), Some("*.log"));
+        assert_eq!(regex_to_glob(r"\.tmp$").as_deref(), Some("*.tmp"));
+        assert_eq!(regex_to_glob(r"^(a|b)\.txt$"), None);
+    }
+
+    #[test]
+    fn merge_replaces_same_name() {
+        let make = |name: &str| CustomCategory {
+            name: name.to_string(),
+            description: None,
+            source: None,
+            enabled: true,
+            rules: Vec::new(),
+        };
+        let mut existing = vec![make("A")];
+        let (added, replaced) = merge_into(&mut existing, vec![make("A"), make("B")]);
+        assert_eq!((added, replaced), (1, 1));
+        assert_eq!(existing.len(), 2);
+    }
+}
@@ -1,19 +1,61 @@
 //! SQLite database operations for scan cache
 
+use crate::config::CacheSettings;
+use crate::output::CategoryResult;
 use crate::scan_cache::session::{ScanSession, ScanStats};
-use crate::scan_cache::signature::{FileSignature, FileStatus};
+use crate::scan_cache::signature::{FileHashes, FileSignature, FileStatus};
+use crate::scan_cache::usn::JournalCursor;
+use crate::scan_diff::Snapshot;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, ErrorCode};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
 use serde_json;
 use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const SCHEMA_VERSION: i32 = 3;
+const SCHEMA_VERSION: i32 = 7;
 const DB_BUSY_TIMEOUT_SECS: u64 = 30;
 
+/// Contents of the scan cache (`wole cache --stats`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    /// File signatures kept for incremental scans
+    pub files: usize,
+    /// Total size of those files
+    pub scanned_bytes: u64,
+    /// Files with saved duplicates hashing progress
+    pub file_hashes: usize,
+    /// Category results kept for change-journal reuse
+    pub category_results: usize,
+    pub sessions: usize,
+    /// Size of the database file
+    pub db_bytes: u64,
+    pub oldest_entry: Option<DateTime<Utc>>,
+    pub last_pruned: Option<DateTime<Utc>>,
+}
+
+/// What `ScanCache::prune` removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    /// Entries for paths that no longer exist
+    pub missing: usize,
+    /// Entries older than `max_age_days`
+    pub expired: usize,
+    /// Oldest entries dropped to get under `max_size_mb`
+    pub evicted: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl PruneStats {
+    /// Entries removed for any reason
+    pub fn removed(&self) -> usize {
+        self.missing + self.expired + self.evicted
+    }
+}
+
 /// Scan cache database
 pub struct ScanCache {
     db: Connection,
@@ -28,12 +70,15 @@ impl ScanCache {
             format!("Failed to create cache directory: {}", cache_dir.display())
         })?;
 
-        let db_path = cache_dir.join("scan_cache.db");
+        Self::open_at(&cache_dir.join("scan_cache.db"))
+    }
 
-        let db = match Self::open_connection(&db_path) {
+    /// Open or create the scan cache database at `db_path`
+    pub fn open_at(db_path: &Path) -> Result<Self> {
+        let db = match Self::open_connection(db_path) {
             Ok(db) => db,
             Err(e) => {
-                return Self::recover_database(&db_path, e);
+                return Self::recover_database(db_path, e);
             }
         };
 
@@ -49,7 +94,7 @@ impl ScanCache {
                 e
             );
             drop(cache);
-            return Self::recover_database(&db_path, e);
+            return Self::recover_database(db_path, e);
         }
 
         Ok(cache)
@@ -299,6 +344,84 @@ impl ScanCache {
             .with_context(|| "Failed to create file_categories path index")?;
 
             // Update schema version
+            tx.execute("UPDATE schema_version SET version = ?1", [3])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 4 {
+            // Migration to version 4: hashing progress of duplicates scans, so an
+            // interrupted scan resumes where it stopped
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS duplicate_hashes (
+                    path TEXT PRIMARY KEY,
+                    size INTEGER NOT NULL,
+                    mtime_secs INTEGER NOT NULL,
+                    mtime_nsecs INTEGER NOT NULL,
+                    partial_hash TEXT,
+                    full_hash TEXT,
+                    segments BLOB NOT NULL,
+                    updated_at INTEGER NOT NULL
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create duplicate_hashes table")?;
+
+            tx.execute("UPDATE schema_version SET version = ?1", [4])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 5 {
+            // Migration to version 5: each category's last result with the
+            // change journal position it was taken at, so it can be reused
+            // while nothing it covers changed
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS category_results (
+                    category TEXT NOT NULL,
+                    root TEXT NOT NULL,
+                    fingerprint TEXT NOT NULL,
+                    result TEXT NOT NULL,
+                    volume TEXT NOT NULL,
+                    journal_id INTEGER NOT NULL,
+                    usn INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL,
+                    PRIMARY KEY (category, root)
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create category_results table")?;
+
+            tx.execute("UPDATE schema_version SET version = ?1", [5])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 6 {
+            // Migration to version 6: cache maintenance bookkeeping (when it
+            // was last pruned)
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS cache_meta (
+                    key TEXT PRIMARY KEY,
+                    value INTEGER NOT NULL
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create cache_meta table")?;
+
+            tx.execute("UPDATE schema_version SET version = ?1", [6])
+                .with_context(|| "Failed to update schema version")?;
+        }
+
+        if from_version < 7 {
+            // Migration to version 7: the last `scan --diff` snapshot per root
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS scan_snapshots (
+                    root TEXT PRIMARY KEY,
+                    snapshot TEXT NOT NULL,
+                    taken_at INTEGER NOT NULL
+                )",
+                [],
+            )
+            .with_context(|| "Failed to create scan_snapshots table")?;
+
             tx.execute("UPDATE schema_version SET version = ?1", [SCHEMA_VERSION])
                 .with_context(|| "Failed to update schema version")?;
         }
@@ -844,11 +967,19 @@ impl ScanCache {
                         "DELETE FROM file_records WHERE category IN ({})",
                         placeholders
                     ),
+                    rusqlite::params_from_iter(query_params.iter()),
+                )?;
+                self.db.execute(
+                    &format!(
+                        "DELETE FROM category_results WHERE category IN ({})",
+                        placeholders
+                    ),
                     rusqlite::params_from_iter(query_params),
                 )?;
             }
         } else {
             self.db.execute("DELETE FROM file_records", [])?;
+            self.db.execute("DELETE FROM category_results", [])?;
         }
         Ok(())
     }
@@ -862,10 +993,371 @@ impl ScanCache {
         self.db.execute("DELETE FROM file_records", [])?;
         // Scan history (used by get_previous_scan_id)
         self.db.execute("DELETE FROM scan_sessions", [])?;
+        // Duplicates hashing progress
+        self.db.execute("DELETE FROM duplicate_hashes", [])?;
+        // Results kept for change-journal reuse
+        self.db.execute("DELETE FROM category_results", [])?;
+        // Baselines for `scan --diff`
+        self.db.execute("DELETE FROM scan_snapshots", [])?;
         self.current_scan_id = None;
         Ok(())
     }
 
+    /// Size of the database file (bytes)
+    fn db_bytes(&self) -> Result<u64> {
+        let pages: i64 = self
+            .db
+            .query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: i64 = self
+            .db
+            .query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok((pages * page_size) as u64)
+    }
+
+    /// Bytes taken by live rows (pages freed by deletes don't count)
+    fn used_bytes(&self) -> Result<u64> {
+        let free: i64 = self
+            .db
+            .query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let page_size: i64 = self
+            .db
+            .query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(self.db_bytes()?.saturating_sub((free * page_size) as u64))
+    }
+
+    fn count(&self, table: &str) -> Result<usize> {
+        let count: i64 =
+            self.db
+                .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                    row.get(0)
+                })?;
+        Ok(count as usize)
+    }
+
+    /// When the cache was last pruned
+    pub fn last_pruned(&self) -> Result<Option<DateTime<Utc>>> {
+        let secs: Option<i64> = self
+            .db
+            .query_row(
+                "SELECT value FROM cache_meta WHERE key = 'last_pruned'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(secs.and_then(|s| DateTime::from_timestamp(s, 0)))
+    }
+
+    /// What the cache holds and how much disk it takes
+    pub fn stats(&self) -> Result<CacheStats> {
+        let (files, scanned_bytes) = self.get_cache_stats()?;
+        let oldest: Option<i64> =
+            self.db
+                .query_row("SELECT MIN(updated_at) FROM file_records", [], |row| {
+                    row.get(0)
+                })?;
+        Ok(CacheStats {
+            files,
+            scanned_bytes,
+            file_hashes: self.count("duplicate_hashes")?,
+            category_results: self.count("category_results")?,
+            sessions: self.count("scan_sessions")?,
+            db_bytes: self.db_bytes()?,
+            oldest_entry: oldest.and_then(|s| DateTime::from_timestamp(s, 0)),
+            last_pruned: self.last_pruned()?,
+        })
+    }
+
+    /// Drop entries for paths that no longer exist and entries untouched for
+    /// more than `max_age_days` (0 keeps them), then evict the oldest file
+    /// records until the live data fits in `max_bytes` (0 = no limit). The
+    /// database is compacted afterwards if anything was removed.
+    pub fn prune(&mut self, max_age_days: u64, max_bytes: u64) -> Result<PruneStats> {
+        let mut stats = PruneStats {
+            bytes_before: self.db_bytes()?,
+            ..Default::default()
+        };
+
+        // Entries whose file is gone
+        let mut missing = Vec::new();
+        for table in ["file_records", "duplicate_hashes"] {
+            let mut stmt = self.db.prepare(&format!("SELECT path FROM {}", table))?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            for row in rows {
+                let path_str = row?;
+                if let Err(e) = std::fs::symlink_metadata(decode_path(&path_str)) {
+                    if e.kind() == io::ErrorKind::NotFound {
+                        missing.push(path_str);
+                    }
+                }
+            }
+        }
+        missing.sort();
+        missing.dedup();
+        {
+            let tx = self.db.transaction()?;
+            for path_str in &missing {
+                tx.execute("DELETE FROM file_categories WHERE path = ?1", [path_str])?;
+                tx.execute("DELETE FROM file_records WHERE path = ?1", [path_str])?;
+                tx.execute("DELETE FROM duplicate_hashes WHERE path = ?1", [path_str])?;
+            }
+            tx.commit()
+                .with_context(|| "Failed to commit prune transaction")?;
+        }
+        stats.missing = missing.len();
+
+        // Entries past their age
+        if max_age_days > 0 {
+            let cutoff = Utc::now().timestamp() - (max_age_days as i64) * 86_400;
+            for table in ["file_records", "duplicate_hashes", "category_results"] {
+                stats.expired += self.db.execute(
+                    &format!("DELETE FROM {} WHERE updated_at < ?1", table),
+                    [cutoff],
+                )?;
+            }
+            // The latest session stays so the next scan isn't taken for a first scan
+            self.db.execute(
+                "DELETE FROM scan_sessions
+                 WHERE started_at < ?1 AND id < (SELECT MAX(id) FROM scan_sessions)",
+                [cutoff],
+            )?;
+        }
+
+        // Oldest entries, a tenth at a time, until the data fits
+        if max_bytes > 0 {
+            while self.used_bytes()? > max_bytes {
+                let mut evicted = 0;
+                for table in ["file_records", "duplicate_hashes"] {
+                    let batch = (self.count(table)? / 10).max(1);
+                    evicted += self.db.execute(
+                        &format!(
+                            "DELETE FROM {table} WHERE path IN
+                                (SELECT path FROM {table} ORDER BY updated_at LIMIT ?1)"
+                        ),
+                        [batch as i64],
+                    )?;
+                }
+                if evicted == 0 {
+                    break;
+                }
+                stats.evicted += evicted;
+            }
+        }
+
+        self.db.execute(
+            "DELETE FROM file_categories
+             WHERE path NOT IN (SELECT path FROM file_records)",
+            [],
+        )?;
+        if stats.removed() > 0 {
+            self.db
+                .execute_batch("VACUUM")
+                .with_context(|| "Failed to compact the cache database")?;
+        }
+        self.db.execute(
+            "INSERT INTO cache_meta (key, value) VALUES ('last_pruned', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1",
+            [Utc::now().timestamp()],
+        )?;
+        stats.bytes_after = self.db_bytes()?;
+        Ok(stats)
+    }
+
+    /// Prune with the `[cache]` settings when the last prune is older than
+    /// `prune_interval_days`. None when it wasn't due.
+    pub fn prune_if_due(&mut self, settings: &CacheSettings) -> Result<Option<PruneStats>> {
+        if settings.prune_interval_days == 0 {
+            return Ok(None);
+        }
+        if let Some(last) = self.last_pruned()? {
+            if Utc::now() - last < chrono::Duration::days(settings.prune_interval_days as i64) {
+                return Ok(None);
+            }
+        }
+        self.prune(settings.max_age_days, settings.max_size_mb * 1024 * 1024)
+            .map(Some)
+    }
+
+    /// Hashes an earlier duplicates scan saved for `path`, if the file still
+    /// has the same size and modification time
+    pub fn get_file_hashes(
+        &self,
+        path: &Path,
+        size: u64,
+        mtime: SystemTime,
+    ) -> Result<Option<FileHashes>> {
+        let (mtime_secs, mtime_nsecs) = system_time_to_secs_nsecs(mtime);
+        let row = self
+            .db
+            .query_row(
+                "SELECT partial_hash, full_hash, segments FROM duplicate_hashes
+                 WHERE path = ?1 AND size = ?2 AND mtime_secs = ?3 AND mtime_nsecs = ?4",
+                params![
+                    normalize_path(path),
+                    clamp_size_to_i64(size),
+                    mtime_secs,
+                    mtime_nsecs
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, Vec<u8>>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(row.map(|(partial, full, segments)| FileHashes {
+            path: path.to_path_buf(),
+            size,
+            mtime,
+            partial,
+            full,
+            segments: segments
+                .chunks_exact(32)
+                .map(|c| c.try_into().expect("32-byte chunk"))
+                .collect(),
+        }))
+    }
+
+    /// Save the hashing progress of a duplicates scan, in one transaction
+    pub fn save_file_hashes(&mut self, entries: &[FileHashes]) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let tx = self.db.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO duplicate_hashes
+                    (path, size, mtime_secs, mtime_nsecs, partial_hash, full_hash, segments, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(path) DO UPDATE SET
+                    size = ?2,
+                    mtime_secs = ?3,
+                    mtime_nsecs = ?4,
+                    partial_hash = ?5,
+                    full_hash = ?6,
+                    segments = ?7,
+                    updated_at = ?8",
+            )?;
+            for entry in entries {
+                let (mtime_secs, mtime_nsecs) = system_time_to_secs_nsecs(entry.mtime);
+                stmt.execute(params![
+                    normalize_path(&entry.path),
+                    clamp_size_to_i64(entry.size),
+                    mtime_secs,
+                    mtime_nsecs,
+                    entry.partial,
+                    entry.full,
+                    entry.segments.concat(),
+                    now
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Save `category`'s result for a scan of `root`, taken when the change
+    /// journal was at `cursor`. `fingerprint` identifies the settings it was
+    /// scanned with.
+    pub fn save_category_result(
+        &mut self,
+        category: &str,
+        root: &Path,
+        fingerprint: &str,
+        result: &CategoryResult,
+        cursor: &JournalCursor,
+    ) -> Result<()> {
+        self.db.execute(
+            "INSERT INTO category_results
+                (category, root, fingerprint, result, volume, journal_id, usn, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(category, root) DO UPDATE SET
+                fingerprint = ?3,
+                result = ?4,
+                volume = ?5,
+                journal_id = ?6,
+                usn = ?7,
+                updated_at = ?8",
+            params![
+                category,
+                normalize_path(root),
+                fingerprint,
+                serde_json::to_string(result)?,
+                cursor.volume,
+                cursor.journal_id as i64,
+                cursor.usn,
+                Utc::now().timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The result saved for `category` and `root` with the same `fingerprint`,
+    /// with the journal position it was taken at and when it was saved
+    pub fn get_category_result(
+        &self,
+        category: &str,
+        root: &Path,
+        fingerprint: &str,
+    ) -> Result<Option<(CategoryResult, JournalCursor, DateTime<Utc>)>> {
+        let row = self
+            .db
+            .query_row(
+                "SELECT result, volume, journal_id, usn, updated_at FROM category_results
+                 WHERE category = ?1 AND root = ?2 AND fingerprint = ?3",
+                params![category, normalize_path(root), fingerprint],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((result, volume, journal_id, usn, updated_at)) = row else {
+            return Ok(None);
+        };
+        let cursor = JournalCursor {
+            volume,
+            journal_id: journal_id as u64,
+            usn,
+        };
+        let saved = DateTime::from_timestamp(updated_at, 0).unwrap_or_else(Utc::now);
+        Ok(Some((serde_json::from_str(&result)?, cursor, saved)))
+    }
+
+    /// Save the `scan --diff` snapshot of `root`, replacing the previous one
+    pub fn save_snapshot(&mut self, root: &Path, snapshot: &Snapshot) -> Result<()> {
+        self.db.execute(
+            "INSERT INTO scan_snapshots (root, snapshot, taken_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(root) DO UPDATE SET snapshot = ?2, taken_at = ?3",
+            params![
+                normalize_path(root),
+                serde_json::to_string(snapshot)?,
+                snapshot.taken_at.timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The last `scan --diff` snapshot of `root`
+    pub fn get_snapshot(&self, root: &Path) -> Result<Option<Snapshot>> {
+        let snapshot: Option<String> = self
+            .db
+            .query_row(
+                "SELECT snapshot FROM scan_snapshots WHERE root = ?1",
+                [normalize_path(root)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        snapshot
+            .map(|s| serde_json::from_str(&s).context("Failed to parse scan snapshot"))
+            .transpose()
+    }
+
     /// Get the previous scan ID (for getting cached results)
     pub fn get_previous_scan_id(&self) -> Result<Option<i64>> {
         let result: Option<i64> = self
@@ -1266,4 +1758,83 @@ mod tests {
         let status = cache.check_file(&test_file).unwrap();
         assert!(matches!(status, FileStatus::New));
     }
+
+    #[test]
+    fn test_category_results_keyed_by_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = ScanCache::open_at(&temp_dir.path().join("scan_cache.db")).unwrap();
+        let root = temp_dir.path();
+        let result = CategoryResult {
+            items: 1,
+            size_bytes: 42,
+            paths: vec![root.join("node_modules")],
+            ..Default::default()
+        };
+        let cursor = JournalCursor {
+            volume: "C:".to_string(),
+            journal_id: 7,
+            usn: 4096,
+        };
+        cache
+            .save_category_result("build", root, "a", &result, &cursor)
+            .unwrap();
+
+        let (saved, saved_cursor, _) = cache
+            .get_category_result("build", root, "a")
+            .unwrap()
+            .unwrap();
+        assert_eq!((saved.size_bytes, saved.paths), (42, result.paths));
+        assert_eq!(saved_cursor, cursor);
+        assert!(cache
+            .get_category_result("build", root, "b")
+            .unwrap()
+            .is_none());
+
+        cache.invalidate(Some(&["build"])).unwrap();
+        assert!(cache
+            .get_category_result("build", root, "a")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_prune_drops_missing_expired_and_oversized_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = ScanCache::open_at(&temp_dir.path().join("scan_cache.db")).unwrap();
+        let scan_id = cache.start_scan("full", &["cache"]).unwrap();
+
+        let mut sigs = Vec::new();
+        for name in ["kept.txt", "gone.txt", "old.txt"] {
+            let file = temp_dir.path().join(name);
+            fs::write(&file, name).unwrap();
+            sigs.push(FileSignature::from_path(&file, false).unwrap());
+        }
+        for sig in &sigs {
+            cache.upsert_file(sig, "cache", scan_id).unwrap();
+        }
+        fs::remove_file(temp_dir.path().join("gone.txt")).unwrap();
+        let long_ago = Utc::now().timestamp() - 40 * 86_400;
+        cache
+            .db
+            .execute(
+                "UPDATE file_records SET updated_at = ?1 WHERE path = ?2",
+                params![long_ago, normalize_path(&temp_dir.path().join("old.txt"))],
+            )
+            .unwrap();
+
+        let stats = cache.prune(30, 0).unwrap();
+        assert_eq!((stats.missing, stats.expired, stats.evicted), (1, 1, 0));
+        assert_eq!(cache.stats().unwrap().files, 1);
+        assert!(matches!(
+            cache.check_file(&temp_dir.path().join("kept.txt")).unwrap(),
+            FileStatus::Unchanged
+        ));
+
+        // Just pruned, so the scheduled prune waits; a 1-byte budget evicts
+        assert_eq!(cache.prune_if_due(&CacheSettings::default()).unwrap(), None);
+        assert!(cache.stats().unwrap().last_pruned.is_some());
+        let stats = cache.prune(0, 1).unwrap();
+        assert_eq!(stats.evicted, 1);
+        assert_eq!(cache.stats().unwrap().files, 0);
+    }
 }
@@ -8,8 +8,9 @@ pub mod context;
 pub mod database;
 pub mod session;
 pub mod signature;
+pub mod usn;
 
 pub use context::CacheContext;
-pub use database::ScanCache;
+pub use database::{CacheStats, PruneStats, ScanCache};
 pub use session::{ScanSession, ScanStats};
-pub use signature::{FileSignature, FileStatus};
+pub use signature::{FileHashes, FileSignature, FileStatus};
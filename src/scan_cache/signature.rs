@@ -130,6 +130,23 @@ impl FileSignature {
     }
 }
 
+/// How far a duplicates scan got hashing one file
+///
+/// Large files are hashed in segments; the hashes of the segments done so far
+/// are kept so an interrupted scan continues from the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHashes {
+    pub path: PathBuf,
+    pub size: u64,
+    pub mtime: SystemTime,
+    /// Hash of the first 4KB
+    pub partial: Option<String>,
+    /// Hash of the whole file, once done
+    pub full: Option<String>,
+    /// Hashes of the segments done so far
+    pub segments: Vec<[u8; 32]>,
+}
+
 /// Status of a file compared to cache
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileStatus {
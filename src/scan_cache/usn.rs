@@ -0,0 +1,469 @@
+//! NTFS change journal (USN journal)
+//!
+//! NTFS logs every change on a volume to its update sequence number journal.
+//! A scan notes where the journal was when it started ([`JournalCursor`]); the
+//! next scan reads what changed since then ([`changes_since`]), and a category
+//! whose scanned folder and reported items saw no change reuses its previous
+//! result instead of walking the disk again.
+//!
+//! The journal can only be read on NTFS volumes, usually with administrator
+//! rights. Elsewhere, or once the journal was recreated or has dropped the
+//! records a cursor points at, reading fails and scans walk as before.
+
+use crate::restore::normalize_path_for_comparison;
+#[cfg(not(windows))]
+use anyhow::bail;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Reading gives up past this many changes; walking is cheaper by then
+#[cfg_attr(not(windows), allow(dead_code))]
+const MAX_CHANGES: usize = 500_000;
+
+/// Folders are looked up at most this deep through renamed/deleted parents
+const MAX_DEPTH: usize = 64;
+
+/// Position in one volume's change journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalCursor {
+    /// Drive, e.g. `C:`
+    pub volume: String,
+    /// Identifies the journal; a recreated journal gets a new ID
+    pub journal_id: u64,
+    /// First update sequence number not seen yet
+    pub usn: i64,
+}
+
+/// One journal record (`USN_RECORD_V2`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsnRecord {
+    pub file_ref: u64,
+    pub parent_ref: u64,
+    pub usn: i64,
+    pub reason: u32,
+    pub name: String,
+}
+
+/// Files changed since a cursor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub paths: Vec<PathBuf>,
+    /// Changes in folders that could not be found, so where they happened is
+    /// unknown
+    pub unresolved: usize,
+}
+
+impl Changes {
+    /// Whether anything changed at or inside one of `scope`, leaving out
+    /// changes under `ignored`. Changes in unknown places count as inside.
+    pub fn touches(&self, scope: &[PathBuf], ignored: &[PathBuf]) -> bool {
+        if self.unresolved > 0 {
+            return true;
+        }
+        let key = |path: &Path| normalize_path_for_comparison(&path.display().to_string());
+        let scope: Vec<String> = scope.iter().map(|p| key(p)).collect();
+        let ignored: Vec<String> = ignored.iter().map(|p| key(p)).collect();
+        self.paths.iter().map(|p| key(p)).any(|path| {
+            !ignored.iter().any(|dir| is_within(&path, dir))
+                && scope.iter().any(|dir| is_within(&path, dir))
+        })
+    }
+}
+
+fn is_within(path: &str, dir: &str) -> bool {
+    let is_sep = |c: char| c == '/' || c == '\\';
+    match path.strip_prefix(dir) {
+        Some(rest) => rest.is_empty() || rest.starts_with(is_sep) || dir.ends_with(is_sep),
+        None => false,
+    }
+}
+
+/// Drive of `path` (`C:`), if it has one
+pub fn volume_of(path: &Path) -> Option<String> {
+    crate::mft::drive_letter(path).map(|letter| format!("{}:", letter.to_ascii_uppercase()))
+}
+
+fn bytes<const N: usize>(buf: &[u8], offset: usize) -> [u8; N] {
+    let mut out = [0; N];
+    out.copy_from_slice(&buf[offset..offset + N]);
+    out
+}
+
+/// Records in a `FSCTL_READ_USN_JOURNAL` output buffer (after its leading
+/// next USN). Records of other versions are skipped.
+pub fn parse_records(buf: &[u8]) -> Vec<UsnRecord> {
+    const HEADER: usize = 60;
+    let mut records = Vec::new();
+    let mut offset = 8;
+    while offset + HEADER <= buf.len() {
+        let length = u32::from_le_bytes(bytes(buf, offset)) as usize;
+        if length < HEADER || offset + length > buf.len() {
+            break;
+        }
+        let record = &buf[offset..offset + length];
+        offset += length;
+        if u16::from_le_bytes(bytes(record, 4)) != 2 {
+            continue;
+        }
+        let name_len = u16::from_le_bytes(bytes(record, 56)) as usize;
+        let name_offset = u16::from_le_bytes(bytes(record, 58)) as usize;
+        let Some(name) = record.get(name_offset..name_offset + name_len) else {
+            continue;
+        };
+        let units: Vec<u16> = name
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        records.push(UsnRecord {
+            file_ref: u64::from_le_bytes(bytes(record, 8)),
+            parent_ref: u64::from_le_bytes(bytes(record, 16)),
+            usn: i64::from_le_bytes(bytes(record, 24)),
+            reason: u32::from_le_bytes(bytes(record, 40)),
+            name: String::from_utf16_lossy(&units),
+        });
+    }
+    records
+}
+
+/// Full paths of `records`. Each folder is looked up once with
+/// `folder_path`; folders it can't find (deleted or renamed since) are
+/// rebuilt from their own records' names where the journal has them.
+pub fn resolve(
+    records: &[UsnRecord],
+    mut folder_path: impl FnMut(u64) -> Option<PathBuf>,
+) -> Changes {
+    let mut named: HashMap<u64, (u64, &str)> = HashMap::new();
+    for record in records {
+        named
+            .entry(record.file_ref)
+            .or_insert((record.parent_ref, &record.name));
+    }
+
+    let mut folders: HashMap<u64, Option<PathBuf>> = HashMap::new();
+    let mut changes = Changes::default();
+    for record in records {
+        // Walk up through folders not found yet, then build paths back down
+        let mut chain = Vec::new();
+        let mut current = record.parent_ref;
+        let base = loop {
+            if let Some(known) = folders.get(&current) {
+                break known.clone();
+            }
+            if let Some(path) = folder_path(current) {
+                folders.insert(current, Some(path.clone()));
+                break Some(path);
+            }
+            match named.get(&current) {
+                Some((parent, _)) if chain.len() < MAX_DEPTH && !chain.contains(&current) => {
+                    chain.push(current);
+                    current = *parent;
+                }
+                _ => {
+                    folders.insert(current, None);
+                    break None;
+                }
+            }
+        };
+        let mut path = base;
+        for folder in chain.into_iter().rev() {
+            path = path.map(|p| p.join(named[&folder].1));
+            folders.insert(folder, path.clone());
+        }
+        match path {
+            Some(folder) => changes.paths.push(folder.join(&record.name)),
+            None => changes.unresolved += 1,
+        }
+    }
+    changes.paths.sort();
+    changes.paths.dedup();
+    changes
+}
+
+/// Where `volume`'s journal is now
+pub fn cursor(volume: &str) -> Result<JournalCursor> {
+    #[cfg(windows)]
+    {
+        let device = journal::open(volume)?;
+        let data = journal::query(&device)?;
+        Ok(JournalCursor {
+            volume: volume.to_string(),
+            journal_id: data.journal_id,
+            usn: data.next_usn,
+        })
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = volume;
+        bail!("The change journal is only available on Windows")
+    }
+}
+
+/// What changed on the cursor's volume since it was taken
+pub fn changes_since(cursor: &JournalCursor) -> Result<Changes> {
+    #[cfg(windows)]
+    {
+        let device = journal::open(&cursor.volume)?;
+        let records = journal::read_since(&device, cursor)?;
+        Ok(resolve(&records, |file_ref| {
+            journal::folder_path(&device, file_ref)
+        }))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = cursor;
+        bail!("The change journal is only available on Windows")
+    }
+}
+
+#[cfg(windows)]
+mod journal {
+    use super::{parse_records, JournalCursor, UsnRecord, MAX_CHANGES};
+    use anyhow::{bail, Context, Result};
+    use std::ffi::c_void;
+    use std::fs::File;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::PathBuf;
+
+    type Handle = *mut c_void;
+
+    const FSCTL_QUERY_USN_JOURNAL: u32 = 0x0009_00F4;
+    const FSCTL_READ_USN_JOURNAL: u32 = 0x0009_00BB;
+    const FILE_READ_ATTRIBUTES: u32 = 0x80;
+    const FILE_SHARE_ALL: u32 = 0x1 | 0x2 | 0x4;
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    const FILE_ID_TYPE: u32 = 0;
+
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct JournalData {
+        pub journal_id: u64,
+        pub first_usn: i64,
+        pub next_usn: i64,
+        pub lowest_valid_usn: i64,
+        pub max_usn: i64,
+        pub maximum_size: u64,
+        pub allocation_delta: u64,
+    }
+
+    #[repr(C)]
+    struct ReadJournalData {
+        start_usn: i64,
+        reason_mask: u32,
+        return_only_on_close: u32,
+        timeout: u64,
+        bytes_to_wait_for: u64,
+        journal_id: u64,
+    }
+
+    #[repr(C)]
+    struct FileIdDescriptor {
+        size: u32,
+        kind: u32,
+        file_id: u64,
+        // The descriptor's union is 16 bytes wide
+        _extended: u64,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn DeviceIoControl(
+            device: Handle,
+            code: u32,
+            in_buffer: *const c_void,
+            in_size: u32,
+            out_buffer: *mut c_void,
+            out_size: u32,
+            returned: *mut u32,
+            overlapped: *mut c_void,
+        ) -> i32;
+        fn OpenFileById(
+            volume: Handle,
+            file_id: *const FileIdDescriptor,
+            access: u32,
+            share: u32,
+            security: *const c_void,
+            flags: u32,
+        ) -> Handle;
+        fn GetFinalPathNameByHandleW(file: Handle, path: *mut u16, len: u32, flags: u32) -> u32;
+        fn CloseHandle(handle: Handle) -> i32;
+    }
+
+    /// Handle on the volume device; denied unless elevated
+    pub fn open(volume: &str) -> Result<File> {
+        let device = format!(r"\\.\{}", volume);
+        File::open(&device)
+            .with_context(|| format!("Failed to open volume {} (requires administrator)", device))
+    }
+
+    pub fn query(device: &File) -> Result<JournalData> {
+        let mut data = JournalData::default();
+        let mut returned = 0u32;
+        let ok = unsafe {
+            DeviceIoControl(
+                device.as_raw_handle(),
+                FSCTL_QUERY_USN_JOURNAL,
+                std::ptr::null(),
+                0,
+                &mut data as *mut JournalData as *mut c_void,
+                std::mem::size_of::<JournalData>() as u32,
+                &mut returned,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error())
+                .context("No change journal on this volume");
+        }
+        Ok(data)
+    }
+
+    /// Every record from the cursor up to the journal's current end
+    pub fn read_since(device: &File, cursor: &JournalCursor) -> Result<Vec<UsnRecord>> {
+        let data = query(device)?;
+        if data.journal_id != cursor.journal_id {
+            bail!("The change journal was recreated");
+        }
+        if cursor.usn < data.lowest_valid_usn {
+            bail!("The change journal no longer has the records since the last scan");
+        }
+
+        let mut input = ReadJournalData {
+            start_usn: cursor.usn,
+            reason_mask: u32::MAX,
+            return_only_on_close: 0,
+            timeout: 0,
+            bytes_to_wait_for: 0,
+            journal_id: cursor.journal_id,
+        };
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut records = Vec::new();
+        while input.start_usn < data.next_usn {
+            let mut returned = 0u32;
+            let ok = unsafe {
+                DeviceIoControl(
+                    device.as_raw_handle(),
+                    FSCTL_READ_USN_JOURNAL,
+                    &input as *const ReadJournalData as *const c_void,
+                    std::mem::size_of::<ReadJournalData>() as u32,
+                    buffer.as_mut_ptr() as *mut c_void,
+                    buffer.len() as u32,
+                    &mut returned,
+                    std::ptr::null_mut(),
+                )
+            };
+            if ok == 0 {
+                return Err(std::io::Error::last_os_error())
+                    .context("Failed to read the change journal");
+            }
+            let returned = returned as usize;
+            if returned < 8 {
+                break;
+            }
+            let next = i64::from_le_bytes(super::bytes(&buffer, 0));
+            records.extend(parse_records(&buffer[..returned]));
+            if records.len() > MAX_CHANGES {
+                bail!("Too many changes since the last scan");
+            }
+            if next <= input.start_usn {
+                break;
+            }
+            input.start_usn = next;
+        }
+        Ok(records)
+    }
+
+    /// Current path of the folder with `file_ref`, if it still exists
+    pub fn folder_path(device: &File, file_ref: u64) -> Option<PathBuf> {
+        let descriptor = FileIdDescriptor {
+            size: std::mem::size_of::<FileIdDescriptor>() as u32,
+            kind: FILE_ID_TYPE,
+            file_id: file_ref,
+            _extended: 0,
+        };
+        let handle = unsafe {
+            OpenFileById(
+                device.as_raw_handle(),
+                &descriptor,
+                FILE_READ_ATTRIBUTES,
+                FILE_SHARE_ALL,
+                std::ptr::null(),
+                FILE_FLAG_BACKUP_SEMANTICS,
+            )
+        };
+        if handle.is_null() || handle as isize == -1 {
+            return None;
+        }
+        let mut path = vec![0u16; 1024];
+        let mut len = unsafe { GetFinalPathNameByHandleW(handle, path.as_mut_ptr(), 1024, 0) };
+        if len as usize > path.len() {
+            path.resize(len as usize, 0);
+            len = unsafe {
+                GetFinalPathNameByHandleW(handle, path.as_mut_ptr(), path.len() as u32, 0)
+            };
+        }
+        unsafe { CloseHandle(handle) };
+        if len == 0 || len as usize > path.len() {
+            return None;
+        }
+        let path = String::from_utf16_lossy(&path[..len as usize]);
+        Some(PathBuf::from(path.strip_prefix(r"\\?\").unwrap_or(&path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(file_ref: u64, parent_ref: u64, usn: i64, name: &str) -> Vec<u8> {
+        let name: Vec<u8> = name.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let length = (60 + name.len()).next_multiple_of(8);
+        let mut buf = vec![0u8; length];
+        buf[0..4].copy_from_slice(&(length as u32).to_le_bytes());
+        buf[4..6].copy_from_slice(&2u16.to_le_bytes());
+        buf[8..16].copy_from_slice(&file_ref.to_le_bytes());
+        buf[16..24].copy_from_slice(&parent_ref.to_le_bytes());
+        buf[24..32].copy_from_slice(&usn.to_le_bytes());
+        buf[40..44].copy_from_slice(&0x100u32.to_le_bytes());
+        buf[56..58].copy_from_slice(&(name.len() as u16).to_le_bytes());
+        buf[58..60].copy_from_slice(&60u16.to_le_bytes());
+        buf[60..60 + name.len()].copy_from_slice(&name);
+        buf
+    }
+
+    #[test]
+    fn journal_records_resolve_to_paths_within_scope() {
+        let mut buf = 300i64.to_le_bytes().to_vec();
+        // A file in a live folder, and one in a folder that was deleted since
+        buf.extend(record(10, 5, 100, "report.pdf"));
+        buf.extend(record(11, 7, 200, "cache.bin"));
+        buf.extend(record(7, 5, 250, "tmp-build"));
+        buf.extend([0u8; 12]);
+        let records = parse_records(&buf);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].name, "cache.bin");
+        assert_eq!((records[1].parent_ref, records[1].usn), (7, 200));
+
+        let root = PathBuf::from("/home/ana");
+        let changes = resolve(&records, |file_ref| (file_ref == 5).then(|| root.clone()));
+        assert_eq!(
+            changes.paths,
+            vec![
+                root.join("report.pdf"),
+                root.join("tmp-build"),
+                root.join("tmp-build/cache.bin"),
+            ]
+        );
+        assert_eq!(changes.unresolved, 0);
+
+        assert!(changes.touches(&[root.join("tmp-build")], &[]));
+        assert!(changes.touches(&[root.join("report.pdf")], &[]));
+        assert!(!changes.touches(&[root.join("tmp")], &[]));
+        assert!(!changes.touches(std::slice::from_ref(&root), std::slice::from_ref(&root)));
+
+        // Nowhere to put a change: it could be anywhere
+        let lost = resolve(&records[1..2], |_| None);
+        assert_eq!(lost.unresolved, 1);
+        assert!(lost.touches(&[root.join("elsewhere")], &[]));
+    }
+}
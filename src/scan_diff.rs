@@ -0,0 +1,312 @@
+//! Scan-to-scan comparison (`wole scan --diff`)
+//!
+//! Each `--diff` scan stores a snapshot of its results in the scan cache:
+//! every reported path and its size, per category. The next `--diff` scan of
+//! the same folder compares against that snapshot and reports what's new,
+//! what grew and what disappeared, so the tools that keep filling the disk
+//! stand out week over week.
+
+use crate::output::ScanResults;
+use crate::theme::Theme;
+use chrono::{DateTime, Local, Utc};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Paths listed per section in the human report
+const SHOWN: usize = 5;
+
+/// Growth below this is noise (logs ticking over), not worth reporting
+const MIN_GROWTH: u64 = 1024 * 1024;
+
+/// One category as it looked at snapshot time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategorySnapshot {
+    pub items: usize,
+    pub size_bytes: u64,
+    /// Size of every reported path
+    pub paths: BTreeMap<PathBuf, u64>,
+}
+
+/// The categories of one scan, keyed by CLI name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: DateTime<Utc>,
+    pub categories: BTreeMap<String, CategorySnapshot>,
+}
+
+impl Snapshot {
+    /// Snapshot of the `categories` (CLI keys) of `results`, sizing each path
+    pub fn of(results: &ScanResults, categories: &[&str]) -> Self {
+        let categories = results
+            .by_category()
+            .into_iter()
+            .filter(|(key, _)| categories.contains(key))
+            .map(|(key, result)| {
+                let paths = result
+                    .paths
+                    .par_iter()
+                    .map(|path| (path.clone(), path_size(path)))
+                    .collect();
+                (
+                    key.to_string(),
+                    CategorySnapshot {
+                        items: result.items,
+                        size_bytes: result.size_bytes,
+                        paths,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            taken_at: Utc::now(),
+            categories,
+        }
+    }
+
+    /// This snapshot with `newer`'s categories replacing its own, so a scan
+    /// of a few categories keeps the baseline of the others
+    pub fn updated_with(mut self, newer: Snapshot) -> Snapshot {
+        self.taken_at = newer.taken_at;
+        self.categories.extend(newer.categories);
+        self
+    }
+}
+
+fn path_size(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => crate::utils::calculate_dir_size(path),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+/// A path whose size changed between the two scans (0 = absent)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathChange {
+    pub path: PathBuf,
+    pub before: u64,
+    pub after: u64,
+}
+
+/// What changed in one category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CategoryDiff {
+    pub category: String,
+    pub size_before: u64,
+    pub size_after: u64,
+    pub new: Vec<PathChange>,
+    pub grew: Vec<PathChange>,
+    pub gone: Vec<PathChange>,
+}
+
+/// Changes since the previous `--diff` scan, largest category change first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScanDiff {
+    pub since: DateTime<Utc>,
+    pub categories: Vec<CategoryDiff>,
+}
+
+/// Compare the categories scanned both times; unchanged ones are left out
+pub fn diff(previous: &Snapshot, current: &Snapshot) -> ScanDiff {
+    let mut categories: Vec<CategoryDiff> = current
+        .categories
+        .iter()
+        .filter_map(|(name, now)| {
+            let before = previous.categories.get(name)?;
+            let mut new = Vec::new();
+            let mut grew = Vec::new();
+            for (path, &after) in &now.paths {
+                match before.paths.get(path) {
+                    None => new.push(PathChange {
+                        path: path.clone(),
+                        before: 0,
+                        after,
+                    }),
+                    Some(&old) if after.saturating_sub(old) >= MIN_GROWTH => {
+                        grew.push(PathChange {
+                            path: path.clone(),
+                            before: old,
+                            after,
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            let mut gone: Vec<PathChange> = before
+                .paths
+                .iter()
+                .filter(|(path, _)| !now.paths.contains_key(*path))
+                .map(|(path, &old)| PathChange {
+                    path: path.clone(),
+                    before: old,
+                    after: 0,
+                })
+                .collect();
+            if new.is_empty()
+                && grew.is_empty()
+                && gone.is_empty()
+                && before.size_bytes == now.size_bytes
+            {
+                return None;
+            }
+            new.sort_by_key(|c| std::cmp::Reverse(c.after));
+            grew.sort_by_key(|c| std::cmp::Reverse(c.after - c.before));
+            gone.sort_by_key(|c| std::cmp::Reverse(c.before));
+            Some(CategoryDiff {
+                category: name.clone(),
+                size_before: before.size_bytes,
+                size_after: now.size_bytes,
+                new,
+                grew,
+                gone,
+            })
+        })
+        .collect();
+    categories.sort_by_key(|c| std::cmp::Reverse(c.size_after.abs_diff(c.size_before)));
+    ScanDiff {
+        since: previous.taken_at,
+        categories,
+    }
+}
+
+fn signed_size(before: u64, after: u64) -> String {
+    let sign = if after >= before { "+" } else { "-" };
+    format!(
+        "{}{}",
+        sign,
+        bytesize::to_string(after.abs_diff(before), false)
+    )
+}
+
+fn print_section(label: &str, changes: &[PathChange]) {
+    for change in changes.iter().take(SHOWN) {
+        let size = match (change.before, change.after) {
+            (0, after) => bytesize::to_string(after, false),
+            (before, 0) => bytesize::to_string(before, false),
+            (before, after) => format!(
+                "{} → {} ({})",
+                bytesize::to_string(before, false),
+                bytesize::to_string(after, false),
+                signed_size(before, after)
+            ),
+        };
+        println!(
+            "  {} {}  {}",
+            label,
+            change.path.display(),
+            Theme::size(&size)
+        );
+    }
+    if changes.len() > SHOWN {
+        println!(
+            "  {}",
+            Theme::muted(&format!("...and {} more", changes.len() - SHOWN))
+        );
+    }
+}
+
+/// Print `diff` for people
+pub fn print_human(diff: &ScanDiff) {
+    println!();
+    println!(
+        "{}",
+        Theme::header(&format!(
+            "Changes since {}",
+            diff.since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        ))
+    );
+    println!("{}", Theme::divider(60));
+
+    if diff.categories.is_empty() {
+        println!();
+        println!("  {}", Theme::muted("Nothing changed."));
+        return;
+    }
+
+    for category in &diff.categories {
+        println!();
+        println!(
+            "{}  {} → {} ({})",
+            Theme::category(&category.category),
+            bytesize::to_string(category.size_before, false),
+            bytesize::to_string(category.size_after, false),
+            signed_size(category.size_before, category.size_after)
+        );
+        print_section(&Theme::warning("+ new "), &category.new);
+        print_section(&Theme::warning("↑ grew"), &category.grew);
+        print_section(&Theme::success("- gone"), &category.gone);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(categories: &[(&str, &[(&str, u64)])]) -> Snapshot {
+        Snapshot {
+            taken_at: Utc::now(),
+            categories: categories
+                .iter()
+                .map(|(name, paths)| {
+                    let paths: BTreeMap<PathBuf, u64> = paths
+                        .iter()
+                        .map(|(p, size)| (PathBuf::from(p), *size))
+                        .collect();
+                    (
+                        name.to_string(),
+                        CategorySnapshot {
+                            items: paths.len(),
+                            size_bytes: paths.values().sum(),
+                            paths,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_new_grown_and_gone_paths_per_category() {
+        const MB: u64 = 1024 * 1024;
+        let previous = snapshot(&[
+            (
+                "cache",
+                &[("/c/npm", 10 * MB), ("/c/pip", 5 * MB), ("/c/old", MB)],
+            ),
+            ("temp", &[("/t/a", MB)]),
+            ("build", &[("/b/target", MB)]),
+        ]);
+        let current = snapshot(&[
+            (
+                "cache",
+                &[
+                    ("/c/npm", 30 * MB),
+                    ("/c/pip", 5 * MB + 10),
+                    ("/c/yarn", 2 * MB),
+                ],
+            ),
+            ("temp", &[("/t/a", MB)]),
+            ("large", &[("/l/iso", 100 * MB)]),
+        ]);
+
+        let diff = diff(&previous, &current);
+        // Unchanged temp is left out; build/large weren't scanned both times
+        assert_eq!(diff.categories.len(), 1);
+        let cache = &diff.categories[0];
+        assert_eq!(cache.category, "cache");
+        assert_eq!(cache.new[0].path, PathBuf::from("/c/yarn"));
+        // Growth under a megabyte is ignored
+        assert_eq!(cache.grew.len(), 1);
+        assert_eq!(
+            (cache.grew[0].before, cache.grew[0].after),
+            (10 * MB, 30 * MB)
+        );
+        assert_eq!(cache.gone[0].path, PathBuf::from("/c/old"));
+
+        let merged = previous.updated_with(current);
+        assert_eq!(merged.categories.len(), 4);
+        assert_eq!(merged.categories["cache"].paths.len(), 3);
+    }
+}
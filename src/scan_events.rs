@@ -2,8 +2,9 @@
 
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Real-time progress updates during scanning.
 #[derive(Debug, Clone)]
@@ -35,6 +36,16 @@ pub enum ScanProgressEvent {
 
     /// Full disk traversal: reading a file (first scan only)
     ReadingFile { path: PathBuf },
+
+    /// Content hashing within a category scan (duplicates), counting files
+    /// and bytes hashed so far.
+    Hashing {
+        category: String,
+        files_hashed: u64,
+        files_total: u64,
+        bytes_hashed: u64,
+        bytes_total: u64,
+    },
 }
 
 /// Throttled emitter for current-path updates during scanning.
@@ -87,4 +98,227 @@ impl ScanPathReporter {
             let _ = lock.send(event);
         }
     }
+
+    /// The channel updates are sent on, for events other than paths
+    pub fn sender(&self) -> std::sync::mpsc::Sender<ScanProgressEvent> {
+        self.tx.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// How often the TUI applies a coalesced batch of progress events
+pub const BATCH_INTERVAL: Duration = Duration::from_millis(75);
+
+/// Maximum events pulled off the channel per poll, so a flood of events can't
+/// stall the UI loop; the rest are picked up on the next poll
+const MAX_EVENTS_PER_POLL: usize = 10_000;
+
+/// Key identifying which events a path/progress update supersedes
+fn progress_key(event: &ScanProgressEvent) -> Option<&str> {
+    match event {
+        ScanProgressEvent::CategoryProgress { category, .. } => Some(category),
+        ScanProgressEvent::ReadingFolder { .. } | ScanProgressEvent::ReadingFile { .. } => Some(""),
+        _ => None,
+    }
+}
+
+/// Category a lifecycle event belongs to
+fn lifecycle_category(event: &ScanProgressEvent) -> Option<&str> {
+    match event {
+        ScanProgressEvent::CategoryStarted { category, .. }
+        | ScanProgressEvent::CategoryFinished { category, .. } => Some(category),
+        _ => None,
+    }
+}
+
+/// Coalesces scan progress events into periodic batches for the UI.
+///
+/// Drop policy: started/finished events are always delivered, in order.
+/// Progress and "reading" updates only carry the latest counters and path, so
+/// a newer one replaces any pending update for the same category (unless a
+/// lifecycle event for that category sits in between). The replaced updates
+/// are counted in `dropped()`.
+pub struct ProgressBatcher {
+    rx: Receiver<ScanProgressEvent>,
+    interval: Duration,
+    last_flush: Instant,
+    pending: Vec<ScanProgressEvent>,
+    received: u64,
+    dropped: u64,
+    disconnected: bool,
+}
+
+impl ProgressBatcher {
+    pub fn new(rx: Receiver<ScanProgressEvent>, interval: Duration) -> Self {
+        Self {
+            rx,
+            interval,
+            last_flush: Instant::now(),
+            pending: Vec::new(),
+            received: 0,
+            dropped: 0,
+            disconnected: false,
+        }
+    }
+
+    /// Pull waiting events and return a batch once the interval has elapsed
+    pub fn poll(&mut self) -> Option<Vec<ScanProgressEvent>> {
+        self.drain(MAX_EVENTS_PER_POLL);
+        if self.pending.is_empty() || self.last_flush.elapsed() < self.interval {
+            return None;
+        }
+        self.last_flush = Instant::now();
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// Drain everything still queued, ignoring the interval (use when the scan ends)
+    pub fn flush(&mut self) -> Vec<ScanProgressEvent> {
+        self.drain(usize::MAX);
+        self.last_flush = Instant::now();
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Total events received from the scanner
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Events superseded by newer updates before reaching the UI
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Whether the sending side has hung up
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    fn drain(&mut self, limit: usize) {
+        for _ in 0..limit {
+            match self.rx.try_recv() {
+                Ok(event) => self.push(event),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, event: ScanProgressEvent) {
+        self.received += 1;
+
+        if let Some(key) = progress_key(&event) {
+            let superseded = self
+                .pending
+                .iter()
+                .rposition(|e| progress_key(e) == Some(key) || lifecycle_category(e) == Some(key))
+                .filter(|&i| progress_key(&self.pending[i]).is_some());
+            if let Some(i) = superseded {
+                let previous = std::mem::replace(&mut self.pending[i], event);
+                merge_progress(&mut self.pending[i], previous);
+                self.dropped += 1;
+                return;
+            }
+        }
+
+        self.pending.push(event);
+    }
+}
+
+/// Keep fields from an older update that the newer one left unset
+fn merge_progress(newer: &mut ScanProgressEvent, older: ScanProgressEvent) {
+    if let (
+        ScanProgressEvent::CategoryProgress {
+            completed_units,
+            total_units,
+            current_path,
+            ..
+        },
+        ScanProgressEvent::CategoryProgress {
+            completed_units: old_completed,
+            total_units: old_total,
+            current_path: old_path,
+            ..
+        },
+    ) = (newer, older)
+    {
+        if total_units.is_none() {
+            *total_units = old_total;
+            *completed_units = (*completed_units).max(old_completed);
+        }
+        if current_path.is_none() {
+            *current_path = old_path;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn progress(category: &str, completed: u64, path: Option<&str>) -> ScanProgressEvent {
+        ScanProgressEvent::CategoryProgress {
+            category: category.to_string(),
+            completed_units: completed,
+            total_units: Some(100),
+            current_path: path.map(PathBuf::from),
+        }
+    }
+
+    #[test]
+    fn coalesces_progress_per_category() {
+        let (tx, rx) = mpsc::channel();
+        let mut batcher = ProgressBatcher::new(rx, Duration::ZERO);
+        for i in 0..1000 {
+            tx.send(progress("Cache", i, Some("/a"))).unwrap();
+            tx.send(progress("Temp", i, None)).unwrap();
+        }
+        tx.send(progress("Temp", 1000, None)).unwrap();
+
+        let batch = batcher.poll().unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batcher.received(), 2001);
+        assert_eq!(batcher.dropped(), 1999);
+        match &batch[1] {
+            ScanProgressEvent::CategoryProgress {
+                completed_units, ..
+            } => assert_eq!(*completed_units, 1000),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lifecycle_events_are_never_dropped_or_reordered() {
+        let (tx, rx) = mpsc::channel();
+        let mut batcher = ProgressBatcher::new(rx, Duration::ZERO);
+        tx.send(progress("Cache", 10, Some("/a"))).unwrap();
+        tx.send(ScanProgressEvent::CategoryFinished {
+            category: "Cache".to_string(),
+            items: 3,
+            size_bytes: 42,
+        })
+        .unwrap();
+        tx.send(progress("Cache", 20, Some("/b"))).unwrap();
+        drop(tx);
+
+        let batch = batcher.flush();
+        assert_eq!(batch.len(), 3);
+        assert!(matches!(
+            batch[1],
+            ScanProgressEvent::CategoryFinished { .. }
+        ));
+        assert_eq!(batcher.dropped(), 0);
+        assert!(batcher.is_disconnected());
+    }
+
+    #[test]
+    fn holds_batches_until_interval_elapses() {
+        let (tx, rx) = mpsc::channel();
+        let mut batcher = ProgressBatcher::new(rx, Duration::from_secs(3600));
+        tx.send(progress("Cache", 1, None)).unwrap();
+        assert!(batcher.poll().is_none());
+        assert_eq!(batcher.flush().len(), 1);
+    }
 }
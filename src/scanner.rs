@@ -1,19 +1,30 @@
+pub mod exclusions;
+mod handle;
+mod streaming;
+
+pub use handle::{scan_in_background, ScanHandle};
+pub use streaming::{scan_streaming, scan_with_events, ScanEvent, ScanStream};
+
 use crate::categories;
 use crate::cli::ScanOptions;
 use crate::config::Config;
 use crate::git;
 use crate::output::{CategoryResult, OutputMode, ScanResults};
 use crate::progress;
+use crate::scan_cache::usn::{self, Changes, JournalCursor};
 use crate::scan_cache::{FileSignature, ScanCache, ScanStats};
 use crate::scan_events::ScanProgressEvent;
+use crate::tasks::{self, CancelToken, TaskKind};
 use crate::theme::Theme;
-use crate::utils;
-use anyhow::Result;
-// use rayon::prelude::*; // Disabled: using sequential scan to avoid thrashing
+use crate::utils::{self, SizeWalk};
+use anyhow::{Context, Result};
+use indicatif::ProgressBar;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 struct RecycleBinIndex {
@@ -79,8 +90,9 @@ impl RecycleBinIndex {
 /// Save scan results to cache in a background thread to avoid blocking the UI.
 /// This function spawns a thread that opens its own database connection and performs
 /// all cache writes asynchronously, allowing the scan results to be returned immediately.
+/// The thread is a supervised writer, so exiting waits for it to finish.
 fn save_results_to_cache_background(results: ScanResults, scan_session_id: i64) {
-    std::thread::spawn(move || {
+    tasks::spawn("cache-write", TaskKind::Writer, move |_| {
         // Open a new cache connection in the background thread
         let mut cache = match ScanCache::open() {
             Ok(cache) => cache,
@@ -136,6 +148,10 @@ fn save_results_to_cache_background(results: ScanResults, scan_session_id: i64)
         add_category_paths(&results.applications.paths, "applications");
         add_category_paths(&results.windows_update.paths, "windows_update");
         add_category_paths(&results.event_logs.paths, "event_logs");
+        add_category_paths(&results.orphaned.paths, "orphaned");
+        add_category_paths(&results.custom.paths, "custom");
+        add_category_paths(&results.ide.paths, "ide");
+        add_category_paths(&results.rust.paths, "rust");
 
         // Save each category's files with its category-specific scan ID
         for (category, files) in category_batches {
@@ -155,11 +171,17 @@ fn save_results_to_cache_background(results: ScanResults, scan_session_id: i64)
 
         // Cleanup stale files (non-fatal - scan already completed)
         let _removed = cache.cleanup_stale(scan_session_id).unwrap_or(0);
+
+        // Scheduled maintenance: expire old entries and keep the database in
+        // its size budget
+        if let Err(e) = cache.prune_if_due(&Config::load().cache) {
+            eprintln!("Warning: Failed to prune scan cache: {}", e);
+        }
     });
 }
 
 fn defer_finish_scan(scan_session_id: i64, stats: ScanStats) {
-    std::thread::spawn(move || {
+    tasks::spawn("cache-finish", TaskKind::Writer, move |_| {
         let mut cache = match ScanCache::open() {
             Ok(cache) => cache,
             Err(e) => {
@@ -257,6 +279,86 @@ fn try_incremental_scan(
     Ok(None)
 }
 
+/// Identifies the settings a category result was scanned with, so a result
+/// is only reused under the same thresholds, exclusions and config
+fn settings_fingerprint(task: &ScanTask, config: &Config) -> String {
+    let settings = format!(
+        "{:?}{}",
+        task,
+        serde_json::to_string(config).unwrap_or_default()
+    );
+    blake3::hash(settings.as_bytes()).to_hex().to_string()
+}
+
+/// Whether a category's result depends only on the files under the scanned
+/// folder, so an unchanged journal means an unchanged result. Every other
+/// category looks outside the folder (caches, temp, browser profiles, user
+/// folders, system locations, toolchains, custom rule paths), depends on
+/// file age, which changes without any write (old, downloads, build, temp),
+/// or on things that aren't files (Recycle Bin, installed programs, accounts
+/// behind orphaned owners).
+fn journal_reusable(task: &ScanTask, config: &Config) -> bool {
+    match task {
+        ScanTask::Duplicates => config.categories.duplicates.scan_paths.is_empty(),
+        _ => false,
+    }
+}
+
+/// Reuse the category's last result when the change journal shows nothing
+/// changed since in the scanned folder or in any item the result reported.
+/// Only categories that pass [`journal_reusable`] are reused, and results
+/// older than `[cache] usn_max_age_hours` never are. `journals` keeps each
+/// journal read for the other categories.
+fn try_journal_reuse(
+    category_name: &str,
+    task: &ScanTask,
+    path: &Path,
+    config: &Config,
+    cache: &ScanCache,
+    journals: &mut Vec<(JournalCursor, Option<Changes>)>,
+) -> Option<CategoryResult> {
+    if !journal_reusable(task, config) {
+        return None;
+    }
+    let fingerprint = settings_fingerprint(task, config);
+    let (result, cursor, saved) = cache
+        .get_category_result(category_name, path, &fingerprint)
+        .ok()??;
+    let max_age = chrono::Duration::hours(config.cache.usn_max_age_hours as i64);
+    if chrono::Utc::now() - saved > max_age {
+        return None;
+    }
+
+    let changes = match journals.iter().find(|(c, _)| *c == cursor) {
+        Some((_, changes)) => changes.clone(),
+        None => {
+            let changes = usn::changes_since(&cursor).ok();
+            journals.push((cursor, changes.clone()));
+            changes
+        }
+    }?;
+
+    let mut scope = vec![path.to_path_buf()];
+    scope.extend(result.paths.iter().cloned());
+    scope.extend(result.hidden_paths.iter().cloned());
+    scope.extend(result.machine_paths.iter().cloned());
+    // wole's own cache and history writes don't count
+    let ignored: Vec<PathBuf> = crate::history::get_history_dir()
+        .ok()
+        .and_then(|dir| dir.parent().map(Path::to_path_buf))
+        .into_iter()
+        .collect();
+    (!changes.touches(&scope, &ignored)).then_some(result)
+}
+
+/// The size walks of a scan with `options`, stopping with `cancel`
+fn size_walk(options: &ScanOptions, cancel: Option<&CancelToken>) -> SizeWalk {
+    SizeWalk {
+        cancel: cancel.cloned(),
+        estimate: options.estimate.clone(),
+    }
+}
+
 /// Execute full category scan
 #[allow(clippy::too_many_arguments)]
 fn execute_category_scan(
@@ -267,37 +369,131 @@ fn execute_category_scan(
     mode: OutputMode,
     build_config: &crate::config::CategoryConfig,
     duplicates_config: &crate::config::DuplicatesConfig,
-    duplicate_groups: &std::cell::RefCell<
-        Option<Vec<crate::categories::duplicates::DuplicateGroup>>,
-    >,
+    duplicates_result: &Mutex<Option<categories::duplicates::DuplicatesResult>>,
+    walk: &SizeWalk,
 ) -> Result<CategoryResult> {
     match task {
-        ScanTask::Cache => categories::cache::scan(path, config, mode),
-        ScanTask::AppCache => categories::app_cache::scan(path, config, mode),
-        ScanTask::Temp => categories::temp::scan(path, config),
-        ScanTask::Trash => categories::trash::scan(),
+        ScanTask::Cache => categories::cache::scan(path, config, mode, walk),
+        ScanTask::AppCache => categories::app_cache::scan(path, config, mode, walk),
+        ScanTask::Temp => categories::temp::scan(path, config, walk),
+        ScanTask::Trash => categories::trash::scan(config.thresholds.trash_min_age_days, walk),
         ScanTask::Build(age) => {
-            categories::build::scan(path, *age, Some(build_config), config, mode)
+            categories::build::scan(path, *age, Some(build_config), config, mode, walk)
         }
         ScanTask::Downloads(age) => categories::downloads::scan(path, *age, config, mode),
         ScanTask::Large(size) => categories::large::scan(path, *size, config, mode),
         ScanTask::Old(age) => categories::old::scan(path, *age, config, mode),
-        ScanTask::Browser => categories::browser::scan(path, config),
-        ScanTask::System => categories::system::scan(path, config),
+        ScanTask::Browser => categories::browser::scan(path, config, walk),
+        ScanTask::System => categories::system::scan(path, config, walk),
         ScanTask::Empty => categories::empty::scan(path, config),
         ScanTask::Duplicates => {
             match categories::duplicates::scan_with_config(path, Some(duplicates_config), config) {
                 Ok(dup_result) => {
                     // Store groups for enhanced display
-                    *duplicate_groups.borrow_mut() = Some(dup_result.groups.clone());
-                    Ok(dup_result.to_category_result())
+                    let category = dup_result.to_category_result();
+                    *duplicates_result.lock().unwrap_or_else(|e| e.into_inner()) = Some(dup_result);
+                    Ok(category)
                 }
                 Err(e) => Err(e),
             }
         }
-        ScanTask::Applications => categories::applications::scan(path, config, mode),
-        ScanTask::WindowsUpdate => categories::windows_update::scan(path, config),
+        ScanTask::Applications => categories::applications::scan(path, config, mode, walk),
+        ScanTask::WindowsUpdate => categories::windows_update::scan(path, config, walk),
         ScanTask::EventLogs => categories::event_logs::scan(path, config),
+        ScanTask::Orphaned => categories::orphaned::scan(path, config, mode, walk),
+        ScanTask::Custom => categories::custom::scan(path, config),
+        ScanTask::Ide => categories::ide::scan(path, config, walk),
+        ScanTask::Rust => categories::rust::scan(path, config, walk),
+    }
+}
+
+/// Category scanners that run at once; more mostly adds seek contention
+const MAX_CATEGORY_WORKERS: usize = 4;
+
+/// How many categories `scan_all` scans at once: `scan_threads` (all cores
+/// when 0), capped at `MAX_CATEGORY_WORKERS`, or 1 with `parallel_scanning` off
+fn category_workers(config: &Config, pending: usize) -> usize {
+    if !config.performance.parallel_scanning {
+        return 1;
+    }
+    let threads = match config.performance.scan_threads {
+        0 => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        n => n as usize,
+    };
+    threads.min(MAX_CATEGORY_WORKERS).min(pending).max(1)
+}
+
+/// Progress shared by the category scans of one `scan_all` call
+struct ScanProgress<'a> {
+    spinner: Option<&'a ProgressBar>,
+    total: usize,
+    done: AtomicUsize,
+    running: Mutex<Vec<&'static str>>,
+}
+
+impl<'a> ScanProgress<'a> {
+    fn new(spinner: Option<&'a ProgressBar>, total: usize, done: usize) -> Self {
+        Self {
+            spinner,
+            total,
+            done: AtomicUsize::new(done),
+            running: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn started(&self, name: &'static str) {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        running.push(name);
+        self.update(&running);
+    }
+
+    /// Mark `name` done, printing a summary line above the spinner if `print`
+    fn finished(
+        &self,
+        name: &'static str,
+        result: &Result<CategoryResult>,
+        elapsed: Duration,
+        print: bool,
+    ) {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        running.retain(|n| *n != name);
+        self.done.fetch_add(1, Ordering::SeqCst);
+        if print {
+            let line = match result {
+                Ok(r) => format!(
+                    "  {} {:<16} {:>7} items  {:>10}  {}",
+                    Theme::success("✓"),
+                    name,
+                    r.items,
+                    bytesize::to_string(r.size_bytes, false),
+                    Theme::muted(&format!("{:.1}s", elapsed.as_secs_f64()))
+                ),
+                Err(_) => format!("  {} {:<16} failed", Theme::error("✗"), name),
+            };
+            match self.spinner {
+                Some(sp) if !sp.is_hidden() => sp.println(line),
+                _ => println!("{}", line),
+            }
+        }
+        self.update(&running);
+    }
+
+    fn update(&self, running: &[&str]) {
+        if let Some(sp) = self.spinner {
+            let done = self.done.load(Ordering::SeqCst);
+            sp.set_message(if running.is_empty() {
+                format!("Scanning ({}/{})...", done, self.total)
+            } else {
+                format!(
+                    "Scanning {} ({}/{} done)...",
+                    running.join(", "),
+                    done,
+                    self.total
+                )
+            });
+        }
     }
 }
 
@@ -305,7 +501,7 @@ fn execute_category_scan(
 ///
 /// Optimizations:
 /// - Clears git cache before scanning for fresh results
-/// - Scans categories in parallel using rayon (2-3x faster on multi-core)
+/// - Scans independent categories at the same time (see `category_workers`)
 /// - Handles errors gracefully - if one category fails, others continue
 /// - Filters out paths matching exclusion patterns from config
 /// - Supports incremental scanning via scan_cache parameter
@@ -314,12 +510,96 @@ pub fn scan_all(
     options: ScanOptions,
     mode: OutputMode,
     config: &Config,
-    mut scan_cache: Option<&mut ScanCache>,
-) -> Result<ScanResults> {
-    // Clear git cache for fresh scan
+    scan_cache: Option<&mut ScanCache>,
+) -> crate::Result<ScanResults> {
+    // Clear git cache, permission tracking and recorded identities for fresh scan
     git::clear_cache();
+    crate::elevation::reset();
+    crate::cleaner::reset_scanned_identities();
+    Ok(scan_root(path, options, mode, config, scan_cache)?)
+}
+
+/// Scan several roots (`--path` given more than once, or every fixed drive
+/// with `--all-drives`) at the same time and merge what they found. Each root
+/// gets its own scan cache connection; per-root progress isn't shown.
+pub fn scan_roots(
+    roots: &[PathBuf],
+    options: ScanOptions,
+    config: &Config,
+    use_cache: bool,
+) -> crate::Result<ScanResults> {
+    crate::elevation::reset();
+    crate::cleaner::reset_scanned_identities();
+    // Roots can share results (temp, caches), which only their paths tell
+    // apart, so summary scans drop paths after merging
+    let summary_only = options.summary_only;
+    let use_cache = use_cache && !summary_only;
+    let scanned: Vec<Result<ScanResults>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = roots
+            .iter()
+            .map(|root| {
+                let options = ScanOptions {
+                    summary_only: false,
+                    ..options.clone()
+                };
+                scope.spawn(move || {
+                    let mut scan_cache = if use_cache {
+                        ScanCache::open().ok()
+                    } else {
+                        None
+                    };
+                    scan_root(
+                        root,
+                        options,
+                        OutputMode::Quiet,
+                        config,
+                        scan_cache.as_mut(),
+                    )
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("scan thread panicked")))
+            })
+            .collect()
+    });
+
+    let mut merged: Option<ScanResults> = None;
+    for (root, result) in roots.iter().zip(scanned) {
+        let results = result.with_context(|| format!("Scan of {} failed", root.display()))?;
+        match merged.as_mut() {
+            Some(merged) => merged.merge(results),
+            None => merged = Some(results),
+        }
+    }
+    let mut merged = merged.ok_or_else(|| crate::Error::Config("No paths to scan".to_string()))?;
+    if summary_only {
+        merged.summarize();
+    }
+    Ok(merged)
+}
 
-    let mut results = ScanResults::default();
+/// One root of `scan_all`, without resetting the per-scan state
+fn scan_root(
+    path: &Path,
+    options: ScanOptions,
+    mode: OutputMode,
+    config: &Config,
+    scan_cache: Option<&mut ScanCache>,
+) -> Result<ScanResults> {
+    // Summary scans neither reuse cached results nor save theirs
+    let mut scan_cache = scan_cache.filter(|_| !options.summary_only);
+    // CLI scans can't be cancelled, so their walks always run to the end
+    let walk = size_walk(&options, None);
+    let mut results = ScanResults {
+        root: Some(path.to_path_buf()),
+        trash_min_age_days: config.thresholds.trash_min_age_days,
+        ..Default::default()
+    };
 
     // Build list of enabled categories
     let mut enabled: Vec<(&str, ScanTask)> = Vec::new();
@@ -373,6 +653,22 @@ pub fn scan_all(
         enabled.push(("event_logs", ScanTask::EventLogs));
     }
 
+    if options.orphaned {
+        enabled.push(("orphaned", ScanTask::Orphaned));
+    }
+
+    if options.custom {
+        enabled.push(("custom", ScanTask::Custom));
+    }
+
+    if options.ide {
+        enabled.push(("ide", ScanTask::Ide));
+    }
+
+    if options.rust {
+        enabled.push(("rust", ScanTask::Rust));
+    }
+
     let total_categories = enabled.len();
 
     if total_categories == 0 {
@@ -448,131 +744,152 @@ pub fn scan_all(
         None
     };
 
-    // Progress counter for parallel tasks
-    let scanned_count = AtomicUsize::new(0);
-    let path_owned = path.to_path_buf();
-
-    // Clone configs for use in parallel closure (needs to be Send + Sync)
+    // Clone configs shared by the category scans
     let build_config = config.categories.build.clone();
     let duplicates_config = config.categories.duplicates.clone();
-    let config_clone = config.clone(); // Clone full config for parallel access
 
-    // Store duplicate groups separately (needs to be stored after scan)
-    use std::cell::RefCell;
-    let duplicate_groups: RefCell<Option<Vec<crate::categories::duplicates::DuplicateGroup>>> =
-        RefCell::new(None);
+    // Store duplicate and similar-image groups separately (needs to be stored after scan)
+    let duplicates_result: Mutex<Option<categories::duplicates::DuplicatesResult>> =
+        Mutex::new(None);
 
-    // Run scans sequentially to avoid disk thrashing and thread pool explosion
-    // Each individual scanner (large, duplicates, build) manages its own parallelism
-    // and uses the full system resources. Running them in parallel causes massive
-    // I/O contention and "loading so bad" system freezes.
-    let scan_results: Vec<(&str, Result<CategoryResult>)> = enabled
-        .iter()
-        .map(|(name, task)| {
-            // Clone config for this task
-            let config = &config_clone;
-
-            // Update progress
-            let count = scanned_count.fetch_add(1, Ordering::SeqCst) + 1;
-            if let Some(ref sp) = spinner {
-                sp.set_message(format!(
-                    "Scanning {} ({}/{})...",
-                    name, count, total_categories
-                ));
-            }
-
-            // Show category header in Normal+ mode
-            if mode != OutputMode::Quiet {
-                println!();
-                println!("{}", Theme::header(&format!("Scanning {}", name)));
+    // Where the change journal is now; results saved by this scan are current
+    // as of here
+    let journal_start = if scan_id.is_some() && config.cache.usn_journal {
+        usn::volume_of(path).and_then(|volume| usn::cursor(&volume).ok())
+    } else {
+        None
+    };
+    let mut journals = Vec::new();
+
+    // Cached results first: the scan cache is a single connection, so lookups
+    // stay on this thread and only the categories that need a walk are queued
+    let mut scan_results: Vec<(&str, Result<CategoryResult>)> = Vec::new();
+    let mut pending: Vec<(&'static str, ScanTask)> = Vec::new();
+    for (name, task) in &enabled {
+        if let (true, Some(cache), Some(_)) = (use_incremental, scan_cache.as_ref(), &journal_start)
+        {
+            if let Some(result) = try_journal_reuse(name, task, path, config, cache, &mut journals)
+            {
+                if mode != OutputMode::Quiet {
+                    println!(
+                        "  {} {}: unchanged since the last scan",
+                        Theme::muted("→"),
+                        name
+                    );
+                }
+                scan_results.push((name, Ok(result)));
+                continue;
             }
-
-            // Try incremental scan if cache is available
-            let result = if use_incremental {
-                if let (Some(cache), Some(scan_session_id)) = (scan_cache.as_mut(), scan_id) {
-                    // Attempt incremental scan (pass scan_session_id, not category scan_id)
-                    match try_incremental_scan(
-                        name,
-                        task,
-                        &path_owned,
-                        config,
-                        cache,
-                        scan_session_id,
-                        mode,
-                    ) {
-                        Ok(Some(cached_result)) => {
-                            // Used cache successfully
-                            Ok(cached_result)
-                        }
-                        Ok(None) => {
-                            // Need to do full scan for this category
-                            execute_category_scan(
-                                name,
-                                task,
-                                &path_owned,
-                                config,
-                                mode,
-                                &build_config,
-                                &duplicates_config,
-                                &duplicate_groups,
-                            )
-                        }
-                        Err(e) => {
-                            // Cache error, fall back to full scan
-                            if mode != OutputMode::Quiet {
-                                eprintln!(
-                                    "Warning: Cache error for {}: {}. Falling back to full scan.",
-                                    name, e
-                                );
-                            }
-                            execute_category_scan(
-                                name,
-                                task,
-                                &path_owned,
-                                config,
-                                mode,
-                                &build_config,
-                                &duplicates_config,
-                                &duplicate_groups,
-                            )
+        }
+        if use_incremental {
+            if let (Some(cache), Some(scan_session_id)) = (scan_cache.as_mut(), scan_id) {
+                // Pass scan_session_id, not the category scan_id
+                match try_incremental_scan(name, task, path, config, cache, scan_session_id, mode) {
+                    Ok(Some(cached_result)) => {
+                        scan_results.push((name, Ok(cached_result)));
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        // Cache error, fall back to full scan
+                        if mode != OutputMode::Quiet {
+                            eprintln!(
+                                "Warning: Cache error for {}: {}. Falling back to full scan.",
+                                name, e
+                            );
                         }
                     }
-                } else {
-                    // Full scan (no cache or cache disabled)
-                    execute_category_scan(
-                        name,
-                        task,
-                        &path_owned,
-                        config,
-                        mode,
-                        &build_config,
-                        &duplicates_config,
-                        &duplicate_groups,
-                    )
                 }
-            } else {
-                // Full scan (no cache or cache disabled)
-                execute_category_scan(
-                    name,
-                    task,
-                    &path_owned,
-                    config,
-                    mode,
-                    &build_config,
-                    &duplicates_config,
-                    &duplicate_groups,
-                )
-            };
+            }
+        }
+        pending.push((name, *task));
+    }
 
-            (*name, result)
-        })
-        .collect();
+    // Independent categories scan side by side. Their detailed output would
+    // interleave, so in parallel runs the scanners are quiet and a line is
+    // printed as each one finishes; verbose runs stay sequential.
+    let workers = category_workers(config, pending.len());
+    let parallel = workers > 1 && matches!(mode, OutputMode::Quiet | OutputMode::Normal);
+    let scan_mode = if parallel { OutputMode::Quiet } else { mode };
+    let scan_progress = ScanProgress::new(spinner.as_ref(), total_categories, scan_results.len());
+
+    let run = |name: &'static str, task: &ScanTask| -> Result<CategoryResult> {
+        scan_progress.started(name);
+        // Show category header in Normal+ mode
+        if !parallel && mode != OutputMode::Quiet {
+            println!();
+            println!("{}", Theme::header(&format!("Scanning {}", name)));
+        }
+        let started = Instant::now();
+        let result = execute_category_scan(
+            name,
+            task,
+            path,
+            config,
+            scan_mode,
+            &build_config,
+            &duplicates_config,
+            &duplicates_result,
+            &walk,
+        );
+        scan_progress.finished(
+            name,
+            &result,
+            started.elapsed(),
+            parallel && mode != OutputMode::Quiet,
+        );
+        result
+    };
+
+    if parallel {
+        // Plain threads rather than rayon workers: the scanners block on jwalk
+        // walks that run on the rayon pool, and blocked workers would starve it
+        let next = AtomicUsize::new(0);
+        let slots: Vec<Mutex<Option<Result<CategoryResult>>>> =
+            pending.iter().map(|_| Mutex::new(None)).collect();
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some((name, task)) = pending.get(i) else {
+                        break;
+                    };
+                    let result = run(name, task);
+                    *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                });
+            }
+        });
+        for ((name, _), slot) in pending.iter().zip(slots) {
+            let result = slot
+                .into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+                .unwrap_or_else(|| Err(anyhow::anyhow!("Scan did not finish")));
+            scan_results.push((name, result));
+        }
+    } else {
+        for (name, task) in &pending {
+            scan_results.push((name, run(name, task)));
+        }
+    }
 
     // Clear spinner
     if let Some(sp) = spinner {
         progress::finish_and_clear(&sp);
     }
 
+    // Keep each category's result for journal-based reuse next time
+    if let (Some(cache), Some(cursor)) = (scan_cache.as_mut(), &journal_start) {
+        for (name, result) in &scan_results {
+            let Some((_, task)) = enabled.iter().find(|(n, _)| n == name) else {
+                continue;
+            };
+            if let Ok(result) = result {
+                let fingerprint = settings_fingerprint(task, config);
+                let _ = cache.save_category_result(name, path, &fingerprint, result, cursor);
+            }
+        }
+    }
+
     // Aggregate results
     for (category, result) in scan_results {
         match (category, result) {
@@ -582,7 +899,12 @@ pub fn scan_all(
             ("trash", Ok(r)) => results.trash = r,
             ("build", Ok(r)) => results.build = r,
             ("downloads", Ok(r)) => results.downloads = r,
-            ("large", Ok(r)) => results.large = r,
+            ("large", Ok(r)) => {
+                results.large = r;
+                if config.categories.large.cloud_files {
+                    results.cloud_files = categories::cloud_files::scan(config);
+                }
+            }
             ("old", Ok(r)) => results.old = r,
             ("browser", Ok(r)) => results.browser = r,
             ("system", Ok(r)) => results.system = r,
@@ -590,11 +912,22 @@ pub fn scan_all(
             ("duplicates", Ok(r)) => {
                 results.duplicates = r;
                 // Store duplicate groups for enhanced display
-                results.duplicates_groups = duplicate_groups.borrow().clone();
+                let groups = duplicates_result
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .take();
+                if let Some(dup_result) = groups.filter(|_| !options.summary_only) {
+                    results.duplicates_groups = Some(dup_result.groups);
+                    results.similar_image_groups = dup_result.similar_images;
+                }
             }
             ("applications", Ok(r)) => results.applications = r,
             ("windows_update", Ok(r)) => results.windows_update = r,
             ("event_logs", Ok(r)) => results.event_logs = r,
+            ("orphaned", Ok(r)) => results.orphaned = r,
+            ("custom", Ok(r)) => results.custom = r,
+            ("ide", Ok(r)) => results.ide = r,
+            ("rust", Ok(r)) => results.rust = r,
             (name, Err(e)) if mode != OutputMode::Quiet => {
                 eprintln!("[WARNING] {} scan failed: {}", name, e);
             }
@@ -607,11 +940,25 @@ pub fn scan_all(
     // so filter_exclusions is no longer needed. However, we keep it as a safety net
     // for any paths that might have been missed (should be rare).
     // This can be removed entirely once we verify all scanners properly handle exclusions.
-    filter_exclusions(&mut results, config);
+    filter_exclusions(&mut results, config, &walk);
 
     // Filter out files that are in the recycle bin (they were cleaned)
     // This ensures cleaned files don't appear in scan results
-    filter_recycle_bin_files(&mut results);
+    filter_recycle_bin_files(&mut results, &walk);
+
+    // Leave out files the user has open in an editor
+    filter_open_in_editors(&mut results, config, mode);
+    results.collect_largest_files();
+
+    // The filters above were the last to need paths
+    if options.summary_only {
+        results.summarize();
+        return Ok(results);
+    }
+
+    // Remember what each result is, so a path swapped for a link before
+    // cleaning is refused
+    crate::cleaner::record_scanned_identities(&results);
 
     // Save scanned files to cache in background thread to avoid blocking UI
     // Return results immediately, cache writes happen asynchronously
@@ -634,7 +981,11 @@ pub fn scan_all(
                 + results.empty.items
                 + results.duplicates.items
                 + results.windows_update.items
-                + results.event_logs.items;
+                + results.event_logs.items
+                + results.orphaned.items
+                + results.custom.items
+                + results.ide.items
+                + results.rust.items;
 
             // Finish scan synchronously to ensure finished_at is set before returning
             // This prevents race condition where next scan doesn't see this scan as finished
@@ -998,17 +1349,62 @@ fn perform_full_disk_traversal(
 }
 
 /// Scan all requested categories and emit progress events for TUI.
+/// Once `cancel` is set the scan stops: size walks end early, the remaining
+/// categories are skipped, and the partial results come back with
+/// `cancelled` set instead of an error.
 pub fn scan_all_with_progress(
     path: &Path,
     options: ScanOptions,
     config: &Config,
     tx: &Sender<ScanProgressEvent>,
-    mut scan_cache: Option<&mut ScanCache>,
+    scan_cache: Option<&mut ScanCache>,
+    cancel: Option<&CancelToken>,
+) -> crate::Result<ScanResults> {
+    Ok(scan_all_with_hook(
+        path,
+        options,
+        config,
+        tx,
+        scan_cache,
+        cancel,
+        &|_, _| {},
+    )?)
+}
+
+/// A category's progress as seen by `scan_all_with_hook`
+enum CategoryUpdate<'a> {
+    Started,
+    Finished(&'a Result<CategoryResult>),
+}
+
+/// `scan_all_with_progress`, calling `on_category` with each category's CLI
+/// key when it starts and finishes
+fn scan_all_with_hook(
+    path: &Path,
+    options: ScanOptions,
+    config: &Config,
+    tx: &Sender<ScanProgressEvent>,
+    scan_cache: Option<&mut ScanCache>,
+    cancel: Option<&CancelToken>,
+    on_category: &dyn Fn(&'static str, CategoryUpdate),
 ) -> Result<ScanResults> {
-    // Clear git cache for fresh scan
+    // Summary scans neither reuse cached results nor save theirs
+    let summary_only = options.summary_only;
+    let mut scan_cache = scan_cache.filter(|_| !summary_only);
+    let is_cancelled = || cancel.map(|c| c.is_cancelled()).unwrap_or(false);
+    // Size walks inside the categories stop with this scan's token only
+    let walk = size_walk(&options, cancel);
+
+    // Clear git cache, permission tracking and recorded identities for fresh scan
     git::clear_cache();
+    crate::elevation::reset();
+    crate::cleaner::reset_scanned_identities();
 
-    let mut results = ScanResults::default();
+    let mut results = ScanResults {
+        root: Some(path.to_path_buf()),
+        trash_min_age_days: config.thresholds.trash_min_age_days,
+        ..Default::default()
+    };
 
     #[derive(Clone, Copy)]
     struct ScanJob {
@@ -1124,6 +1520,34 @@ pub fn scan_all_with_progress(
             task: ScanTask::EventLogs,
         });
     }
+    if options.orphaned {
+        enabled.push(ScanJob {
+            key: "orphaned",
+            display: "Orphaned Owners",
+            task: ScanTask::Orphaned,
+        });
+    }
+    if options.custom {
+        enabled.push(ScanJob {
+            key: "custom",
+            display: "Custom Rules",
+            task: ScanTask::Custom,
+        });
+    }
+    if options.ide {
+        enabled.push(ScanJob {
+            key: "ide",
+            display: "IDE Caches",
+            task: ScanTask::Ide,
+        });
+    }
+    if options.rust {
+        enabled.push(ScanJob {
+            key: "rust",
+            display: "Rust Toolchain",
+            task: ScanTask::Rust,
+        });
+    }
 
     if enabled.is_empty() {
         return Ok(results);
@@ -1156,7 +1580,7 @@ pub fn scan_all_with_progress(
 
     // On first scan, optionally perform full disk traversal to build baseline BEFORE category scans
     // (disabled by default; enable via config.cache.full_disk_baseline).
-    if is_first_scan && config.cache.full_disk_baseline {
+    if is_first_scan && config.cache.full_disk_baseline && !is_cancelled() {
         if let Some(cache) = scan_cache.as_mut() {
             if let Some(id) = scan_id {
                 // Perform full disk traversal with progress reporting
@@ -1176,9 +1600,9 @@ pub fn scan_all_with_progress(
     let build_config = config.categories.build.clone();
     let duplicates_config = config.categories.duplicates.clone();
 
-    // Store duplicate groups separately (needs to be stored after scan)
+    // Store duplicate and similar-image groups separately (needs to be stored after scan)
     use std::cell::RefCell;
-    let duplicate_groups: RefCell<Option<Vec<crate::categories::duplicates::DuplicateGroup>>> =
+    let duplicates_result: RefCell<Option<categories::duplicates::DuplicatesResult>> =
         RefCell::new(None);
 
     let scan_results: Vec<(&str, &str, Result<CategoryResult>)> = enabled
@@ -1186,6 +1610,12 @@ pub fn scan_all_with_progress(
         .map(|job| {
             let display = job.display;
 
+            // Categories run one after another, so a cancelled scan stops at the next one
+            if is_cancelled() {
+                return (job.key, display, Err(anyhow::anyhow!("Scan cancelled")));
+            }
+
+            on_category(job.key, CategoryUpdate::Started);
             let send_started = || {
                 let _ = tx.send(ScanProgressEvent::CategoryStarted {
                     category: display.to_string(),
@@ -1195,14 +1625,18 @@ pub fn scan_all_with_progress(
             };
 
             let result = match job.task {
-                ScanTask::Cache => categories::cache::scan_with_progress(&path_owned, config, tx),
+                ScanTask::Cache => {
+                    categories::cache::scan_with_progress(&path_owned, config, tx, &walk)
+                }
                 ScanTask::AppCache => {
-                    categories::app_cache::scan_with_progress(&path_owned, config, tx)
+                    categories::app_cache::scan_with_progress(&path_owned, config, tx, &walk)
+                }
+                ScanTask::Temp => {
+                    categories::temp::scan_with_progress(&path_owned, config, tx, &walk)
                 }
-                ScanTask::Temp => categories::temp::scan_with_progress(&path_owned, config, tx),
                 ScanTask::Trash => {
                     send_started();
-                    categories::trash::scan()
+                    categories::trash::scan(config.thresholds.trash_min_age_days, &walk)
                 }
                 ScanTask::Build(age) => {
                     send_started();
@@ -1212,6 +1646,7 @@ pub fn scan_all_with_progress(
                         Some(&build_config),
                         config,
                         OutputMode::Quiet,
+                        &walk,
                     )
                 }
                 ScanTask::Downloads(age) => {
@@ -1228,40 +1663,59 @@ pub fn scan_all_with_progress(
                 }
                 ScanTask::Browser => {
                     send_started();
-                    categories::browser::scan(&path_owned, config)
+                    categories::browser::scan(&path_owned, config, &walk)
                 }
                 ScanTask::System => {
                     send_started();
-                    categories::system::scan(&path_owned, config)
+                    categories::system::scan(&path_owned, config, &walk)
                 }
                 ScanTask::Empty => categories::empty::scan_with_progress(&path_owned, config, tx),
                 ScanTask::Duplicates => {
                     send_started();
-                    match categories::duplicates::scan_with_config(
+                    match categories::duplicates::scan_with_config_with_progress(
                         &path_owned,
                         Some(&duplicates_config),
                         config,
+                        tx,
+                        cancel,
                     ) {
                         Ok(dup_result) => {
-                            *duplicate_groups.borrow_mut() = Some(dup_result.groups.clone());
-                            Ok(dup_result.to_category_result())
+                            let category = dup_result.to_category_result();
+                            *duplicates_result.borrow_mut() = Some(dup_result);
+                            Ok(category)
                         }
                         Err(e) => Err(e),
                     }
                 }
                 ScanTask::Applications => {
-                    categories::applications::scan_with_progress(&path_owned, config, tx)
+                    categories::applications::scan_with_progress(&path_owned, config, tx, &walk)
                 }
                 ScanTask::WindowsUpdate => {
                     send_started();
-                    categories::windows_update::scan(&path_owned, config)
+                    categories::windows_update::scan(&path_owned, config, &walk)
                 }
                 ScanTask::EventLogs => {
                     send_started();
                     categories::event_logs::scan(&path_owned, config)
                 }
+                ScanTask::Orphaned => {
+                    categories::orphaned::scan_with_progress(&path_owned, config, tx, &walk)
+                }
+                ScanTask::Custom => {
+                    send_started();
+                    categories::custom::scan(&path_owned, config)
+                }
+                ScanTask::Ide => {
+                    send_started();
+                    categories::ide::scan(&path_owned, config, &walk)
+                }
+                ScanTask::Rust => {
+                    send_started();
+                    categories::rust::scan(&path_owned, config, &walk)
+                }
             };
 
+            on_category(job.key, CategoryUpdate::Finished(&result));
             if let Ok(ref category_result) = result {
                 if !matches!(
                     job.task,
@@ -1296,34 +1750,67 @@ pub fn scan_all_with_progress(
             ("trash", Ok(r)) => results.trash = r,
             ("build", Ok(r)) => results.build = r,
             ("downloads", Ok(r)) => results.downloads = r,
-            ("large", Ok(r)) => results.large = r,
+            ("large", Ok(r)) => {
+                results.large = r;
+                if config.categories.large.cloud_files {
+                    results.cloud_files = categories::cloud_files::scan(config);
+                }
+            }
             ("old", Ok(r)) => results.old = r,
             ("browser", Ok(r)) => results.browser = r,
             ("system", Ok(r)) => results.system = r,
             ("empty", Ok(r)) => results.empty = r,
             ("duplicates", Ok(r)) => {
                 results.duplicates = r;
-                results.duplicates_groups = duplicate_groups.borrow().clone();
+                if let Some(dup_result) = duplicates_result.borrow_mut().take() {
+                    results.duplicates_groups = Some(dup_result.groups);
+                    results.similar_image_groups = dup_result.similar_images;
+                }
             }
             ("applications", Ok(r)) => results.applications = r,
             ("windows_update", Ok(r)) => results.windows_update = r,
             ("event_logs", Ok(r)) => results.event_logs = r,
+            ("orphaned", Ok(r)) => results.orphaned = r,
+            ("custom", Ok(r)) => results.custom = r,
+            ("ide", Ok(r)) => results.ide = r,
+            ("rust", Ok(r)) => results.rust = r,
             (_name, Err(_e)) => {}
             _ => {}
         }
     }
 
-    filter_exclusions(&mut results, config);
+    // Partial results must not reach the cache: stale cleanup would drop the
+    // categories that were never scanned
+    // A cancelled scan keeps what it found; the categories it didn't reach
+    // stay empty
+    results.cancelled = is_cancelled();
+
+    filter_exclusions(&mut results, config, &walk);
 
     // Filter out files that are in the recycle bin (they were cleaned)
     // This ensures cleaned files don't appear in scan results
-    filter_recycle_bin_files(&mut results);
+    filter_recycle_bin_files(&mut results, &walk);
+
+    // Leave out files the user has open in an editor
+    filter_open_in_editors(&mut results, config, OutputMode::Quiet);
+    results.collect_largest_files();
+
+    if summary_only {
+        results.summarize();
+        return Ok(results);
+    }
+
+    // Remember what each result is, so a path swapped for a link before
+    // cleaning is refused
+    crate::cleaner::record_scanned_identities(&results);
 
     // Save scanned files to cache in background thread to avoid blocking UI
     // Return results immediately, cache writes happen asynchronously
     // CRITICAL: finish_scan() must be called synchronously to prevent race condition
     // where subsequent scans don't see this scan as finished
-    if let Some(cache) = scan_cache.as_mut() {
+    // Partial results must not reach the cache: stale cleanup would drop the
+    // files of categories that were never scanned
+    if let Some(cache) = scan_cache.as_mut().filter(|_| !results.cancelled) {
         if let Some(scan_session_id) = cache.current_scan_id() {
             // Calculate stats synchronously (needed for finish_scan)
             let total_files = results.cache.items
@@ -1340,7 +1827,11 @@ pub fn scan_all_with_progress(
                 + results.empty.items
                 + results.duplicates.items
                 + results.windows_update.items
-                + results.event_logs.items;
+                + results.event_logs.items
+                + results.orphaned.items
+                + results.custom.items
+                + results.ide.items
+                + results.rust.items;
 
             // Finish scan synchronously to ensure finished_at is set before returning
             // This prevents race condition where next scan doesn't see this scan as finished
@@ -1368,7 +1859,7 @@ pub fn scan_all_with_progress(
 }
 
 /// Scan task enum for parallel execution
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 enum ScanTask {
     Cache,
     AppCache,
@@ -1385,13 +1876,17 @@ enum ScanTask {
     Applications,
     WindowsUpdate,
     EventLogs,
+    Orphaned,
+    Custom,
+    Ide,
+    Rust,
 }
 
 /// Filter out files that are in the recycle bin from scan results
 /// Files in recycle bin were already cleaned, so exclude them from results
 /// but keep them tracked in cache (they can be restored)
 /// Note: This does NOT filter the trash category itself - that's a separate scan
-fn filter_recycle_bin_files(results: &mut ScanResults) {
+fn filter_recycle_bin_files(results: &mut ScanResults, walk: &SizeWalk) {
     let recycle_bin_index = RecycleBinIndex::from_system();
     let Some(recycle_bin_index) = recycle_bin_index else {
         return;
@@ -1414,7 +1909,7 @@ fn filter_recycle_bin_files(results: &mut ScanResults) {
                     if metadata.is_file() {
                         excluded_size += metadata.len();
                     } else if metadata.is_dir() {
-                        excluded_size += utils::calculate_dir_size(path);
+                        excluded_size += utils::calculate_dir_size_in(path, walk);
                     }
                 }
             }
@@ -1436,7 +1931,7 @@ fn filter_recycle_bin_files(results: &mut ScanResults) {
                     if metadata.is_file() {
                         total += metadata.len();
                     } else if metadata.is_dir() {
-                        total += utils::calculate_dir_size(path);
+                        total += utils::calculate_dir_size_in(path, walk);
                     }
                 }
             }
@@ -1477,6 +1972,13 @@ fn filter_recycle_bin_files(results: &mut ScanResults) {
         &mut results.event_logs.paths,
         &mut results.event_logs.size_bytes,
     );
+    filter_and_recalculate(
+        &mut results.orphaned.paths,
+        &mut results.orphaned.size_bytes,
+    );
+    filter_and_recalculate(&mut results.custom.paths, &mut results.custom.size_bytes);
+    filter_and_recalculate(&mut results.ide.paths, &mut results.ide.size_bytes);
+    filter_and_recalculate(&mut results.rust.paths, &mut results.rust.size_bytes);
     // NOTE: Do NOT filter results.trash - that category scans the recycle bin itself
 
     // Update item counts
@@ -1494,12 +1996,54 @@ fn filter_recycle_bin_files(results: &mut ScanResults) {
     results.applications.items = results.applications.paths.len();
     results.windows_update.items = results.windows_update.paths.len();
     results.event_logs.items = results.event_logs.paths.len();
+    results.orphaned.items = results.orphaned.paths.len();
+    results.custom.items = results.custom.paths.len();
+    results.ide.items = results.ide.paths.len();
+    results.rust.items = results.rust.paths.len();
+}
+
+/// Remove Large, Old and Downloads results that are open in an editor
+/// (`[safety] skip_open_in_editors`)
+fn filter_open_in_editors(results: &mut ScanResults, config: &Config, mode: OutputMode) {
+    if !config.safety.skip_open_in_editors
+        || results.large.paths.is_empty()
+            && results.old.paths.is_empty()
+            && results.downloads.paths.is_empty()
+    {
+        return;
+    }
+    let open = crate::open_files::OpenFiles::detect();
+    let mut skipped = 0;
+    for category in [&mut results.large, &mut results.old, &mut results.downloads] {
+        let removed =
+            crate::open_files::retain_closed(&mut category.paths, &mut category.size_bytes, &open);
+        category.items = category.paths.len();
+        skipped += removed.len();
+        if mode == OutputMode::VeryVerbose {
+            for (path, reason) in &removed {
+                println!(
+                    "    {} Skipped {} ({})",
+                    Theme::muted("•"),
+                    utils::display_path(path),
+                    reason.label()
+                );
+            }
+        }
+    }
+    if skipped > 0 && mode != OutputMode::Quiet {
+        println!(
+            "  {} Left out {} file{} open in editors",
+            Theme::muted("→"),
+            skipped,
+            if skipped == 1 { "" } else { "s" }
+        );
+    }
 }
 
 /// Filter out paths matching exclusion patterns
 ///
 /// Optimized to avoid recalculating sizes - uses pre-calculated sizes from scan results
-fn filter_exclusions(results: &mut ScanResults, config: &Config) {
+fn filter_exclusions(results: &mut ScanResults, config: &Config, walk: &SizeWalk) {
     // Helper to filter paths and recalculate size_bytes efficiently
     let filter_and_recalculate = |paths: &mut Vec<std::path::PathBuf>, size_bytes: &mut u64| {
         let original_count = paths.len();
@@ -1514,7 +2058,7 @@ fn filter_exclusions(results: &mut ScanResults, config: &Config) {
                     if metadata.is_file() {
                         excluded_size += metadata.len();
                     } else if metadata.is_dir() {
-                        excluded_size += utils::calculate_dir_size(path);
+                        excluded_size += utils::calculate_dir_size_in(path, walk);
                     }
                 }
             }
@@ -1536,7 +2080,7 @@ fn filter_exclusions(results: &mut ScanResults, config: &Config) {
                     if metadata.is_file() {
                         *size_bytes += metadata.len();
                     } else if metadata.is_dir() {
-                        *size_bytes += utils::calculate_dir_size(path);
+                        *size_bytes += utils::calculate_dir_size_in(path, walk);
                     }
                 }
             }
@@ -1568,6 +2112,13 @@ fn filter_exclusions(results: &mut ScanResults, config: &Config) {
         &mut results.applications.paths,
         &mut results.applications.size_bytes,
     );
+    filter_and_recalculate(
+        &mut results.orphaned.paths,
+        &mut results.orphaned.size_bytes,
+    );
+    filter_and_recalculate(&mut results.custom.paths, &mut results.custom.size_bytes);
+    filter_and_recalculate(&mut results.ide.paths, &mut results.ide.size_bytes);
+    filter_and_recalculate(&mut results.rust.paths, &mut results.rust.size_bytes);
 
     // Recalculate item counts after filtering
     results.cache.items = results.cache.paths.len();
@@ -1585,6 +2136,32 @@ fn filter_exclusions(results: &mut ScanResults, config: &Config) {
     results.applications.items = results.applications.paths.len();
     results.windows_update.items = results.windows_update.paths.len();
     results.event_logs.items = results.event_logs.paths.len();
+    results.orphaned.items = results.orphaned.paths.len();
+    results.custom.items = results.custom.paths.len();
+    results.ide.items = results.ide.paths.len();
+    results.rust.items = results.rust.paths.len();
+
+    // The separately reported buckets and duplicate groups follow the same patterns
+    for category in [
+        &mut results.large,
+        &mut results.old,
+        &mut results.cache,
+        &mut results.system,
+    ] {
+        filter_and_recalculate(&mut category.hidden_paths, &mut category.hidden_bytes);
+        filter_and_recalculate(&mut category.machine_paths, &mut category.machine_bytes);
+    }
+    if let Some(groups) = results.duplicates_groups.as_mut() {
+        for group in groups.iter_mut() {
+            group.paths.retain(|p| !config.is_excluded(p));
+        }
+        groups.retain(|g| g.paths.len() > 1);
+    }
+    for group in results.similar_image_groups.iter_mut() {
+        group.images.retain(|(p, _)| !config.is_excluded(p));
+    }
+    results.similar_image_groups.retain(|g| g.images.len() > 1);
+    results.cloud_files.retain(|f| !config.is_excluded(&f.path));
 }
 
 /// Calculate total size of paths (files only - not used for directories)
@@ -1631,9 +2208,15 @@ mod tests {
             duplicates: false,
             windows_update: false,
             event_logs: false,
+            orphaned: false,
+            custom: false,
+            ide: false,
+            rust: false,
             project_age_days: 14,
             min_age_days: 30,
             min_size_bytes: 100 * 1024 * 1024,
+            summary_only: false,
+            estimate: None,
         };
         let config = Config::default();
 
@@ -1645,6 +2228,38 @@ mod tests {
         assert_eq!(results.build.items, 0);
     }
 
+    #[test]
+    fn parallel_scan_matches_sequential() {
+        let temp_dir = create_test_dir();
+        fs::create_dir_all(temp_dir.path().join("d")).unwrap();
+        for file in ["d/one.bin", "d/two.bin"] {
+            fs::write(temp_dir.path().join(file), vec![7u8; 64 * 1024]).unwrap();
+        }
+        let keys: Vec<String> = ["empty", "duplicates"].map(String::from).to_vec();
+
+        let mut config = Config::default();
+        config.performance.scan_threads = 4;
+        assert_eq!(category_workers(&config, 2), 2);
+        assert_eq!(category_workers(&config, 9), MAX_CATEGORY_WORKERS);
+        let options = ScanOptions::from_keys(&keys, &config);
+        let parallel =
+            scan_all(temp_dir.path(), options, OutputMode::Quiet, &config, None).unwrap();
+
+        config.performance.parallel_scanning = false;
+        assert_eq!(category_workers(&config, 2), 1);
+        let options = ScanOptions::from_keys(&keys, &config);
+        let sequential =
+            scan_all(temp_dir.path(), options, OutputMode::Quiet, &config, None).unwrap();
+
+        assert_eq!(parallel.empty.items, sequential.empty.items);
+        assert_eq!(parallel.duplicates.items, sequential.duplicates.items);
+        assert_eq!(
+            parallel.duplicates_groups.map(|g| g.len()),
+            sequential.duplicates_groups.map(|g| g.len())
+        );
+        assert!(sequential.duplicates.items > 0);
+    }
+
     #[test]
     fn test_filter_exclusions() {
         let mut results = ScanResults::default();
@@ -1669,7 +2284,7 @@ mod tests {
             .push("**/important-project/**".to_string());
 
         // Filter exclusions
-        filter_exclusions(&mut results, &config);
+        filter_exclusions(&mut results, &config, &SizeWalk::default());
 
         // Should have filtered out the important-project path
         assert_eq!(results.cache.items, 1);
@@ -1694,4 +2309,26 @@ mod tests {
 
         assert_eq!(total, 10); // 5 bytes + 5 bytes
     }
+
+    #[test]
+    fn only_root_bound_categories_reuse_journal_results() {
+        let mut config = Config::default();
+        assert!(journal_reusable(&ScanTask::Duplicates, &config));
+        // Outside the scanned folder, or aged rather than changed
+        for task in [
+            ScanTask::Cache,
+            ScanTask::AppCache,
+            ScanTask::Temp,
+            ScanTask::Browser,
+            ScanTask::Old(30),
+            ScanTask::Downloads(30),
+            ScanTask::Build(14),
+            ScanTask::Large(1024),
+            ScanTask::Orphaned,
+        ] {
+            assert!(!journal_reusable(&task, &config), "{:?}", task);
+        }
+        config.categories.duplicates.scan_paths = vec!["D:\\".to_string()];
+        assert!(!journal_reusable(&ScanTask::Duplicates, &config));
+    }
 }
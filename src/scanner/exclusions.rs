@@ -0,0 +1,136 @@
+//! Exclusion patterns
+//!
+//! `--exclude` and `[exclusions] patterns` take globs or regular expressions.
+//! Globs (`**/cache/**`, `*.iso`, `node_modules`) match anywhere in the path
+//! unless they start with `/` or `**/`, and a glob naming a folder also
+//! excludes everything inside it. Patterns starting with `re:` are regular
+//! expressions searched for in the full path, e.g. `re:/\.venv\d*/`. Paths
+//! are matched with `/` separators on every platform, and case-insensitively
+//! on Windows.
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex_automata::meta::Regex;
+use regex_automata::util::syntax;
+use std::path::Path;
+
+/// Prefix marking a pattern as a regular expression
+pub const REGEX_PREFIX: &str = "re:";
+
+/// Compiled exclusion patterns
+#[derive(Debug, Clone)]
+pub struct ExclusionSet {
+    globs: GlobSet,
+    regexes: Vec<Regex>,
+}
+
+enum Compiled {
+    Globs(Vec<globset::Glob>),
+    Regex(Regex),
+}
+
+fn compile(pattern: &str) -> Result<Compiled> {
+    let case_insensitive = cfg!(windows);
+    if let Some(expr) = pattern.strip_prefix(REGEX_PREFIX) {
+        let regex = Regex::builder()
+            .syntax(syntax::Config::new().case_insensitive(case_insensitive))
+            .build(expr)
+            .with_context(|| format!("Invalid exclude regex '{}'", expr))?;
+        return Ok(Compiled::Regex(regex));
+    }
+
+    let pattern = pattern.replace('\\', "/");
+    let anchored = if pattern.starts_with("**/") || pattern.starts_with('/') {
+        pattern
+    } else {
+        format!("**/{}", pattern)
+    };
+    let mut globs = vec![anchored.clone()];
+    if !anchored.ends_with("/**") {
+        globs.push(format!("{}/**", anchored.trim_end_matches('/')));
+    }
+    globs
+        .iter()
+        .map(|g| {
+            GlobBuilder::new(g)
+                .case_insensitive(case_insensitive)
+                .build()
+                .with_context(|| format!("Invalid exclude glob '{}'", g))
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Compiled::Globs)
+}
+
+/// Check that every pattern compiles
+pub fn validate(patterns: &[String]) -> crate::Result<()> {
+    for pattern in patterns {
+        compile(pattern).map_err(|e| crate::Error::Config(format!("{:#}", e)))?;
+    }
+    Ok(())
+}
+
+impl ExclusionSet {
+    /// Compile `patterns`, skipping (and returning) the ones that don't compile
+    pub fn new(patterns: &[String]) -> (Self, Vec<String>) {
+        let mut builder = GlobSetBuilder::new();
+        let mut regexes = Vec::new();
+        let mut invalid = Vec::new();
+        for pattern in patterns {
+            match compile(pattern) {
+                Ok(Compiled::Globs(globs)) => {
+                    for glob in globs {
+                        builder.add(glob);
+                    }
+                }
+                Ok(Compiled::Regex(regex)) => regexes.push(regex),
+                Err(_) => invalid.push(pattern.clone()),
+            }
+        }
+        let globs = builder.build().unwrap_or_else(|_| GlobSet::empty());
+        (Self { globs, regexes }, invalid)
+    }
+
+    pub fn is_match(&self, path: &Path) -> bool {
+        let normalized = path.to_string_lossy().replace('\\', "/");
+        self.globs.is_match(normalized.as_str())
+            || self.regexes.iter().any(|r| r.is_match(normalized.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_and_regexes_exclude_paths() {
+        let patterns: Vec<String> = [
+            "**/cache/**",
+            "*.iso",
+            "node_modules",
+            r"re:/\.venv\d*/",
+            "re:^/srv/",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let (set, invalid) = ExclusionSet::new(&patterns);
+        assert!(invalid.is_empty());
+
+        assert!(set.is_match(Path::new("/home/me/.npm/cache/x/y.tgz")));
+        assert!(set.is_match(Path::new("/home/me/Downloads/ubuntu.iso")));
+        assert!(set.is_match(Path::new("/work/app/node_modules")));
+        assert!(set.is_match(Path::new("/work/app/node_modules/react/index.js")));
+        assert!(set.is_match(Path::new("/work/app/.venv311/lib/site.py")));
+        assert!(set.is_match(Path::new("/srv/data.bin")));
+        assert!(set.is_match(Path::new(r"C:\Users\me\cache\a.bin")));
+
+        assert!(!set.is_match(Path::new("/home/me/caches/a.bin")));
+        assert!(!set.is_match(Path::new("/home/me/iso/readme.txt")));
+        assert!(!set.is_match(Path::new("/data/srv/x")));
+
+        let bad = vec!["re:(unclosed".to_string(), "a{b".to_string()];
+        assert!(validate(&bad[..1]).is_err());
+        assert!(validate(&patterns).is_ok());
+        assert_eq!(ExclusionSet::new(&bad).1, bad);
+    }
+}
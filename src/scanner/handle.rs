@@ -0,0 +1,148 @@
+//! Cancellable background scans
+//!
+//! `scan_in_background` runs a regular scan on its own thread and returns a
+//! [`ScanHandle`]. `cancel()` may be called from any thread; the scan stops
+//! promptly, also in the middle of a category's folder walk, and `wait()`
+//! still returns what it found until then, with `ScanResults::cancelled`
+//! set. Cancelled scans don't update the scan cache.
+//!
+//! ```no_run
+//! use wole::cli::ScanOptions;
+//! use wole::config::Config;
+//! use wole::scanner::scan_in_background;
+//!
+//! let config = Config::load();
+//! let options = ScanOptions::from_keys(&["build".into(), "large".into()], &config);
+//! let scan = scan_in_background(std::path::Path::new("."), options, &config);
+//! std::thread::sleep(std::time::Duration::from_secs(5));
+//! scan.cancel();
+//! let results = scan.wait()?;
+//! println!("{} build items (cancelled: {})", results.build.items, results.cancelled);
+//! # Ok::<(), wole::Error>(())
+//! ```
+
+use super::scan_all_with_progress;
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::output::ScanResults;
+use crate::scan_cache::ScanCache;
+use crate::scan_events::ScanProgressEvent;
+use crate::tasks::{self, CancelToken, TaskKind};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// A scan running on a background thread. Dropping the handle cancels it.
+pub struct ScanHandle {
+    cancel: CancelToken,
+    progress: Receiver<ScanProgressEvent>,
+    result: Receiver<crate::Result<ScanResults>>,
+}
+
+impl ScanHandle {
+    /// Stop the scan; `wait` then returns the partial results
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Progress events, as the TUI's scanning screen gets them
+    pub fn progress(&self) -> &Receiver<ScanProgressEvent> {
+        &self.progress
+    }
+
+    /// The results if the scan has ended, without blocking
+    pub fn try_wait(&self) -> Option<crate::Result<ScanResults>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(stopped())),
+        }
+    }
+
+    /// Block until the scan ends and return its results
+    pub fn wait(self) -> crate::Result<ScanResults> {
+        self.result.recv().unwrap_or_else(|_| Err(stopped()))
+    }
+}
+
+impl Drop for ScanHandle {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+fn stopped() -> crate::Error {
+    crate::Error::Other("Scan thread stopped without results".to_string())
+}
+
+/// Scan `path` on a background thread, using the scan cache when
+/// `[cache] enabled` is set
+pub fn scan_in_background(path: &Path, options: ScanOptions, config: &Config) -> ScanHandle {
+    let (progress_tx, progress) = mpsc::channel();
+    let (result_tx, result) = mpsc::channel();
+    let path = path.to_path_buf();
+    let config = config.clone();
+
+    let cancel = tasks::spawn("scan", TaskKind::Worker, move |cancel| {
+        let mut scan_cache = if config.cache.enabled {
+            ScanCache::open().ok()
+        } else {
+            None
+        };
+        let _ = result_tx.send(scan_all_with_progress(
+            &path,
+            options,
+            &config,
+            &progress_tx,
+            scan_cache.as_mut(),
+            Some(&cancel),
+        ));
+    });
+
+    ScanHandle {
+        cancel,
+        progress,
+        result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn cancelled_scans_return_partial_results() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("one.bin"), vec![7u8; 64 * 1024]).unwrap();
+        fs::write(temp_dir.path().join("two.bin"), vec![7u8; 64 * 1024]).unwrap();
+        let mut config = Config::default();
+        config.cache.enabled = false;
+        let options = ScanOptions::from_keys(&["duplicates".to_string()], &config);
+
+        let finished = scan_in_background(temp_dir.path(), options.clone(), &config)
+            .wait()
+            .unwrap();
+        assert!(!finished.cancelled);
+        assert!(!finished.duplicates.paths.is_empty());
+
+        // Cancelled before the first category: nothing scanned, but no error
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let (progress_tx, _) = mpsc::channel();
+        let partial = scan_all_with_progress(
+            temp_dir.path(),
+            options,
+            &config,
+            &progress_tx,
+            None,
+            Some(&cancel),
+        )
+        .unwrap();
+        assert!(partial.cancelled);
+        assert!(partial.duplicates.paths.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+//! Streaming scans
+//!
+//! `scan_streaming` runs a scan on a background thread and hands out its
+//! results as they come in, for applications that embed wole and want to
+//! show results incrementally. Each category's items are sent as soon as
+//! that category finishes, after the same exclusion, Recycle Bin and
+//! open-in-editor filters a regular scan applies; the complete
+//! `ScanResults` follows at the end. Streaming scans don't read or update
+//! the scan cache; `scan_with_events` runs the same scan on the calling
+//! thread with a cache, as `--json-stream` does.
+//!
+//! ```no_run
+//! use wole::cli::ScanOptions;
+//! use wole::config::Config;
+//! use wole::scanner::{scan_streaming, ScanEvent};
+//!
+//! let config = Config::load();
+//! let options = ScanOptions::from_keys(&["cache".into(), "temp".into()], &config);
+//! for event in scan_streaming(std::path::Path::new("."), options, &config) {
+//!     match event {
+//!         ScanEvent::ItemFound { category, path } => println!("{}: {}", category, path.display()),
+//!         ScanEvent::Finished(results) => println!("{} items", results.cache.items),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use super::{
+    filter_exclusions, filter_open_in_editors, filter_recycle_bin_files, scan_all_with_hook,
+    size_walk, CategoryUpdate,
+};
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::output::{CategoryResult, OutputMode, ScanResults};
+use crate::scan_cache::ScanCache;
+use crate::tasks::{self, CancelToken, TaskKind};
+use crate::utils::SizeWalk;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+/// Something that happened during a streaming scan. Categories are named by
+/// their CLI key (`cache`, `app_cache`, ...).
+#[derive(Debug, Clone)]
+pub enum ScanEvent {
+    /// A category started scanning
+    CategoryStarted { category: &'static str },
+    /// A cleanable item, sent when its category finishes
+    ItemFound {
+        category: &'static str,
+        path: PathBuf,
+    },
+    /// A category finished; totals are for the items sent
+    CategoryFinished {
+        category: &'static str,
+        items: usize,
+        size_bytes: u64,
+    },
+    /// A category (or, without a category, the whole scan) failed
+    Error {
+        category: Option<&'static str>,
+        message: String,
+    },
+    /// The scan ended; always the last event unless it failed. A cancelled
+    /// scan finishes with what it found and `cancelled` set.
+    Finished(Box<ScanResults>),
+}
+
+/// Events of a running streaming scan. Iterating blocks until the next
+/// event; dropping the stream cancels the scan.
+pub struct ScanStream {
+    rx: Receiver<ScanEvent>,
+    cancel: CancelToken,
+}
+
+impl ScanStream {
+    /// Stop the scan; it still finishes with the partial results
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    /// The underlying channel, for polling with `try_recv` from a UI loop
+    pub fn receiver(&self) -> &Receiver<ScanEvent> {
+        &self.rx
+    }
+}
+
+impl Iterator for ScanStream {
+    type Item = ScanEvent;
+
+    fn next(&mut self) -> Option<ScanEvent> {
+        self.rx.recv().ok()
+    }
+}
+
+impl Drop for ScanStream {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Send a finished category's items after the filters a full scan applies
+fn send_category(
+    send: &dyn Fn(ScanEvent),
+    category: &'static str,
+    result: &CategoryResult,
+    config: &Config,
+    walk: &SizeWalk,
+) {
+    let mut single = ScanResults::default();
+    let Some(slot) = single.category_mut(category) else {
+        return;
+    };
+    *slot = result.clone();
+    filter_exclusions(&mut single, config, walk);
+    filter_recycle_bin_files(&mut single, walk);
+    if matches!(category, "large" | "old" | "downloads") {
+        filter_open_in_editors(&mut single, config, OutputMode::Quiet);
+    }
+
+    let Some(filtered) = single.category_mut(category) else {
+        return;
+    };
+    for path in &filtered.paths {
+        send(ScanEvent::ItemFound {
+            category,
+            path: path.clone(),
+        });
+    }
+    send(ScanEvent::CategoryFinished {
+        category,
+        items: filtered.items,
+        size_bytes: filtered.size_bytes,
+    });
+}
+
+/// Run a scan on the calling thread, passing each category's events to
+/// `send` as it finishes. Returns the complete results instead of sending
+/// `ScanEvent::Finished`.
+pub fn scan_with_events(
+    path: &Path,
+    options: ScanOptions,
+    config: &Config,
+    scan_cache: Option<&mut ScanCache>,
+    cancel: Option<&CancelToken>,
+    send: &dyn Fn(ScanEvent),
+) -> crate::Result<ScanResults> {
+    // Progress events are for the TUI; nobody reads them here
+    let (progress_tx, _) = mpsc::channel();
+    let walk = size_walk(&options, cancel);
+    let on_category = |category: &'static str, update: CategoryUpdate| match update {
+        CategoryUpdate::Started => send(ScanEvent::CategoryStarted { category }),
+        CategoryUpdate::Finished(Ok(result)) => {
+            send_category(send, category, result, config, &walk)
+        }
+        CategoryUpdate::Finished(Err(e)) => send(ScanEvent::Error {
+            category: Some(category),
+            message: format!("{:#}", e),
+        }),
+    };
+    Ok(scan_all_with_hook(
+        path,
+        options,
+        config,
+        &progress_tx,
+        scan_cache,
+        cancel,
+        &on_category,
+    )?)
+}
+
+/// Scan `path` on a background thread, streaming events as categories finish
+pub fn scan_streaming(path: &Path, options: ScanOptions, config: &Config) -> ScanStream {
+    let (tx, rx) = mpsc::channel();
+    let path = path.to_path_buf();
+    let config = config.clone();
+
+    let cancel = tasks::spawn("stream-scan", TaskKind::Worker, move |cancel| {
+        let send = |event: ScanEvent| {
+            let _ = tx.send(event);
+        };
+        let event = match scan_with_events(&path, options, &config, None, Some(&cancel), &send) {
+            Ok(results) => ScanEvent::Finished(Box::new(results)),
+            Err(e) => ScanEvent::Error {
+                category: None,
+                message: format!("{:#}", e),
+            },
+        };
+        let _ = tx.send(event);
+    });
+
+    ScanStream { rx, cancel }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn items_arrive_before_the_final_results() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp_dir.path().join("d")).unwrap();
+        for file in ["d/one.bin", "d/two.bin"] {
+            fs::write(temp_dir.path().join(file), vec![7u8; 64 * 1024]).unwrap();
+        }
+        let config = Config::default();
+        let options = ScanOptions::from_keys(&["duplicates".to_string()], &config);
+
+        let events: Vec<ScanEvent> = scan_streaming(temp_dir.path(), options, &config).collect();
+        assert!(matches!(
+            events.first(),
+            Some(ScanEvent::CategoryStarted {
+                category: "duplicates"
+            })
+        ));
+        let found: Vec<&PathBuf> = events
+            .iter()
+            .filter_map(|e| match e {
+                ScanEvent::ItemFound { path, .. } => Some(path),
+                _ => None,
+            })
+            .collect();
+        let Some(ScanEvent::Finished(results)) = events.last() else {
+            panic!("scan did not finish: {:?}", events.last());
+        };
+        assert!(!found.is_empty());
+        assert_eq!(found.len(), results.duplicates.paths.len());
+    }
+}
@@ -0,0 +1,266 @@
+//! Scheduled scans
+//!
+//! `wole daemon` scans the `[daemon]` categories every `interval_hours`,
+//! whether or not the machine is idle, so cleanup audits run without a Task
+//! Scheduler entry. Each scan refreshes the scan cache and writes a JSON
+//! report (the `--json` format) to the cache directory. Afterwards the
+//! optional `hook` command runs, and with `[notifications]` enabled a desktop
+//! notification is shown when enough space is cleanable.
+
+use crate::app_data::{self, unix_now};
+use crate::cli::ScanOptions;
+use crate::config::Config;
+use crate::history;
+use crate::notify::{self, ActionListener};
+use crate::output::{self, OutputMode};
+use crate::power;
+use crate::scan_cache::ScanCache;
+use crate::scanner;
+use crate::tasks::CancelToken;
+use crate::utils;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// How often the daemon checks whether a scan is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// State file in the cache directory
+const STATE_FILE: &str = "schedule.json";
+
+/// What the last scheduled scan found, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleState {
+    /// Unix timestamp of the last completed scan
+    pub last_run: Option<u64>,
+    #[serde(default)]
+    pub last_duration_secs: u64,
+    /// Cleanable bytes per category (CLI keys)
+    #[serde(default)]
+    pub cleanable_bytes: BTreeMap<String, u64>,
+    /// Unix timestamp of the last cleanable-space notification
+    #[serde(default)]
+    pub last_notified: Option<u64>,
+}
+
+impl ScheduleState {
+    pub fn load() -> Self {
+        app_data::load(STATE_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        app_data::save(STATE_FILE, self)
+    }
+
+    /// Seconds until the next scan is due (0 when it's due now)
+    pub fn due_in(&self, now: u64, interval_hours: u64) -> u64 {
+        match self.last_run {
+            Some(last) => (last + interval_hours.max(1) * 3600).saturating_sub(now),
+            None => 0,
+        }
+    }
+
+    pub fn cleanable_total(&self) -> u64 {
+        self.cleanable_bytes.values().sum()
+    }
+}
+
+/// Path of the JSON report written after each scheduled scan
+pub fn report_path() -> Result<PathBuf> {
+    Ok(app_data::cache_dir()?.join("last_scan.json"))
+}
+
+/// How long to wait before retrying after `failures` scans in a row failed:
+/// 5 minutes, doubling up to 6 hours
+pub fn retry_delay(failures: u32) -> Duration {
+    let minutes = 5u64.saturating_mul(1 << failures.saturating_sub(1).min(7));
+    Duration::from_secs(minutes.min(6 * 60) * 60)
+}
+
+/// Run `hook` through the shell with the report path and cleanable total
+fn run_hook(hook: &str, report: &Path, cleanable_bytes: u64) -> Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", hook]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", hook]);
+        c
+    };
+    let status = command
+        .env("WOLE_REPORT", report)
+        .env("WOLE_CLEANABLE_BYTES", cleanable_bytes.to_string())
+        .status()
+        .with_context(|| format!("Failed to run hook: {}", hook))?;
+    if !status.success() {
+        anyhow::bail!("Hook exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Scan now: refresh the scan cache, write the report, run the hook and
+/// notify. Returns the saved state.
+pub fn run_scan(config: &Config, log: &mut impl FnMut(&str)) -> Result<ScheduleState> {
+    let started = Instant::now();
+    let mut state = ScheduleState::load();
+
+    log("Scanning...");
+    let mut cache = ScanCache::open().context("Failed to open scan cache")?;
+    let root = directories::UserDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(utils::get_root_disk_path);
+    let options = ScanOptions::from_keys(&config.daemon.categories, config);
+    let results = scanner::scan_all(
+        &root,
+        options.clone(),
+        OutputMode::Quiet,
+        config,
+        Some(&mut cache),
+    )?;
+    if let Err(e) = history::record_sizes(&history::SizeSample::of(&results, &options.keys())) {
+        log(&format!("Failed to record size trend: {}", e));
+    }
+
+    let report = report_path()?;
+    std::fs::write(&report, output::to_json(&results, None)?)
+        .with_context(|| format!("Failed to write {}", report.display()))?;
+
+    state.last_run = Some(unix_now());
+    state.last_duration_secs = started.elapsed().as_secs();
+    state.cleanable_bytes = results
+        .by_category()
+        .into_iter()
+        .filter(|(_, category)| category.size_bytes > 0)
+        .map(|(key, category)| (key.to_string(), category.size_bytes))
+        .collect();
+    log(&format!(
+        "Scan completed: {} cleanable ({}s)",
+        bytesize::to_string(state.cleanable_total(), false),
+        state.last_duration_secs
+    ));
+
+    if !config.daemon.hook.trim().is_empty() {
+        if let Err(e) = run_hook(&config.daemon.hook, &report, state.cleanable_total()) {
+            log(&format!("Hook failed: {}", e));
+        }
+    }
+    if config.notifications.enabled {
+        match notify::notify_cleanable(
+            &config.notifications,
+            &state.cleanable_bytes,
+            &mut state.last_notified,
+            unix_now(),
+        ) {
+            Ok(Some(title)) => log(&format!("Notified: {}", title)),
+            Ok(None) => {}
+            Err(e) => log(&format!("Notification failed: {}", e)),
+        }
+    }
+
+    state.save()?;
+    Ok(state)
+}
+
+/// Scan every `interval_hours` until cancelled, answering notification
+/// actions in between. A scan waits while `[power]` asks to defer it, and
+/// after a failure the next attempt backs off (see `retry_delay`).
+pub fn run(
+    config: &Config,
+    interval_hours: u64,
+    cancel: &CancelToken,
+    mut log: impl FnMut(&str),
+) -> Result<()> {
+    let listener = if config.notifications.enabled {
+        ActionListener::start()
+            .map_err(|e| log(&format!("Notification actions unavailable: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    let mut failures = 0u32;
+    let mut retry_at: Option<Instant> = None;
+    let mut deferred = false;
+    while !cancel.is_cancelled() {
+        let due_in = ScheduleState::load().due_in(unix_now(), interval_hours);
+        let backing_off = retry_at.is_some_and(|at| Instant::now() < at);
+        let wait = if due_in == 0 && !backing_off {
+            match power::check(&config.power) {
+                Some(advice) if advice.defer => {
+                    if !deferred {
+                        log(&format!("{}. Deferring the scheduled scan", advice.message));
+                        deferred = true;
+                    }
+                }
+                advice => {
+                    if let Some(advice) = advice {
+                        log(&advice.message);
+                    }
+                    deferred = false;
+                    match run_scan(config, &mut log) {
+                        Ok(_) => {
+                            failures = 0;
+                            retry_at = None;
+                        }
+                        Err(e) => {
+                            failures += 1;
+                            let delay = retry_delay(failures);
+                            log(&format!(
+                                "Scheduled scan failed: {} (retrying in {} min)",
+                                e,
+                                delay.as_secs() / 60
+                            ));
+                            retry_at = Some(Instant::now() + delay);
+                        }
+                    }
+                }
+            }
+            CHECK_INTERVAL
+        } else if due_in == 0 {
+            CHECK_INTERVAL
+        } else {
+            CHECK_INTERVAL.min(Duration::from_secs(due_in))
+        };
+
+        // Sleep in short slices so cancellation and actions are noticed quickly
+        let wake = Instant::now() + wait;
+        while Instant::now() < wake && !cancel.is_cancelled() {
+            if let Some(listener) = &listener {
+                listener.poll(config, &mut log);
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_scan_follows_the_interval() {
+        let mut state = ScheduleState::default();
+        assert_eq!(state.due_in(10_000, 24), 0);
+
+        state.last_run = Some(10_000);
+        assert_eq!(state.due_in(10_000 + 3600, 24), 23 * 3600);
+        assert_eq!(state.due_in(10_000 + 25 * 3600, 24), 0);
+        // An interval of 0 still waits an hour rather than scanning in a loop
+        assert_eq!(state.due_in(10_000, 0), 3600);
+
+        state.cleanable_bytes = [("cache".to_string(), 3), ("temp".to_string(), 4)]
+            .into_iter()
+            .collect();
+        assert_eq!(state.cleanable_total(), 7);
+
+        // Failed scans back off instead of retrying every check
+        assert_eq!(retry_delay(1), Duration::from_secs(5 * 60));
+        assert_eq!(retry_delay(2), Duration::from_secs(10 * 60));
+        assert_eq!(retry_delay(30), Duration::from_secs(6 * 3600));
+    }
+}
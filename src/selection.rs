@@ -0,0 +1,186 @@
+//! Per-item selection for `wole clean --interactive`
+//!
+//! Lists every item a clean would delete, numbered, and lets the user skip
+//! some of them (`2,5-7`) before the usual confirmation. Skipped items are
+//! dropped from the scan results, so everything downstream (the plan
+//! summary, deletion, history, `--json`) only sees what's left.
+
+use crate::output::ScanResults;
+use crate::theme::Theme;
+use crate::utils;
+use anyhow::{bail, Result};
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// One deletable item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    /// CLI key of the item's category (`build`, `cache`, ...)
+    pub category: &'static str,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+fn path_size(path: &Path) -> u64 {
+    match std::fs::metadata(path) {
+        Ok(m) if m.is_dir() => utils::calculate_dir_size(path),
+        Ok(m) => m.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Every item in `results`, in category order. A category with a single
+/// path uses its reported size; otherwise each path is sized.
+pub fn items(results: &ScanResults) -> Vec<Item> {
+    let mut items = Vec::new();
+    for (category, result) in results.by_category() {
+        let single = result.paths.len() == 1;
+        for path in &result.paths {
+            items.push(Item {
+                category,
+                path: path.clone(),
+                size: if single {
+                    result.size_bytes
+                } else {
+                    path_size(path)
+                },
+            });
+        }
+    }
+    items
+}
+
+/// Parse 1-based item numbers and ranges (`2, 5-7`) against `count` items
+pub fn parse_numbers(input: &str, count: usize) -> Result<BTreeSet<usize>> {
+    let mut numbers = BTreeSet::new();
+    for part in input.split([',', ' ']).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (part, part),
+        };
+        let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) else {
+            bail!("'{}' is not an item number or range", part);
+        };
+        if start == 0 || end > count || start > end {
+            bail!("'{}' is outside 1-{}", part, count);
+        }
+        numbers.extend(start..=end);
+    }
+    Ok(numbers)
+}
+
+/// Drop the items numbered in `skipped` from `results`
+pub fn deselect(results: &mut ScanResults, items: &[Item], skipped: &BTreeSet<usize>) {
+    for item in skipped.iter().filter_map(|n| items.get(n - 1)) {
+        let Some(category) = results.category_mut(item.category) else {
+            continue;
+        };
+        category.paths.retain(|p| p != &item.path);
+        category.items = category.paths.len();
+        category.size_bytes = if category.paths.is_empty() {
+            0
+        } else {
+            category.size_bytes.saturating_sub(item.size)
+        };
+    }
+}
+
+/// Show the checklist and drop the items the user skips. Returns how many
+/// were skipped.
+pub fn prompt(results: &mut ScanResults) -> Result<usize> {
+    let items = items(results);
+    if items.is_empty() {
+        return Ok(0);
+    }
+
+    println!();
+    println!("{}", Theme::header("Items to clean"));
+    println!("{}", Theme::divider(60));
+    let width = items.len().to_string().len();
+    for (i, item) in items.iter().enumerate() {
+        println!(
+            "  {:>width$}. {} {:>10}  {}",
+            i + 1,
+            Theme::muted(&format!("{:<14}", item.category)),
+            bytesize::to_string(item.size, false),
+            utils::display_path(&item.path),
+            width = width
+        );
+    }
+    println!();
+
+    let skipped = loop {
+        print!("Numbers to skip (e.g. 2,5-7), or Enter to keep all: ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        match parse_numbers(input.trim(), items.len()) {
+            Ok(skipped) => break skipped,
+            Err(e) => println!("{}", Theme::error(&e.to_string())),
+        }
+    };
+
+    deselect(results, &items, &skipped);
+    if !skipped.is_empty() {
+        let bytes: u64 = skipped.iter().map(|n| items[n - 1].size).sum();
+        println!(
+            "{}",
+            Theme::muted(&format!(
+                "Skipping {} item(s) ({})",
+                skipped.len(),
+                bytesize::to_string(bytes, false)
+            ))
+        );
+    }
+    Ok(skipped.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::CategoryResult;
+
+    #[test]
+    fn skipped_items_are_dropped_from_results() {
+        let mut results = ScanResults {
+            build: CategoryResult {
+                items: 3,
+                size_bytes: 600,
+                paths: vec!["a".into(), "b".into(), "c".into()],
+                ..Default::default()
+            },
+            temp: CategoryResult {
+                items: 1,
+                size_bytes: 50,
+                paths: vec!["t".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let items: Vec<Item> = [
+            ("build", "a", 100),
+            ("build", "b", 200),
+            ("build", "c", 300),
+        ]
+        .into_iter()
+        .chain([("temp", "t", 50)])
+        .map(|(category, path, size)| Item {
+            category,
+            path: path.into(),
+            size,
+        })
+        .collect();
+
+        let skipped = parse_numbers("2, 4", items.len()).unwrap();
+        assert_eq!(parse_numbers("1-3", 4).unwrap().len(), 3);
+        assert!(parse_numbers("5", 4).is_err());
+        assert!(parse_numbers("x", 4).is_err());
+        assert!(parse_numbers("", 4).unwrap().is_empty());
+
+        deselect(&mut results, &items, &skipped);
+        assert_eq!(results.build.paths, vec![PathBuf::from("a"), "c".into()]);
+        assert_eq!((results.build.items, results.build.size_bytes), (2, 400));
+        assert_eq!((results.temp.items, results.temp.size_bytes), (0, 0));
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 
 /// Parse human-readable size strings to bytes
 ///
@@ -8,52 +8,7 @@ use anyhow::{bail, Result};
 /// - "1GB"   -> 1_073_741_824
 /// - "500KB" -> 512_000
 pub fn parse_size(s: &str) -> Result<u64> {
-    let s = s.trim();
-
-    if s.is_empty() {
-        bail!("Empty size string");
-    }
-
-    // Find where the number ends and unit begins
-    let mut num_end = s.len();
-    for (i, c) in s.char_indices() {
-        if !c.is_ascii_digit() && c != '.' {
-            num_end = i;
-            break;
-        }
-    }
-
-    if num_end == s.len() {
-        // No unit found, assume bytes
-        return Ok(s.parse::<u64>()?);
-    }
-
-    if num_end == 0 {
-        // String starts with non-digit, invalid format
-        bail!("Size string must start with a number: {}", s);
-    }
-
-    let num_str = &s[..num_end];
-    let unit_str = s[num_end..].trim().to_uppercase();
-
-    let num: f64 = num_str
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid number: {}", num_str))?;
-
-    let multiplier = match unit_str.as_str() {
-        "B" => 1u64,
-        "KB" => 1024u64,
-        "MB" => 1024u64 * 1024,
-        "GB" => 1024u64 * 1024 * 1024,
-        "TB" => 1024u64 * 1024 * 1024 * 1024,
-        _ => bail!(
-            "Unknown size unit: {}. Supported: B, KB, MB, GB, TB",
-            unit_str
-        ),
-    };
-
-    let bytes = (num * multiplier as f64) as u64;
-    Ok(bytes)
+    Ok(wole_core::size::parse_size(s)?)
 }
 
 #[cfg(test)]
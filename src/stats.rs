@@ -0,0 +1,144 @@
+//! Lifetime cleaning statistics
+//!
+//! Running totals of everything wole has cleaned, kept next to the deletion
+//! history in `lifetime_stats.json` and updated whenever a cleanup session's
+//! log is saved. The first load rebuilds the totals from the existing
+//! history logs, so users upgrading keep their past cleanups. Shown by
+//! `wole stats` and on the TUI dashboard.
+
+use crate::history::{self, DeletionLog};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const STATS_FILE: &str = "lifetime_stats.json";
+
+/// Totals across every cleanup session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// Bytes successfully cleaned
+    pub bytes_cleaned: u64,
+    /// Items successfully cleaned
+    pub items_cleaned: u64,
+    /// Cleanup sessions that removed at least one item
+    pub sessions: u64,
+    /// Start of the first counted session
+    #[serde(default)]
+    pub first_session: Option<DateTime<Utc>>,
+    /// Start of the latest counted session; older sessions are never counted twice
+    #[serde(default)]
+    pub last_session: Option<DateTime<Utc>>,
+}
+
+fn stats_path() -> Result<PathBuf> {
+    Ok(history::get_history_dir()?.join(STATS_FILE))
+}
+
+impl LifetimeStats {
+    /// Load the totals, rebuilding them from the history logs if they were
+    /// never saved
+    pub fn load() -> Self {
+        let Ok(path) = stats_path() else {
+            return Self::default();
+        };
+        if let Some(stats) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+        {
+            return stats;
+        }
+        let stats = Self::from_history();
+        let _ = stats.save();
+        stats
+    }
+
+    /// Totals of every session in the history directory
+    pub fn from_history() -> Self {
+        let mut logs: Vec<DeletionLog> = history::list_logs()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|path| history::load_log(path).ok())
+            .collect();
+        logs.sort_by_key(|log| log.session_start);
+        let mut stats = Self::default();
+        for log in &logs {
+            stats.add_session(log);
+        }
+        stats
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = stats_path()?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Add a session's successful deletions. Returns false (and changes
+    /// nothing) if it was empty or is not newer than the last counted one.
+    pub fn add_session(&mut self, log: &DeletionLog) -> bool {
+        let items = log.records.iter().filter(|r| r.success).count() as u64;
+        // Compared in whole seconds, the precision the history logs keep
+        let already_counted = self
+            .last_session
+            .is_some_and(|last| log.session_start.timestamp() <= last.timestamp());
+        if items == 0 || already_counted {
+            return false;
+        }
+        self.bytes_cleaned += log.total_bytes_cleaned;
+        self.items_cleaned += items;
+        self.sessions += 1;
+        self.first_session.get_or_insert(log.session_start);
+        self.last_session = Some(log.session_start);
+        true
+    }
+}
+
+/// Count a finished cleanup session in the lifetime totals
+pub fn record_session(log: &DeletionLog) -> Result<LifetimeStats> {
+    let mut stats = LifetimeStats::load();
+    if stats.add_session(log) {
+        stats.save()?;
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::path::Path;
+
+    #[test]
+    fn sessions_are_counted_once() {
+        let now = Utc::now();
+        let mut first = DeletionLog::new();
+        first.session_start = now - Duration::days(3);
+        first.log_success(Path::new("/tmp/a"), 1_000, "cache", false);
+        first.log_success(Path::new("/tmp/b"), 500, "temp", false);
+        first.log_failure(Path::new("/tmp/c"), 9_999, "temp", false, "locked");
+
+        let mut empty = DeletionLog::new();
+        empty.session_start = now - Duration::days(2);
+        empty.log_failure(Path::new("/tmp/d"), 10, "temp", false, "locked");
+
+        let mut second = DeletionLog::new();
+        second.session_start = now;
+        second.log_success(Path::new("/tmp/e"), 2_000, "build", true);
+
+        let mut stats = LifetimeStats::default();
+        assert!(stats.add_session(&first));
+        assert!(!stats.add_session(&empty));
+        assert!(stats.add_session(&second));
+        // Already counted (e.g. after a rebuild from the history logs)
+        assert!(!stats.add_session(&first));
+        assert!(!stats.add_session(&second));
+
+        assert_eq!(stats.bytes_cleaned, 3_500);
+        assert_eq!(stats.items_cleaned, 3);
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.first_session, Some(first.session_start));
+        assert_eq!(stats.last_session, Some(now));
+    }
+}
@@ -7,7 +7,6 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::time::{Duration, Instant};
 use sysinfo::System;
-use unicode_width::UnicodeWidthStr;
 
 // Thread-local state for tracking metrics over time (for delta calculations)
 thread_local! {
@@ -67,8 +66,9 @@ use windows::{
 struct DiskState {
     #[cfg(windows)]
     io_monitor: Option<WindowsDiskIOMonitor>,
+    /// Cumulative bytes read and written at the last sample
     #[cfg(not(windows))]
-    _phantom: std::marker::PhantomData<()>,
+    previous_io: Option<(u64, u64, Instant)>,
 }
 
 #[cfg(windows)]
@@ -84,7 +84,7 @@ impl std::fmt::Debug for DiskState {
 impl std::fmt::Debug for DiskState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DiskState")
-            .field("_phantom", &self._phantom)
+            .field("previous_io", &self.previous_io)
             .finish()
     }
 }
@@ -300,6 +300,9 @@ pub struct DiskMetrics {
     pub used_percent: f32,
     pub read_speed_mb: f64,
     pub write_speed_mb: f64,
+    /// Quota on the system drive that leaves less room than the disk
+    #[serde(default)]
+    pub quota: Option<crate::quota::Quota>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -433,11 +436,13 @@ pub struct BootInfo {
 /// Gather status asynchronously in a background thread
 /// This allows the UI to remain responsive while gathering system metrics
 pub fn gather_status_async(sender: std::sync::mpsc::Sender<Result<SystemStatus>>) {
-    std::thread::spawn(move || {
+    crate::tasks::spawn("status", crate::tasks::TaskKind::Worker, move |cancel| {
         use sysinfo::System;
         let mut system = System::new();
         let result = gather_status(&mut system);
-        let _ = sender.send(result);
+        if !cancel.is_cancelled() {
+            let _ = sender.send(result);
+        }
     });
 }
 
@@ -789,10 +794,183 @@ fn gather_disk_io_speeds(state: &mut DiskState, _elapsed: Duration) -> (f64, f64
 }
 
 #[cfg(not(windows))]
-fn gather_disk_io_speeds(_state: &mut DiskState, _elapsed: Duration) -> (f64, f64) {
-    // On non-Windows, disk I/O stats require sysinfo 0.37.2+ or platform-specific APIs
-    // For now, return zeros (can be enhanced with /proc/diskstats on Linux)
-    (0.0, 0.0)
+fn gather_disk_io_speeds(state: &mut DiskState, _elapsed: Duration) -> (f64, f64) {
+    // The OS only exposes cumulative byte counters, so rates come from the
+    // difference between two samples - the first call just records one
+    let Some((read, written)) = disk_io_totals() else {
+        return (0.0, 0.0);
+    };
+    let now = Instant::now();
+    let speeds = match state.previous_io {
+        Some((prev_read, prev_written, at)) => {
+            let secs = now.duration_since(at).as_secs_f64();
+            if secs > 0.0 {
+                let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0) / secs;
+                (
+                    mb(read.saturating_sub(prev_read)),
+                    mb(written.saturating_sub(prev_written)),
+                )
+            } else {
+                (0.0, 0.0)
+            }
+        }
+        None => (0.0, 0.0),
+    };
+    state.previous_io = Some((read, written, now));
+    speeds
+}
+
+/// Sum sectors read and written (fields 6 and 10) over the devices accepted
+/// by `is_disk`, in bytes. Sectors in /proc/diskstats are always 512 bytes.
+#[cfg(any(target_os = "linux", test))]
+fn parse_diskstats(text: &str, is_disk: impl Fn(&str) -> bool) -> (u64, u64) {
+    const SECTOR_SIZE: u64 = 512;
+    let mut read = 0u64;
+    let mut written = 0u64;
+    for line in text.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 || !is_disk(fields[2]) {
+            continue;
+        }
+        read += fields[5].parse::<u64>().unwrap_or(0) * SECTOR_SIZE;
+        written += fields[9].parse::<u64>().unwrap_or(0) * SECTOR_SIZE;
+    }
+    (read, written)
+}
+
+#[cfg(target_os = "linux")]
+fn disk_io_totals() -> Option<(u64, u64)> {
+    let text = std::fs::read_to_string("/proc/diskstats").ok()?;
+    // Only whole physical disks have a device link; this skips partitions
+    // (counted in their disk already) and loop, dm and md devices
+    Some(parse_diskstats(&text, |name| {
+        Path::new("/sys/block").join(name).join("device").exists()
+    }))
+}
+
+#[cfg(target_os = "macos")]
+fn disk_io_totals() -> Option<(u64, u64)> {
+    macos_disk_io::totals()
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+fn disk_io_totals() -> Option<(u64, u64)> {
+    None
+}
+
+/// Per-drive byte counters from IOKit's `IOBlockStorageDriver` statistics
+#[cfg(target_os = "macos")]
+mod macos_disk_io {
+    use std::ffi::{c_char, c_void, CStr};
+    use std::ptr;
+
+    type CFTypeRef = *const c_void;
+    type CFAllocatorRef = *const c_void;
+    type CFMutableDictionaryRef = *mut c_void;
+    type IoObject = u32;
+    type KernReturn = i32;
+
+    const KERN_SUCCESS: KernReturn = 0;
+    const K_IO_MAIN_PORT_DEFAULT: u32 = 0;
+    const K_CF_NUMBER_SINT64_TYPE: i32 = 4;
+    const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOServiceMatching(name: *const c_char) -> CFMutableDictionaryRef;
+        fn IOServiceGetMatchingServices(
+            main_port: u32,
+            matching: CFMutableDictionaryRef,
+            existing: *mut IoObject,
+        ) -> KernReturn;
+        fn IOIteratorNext(iterator: IoObject) -> IoObject;
+        fn IOObjectRelease(object: IoObject) -> KernReturn;
+        fn IORegistryEntryCreateCFProperties(
+            entry: IoObject,
+            properties: *mut CFMutableDictionaryRef,
+            allocator: CFAllocatorRef,
+            options: u32,
+        ) -> KernReturn;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(
+            alloc: CFAllocatorRef,
+            c_str: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+        fn CFNumberGetValue(number: CFTypeRef, the_type: i32, value: *mut c_void) -> u8;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    fn cf_string(s: &CStr) -> CFTypeRef {
+        unsafe { CFStringCreateWithCString(ptr::null(), s.as_ptr(), K_CF_STRING_ENCODING_UTF8) }
+    }
+
+    /// Read an SInt64 number stored under `key`, or 0
+    unsafe fn number(dict: CFTypeRef, key: CFTypeRef) -> u64 {
+        let value = CFDictionaryGetValue(dict, key);
+        let mut out: i64 = 0;
+        if value.is_null()
+            || CFNumberGetValue(
+                value,
+                K_CF_NUMBER_SINT64_TYPE,
+                &mut out as *mut i64 as *mut c_void,
+            ) == 0
+        {
+            return 0;
+        }
+        out.max(0) as u64
+    }
+
+    pub(super) fn totals() -> Option<(u64, u64)> {
+        unsafe {
+            let matching = IOServiceMatching(c"IOBlockStorageDriver".as_ptr());
+            if matching.is_null() {
+                return None;
+            }
+            // Consumes `matching`
+            let mut iterator: IoObject = 0;
+            if IOServiceGetMatchingServices(K_IO_MAIN_PORT_DEFAULT, matching, &mut iterator)
+                != KERN_SUCCESS
+            {
+                return None;
+            }
+
+            let statistics = cf_string(c"Statistics");
+            let bytes_read = cf_string(c"Bytes (Read)");
+            let bytes_written = cf_string(c"Bytes (Write)");
+            let (mut read, mut written) = (0u64, 0u64);
+            loop {
+                let drive = IOIteratorNext(iterator);
+                if drive == 0 {
+                    break;
+                }
+                let mut properties: CFMutableDictionaryRef = ptr::null_mut();
+                if IORegistryEntryCreateCFProperties(drive, &mut properties, ptr::null(), 0)
+                    == KERN_SUCCESS
+                    && !properties.is_null()
+                {
+                    let stats = CFDictionaryGetValue(properties, statistics);
+                    if !stats.is_null() {
+                        read += number(stats, bytes_read);
+                        written += number(stats, bytes_written);
+                    }
+                    CFRelease(properties);
+                }
+                IOObjectRelease(drive);
+            }
+            IOObjectRelease(iterator);
+            for s in [statistics, bytes_read, bytes_written] {
+                if !s.is_null() {
+                    CFRelease(s);
+                }
+            }
+            Some((read, written))
+        }
+    }
 }
 
 fn gather_disk_metrics(
@@ -835,11 +1013,13 @@ fn gather_disk_metrics(
         used_percent,
         read_speed_mb,
         write_speed_mb,
+        quota: crate::emergency::system_drive_space(&crate::config::Config::load().quota)
+            .and_then(|space| space.quota),
     }
 }
 
 #[cfg(feature = "battery")]
-fn gather_power_metrics() -> Option<PowerMetrics> {
+pub(crate) fn gather_power_metrics() -> Option<PowerMetrics> {
     use battery::{
         units::{
             electric_potential::volt, energy::watt_hour, power::watt, ratio::percent, time::second,
@@ -918,7 +1098,7 @@ fn gather_power_metrics() -> Option<PowerMetrics> {
 }
 
 #[cfg(not(feature = "battery"))]
-fn gather_power_metrics() -> Option<PowerMetrics> {
+pub(crate) fn gather_power_metrics() -> Option<PowerMetrics> {
     // Battery information not available without battery crate
     None
 }
@@ -2316,7 +2496,7 @@ pub fn gather_disk_breakdown_cached_only() -> Option<DiskBreakdown> {
 /// This allows the UI to remain responsive while scanning
 #[cfg(windows)]
 pub fn refresh_disk_breakdown_async() {
-    std::thread::spawn(|| {
+    crate::tasks::spawn("disk-breakdown", crate::tasks::TaskKind::Worker, |_| {
         let _ = gather_disk_breakdown();
     });
 }
@@ -2593,13 +2773,19 @@ fn calculate_health_score(
     // Memory score: lower usage is better
     let memory_score = (100.0 - memory.used_percent).max(0.0);
 
-    // Disk score: more free space is better
+    // Disk score: more free space is better. A quota with less room left
+    // than the disk is what the user runs into first, so it wins.
     let disk_free_percent = if disk.total_gb > 0.0 {
         (disk.free_gb / disk.total_gb) * 100.0
     } else {
         0.0
     };
-    let disk_score = disk_free_percent;
+    let disk_score = match &disk.quota {
+        Some(quota) if quota.limit > 0 => {
+            disk_free_percent.min(quota.available() as f64 * 100.0 / quota.limit as f64)
+        }
+        _ => disk_free_percent,
+    };
 
     // Temperature score (if available): lower is better
     let temp_score = if let Some(power) = power {
@@ -3077,6 +3263,21 @@ fn format_disk_section_new(status: &SystemStatus) -> Vec<String> {
         &free_value,
         MAIN_VALUE_WIDTH,
     ));
+    if let Some(quota) = &status.disk.quota {
+        let quota_value = format!(
+            "{} left ({})",
+            bytesize::to_string(quota.available(), false),
+            quota.kind.label()
+        );
+        lines.push(format_bar_value_line(
+            "Quota",
+            MAIN_LABEL_WIDTH,
+            None,
+            MAIN_BAR_WIDTH,
+            &quota_value,
+            MAIN_VALUE_WIDTH,
+        ));
+    }
 
     // Read/Write speeds - consistent format
     if status.disk.read_speed_mb < 1.0 {
@@ -3597,7 +3798,7 @@ fn format_two_columns(left: &[String], right: &[String], col_width: usize) -> St
         let right_line = right.get(i).map(|s| s.as_str()).unwrap_or("");
 
         // Calculate visible width using Unicode width
-        let left_visible_width = visible_width(left_line);
+        let left_visible_width = crate::output::render::display_width(left_line);
 
         // ALWAYS pad left column to col_width - no exceptions
         let padded_left = if left_visible_width < col_width {
@@ -3627,7 +3828,7 @@ fn format_single_column(lines: &[String]) -> String {
 }
 
 fn pad_to_visible(s: &str, width: usize) -> String {
-    let visible_len = visible_width(s);
+    let visible_len = crate::output::render::display_width(s);
     if visible_len >= width {
         s.to_string()
     } else {
@@ -3636,7 +3837,7 @@ fn pad_to_visible(s: &str, width: usize) -> String {
 }
 
 fn pad_bar_to_visible(bar: &str, width: usize) -> String {
-    let visible_len = visible_width(bar);
+    let visible_len = crate::output::render::display_width(bar);
     if visible_len >= width {
         // Truncate if too long (shouldn't happen, but be safe)
         truncate_to_visible(bar, width)
@@ -3681,36 +3882,6 @@ fn truncate_to_visible(s: &str, width: usize) -> String {
     result
 }
 
-fn strip_ansi_codes(s: &str) -> String {
-    // Remove ANSI escape sequences (simplified version)
-    let mut result = String::new();
-    let mut chars = s.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Skip ANSI escape sequence
-            if chars.peek() == Some(&'[') {
-                chars.next(); // consume '['
-                              // Skip until we find 'm' or end
-                for c in chars.by_ref() {
-                    if c == 'm' {
-                        break;
-                    }
-                }
-            }
-        } else {
-            result.push(ch);
-        }
-    }
-
-    result
-}
-
-/// Calculate the visible display width of a string, accounting for ANSI codes and Unicode width
-fn visible_width(s: &str) -> usize {
-    UnicodeWidthStr::width(strip_ansi_codes(s).as_str())
-}
-
 fn create_colored_bar(value: f32, width: usize) -> String {
     let clamped = value.clamp(0.0, 1.0);
     let filled = (clamped * width as f32).round() as usize;
@@ -3748,3 +3919,21 @@ fn create_colored_bar_inverted(value: f32, width: usize) -> String {
 
     format!("{}{}{}", color_code, bar, reset)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diskstats_sums_whole_disks_only() {
+        let text = "\
+ 259       0 nvme0n1 100 0 2048 50 200 0 4096 80 0 120 130
+ 259       1 nvme0n1p1 90 0 1024 40 150 0 2048 60 0 100 100
+   7       0 loop0 10 0 512 1 0 0 0 0 0 1 1
+   8       0 sda 1 0 8 1 2 0 16 1 0 2 2
+";
+        let (read, written) = parse_diskstats(text, |name| name == "nvme0n1" || name == "sda");
+        assert_eq!(read, (2048 + 8) * 512);
+        assert_eq!(written, (4096 + 16) * 512);
+    }
+}
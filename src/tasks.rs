@@ -0,0 +1,219 @@
+//! Supervision of background threads
+//!
+//! Scans, status refreshes and scan-cache writes run on their own threads.
+//! Spawning them through the supervisor keeps their `JoinHandle`s and a
+//! cancellation flag per task, so that on exit `shutdown()` can ask workers to
+//! stop and wait for pending writes instead of the process killing them halfway.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long `shutdown()` waits for writers (cache and history writes) to finish
+pub const WRITER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long `shutdown()` waits for cancelled workers before detaching them
+pub const WORKER_GRACE: Duration = Duration::from_millis(500);
+
+/// What a task does, which decides how shutdown treats it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// Produces results for the UI; safe to abandon once cancelled
+    Worker,
+    /// Persists data; shutdown waits for it to finish
+    Writer,
+}
+
+/// Cooperative cancellation flag shared with a task
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Guard that cancels the token when dropped (e.g. on early return)
+    pub fn guard(&self) -> CancelGuard {
+        CancelGuard(self.clone())
+    }
+}
+
+/// Cancels its token on drop
+pub struct CancelGuard(CancelToken);
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+struct Task {
+    name: &'static str,
+    kind: TaskKind,
+    cancel: CancelToken,
+    handle: JoinHandle<()>,
+}
+
+/// Outcome of `TaskSupervisor::shutdown`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Tasks that finished before the deadline
+    pub joined: usize,
+    /// Names of tasks still running when the deadline passed
+    pub detached: Vec<&'static str>,
+}
+
+/// Owns the handles and cancellation flags of spawned background threads
+#[derive(Default)]
+pub struct TaskSupervisor {
+    tasks: Mutex<Vec<Task>>,
+}
+
+impl TaskSupervisor {
+    pub const fn new() -> Self {
+        Self {
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Spawn a supervised thread. The closure receives the task's cancel token.
+    pub fn spawn<F>(&self, name: &'static str, kind: TaskKind, f: F) -> CancelToken
+    where
+        F: FnOnce(CancelToken) + Send + 'static,
+    {
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+        let handle = std::thread::Builder::new()
+            .name(format!("wole-{}", name))
+            .spawn(move || f(token))
+            .expect("failed to spawn background thread");
+
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.retain(|t| !t.handle.is_finished());
+            tasks.push(Task {
+                name,
+                kind,
+                cancel: cancel.clone(),
+                handle,
+            });
+        }
+        cancel
+    }
+
+    /// Number of tasks that are still running
+    pub fn active(&self) -> usize {
+        self.tasks
+            .lock()
+            .map(|tasks| tasks.iter().filter(|t| !t.handle.is_finished()).count())
+            .unwrap_or(0)
+    }
+
+    /// Cancel every task, wait up to `writer_timeout` for writers and
+    /// `worker_grace` for workers, and detach whatever is still running.
+    pub fn shutdown(&self, writer_timeout: Duration, worker_grace: Duration) -> ShutdownReport {
+        let tasks = match self.tasks.lock() {
+            Ok(mut tasks) => std::mem::take(&mut *tasks),
+            Err(_) => return ShutdownReport::default(),
+        };
+        for task in &tasks {
+            task.cancel.cancel();
+        }
+
+        let start = Instant::now();
+        let mut report = ShutdownReport::default();
+        for task in tasks {
+            let limit = match task.kind {
+                TaskKind::Writer => writer_timeout,
+                TaskKind::Worker => worker_grace,
+            };
+            while !task.handle.is_finished() && start.elapsed() < limit {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            if task.handle.is_finished() {
+                let _ = task.handle.join();
+                report.joined += 1;
+            } else {
+                report.detached.push(task.name);
+            }
+        }
+        report
+    }
+}
+
+static SUPERVISOR: TaskSupervisor = TaskSupervisor::new();
+
+/// Process-wide supervisor
+pub fn supervisor() -> &'static TaskSupervisor {
+    &SUPERVISOR
+}
+
+/// Spawn a thread on the process-wide supervisor
+pub fn spawn<F>(name: &'static str, kind: TaskKind, f: F) -> CancelToken
+where
+    F: FnOnce(CancelToken) + Send + 'static,
+{
+    SUPERVISOR.spawn(name, kind, f)
+}
+
+/// Stop background work before exiting, using the default timeouts
+pub fn shutdown() -> ShutdownReport {
+    SUPERVISOR.shutdown(WRITER_TIMEOUT, WORKER_GRACE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn shutdown_cancels_workers_and_waits_for_writers() {
+        let supervisor = TaskSupervisor::new();
+        let written = Arc::new(AtomicUsize::new(0));
+
+        supervisor.spawn("worker", TaskKind::Worker, |cancel| {
+            while !cancel.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        });
+        let w = written.clone();
+        supervisor.spawn("writer", TaskKind::Writer, move |_| {
+            std::thread::sleep(Duration::from_millis(50));
+            w.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let report = supervisor.shutdown(Duration::from_secs(5), Duration::from_secs(1));
+        assert_eq!(report.joined, 2);
+        assert!(report.detached.is_empty());
+        assert_eq!(written.load(Ordering::SeqCst), 1);
+        assert_eq!(supervisor.active(), 0);
+    }
+
+    #[test]
+    fn stuck_workers_are_detached_after_grace() {
+        let supervisor = TaskSupervisor::new();
+        supervisor.spawn("stuck", TaskKind::Worker, |_| {
+            std::thread::sleep(Duration::from_millis(300));
+        });
+        let report = supervisor.shutdown(Duration::from_secs(1), Duration::from_millis(20));
+        assert_eq!(report.detached, vec!["stuck"]);
+    }
+
+    #[test]
+    fn guard_cancels_on_drop() {
+        let token = CancelToken::new();
+        {
+            let _guard = token.guard();
+        }
+        assert!(token.is_cancelled());
+    }
+}
@@ -35,10 +35,16 @@ fn catch_trash_panic<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
 }
 
 pub fn delete(path: &Path) -> Result<()> {
+    #[cfg(feature = "failure-injection")]
+    crate::cleaner::failure_injection::inject(path, true)?;
     catch_trash_panic(|| Ok(trash::delete(path)?))
 }
 
 pub fn delete_all(paths: &[PathBuf]) -> Result<()> {
+    #[cfg(feature = "failure-injection")]
+    if crate::cleaner::failure_injection::batch_fails(paths) {
+        return Err(anyhow!("Recycle Bin batch failed (injected)"));
+    }
     catch_trash_panic(|| Ok(trash::delete_all(paths)?))
 }
 
@@ -46,6 +52,10 @@ pub fn list() -> Result<Vec<trash::TrashItem>> {
     catch_trash_panic(|| Ok(trash::os_limited::list()?))
 }
 
+pub fn metadata(item: &trash::TrashItem) -> Result<trash::TrashItemMetadata> {
+    catch_trash_panic(|| Ok(trash::os_limited::metadata(item)?))
+}
+
 pub fn purge_all(items: &[trash::TrashItem]) -> Result<()> {
     catch_trash_panic(|| Ok(trash::os_limited::purge_all(items)?))
 }
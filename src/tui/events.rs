@@ -50,6 +50,32 @@ pub fn handle_event(
             handle_optimize_event(app_state, key, modifiers)
         }
         crate::tui::state::Screen::Status { .. } => handle_status_event(app_state, key, modifiers),
+        crate::tui::state::Screen::History { .. } => {
+            handle_history_event(app_state, key, modifiers)
+        }
+        crate::tui::state::Screen::Custom { index } => {
+            handle_custom_event(app_state, index, key, modifiers)
+        }
+    }
+}
+
+fn handle_custom_event(
+    app_state: &mut AppState,
+    index: usize,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+) -> EventResult {
+    use crate::tui::extensions::{with_screen, ScreenAction};
+
+    let action = with_screen(index, |screen| screen.handle_key(key, modifiers, app_state))
+        .unwrap_or(ScreenAction::Back);
+    match action {
+        ScreenAction::Stay => EventResult::Continue,
+        ScreenAction::Back => {
+            app_state.screen = crate::tui::state::Screen::Dashboard;
+            EventResult::Continue
+        }
+        ScreenAction::Quit => EventResult::Quit,
     }
 }
 
@@ -304,8 +330,11 @@ fn handle_dashboard_event(
         }
         KeyCode::Down => {
             if app_state.focus_actions {
-                // Navigate in actions list (7 actions: Scan, Clean, Analyze, Restore, Config, Optimize, Status)
-                if app_state.action_cursor < 6 {
+                // Navigate in actions list (the built-in actions, then registered screens)
+                let last = crate::tui::state::BUILTIN_ACTIONS
+                    + crate::tui::extensions::actions().len()
+                    - 1;
+                if app_state.action_cursor < last {
                     app_state.action_cursor += 1;
                 }
             } else {
@@ -348,6 +377,36 @@ fn handle_dashboard_event(
                 }
             }
 
+            // Duplicates and Disk Insights read a lot of data; check battery first
+            let heavy_scan = match app_state.action_cursor {
+                0 | 1 => app_state
+                    .categories
+                    .iter()
+                    .any(|c| c.enabled && c.name == "Duplicates"),
+                2 => true,
+                _ => false,
+            };
+            let power_advice = if heavy_scan {
+                crate::power::check(&app_state.config.power)
+            } else {
+                None
+            };
+            if let Some(advice) = &power_advice {
+                if advice.defer && !app_state.power_override {
+                    // Second Enter press overrides the deferral
+                    app_state.power_override = true;
+                    app_state.dashboard_message = Some(format!(
+                        "⚠ {}. Scan deferred: plug in, or press Enter again to scan anyway",
+                        advice.message
+                    ));
+                    return EventResult::Continue;
+                }
+            }
+            if heavy_scan {
+                app_state.power_override = false;
+            }
+            let power_notice = power_advice.map(|advice| format!("⚠ {}", advice.message));
+
             match app_state.action_cursor {
                 0 => {
                     // Scan action
@@ -370,12 +429,14 @@ fn handle_dashboard_event(
                         progress: crate::tui::state::ScanProgress {
                             current_category: String::new(),
                             current_path: None,
-                            notice: None,
+                            notice: power_notice.clone(),
                             category_progress,
                             total_scanned: 0,
                             total_found: 0,
                             total_size: 0,
                             start_time: std::time::Instant::now(),
+                            hashing: None,
+                            stopping: false,
                         },
                     };
                 }
@@ -397,12 +458,14 @@ fn handle_dashboard_event(
                         progress: crate::tui::state::ScanProgress {
                             current_category: String::new(),
                             current_path: None,
-                            notice: None,
+                            notice: power_notice.clone(),
                             category_progress,
                             total_scanned: 0,
                             total_found: 0,
                             total_size: 0,
                             start_time: std::time::Instant::now(),
+                            hashing: None,
+                            stopping: false,
                         },
                     };
                 }
@@ -422,7 +485,7 @@ fn handle_dashboard_event(
                         progress: crate::tui::state::ScanProgress {
                             current_category: "Disk Insights".to_string(),
                             current_path: Some(scan_path),
-                            notice: None,
+                            notice: power_notice.clone(),
                             category_progress: vec![crate::tui::state::CategoryProgress {
                                 name: "Analyzing disk usage".to_string(),
                                 completed: false,
@@ -433,6 +496,8 @@ fn handle_dashboard_event(
                             total_found: 0,
                             total_size: 0,
                             start_time: std::time::Instant::now(),
+                            hashing: None,
+                            stopping: false,
                         },
                     };
                 }
@@ -480,7 +545,17 @@ fn handle_dashboard_event(
                     app_state.reset_config_editor();
                     app_state.screen = crate::tui::state::Screen::Config;
                 }
-                _ => {}
+                cursor => {
+                    // A screen registered through tui::extensions
+                    let index = cursor - crate::tui::state::BUILTIN_ACTIONS;
+                    if crate::tui::extensions::with_screen(index, |screen| {
+                        screen.on_open(app_state)
+                    })
+                    .is_some()
+                    {
+                        app_state.screen = crate::tui::state::Screen::Custom { index };
+                    }
+                }
             }
             EventResult::Continue
         }
@@ -500,14 +575,15 @@ fn handle_config_event(
     // 1 min_age_days (u64)
     // 2 min_size_mb (u64)
     // 3 default_scan_path (string/none)
-    // 4 animations (bool)
-    // 5 refresh_rate_ms (u64)
-    // 6 show_storage_info (bool)
-    // 7 scan_depth_user (u8)
-    // 8 scan_depth_entire_disk (u8)
-    // 9 full_disk_baseline (bool)
-    // 10 clear_cache (action)
-    let fields_len = 11usize;
+    // 4 scan_roots (list, separated by ';')
+    // 5 animations (bool)
+    // 6 refresh_rate_ms (u64)
+    // 7 show_storage_info (bool)
+    // 8 scan_depth_user (u8)
+    // 9 scan_depth_entire_disk (u8)
+    // 10 full_disk_baseline (bool)
+    // 11 clear_cache (action)
+    let fields_len = 12usize;
 
     // Editing mode has its own key handling.
     if let ConfigEditorMode::Editing { ref mut buffer } = app_state.config_editor.mode {
@@ -558,14 +634,39 @@ fn handle_config_event(
                         }
                         changed = true;
                     }
-                    5 => match raw.parse::<u64>() {
+                    4 => {
+                        let mut roots: Vec<String> = Vec::new();
+                        for root in raw.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+                            if !roots.iter().any(|r| r == root) {
+                                roots.push(root.to_string());
+                            }
+                        }
+                        let missing: Vec<&String> = roots
+                            .iter()
+                            .filter(|r| !std::path::Path::new(r.as_str()).is_dir())
+                            .collect();
+                        if missing.is_empty() {
+                            app_state.config.paths.scan_roots = roots;
+                            changed = true;
+                        } else {
+                            err = Some(format!(
+                                "Not a folder: {}",
+                                missing
+                                    .iter()
+                                    .map(|r| r.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("; ")
+                            ));
+                        }
+                    }
+                    6 => match raw.parse::<u64>() {
                         Ok(v) => {
                             app_state.config.ui.refresh_rate_ms = v;
                             changed = true;
                         }
                         Err(_) => err = Some("Invalid number for refresh rate (ms).".to_string()),
                     },
-                    7 => match raw.parse::<u8>() {
+                    8 => match raw.parse::<u8>() {
                         Ok(v) => {
                             app_state.config.ui.scan_depth_user = v;
                             changed = true;
@@ -576,7 +677,7 @@ fn handle_config_event(
                             )
                         }
                     },
-                    8 => match raw.parse::<u8>() {
+                    9 => match raw.parse::<u8>() {
                         Ok(v) => {
                             app_state.config.ui.scan_depth_entire_disk = v;
                             changed = true;
@@ -611,7 +712,7 @@ fn handle_config_event(
             KeyCode::Char(c) => {
                 let selected = app_state.config_editor.selected;
                 // Numeric fields accept digits only.
-                let is_numeric = matches!(selected, 0 | 1 | 2 | 5 | 7 | 8);
+                let is_numeric = matches!(selected, 0 | 1 | 2 | 6 | 8 | 9);
                 if is_numeric {
                     if c.is_ascii_digit() {
                         buffer.push(c);
@@ -653,7 +754,7 @@ fn handle_config_event(
         KeyCode::Char(' ') => {
             // Space toggles boolean fields when selected.
             match app_state.config_editor.selected {
-                4 => {
+                5 => {
                     app_state.config.ui.animations = !app_state.config.ui.animations;
                     match app_state.config.save() {
                         Ok(()) => app_state.config_editor.message = Some("Saved.".to_string()),
@@ -663,7 +764,7 @@ fn handle_config_event(
                     }
                     app_state.apply_config_to_state();
                 }
-                6 => {
+                7 => {
                     app_state.config.ui.show_storage_info = !app_state.config.ui.show_storage_info;
                     match app_state.config.save() {
                         Ok(()) => app_state.config_editor.message = Some("Saved.".to_string()),
@@ -673,7 +774,7 @@ fn handle_config_event(
                     }
                     app_state.apply_config_to_state();
                 }
-                9 => {
+                10 => {
                     app_state.config.cache.full_disk_baseline =
                         !app_state.config.cache.full_disk_baseline;
                     match app_state.config.save() {
@@ -689,7 +790,7 @@ fn handle_config_event(
         }
         KeyCode::Enter => {
             match app_state.config_editor.selected {
-                4 => {
+                5 => {
                     // Toggle bool
                     app_state.config.ui.animations = !app_state.config.ui.animations;
                     match app_state.config.save() {
@@ -700,7 +801,7 @@ fn handle_config_event(
                     }
                     app_state.apply_config_to_state();
                 }
-                6 => {
+                7 => {
                     // Toggle bool
                     app_state.config.ui.show_storage_info = !app_state.config.ui.show_storage_info;
                     match app_state.config.save() {
@@ -711,7 +812,7 @@ fn handle_config_event(
                     }
                     app_state.apply_config_to_state();
                 }
-                9 => {
+                10 => {
                     // Toggle bool
                     app_state.config.cache.full_disk_baseline =
                         !app_state.config.cache.full_disk_baseline;
@@ -755,28 +856,37 @@ fn handle_config_event(
                         "Edit path (blank = auto-detect). Enter saves; Esc cancels.".to_string(),
                     );
                 }
-                5 => {
+                4 => {
+                    app_state.config_editor.mode = ConfigEditorMode::Editing {
+                        buffer: app_state.config.paths.scan_roots.join("; "),
+                    };
+                    app_state.config_editor.message = Some(
+                        "Folders separated by ';' (blank = default scan path only). Enter saves; Esc cancels."
+                            .to_string(),
+                    );
+                }
+                6 => {
                     app_state.config_editor.mode = ConfigEditorMode::Editing {
                         buffer: app_state.config.ui.refresh_rate_ms.to_string(),
                     };
                     app_state.config_editor.message =
                         Some("Edit value, then Enter to save (Esc cancels).".to_string());
                 }
-                7 => {
+                8 => {
                     app_state.config_editor.mode = ConfigEditorMode::Editing {
                         buffer: app_state.config.ui.scan_depth_user.to_string(),
                     };
                     app_state.config_editor.message =
                         Some("Edit value (0-255), then Enter to save (Esc cancels).".to_string());
                 }
-                8 => {
+                9 => {
                     app_state.config_editor.mode = ConfigEditorMode::Editing {
                         buffer: app_state.config.ui.scan_depth_entire_disk.to_string(),
                     };
                     app_state.config_editor.message =
                         Some("Edit value (0-255), then Enter to save (Esc cancels).".to_string());
                 }
-                10 => {
+                11 => {
                     // Clear scan cache
                     match crate::scan_cache::ScanCache::open() {
                         Ok(mut cache) => match cache.clear_all() {
@@ -895,7 +1005,20 @@ fn handle_scanning_event(
 ) -> EventResult {
     match key {
         KeyCode::Esc => {
-            // Cancel scan - return to dashboard
+            // First Esc stops a cleanable-file scan and keeps what it found;
+            // a second one (or Esc during Disk Insights) returns to the dashboard
+            if let crate::tui::state::Screen::Scanning { ref mut progress } = app_state.screen {
+                if app_state.pending_action != crate::tui::state::PendingAction::Analyze
+                    && !progress.stopping
+                {
+                    progress.stopping = true;
+                    progress.notice = Some(
+                        "Stopping, keeping what was found so far (Esc again to discard)"
+                            .to_string(),
+                    );
+                    return EventResult::Continue;
+                }
+            }
             app_state.screen = crate::tui::state::Screen::Dashboard;
             app_state.pending_action = crate::tui::state::PendingAction::None;
             EventResult::Continue
@@ -1064,6 +1187,47 @@ fn handle_results_event(
             app_state.search_navigated = false; // Reset navigation flag when entering search mode
             EventResult::Continue
         }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            // Cycle sort column: size -> name -> age -> modified -> path -> category
+            let sort = app_state
+                .results_sort
+                .next_column(crate::tui::widgets::table::RESULTS_SORT_COLUMNS);
+            app_state.set_results_sort(sort);
+            EventResult::Continue
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            // Toggle grouped-by-category and flat views
+            app_state.toggle_results_view();
+            EventResult::Continue
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') => {
+            // Cycle drive filter: all drives -> C: -> D: -> ... -> all drives
+            app_state.cycle_volume_filter();
+            EventResult::Continue
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            // Reverse sort direction
+            let sort = app_state.results_sort.reversed();
+            app_state.set_results_sort(sort);
+            EventResult::Continue
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') if app_state.elevation_hint.is_some() => {
+            // Relaunch as administrator, handing over the current scan settings
+            let relaunched = crate::elevation::write_handoff(&app_state.elevation_handoff())
+                .and_then(|path| {
+                    crate::elevation::relaunch_elevated(&path).inspect_err(|_| {
+                        let _ = std::fs::remove_file(&path);
+                    })
+                });
+            match relaunched {
+                Ok(()) => EventResult::Quit,
+                Err(e) => {
+                    app_state.elevation_notice =
+                        Some(format!("Could not relaunch as admin: {}", e));
+                    EventResult::Continue
+                }
+            }
+        }
         KeyCode::Esc => {
             // If there's an active search filter, clear it; otherwise go back to Dashboard
             if !app_state.search_query.is_empty() {
@@ -1330,6 +1494,26 @@ fn handle_results_event(
 
             EventResult::Continue
         }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            // Leave the item, folder or category out of this clean (or bring it back)
+            let Some(row) = rows.get(app_state.cursor) else {
+                return EventResult::Continue;
+            };
+            let items = match *row {
+                crate::tui::state::ResultsRow::Item { item_idx, .. } => vec![item_idx],
+                crate::tui::state::ResultsRow::FolderHeader {
+                    group_idx,
+                    folder_idx,
+                    ..
+                } => app_state.folder_item_indices(group_idx, folder_idx),
+                crate::tui::state::ResultsRow::CategoryHeader { group_idx } => {
+                    app_state.category_item_indices(group_idx)
+                }
+                crate::tui::state::ResultsRow::Spacer => Vec::new(),
+            };
+            app_state.toggle_excluded(items);
+            EventResult::Continue
+        }
         KeyCode::Enter | KeyCode::Char('m') | KeyCode::Char('j') => {
             // Check for Ctrl+Enter to expand/collapse sibling groups.
             //
@@ -1578,6 +1762,15 @@ fn handle_results_event(
 
             EventResult::Continue
         }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            // Preview the item under the cursor
+            if let Some(crate::tui::state::ResultsRow::Item { item_idx, .. }) =
+                rows.get(app_state.cursor).copied()
+            {
+                app_state.open_preview(item_idx);
+            }
+            EventResult::Continue
+        }
         KeyCode::Char('c') | KeyCode::Char('C') => {
             // Confirm deletion
             if app_state.selected_count() > 0 {
@@ -1609,6 +1802,9 @@ fn handle_preview_event(
         KeyCode::Char('d') | KeyCode::Char('D') => {
             // Delete this item
             if let crate::tui::state::Screen::Preview { index } = app_state.screen {
+                if let Some(item) = app_state.all_items.get(index) {
+                    app_state.excluded_paths.remove(&item.path);
+                }
                 app_state.selected_items.insert(index);
                 // Snapshot current selection when entering confirm screen
                 app_state.confirm_snapshot = app_state.selected_items.clone();
@@ -1658,6 +1854,10 @@ fn handle_confirm_event(
     key: KeyCode,
     modifiers: KeyModifiers,
 ) -> EventResult {
+    if app_state.typed_confirm.is_some() {
+        return handle_typed_confirm_event(app_state, key);
+    }
+
     let rows = app_state.confirm_rows();
     let max_row = rows.len().saturating_sub(1);
 
@@ -1731,6 +1931,10 @@ fn handle_confirm_event(
 
     match key {
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            // Cancel - back to Disk Insights if that's where the deletion started
+            if app_state.cancel_insights_delete() {
+                return EventResult::Continue;
+            }
             // Cancel - back to results
             // Clear confirm snapshot and cache since we're leaving confirm screen
             app_state.confirm_snapshot.clear();
@@ -2173,19 +2377,7 @@ fn handle_confirm_event(
                 return EventResult::Continue;
             }
             if let crate::tui::state::Screen::Confirm { permanent } = app_state.screen {
-                app_state.permanent_delete = permanent;
-                // Clear confirm snapshot and cache since we're leaving confirm screen
-                app_state.confirm_snapshot.clear();
-                app_state.clear_confirm_cache();
-                app_state.screen = crate::tui::state::Screen::Cleaning {
-                    progress: crate::tui::state::CleanProgress {
-                        current_category: String::new(),
-                        current_path: None,
-                        cleaned: 0,
-                        total: app_state.selected_count() as u64,
-                        errors: 0,
-                    },
-                };
+                request_clean(app_state, permanent);
             }
             EventResult::Continue
         }
@@ -2198,19 +2390,7 @@ fn handle_confirm_event(
             if let crate::tui::state::Screen::Confirm { ref mut permanent } = app_state.screen {
                 *permanent = true;
                 // Then trigger cleaning with permanent flag set
-                app_state.permanent_delete = true;
-                // Clear confirm snapshot and cache since we're leaving confirm screen
-                app_state.confirm_snapshot.clear();
-                app_state.clear_confirm_cache();
-                app_state.screen = crate::tui::state::Screen::Cleaning {
-                    progress: crate::tui::state::CleanProgress {
-                        current_category: String::new(),
-                        current_path: None,
-                        cleaned: 0,
-                        total: app_state.selected_count() as u64,
-                        errors: 0,
-                    },
-                };
+                request_clean(app_state, true);
             }
             EventResult::Continue
         }
@@ -2218,6 +2398,68 @@ fn handle_confirm_event(
     }
 }
 
+/// Start cleaning, or ask for the size to be typed first when the selection
+/// is above the `[safety]` typed-confirmation thresholds
+fn request_clean(app_state: &mut AppState, permanent: bool) {
+    let phrase = crate::cleaner::required_phrase(
+        app_state.selected_size(),
+        app_state.selected_count() as u64,
+        &app_state.config.safety,
+    );
+    match phrase {
+        Some(phrase) => {
+            app_state.typed_confirm = Some(crate::tui::state::TypedConfirm {
+                phrase,
+                input: String::new(),
+                permanent,
+            });
+        }
+        None => start_clean(app_state, permanent),
+    }
+}
+
+fn start_clean(app_state: &mut AppState, permanent: bool) {
+    app_state.typed_confirm = None;
+    app_state.permanent_delete = permanent;
+    // Clear confirm snapshot and cache since we're leaving confirm screen
+    app_state.confirm_snapshot.clear();
+    app_state.clear_confirm_cache();
+    app_state.screen = crate::tui::state::Screen::Cleaning {
+        progress: crate::tui::state::CleanProgress {
+            current_category: String::new(),
+            current_path: None,
+            cleaned: 0,
+            total: app_state.selected_count() as u64,
+            errors: 0,
+        },
+    };
+}
+
+/// Keys while the typed confirmation prompt is open: text goes into the
+/// input, Enter checks it, Esc returns to the normal Confirm screen
+fn handle_typed_confirm_event(app_state: &mut AppState, key: KeyCode) -> EventResult {
+    let Some(typed) = app_state.typed_confirm.as_mut() else {
+        return EventResult::Continue;
+    };
+    match key {
+        KeyCode::Esc => app_state.typed_confirm = None,
+        KeyCode::Backspace => {
+            typed.input.pop();
+        }
+        KeyCode::Char(c) => typed.input.push(c),
+        KeyCode::Enter => {
+            if crate::cleaner::phrase_matches(&typed.phrase, &typed.input) {
+                let permanent = typed.permanent;
+                start_clean(app_state, permanent);
+            } else {
+                typed.input.clear();
+            }
+        }
+        _ => {}
+    }
+    EventResult::Continue
+}
+
 fn handle_cleaning_event(
     _app_state: &mut AppState,
     _key: KeyCode,
@@ -2234,7 +2476,10 @@ fn handle_success_event(
 ) -> EventResult {
     match key {
         KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('b') | KeyCode::Char('B') => {
-            // Navigate back to Results if there are remaining items
+            // Navigate back to Disk Insights, or to Results if there are remaining items
+            if app_state.finish_insights_delete() {
+                return EventResult::Continue;
+            }
             if !app_state.all_items.is_empty() {
                 app_state.screen = crate::tui::state::Screen::Results;
                 // Reset cursor to safe position
@@ -2246,6 +2491,13 @@ fn handle_success_event(
             }
             EventResult::Continue
         }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            // Show this clean in the history
+            app_state.finish_insights_delete();
+            let session = app_state.last_session_log.clone();
+            app_state.open_history(session.as_deref());
+            EventResult::Continue
+        }
         _ => {
             // Any other key returns to dashboard with a fresh start
             *app_state = AppState::new();
@@ -2254,6 +2506,66 @@ fn handle_success_event(
     }
 }
 
+fn handle_history_event(
+    app_state: &mut AppState,
+    key: KeyCode,
+    _modifiers: KeyModifiers,
+) -> EventResult {
+    let crate::tui::state::Screen::History {
+        ref sessions,
+        index,
+        ref log,
+        ref mut cursor,
+        ref mut message,
+    } = app_state.screen
+    else {
+        return EventResult::Continue;
+    };
+    let records = log.as_ref().map_or(0, |log| log.records.len());
+
+    match key {
+        KeyCode::Esc
+        | KeyCode::Backspace
+        | KeyCode::Char('b')
+        | KeyCode::Char('B')
+        | KeyCode::Char('q')
+        | KeyCode::Char('Q') => {
+            // Return to dashboard
+            *app_state = AppState::new();
+        }
+        KeyCode::Up => *cursor = cursor.saturating_sub(1),
+        KeyCode::Down if *cursor + 1 < records => *cursor += 1,
+        KeyCode::PageUp => *cursor = cursor.saturating_sub(10),
+        KeyCode::PageDown => *cursor = (*cursor + 10).min(records.saturating_sub(1)),
+        // Older and newer sessions
+        KeyCode::Left if index + 1 < sessions.len() => app_state.show_history_session(index + 1),
+        KeyCode::Right if index > 0 => app_state.show_history_session(index - 1),
+        KeyCode::Char('f') | KeyCode::Char('F') => app_state.retry_history_failures(),
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            let restorable = log.as_ref().map_or(0, |log| {
+                log.records
+                    .iter()
+                    .filter(|r| r.success && !r.permanent)
+                    .count()
+            });
+            if restorable == 0 {
+                *message = Some(
+                    "Nothing to restore: this session deleted permanently or failed".to_string(),
+                );
+            } else {
+                app_state.restore_session = sessions.get(index).cloned();
+                app_state.screen = crate::tui::state::Screen::Restore {
+                    progress: None,
+                    result: None,
+                    restore_all_bin: false,
+                };
+            }
+        }
+        _ => {}
+    }
+    EventResult::Continue
+}
+
 fn handle_restore_selection_event(
     app_state: &mut AppState,
     key: KeyCode,
@@ -2325,7 +2637,8 @@ fn handle_disk_insights_event(
     key: KeyCode,
     _modifiers: KeyModifiers,
 ) -> EventResult {
-    use crate::disk_usage::{find_folder_by_path, SortBy};
+    use crate::disk_usage::find_folder_by_path;
+    use crate::tui::widgets::table::{sort_rows, DISK_INSIGHTS_SORT_COLUMNS};
 
     if let crate::tui::state::Screen::DiskInsights {
         ref insights,
@@ -2348,22 +2661,9 @@ fn handle_disk_insights_event(
             files.retain(|file| file.name.to_lowercase().contains(&query));
         }
 
-        // Sort children folders (must match render order)
-        match *sort_by {
-            SortBy::Size => children.sort_by_key(|b| std::cmp::Reverse(b.size)),
-            SortBy::Name => children.sort_by(|a, b| a.name.cmp(&b.name)),
-            SortBy::Files => children.sort_by_key(|b| std::cmp::Reverse(b.file_count)),
-        }
-
-        // Sort files (must match render order)
-        match *sort_by {
-            SortBy::Size => files.sort_by_key(|b| std::cmp::Reverse(b.size)),
-            SortBy::Name => files.sort_by(|a, b| a.name.cmp(&b.name)),
-            SortBy::Files => {
-                // For files, Files sort doesn't make sense, so use size
-                files.sort_by_key(|b| std::cmp::Reverse(b.size));
-            }
-        }
+        // Sort folders and files (must match render order)
+        sort_rows(&mut children, *sort_by);
+        sort_rows(&mut files, *sort_by);
 
         let children_count = children.len();
         let files_count = files.len();
@@ -2507,17 +2807,54 @@ fn handle_disk_insights_event(
                 EventResult::Continue
             }
             KeyCode::Char('s') | KeyCode::Char('S') => {
-                // Cycle sort order: size -> name -> files -> size
-                *sort_by = match *sort_by {
-                    SortBy::Size => SortBy::Name,
-                    SortBy::Name => SortBy::Files,
-                    SortBy::Files => SortBy::Size,
-                };
+                // Cycle sort column: size -> name -> files -> category -> size
+                *sort_by = sort_by.next_column(DISK_INSIGHTS_SORT_COLUMNS);
+                EventResult::Continue
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                // Reverse sort direction
+                *sort_by = sort_by.reversed();
                 EventResult::Continue
             }
             KeyCode::Char('l') | KeyCode::Char('L') => {
-                // Show largest files (could open a modal or switch view)
-                // For now, just continue - could be enhanced later
+                // Toggle the largest-files pane next to the folder list
+                app_state.insights_largest_files = !app_state.insights_largest_files;
+                EventResult::Continue
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                // Delete the selected items, or the one under the cursor if none are selected
+                let mut targets: Vec<(std::path::PathBuf, u64)> = Vec::new();
+                if selected_paths.is_empty() {
+                    if let Some(child) = children.get(*cursor) {
+                        targets.push((child.path.clone(), child.size));
+                    } else if let Some(file) = files.get(cursor.saturating_sub(children_count)) {
+                        targets.push((file.path.clone(), file.size));
+                    }
+                } else {
+                    for path in selected_paths.iter() {
+                        let size = find_folder_by_path(&insights.root, path)
+                            .map(|node| node.size)
+                            .or_else(|| {
+                                let parent = path.parent()?;
+                                find_folder_by_path(&insights.root, parent)?
+                                    .files
+                                    .iter()
+                                    .find(|f| f.path == *path)
+                                    .map(|f| f.size)
+                            })
+                            .unwrap_or(0);
+                        targets.push((path.clone(), size));
+                    }
+                    // A selected folder already covers anything selected inside it
+                    let folders: Vec<std::path::PathBuf> =
+                        targets.iter().map(|(p, _)| p.clone()).collect();
+                    targets.retain(|(path, _)| {
+                        !folders.iter().any(|f| f != path && path.starts_with(f))
+                    });
+                    targets.sort();
+                }
+                app_state.search_mode = false;
+                app_state.delete_from_insights(targets);
                 EventResult::Continue
             }
             KeyCode::Char(' ') => {
@@ -0,0 +1,178 @@
+//! Custom screens for binaries that embed the TUI
+//!
+//! A downstream binary can add its own panels (say, a lab data cleanup
+//! screen) without forking the screen list: register a [`CustomScreen`]
+//! before calling [`crate::tui::run`] and it shows up as an extra action at
+//! the bottom of the dashboard. wole draws the logo and the shortcuts bar;
+//! the screen draws the area in between and handles every key while open.
+//!
+//! ```no_run
+//! use crossterm::event::{KeyCode, KeyModifiers};
+//! use ratatui::{layout::Rect, widgets::Paragraph, Frame};
+//! use wole::tui::extensions::{register_screen, CustomScreen, ScreenAction};
+//! use wole::tui::state::AppState;
+//!
+//! struct LabData;
+//!
+//! impl CustomScreen for LabData {
+//!     fn name(&self) -> &str {
+//!         "Lab data"
+//!     }
+//!     fn description(&self) -> &str {
+//!         "Remove expired instrument exports"
+//!     }
+//!     fn render(&mut self, f: &mut Frame, area: Rect, _app_state: &AppState) {
+//!         f.render_widget(Paragraph::new("Nothing to clean"), area);
+//!     }
+//!     fn handle_key(&mut self, key: KeyCode, _: KeyModifiers, _: &mut AppState) -> ScreenAction {
+//!         match key {
+//!             KeyCode::Esc => ScreenAction::Back,
+//!             _ => ScreenAction::Stay,
+//!         }
+//!     }
+//! }
+//!
+//! register_screen(LabData);
+//! wole::tui::run(None).unwrap();
+//! ```
+
+use crate::tui::state::AppState;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{layout::Rect, Frame};
+use std::sync::Mutex;
+
+/// What to do after a custom screen handled a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenAction {
+    /// Keep the screen open
+    Stay,
+    /// Return to the dashboard
+    Back,
+    /// Exit the TUI
+    Quit,
+}
+
+/// A screen added to the TUI by an embedding binary
+pub trait CustomScreen: Send {
+    /// Action label on the dashboard
+    fn name(&self) -> &str;
+
+    /// One-line description shown under the label
+    fn description(&self) -> &str;
+
+    /// Called each time the screen is opened from the dashboard
+    fn on_open(&mut self, _app_state: &mut AppState) {}
+
+    /// Draw the screen's content into `area` (between the logo and the
+    /// shortcuts bar)
+    fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState);
+
+    /// Handle a key press. Esc is passed through too; return
+    /// [`ScreenAction::Back`] to close the screen.
+    fn handle_key(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        app_state: &mut AppState,
+    ) -> ScreenAction;
+
+    /// Key hints for the shortcuts bar
+    fn shortcuts(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("Esc", "Back")]
+    }
+}
+
+static SCREENS: Mutex<Vec<Box<dyn CustomScreen>>> = Mutex::new(Vec::new());
+
+/// Add a screen to the dashboard's actions, after the built-in ones
+pub fn register_screen(screen: impl CustomScreen + 'static) {
+    screens().push(Box::new(screen));
+}
+
+fn screens() -> std::sync::MutexGuard<'static, Vec<Box<dyn CustomScreen>>> {
+    SCREENS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Name and description of every registered screen, in registration order
+pub(crate) fn actions() -> Vec<(String, String)> {
+    screens()
+        .iter()
+        .map(|s| (s.name().to_string(), s.description().to_string()))
+        .collect()
+}
+
+/// Run `f` on the screen registered `index`th, if there is one
+pub(crate) fn with_screen<R>(
+    index: usize,
+    f: impl FnOnce(&mut dyn CustomScreen) -> R,
+) -> Option<R> {
+    screens().get_mut(index).map(|screen| f(screen.as_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::events::{handle_event, EventResult};
+    use crate::tui::state::Screen;
+
+    struct Probe;
+
+    impl CustomScreen for Probe {
+        fn name(&self) -> &str {
+            "Probe"
+        }
+        fn description(&self) -> &str {
+            "Test screen"
+        }
+        fn render(&mut self, _f: &mut Frame, _area: Rect, _app_state: &AppState) {}
+        fn handle_key(
+            &mut self,
+            key: KeyCode,
+            _modifiers: KeyModifiers,
+            _app_state: &mut AppState,
+        ) -> ScreenAction {
+            match key {
+                KeyCode::Esc => ScreenAction::Back,
+                KeyCode::Char('q') => ScreenAction::Quit,
+                _ => ScreenAction::Stay,
+            }
+        }
+    }
+
+    #[test]
+    fn registered_screen_opens_from_dashboard_and_gets_keys() {
+        register_screen(Probe);
+        let index = actions().len() - 1;
+
+        let mut app_state = AppState::new();
+        app_state.focus_actions = true;
+        for _ in 0..20 {
+            handle_event(&mut app_state, KeyCode::Down, KeyModifiers::empty());
+        }
+        assert_eq!(
+            app_state.action_cursor,
+            crate::tui::state::BUILTIN_ACTIONS + index
+        );
+
+        handle_event(&mut app_state, KeyCode::Enter, KeyModifiers::empty());
+        assert!(matches!(app_state.screen, Screen::Custom { index: i } if i == index));
+
+        let none = KeyModifiers::empty();
+        assert_eq!(
+            handle_event(&mut app_state, KeyCode::Char('x'), none),
+            EventResult::Continue
+        );
+        assert_eq!(
+            handle_event(&mut app_state, KeyCode::Esc, none),
+            EventResult::Continue
+        );
+        assert!(matches!(app_state.screen, Screen::Dashboard));
+        assert_eq!(with_screen(index, |_| ()), Some(()));
+
+        handle_event(&mut app_state, KeyCode::Enter, none);
+        assert_eq!(
+            handle_event(&mut app_state, KeyCode::Char('q'), none),
+            EventResult::Quit
+        );
+    }
+}
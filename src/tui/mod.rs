@@ -3,6 +3,7 @@
 //! Provides a full-screen terminal UI using Ratatui for interactive file cleanup
 
 pub mod events;
+pub mod extensions;
 pub mod screens;
 pub mod state;
 pub mod theme;
@@ -15,6 +16,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::HashSet;
 use std::io::stdout;
 use std::path::PathBuf;
 use std::sync::mpsc;
@@ -24,6 +26,7 @@ use self::events::{handle_event, handle_mouse_event};
 use self::screens::render;
 use self::state::AppState;
 use crate::cleaner;
+use crate::cleaner::quarantine::Quarantine;
 use crate::cli::ScanOptions;
 use crate::config::Config;
 use crate::debug_log;
@@ -31,6 +34,37 @@ use crate::restore;
 use crate::scan_cache::ScanCache;
 use crate::scan_events::ScanProgressEvent;
 use crate::scanner;
+use crate::tasks::TaskKind;
+
+/// Resume the TUI in an elevated process from a handoff file written by the
+/// unelevated session (see `crate::elevation`). The handoff restores the scan
+/// path, categories and search, and rescans when the session asked for it;
+/// the file is removed once read.
+pub fn resume_elevated(handoff_path: &std::path::Path) -> Result<()> {
+    let handoff = crate::elevation::take_handoff(handoff_path)?;
+    let mut app_state = AppState::new();
+    app_state.apply_elevation_handoff(handoff);
+    run(Some(app_state))
+}
+
+/// Open the TUI straight into a scan of the default categories
+/// (the "Review" action of a daemon notification)
+pub fn review() -> Result<()> {
+    let mut app_state = AppState::new();
+    let handoff = crate::elevation::ElevationHandoff {
+        scan_path: app_state.scan_path.clone(),
+        categories: app_state
+            .categories
+            .iter()
+            .filter(|c| c.enabled)
+            .map(|c| c.name.clone())
+            .collect(),
+        search_query: String::new(),
+        rescan: true,
+    };
+    app_state.apply_elevation_handoff(handoff);
+    run(Some(app_state))
+}
 
 /// Run the TUI application
 pub fn run(initial_state: Option<AppState>) -> Result<()> {
@@ -43,7 +77,16 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
 
     // Initialize app state (use provided or create new)
     let mut app_state = initial_state.unwrap_or_default();
-    let mut scan_pending = false;
+    // Point to emergency mode when the system drive is nearly full
+    if app_state.dashboard_message.is_none() {
+        app_state.dashboard_message = crate::emergency::banner(&Config::load());
+    }
+    // Finish folders a previous run left staged for deletion, and drop
+    // quarantined items past their retention
+    crate::cleaner::background_delete::resume_pending();
+    crate::cleaner::quarantine::purge_expired();
+    // A resumed (elevated) session may start straight into a scan
+    let mut scan_pending = matches!(app_state.screen, crate::tui::state::Screen::Scanning { .. });
     let mut clean_pending = false;
     let mut last_tick_update = std::time::Instant::now();
 
@@ -69,6 +112,11 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
             app_state.tick = app_state.tick.wrapping_add(1);
         }
 
+        // Keep the Dashboard's status panel current (using background thread)
+        if matches!(app_state.screen, crate::tui::state::Screen::Dashboard) {
+            app_state.dashboard_status.poll();
+        }
+
         // Auto-refresh Status screen every 2 seconds (using background thread)
         if let crate::tui::state::Screen::Status {
             ref mut status,
@@ -136,6 +184,14 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
                 crate::trash_ops::list()
                     .map(|items| items.len())
                     .map_err(|e| anyhow::anyhow!("Failed to list Recycle Bin: {}", e))
+            } else if let Some(ref session) = app_state.restore_session {
+                // A session picked on the History screen
+                crate::history::load_log(session).map(|log| {
+                    log.records
+                        .iter()
+                        .filter(|r| r.success && !r.permanent)
+                        .count()
+                })
             } else {
                 // For restore from last deletion, get count from history
                 restore::get_restore_count()
@@ -252,7 +308,7 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
                 terminal.draw(|f| render(f, &mut app_state))?;
 
                 // Perform disk insights scan in background thread to allow animation
-                use crate::disk_usage::SortBy;
+                use crate::tui::widgets::table::TableSort;
                 use crate::utils;
 
                 // Determine appropriate depth based on scan path and config
@@ -271,20 +327,25 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
 
                 // Clone progress_tx for the callback
                 let progress_tx_clone = progress_tx.clone();
-                std::thread::spawn(move || {
-                    use crate::disk_usage::scan_directory_with_progress;
-                    let progress_callback: Option<crate::disk_usage::ProgressCallback> =
-                        Some(Box::new(move |path: &std::path::Path| {
-                            // Send progress update (ignore errors if receiver is dropped)
-                            let _ = progress_tx_clone.send(path.to_path_buf());
-                        }));
-                    let result = scan_directory_with_progress(
-                        &scan_path_clone,
-                        effective_depth,
-                        progress_callback,
-                    );
-                    let _ = tx.send(result);
-                });
+                let insights_cancel =
+                    crate::tasks::spawn("disk-insights", TaskKind::Worker, move |cancel| {
+                        use crate::disk_usage::scan_directory_with_progress;
+                        let progress_callback: Option<crate::disk_usage::ProgressCallback> =
+                            Some(Box::new(move |path: &std::path::Path| {
+                                // Send progress update (ignore errors if receiver is dropped)
+                                let _ = progress_tx_clone.send(path.to_path_buf());
+                            }));
+                        let result = scan_directory_with_progress(
+                            &scan_path_clone,
+                            effective_depth,
+                            progress_callback,
+                            Some(&cancel),
+                        );
+                        let _ = tx.send(result);
+                    });
+
+                // Leaving this loop for any reason tells the worker to stop
+                let _insights_guard = insights_cancel.guard();
 
                 // Wait for scan result while updating tick and redrawing for animation
                 let mut last_tick_update_scan = std::time::Instant::now();
@@ -306,7 +367,7 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
                                 insights,
                                 current_path: scan_path,
                                 cursor: 0,
-                                sort_by: SortBy::Size,
+                                sort_by: TableSort::default(),
                                 selected_paths: std::collections::HashSet::new(),
                             };
                             app_state.pending_action = crate::tui::state::PendingAction::None;
@@ -483,10 +544,20 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
                         failed_temp_files,
                     };
                     app_state.permanent_delete = false; // Reset flag
+                    let pending = crate::cleaner::background_delete::running().len();
+                    if pending > 0 {
+                        app_state.dashboard_message = Some(format!(
+                            "Reclaiming space from {} large folder{} in the background (quitting pauses it until the next run)",
+                            pending,
+                            if pending == 1 { "" } else { "s" }
+                        ));
+                    }
                 }
                 Err(e) => {
                     eprintln!("Cleanup error: {}", e);
-                    app_state.screen = crate::tui::state::Screen::Results;
+                    if !app_state.cancel_insights_delete() {
+                        app_state.screen = crate::tui::state::Screen::Results;
+                    }
                     app_state.permanent_delete = false; // Reset flag
                 }
             }
@@ -568,6 +639,9 @@ pub fn run(initial_state: Option<AppState>) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    // Stop scans and status refreshes, and let pending cache writes finish
+    crate::tasks::shutdown();
+
     Ok(())
 }
 
@@ -659,6 +733,10 @@ fn perform_scan_with_progress(
                     results.windows_update.size_bytes,
                 ),
                 "Event Logs" => (results.event_logs.items, results.event_logs.size_bytes),
+                "Orphaned Owners" => (results.orphaned.items, results.orphaned.size_bytes),
+                "Custom Rules" => (results.custom.items, results.custom.size_bytes),
+                "IDE Caches" => (results.ide.items, results.ide.size_bytes),
+                "Rust Toolchain" => (results.rust.items, results.rust.size_bytes),
                 _ => (0, 0),
             };
 
@@ -716,6 +794,10 @@ fn perform_scan_with_progress(
     let mut duplicates = false;
     let mut windows_update = false;
     let mut event_logs = false;
+    let mut orphaned = false;
+    let mut custom = false;
+    let mut ide = false;
+    let mut rust = false;
 
     for cat in &app_state.categories {
         match cat.name.as_str() {
@@ -734,6 +816,10 @@ fn perform_scan_with_progress(
             "Duplicates" => duplicates = cat.enabled,
             "Windows Update" => windows_update = cat.enabled,
             "Event Logs" => event_logs = cat.enabled,
+            "Orphaned Owners" => orphaned = cat.enabled,
+            "Custom Rules" => custom = cat.enabled,
+            "IDE Caches" => ide = cat.enabled,
+            "Rust Toolchain" => rust = cat.enabled,
             _ => {}
         }
     }
@@ -760,9 +846,15 @@ fn perform_scan_with_progress(
         duplicates,
         windows_update,
         event_logs,
+        orphaned,
+        custom,
+        ide,
+        rust,
         project_age_days: config.thresholds.project_age_days,
         min_age_days: config.thresholds.min_age_days,
         min_size_bytes,
+        summary_only: false,
+        estimate: None,
     };
 
     let mut first_scan_detected = false;
@@ -777,10 +869,14 @@ fn perform_scan_with_progress(
             // Deep baseline (heavier): full-disk traversal enabled.
             let root_path = crate::utils::get_root_disk_path();
             app_state.scan_path = root_path.clone();
+            let power_warning = crate::power::check(&config.power)
+                .map(|advice| format!(" · ⚠ {}", advice.message))
+                .unwrap_or_default();
             if let crate::tui::state::Screen::Scanning { ref mut progress } = app_state.screen {
-                progress.notice = Some(
-                    "First scan: building deep baseline (full-disk traversal enabled)".to_string(),
-                );
+                progress.notice = Some(format!(
+                    "First scan: building deep baseline (full-disk traversal enabled){}",
+                    power_warning
+                ));
                 progress.current_path = Some(root_path);
             }
         } else {
@@ -843,32 +939,56 @@ fn perform_scan_with_progress(
 
     // Run scan in background thread - this is a blocking call but we need results
     // The main loop will continue running and updating tick/redrawing while we wait
-    let scan_path = app_state.scan_path.clone();
+    // The deep baseline walks the whole drive, which covers any scan roots
+    let scan_roots = if first_scan_detected && config.cache.full_disk_baseline {
+        vec![app_state.scan_path.clone()]
+    } else {
+        app_state.scan_roots()
+    };
     let scan_options = options.clone();
     let scan_config = config.clone();
     let use_cache = scan_config.cache.enabled;
 
     let (result_tx, result_rx) = std::sync::mpsc::channel();
     let (progress_tx, progress_rx) = std::sync::mpsc::channel();
-    let _scan_handle = std::thread::spawn(move || {
+    let scan_cancel = crate::tasks::spawn("scan", TaskKind::Worker, move |cancel| {
         let mut scan_cache = if use_cache {
             ScanCache::open().ok()
         } else {
             None
         };
-        let result = scanner::scan_all_with_progress(
-            &scan_path,
-            scan_options,
-            &scan_config,
-            &progress_tx,
-            scan_cache.as_mut(),
-        );
-        let _ = result_tx.send(result);
+        // Scan each root in turn and merge what they found
+        let mut merged: Option<crate::output::ScanResults> = None;
+        for root in &scan_roots {
+            let result = scanner::scan_all_with_progress(
+                root,
+                scan_options.clone(),
+                &scan_config,
+                &progress_tx,
+                scan_cache.as_mut(),
+                Some(&cancel),
+            );
+            match (result, merged.as_mut()) {
+                (Ok(results), Some(merged)) => merged.merge(results),
+                (Ok(results), None) => merged = Some(results),
+                (Err(e), _) => {
+                    let _ = result_tx.send(Err(e));
+                    return;
+                }
+            }
+            if cancel.is_cancelled() {
+                break;
+            }
+        }
+        if let Some(results) = merged {
+            let _ = result_tx.send(Ok(results));
+        }
     });
+    // Cancelling the scan (Esc) or quitting returns early; stop the worker too
+    let _scan_guard = scan_cancel.guard();
 
     // Wait for scan to complete, manually updating tick and redrawing for spinner animation
     let mut last_tick_update = std::time::Instant::now();
-    let mut last_progress_draw = std::time::Instant::now();
     let mut running_total_items = 0usize;
     let mut running_total_bytes = 0u64;
     let mut completed_categories: std::collections::HashSet<String> =
@@ -952,28 +1072,66 @@ fn perform_scan_with_progress(
 
                     progress.total_scanned = completed_categories.len();
                 }
+                ScanProgressEvent::Hashing {
+                    category,
+                    files_hashed,
+                    files_total,
+                    bytes_hashed,
+                    bytes_total,
+                } => {
+                    let hashing = progress.hashing.get_or_insert_with(|| {
+                        crate::tui::state::HashingProgress {
+                            files_hashed,
+                            files_total,
+                            bytes_hashed,
+                            bytes_total,
+                            started: std::time::Instant::now(),
+                            started_bytes: bytes_hashed,
+                        }
+                    });
+                    hashing.files_hashed = files_hashed;
+                    hashing.files_total = files_total;
+                    hashing.bytes_hashed = bytes_hashed;
+                    hashing.bytes_total = bytes_total;
+                    if let Some(cat_progress) = progress
+                        .category_progress
+                        .iter_mut()
+                        .find(|c| c.name == category)
+                    {
+                        if bytes_total > 0 {
+                            cat_progress.progress_pct = bytes_hashed as f32 / bytes_total as f32;
+                        }
+                    }
+                }
             }
         }
     };
 
+    // Per-file events are coalesced into ~75ms batches so huge scans can't flood the UI loop
+    let mut progress_batcher =
+        crate::scan_events::ProgressBatcher::new(progress_rx, crate::scan_events::BATCH_INTERVAL);
+
     let results = loop {
-        let mut progress_updated = false;
-        while let Ok(event) = progress_rx.try_recv() {
-            apply_progress_event(event, app_state);
-            progress_updated = true;
-        }
-        if progress_updated && last_progress_draw.elapsed().as_millis() >= 50 {
+        if let Some(batch) = progress_batcher.poll() {
+            for event in batch {
+                apply_progress_event(event, app_state);
+            }
             let _ = terminal.draw(|f| render(f, app_state));
-            last_progress_draw = std::time::Instant::now();
         }
 
         match result_rx.try_recv() {
             Ok(Ok(results)) => break results,
-            Ok(Err(e)) => return Err(e),
+            Ok(Err(e)) => return Err(e.into()),
             Err(std::sync::mpsc::TryRecvError::Empty) => {
                 // Scan still in progress, check for cancellation
-                if !matches!(app_state.screen, crate::tui::state::Screen::Scanning { .. }) {
-                    return Ok(());
+                match app_state.screen {
+                    crate::tui::state::Screen::Scanning { ref progress } => {
+                        // Esc: stop the worker and wait for what it found
+                        if progress.stopping {
+                            scan_cancel.cancel();
+                        }
+                    }
+                    _ => return Ok(()),
                 }
 
                 // Increment tick frequently for smooth spinner animation (every 100ms)
@@ -982,7 +1140,6 @@ fn perform_scan_with_progress(
                     last_tick_update = std::time::Instant::now();
                     // Redraw terminal to show spinner animation
                     let _ = terminal.draw(|f| render(f, app_state));
-                    last_progress_draw = last_tick_update;
                 }
 
                 // Process events to allow cancellation
@@ -1008,7 +1165,7 @@ fn perform_scan_with_progress(
         }
     };
 
-    while let Ok(event) = progress_rx.try_recv() {
+    for event in progress_batcher.flush() {
         apply_progress_event(event, app_state);
     }
 
@@ -1018,6 +1175,13 @@ fn perform_scan_with_progress(
         return Ok(());
     }
 
+    let _ =
+        crate::history::record_sizes(&crate::history::SizeSample::of(&results, &options.keys()));
+    app_state.size_trend = crate::history::recent_totals(
+        crate::tui::state::SIZE_TREND_WEEKS,
+        crate::tui::state::SIZE_TREND_POINTS,
+    );
+
     if completed_categories.len() < total_categories {
         for cat_progress_name in &enabled_categories {
             if completed_categories.contains(cat_progress_name) {
@@ -1066,7 +1230,13 @@ fn perform_scan_with_progress(
         }
     }
 
+    // Partial results go to the results screen, not straight to cleaning
+    if results.cancelled {
+        app_state.pending_action = crate::tui::state::PendingAction::None;
+    }
     app_state.scan_results = Some(results);
+    app_state.elevation_hint = crate::elevation::hint();
+    app_state.elevation_notice = None;
 
     // Store enabled categories for future reuse checks
     app_state.last_scan_categories = Some(
@@ -1098,6 +1268,7 @@ fn empty_batch_result() -> cleaner::BatchDeleteResult {
         skipped_paths: Vec::new(),
         locked_paths: Vec::new(),
         permission_denied_paths: Vec::new(),
+        failures: std::collections::HashMap::new(),
     }
 }
 
@@ -1106,14 +1277,21 @@ fn run_batch_delete_with_ui(
     terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
     paths: Vec<PathBuf>,
     permanent: bool,
+    quarantine: Option<&Quarantine>,
 ) -> cleaner::BatchDeleteResult {
     if paths.is_empty() {
         return empty_batch_result();
     }
 
     let (tx, rx) = mpsc::channel();
-    std::thread::spawn(move || {
-        let result = cleaner::clean_paths_batch(&paths, permanent);
+    let quarantine = quarantine.cloned();
+    crate::tasks::spawn("delete-batch", TaskKind::Writer, move |_| {
+        let result = cleaner::clean_paths_batch_with_progress(
+            &paths,
+            permanent,
+            quarantine.as_ref(),
+            &|_| {},
+        );
         let _ = tx.send(result);
     });
 
@@ -1145,17 +1323,83 @@ fn run_batch_delete_with_ui(
     }
 }
 
+/// Batch delete `paths`, deleting the ones in `cross_volume` permanently even
+/// when recycling (they are large and on a volume without a usable Recycle Bin)
+fn run_routed_batch_delete_with_ui(
+    app_state: &mut AppState,
+    terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+    paths: &[PathBuf],
+    permanent: bool,
+    quarantine: Option<&Quarantine>,
+    cross_volume: &HashSet<PathBuf>,
+) -> cleaner::BatchDeleteResult {
+    let (forced, rest): (Vec<PathBuf>, Vec<PathBuf>) = paths
+        .iter()
+        .cloned()
+        .partition(|p| !permanent && cross_volume.contains(p));
+    let mut result = run_batch_delete_with_ui(app_state, terminal, rest, permanent, quarantine);
+    if !forced.is_empty() {
+        result.merge(run_batch_delete_with_ui(
+            app_state, terminal, forced, true, quarantine,
+        ));
+    }
+    result
+}
+
+/// Paths that `[cross_volume]` routes to permanent deletion, grouped by
+/// category so per-category policies apply. Sets a dashboard notice when any
+/// item is rerouted or recycling will copy across volumes.
+fn cross_volume_permanent_paths(
+    app_state: &mut AppState,
+    items: &[(usize, PathBuf, u64)],
+) -> HashSet<PathBuf> {
+    let settings = Config::load().cross_volume;
+    let mut by_category: std::collections::HashMap<String, Vec<PathBuf>> =
+        std::collections::HashMap::new();
+    let mut sizes: std::collections::HashMap<PathBuf, u64> = std::collections::HashMap::new();
+    for (idx, path, size) in items {
+        let category = app_state
+            .all_items
+            .get(*idx)
+            .map(|i| i.category.clone())
+            .unwrap_or_default();
+        by_category.entry(category).or_default().push(path.clone());
+        sizes.insert(path.clone(), *size);
+    }
+
+    let mut permanent = HashSet::new();
+    let mut notices = Vec::new();
+    for (category, paths) in by_category {
+        let split =
+            cleaner::split_for_recycle(&paths, &category, &settings, cleaner::recycle_route, |p| {
+                sizes.get(p).copied().unwrap_or(0)
+            });
+        if let Some(notice) = split.notice(&category) {
+            debug_log::cleaning_log(&notice);
+            notices.push(notice);
+        }
+        permanent.extend(split.permanent);
+    }
+    if !notices.is_empty() {
+        notices.sort();
+        app_state.dashboard_message = Some(notices.join("; "));
+    }
+    permanent
+}
+
 fn run_delete_with_ui(
     app_state: &mut AppState,
     terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
     path: PathBuf,
     permanent: bool,
+    quarantine: Option<&Quarantine>,
 ) -> anyhow::Result<cleaner::DeleteOutcome> {
     let display_path = path.display().to_string();
     let (tx, rx) = mpsc::channel();
     let path_for_thread = path.clone();
-    std::thread::spawn(move || {
-        let result = cleaner::delete_with_precheck(&path_for_thread, permanent);
+    let quarantine = quarantine.cloned();
+    crate::tasks::spawn("delete", TaskKind::Writer, move |_| {
+        let result = cleaner::precheck_and_delete(&path_for_thread, permanent, quarantine.as_ref());
         let _ = tx.send(result);
     });
 
@@ -1195,10 +1439,12 @@ fn perform_cleanup(
     terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
 ) -> anyhow::Result<(u64, u64, usize, Vec<PathBuf>)> {
     use crate::categories;
-    use crate::history::DeletionLog;
+    use crate::history::{DeletionLog, FailureInfo};
 
     // Create deletion log for audit trail
     let mut history = DeletionLog::new();
+    // Where non-permanent deletes go instead of the Recycle Bin, if anywhere
+    let quarantine = cleaner::quarantine::configured();
 
     // Collect all items to clean with their categories
     // Separate trash items since they're cleaned all at once
@@ -1242,8 +1488,37 @@ fn perform_cleanup(
         let _ = terminal.draw(|f| render(f, app_state));
 
         debug_log::cleaning_log("trash clean start");
-        match categories::trash::clean() {
-            Ok(()) => {
+        // Empty in batches so a huge bin shows progress; Esc stops between batches.
+        let min_age_days = app_state.config.thresholds.trash_min_age_days;
+        let emptied = categories::trash::clean_with_progress(min_age_days, |purged, total| {
+            if let crate::tui::state::Screen::Cleaning { ref mut progress } = app_state.screen {
+                progress.current_category =
+                    format!("Emptying Recycle Bin ({}/{})... Esc to stop", purged, total);
+            }
+            let _ = terminal.draw(|f| render(f, app_state));
+
+            let mut keep_going = true;
+            while event::poll(Duration::from_millis(0)).unwrap_or(false) {
+                if let Ok(Event::Key(key)) = event::read() {
+                    if key.kind == KeyEventKind::Press && key.code == crossterm::event::KeyCode::Esc
+                    {
+                        keep_going = false;
+                    }
+                }
+            }
+            keep_going
+        });
+        match emptied {
+            Ok(outcome) if outcome.cancelled => {
+                // Count what was actually purged; the rest stays in the bin
+                trash_cleaned = outcome.purged.min(trash_items.len()) as u64;
+                debug_log::cleaning_log(&format!(
+                    "trash clean cancelled: purged={} total={}",
+                    outcome.purged, outcome.total
+                ));
+                history.log_success(std::path::Path::new("Recycle Bin"), 0, "trash", true);
+            }
+            Ok(_) => {
                 // All trash items are cleaned
                 trash_cleaned = trash_items.len() as u64;
                 // Log trash cleanup success
@@ -1259,12 +1534,12 @@ fn perform_cleanup(
                 trash_errors = trash_items.len();
                 debug_log::cleaning_log(&format!("trash clean failed: {}", e));
                 // Log trash cleanup failure
-                history.log_failure(
+                history.log_error(
                     std::path::Path::new("Recycle Bin"),
                     trash_total_bytes,
                     "trash",
                     true,
-                    &e.to_string(),
+                    &e,
                 );
             }
         }
@@ -1273,7 +1548,7 @@ fn perform_cleanup(
 
     let total = (items_to_clean.len() + trash_items.len()) as u64;
     let mut cleaned = trash_cleaned;
-    let mut cleaned_bytes = if trash_cleaned > 0 {
+    let mut cleaned_bytes = if trash_cleaned == trash_items.len() as u64 {
         trash_total_bytes
     } else {
         0
@@ -1383,7 +1658,7 @@ fn perform_cleanup(
                 let artifacts =
                     crate::categories::applications::get_app_artifact_paths(&install_path);
                 for artifact in artifacts {
-                    match cleaner::delete_with_precheck(&artifact, permanent) {
+                    match cleaner::precheck_and_delete(&artifact, permanent, quarantine.as_ref()) {
                         Ok(cleaner::DeleteOutcome::Deleted) => {}
                         Ok(
                             cleaner::DeleteOutcome::SkippedMissing
@@ -1454,7 +1729,13 @@ fn perform_cleanup(
                 let _ = terminal.draw(|f| render(f, app_state));
             }
 
-            let delete_result = run_delete_with_ui(app_state, terminal, path.clone(), permanent);
+            let delete_result = run_delete_with_ui(
+                app_state,
+                terminal,
+                path.clone(),
+                permanent,
+                quarantine.as_ref(),
+            );
 
             match delete_result {
                 Ok(cleaner::DeleteOutcome::Deleted) => {
@@ -1503,13 +1784,7 @@ fn perform_cleanup(
                     ));
                     // Log failure
                     let category_lower = category.to_lowercase();
-                    history.log_failure(
-                        &path,
-                        size_bytes,
-                        &category_lower,
-                        permanent,
-                        &e.to_string(),
-                    );
+                    history.log_error(&path, size_bytes, &category_lower, permanent, &e);
                 }
             }
 
@@ -1550,7 +1825,13 @@ fn perform_cleanup(
                 let _ = terminal.draw(|f| render(f, app_state));
             }
 
-            match run_delete_with_ui(app_state, terminal, path.clone(), permanent) {
+            match run_delete_with_ui(
+                app_state,
+                terminal,
+                path.clone(),
+                permanent,
+                quarantine.as_ref(),
+            ) {
                 Ok(cleaner::DeleteOutcome::Deleted) => {
                     cleaned += 1;
                     cleaned_bytes += size_bytes;
@@ -1587,7 +1868,7 @@ fn perform_cleanup(
                         e
                     ));
                     // Log failure
-                    history.log_failure(&path, size_bytes, "cache", permanent, &e.to_string());
+                    history.log_error(&path, size_bytes, "cache", permanent, &e);
                 }
             }
 
@@ -1602,6 +1883,15 @@ fn perform_cleanup(
         }
     }
 
+    // Large items on volumes without a usable Recycle Bin follow `[cross_volume]`
+    // The quarantine takes them as they are
+    let cross_volume = if permanent || quarantine.is_some() {
+        HashSet::new()
+    } else {
+        let routable: Vec<_> = temp_items.iter().chain(&batch_items).cloned().collect();
+        cross_volume_permanent_paths(app_state, &routable)
+    };
+
     // Track failed temp files to show to user
     let mut failed_temp_files: Vec<PathBuf> = Vec::new();
 
@@ -1632,6 +1922,7 @@ fn perform_cleanup(
         let mut temp_errors = 0;
         let mut deleted_paths = Vec::new();
         let mut skipped_paths = Vec::new();
+        let mut failures: HashMap<PathBuf, FailureInfo> = HashMap::new();
 
         // Track last tick update for continuous animation
         let mut last_tick_update = std::time::Instant::now();
@@ -1657,10 +1948,17 @@ fn perform_cleanup(
 
             // Delete this batch
             debug_log::cleaning_log(&format!("temp batch delete: count={}", batch_chunk.len()));
-            let batch_result =
-                run_batch_delete_with_ui(app_state, terminal, batch_chunk.to_vec(), permanent);
+            let batch_result = run_routed_batch_delete_with_ui(
+                app_state,
+                terminal,
+                batch_chunk,
+                permanent,
+                quarantine.as_ref(),
+                &cross_volume,
+            );
             temp_success += batch_result.success_count;
             temp_errors += batch_result.error_count;
+            failures.extend(batch_result.failure_details());
             deleted_paths.extend(batch_result.deleted_paths);
             skipped_paths.extend(batch_result.skipped_paths);
 
@@ -1693,6 +1991,7 @@ fn perform_cleanup(
                     .get(path)
                     .cloned()
                     .unwrap_or_else(|| "temp".to_string());
+                let permanent = permanent || cross_volume.contains(path);
                 history.log_success(path, *size, &category, permanent);
             }
         }
@@ -1706,13 +2005,11 @@ fn perform_cleanup(
                         .get(path)
                         .cloned()
                         .unwrap_or_else(|| "temp".to_string());
-                    history.log_failure(
-                        path,
-                        *size,
-                        &category,
-                        permanent,
-                        "Temp file deletion failed (may be locked)",
-                    );
+                    let info = failures.get(path).cloned().unwrap_or_else(|| {
+                        FailureInfo::from_message("Temp file deletion failed (may be locked)")
+                    });
+                    let permanent = permanent || cross_volume.contains(path);
+                    history.log_failure_info(path, *size, &category, permanent, &info);
                 }
             }
         }
@@ -1763,6 +2060,7 @@ fn perform_cleanup(
         let mut batch_errors = 0;
         let mut deleted_paths = Vec::new();
         let mut skipped_paths = Vec::new();
+        let mut failures: HashMap<PathBuf, FailureInfo> = HashMap::new();
 
         // Track last tick update for continuous animation
         let mut last_tick_update = std::time::Instant::now();
@@ -1787,10 +2085,17 @@ fn perform_cleanup(
 
             // Delete this batch
             debug_log::cleaning_log(&format!("batch delete chunk: count={}", batch_chunk.len()));
-            let batch_result =
-                run_batch_delete_with_ui(app_state, terminal, batch_chunk.to_vec(), permanent);
+            let batch_result = run_routed_batch_delete_with_ui(
+                app_state,
+                terminal,
+                batch_chunk,
+                permanent,
+                quarantine.as_ref(),
+                &cross_volume,
+            );
             batch_success += batch_result.success_count;
             batch_errors += batch_result.error_count;
+            failures.extend(batch_result.failure_details());
             deleted_paths.extend(batch_result.deleted_paths);
             skipped_paths.extend(batch_result.skipped_paths);
 
@@ -1824,6 +2129,7 @@ fn perform_cleanup(
                     .get(path)
                     .cloned()
                     .unwrap_or_else(|| "unknown".to_string());
+                let permanent = permanent || cross_volume.contains(path);
                 history.log_success(path, *size, &category, permanent);
             }
         }
@@ -1836,7 +2142,12 @@ fn perform_cleanup(
                         .get(path)
                         .cloned()
                         .unwrap_or_else(|| "unknown".to_string());
-                    history.log_failure(path, *size, &category, permanent, "Batch deletion failed");
+                    let info = failures
+                        .get(path)
+                        .cloned()
+                        .unwrap_or_else(|| FailureInfo::from_message("Batch deletion failed"));
+                    let permanent = permanent || cross_volume.contains(path);
+                    history.log_failure_info(path, *size, &category, permanent, &info);
                 }
             }
         }
@@ -1879,12 +2190,16 @@ fn perform_cleanup(
     app_state.rebuild_groups_from_all_items();
 
     // Save deletion history log
-    if let Err(e) = history.save() {
-        // Log error but don't fail the cleanup operation
-        // In production, this is silently ignored to avoid disrupting the UI
-        #[cfg(debug_assertions)]
-        eprintln!("[DEBUG] Failed to save deletion log: {}", e);
+    match history.save() {
+        Ok(path) => app_state.last_session_log = Some(path),
+        Err(e) => {
+            // Log error but don't fail the cleanup operation
+            // In production, this is silently ignored to avoid disrupting the UI
+            #[cfg(debug_assertions)]
+            eprintln!("[DEBUG] Failed to save deletion log: {}", e);
+        }
     }
+    app_state.lifetime_stats = crate::stats::LifetimeStats::load();
 
     debug_log::cleaning_log(&format!(
         "cleanup complete: cleaned={} errors={} cleaned_bytes={}",
@@ -1899,16 +2214,20 @@ fn perform_restore(
     app_state: &mut AppState,
     terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
 ) -> anyhow::Result<restore::RestoreResult> {
-    // Get the most recent log
+    // The session picked on the History screen, or the most recent one
     use crate::history::{list_logs, load_log};
-    let logs = list_logs()?;
-    if logs.is_empty() {
-        return Err(anyhow::anyhow!(
-            "No deletion history found. Nothing to restore."
-        ));
-    }
-
-    let latest_log = load_log(&logs[0])?;
+    let latest_log = match app_state.restore_session.take() {
+        Some(session) => load_log(&session)?,
+        None => {
+            let logs = list_logs()?;
+            if logs.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No deletion history found. Nothing to restore."
+                ));
+            }
+            load_log(&logs[0])?
+        }
+    };
 
     // Get current Recycle Bin contents
     let recycle_bin_items =
@@ -151,56 +151,76 @@ fn render_body(f: &mut Frame, area: Rect, app_state: &AppState) {
         ),
     ]));
 
-    // 4 animations
+    // 4 scan_roots
+    field_lines.push(Line::from(vec![
+        Span::styled("  Scan roots:", Styles::secondary()),
+        Span::styled(
+            "   (scanned one after another, results merged)",
+            Styles::secondary(),
+        ),
+    ]));
+    let roots_text = if editing && selected == 4 {
+        edit_buffer.unwrap_or("").to_string()
+    } else if config.paths.scan_roots.is_empty() {
+        "(none - default scan path only)".to_string()
+    } else {
+        config.paths.scan_roots.join("; ")
+    };
+    field_lines.push(Line::from(vec![
+        Span::styled("    ", Styles::secondary()),
+        Span::styled(roots_text, field_style(4)),
+    ]));
+
+    // 5 animations
     field_lines.push(Line::from(vec![
         Span::styled("  Animations:       ", Styles::secondary()),
-        Span::styled(format!("{}", config.ui.animations), field_style(4)),
+        Span::styled(format!("{}", config.ui.animations), field_style(5)),
         Span::styled("   (Space/Enter toggles)", Styles::secondary()),
     ]));
 
-    // 5 refresh_rate_ms
+    // 6 refresh_rate_ms
     field_lines.push(Line::from(vec![
         Span::styled("  Refresh (ms):     ", Styles::secondary()),
         Span::styled(
-            if editing && selected == 5 {
+            if editing && selected == 6 {
                 edit_buffer.unwrap_or("").to_string()
             } else {
                 format!("{}", config.ui.refresh_rate_ms)
             },
-            field_style(5),
+            field_style(6),
         ),
     ]));
 
-    // 6 show_storage_info
+    // 7 show_storage_info
     field_lines.push(Line::from(vec![
         Span::styled("  Show storage info:", Styles::secondary()),
-        Span::styled(format!("{}", config.ui.show_storage_info), field_style(6)),
+        Span::styled(format!("{}", config.ui.show_storage_info), field_style(7)),
         Span::styled("   (Space/Enter toggles)", Styles::secondary()),
     ]));
 
-    // 7 scan_depth_user
+    // 8 scan_depth_user
     field_lines.push(Line::from(vec![
         Span::styled("  Scan depth (user):  ", Styles::secondary()),
         Span::styled(
-            if editing && selected == 7 {
+            if editing && selected == 8 {
                 edit_buffer.unwrap_or("").to_string()
             } else {
                 format!("{}", config.ui.scan_depth_user)
             },
-            field_style(7),
+            field_style(8),
         ),
     ]));
 
-    // 8 scan_depth_entire_disk
+    // 9 scan_depth_entire_disk
     field_lines.push(Line::from(vec![
         Span::styled("  Scan depth (disk):  ", Styles::secondary()),
         Span::styled(
-            if editing && selected == 8 {
+            if editing && selected == 9 {
                 edit_buffer.unwrap_or("").to_string()
             } else {
                 format!("{}", config.ui.scan_depth_entire_disk)
             },
-            field_style(8),
+            field_style(9),
         ),
     ]));
 
@@ -213,12 +233,12 @@ fn render_body(f: &mut Frame, area: Rect, app_state: &AppState) {
         Span::styled("    Enabled: ", Styles::secondary()),
         Span::styled(format!("{}", config.cache.enabled), Styles::primary()),
     ]));
-    // 9 full_disk_baseline (bool)
+    // 10 full_disk_baseline (bool)
     field_lines.push(Line::from(vec![
         Span::styled("    Full disk baseline: ", Styles::secondary()),
         Span::styled(
             format!("{}", config.cache.full_disk_baseline),
-            field_style(9),
+            field_style(10),
         ),
         Span::styled("   (Space/Enter toggles)", Styles::secondary()),
     ]));
@@ -238,12 +258,12 @@ fn render_body(f: &mut Frame, area: Rect, app_state: &AppState) {
     ]));
     field_lines.push(Line::from(""));
 
-    // 10 clear_cache (action button)
+    // 11 clear_cache (action button)
     field_lines.push(Line::from(vec![
         Span::styled("  Clear scan cache:   ", Styles::secondary()),
         Span::styled(
             "[Press Enter to clear]",
-            if selected == 10 {
+            if selected == 11 {
                 Styles::selected()
             } else {
                 Styles::warning()
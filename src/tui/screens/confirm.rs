@@ -176,32 +176,58 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
     // File list (larger, on the right)
     render_file_list(f, items_chunks[1], app_state);
 
-    // Actions
-    let actions_lines = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    [Y] ", Styles::emphasis()),
-            Span::styled(
-                if includes_apps {
-                    "Proceed (apps uninstall)"
-                } else {
-                    "Delete (to Recycle Bin)"
-                },
-                Styles::primary(),
-            ),
-            Span::styled("       [N] ", Styles::secondary()),
-            Span::styled("Cancel", Styles::secondary()),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    [P] ", Styles::warning()),
-            Span::styled("Permanent Delete", Styles::warning()),
-            Span::styled(
-                " (bypass Recycle Bin - cannot be undone!)",
-                Styles::secondary(),
-            ),
-        ]),
-    ];
+    // Actions (replaced by the typed confirmation prompt for large deletions)
+    let actions_lines = if let Some(ref typed) = app_state.typed_confirm {
+        vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("    Large deletion. Type ", Styles::warning()),
+                Span::styled(typed.phrase.clone(), Styles::emphasis()),
+                Span::styled(" to confirm: ", Styles::warning()),
+                Span::styled(format!("{}_", typed.input), Styles::primary()),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("    [Enter] ", Styles::emphasis()),
+                Span::styled(
+                    if typed.permanent {
+                        "Permanent Delete"
+                    } else {
+                        "Delete"
+                    },
+                    Styles::primary(),
+                ),
+                Span::styled("       [Esc] ", Styles::secondary()),
+                Span::styled("Back", Styles::secondary()),
+            ]),
+        ]
+    } else {
+        vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("    [Y] ", Styles::emphasis()),
+                Span::styled(
+                    if includes_apps {
+                        "Proceed (apps uninstall)"
+                    } else {
+                        "Delete (to Recycle Bin)"
+                    },
+                    Styles::primary(),
+                ),
+                Span::styled("       [N] ", Styles::secondary()),
+                Span::styled("Cancel", Styles::secondary()),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("    [P] ", Styles::warning()),
+                Span::styled("Permanent Delete", Styles::warning()),
+                Span::styled(
+                    " (bypass Recycle Bin - cannot be undone!)",
+                    Styles::secondary(),
+                ),
+            ]),
+        ]
+    };
     let actions = Paragraph::new(actions_lines).block(
         Block::default()
             .borders(Borders::ALL)
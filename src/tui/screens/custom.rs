@@ -0,0 +1,38 @@
+//! Screens registered through `crate::tui::extensions`
+
+use crate::tui::{
+    extensions,
+    state::AppState,
+    widgets::{
+        logo::{render_logo, render_tagline, LOGO_WITH_TAGLINE_HEIGHT},
+        shortcuts::{get_shortcuts, render_shortcuts},
+    },
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app_state: &AppState, index: usize) {
+    let area = f.area();
+
+    let is_small = area.height < 20 || area.width < 60;
+    let shortcuts_height = if is_small { 2 } else { 3 };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(LOGO_WITH_TAGLINE_HEIGHT),
+            Constraint::Min(1),
+            Constraint::Length(shortcuts_height),
+        ])
+        .split(area);
+
+    render_logo(f, chunks[0]);
+    render_tagline(f, chunks[0]);
+
+    extensions::with_screen(index, |screen| screen.render(f, chunks[1], app_state));
+
+    let shortcuts = get_shortcuts(&app_state.screen, Some(app_state));
+    render_shortcuts(f, chunks[2], &shortcuts);
+}
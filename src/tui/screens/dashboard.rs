@@ -1,6 +1,8 @@
 //! Dashboard screen - category selection
 
+use crate::output::render;
 use crate::tui::{
+    extensions,
     state::AppState,
     theme::Styles,
     widgets::{
@@ -53,7 +55,7 @@ fn render_header(f: &mut Frame, area: Rect, _is_small: bool) {
 }
 
 fn render_actions(f: &mut Frame, area: Rect, app_state: &AppState) {
-    let actions = [
+    let builtin = [
         ("Scan", "Find cleanable files (safe, dry-run)"),
         ("Clean", "Delete selected files"),
         ("Analyze", "Explore disk usage (folder sizes)"),
@@ -62,9 +64,12 @@ fn render_actions(f: &mut Frame, area: Rect, app_state: &AppState) {
         ("Status", "Real-time system health dashboard"),
         ("Config", "View or modify settings"),
     ];
+    let custom = extensions::actions();
+    let actions = builtin
+        .into_iter()
+        .chain(custom.iter().map(|(a, d)| (a.as_str(), d.as_str())));
 
     let items: Vec<ListItem> = actions
-        .iter()
         .enumerate()
         .map(|(i, (action, desc))| {
             let is_selected = i == app_state.action_cursor && app_state.focus_actions;
@@ -79,9 +84,9 @@ fn render_actions(f: &mut Frame, area: Rect, app_state: &AppState) {
             // Always show full description - no truncation
             let line = Line::from(vec![
                 Span::styled(prefix, action_style),
-                Span::styled(*action, action_style),
+                Span::styled(action, action_style),
                 Span::raw("\n   "),
-                Span::styled(*desc, Styles::secondary()),
+                Span::styled(desc, Styles::secondary()),
             ]);
             ListItem::new(line)
         })
@@ -111,6 +116,139 @@ fn render_actions(f: &mut Frame, area: Rect, app_state: &AppState) {
     f.render_stateful_widget(list, area, &mut list_state);
 }
 
+/// Lifetime totals, right-aligned on the actions title row when there's room
+fn render_lifetime_badge(f: &mut Frame, area: Rect, app_state: &AppState, title_width: usize) {
+    let stats = &app_state.lifetime_stats;
+    if stats.sessions == 0 {
+        return;
+    }
+    let badge = format!(
+        "{} reclaimed · {} items · {} sessions",
+        render::format_size(stats.bytes_cleaned),
+        render::format_number(stats.items_cleaned),
+        render::format_number(stats.sessions)
+    );
+    let label = "Lifetime: ";
+    if label.len() + render::display_width(&badge) + title_width + 2 > area.width as usize {
+        return;
+    }
+    let badge = Paragraph::new(Line::from(vec![
+        Span::styled(label, Styles::secondary()),
+        Span::styled(badge, Styles::emphasis()),
+    ]))
+    .alignment(ratatui::layout::Alignment::Right);
+    f.render_widget(badge, area);
+}
+
+const STATUS_PANEL_WIDTH: u16 = 38;
+
+/// CPU, memory and per-drive usage, so disk pressure is visible before
+/// choosing what to clean
+fn render_status_panel(f: &mut Frame, area: Rect, app_state: &AppState) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Styles::border())
+        .title("STATUS");
+
+    let Some(status) = app_state.dashboard_status.status.as_deref() else {
+        let waiting = Paragraph::new(Line::from(Span::styled(
+            " Gathering system status...",
+            Styles::secondary(),
+        )))
+        .block(block);
+        f.render_widget(waiting, area);
+        return;
+    };
+
+    let health_style = match status.health_score {
+        80..=100 => Styles::success(),
+        60..=79 => Styles::warning(),
+        _ => Styles::error(),
+    };
+    let usage_style = |percent: f32| {
+        if percent > 95.0 {
+            Styles::error()
+        } else if percent > 85.0 {
+            Styles::warning()
+        } else {
+            Styles::primary()
+        }
+    };
+    let bar = |percent: f32| {
+        let filled = ((percent / 100.0).clamp(0.0, 1.0) * 10.0).round() as usize;
+        format!("{}{}", "▰".repeat(filled), "▱".repeat(10 - filled))
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" Health ", Styles::secondary()),
+            Span::styled(format!("{}/100", status.health_score), health_style),
+        ]),
+        Line::from(vec![
+            Span::styled(" CPU    ", Styles::secondary()),
+            Span::styled(
+                bar(status.cpu.total_usage),
+                usage_style(status.cpu.total_usage),
+            ),
+            Span::styled(
+                format!(" {:>3.0}%", status.cpu.total_usage),
+                Styles::primary(),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(" Memory ", Styles::secondary()),
+            Span::styled(
+                bar(status.memory.used_percent),
+                usage_style(status.memory.used_percent),
+            ),
+            Span::styled(
+                format!(
+                    " {:.1}/{:.0} GB",
+                    status.memory.used_gb, status.memory.total_gb
+                ),
+                Styles::primary(),
+            ),
+        ]),
+    ];
+
+    // Reclaimable space across recent scans, so steady bloat shows up
+    let trend = &app_state.size_trend;
+    if let (Some(&first), Some(&last)) = (trend.first(), trend.last()) {
+        if trend.len() > 1 {
+            let sign = if last >= first { "+" } else { "-" };
+            lines.push(Line::from(vec![
+                Span::styled(" Bloat  ", Styles::secondary()),
+                Span::styled(render::sparkline(trend), Styles::warning()),
+                Span::styled(
+                    format!(
+                        " {}{}/{}w",
+                        sign,
+                        render::format_size(last.abs_diff(first)),
+                        crate::tui::state::SIZE_TREND_WEEKS
+                    ),
+                    Styles::primary(),
+                ),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+
+    // Fill what's left with drives, fullest first
+    let room = area.height.saturating_sub(2) as usize;
+    let mut disks: Vec<_> = status.disks.iter().collect();
+    disks.sort_by(|a, b| b.used_percent.total_cmp(&a.used_percent));
+    for disk in disks.into_iter().take(room.saturating_sub(lines.len())) {
+        let mount: String = disk.mount_point.chars().take(6).collect();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<7}", mount), Styles::secondary()),
+            Span::styled(bar(disk.used_percent), usage_style(disk.used_percent)),
+            Span::styled(format!(" {:.0} GB free", disk.free_gb), Styles::primary()),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bool) {
     // Single column layout - flow vertically, no columns.
     //
@@ -118,8 +256,10 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
     // could consume almost the entire viewport on smaller terminals, making Categories appear
     // "empty"/broken.
     let min_categories_height: u16 = if area.height < 24 { 10 } else { 14 };
-    // Calculate exact height needed for actions: 1 (title) + 14 (7 actions × 2 lines + borders/padding)
-    let actions_height: u16 = 15; // Fixed compact height to maximize space for categories
+    // Calculate exact height needed for actions: 1 (title) + 14 (7 actions × 2 lines + borders/padding),
+    // plus a line per registered custom screen
+    let list_height = 14 + extensions::actions().len() as u16;
+    let actions_height: u16 = list_height + 1; // Fixed compact height to maximize space for categories
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -129,14 +269,26 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
         ])
         .split(area);
 
+    // Status panel beside the actions when there's room for both
+    let actions_area = if area.width >= 90 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(50), Constraint::Length(STATUS_PANEL_WIDTH)])
+            .split(chunks[0]);
+        render_status_panel(f, columns[1], app_state);
+        columns[0]
+    } else {
+        chunks[0]
+    };
+
     // Actions section with minimal spacing
     let action_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),  // Title
-            Constraint::Length(14), // Actions list - exactly 14 lines (7 actions × 2 lines + borders/padding)
+            Constraint::Length(1),           // Title
+            Constraint::Length(list_height), // Actions list
         ])
-        .split(chunks[0]);
+        .split(actions_area);
 
     let (text, style) = if let Some(msg) = &app_state.dashboard_message {
         (
@@ -151,6 +303,7 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
         .style(style)
         .alignment(ratatui::layout::Alignment::Left);
     f.render_widget(title, action_chunks[0]);
+    render_lifetime_badge(f, action_chunks[0], app_state, text.len());
 
     render_actions(f, action_chunks[1], app_state);
 
@@ -178,13 +331,18 @@ fn render_content(f: &mut Frame, area: Rect, app_state: &AppState, _is_small: bo
         match cat_name {
             "Trash" | "Temp Files" | "Browser Cache" | "Application Cache" | "System Cache"
             | "Empty Folders" => Some("A. Quick Clean (recommended)"),
-            "Build Artifacts" | "Package Cache" => Some("B. Developer Cleanup"),
+            "Build Artifacts" | "Package Cache" | "IDE Caches" | "Rust Toolchain" => {
+                Some("B. Developer Cleanup")
+            }
             "Installed Applications"
             | "Old Downloads"
             | "Large Files"
             | "Old Files"
-            | "Duplicates" => Some("C. Space Hunters (review required)"),
-            "Windows Update" | "Event Logs" => Some("D. Advanced (admin required)"),
+            | "Duplicates"
+            | "Custom Rules" => Some("C. Space Hunters (review required)"),
+            "Windows Update" | "Event Logs" | "Orphaned Owners" => {
+                Some("D. Advanced (admin required)")
+            }
             _ => None,
         }
     }
@@ -1,12 +1,13 @@
 //! Disk Insights screen - interactive folder navigation
 
-use crate::disk_usage::{find_folder_by_path, SortBy};
+use crate::disk_usage::find_folder_by_path;
 use crate::tui::{
     state::AppState,
     theme::Styles,
     widgets::{
         logo::{render_logo, render_tagline, LOGO_WITH_TAGLINE_HEIGHT},
         shortcuts::{get_shortcuts, render_shortcuts},
+        table::{sort_rows, totals_footer, TableSort},
     },
 };
 use bytesize::to_string as bytesize_to_string;
@@ -43,13 +44,23 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         };
 
     let shortcuts_height = 3;
+    // The header gets a second line for the breakdown of the current folder
+    let header_height = if find_folder_by_path(&insights_clone.root, &current_path_clone)
+        .unwrap_or(&insights_clone.root)
+        .breakdown_summary(1)
+        .is_some()
+    {
+        4
+    } else {
+        3
+    };
 
     // Layout: logo, header, search bar, content, shortcuts
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(LOGO_WITH_TAGLINE_HEIGHT), // Logo + tagline
-            Constraint::Length(3),                        // Header
+            Constraint::Length(header_height),            // Header
             Constraint::Length(3),                        // Search bar
             Constraint::Min(1),                           // Content
             Constraint::Length(shortcuts_height),
@@ -66,10 +77,21 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
     // Render search bar
     render_search_bar(f, chunks[2], app_state);
 
-    // Render content
+    // Render content, with the largest files beside it when toggled on (L)
+    let content_area =
+        if app_state.insights_largest_files && !insights_clone.largest_files.is_empty() {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(chunks[3]);
+            render_largest_files(f, panes[1], &insights_clone);
+            panes[0]
+        } else {
+            chunks[3]
+        };
     render_content(
         f,
-        chunks[3],
+        content_area,
         &insights_clone,
         &current_path_clone,
         cursor,
@@ -113,19 +135,39 @@ fn render_header(
     // Find current folder node
     let current_node = find_folder_by_path(&insights.root, current_path).unwrap_or(&insights.root);
 
-    let header_text = format!(
+    let mut header_text = format!(
         "{}  |  Total: {}  |  {} files",
         breadcrumb_str,
         bytesize_to_string(current_node.size, true),
         format_number(current_node.file_count)
     );
+    // Volumes mounted directly in this folder aren't part of its total
+    let mounted = insights
+        .mounted_volumes
+        .iter()
+        .filter(|m| m.parent() == Some(current_path))
+        .count();
+    if mounted > 0 {
+        header_text.push_str(&format!(
+            "  |  {} mounted volume{} not counted",
+            mounted,
+            if mounted == 1 { "" } else { "s" }
+        ));
+    }
 
-    let header = Paragraph::new(Line::from(vec![
+    let mut lines = vec![Line::from(vec![
         Span::styled("Disk Insights", Styles::header()),
         Span::raw("  "),
         Span::styled(&header_text, Styles::secondary()),
-    ]))
-    .block(
+    ])];
+    if let Some(summary) = current_node.breakdown_summary(4) {
+        lines.push(Line::from(vec![
+            Span::styled("What's taking space here: ", Styles::secondary()),
+            Span::styled(summary, Styles::emphasis()),
+        ]));
+    }
+
+    let header = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Styles::border()),
@@ -166,7 +208,7 @@ fn render_content(
     insights: &crate::disk_usage::DiskInsights,
     current_path: &std::path::Path,
     cursor: usize,
-    sort_by: SortBy,
+    sort_by: TableSort,
     selected_paths: &std::collections::HashSet<std::path::PathBuf>,
     app_state: &AppState,
 ) {
@@ -184,22 +226,9 @@ fn render_content(
         files.retain(|file| file.name.to_lowercase().contains(&query));
     }
 
-    // Sort children folders
-    match sort_by {
-        SortBy::Size => children.sort_by_key(|b| std::cmp::Reverse(b.size)),
-        SortBy::Name => children.sort_by(|a, b| a.name.cmp(&b.name)),
-        SortBy::Files => children.sort_by_key(|b| std::cmp::Reverse(b.file_count)),
-    }
-
-    // Sort files
-    match sort_by {
-        SortBy::Size => files.sort_by_key(|b| std::cmp::Reverse(b.size)),
-        SortBy::Name => files.sort_by(|a, b| a.name.cmp(&b.name)),
-        SortBy::Files => {
-            // For files, Files sort doesn't make sense, so use size
-            files.sort_by_key(|b| std::cmp::Reverse(b.size));
-        }
-    }
+    // Sort folders and files
+    sort_rows(&mut children, sort_by);
+    sort_rows(&mut files, sort_by);
 
     // Combine folders and files into a single list for display
     // Folders come first, then files
@@ -374,11 +403,22 @@ fn render_content(
         "Empty"
     };
 
+    // Totals footer for what's listed (follows the filter)
+    let listed_size: u64 =
+        children.iter().map(|c| c.size).sum::<u64>() + files.iter().map(|f| f.size).sum::<u64>();
+    let footer = totals_footer(
+        total_items,
+        listed_size,
+        sort_by,
+        !app_state.search_query.is_empty(),
+    );
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Styles::border())
-            .title(title),
+            .title(title)
+            .title_bottom(footer),
     );
 
     let mut list_state = ratatui::widgets::ListState::default();
@@ -387,6 +427,33 @@ fn render_content(
     f.render_stateful_widget(list, area, &mut list_state);
 }
 
+/// The largest files anywhere under the scan root
+fn render_largest_files(f: &mut Frame, area: Rect, insights: &crate::disk_usage::DiskInsights) {
+    let root = &insights.root.path;
+    let items: Vec<ListItem> = insights
+        .largest_files
+        .iter()
+        .map(|(path, size)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:>10}", bytesize_to_string(*size, true)),
+                    Styles::emphasis(),
+                ),
+                Span::raw("  "),
+                Span::raw(crate::utils::to_relative_path(path, root)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Styles::border())
+            .title("Largest Files"),
+    );
+    f.render_widget(list, area);
+}
+
 fn format_number(n: u64) -> String {
     let s = n.to_string();
     let mut result = String::new();
@@ -0,0 +1,195 @@
+//! History screen - browse past cleanup sessions
+
+use crate::tui::{
+    state::AppState,
+    theme::Styles,
+    widgets::{
+        logo::{render_logo, render_tagline, LOGO_WITH_TAGLINE_HEIGHT},
+        shortcuts::{get_shortcuts, render_shortcuts},
+    },
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub fn render(f: &mut Frame, app_state: &AppState) {
+    let area = f.area();
+
+    let is_small = area.height < 20 || area.width < 60;
+    let shortcuts_height = if is_small { 2 } else { 3 };
+
+    // Layout: header, session summary, deletions, shortcuts
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(LOGO_WITH_TAGLINE_HEIGHT),
+            Constraint::Length(5),
+            Constraint::Min(3),
+            Constraint::Length(shortcuts_height),
+        ])
+        .split(area);
+
+    render_logo(f, chunks[0]);
+    render_tagline(f, chunks[0]);
+
+    if let crate::tui::state::Screen::History {
+        ref sessions,
+        index,
+        ref log,
+        cursor,
+        ref message,
+    } = app_state.screen
+    {
+        render_summary(f, chunks[1], sessions.len(), index, log.as_ref(), message);
+        render_records(f, chunks[2], app_state, log.as_ref(), cursor);
+    }
+
+    let shortcuts = get_shortcuts(&app_state.screen, Some(app_state));
+    render_shortcuts(f, chunks[3], &shortcuts);
+}
+
+fn render_summary(
+    f: &mut Frame,
+    area: Rect,
+    total: usize,
+    index: usize,
+    log: Option<&crate::history::DeletionLog>,
+    message: &Option<String>,
+) {
+    let mut lines = Vec::new();
+    match log {
+        Some(log) => {
+            let when = log
+                .session_start
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M");
+            lines.push(Line::from(vec![
+                Span::styled("  Session ", Styles::secondary()),
+                Span::styled(when.to_string(), Styles::emphasis()),
+                Span::styled(
+                    format!("  ({} of {}, newest first)", index + 1, total),
+                    Styles::secondary(),
+                ),
+            ]));
+            let errors_style = if log.errors > 0 {
+                Styles::warning()
+            } else {
+                Styles::success()
+            };
+            lines.push(Line::from(vec![
+                Span::styled("  Deleted ", Styles::secondary()),
+                Span::styled(
+                    format!("{}", log.total_items - log.errors),
+                    Styles::emphasis(),
+                ),
+                Span::styled(" items · freed ", Styles::secondary()),
+                Span::styled(
+                    bytesize::to_string(log.total_bytes_cleaned, false),
+                    Styles::emphasis(),
+                ),
+                Span::styled(" · failed ", Styles::secondary()),
+                Span::styled(format!("{}", log.errors), errors_style),
+            ]));
+        }
+        None if total == 0 => lines.push(Line::from(Span::styled(
+            "  No cleanup sessions yet",
+            Styles::secondary(),
+        ))),
+        None => lines.push(Line::from(Span::styled(
+            "  This session's log couldn't be read",
+            Styles::warning(),
+        ))),
+    }
+    if let Some(message) = message {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", message),
+            Styles::emphasis(),
+        )));
+    }
+
+    let summary = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Styles::border())
+            .title("HISTORY"),
+    );
+    f.render_widget(summary, area);
+}
+
+fn render_records(
+    f: &mut Frame,
+    area: Rect,
+    app_state: &AppState,
+    log: Option<&crate::history::DeletionLog>,
+    cursor: usize,
+) {
+    let records = log.map(|log| log.records.as_slice()).unwrap_or_default();
+    let height = area.height.saturating_sub(2) as usize;
+    let offset = cursor.saturating_sub(height.saturating_sub(1));
+    let width = area.width.saturating_sub(2) as usize;
+
+    let lines: Vec<Line> = records
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, record)| {
+            let (mark, mark_style) = if record.success {
+                ("✓", Styles::success())
+            } else {
+                ("✗", Styles::error())
+            };
+            let how = if record.permanent {
+                "permanent"
+            } else {
+                "recycled"
+            };
+            let detail = if record.success {
+                how.to_string()
+            } else {
+                record.error.clone().unwrap_or_else(|| "failed".to_string())
+            };
+            let size = bytesize::to_string(record.size_bytes, false);
+            let path = crate::utils::to_relative_path(
+                std::path::Path::new(&record.path),
+                &app_state.scan_path,
+            );
+            let fixed = 4 + 11 + 3 + detail.chars().count();
+            let path = truncate_start(&path, width.saturating_sub(fixed).max(8));
+
+            let row_style = if i == cursor {
+                Styles::selected()
+            } else {
+                Styles::primary()
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", mark), mark_style),
+                Span::styled(format!("{:>10} ", size), Styles::secondary()),
+                Span::styled(path, row_style),
+                Span::styled(format!("  {}", detail), Styles::muted()),
+            ])
+        })
+        .collect();
+
+    let title = format!("DELETIONS ({})", records.len());
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Styles::border())
+            .title(title),
+    );
+    f.render_widget(list, area);
+}
+
+/// Keep the end of a long path, which is the part that tells items apart
+fn truncate_start(text: &str, max: usize) -> String {
+    let count = text.chars().count();
+    if count <= max {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(count - max.saturating_sub(3)).collect();
+    format!("...{}", tail)
+}
@@ -2,8 +2,10 @@
 
 pub mod config;
 pub mod confirm;
+pub mod custom;
 pub mod dashboard;
 pub mod disk_insights;
+pub mod history;
 pub mod optimize;
 pub mod preview;
 pub mod restore;
@@ -34,5 +36,7 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         crate::tui::state::Screen::DiskInsights { .. } => disk_insights::render(f, app_state),
         crate::tui::state::Screen::Optimize { .. } => optimize::render(f, app_state),
         crate::tui::state::Screen::Status { .. } => status::render(f, app_state),
+        crate::tui::state::Screen::History { .. } => history::render(f, app_state),
+        crate::tui::state::Screen::Custom { index } => custom::render(f, app_state, index),
     }
 }
@@ -15,7 +15,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn render(f: &mut Frame, app_state: &AppState) {
     let area = f.area();
@@ -97,7 +97,13 @@ pub fn render(f: &mut Frame, app_state: &AppState) {
         );
 
         // Right: Will Delete preview
-        render_delete_preview(f, split_chunks[1], item, &app_state.scan_path);
+        render_delete_preview(
+            f,
+            split_chunks[1],
+            item,
+            &app_state.scan_path,
+            &app_state.preview_largest,
+        );
     }
 
     // Shortcuts
@@ -110,6 +116,7 @@ fn render_delete_preview(
     area: Rect,
     item: &crate::tui::state::ResultItem,
     base_path: &Path,
+    largest: &[(PathBuf, u64)],
 ) {
     let path_display = crate::utils::to_relative_path(&item.path, base_path);
     let path_truncated = if path_display.len() > 50 {
@@ -118,7 +125,7 @@ fn render_delete_preview(
         path_display
     };
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![Span::styled(
             "THIS FILE WILL BE DELETED:",
             Styles::danger(),
@@ -157,13 +164,48 @@ fn render_delete_preview(
                 },
             ),
         ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  ⚠ Remember: [D] deletes ALL selected files, not just this one",
-            Styles::warning(),
-        )]),
     ];
 
+    // What it takes to get build artifacts and package caches back
+    if item.category == "Build Artifacts" || item.category == "Package Cache" {
+        if let Some(cost) = crate::recreate_cost::estimate(&item.path, item.size_bytes) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("  Recreate: ", Styles::header()),
+                Span::styled(cost.describe(), Styles::secondary()),
+            ]));
+        }
+    }
+
+    // For folders, show what dominates their size
+    if !largest.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "  Largest files inside:",
+            Styles::header(),
+        )]));
+        for (path, size) in largest {
+            let name = path
+                .strip_prefix(&item.path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:>10}  ", bytesize::to_string(*size, false)),
+                    Styles::emphasis(),
+                ),
+                Span::styled(truncate_name(&name, 40), Styles::secondary()),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  ⚠ Remember: [D] deletes ALL selected files, not just this one",
+        Styles::warning(),
+    )]));
+
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -174,3 +216,14 @@ fn render_delete_preview(
 
     f.render_widget(paragraph, area);
 }
+
+/// Keep the tail of long names (the file name is the informative part)
+fn truncate_name(name: &str, max_chars: usize) -> String {
+    let count = name.chars().count();
+    if count <= max_chars {
+        name.to_string()
+    } else {
+        let tail: String = name.chars().skip(count - (max_chars - 3)).collect();
+        format!("...{}", tail)
+    }
+}
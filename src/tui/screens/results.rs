@@ -1,11 +1,12 @@
 //! Results screen with grouped categories
 
 use crate::tui::{
-    state::AppState,
+    state::{AppState, ResultItem},
     theme::Styles,
     widgets::{
         logo::{render_logo, render_tagline, LOGO_WITH_TAGLINE_HEIGHT},
         shortcuts::{get_shortcuts, render_shortcuts},
+        table::{SortColumn, TableSort},
     },
 };
 use crate::utils::{detect_file_type, FileType};
@@ -53,7 +54,36 @@ fn truncate_end(s: &str, max_chars: usize) -> String {
 /// - "/type:image dynamics" -> "dynamics"
 /// - "type:.jpg dyn" -> "dyn"
 /// - "dynamics" -> "dynamics"
+/// - "*acme-*.log" -> "acme-" (globs highlight their longest literal part)
 fn highlight_text_query(search_query: &str) -> String {
+    let text = search_text_query(search_query);
+    if !text.contains(crate::tui::state::GLOB_CHARS) {
+        return text;
+    }
+    let (mut longest, mut current, mut in_class) = (String::new(), String::new(), false);
+    for c in text.chars() {
+        match c {
+            '[' => in_class = true,
+            ']' if in_class => in_class = false,
+            '*' | '?' => {}
+            c if !in_class => {
+                current.push(c);
+                continue;
+            }
+            _ => continue,
+        }
+        if current.len() > longest.len() {
+            longest = std::mem::take(&mut current);
+        }
+        current.clear();
+    }
+    if current.len() > longest.len() {
+        longest = current;
+    }
+    longest
+}
+
+fn search_text_query(search_query: &str) -> String {
     let query = search_query.trim();
     if query.is_empty() {
         return String::new();
@@ -171,6 +201,10 @@ fn category_emoji(category_name: &str) -> &'static str {
         "Duplicates" => "📋",
         "Windows Update" => "🔄",
         "Event Logs" => "📋",
+        "Orphaned Owners" => "👤",
+        "Custom Rules" => "📝",
+        "IDE Caches" => "🧰",
+        "Rust Toolchain" => "🦀",
         _ => "📁", // Default folder emoji
     }
 }
@@ -221,6 +255,48 @@ fn folder_emoji(app_state: &AppState, folder: &crate::tui::state::FolderGroup) -
     }
 }
 
+/// Width of the date column
+const DATE_WIDTH: usize = 16;
+
+/// Width of the hint column (Large Files: file type or in-use status)
+const HINT_WIDTH: usize = 14;
+
+/// Hint column of an item, in `warning` style when the file is in use
+fn hint_span(item: &ResultItem, base: Style, warning: Style) -> Span<'static> {
+    match &item.hint {
+        Some(hint) => Span::styled(
+            format!(
+                " | {:>width$}",
+                truncate_end(hint, HINT_WIDTH),
+                width = HINT_WIDTH
+            ),
+            if item.in_use { warning } else { base },
+        ),
+        None => Span::raw(""),
+    }
+}
+
+/// Characters the hint column takes in an item's row
+fn hint_width(item: &ResultItem) -> usize {
+    if item.hint.is_some() {
+        3 + HINT_WIDTH
+    } else {
+        0
+    }
+}
+
+/// Date column of an item: when an application was last opened; otherwise
+/// when it was last modified if the list is sorted by that, or last used
+fn item_date(item: &ResultItem, sort: TableSort) -> String {
+    if item.category == "Installed Applications" {
+        format_date(item.last_opened)
+    } else if sort.column == SortColumn::Modified {
+        format!("edited {}", format_date(item.modified))
+    } else {
+        format!("used {}", format_date(item.accessed))
+    }
+}
+
 fn format_date(t: Option<SystemTime>) -> String {
     let Some(t) = t else {
         return "--".to_string();
@@ -378,7 +454,7 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         8
     } else {
         5
-    };
+    } + u16::from(app_state.elevation_hint.is_some());
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -415,6 +491,21 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         Span::styled("Categories: ", Styles::secondary()),
         Span::styled(format!("{}", categories_count), Styles::emphasis()),
     ])];
+    let (excluded_count, excluded_bytes) = app_state.excluded_summary();
+    if excluded_count > 0 {
+        summary_lines[0].spans.extend([
+            Span::styled(" │ ", Styles::secondary()),
+            Span::styled("Excluded: ", Styles::secondary()),
+            Span::styled(
+                format!(
+                    "{} ({})",
+                    excluded_count,
+                    bytesize::to_string(excluded_bytes, false)
+                ),
+                Styles::warning(),
+            ),
+        ]);
+    }
 
     // Second line: storage info or free space and fun comparison
     let mut line2_spans = vec![Span::styled("  ", Styles::secondary())];
@@ -496,6 +587,29 @@ pub fn render(f: &mut Frame, app_state: &mut AppState) {
         }
     }
 
+    if app_state.scan_results.as_ref().is_some_and(|r| r.cancelled) {
+        summary_lines.push(Line::from(vec![Span::styled(
+            "  ⚠ Scan stopped early: these are partial results",
+            Styles::warning(),
+        )]));
+    }
+
+    // Scan skipped protected folders: offer to relaunch elevated
+    if let Some(ref hint) = app_state.elevation_hint {
+        let line = match app_state.elevation_notice {
+            Some(ref notice) => Line::from(vec![Span::styled(
+                format!("  ⚠ {}", notice),
+                Styles::warning(),
+            )]),
+            None => Line::from(vec![
+                Span::styled(format!("  ⚠ {} │ ", hint.summary()), Styles::warning()),
+                Span::styled("[A]", Styles::emphasis()),
+                Span::styled(" relaunch as admin to include them", Styles::secondary()),
+            ]),
+        };
+        summary_lines.push(line);
+    }
+
     summary_lines.push(Line::from(""));
     summary_lines.push(Line::from(vec![
         Span::styled("  Press ", Styles::secondary()),
@@ -631,7 +745,8 @@ fn render_search_bar(f: &mut Frame, area: Rect, app_state: &AppState) {
     let search_text = if app_state.search_mode {
         format!("/ {}_", app_state.search_query) // Cursor indicator
     } else if app_state.search_query.is_empty() {
-        "Press / to filter results... Use /type:image, /type:.jpg, etc.".to_string()
+        "Press / to filter results... Use text, globs (*acme*, *.log), /type:image, /type:.jpg, etc."
+            .to_string()
     } else {
         let mut filter_text = String::new();
         let has_extension_filter = extension_filter.is_some();
@@ -640,7 +755,9 @@ fn render_search_bar(f: &mut Frame, area: Rect, app_state: &AppState) {
         } else if let Some(file_type) = type_filter {
             filter_text.push_str(&format!("Type: {} ", file_type.as_str()));
         }
-        if !text_query.is_empty() {
+        if text_query.contains(crate::tui::state::GLOB_CHARS) {
+            filter_text.push_str(&format!("Glob: {}", text_query));
+        } else if !text_query.is_empty() {
             filter_text.push_str(&format!("Text: {}", text_query));
         } else if has_extension_filter || type_filter.is_some() {
             filter_text.push_str("(all matching)");
@@ -734,10 +851,55 @@ fn match_file_type_string(type_str: &str) -> Option<crate::utils::FileType> {
 }
 
 fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
-    let block = Block::default()
+    // Totals footer follows the active search filter
+    let (visible_count, visible_size) = app_state.visible_totals();
+    let footer = crate::tui::widgets::table::totals_footer(
+        visible_count,
+        visible_size,
+        app_state.results_sort,
+        !app_state.search_query.trim().is_empty() || app_state.volume_filter.is_some(),
+    );
+    let view = if app_state.results_flat {
+        "ALL ITEMS"
+    } else {
+        "CATEGORIES"
+    };
+    let title = match &app_state.volume_filter {
+        Some(volume) => format!("{} · {}", view, volume),
+        None => view.to_string(),
+    };
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(Styles::border())
-        .title("CATEGORIES");
+        .title(title)
+        .title_bottom(footer);
+
+    // Per-drive subtotals when results span several drives
+    let volumes = app_state.result_volumes();
+    if volumes.len() > 1 {
+        let mut spans = Vec::new();
+        for (i, (volume, count, size)) in volumes.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" · ", Styles::secondary()));
+            }
+            let style = if app_state.volume_filter.as_ref() == Some(volume) {
+                Styles::emphasis()
+            } else {
+                Styles::secondary()
+            };
+            spans.push(Span::styled(
+                format!(
+                    "{} {} ({})",
+                    volume,
+                    bytesize::to_string(*size, false),
+                    count
+                ),
+                style,
+            ));
+        }
+        spans.push(Span::raw(" "));
+        block = block.title_top(Line::from(spans).right_aligned());
+    }
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -810,11 +972,7 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                             crate::utils::to_relative_path(&item.path, &app_state.scan_path)
                         };
                         let size_str = bytesize::to_string(item.size_bytes, false);
-                        let date_str = if item.category == "Installed Applications" {
-                            Some(format_date(item.last_opened))
-                        } else {
-                            None
-                        };
+                        let date_str = Some(item_date(item, app_state.results_sort));
                         let indent = if app_state.category_groups.len() > 1 {
                             "    "
                         } else {
@@ -826,9 +984,13 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
 
                         // Calculate fixed widths for metadata columns
                         // Size column: 8 chars (e.g., "793.7 MiB")
-                        // Date column: 3 chars (" | ") + up to 10 chars (e.g., "yesterday", "2mo ago")
-                        let date_width = if date_str.is_some() { 3 + 10 } else { 0 };
-                        let metadata_width = 8 + date_width;
+                        // Date column: 3 chars (" | ") + up to 16 chars (e.g., "used yesterday", "edited 2mo ago")
+                        let date_width = if date_str.is_some() {
+                            3 + DATE_WIDTH
+                        } else {
+                            0
+                        };
+                        let metadata_width = 8 + date_width + hint_width(item);
 
                         let fixed_prefix = indent.len()
                             + 3 /*checkbox*/
@@ -868,10 +1030,14 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                         spans.extend([
                             Span::styled(format!("{:>8}", size_str), Styles::secondary()),
                             if let Some(date) = date_str {
-                                Span::styled(format!(" | {:>10}", date), Styles::secondary())
+                                Span::styled(
+                                    format!(" | {:>width$}", date, width = DATE_WIDTH),
+                                    Styles::secondary(),
+                                )
                             } else {
                                 Span::raw("")
                             },
+                            hint_span(item, Styles::secondary(), Styles::warning()),
                         ]);
                         lines.push(Line::from(spans));
                     }
@@ -952,6 +1118,14 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                 let total_in_group = item_indices.len();
 
                 let (checkbox, checkbox_style) = tri_checkbox(selected_in_group, total_in_group);
+                // Sizes shown leave out what's excluded from this clean
+                let excluded_in_group = app_state.excluded_size(
+                    group
+                        .items
+                        .iter()
+                        .chain(group.folder_groups.iter().flat_map(|fg| fg.items.iter()))
+                        .copied(),
+                );
                 let exp_marker = if group.expanded || !app_state.search_query.is_empty() {
                     "▾"
                 } else {
@@ -969,7 +1143,10 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                     ),
                     Span::styled(format!("{:<12}", group.name), apply_sel(Styles::emphasis())),
                     Span::styled(
-                        format!("{:>8}", bytesize::to_string(group.total_size, false)),
+                        format!(
+                            "{:>8}",
+                            bytesize::to_string(group.total_size - excluded_in_group, false)
+                        ),
                         apply_sel(Styles::primary()),
                     ),
                     Span::styled("    ", apply_sel(Styles::secondary())),
@@ -1043,7 +1220,10 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                         }
                     }
                 }
-                let size_str = bytesize::to_string(folder.total_size, false);
+                let size_str = bytesize::to_string(
+                    folder.total_size - app_state.excluded_size(folder.items.iter().copied()),
+                    false,
+                );
                 let folder_emoji_icon = folder_emoji(app_state, folder);
 
                 // Indent folder headers by nesting depth.
@@ -1144,11 +1324,7 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                 };
 
                 let size_str = bytesize::to_string(item.size_bytes, false);
-                let date_str = if item.category == "Installed Applications" {
-                    Some(format_date(item.last_opened))
-                } else {
-                    None
-                };
+                let date_str = Some(item_date(item, app_state.results_sort));
 
                 // Add emoji based on file type
                 let file_type = detect_file_type(&item.path);
@@ -1156,9 +1332,13 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
 
                 // Calculate fixed widths for metadata columns
                 // Size column: 2 spaces + 8 chars (e.g., "793.7 MiB")
-                // Date column: 3 chars (" | ") + up to 10 chars (e.g., "yesterday", "2mo ago")
-                let date_width = if date_str.is_some() { 3 + 10 } else { 0 };
-                let metadata_width = 2 + 8 + date_width;
+                // Date column: 3 chars (" | ") + up to 16 chars (e.g., "used yesterday", "edited 2mo ago")
+                let date_width = if date_str.is_some() {
+                    3 + DATE_WIDTH
+                } else {
+                    0
+                };
+                let metadata_width = 2 + 8 + date_width + hint_width(item);
 
                 let fixed_prefix = indent.len()
                     + 3 /*prefix+spaces*/
@@ -1185,7 +1365,19 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                 } else {
                     row_style
                 };
+                // Struck through while left out of this clean
+                let excluded = app_state.is_excluded(item_idx);
+                let path_style = if excluded {
+                    path_style.add_modifier(Modifier::CROSSED_OUT | Modifier::DIM)
+                } else {
+                    path_style
+                };
                 let path_hl_style = path_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                let size_style = if excluded {
+                    Styles::secondary().add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    Styles::secondary()
+                };
 
                 let mut item_spans = vec![
                     Span::styled(format!("{}{} ", indent, prefix), row_style),
@@ -1200,12 +1392,20 @@ fn render_grouped_results(f: &mut Frame, area: Rect, app_state: &mut AppState) {
                     path_hl_style,
                 ));
                 item_spans.extend([
-                    Span::styled(format!("  {:>8}", size_str), apply_sel(Styles::secondary())),
+                    Span::styled(format!("  {:>8}", size_str), apply_sel(size_style)),
                     if let Some(date) = date_str {
-                        Span::styled(format!(" | {:>10}", date), apply_sel(Styles::secondary()))
+                        Span::styled(
+                            format!(" | {:>width$}", date, width = DATE_WIDTH),
+                            apply_sel(Styles::secondary()),
+                        )
                     } else {
                         Span::raw("")
                     },
+                    hint_span(
+                        item,
+                        apply_sel(Styles::secondary()),
+                        apply_sel(Styles::warning()),
+                    ),
                 ]);
                 lines.push(Line::from(item_spans));
             }
@@ -69,18 +69,18 @@ pub fn render(f: &mut Frame, app_state: &AppState) {
         }
     );
 
-    // Adjust constraints for small viewports
-    let status_height = if is_small {
-        if has_notice {
-            3
-        } else {
-            2
+    let has_hashing = matches!(
+        app_state.screen,
+        crate::tui::state::Screen::Scanning {
+            progress: crate::tui::state::ScanProgress {
+                hashing: Some(_),
+                ..
+            }
         }
-    } else if has_notice {
-        4
-    } else {
-        3
-    };
+    );
+
+    // Adjust constraints for small viewports
+    let status_height = if is_small { 2 } else { 3 } + has_notice as u16 + has_hashing as u16;
     let shortcuts_height = if is_small { 2 } else { 3 };
     let min_progress_height = if is_small { 3 } else { 8 };
 
@@ -181,6 +181,29 @@ pub fn render(f: &mut Frame, app_state: &AppState) {
                 Styles::secondary(),
             )]));
         }
+        if let Some(ref hashing) = progress.hashing {
+            let mut text = format!(
+                "Hashing {}/{} files │ {} of {}",
+                hashing.files_hashed,
+                hashing.files_total,
+                bytesize::to_string(hashing.bytes_hashed, false),
+                bytesize::to_string(hashing.bytes_total, false)
+            );
+            if let Some(eta) = hashing
+                .eta()
+                .filter(|_| hashing.files_hashed < hashing.files_total)
+            {
+                let secs = eta.as_secs();
+                text.push_str(&if secs < 60 {
+                    format!(" │ ~{}s left", secs)
+                } else if secs < 3600 {
+                    format!(" │ ~{}m {}s left", secs / 60, secs % 60)
+                } else {
+                    format!(" │ ~{}h {}m left", secs / 3600, (secs % 3600) / 60)
+                });
+            }
+            status_lines.push(Line::from(vec![Span::styled(text, Styles::secondary())]));
+        }
         // Use simpler borders on small viewports to avoid rendering issues
         let borders = if is_small {
             Borders::TOP | Borders::BOTTOM
@@ -375,16 +375,22 @@ pub fn render(f: &mut Frame, app_state: &AppState) {
         vec![Line::from(vec![
             Span::styled("  Press ", Styles::secondary()),
             Span::styled("[Esc] or [B]", Styles::emphasis()),
+            Span::styled(" to return to results, ", Styles::secondary()),
+            Span::styled("[H]", Styles::emphasis()),
             Span::styled(
-                " to return to results, or any other key for dashboard",
+                " to see this clean in the history, or any other key for dashboard",
                 Styles::secondary(),
             ),
         ])]
     } else {
-        vec![Line::from(vec![Span::styled(
-            "  Press any key to return to dashboard...",
-            Styles::secondary(),
-        )])]
+        vec![Line::from(vec![
+            Span::styled("  Press ", Styles::secondary()),
+            Span::styled("[H]", Styles::emphasis()),
+            Span::styled(
+                " to see this clean in the history, or any other key to return to dashboard...",
+                Styles::secondary(),
+            ),
+        ])]
     };
 
     let message = Paragraph::new(message_text).block(
@@ -83,6 +83,14 @@ pub enum ConfirmRow {
     Spacer,
 }
 
+/// Built-in dashboard actions (Scan through Config); screens registered
+/// through `crate::tui::extensions` are listed after them
+pub const BUILTIN_ACTIONS: usize = 7;
+
+/// Weeks and points of the reclaimable-space sparkline on the dashboard
+pub const SIZE_TREND_WEEKS: i64 = 8;
+pub const SIZE_TREND_POINTS: usize = 14;
+
 /// Current screen being displayed
 #[derive(Debug)]
 pub enum Screen {
@@ -119,7 +127,7 @@ pub enum Screen {
         insights: crate::disk_usage::DiskInsights,
         current_path: PathBuf,
         cursor: usize,
-        sort_by: crate::disk_usage::SortBy,
+        sort_by: crate::tui::widgets::table::TableSort,
         selected_paths: std::collections::HashSet<PathBuf>,
     },
     Optimize {
@@ -135,6 +143,18 @@ pub enum Screen {
         status_receiver:
             Option<std::sync::mpsc::Receiver<anyhow::Result<crate::status::SystemStatus>>>,
     },
+    /// Past cleanup sessions, one at a time, newest first
+    History {
+        sessions: Vec<PathBuf>,                   // history logs, newest first
+        index: usize,                             // session shown
+        log: Option<crate::history::DeletionLog>, // None when the log couldn't be read
+        cursor: usize,
+        message: Option<String>,
+    },
+    /// A screen registered through `crate::tui::extensions`
+    Custom {
+        index: usize,
+    },
 }
 
 impl Clone for Screen {
@@ -210,6 +230,20 @@ impl Clone for Screen {
                 // Receiver cannot be cloned, so set to None
                 status_receiver: None,
             },
+            Screen::History {
+                sessions,
+                index,
+                log,
+                cursor,
+                message,
+            } => Screen::History {
+                sessions: sessions.clone(),
+                index: *index,
+                log: log.clone(),
+                cursor: *cursor,
+                message: message.clone(),
+            },
+            Screen::Custom { index } => Screen::Custom { index: *index },
         }
     }
 }
@@ -235,6 +269,38 @@ pub struct ScanProgress {
     pub total_found: usize,
     pub total_size: u64,
     pub start_time: std::time::Instant,
+    /// Duplicate hashing progress, once hashing has started
+    pub hashing: Option<HashingProgress>,
+    /// Esc was pressed: the scan is stopping and its partial results will be shown
+    pub stopping: bool,
+}
+
+/// Files and bytes hashed by the duplicates scan
+#[derive(Debug, Clone)]
+pub struct HashingProgress {
+    pub files_hashed: u64,
+    pub files_total: u64,
+    pub bytes_hashed: u64,
+    pub bytes_total: u64,
+    /// When hashing started and how much was already done then (resumed from
+    /// an earlier scan), for the ETA
+    pub started: std::time::Instant,
+    pub started_bytes: u64,
+}
+
+impl HashingProgress {
+    /// Estimated time left at the rate hashed so far in this scan
+    pub fn eta(&self) -> Option<std::time::Duration> {
+        let hashed = self.bytes_hashed.saturating_sub(self.started_bytes);
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if hashed == 0 || elapsed < 1.0 {
+            return None;
+        }
+        let left = self.bytes_total.saturating_sub(self.bytes_hashed) as f64;
+        Some(std::time::Duration::from_secs_f64(
+            left / (hashed as f64 / elapsed),
+        ))
+    }
 }
 
 /// Progress for a single category during scan
@@ -246,6 +312,28 @@ pub struct CategoryProgress {
     pub size: Option<u64>,
 }
 
+/// Typed confirmation in progress on the Confirm screen (large deletions)
+#[derive(Debug, Clone)]
+pub struct TypedConfirm {
+    /// What the user has to type (the total size)
+    pub phrase: String,
+    pub input: String,
+    /// Delete mode chosen with Y or P
+    pub permanent: bool,
+}
+
+/// Files and folders sent to the Confirm screen from Disk Insights
+#[derive(Debug)]
+pub struct InsightsDelete {
+    /// The Disk Insights screen to return to afterwards
+    pub screen: Screen,
+    pub targets: Vec<PathBuf>,
+    /// Index in `all_items` where the appended target items start
+    pub first_item: usize,
+    /// Results selection set aside while the targets are confirmed
+    pub selection: HashSet<usize>,
+}
+
 /// Progress tracking for cleaning
 #[derive(Debug, Clone)]
 pub struct CleanProgress {
@@ -339,6 +427,20 @@ pub const CATEGORIES: &[CategoryDef] = &[
         description:
             "npm/pnpm/Cargo/Playwright; ~/.cache; Claude ~/.claude projects/plans/file-history/tasks",
     },
+    CategoryDef {
+        name: "IDE Caches",
+        scan_field: "ide",
+        safe: true,
+        default_enabled: false,
+        description: "JetBrains/Android Studio caches, VS Code VSIX cache, unused Android system images, Xcode DerivedData",
+    },
+    CategoryDef {
+        name: "Rust Toolchain",
+        scan_field: "rust",
+        safe: true,
+        default_enabled: false,
+        description: "Cargo registry crates and rustup toolchains unused for 90+ days; sccache cache",
+    },
     // C. Space Hunters (review required, biggest wins)
     CategoryDef {
         name: "Installed Applications",
@@ -375,6 +477,13 @@ pub const CATEGORIES: &[CategoryDef] = &[
         default_enabled: false,
         description: "Identical file copies",
     },
+    CategoryDef {
+        name: "Custom Rules",
+        scan_field: "custom",
+        safe: false,
+        default_enabled: false,
+        description: "Your own or imported BleachBit/CCleaner rules",
+    },
     // D. Advanced (admin / system)
     CategoryDef {
         name: "Windows Update",
@@ -390,6 +499,13 @@ pub const CATEGORIES: &[CategoryDef] = &[
         default_enabled: false,
         description: "System event logs (requires admin)",
     },
+    CategoryDef {
+        name: "Orphaned Owners",
+        scan_field: "orphaned",
+        safe: false,
+        default_enabled: false,
+        description: "Files owned by deleted or unknown accounts",
+    },
 ];
 
 /// Category selection state
@@ -470,7 +586,10 @@ fn dashboard_order_index(category_name: &str) -> usize {
 /// Returns: 1 = Review (biggest wins), 2 = Safe, 3 = Admin/System
 fn results_group_priority(category_name: &str, safe: bool) -> u8 {
     // Admin/system categories
-    if category_name == "Windows Update" || category_name == "Event Logs" {
+    if matches!(
+        category_name,
+        "Windows Update" | "Event Logs" | "Orphaned Owners"
+    ) {
         return 3;
     }
     // Review categories (not safe, not admin)
@@ -481,6 +600,40 @@ fn results_group_priority(category_name: &str, safe: bool) -> u8 {
     2
 }
 
+/// Default ordering of category groups on the Results screen:
+/// priority group, then size descending, item count descending, name
+fn compare_result_groups(a: &CategoryGroup, b: &CategoryGroup) -> std::cmp::Ordering {
+    let a_priority = results_group_priority(&a.name, a.safe);
+    let b_priority = results_group_priority(&b.name, b.safe);
+    let priority_cmp = a_priority.cmp(&b_priority);
+    if priority_cmp != std::cmp::Ordering::Equal {
+        return priority_cmp;
+    }
+    // Within same group, sort by size descending
+    let size_cmp = b.total_size.cmp(&a.total_size);
+    if size_cmp == std::cmp::Ordering::Equal {
+        // Then by item count descending
+        let a_count = if a.grouped_by_folder {
+            a.folder_groups.iter().map(|fg| fg.items.len()).sum()
+        } else {
+            a.items.len()
+        };
+        let b_count = if b.grouped_by_folder {
+            b.folder_groups.iter().map(|fg| fg.items.len()).sum()
+        } else {
+            b.items.len()
+        };
+        let count_cmp = b_count.cmp(&a_count);
+        if count_cmp == std::cmp::Ordering::Equal {
+            a.name.cmp(&b.name) // Finally by name ascending for stability
+        } else {
+            count_cmp
+        }
+    } else {
+        size_cmp
+    }
+}
+
 pub(crate) fn build_folder_hierarchy(
     scan_path: &Path,
     group_name: &str,
@@ -546,6 +699,116 @@ pub(crate) fn build_folder_hierarchy(
     FolderHierarchy { roots, children }
 }
 
+/// Characters that turn a Results search into a glob
+pub(crate) const GLOB_CHARS: &[char] = &['*', '?', '['];
+
+/// Text part of a Results search: a case-insensitive substring of the path
+/// (or app name), or a glob when it contains `*`, `?` or `[`.
+///
+/// A glob matches the file name or the whole path, where `*` also crosses
+/// folders (`*acme*`, `*.log`, `C:\dev\*\node_modules`).
+enum SearchText {
+    Any,
+    Substring(String),
+    Glob(globset::GlobMatcher),
+}
+
+impl SearchText {
+    fn new(text_query: &str) -> Self {
+        let text = text_query.trim().to_lowercase();
+        if text.is_empty() {
+            return Self::Any;
+        }
+        if text.contains(GLOB_CHARS) {
+            if let Ok(glob) = globset::GlobBuilder::new(&text)
+                .case_insensitive(true)
+                .backslash_escape(false)
+                .build()
+            {
+                return Self::Glob(glob.compile_matcher());
+            }
+        }
+        Self::Substring(text)
+    }
+
+    fn matches(&self, item: &ResultItem) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Substring(text) => {
+                item.path
+                    .display()
+                    .to_string()
+                    .to_lowercase()
+                    .contains(text)
+                    || item
+                        .display_name
+                        .as_ref()
+                        .is_some_and(|name| name.to_lowercase().contains(text))
+            }
+            Self::Glob(glob) => {
+                glob.is_match(&item.path)
+                    || item
+                        .path
+                        .file_name()
+                        .is_some_and(|name| glob.is_match(name))
+                    || item
+                        .display_name
+                        .as_ref()
+                        .is_some_and(|name| glob.is_match(name))
+            }
+        }
+    }
+}
+
+/// Live system metrics shown beside the Dashboard actions, refreshed in the
+/// background while the Dashboard is open
+#[derive(Default)]
+pub struct DashboardStatus {
+    pub status: Option<Box<crate::status::SystemStatus>>,
+    pub last_refresh: Option<std::time::Instant>,
+    pub receiver: Option<std::sync::mpsc::Receiver<anyhow::Result<crate::status::SystemStatus>>>,
+}
+
+impl DashboardStatus {
+    /// Seconds between refreshes; a full gather takes a moment, so this is
+    /// slower than the Status screen
+    pub const REFRESH_SECS: u64 = 5;
+
+    /// Whether a new refresh should start
+    pub fn due(&self) -> bool {
+        self.receiver.is_none()
+            && self
+                .last_refresh
+                .is_none_or(|at| at.elapsed().as_secs() >= Self::REFRESH_SECS)
+    }
+
+    /// Pick up a finished refresh and start the next one when it's due
+    pub fn poll(&mut self) {
+        if let Some(ref receiver) = self.receiver {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.receiver = None;
+                    self.last_refresh = Some(std::time::Instant::now());
+                    // Keep showing the old numbers if this one failed
+                    if let Ok(status) = result {
+                        self.status = Some(Box::new(status));
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    self.last_refresh = Some(std::time::Instant::now());
+                }
+            }
+        }
+        if self.due() {
+            let (tx, rx) = std::sync::mpsc::channel();
+            self.receiver = Some(rx);
+            crate::status::gather_status_async(tx);
+        }
+    }
+}
+
 /// Main application state
 pub struct AppState {
     pub screen: Screen,
@@ -571,9 +834,26 @@ pub struct AppState {
     pub search_mode: bool,                        // whether search mode is active
     pub search_query: String,                     // current search query
     pub search_navigated: bool, // true if user navigated while in search mode (space should toggle selection)
+    pub insights_largest_files: bool, // Disk Insights shows the largest-files pane (L)
     pub dashboard_message: Option<String>, // temporary message for dashboard (e.g. warnings)
+    pub lifetime_stats: crate::stats::LifetimeStats, // lifetime cleaning totals shown on the dashboard
+    pub size_trend: Vec<u64>, // total reclaimable bytes over recent weeks (dashboard sparkline)
     pub last_scan_categories: Option<std::collections::HashSet<String>>, // categories enabled during last scan (for result reuse)
     pub first_scan_stats: Option<(usize, u64)>, // (total_files, total_storage) for first scan summary
+    pub results_sort: crate::tui::widgets::table::TableSort, // active column sort for the Results screen
+    pub results_flat: bool, // Results screen shows one sorted list instead of category/folder groups
+    pub elevation_hint: Option<crate::elevation::ElevationHint>, // set when the last scan hit many access-denied folders
+    pub elevation_notice: Option<String>, // result of a failed relaunch-as-admin attempt
+    pub preview_largest: Vec<(PathBuf, u64)>, // largest files inside the previewed directory
+    pub power_override: bool, // user chose to run a heavy scan despite a low-battery deferral
+    pub typed_confirm: Option<TypedConfirm>, // set while the user types the size to confirm a large deletion
+    pub volume_filter: Option<String>,       // drive the Results screen is narrowed to (e.g. "D:")
+    pub volume_hidden_items: Vec<ResultItem>, // items on other drives, set aside while volume_filter is active
+    pub excluded_paths: HashSet<PathBuf>, // left out of this clean with `x` (never written to config)
+    pub insights_delete: Option<InsightsDelete>, // set while deleting from the Disk Insights tree
+    pub last_session_log: Option<PathBuf>, // history log written by the last clean
+    pub restore_session: Option<PathBuf>, // session the Restore screen restores (None = latest)
+    pub dashboard_status: DashboardStatus, // CPU, memory and disk panel on the Dashboard
 }
 
 /// A single result item for display in the table
@@ -583,9 +863,14 @@ pub struct ResultItem {
     pub size_bytes: u64,
     pub age_days: Option<u64>,
     pub last_opened: Option<SystemTime>, // currently only populated for Installed Applications
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
     pub category: String,
     pub safe: bool, // true for cache/temp/trash, false for large/old/duplicates
     pub display_name: Option<String>, // Optional display name (used for applications)
+    pub hint: Option<String>, // Large Files: file type, or why the file is in use; Old Files: timestamp used; Build Artifacts: unpushed work
+    pub in_use: bool, // Large Files: open in an editor or locked by another process; Build Artifacts: repository has unpushed work
 }
 
 impl AppState {
@@ -703,9 +988,26 @@ impl AppState {
             search_mode: false,
             search_query: String::new(),
             search_navigated: false,
+            insights_largest_files: false,
             dashboard_message: None,
+            lifetime_stats: crate::stats::LifetimeStats::load(),
+            size_trend: crate::history::recent_totals(SIZE_TREND_WEEKS, SIZE_TREND_POINTS),
             last_scan_categories: None, // No previous scan initially
             first_scan_stats: None,     // No first scan stats initially
+            results_sort: crate::tui::widgets::table::TableSort::default(),
+            results_flat: false,
+            elevation_hint: None,
+            elevation_notice: None,
+            preview_largest: Vec::new(),
+            power_override: false,
+            typed_confirm: None,
+            volume_filter: None,
+            volume_hidden_items: Vec::new(),
+            excluded_paths: HashSet::new(),
+            insights_delete: None,
+            last_session_log: None,
+            restore_session: None,
+            dashboard_status: DashboardStatus::default(),
         }
     }
 
@@ -765,8 +1067,10 @@ impl AppState {
     /// Flatten scan results into a single list for table display
     pub fn flatten_results(&mut self) {
         if let Some(ref results) = self.scan_results {
+            self.volume_hidden_items.clear();
             self.all_items.clear();
             self.selected_items.clear();
+            self.excluded_paths.clear();
             self.category_groups.clear();
 
             // Clone scan_path to avoid borrow checker issues with mut self later
@@ -815,6 +1119,8 @@ impl AppState {
                 })
             };
 
+            let old_basis = self.config.categories.old.age_basis.unwrap_or_default();
+
             // Helper to add items from a category
             let mut add_category = |paths: &[PathBuf],
                                     size_bytes: u64,
@@ -822,6 +1128,9 @@ impl AppState {
                                     safe: bool| {
                 let start_idx = self.all_items.len();
                 let mut total_size = 0u64;
+                // Type and in-use status help judge large files at a glance
+                let open = (category == "Large Files" && !paths.is_empty())
+                    .then(crate::open_files::OpenFiles::detect);
 
                 for path in paths {
                     // "Age" is mostly used for Old/Large files. For Installed Applications, we'll
@@ -832,17 +1141,23 @@ impl AppState {
                         None
                     };
 
+                    let times = crate::utils::file_times(path);
+                    // Old files show their age by the configured timestamp, and which one it was
+                    let old_age = (category == "Old Files")
+                        .then(|| crate::categories::old::file_age(path, old_basis))
+                        .flatten();
                     let age_days = if category == "Installed Applications" {
                         last_opened
-                            .and_then(|t| t.elapsed().ok())
-                            .map(|d| d.as_secs() / 86400)
                     } else {
-                        std::fs::metadata(path)
-                            .ok()
-                            .and_then(|m| m.accessed().ok())
-                            .and_then(|t| t.elapsed().ok())
-                            .map(|d| d.as_secs() / 86400)
-                    };
+                        times.accessed
+                    }
+                    .and_then(|t| t.elapsed().ok())
+                    .map(|d| d.as_secs() / 86400);
+                    let age_days = old_age.map(|age| age.days).or(age_days);
+                    // Build artifacts whose repository holds work that exists nowhere else
+                    let local_work = (category == "Build Artifacts")
+                        .then(|| crate::git::repo_state_cached(path))
+                        .filter(|repo| repo.has_local_work());
 
                     // NOTE: `metadata.len()` on directories is NOT the folder's contents size.
                     // For Installed Applications we already computed real directory sizes during
@@ -859,6 +1174,10 @@ impl AppState {
 
                     total_size += item_size;
 
+                    let hints = open
+                        .as_ref()
+                        .map(|open| crate::categories::large::FileHints::of(path, open));
+
                     // Get display name for applications
                     // Handle edge case: if lookup fails, try with canonicalized path
                     let display_name = if category == "Installed Applications" {
@@ -877,9 +1196,27 @@ impl AppState {
                         size_bytes: item_size,
                         age_days,
                         last_opened,
+                        created: times.created,
+                        modified: times.modified,
+                        accessed: times.accessed,
                         category: category.to_string(),
                         safe,
                         display_name,
+                        hint: hints
+                            .as_ref()
+                            .map(|h| h.in_use().unwrap_or_else(|| h.file_type.as_str().into()))
+                            .or_else(|| old_age.map(|age| age.stamp.as_str().to_string()))
+                            .or_else(|| {
+                                local_work.map(|repo| {
+                                    match (repo.dirty, repo.unpushed) {
+                                        (true, true) => "dirty, unpushed",
+                                        (true, false) => "dirty",
+                                        _ => "unpushed",
+                                    }
+                                    .to_string()
+                                })
+                            }),
+                        in_use: hints.is_some_and(|h| h.open.is_some()) || local_work.is_some(),
                     });
                 }
 
@@ -1441,6 +1778,38 @@ impl AppState {
                     false,
                 );
             }
+            if is_category_enabled("Orphaned Owners") {
+                add_category(
+                    &results.orphaned.paths,
+                    results.orphaned.size_bytes,
+                    "Orphaned Owners",
+                    false,
+                );
+            }
+            if is_category_enabled("Custom Rules") {
+                add_category(
+                    &results.custom.paths,
+                    results.custom.size_bytes,
+                    "Custom Rules",
+                    false,
+                );
+            }
+            if is_category_enabled("IDE Caches") {
+                add_category(
+                    &results.ide.paths,
+                    results.ide.size_bytes,
+                    "IDE Caches",
+                    true,
+                );
+            }
+            if is_category_enabled("Rust Toolchain") {
+                add_category(
+                    &results.rust.paths,
+                    results.rust.size_bytes,
+                    "Rust Toolchain",
+                    true,
+                );
+            }
 
             // Sort category groups for results screen:
             // First: Respect dashboard order (Quick Clean -> Developer Cleanup -> Space Hunters -> Advanced)
@@ -1477,6 +1846,7 @@ impl AppState {
                     size_cmp
                 }
             });
+            self.apply_results_sort();
 
             // Build path_to_indices mapping for cross-category selection sync
             // This allows selecting a file in one category to also select it in other categories
@@ -1507,12 +1877,88 @@ impl AppState {
 
             self.cursor = 0;
             self.scroll_offset = 0;
+
+            // Keep a drive filter across rescans while that drive still has results
+            if let Some(volume) = self.volume_filter.take() {
+                if self.result_volumes().iter().any(|(v, ..)| *v == volume) {
+                    self.set_volume_filter(Some(volume));
+                }
+            }
+        }
+    }
+
+    /// Drives with results, sorted, with their item count and size.
+    /// Includes items set aside by the volume filter.
+    pub fn result_volumes(&self) -> Vec<(String, usize, u64)> {
+        let mut volumes: std::collections::BTreeMap<String, (usize, u64)> =
+            std::collections::BTreeMap::new();
+        for item in self.all_items.iter().chain(&self.volume_hidden_items) {
+            let entry = volumes
+                .entry(crate::utils::volume_of(&item.path))
+                .or_default();
+            entry.0 += 1;
+            entry.1 += item.size_bytes;
         }
+        volumes
+            .into_iter()
+            .map(|(volume, (count, size))| (volume, count, size))
+            .collect()
+    }
+
+    /// Cycle the Results screen through: all drives, then each drive in turn
+    pub fn cycle_volume_filter(&mut self) {
+        let volumes: Vec<String> = self.result_volumes().into_iter().map(|v| v.0).collect();
+        let next = match &self.volume_filter {
+            None => volumes.first().cloned(),
+            Some(current) => volumes
+                .iter()
+                .position(|v| v == current)
+                .and_then(|i| volumes.get(i + 1).cloned()),
+        };
+        // A single drive has nothing to narrow down
+        let next = if volumes.len() < 2 { None } else { next };
+        self.set_volume_filter(next);
+    }
+
+    /// Show only items on `volume` (None shows every drive). Items on other
+    /// drives are set aside and deselected so they can't be cleaned unseen.
+    pub fn set_volume_filter(&mut self, volume: Option<String>) {
+        // Bring back everything set aside by the previous filter
+        self.all_items.append(&mut self.volume_hidden_items);
+
+        if let Some(ref volume) = volume {
+            let mut kept = Vec::with_capacity(self.all_items.len());
+            let mut remap: HashMap<usize, usize> = HashMap::new();
+            for (old_idx, item) in std::mem::take(&mut self.all_items).into_iter().enumerate() {
+                if crate::utils::volume_of(&item.path) == *volume {
+                    remap.insert(old_idx, kept.len());
+                    kept.push(item);
+                } else {
+                    self.volume_hidden_items.push(item);
+                }
+            }
+            self.all_items = kept;
+            self.selected_items = self
+                .selected_items
+                .iter()
+                .filter_map(|idx| remap.get(idx).copied())
+                .collect();
+        }
+
+        self.volume_filter = volume;
+        self.rebuild_groups_from_all_items();
+        self.apply_results_sort();
+        self.cursor = 0;
+        self.scroll_offset = 0;
     }
 
     /// Build a flattened list of rows for the Results screen.
     /// When there's only one category, skip the category header.
     pub fn results_rows(&self) -> Vec<ResultsRow> {
+        if self.results_flat {
+            return self.flat_results_rows(&|_| true);
+        }
+
         let mut rows = Vec::new();
         let skip_category_header = self.category_groups.len() == 1;
 
@@ -1868,6 +2314,8 @@ impl AppState {
 
         let (type_filter, extension_filter, text_query) = self.parse_search_query();
 
+        let text_matcher = SearchText::new(&text_query);
+
         // Clone extension filter for use in closure
         let extension_filter_clone = extension_filter.clone();
 
@@ -1897,15 +2345,8 @@ impl AppState {
                     }
                 }
 
-                // Check text query if present
-                if !text_query.is_empty() {
-                    let path_str = item.path.display().to_string().to_lowercase();
-                    if path_str.contains(&text_query) {
-                        return true;
-                    }
-                    if let Some(display_name) = item.display_name.as_ref() {
-                        return display_name.to_lowercase().contains(&text_query);
-                    }
+                // Check text query (substring or glob) if present
+                if !text_matcher.matches(item) {
                     return false;
                 }
 
@@ -1916,6 +2357,10 @@ impl AppState {
             }
         };
 
+        if self.results_flat {
+            return self.flat_results_rows(&item_matches);
+        }
+
         for (group_idx, group) in self.category_groups.iter().enumerate() {
             let mut has_matching_items = false;
             let mut matching_rows = Vec::new();
@@ -2885,6 +3330,9 @@ impl AppState {
             }
         }
 
+        // Items left out of this clean stay unselected
+        all_related_indices.retain(|&idx| !self.is_excluded(idx));
+
         // Check if all related indices are selected
         let all_selected = all_related_indices
             .iter()
@@ -2900,6 +3348,250 @@ impl AppState {
         }
     }
 
+    /// Whether the item at `idx` is left out of this clean
+    pub fn is_excluded(&self, idx: usize) -> bool {
+        self.all_items
+            .get(idx)
+            .is_some_and(|item| self.excluded_paths.contains(&item.path))
+    }
+
+    /// Total size of the excluded items among `indices`
+    pub fn excluded_size(&self, indices: impl IntoIterator<Item = usize>) -> u64 {
+        indices
+            .into_iter()
+            .filter(|&idx| self.is_excluded(idx))
+            .filter_map(|idx| self.all_items.get(idx))
+            .map(|item| item.size_bytes)
+            .sum()
+    }
+
+    /// Leave items out of the current clean, or bring them back when all of
+    /// them are already out. Excluded items are deselected (in every category
+    /// they appear in) and can't be selected until brought back; the config
+    /// isn't touched.
+    pub fn toggle_excluded(&mut self, item_indices: impl IntoIterator<Item = usize>) {
+        let paths: HashSet<PathBuf> = item_indices
+            .into_iter()
+            .filter_map(|idx| self.all_items.get(idx))
+            .map(|item| item.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        let affected: Vec<usize> = self
+            .all_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| paths.contains(&item.path))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if paths.iter().all(|p| self.excluded_paths.contains(p)) {
+            for path in &paths {
+                self.excluded_paths.remove(path);
+            }
+            self.selected_items.extend(affected);
+        } else {
+            self.excluded_paths.extend(paths);
+            for idx in affected {
+                self.selected_items.remove(&idx);
+            }
+        }
+    }
+
+    /// Count and total size of the excluded items
+    pub fn excluded_summary(&self) -> (usize, u64) {
+        let indices = (0..self.all_items.len()).filter(|&idx| self.is_excluded(idx));
+        let count = indices.clone().count();
+        (count, self.excluded_size(indices))
+    }
+
+    /// Send files and folders picked in Disk Insights through the regular
+    /// Confirm -> Cleaning -> Success flow.
+    ///
+    /// The targets are appended to `all_items` as their own category and become
+    /// the whole selection; the Results selection is restored afterwards.
+    pub fn delete_from_insights(&mut self, targets: Vec<(PathBuf, u64)>) {
+        if targets.is_empty() || self.insights_delete.is_some() {
+            return;
+        }
+
+        let first_item = self.all_items.len();
+        let paths = targets.iter().map(|(path, _)| path.clone()).collect();
+        for (path, size_bytes) in targets {
+            let metadata = std::fs::metadata(&path).ok();
+            self.all_items.push(ResultItem {
+                path,
+                size_bytes,
+                age_days: None,
+                last_opened: None,
+                created: metadata.as_ref().and_then(|m| m.created().ok()),
+                modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+                accessed: metadata.as_ref().and_then(|m| m.accessed().ok()),
+                category: "Disk Insights".to_string(),
+                safe: false,
+                display_name: None,
+                hint: None,
+                in_use: false,
+            });
+        }
+
+        let selection = std::mem::replace(
+            &mut self.selected_items,
+            (first_item..self.all_items.len()).collect(),
+        );
+        let screen = std::mem::replace(&mut self.screen, Screen::Confirm { permanent: false });
+        self.insights_delete = Some(InsightsDelete {
+            screen,
+            targets: paths,
+            first_item,
+            selection,
+        });
+
+        self.confirm_snapshot = self.selected_items.clone();
+        self.cache_confirm_groups();
+        self.cursor = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Leave the Confirm screen without deleting and go back to Disk Insights.
+    /// Returns false when the Confirm screen wasn't opened from Disk Insights.
+    pub fn cancel_insights_delete(&mut self) -> bool {
+        let Some(pending) = self.insights_delete.take() else {
+            return false;
+        };
+        self.all_items.truncate(pending.first_item);
+        self.selected_items = pending.selection;
+        self.confirm_snapshot.clear();
+        self.clear_confirm_cache();
+        self.screen = pending.screen;
+        true
+    }
+
+    /// Return to Disk Insights after cleaning, with the deleted paths dropped
+    /// from the tree. Returns false when the clean didn't start there.
+    pub fn finish_insights_delete(&mut self) -> bool {
+        let Some(mut pending) = self.insights_delete.take() else {
+            return false;
+        };
+        // Cleaning already removed the appended items from `all_items`
+        self.all_items.truncate(pending.first_item);
+        self.selected_items = pending
+            .selection
+            .into_iter()
+            .filter(|&idx| idx < pending.first_item)
+            .collect();
+        if let Screen::DiskInsights {
+            ref mut insights,
+            ref mut selected_paths,
+            ..
+        } = pending.screen
+        {
+            for target in pending.targets.iter().filter(|p| !p.exists()) {
+                insights.remove_path(target);
+                selected_paths.remove(target);
+            }
+        }
+        self.screen = pending.screen;
+        true
+    }
+
+    /// Folders a scan covers: the configured `paths.scan_roots` that exist,
+    /// or the single scan path when none are set.
+    pub fn scan_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for root in self.config.paths.scan_roots.iter().map(PathBuf::from) {
+            if root.is_dir() && !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        if roots.is_empty() {
+            roots.push(self.scan_path.clone());
+        }
+        roots
+    }
+
+    /// Open the History screen on `session` (a history log), or on the
+    /// newest session when it's `None` or no longer listed.
+    pub fn open_history(&mut self, session: Option<&Path>) {
+        let sessions = crate::history::list_logs().unwrap_or_default();
+        let index = session
+            .and_then(|s| sessions.iter().position(|p| p == s))
+            .unwrap_or(0);
+        self.screen = Screen::History {
+            sessions,
+            index: 0,
+            log: None,
+            cursor: 0,
+            message: None,
+        };
+        self.show_history_session(index);
+    }
+
+    /// Show another session on the History screen (clamped to the list).
+    pub fn show_history_session(&mut self, index: usize) {
+        if let Screen::History {
+            ref sessions,
+            index: ref mut shown,
+            ref mut log,
+            ref mut cursor,
+            ref mut message,
+        } = self.screen
+        {
+            let index = index.min(sessions.len().saturating_sub(1));
+            *shown = index;
+            *log = sessions
+                .get(index)
+                .and_then(|path| crate::history::load_log(path).ok());
+            *cursor = 0;
+            *message = None;
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Retry the failed deletions of the session on the History screen.
+    ///
+    /// The retry is saved as a session of its own, which is shown next.
+    pub fn retry_history_failures(&mut self) {
+        let Screen::History {
+            log: Some(ref log), ..
+        } = self.screen
+        else {
+            return;
+        };
+        let failed = log.errors;
+        let text = if failed == 0 {
+            "Nothing to retry: every deletion succeeded".to_string()
+        } else {
+            let retry = log.retry_failures();
+            let summary = format!(
+                "Retried {}: {} deleted ({}), {} still failing",
+                failed,
+                retry.total_items - retry.errors,
+                bytesize::to_string(retry.total_bytes_cleaned, false),
+                retry.errors
+            );
+            if retry.total_items == 0 {
+                summary
+            } else {
+                match retry.save() {
+                    Ok(path) => {
+                        self.lifetime_stats = crate::stats::LifetimeStats::load();
+                        self.open_history(Some(&path));
+                        summary
+                    }
+                    Err(e) => format!("{} (not saved to history: {})", summary, e),
+                }
+            }
+        };
+        if let Screen::History {
+            ref mut message, ..
+        } = self.screen
+        {
+            *message = Some(text);
+        }
+    }
+
     /// Get all item indices belonging to a given category group.
     /// If search_query is active, only returns items that match the filter.
     pub fn category_item_indices(&self, group_idx: usize) -> Vec<usize> {
@@ -2920,6 +3612,7 @@ impl AppState {
         // If search query is active, filter items to only include matches
         if !self.search_query.trim().is_empty() {
             let (type_filter, extension_filter, text_query) = self.parse_search_query();
+            let text_matcher = SearchText::new(&text_query);
             let extension_filter_clone = extension_filter.clone();
 
             all_items
@@ -2945,15 +3638,8 @@ impl AppState {
                             }
                         }
 
-                        // Check text query if present
-                        if !text_query.is_empty() {
-                            let path_str = item.path.display().to_string().to_lowercase();
-                            if path_str.contains(&text_query) {
-                                return true;
-                            }
-                            if let Some(display_name) = item.display_name.as_ref() {
-                                return display_name.to_lowercase().contains(&text_query);
-                            }
+                        // Check text query (substring or glob) if present
+                        if !text_matcher.matches(item) {
                             return false;
                         }
 
@@ -3002,6 +3688,7 @@ impl AppState {
         // If search query is active, filter items to only include matches
         if !self.search_query.trim().is_empty() {
             let (type_filter, extension_filter, text_query) = self.parse_search_query();
+            let text_matcher = SearchText::new(&text_query);
             let extension_filter_clone = extension_filter.clone();
 
             all_items
@@ -3027,15 +3714,8 @@ impl AppState {
                             }
                         }
 
-                        // Check text query if present
-                        if !text_query.is_empty() {
-                            let path_str = item.path.display().to_string().to_lowercase();
-                            if path_str.contains(&text_query) {
-                                return true;
-                            }
-                            if let Some(display_name) = item.display_name.as_ref() {
-                                return display_name.to_lowercase().contains(&text_query);
-                            }
+                        // Check text query (substring or glob) if present
+                        if !text_matcher.matches(item) {
                             return false;
                         }
 
@@ -3428,37 +4108,8 @@ impl AppState {
         // Group 1: Review categories (sorted by size desc)
         // Group 2: Safe categories (sorted by size desc)
         // Group 3: Admin/system categories (sorted by size desc)
-        self.category_groups.sort_by(|a, b| {
-            let a_priority = results_group_priority(&a.name, a.safe);
-            let b_priority = results_group_priority(&b.name, b.safe);
-            let priority_cmp = a_priority.cmp(&b_priority);
-            if priority_cmp != std::cmp::Ordering::Equal {
-                return priority_cmp;
-            }
-            // Within same group, sort by size descending
-            let size_cmp = b.total_size.cmp(&a.total_size);
-            if size_cmp == std::cmp::Ordering::Equal {
-                // Then by item count descending
-                let a_count = if a.grouped_by_folder {
-                    a.folder_groups.iter().map(|fg| fg.items.len()).sum()
-                } else {
-                    a.items.len()
-                };
-                let b_count = if b.grouped_by_folder {
-                    b.folder_groups.iter().map(|fg| fg.items.len()).sum()
-                } else {
-                    b.items.len()
-                };
-                let count_cmp = b_count.cmp(&a_count);
-                if count_cmp == std::cmp::Ordering::Equal {
-                    a.name.cmp(&b.name) // Finally by name ascending for stability
-                } else {
-                    count_cmp
-                }
-            } else {
-                size_cmp
-            }
-        });
+        self.category_groups.sort_by(compare_result_groups);
+        self.apply_results_sort();
 
         // Rebuild path_to_indices mapping for cross-category selection sync
         self.path_to_indices.clear();
@@ -3470,6 +4121,184 @@ impl AppState {
         }
     }
 
+    /// Open the Preview screen for an item, with the largest files the scan found in it
+    pub fn open_preview(&mut self, index: usize) {
+        self.preview_largest = self
+            .all_items
+            .get(index)
+            .zip(self.scan_results.as_ref())
+            .map(|(item, results)| results.largest_files_in(&item.path).to_vec())
+            .unwrap_or_default();
+        self.screen = Screen::Preview { index };
+    }
+
+    /// State to carry over when relaunching elevated
+    pub fn elevation_handoff(&self) -> crate::elevation::ElevationHandoff {
+        crate::elevation::ElevationHandoff {
+            scan_path: self.scan_path.clone(),
+            categories: self
+                .categories
+                .iter()
+                .filter(|cat| cat.enabled)
+                .map(|cat| cat.name.clone())
+                .collect(),
+            search_query: self.search_query.clone(),
+            rescan: true,
+        }
+    }
+
+    /// Restore state handed over by an unelevated session and, if requested,
+    /// go straight to scanning
+    pub fn apply_elevation_handoff(&mut self, handoff: crate::elevation::ElevationHandoff) {
+        self.scan_path = handoff.scan_path;
+        for cat in &mut self.categories {
+            cat.enabled = handoff.categories.contains(&cat.name);
+        }
+        self.search_query = handoff.search_query;
+
+        if handoff.rescan && self.categories.iter().any(|c| c.enabled) {
+            self.pending_action = PendingAction::None;
+            let category_progress = self
+                .categories
+                .iter()
+                .filter(|cat| cat.enabled)
+                .map(|cat| CategoryProgress {
+                    name: cat.name.clone(),
+                    completed: false,
+                    progress_pct: 0.0,
+                    size: None,
+                })
+                .collect();
+            self.screen = Screen::Scanning {
+                progress: ScanProgress {
+                    current_category: String::new(),
+                    current_path: None,
+                    notice: None,
+                    category_progress,
+                    total_scanned: 0,
+                    total_found: 0,
+                    total_size: 0,
+                    start_time: std::time::Instant::now(),
+                    hashing: None,
+                    stopping: false,
+                },
+            };
+        }
+    }
+
+    /// Change the Results screen sort and reorder the current results.
+    pub fn set_results_sort(&mut self, sort: crate::tui::widgets::table::TableSort) {
+        use crate::tui::widgets::table::SortColumn;
+
+        let was_by_category = self.results_sort.column == SortColumn::Category;
+        self.results_sort = sort;
+        if was_by_category && sort.column != SortColumn::Category {
+            // Back to the default priority order
+            self.category_groups.sort_by(compare_result_groups);
+        }
+        self.apply_results_sort();
+    }
+
+    /// Switch the Results screen between grouped and flat views, keeping the
+    /// cursor on the same item when it is on one.
+    pub fn toggle_results_view(&mut self) {
+        let rows = self.filtered_results_rows();
+        let current = match rows.get(self.cursor) {
+            Some(ResultsRow::Item { item_idx, .. }) => Some(*item_idx),
+            _ => None,
+        };
+        self.results_flat = !self.results_flat;
+        let rows = self.filtered_results_rows();
+        self.cursor = current
+            .and_then(|idx| {
+                rows.iter().position(
+                    |r| matches!(r, ResultsRow::Item { item_idx, .. } if *item_idx == idx),
+                )
+            })
+            .unwrap_or(0);
+        self.scroll_offset = self.cursor.saturating_sub(self.visible_height / 2);
+    }
+
+    /// Every result as one list ordered by `results_sort`, without category
+    /// or folder headers (the flat view). `keep` filters items by index.
+    fn flat_results_rows(&self, keep: &dyn Fn(usize) -> bool) -> Vec<ResultsRow> {
+        let mut seen = HashSet::new();
+        let mut indices: Vec<usize> = self
+            .category_groups
+            .iter()
+            .flat_map(|group| {
+                if group.grouped_by_folder {
+                    group
+                        .folder_groups
+                        .iter()
+                        .flat_map(|fg| fg.items.iter().copied())
+                        .collect()
+                } else {
+                    group.items.clone()
+                }
+            })
+            .filter(|&idx| seen.insert(idx) && keep(idx))
+            .collect();
+        crate::tui::widgets::table::sort_indices(&mut indices, &self.all_items, self.results_sort);
+        indices
+            .into_iter()
+            .map(|item_idx| ResultsRow::Item { item_idx, depth: 0 })
+            .collect()
+    }
+
+    /// Apply `results_sort` to the Results screen.
+    ///
+    /// Items are reordered within their category and folder groups; folder
+    /// groups keep their layout (common parent first). Category groups are
+    /// only reordered when sorting by category.
+    pub fn apply_results_sort(&mut self) {
+        use crate::tui::widgets::table::{sort_indices, SortColumn};
+
+        let sort = self.results_sort;
+        let items = &self.all_items;
+        for group in &mut self.category_groups {
+            sort_indices(&mut group.items, items, sort);
+            for folder in &mut group.folder_groups {
+                sort_indices(&mut folder.items, items, sort);
+            }
+        }
+
+        if sort.column == SortColumn::Category {
+            self.category_groups.sort_by(|a, b| {
+                let o = a.name.cmp(&b.name);
+                if sort.descending {
+                    o.reverse()
+                } else {
+                    o
+                }
+            });
+        }
+    }
+
+    /// Item count and total size of what the Results screen currently shows.
+    ///
+    /// Follows the active search filter; collapsed groups still count.
+    pub fn visible_totals(&self) -> (usize, u64) {
+        let mut seen: HashSet<usize> = HashSet::new();
+        if self.search_query.trim().is_empty() {
+            for group_idx in 0..self.category_groups.len() {
+                seen.extend(self.category_item_indices(group_idx));
+            }
+        } else {
+            for row in self.filtered_results_rows() {
+                if let ResultsRow::Item { item_idx, .. } = row {
+                    seen.insert(item_idx);
+                }
+            }
+        }
+        let size = seen
+            .iter()
+            .filter_map(|&i| self.all_items.get(i))
+            .map(|item| item.size_bytes)
+            .sum();
+        (seen.len(), size)
+    }
+
     /// Get total size of selected items
     pub fn selected_size(&self) -> u64 {
         self.selected_items
@@ -3506,3 +4335,217 @@ impl Default for AppState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::CategoryResult;
+
+    #[test]
+    fn dashboard_status_refreshes_only_when_due() {
+        let mut status = DashboardStatus::default();
+        assert!(status.due());
+
+        status.last_refresh = Some(std::time::Instant::now());
+        assert!(!status.due());
+
+        let (_tx, rx) = std::sync::mpsc::channel();
+        status.last_refresh = None;
+        status.receiver = Some(rx);
+        assert!(!status.due(), "a refresh is already running");
+    }
+
+    #[test]
+    fn excluded_items_stay_out_of_the_selection_and_totals() {
+        let mut app_state = AppState::new();
+        app_state.scan_results = Some(ScanResults {
+            temp: CategoryResult {
+                items: 2,
+                size_bytes: 300,
+                paths: vec![PathBuf::from("/t/a.tmp"), PathBuf::from("/t/b.tmp")],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app_state.flatten_results();
+        assert_eq!(app_state.selected_size(), 300);
+
+        app_state.toggle_excluded([0]);
+        assert_eq!(app_state.selected_count(), 1);
+        assert_eq!(app_state.selected_size(), 150);
+        assert_eq!(app_state.excluded_summary(), (1, 150));
+
+        // Selecting the whole category skips the excluded item
+        app_state.toggle_items([0, 1]);
+        app_state.toggle_items([0, 1]);
+        assert_eq!(app_state.selected_count(), 1);
+        assert!(!app_state.selected_items.contains(&0));
+
+        // Pressing x again brings it back, selected
+        app_state.toggle_excluded([0]);
+        assert_eq!(app_state.excluded_summary(), (0, 0));
+        assert_eq!(app_state.selected_size(), 300);
+    }
+
+    #[test]
+    fn results_search_accepts_substrings_and_globs() {
+        let mut app_state = AppState::new();
+        app_state.scan_results = Some(ScanResults {
+            build: CategoryResult {
+                items: 2,
+                size_bytes: 200,
+                paths: vec![
+                    PathBuf::from("/dev/acme-web/node_modules"),
+                    PathBuf::from("/dev/tools/target"),
+                ],
+                ..Default::default()
+            },
+            temp: CategoryResult {
+                items: 1,
+                size_bytes: 10,
+                paths: vec![PathBuf::from("/tmp/Setup.LOG")],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app_state.flatten_results();
+
+        let visible = |app_state: &mut AppState, query: &str| {
+            app_state.search_query = query.to_string();
+            app_state.visible_totals().0
+        };
+        assert_eq!(visible(&mut app_state, ""), 3);
+        assert_eq!(visible(&mut app_state, "tools"), 1);
+        assert_eq!(visible(&mut app_state, "*acme*"), 1);
+        assert_eq!(visible(&mut app_state, "*.log"), 1);
+        assert_eq!(visible(&mut app_state, "/dev/*"), 2);
+        assert_eq!(visible(&mut app_state, "node_modules"), 1);
+        assert_eq!(visible(&mut app_state, "*.zip"), 0);
+    }
+
+    #[test]
+    fn scan_roots_fall_back_to_the_scan_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dev = temp_dir.path().join("dev");
+        std::fs::create_dir(&dev).unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.config.paths.scan_roots = Vec::new();
+        assert_eq!(app_state.scan_roots(), vec![app_state.scan_path.clone()]);
+
+        let root = |p: &Path| p.display().to_string();
+        app_state.config.paths.scan_roots = vec![
+            root(&dev),
+            root(&temp_dir.path().join("missing")),
+            root(temp_dir.path()),
+            root(&dev),
+        ];
+        assert_eq!(
+            app_state.scan_roots(),
+            vec![dev.clone(), temp_dir.path().to_path_buf()]
+        );
+    }
+
+    #[test]
+    fn flat_results_view_sorts_across_categories() {
+        use crate::tui::widgets::table::{SortColumn, TableSort};
+
+        let mut app_state = AppState::new();
+        app_state.scan_results = Some(ScanResults {
+            build: CategoryResult {
+                items: 2,
+                size_bytes: 200,
+                paths: vec![PathBuf::from("/b/target"), PathBuf::from("/d/dist")],
+                ..Default::default()
+            },
+            temp: CategoryResult {
+                items: 1,
+                size_bytes: 10,
+                paths: vec![PathBuf::from("/c/setup.log")],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app_state.flatten_results();
+        app_state.set_results_sort(TableSort::new(SortColumn::Path));
+
+        let paths = |app_state: &AppState| -> Vec<String> {
+            app_state
+                .filtered_results_rows()
+                .into_iter()
+                .filter_map(|row| match row {
+                    ResultsRow::Item { item_idx, .. } => {
+                        Some(app_state.all_items[item_idx].path.display().to_string())
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        let grouped = app_state.results_rows();
+        assert!(grouped
+            .iter()
+            .any(|r| matches!(r, ResultsRow::CategoryHeader { .. })));
+
+        app_state.toggle_results_view();
+        let flat = app_state.results_rows();
+        assert_eq!(flat.len(), 3);
+        assert!(flat
+            .iter()
+            .all(|r| matches!(r, ResultsRow::Item { depth: 0, .. })));
+        assert_eq!(paths(&app_state), ["/b/target", "/c/setup.log", "/d/dist"]);
+
+        app_state.search_query = "*.log".to_string();
+        assert_eq!(paths(&app_state), ["/c/setup.log"]);
+
+        app_state.search_query.clear();
+        app_state.toggle_results_view();
+        assert_eq!(app_state.results_rows(), grouped);
+    }
+
+    #[test]
+    fn disk_insights_deletion_goes_through_confirm_and_back() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let keep = temp_dir.path().join("keep.bin");
+        let old = temp_dir.path().join("old");
+        std::fs::write(&keep, vec![0u8; 10]).unwrap();
+        std::fs::create_dir(&old).unwrap();
+        std::fs::write(old.join("a.bin"), vec![0u8; 40]).unwrap();
+        let insights = crate::disk_usage::scan_directory(temp_dir.path(), 3).unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.screen = Screen::DiskInsights {
+            insights,
+            current_path: temp_dir.path().to_path_buf(),
+            cursor: 0,
+            sort_by: Default::default(),
+            selected_paths: HashSet::from([old.clone()]),
+        };
+
+        app_state.delete_from_insights(vec![(old.clone(), 40)]);
+        assert!(matches!(app_state.screen, Screen::Confirm { .. }));
+        assert_eq!(app_state.selected_size(), 40);
+
+        // Cancelling leaves nothing behind
+        assert!(app_state.cancel_insights_delete());
+        assert!(app_state.all_items.is_empty());
+        assert!(matches!(app_state.screen, Screen::DiskInsights { .. }));
+
+        // Deleted paths are dropped from the tree when coming back
+        app_state.delete_from_insights(vec![(old.clone(), 40)]);
+        std::fs::remove_dir_all(&old).unwrap();
+        app_state.all_items.clear();
+        assert!(app_state.finish_insights_delete());
+        let Screen::DiskInsights {
+            insights,
+            selected_paths,
+            ..
+        } = &app_state.screen
+        else {
+            panic!("expected Disk Insights");
+        };
+        assert!(insights.root.children.is_empty());
+        assert_eq!(insights.total_size, 10);
+        assert_eq!(insights.root.size, 10);
+        assert!(selected_paths.is_empty());
+    }
+}
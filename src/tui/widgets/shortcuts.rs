@@ -101,6 +101,11 @@ pub fn get_shortcuts(
                     ("↑↓", "Navigate"),
                     ("Tab", "Next Category"),
                     ("Enter", "open"),
+                    ("P", "Preview"),
+                    ("X", "Exclude"),
+                    ("S/R", "Sort/Reverse"),
+                    ("G", "Group/Flat"),
+                    ("V", "Drive"),
                     ("Ctrl+Enter", "Collapse group..."),
                     ("Esc", "Clear Filter"),
                     ("Q", "Quit"),
@@ -113,6 +118,11 @@ pub fn get_shortcuts(
                     ("↑↓", "Navigate"),
                     ("Tab", "Next Category"),
                     ("Enter", "open"),
+                    ("P", "Preview"),
+                    ("X", "Exclude"),
+                    ("S/R", "Sort/Reverse"),
+                    ("G", "Group/Flat"),
+                    ("V", "Drive"),
                     ("Ctrl+Enter", "Collapse group..."),
                     ("Esc", "Back"),
                     ("Q", "Quit"),
@@ -138,11 +148,22 @@ pub fn get_shortcuts(
                 .unwrap_or(false);
 
             if has_remaining {
-                vec![("Esc/B", "Back to Results"), ("Any Key", "Dashboard")]
+                vec![
+                    ("Esc/B", "Back to Results"),
+                    ("H", "Show in History"),
+                    ("Any Key", "Dashboard"),
+                ]
             } else {
-                vec![("Any Key", "Dashboard")]
+                vec![("H", "Show in History"), ("Any Key", "Dashboard")]
             }
         }
+        crate::tui::state::Screen::History { .. } => vec![
+            ("↑↓", "Navigate"),
+            ("←→", "Older/Newer"),
+            ("F", "Retry failures"),
+            ("R", "Restore"),
+            ("Esc/B/Q", "Back"),
+        ],
         crate::tui::state::Screen::RestoreSelection { .. } => {
             vec![("↑↓", "Navigate"), ("Enter", "Select"), ("Esc/B/Q", "Back")]
         }
@@ -160,10 +181,12 @@ pub fn get_shortcuts(
             {
                 vec![
                     ("Space", "Select"),
+                    ("D", "Delete"),
                     ("↑↓", "Navigate"),
                     ("Enter", "Drill In"),
                     ("Backspace", "Go Back"),
-                    ("S", "Sort"),
+                    ("S/R", "Sort/Reverse"),
+                    ("L", "Largest"),
                     ("/", "Search"),
                     ("Esc", "Clear Filter"),
                     ("Q", "Quit"),
@@ -171,16 +194,22 @@ pub fn get_shortcuts(
             } else {
                 vec![
                     ("Space", "Select"),
+                    ("D", "Delete"),
                     ("↑↓", "Navigate"),
                     ("Enter", "Drill In"),
                     ("Backspace", "Go Back"),
-                    ("S", "Sort"),
+                    ("S/R", "Sort/Reverse"),
+                    ("L", "Largest"),
                     ("/", "Search"),
                     ("Q/Esc", "Quit"),
                 ]
             }
         }
         crate::tui::state::Screen::Status { .. } => vec![("Esc/Q", "Back"), ("R", "Refresh")],
+        crate::tui::state::Screen::Custom { index } => {
+            crate::tui::extensions::with_screen(*index, |screen| screen.shortcuts())
+                .unwrap_or_else(|| vec![("Esc", "Back")])
+        }
         crate::tui::state::Screen::Optimize { .. } => {
             if app_state
                 .and_then(|s| {
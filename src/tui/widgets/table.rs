@@ -1,7 +1,11 @@
 //! Table widget for displaying scan results
+//!
+//! Also holds the column sorting and totals footer shared by the Results and
+//! Disk Insights screens, so both tables sort and summarize the same way.
 
+use crate::disk_usage::{FileInfo, FolderNode};
 use crate::tui::{
-    state::ResultItem,
+    state::{FolderGroup, ResultItem},
     theme::{category_style, Styles},
 };
 use ratatui::{
@@ -11,6 +15,282 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
+use std::cmp::Ordering;
+
+/// Column a table can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Size,
+    Name,
+    Age,
+    Modified,
+    Category,
+    Path,
+    Files,
+}
+
+impl SortColumn {
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Size => "Size",
+            SortColumn::Name => "Name",
+            SortColumn::Age => "Age",
+            SortColumn::Modified => "Modified",
+            SortColumn::Category => "Category",
+            SortColumn::Path => "Path",
+            SortColumn::Files => "Files",
+        }
+    }
+
+    /// Direction a column starts in: biggest/oldest first, names A-Z
+    fn default_descending(self) -> bool {
+        !matches!(
+            self,
+            SortColumn::Name | SortColumn::Category | SortColumn::Path
+        )
+    }
+}
+
+/// Columns offered on the Results screen
+pub const RESULTS_SORT_COLUMNS: &[SortColumn] = &[
+    SortColumn::Size,
+    SortColumn::Name,
+    SortColumn::Age,
+    SortColumn::Modified,
+    SortColumn::Path,
+    SortColumn::Category,
+];
+
+/// Columns offered on the Disk Insights screen (folder nodes carry no timestamps)
+pub const DISK_INSIGHTS_SORT_COLUMNS: &[SortColumn] = &[
+    SortColumn::Size,
+    SortColumn::Name,
+    SortColumn::Files,
+    SortColumn::Category,
+];
+
+/// Active sort for a table: a column plus a direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl Default for TableSort {
+    fn default() -> Self {
+        Self::new(SortColumn::Size)
+    }
+}
+
+impl TableSort {
+    pub fn new(column: SortColumn) -> Self {
+        Self {
+            column,
+            descending: column.default_descending(),
+        }
+    }
+
+    /// Move to the next column in `columns`, resetting to its natural direction
+    pub fn next_column(self, columns: &[SortColumn]) -> Self {
+        let pos = columns.iter().position(|c| *c == self.column);
+        let next = match pos {
+            Some(i) => columns[(i + 1) % columns.len()],
+            None => columns.first().copied().unwrap_or(SortColumn::Size),
+        };
+        Self::new(next)
+    }
+
+    /// Flip the direction, keeping the column
+    pub fn reversed(self) -> Self {
+        Self {
+            column: self.column,
+            descending: !self.descending,
+        }
+    }
+
+    /// Short label for headers/footers, e.g. "Size ↓"
+    pub fn label(self) -> String {
+        format!(
+            "{} {}",
+            self.column.label(),
+            if self.descending { "↓" } else { "↑" }
+        )
+    }
+
+    /// Compare two rows by the active column.
+    ///
+    /// Ties (and rows with no value for the column, which always sort last)
+    /// fall back to size descending, then name ascending, so the order is
+    /// stable whichever column is active.
+    pub fn compare<T: SortableRow + ?Sized>(self, a: &T, b: &T) -> Ordering {
+        let primary = match self.column {
+            SortColumn::Size => Some(a.sort_size().cmp(&b.sort_size())),
+            SortColumn::Name => Some(a.sort_name().cmp(&b.sort_name())),
+            SortColumn::Category => Some(a.sort_category().cmp(b.sort_category())),
+            SortColumn::Path => Some(a.sort_path().cmp(&b.sort_path())),
+            SortColumn::Age | SortColumn::Modified | SortColumn::Files => {
+                let (x, y) = match self.column {
+                    SortColumn::Age => (a.sort_age_days(), b.sort_age_days()),
+                    SortColumn::Modified => (a.sort_modified_days(), b.sort_modified_days()),
+                    _ => (a.sort_files(), b.sort_files()),
+                };
+                match (x, y) {
+                    (Some(x), Some(y)) => Some(x.cmp(&y)),
+                    (Some(_), None) => return Ordering::Less,
+                    (None, Some(_)) => return Ordering::Greater,
+                    (None, None) => None,
+                }
+            }
+        };
+
+        let primary = primary.map(|o| if self.descending { o.reverse() } else { o });
+        match primary {
+            Some(o) if o != Ordering::Equal => o,
+            _ => b
+                .sort_size()
+                .cmp(&a.sort_size())
+                .then_with(|| a.sort_name().cmp(&b.sort_name())),
+        }
+    }
+}
+
+impl From<crate::disk_usage::SortBy> for TableSort {
+    fn from(sort_by: crate::disk_usage::SortBy) -> Self {
+        use crate::disk_usage::SortBy;
+        Self::new(match sort_by {
+            SortBy::Size => SortColumn::Size,
+            SortBy::Name => SortColumn::Name,
+            SortBy::Files => SortColumn::Files,
+        })
+    }
+}
+
+/// A row that can be ordered by the shared table sort
+pub trait SortableRow {
+    fn sort_size(&self) -> u64;
+    /// Lowercased display name
+    fn sort_name(&self) -> String;
+    fn sort_age_days(&self) -> Option<u64> {
+        None
+    }
+    /// Days since last modified
+    fn sort_modified_days(&self) -> Option<u64> {
+        None
+    }
+    fn sort_category(&self) -> &str {
+        ""
+    }
+    /// Lowercased full path; rows without one sort by name
+    fn sort_path(&self) -> String {
+        self.sort_name()
+    }
+    fn sort_files(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Sort rows in place by `sort` (stable)
+pub fn sort_rows<T: SortableRow>(rows: &mut [T], sort: TableSort) {
+    rows.sort_by(|a, b| sort.compare(a, b));
+}
+
+/// Sort indices into `items` in place by `sort` (stable)
+pub fn sort_indices<T: SortableRow>(indices: &mut [usize], items: &[T], sort: TableSort) {
+    indices.sort_by(|&a, &b| match (items.get(a), items.get(b)) {
+        (Some(x), Some(y)) => sort.compare(x, y),
+        _ => a.cmp(&b),
+    });
+}
+
+fn file_name_lower(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| path.display().to_string().to_lowercase())
+}
+
+impl SortableRow for ResultItem {
+    fn sort_size(&self) -> u64 {
+        self.size_bytes
+    }
+    fn sort_name(&self) -> String {
+        match self.display_name.as_ref() {
+            Some(name) => name.to_lowercase(),
+            None => file_name_lower(&self.path),
+        }
+    }
+    fn sort_age_days(&self) -> Option<u64> {
+        self.age_days
+    }
+    fn sort_modified_days(&self) -> Option<u64> {
+        self.modified
+            .and_then(|t| t.elapsed().ok())
+            .map(|d| d.as_secs() / 86400)
+    }
+    fn sort_category(&self) -> &str {
+        &self.category
+    }
+    fn sort_path(&self) -> String {
+        self.path.display().to_string().to_lowercase()
+    }
+}
+
+impl SortableRow for FolderGroup {
+    fn sort_size(&self) -> u64 {
+        self.total_size
+    }
+    fn sort_name(&self) -> String {
+        self.folder_name.to_lowercase()
+    }
+}
+
+impl SortableRow for FolderNode {
+    fn sort_size(&self) -> u64 {
+        self.size
+    }
+    fn sort_name(&self) -> String {
+        self.name.to_lowercase()
+    }
+    fn sort_category(&self) -> &str {
+        "folder"
+    }
+    fn sort_files(&self) -> Option<u64> {
+        Some(self.file_count)
+    }
+}
+
+impl SortableRow for FileInfo {
+    fn sort_size(&self) -> u64 {
+        self.size
+    }
+    fn sort_name(&self) -> String {
+        self.name.to_lowercase()
+    }
+    fn sort_category(&self) -> &str {
+        crate::utils::detect_file_type(&self.path).as_str()
+    }
+}
+
+/// Totals footer shown under a sortable table, e.g.
+/// " 42 items · 1.2 GB · sorted by Size ↓ (filtered) "
+pub fn totals_footer(
+    count: usize,
+    total_bytes: u64,
+    sort: TableSort,
+    filtered: bool,
+) -> Line<'static> {
+    let mut spans = vec![
+        Span::styled(format!(" {} items", count), Styles::emphasis()),
+        Span::styled(" · ", Styles::secondary()),
+        Span::styled(bytesize::to_string(total_bytes, false), Styles::emphasis()),
+        Span::styled(" · sorted by ", Styles::secondary()),
+        Span::styled(sort.label(), Styles::primary()),
+    ];
+    if filtered {
+        spans.push(Span::styled(" (filtered)", Styles::secondary()));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans)
+}
 
 /// Render the results table
 #[allow(clippy::too_many_arguments)]
@@ -157,3 +437,86 @@ pub fn render_results_table(
 
     f.render_widget(table, table_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn item(name: &str, size: u64, age: Option<u64>, category: &str) -> ResultItem {
+        ResultItem {
+            path: PathBuf::from(format!("/x/{}", name)),
+            size_bytes: size,
+            age_days: age,
+            last_opened: None,
+            created: None,
+            accessed: None,
+            modified: age.map(|days| {
+                // Modified in the reverse order of access, to tell the columns apart
+                std::time::SystemTime::now() - std::time::Duration::from_secs((10 - days) * 86400)
+            }),
+            category: category.to_string(),
+            safe: true,
+            display_name: None,
+            hint: None,
+            in_use: false,
+        }
+    }
+
+    fn names(items: &[ResultItem]) -> Vec<String> {
+        items.iter().map(|i| i.sort_name()).collect()
+    }
+
+    #[test]
+    fn sorts_by_each_column_with_stable_tiebreak() {
+        let mut items = vec![
+            item("b", 10, Some(3), "Temp Files"),
+            item("a", 10, None, "Cache"),
+            item("c", 30, Some(9), "Cache"),
+        ];
+
+        sort_rows(&mut items, TableSort::new(SortColumn::Size));
+        assert_eq!(names(&items), ["c", "a", "b"]);
+
+        sort_rows(&mut items, TableSort::new(SortColumn::Name));
+        assert_eq!(names(&items), ["a", "b", "c"]);
+
+        // Oldest first; unknown age goes last
+        sort_rows(&mut items, TableSort::new(SortColumn::Age));
+        assert_eq!(names(&items), ["c", "b", "a"]);
+        sort_rows(&mut items, TableSort::new(SortColumn::Age).reversed());
+        assert_eq!(names(&items), ["b", "c", "a"]);
+        sort_rows(&mut items, TableSort::new(SortColumn::Modified));
+        assert_eq!(names(&items), ["b", "c", "a"]);
+
+        // Same category falls back to size desc, then name
+        sort_rows(&mut items, TableSort::new(SortColumn::Category));
+        assert_eq!(names(&items), ["c", "a", "b"]);
+
+        sort_rows(&mut items, TableSort::new(SortColumn::Path));
+        assert_eq!(names(&items), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn next_column_cycles_and_resets_direction() {
+        let sort = TableSort::new(SortColumn::Size).reversed();
+        let next = sort.next_column(RESULTS_SORT_COLUMNS);
+        assert_eq!(next.column, SortColumn::Name);
+        assert!(!next.descending);
+
+        let last = TableSort::new(SortColumn::Category).next_column(RESULTS_SORT_COLUMNS);
+        assert_eq!(last, TableSort::new(SortColumn::Size));
+
+        // Column not offered on this screen starts over
+        let age = TableSort::new(SortColumn::Age).next_column(DISK_INSIGHTS_SORT_COLUMNS);
+        assert_eq!(age.column, SortColumn::Size);
+    }
+
+    #[test]
+    fn sort_indices_orders_by_referenced_items() {
+        let items = vec![item("a", 1, None, "x"), item("b", 5, None, "x")];
+        let mut idx = vec![0, 1];
+        sort_indices(&mut idx, &items, TableSort::default());
+        assert_eq!(idx, [1, 0]);
+    }
+}
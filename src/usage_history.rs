@@ -0,0 +1,473 @@
+//! Disk usage history
+//!
+//! Every fresh Disk Insights scan records the size of each folder in its
+//! tree, so sizes can be followed over time. Storing the whole tree each run
+//! would grow without bound, so each scanned root gets one append-only log
+//! (`usage_history/<root hash>.jsonl`) whose first line is a full snapshot
+//! and every later line only the folders that changed, appeared or
+//! disappeared since the previous one.
+//!
+//! Every [`COMPACT_EVERY`] records the log is compacted: the snapshots are
+//! thinned (all of the last week, the latest per day for three months, the
+//! latest per week before that) and re-encoded, and the oldest are dropped
+//! until the log fits in [`MAX_LOG_BYTES`].
+//!
+//! With `[history] compress` set, new and compacted logs are stored
+//! zstd-compressed (appends add a frame); reading detects the format from
+//! the content, so a log keeps its name either way. A gzip log from an older
+//! version is rewritten in zstd on its next append.
+
+use crate::disk_usage::{DiskInsights, FolderNode};
+use crate::history::Encoding;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Records appended between compactions
+pub const COMPACT_EVERY: usize = 20;
+
+/// Size budget of one root's log after compaction (bytes)
+pub const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Every snapshot of this age or younger is kept by compaction
+const KEEP_ALL_DAYS: i64 = 7;
+
+/// Older snapshots keep one per day up to this age, then one per week
+const KEEP_DAILY_DAYS: i64 = 90;
+
+/// Folder sizes at one point in time, keyed by path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub time: DateTime<Utc>,
+    pub sizes: BTreeMap<String, u64>,
+}
+
+impl Snapshot {
+    /// Size of every folder in a Disk Insights tree
+    pub fn of(insights: &DiskInsights, time: DateTime<Utc>) -> Self {
+        fn walk(node: &FolderNode, sizes: &mut BTreeMap<String, u64>) {
+            sizes.insert(key(&node.path), node.size);
+            for child in &node.children {
+                walk(child, sizes);
+            }
+        }
+        let mut sizes = BTreeMap::new();
+        walk(&insights.root, &mut sizes);
+        Self { time, sizes }
+    }
+}
+
+/// One line of a log: a full snapshot, or the changes since the previous one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Record {
+    time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    full: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    sizes: BTreeMap<String, u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    removed: Vec<String>,
+}
+
+impl Record {
+    fn full(snapshot: &Snapshot) -> Self {
+        Self {
+            time: snapshot.time,
+            full: true,
+            sizes: snapshot.sizes.clone(),
+            removed: Vec::new(),
+        }
+    }
+
+    fn delta(previous: &BTreeMap<String, u64>, snapshot: &Snapshot) -> Self {
+        Self {
+            time: snapshot.time,
+            full: false,
+            sizes: snapshot
+                .sizes
+                .iter()
+                .filter(|(path, size)| previous.get(*path) != Some(size))
+                .map(|(path, size)| (path.clone(), *size))
+                .collect(),
+            removed: previous
+                .keys()
+                .filter(|path| !snapshot.sizes.contains_key(*path))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Apply this record to the state of the previous snapshot
+    fn apply(&self, state: &mut BTreeMap<String, u64>) {
+        if self.full {
+            state.clear();
+        }
+        for path in &self.removed {
+            state.remove(path);
+        }
+        state.extend(self.sizes.iter().map(|(p, s)| (p.clone(), *s)));
+    }
+}
+
+fn key(path: &Path) -> String {
+    path.display().to_string()
+}
+
+/// The usage history of one scanned root
+#[derive(Debug, Clone)]
+pub struct UsageHistory {
+    log_path: PathBuf,
+    /// Write new and compacted logs compressed
+    compress: bool,
+}
+
+impl UsageHistory {
+    /// History of `root`, kept next to the deletion history
+    pub fn open(root: &Path) -> Result<Self> {
+        let hash = blake3::hash(key(root).to_lowercase().as_bytes());
+        let mut history = Self::at(history_dir()?.join(format!("{}.jsonl", &hash.to_hex()[..16])));
+        history.compress = crate::config::Config::load().history.compress;
+        Ok(history)
+    }
+
+    /// History stored in `log_path`
+    pub fn at(log_path: PathBuf) -> Self {
+        Self {
+            log_path,
+            compress: false,
+        }
+    }
+
+    /// Store this log compressed from its next write on
+    pub fn compressed(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    fn records(&self) -> Result<Vec<Record>> {
+        let content = match crate::history::read_text(&self.log_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", self.log_path.display()))
+            }
+        };
+        // A line cut short by a crash mid-append is skipped
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Append `snapshot`, compacting the log every [`COMPACT_EVERY`] records
+    pub fn record(&self, snapshot: &Snapshot) -> Result<()> {
+        let records = self.records()?;
+        let record = if records.is_empty() {
+            Record::full(snapshot)
+        } else {
+            let mut state = BTreeMap::new();
+            for r in &records {
+                r.apply(&mut state);
+            }
+            Record::delta(&state, snapshot)
+        };
+
+        // Appending keeps the log's current form, moving gzip logs to zstd; a
+        // new log follows the setting
+        let stored = if records.is_empty() {
+            None
+        } else {
+            Some(stored_encoding(&self.log_path))
+        };
+        let line = format!("{}\n", serde_json::to_string(&record)?);
+        if stored == Some(Encoding::Gzip) {
+            self.rewrite_with(&line)?;
+        } else {
+            let compress = stored.map_or(self.compress, |e| e == Encoding::Zstd);
+            self.append(&line, compress, stored.is_none())?;
+        }
+
+        if (records.len() + 1) % COMPACT_EVERY == 0 {
+            self.compact(snapshot.time)?;
+        }
+        Ok(())
+    }
+
+    /// Append `line`; `fresh` starts the log over
+    fn append(&self, line: &str, compress: bool, fresh: bool) -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .with_context(|| format!("Failed to open {}", self.log_path.display()))?;
+        if fresh {
+            // Drop a line cut short by a crash so the log starts clean
+            file.set_len(0)?;
+        }
+        file.write_all(&crate::history::encode_text(line, compress)?)?;
+        Ok(())
+    }
+
+    /// Rewrite a gzip log in zstd with `line` added, instead of mixing
+    /// formats in one file
+    fn rewrite_with(&self, line: &str) -> Result<()> {
+        let mut text = crate::history::read_text(&self.log_path)
+            .with_context(|| format!("Failed to read {}", self.log_path.display()))?;
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(line);
+        self.replace(crate::history::encode_text(&text, true)?)
+    }
+
+    fn replace(&self, encoded: Vec<u8>) -> Result<()> {
+        let tmp = self.log_path.with_extension("jsonl.tmp");
+        fs::write(&tmp, encoded).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.log_path)
+            .with_context(|| format!("Failed to replace {}", self.log_path.display()))
+    }
+
+    /// Every stored snapshot, oldest first
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        let mut state = BTreeMap::new();
+        Ok(self
+            .records()?
+            .into_iter()
+            .map(|record| {
+                record.apply(&mut state);
+                Snapshot {
+                    time: record.time,
+                    sizes: state.clone(),
+                }
+            })
+            .collect())
+    }
+
+    /// Size of `folder` at each snapshot that included it, oldest first.
+    /// Only that folder's entry is followed through the log, so this doesn't
+    /// rebuild every snapshot.
+    pub fn size_over_time(&self, folder: &Path) -> Result<Vec<(DateTime<Utc>, u64)>> {
+        let folder = key(folder);
+        let mut size = None;
+        let mut points = Vec::new();
+        for record in self.records()? {
+            if record.full || record.removed.contains(&folder) {
+                size = None;
+            }
+            if let Some(s) = record.sizes.get(&folder) {
+                size = Some(*s);
+            }
+            if let Some(s) = size {
+                points.push((record.time, s));
+            }
+        }
+        Ok(points)
+    }
+
+    /// Thin out old snapshots and rewrite the log within [`MAX_LOG_BYTES`]
+    pub fn compact(&self, now: DateTime<Utc>) -> Result<()> {
+        let mut kept = thin(self.snapshots()?, now);
+        let mut encoded = crate::history::encode_text(&encode(&kept)?, self.compress)?;
+        while encoded.len() as u64 > MAX_LOG_BYTES && kept.len() > 1 {
+            kept.remove(0);
+            encoded = crate::history::encode_text(&encode(&kept)?, self.compress)?;
+        }
+        self.replace(encoded)
+    }
+}
+
+/// Keep every recent snapshot, then the latest per day, then per week
+fn thin(snapshots: Vec<Snapshot>, now: DateTime<Utc>) -> Vec<Snapshot> {
+    let mut seen = HashSet::new();
+    let mut kept: Vec<Snapshot> = snapshots
+        .into_iter()
+        .rev()
+        .filter(|snapshot| {
+            let age = now - snapshot.time;
+            if age <= Duration::days(KEEP_ALL_DAYS) {
+                return true;
+            }
+            let bucket = if age <= Duration::days(KEEP_DAILY_DAYS) {
+                snapshot.time.date_naive().to_string()
+            } else {
+                let week = snapshot.time.date_naive().iso_week();
+                format!("{}-W{}", week.year(), week.week())
+            };
+            seen.insert(bucket)
+        })
+        .collect();
+    kept.reverse();
+    kept
+}
+
+fn encode(snapshots: &[Snapshot]) -> Result<String> {
+    let mut out = String::new();
+    let mut previous: Option<&Snapshot> = None;
+    for snapshot in snapshots {
+        let record = match previous {
+            None => Record::full(snapshot),
+            Some(previous) => Record::delta(&previous.sizes, snapshot),
+        };
+        out.push_str(&serde_json::to_string(&record)?);
+        out.push('\n');
+        previous = Some(snapshot);
+    }
+    Ok(out)
+}
+
+/// How the log at `path` is stored
+fn stored_encoding(path: &Path) -> Encoding {
+    use std::io::Read;
+    let mut magic = [0u8; 4];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .map(|_| Encoding::of(&magic))
+        .unwrap_or(Encoding::Plain)
+}
+
+fn history_dir() -> Result<PathBuf> {
+    let dir = crate::history::get_history_dir()?.with_file_name("usage_history");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Compact every root's log, storing it compressed when `compress` is set.
+/// Returns how many logs were compacted and their total size before and after.
+pub fn compact_all(compress: bool, now: DateTime<Utc>) -> Result<(usize, u64, u64)> {
+    let size = |path: &Path| fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let (mut count, mut before, mut after) = (0, 0, 0);
+    for entry in fs::read_dir(history_dir()?)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        before += size(&path);
+        UsageHistory::at(path.clone())
+            .compressed(compress)
+            .compact(now)?;
+        after += size(&path);
+        count += 1;
+    }
+    Ok((count, before, after))
+}
+
+/// Record a fresh Disk Insights scan of `root`
+pub fn record_insights(root: &Path, insights: &DiskInsights) -> Result<()> {
+    UsageHistory::open(root)?.record(&Snapshot::of(insights, Utc::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn snapshot(days_ago: i64, now: DateTime<Utc>, sizes: &[(&str, u64)]) -> Snapshot {
+        Snapshot {
+            time: now - Duration::days(days_ago),
+            sizes: sizes.iter().map(|(p, s)| (p.to_string(), *s)).collect(),
+        }
+    }
+
+    #[test]
+    fn compressed_logs_append_and_replay() {
+        let temp = TempDir::new().unwrap();
+        let history = UsageHistory::at(temp.path().join("root.jsonl")).compressed(true);
+        let now = Utc.with_ymd_and_hms(2025, 6, 18, 12, 0, 0).unwrap();
+
+        history.record(&snapshot(2, now, &[("/r", 10)])).unwrap();
+        // Appending to a compressed log adds a member even if the setting changed
+        let history = UsageHistory::at(temp.path().join("root.jsonl"));
+        history.record(&snapshot(1, now, &[("/r", 12)])).unwrap();
+
+        let bytes = fs::read(temp.path().join("root.jsonl")).unwrap();
+        assert!(crate::history::is_compressed(&bytes));
+        let sizes: Vec<u64> = history
+            .size_over_time(Path::new("/r"))
+            .unwrap()
+            .into_iter()
+            .map(|(_, size)| size)
+            .collect();
+        assert_eq!(sizes, vec![10, 12]);
+    }
+
+    #[test]
+    fn gzip_logs_move_to_zstd_on_append() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("root.jsonl");
+        let now = Utc.with_ymd_and_hms(2025, 6, 18, 12, 0, 0).unwrap();
+        let first = format!(
+            "{}\n",
+            serde_json::to_string(&Record::full(&snapshot(2, now, &[("/r", 10)]))).unwrap()
+        );
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(first.as_bytes()).unwrap();
+        fs::write(&path, gzip.finish().unwrap()).unwrap();
+
+        let history = UsageHistory::at(path.clone());
+        history.record(&snapshot(1, now, &[("/r", 12)])).unwrap();
+
+        assert_eq!(stored_encoding(&path), Encoding::Zstd);
+        let sizes: Vec<u64> = history
+            .size_over_time(Path::new("/r"))
+            .unwrap()
+            .into_iter()
+            .map(|(_, size)| size)
+            .collect();
+        assert_eq!(sizes, vec![10, 12]);
+    }
+
+    #[test]
+    fn deltas_replay_to_snapshots_and_compaction_thins_old_ones() {
+        let temp = TempDir::new().unwrap();
+        let history = UsageHistory::at(temp.path().join("root.jsonl"));
+        // A Wednesday: 200 and 199 days earlier fall in the same ISO week
+        let now = Utc.with_ymd_and_hms(2025, 6, 18, 12, 0, 0).unwrap();
+
+        history
+            .record(&snapshot(200, now, &[("/r", 10), ("/r/a", 6), ("/r/b", 4)]))
+            .unwrap();
+        history
+            .record(&snapshot(199, now, &[("/r", 12), ("/r/a", 8), ("/r/b", 4)]))
+            .unwrap();
+        history
+            .record(&snapshot(100, now, &[("/r", 5), ("/r/b", 5)]))
+            .unwrap();
+        history
+            .record(&snapshot(1, now, &[("/r", 9), ("/r/a", 4), ("/r/b", 5)]))
+            .unwrap();
+
+        // Only what changed is stored after the first line
+        let records = history.records().unwrap();
+        assert!(records[0].full);
+        assert_eq!(records[1].sizes.len(), 2);
+        assert_eq!(records[2].removed, vec!["/r/a".to_string()]);
+
+        assert_eq!(
+            history.snapshots().unwrap()[2],
+            snapshot(100, now, &[("/r", 5), ("/r/b", 5)])
+        );
+        let sizes: Vec<u64> = history
+            .size_over_time(Path::new("/r/a"))
+            .unwrap()
+            .into_iter()
+            .map(|(_, size)| size)
+            .collect();
+        assert_eq!(sizes, vec![6, 8, 4]);
+
+        // Of the two snapshots in one week only the later stays
+        history.compact(now).unwrap();
+        let snapshots = history.snapshots().unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(
+            snapshots[0],
+            snapshot(199, now, &[("/r", 12), ("/r/a", 8), ("/r/b", 4)])
+        );
+        assert_eq!(history.size_over_time(Path::new("/r")).unwrap().len(), 3);
+    }
+}
@@ -3,7 +3,9 @@
 //! This module contains common functions used across multiple category scanners
 //! to reduce code duplication and ensure consistent behavior.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 /// Get the root disk path for the current system
 ///
@@ -37,6 +39,145 @@ pub fn get_root_disk_path() -> PathBuf {
     }
 }
 
+/// Volume a path lives on, in the form users type it: the drive (`C:`) or
+/// UNC share (`\\server\share`) on Windows, `/` elsewhere. Paths inside a
+/// volume mounted into a folder (`mountvol C:\Data ...`) belong to that
+/// folder (`C:\Data`), not to the drive holding it.
+pub fn volume_of(path: &Path) -> String {
+    mounted_volume_label(drive_of(path), mount_root(path).as_deref())
+}
+
+/// `drive`, or the folder a different volume is mounted at
+fn mounted_volume_label(drive: String, mount_root: Option<&Path>) -> String {
+    let Some(root) = mount_root else {
+        return drive;
+    };
+    let root = display_path(root);
+    let root = root.trim_end_matches(['\\', '/']);
+    if root.is_empty() || root.eq_ignore_ascii_case(&drive) {
+        drive
+    } else {
+        root.to_string()
+    }
+}
+
+/// Mount point of the volume holding `path` (e.g. `C:\` or `C:\mnt\data\`)
+#[cfg(windows)]
+pub fn mount_root(path: &Path) -> Option<PathBuf> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetVolumePathNameW;
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut buf = [0u16; 1024];
+    // SAFETY: `wide` is NUL-terminated and `buf` is a valid output buffer.
+    unsafe { GetVolumePathNameW(PCWSTR(wide.as_ptr()), &mut buf) }.ok()?;
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    Some(PathBuf::from(String::from_utf16_lossy(&buf[..len])))
+}
+
+/// Volumes are only reported per drive outside Windows
+#[cfg(not(windows))]
+pub fn mount_root(_path: &Path) -> Option<PathBuf> {
+    None
+}
+
+/// Whether another volume is mounted at the folder `dir`
+pub fn is_mount_point(dir: &Path) -> bool {
+    #[cfg(windows)]
+    {
+        // Mount points are reparse points the volume path resolves to
+        is_windows_reparse_point(dir)
+            && mount_root(dir).is_some_and(|root| {
+                root.to_string_lossy()
+                    .trim_end_matches('\\')
+                    .eq_ignore_ascii_case(dir.to_string_lossy().trim_end_matches('\\'))
+            })
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let device = |p: &Path| std::fs::symlink_metadata(p).ok().map(|m| m.dev());
+        match (device(dir), dir.parent().and_then(device)) {
+            (Some(dir), Some(parent)) => dir != parent,
+            _ => false,
+        }
+    }
+    #[cfg(not(any(windows, unix)))]
+    {
+        let _ = dir;
+        false
+    }
+}
+
+/// Drive or UNC share at the start of `path`
+fn drive_of(path: &Path) -> String {
+    use std::path::{Component, Prefix};
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                format!("{}:", (letter as char).to_ascii_uppercase())
+            }
+            Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => format!(
+                r"\\{}\{}",
+                server.to_string_lossy(),
+                share.to_string_lossy()
+            ),
+            _ => prefix.as_os_str().to_string_lossy().into_owned(),
+        },
+        _ => "/".to_string(),
+    }
+}
+
+/// Normalize a volume typed by the user (`d`, `D:`, `d:\`) to the form
+/// returned by `volume_of`
+pub fn normalize_volume(volume: &str) -> String {
+    let trimmed = volume.trim().trim_end_matches(['\\', '/']);
+    let mut chars = trimmed.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (None, ..) => "/".to_string(),
+        (Some(letter), None | Some(':'), None) if letter.is_ascii_alphabetic() => {
+            format!("{}:", letter.to_ascii_uppercase())
+        }
+        _ => trimmed.to_string(),
+    }
+}
+
+/// Whether `path` lives on `volume` (as returned by `normalize_volume`)
+pub fn is_on_volume(path: &Path, volume: &str) -> bool {
+    volume_of(path).eq_ignore_ascii_case(volume)
+}
+
+/// When a path was created, last modified and last accessed, where the
+/// filesystem records it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileTimes {
+    pub created: Option<std::time::SystemTime>,
+    pub modified: Option<std::time::SystemTime>,
+    pub accessed: Option<std::time::SystemTime>,
+}
+
+/// Timestamps of `path` (all `None` if it can't be read)
+pub fn file_times(path: &Path) -> FileTimes {
+    std::fs::metadata(path)
+        .map(|meta| FileTimes::from_metadata(&meta))
+        .unwrap_or_default()
+}
+
+impl FileTimes {
+    pub fn from_metadata(meta: &std::fs::Metadata) -> Self {
+        Self {
+            created: meta.created().ok(),
+            modified: meta.modified().ok(),
+            accessed: meta.accessed().ok(),
+        }
+    }
+}
+
 /// Normalize a path for display (strip Windows long-path prefixes).
 pub fn display_path(path: &Path) -> String {
     let path_str = path.to_string_lossy().to_string();
@@ -176,13 +317,15 @@ pub fn safe_remove_dir_all(path: &Path) -> std::io::Result<()> {
 /// - Issues with OneDrive placeholder files
 pub fn should_skip_entry(path: &Path) -> bool {
     // Check for symlink via symlink_metadata
-    if let Ok(meta) = std::fs::symlink_metadata(path) {
-        if meta.file_type().is_symlink() {
-            return true;
-        }
-    }
+    let is_symlink = std::fs::symlink_metadata(path)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false);
     // Check for Windows reparse points (junctions, OneDrive placeholders)
-    is_windows_reparse_point(path)
+    let skip = is_symlink || is_windows_reparse_point(path);
+    if skip {
+        crate::recording::note(crate::recording::DecisionKind::SkippedLink, path);
+    }
+    skip
 }
 
 /// Returns true if this path is a Windows reparse point (junction/symlink/mount point).
@@ -209,6 +352,30 @@ pub fn is_windows_reparse_point(path: &Path) -> bool {
     }
 }
 
+/// Windows attribute bits marking an online-only cloud file (OneDrive Files
+/// On-Demand and other cloud sync providers): offline, recall on open and
+/// recall on data access
+const CLOUD_PLACEHOLDER_ATTRIBUTES: u32 = 0x1000 | 0x0004_0000 | 0x0040_0000;
+
+/// Whether a file with these Windows attributes is an online-only cloud
+/// placeholder, whose listed size is in the cloud rather than on this disk
+pub fn is_placeholder_attributes(attributes: u32) -> bool {
+    attributes & CLOUD_PLACEHOLDER_ATTRIBUTES != 0
+}
+
+/// Bytes a file takes up on this disk: its length, or 0 for an online-only
+/// cloud placeholder
+pub fn local_len(meta: &std::fs::Metadata) -> u64 {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        if is_placeholder_attributes(meta.file_attributes()) {
+            return 0;
+        }
+    }
+    meta.len()
+}
+
 /// Calculate total size of a directory tree using parallel traversal.
 ///
 /// Uses jwalk for parallel directory traversal which is 2-4x faster than sequential.
@@ -220,75 +387,96 @@ pub fn is_windows_reparse_point(path: &Path) -> bool {
 /// - Handle symlinks and reparse points safely (don't follow)
 /// - Limit depth to prevent runaway scans
 /// - Handle Windows long paths (>260 chars) gracefully
+/// - Count online-only cloud placeholders as 0 bytes (see `local_len`)
+/// - For walks of a quick estimate (`wole scan --quick`, see `SizeWalk`),
+///   extrapolate from a seeded sample of the top-level subfolders
+///
+/// Walks run by a scan go through `calculate_dir_size_in` instead, so they
+/// stop and sample with it.
 pub fn calculate_dir_size(path: &Path) -> u64 {
-    use jwalk::WalkDir;
-    use std::sync::atomic::{AtomicU64, Ordering};
+    calculate_dir_size_in(path, &SizeWalk::default())
+}
 
-    const MAX_DEPTH: usize = 15;
+/// `calculate_dir_size` as part of a scan: stops early, with the size counted
+/// so far, once the scan's token is cancelled, and samples for its estimate
+pub fn calculate_dir_size_in(path: &Path, walk: &SizeWalk) -> u64 {
+    calculate_dir_size_with_progress(path, walk, &|_: &Path| {})
+}
 
-    let total = AtomicU64::new(0);
+/// Calculate directory size and emit progress for each file visited.
+///
+/// Uses the same traversal rules as calculate_dir_size_in().
+pub fn calculate_dir_size_with_progress<F>(path: &Path, walk: &SizeWalk, on_path: &F) -> u64
+where
+    F: Fn(&Path) + Sync,
+{
+    dir_size(path, on_path, walk)
+}
 
-    WalkDir::new(path)
-        .max_depth(MAX_DEPTH)
-        .follow_links(false)
-        .parallelism(jwalk::Parallelism::RayonDefaultPool {
-            busy_timeout: std::time::Duration::from_secs(1),
-        })
-        .process_read_dir(|_depth, _path, _state, children| {
-            // Skip directories we don't want to descend into
-            children.retain(|entry| {
-                if let Ok(ref e) = entry {
-                    if e.file_type().is_symlink() {
-                        return false;
-                    }
-                    if e.file_type().is_dir() {
-                        if let Some(name) = e.path().file_name() {
-                            let name_str = name.to_string_lossy();
-                            // Skip .git internals
-                            if name_str == ".git" {
-                                return false;
-                            }
-                        }
-                    }
-                }
-                true
-            });
-        })
-        .into_iter()
-        .for_each(|entry| {
-            if let Ok(e) = entry {
-                if e.file_type().is_file() {
-                    if let Ok(meta) = e.metadata() {
-                        total.fetch_add(meta.len(), Ordering::Relaxed);
-                    }
-                }
-            }
-        });
+/// What the folder size walks of one scan share: the scan's cancel token and
+/// its quick estimate, if any. The default, for walks outside a scan, never
+/// stops and walks every folder.
+#[derive(Debug, Clone, Default)]
+pub struct SizeWalk {
+    pub cancel: Option<crate::tasks::CancelToken>,
+    pub estimate: Option<std::sync::Arc<crate::estimate::Estimate>>,
+}
 
-    total.load(Ordering::Relaxed)
+impl SizeWalk {
+    /// Walks that stop once `cancel` is cancelled
+    pub fn cancellable(cancel: &crate::tasks::CancelToken) -> Self {
+        Self {
+            cancel: Some(cancel.clone()),
+            estimate: None,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(crate::tasks::CancelToken::is_cancelled)
+    }
 }
 
-/// Calculate directory size and emit progress for each file visited.
-///
-/// Uses the same traversal rules as calculate_dir_size().
-pub fn calculate_dir_size_with_progress<F>(path: &Path, on_path: &F) -> u64
+/// The walk behind `calculate_dir_size`
+fn dir_size<F>(path: &Path, on_path: &F, walk: &SizeWalk) -> u64
 where
     F: Fn(&Path) + Sync,
 {
-    use jwalk::WalkDir;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
     use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
 
     const MAX_DEPTH: usize = 15;
 
-    let total = AtomicU64::new(0);
-
-    WalkDir::new(path)
+    // Files directly in `path`, and files inside its subfolders
+    let direct = AtomicU64::new(0);
+    let nested = AtomicU64::new(0);
+    // Subfolders of `path`: all of them, and the ones walked
+    let subdirs_total = Arc::new(AtomicU64::new(0));
+    let subdirs_walked = Arc::new(AtomicU64::new(0));
+    let estimate = walk.estimate.clone();
+    // Min-heap of the largest files seen so far
+    let mut largest: BinaryHeap<Reverse<(u64, PathBuf)>> =
+        BinaryHeap::with_capacity(LARGEST_FILES_COUNT + 1);
+
+    let (total_counter, walked_counter) = (subdirs_total.clone(), subdirs_walked.clone());
+    let walk_cancel = walk.clone();
+    // Each file entry carries its size when the platform listed the folder in
+    // batches (see fast_dir); otherwise it's read per file below
+    jwalk::WalkDirGeneric::<((), Option<u64>)>::new(path)
         .max_depth(MAX_DEPTH)
         .follow_links(false)
         .parallelism(jwalk::Parallelism::RayonDefaultPool {
             busy_timeout: std::time::Duration::from_secs(1),
         })
-        .process_read_dir(|_depth, _path, _state, children| {
+        .process_read_dir(move |depth, dir, _state, children| {
+            if walk_cancel.is_cancelled() {
+                children.clear();
+                return;
+            }
+
             // Skip directories we don't want to descend into
             children.retain(|entry| {
                 if let Ok(ref e) = entry {
@@ -307,21 +495,127 @@ where
                 }
                 true
             });
+
+            if let Some(listing) = crate::fast_dir::list(dir) {
+                let sizes: std::collections::HashMap<_, _> = listing
+                    .into_iter()
+                    .filter(|e| !e.is_dir && !e.is_symlink)
+                    .map(|e| (e.name, e.len))
+                    .collect();
+                for e in children.iter_mut().flatten() {
+                    if e.file_type().is_file() {
+                        e.client_state = sizes.get(&e.file_name).copied();
+                    }
+                }
+            }
+
+            // Quick estimate: only walk a sample of the top-level subfolders
+            let (Some(estimate), Some(0)) = (&estimate, depth) else {
+                return;
+            };
+            let subdirs: Vec<std::path::PathBuf> = children
+                .iter()
+                .flatten()
+                .filter(|e| e.file_type().is_dir())
+                .map(|e| e.path())
+                .collect();
+            let refs: Vec<&Path> = subdirs.iter().map(|p| p.as_path()).collect();
+            let keep = estimate.sample(&refs);
+            total_counter.store(subdirs.len() as u64, Ordering::Relaxed);
+            walked_counter.store(
+                keep.iter().filter(|k| **k).count() as u64,
+                Ordering::Relaxed,
+            );
+            let mut next = keep.into_iter();
+            children.retain(|entry| match entry {
+                Ok(e) if e.file_type().is_dir() => next.next().unwrap_or(true),
+                _ => true,
+            });
         })
         .into_iter()
+        .take_while(|_| !walk.is_cancelled())
         .for_each(|entry| {
             if let Ok(e) = entry {
                 if e.file_type().is_file() {
                     let path = e.path();
                     on_path(&path);
-                    if let Ok(meta) = e.metadata() {
-                        total.fetch_add(meta.len(), Ordering::Relaxed);
+                    let len = e
+                        .client_state
+                        .or_else(|| e.metadata().ok().map(|m| local_len(&m)));
+                    if let Some(len) = len {
+                        let bucket = if e.depth() <= 1 { &direct } else { &nested };
+                        bucket.fetch_add(len, Ordering::Relaxed);
+                        largest.push(Reverse((len, path)));
+                        if largest.len() > LARGEST_FILES_COUNT {
+                            largest.pop();
+                        }
                     }
                 }
             }
         });
 
-    total.load(Ordering::Relaxed)
+    let direct = direct.load(Ordering::Relaxed);
+    let nested = nested.load(Ordering::Relaxed);
+    let walked = subdirs_walked.load(Ordering::Relaxed);
+    let size = if walked == 0 {
+        direct + nested
+    } else {
+        let scale = subdirs_total.load(Ordering::Relaxed) as f64 / walked as f64;
+        direct + (nested as f64 * scale) as u64
+    };
+
+    let mut largest_files: Vec<(PathBuf, u64)> = largest
+        .into_iter()
+        .map(|Reverse((len, path))| (path, len))
+        .collect();
+    largest_files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    record_sized_dir(
+        path,
+        SizedDir {
+            size,
+            largest_files,
+        },
+    );
+    size
+}
+
+/// Number of largest descendants kept for each folder a size walk visits
+pub const LARGEST_FILES_COUNT: usize = 5;
+
+/// Folders kept by `record_sized_dir` before they're all dropped, so walks
+/// nobody takes the results of don't grow a long-running process
+const MAX_SIZED_DIRS: usize = 4096;
+
+/// What the size walk of a folder found
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SizedDir {
+    pub size: u64,
+    /// The folder's largest files (`LARGEST_FILES_COUNT` of them), largest first
+    pub largest_files: Vec<(PathBuf, u64)>,
+}
+
+/// Folders sized and not yet taken
+static SIZED_DIRS: Mutex<Option<HashMap<PathBuf, SizedDir>>> = Mutex::new(None);
+
+fn record_sized_dir(path: &Path, sized: SizedDir) {
+    let mut dirs = SIZED_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+    let dirs = dirs.get_or_insert_with(HashMap::new);
+    if dirs.len() >= MAX_SIZED_DIRS {
+        dirs.clear();
+    }
+    dirs.insert(path.to_path_buf(), sized);
+}
+
+/// What the last size walk of `path` found, if it hasn't been taken yet.
+///
+/// Lets scan results carry each folder's largest files without walking the
+/// folder a second time.
+pub fn take_sized_dir(path: &Path) -> Option<SizedDir> {
+    SIZED_DIRS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()?
+        .remove(path)
 }
 
 /// Fast size calculation for a single directory level (no recursion).
@@ -329,13 +623,21 @@ where
 /// Use this for quick estimates when you don't need exact totals.
 /// Much faster than calculate_dir_size() for large directories.
 pub fn calculate_shallow_size(path: &Path) -> u64 {
+    if let Some(listing) = crate::fast_dir::list(path) {
+        return listing
+            .iter()
+            .filter(|e| !e.is_dir && !e.is_symlink)
+            .map(|e| e.len)
+            .sum();
+    }
+
     let mut total = 0u64;
 
     if let Ok(entries) = safe_read_dir(path) {
         for entry in entries.flatten() {
             if let Ok(meta) = safe_metadata(&entry.path()) {
                 if meta.is_file() {
-                    total += meta.len();
+                    total += local_len(&meta);
                 }
             }
         }
@@ -763,6 +1065,26 @@ pub fn is_hidden(path: &Path) -> bool {
     false
 }
 
+/// Check if a file has the Windows system attribute
+#[allow(unused_variables)]
+pub fn is_system_file(path: &Path) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        if let Ok(meta) = std::fs::symlink_metadata(path) {
+            const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+            return meta.file_attributes() & FILE_ATTRIBUTE_SYSTEM != 0;
+        }
+    }
+
+    false
+}
+
+/// Hidden or system files are riskier to delete and are reported separately
+pub fn is_hidden_or_system(path: &Path) -> bool {
+    is_hidden(path) || is_system_file(path)
+}
+
 /// Convert an absolute path to a relative path based on a base directory.
 ///
 /// If the path is not under the base directory, tries to show a relative path from
@@ -934,6 +1256,14 @@ pub const SYSTEM_DIRS: &[&str] = &[
 /// - `C:\Program Files\...` is a system path (blocked) ✓
 /// - `C:\Users\...\AppData\Local\Microsoft\Windows\Caches` is NOT a system path (should NOT be blocked) ✓
 pub fn is_system_path(path: &Path) -> bool {
+    let system = contains_system_dir(path);
+    if system {
+        crate::recording::note(crate::recording::DecisionKind::SystemPath, path);
+    }
+    system
+}
+
+fn contains_system_dir(path: &Path) -> bool {
     let mut normal_component_count = 0;
     // Only check the first 2 Normal components after the root
     // This prevents false positives from paths like:
@@ -990,6 +1320,48 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn size_walks_record_the_largest_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        for i in 0..LARGEST_FILES_COUNT {
+            fs::write(dir.path().join(format!("small{}.txt", i)), vec![0u8; 10]).unwrap();
+        }
+        fs::write(dir.path().join("nested/medium.bin"), vec![0u8; 100]).unwrap();
+        fs::write(dir.path().join("nested/deeper/big.bin"), vec![0u8; 1000]).unwrap();
+
+        let size = calculate_dir_size(dir.path());
+        let sized = take_sized_dir(dir.path()).unwrap();
+        assert_eq!(sized.size, size);
+        assert_eq!(sized.largest_files.len(), LARGEST_FILES_COUNT);
+        assert_eq!(
+            sized.largest_files[0],
+            (dir.path().join("nested/deeper/big.bin"), 1000)
+        );
+        assert_eq!(
+            sized.largest_files[1],
+            (dir.path().join("nested/medium.bin"), 100)
+        );
+
+        // Taken once
+        assert!(take_sized_dir(dir.path()).is_none());
+    }
+
+    #[test]
+    fn folder_mounts_are_their_own_volume() {
+        let label =
+            |root: Option<&str>| mounted_volume_label("C:".to_string(), root.map(Path::new));
+        assert_eq!(label(None), "C:");
+        assert_eq!(label(Some(r"C:\")), "C:");
+        #[cfg(windows)]
+        assert_eq!(label(Some(r"\\?\c:\")), "C:");
+        assert_eq!(label(Some(r"C:\Data\")), r"C:\Data");
+
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("plain")).unwrap();
+        assert!(!is_mount_point(&dir.path().join("plain")));
+    }
+
     #[test]
     fn test_file_type_detection() {
         // Video files
@@ -1248,6 +1620,23 @@ mod tests {
         assert_eq!(size, 10); // 5 + 5 bytes
     }
 
+    #[test]
+    fn size_walks_stop_only_with_their_own_scan() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("nested")).unwrap();
+        fs::write(temp_dir.path().join("nested/file.txt"), "hello").unwrap();
+
+        let cancelled = crate::tasks::CancelToken::new();
+        cancelled.cancel();
+        let running = crate::tasks::CancelToken::new();
+
+        // Another scan's cancellation leaves this walk alone
+        let walk = SizeWalk::cancellable(&running);
+        assert_eq!(calculate_dir_size_in(temp_dir.path(), &walk), 5);
+        let walk = SizeWalk::cancellable(&cancelled);
+        assert_eq!(calculate_dir_size_in(temp_dir.path(), &walk), 0);
+    }
+
     #[test]
     fn test_file_type_emoji() {
         assert_eq!(FileType::Video.emoji(), "🎬");
@@ -0,0 +1,222 @@
+//! Per-volume views of scan results
+//!
+//! On multi-drive machines the drive that's actually full is usually the one
+//! that matters. `--volume D:` narrows results to one drive after scanning, and
+//! the scan summary breaks totals down per drive when results span several.
+
+use crate::output::{CategoryResult, ScanResults};
+use crate::utils;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Items and bytes found on one volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeSubtotal {
+    pub volume: String,
+    pub items: usize,
+    pub size_bytes: u64,
+}
+
+fn categories_mut(results: &mut ScanResults) -> [&mut CategoryResult; 19] {
+    [
+        &mut results.cache,
+        &mut results.app_cache,
+        &mut results.temp,
+        &mut results.trash,
+        &mut results.build,
+        &mut results.downloads,
+        &mut results.large,
+        &mut results.old,
+        &mut results.applications,
+        &mut results.browser,
+        &mut results.system,
+        &mut results.empty,
+        &mut results.duplicates,
+        &mut results.windows_update,
+        &mut results.event_logs,
+        &mut results.orphaned,
+        &mut results.custom,
+        &mut results.ide,
+        &mut results.rust,
+    ]
+}
+
+fn categories(results: &ScanResults) -> [&CategoryResult; 19] {
+    [
+        &results.cache,
+        &results.app_cache,
+        &results.temp,
+        &results.trash,
+        &results.build,
+        &results.downloads,
+        &results.large,
+        &results.old,
+        &results.applications,
+        &results.browser,
+        &results.system,
+        &results.empty,
+        &results.duplicates,
+        &results.windows_update,
+        &results.event_logs,
+        &results.orphaned,
+        &results.custom,
+        &results.ide,
+        &results.rust,
+    ]
+}
+
+/// Mount points of the fixed (non-removable) drives, e.g. `C:\` and `D:\`
+pub fn fixed_drives() -> Vec<PathBuf> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let mut drives: Vec<PathBuf> = disks
+        .list()
+        .iter()
+        .filter(|disk| !disk.is_removable())
+        .map(|disk| disk.mount_point().to_path_buf())
+        .filter(|mount| mount.is_dir())
+        .collect();
+    drives.sort();
+    drives.dedup();
+    drives
+}
+
+/// Size of a file or folder on disk
+fn path_size(path: &Path) -> u64 {
+    match std::fs::metadata(path) {
+        Ok(m) if m.is_dir() => utils::calculate_dir_size(path),
+        Ok(m) => m.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Drop the paths in `paths` that aren't on `volume` and subtract their size.
+/// Only sizes the removed paths, and only when some paths are kept.
+fn retain_on_volume(paths: &mut Vec<PathBuf>, size_bytes: &mut u64, volume: &str) {
+    let (kept, removed): (Vec<PathBuf>, Vec<PathBuf>) = std::mem::take(paths)
+        .into_iter()
+        .partition(|p| utils::is_on_volume(p, volume));
+    if kept.is_empty() {
+        *size_bytes = 0;
+    } else if !removed.is_empty() {
+        let removed_size: u64 = removed.iter().map(|p| path_size(p)).sum();
+        *size_bytes = size_bytes.saturating_sub(removed_size);
+    }
+    *paths = kept;
+}
+
+/// Keep only results on `volume` (`D:`, `d`, `D:\` are all accepted)
+pub fn filter_results(results: &mut ScanResults, volume: &str) {
+    let volume = utils::normalize_volume(volume);
+    for category in categories_mut(results) {
+        retain_on_volume(&mut category.paths, &mut category.size_bytes, &volume);
+        category.items = category.paths.len();
+        retain_on_volume(
+            &mut category.hidden_paths,
+            &mut category.hidden_bytes,
+            &volume,
+        );
+        retain_on_volume(
+            &mut category.machine_paths,
+            &mut category.machine_bytes,
+            &volume,
+        );
+    }
+    if let Some(groups) = results.duplicates_groups.as_mut() {
+        for group in groups.iter_mut() {
+            group.paths.retain(|p| utils::is_on_volume(p, &volume));
+        }
+        groups.retain(|g| g.paths.len() > 1);
+    }
+    for group in results.similar_image_groups.iter_mut() {
+        group
+            .images
+            .retain(|(p, _)| utils::is_on_volume(p, &volume));
+    }
+    results.similar_image_groups.retain(|g| g.images.len() > 1);
+    results
+        .cloud_files
+        .retain(|f| utils::is_on_volume(&f.path, &volume));
+}
+
+/// Per-volume totals, sorted by volume. A category whose paths are all on one
+/// volume contributes its reported size; mixed categories are sized per path.
+pub fn subtotals(results: &ScanResults) -> Vec<VolumeSubtotal> {
+    let mut totals: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+    for category in categories(results) {
+        let mut by_volume: BTreeMap<String, Vec<&PathBuf>> = BTreeMap::new();
+        for path in &category.paths {
+            by_volume
+                .entry(utils::volume_of(path))
+                .or_default()
+                .push(path);
+        }
+        let single = by_volume.len() == 1;
+        for (volume, paths) in by_volume {
+            let size = if single {
+                category.size_bytes
+            } else {
+                paths.iter().map(|p| path_size(p)).sum()
+            };
+            let entry = totals.entry(volume).or_default();
+            entry.0 += paths.len();
+            entry.1 += size;
+        }
+    }
+    totals
+        .into_iter()
+        .map(|(volume, (items, size_bytes))| VolumeSubtotal {
+            volume,
+            items,
+            size_bytes,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn results_filter_and_subtotal_by_volume() {
+        assert_eq!(utils::normalize_volume("d"), "D:");
+        assert_eq!(utils::normalize_volume("d:\\"), "D:");
+        assert_eq!(utils::normalize_volume(" E: "), "E:");
+        assert_eq!(utils::normalize_volume("/"), "/");
+        #[cfg(windows)]
+        assert_eq!(utils::volume_of(Path::new(r"c:\Users\me")), "C:");
+        #[cfg(not(windows))]
+        assert_eq!(utils::volume_of(Path::new("/home/me")), "/");
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.bin");
+        fs::write(&file, vec![0u8; 10]).unwrap();
+
+        let mut results = ScanResults::default();
+        results.large.paths = vec![file.clone()];
+        results.large.items = 1;
+        results.large.size_bytes = 10;
+
+        let here = utils::volume_of(&file);
+        let totals = subtotals(&results);
+        assert_eq!(
+            totals,
+            vec![VolumeSubtotal {
+                volume: here.clone(),
+                items: 1,
+                size_bytes: 10
+            }]
+        );
+
+        let mut same = results.clone();
+        filter_results(&mut same, &here);
+        assert_eq!(same.large.items, 1);
+        assert_eq!(same.large.size_bytes, 10);
+
+        filter_results(&mut results, "Q:");
+        assert_eq!(results.large.items, 0);
+        assert_eq!(results.large.size_bytes, 0);
+        assert!(subtotals(&results).is_empty());
+    }
+}
@@ -46,9 +46,15 @@ fn test_scan_build_artifacts_inactive_project() {
         duplicates: false,
         windows_update: false,
         event_logs: false,
+        orphaned: false,
+        custom: false,
+        ide: false,
+        rust: false,
         project_age_days: 14,
         min_age_days: 30,
         min_size_bytes: 100 * 1024 * 1024,
+        summary_only: false,
+        estimate: None,
     };
 
     let config = Config::default();
@@ -94,9 +100,15 @@ fn test_scan_empty_directory() {
         duplicates: false,
         windows_update: false,
         event_logs: false,
+        orphaned: false,
+        custom: false,
+        ide: false,
+        rust: false,
         project_age_days: 14,
         min_age_days: 30,
         min_size_bytes: 100 * 1024 * 1024,
+        summary_only: false,
+        estimate: None,
     };
 
     let config = Config::default();
@@ -110,6 +122,64 @@ fn test_scan_empty_directory() {
     assert_eq!(results.build.items, 0);
 }
 
+#[test]
+fn test_scan_roots_merges_every_root() {
+    let dirs: Vec<TempDir> = (0..2).map(|_| create_test_dir()).collect();
+    let roots: Vec<PathBuf> = dirs.iter().map(|d| d.path().to_path_buf()).collect();
+    for (i, root) in roots.iter().enumerate() {
+        let content = format!("copy of file {}", i).repeat(100);
+        fs::write(root.join("a.txt"), &content).unwrap();
+        fs::write(root.join("b.txt"), &content).unwrap();
+    }
+
+    let options = ScanOptions {
+        cache: false,
+        app_cache: false,
+        temp: false,
+        trash: false,
+        build: false,
+        downloads: false,
+        large: false,
+        old: false,
+        applications: false,
+        browser: false,
+        system: false,
+        empty: false,
+        duplicates: true,
+        windows_update: false,
+        event_logs: false,
+        orphaned: false,
+        custom: false,
+        ide: false,
+        rust: false,
+        project_age_days: 14,
+        min_age_days: 30,
+        min_size_bytes: 100 * 1024 * 1024,
+        summary_only: false,
+        estimate: None,
+    };
+
+    let config = Config::default();
+    let summary_options = ScanOptions {
+        summary_only: true,
+        ..options.clone()
+    };
+    let results = scanner::scan_roots(&roots, options, &config, false).unwrap();
+    // Each root's duplicate pair is found and kept as its own group
+    assert_eq!(results.duplicates_groups.as_ref().map(|g| g.len()), Some(2));
+    for root in &roots {
+        assert!(results.duplicates.paths.iter().any(|p| p.starts_with(root)));
+    }
+
+    // A summary scan comes back with the same totals and no paths
+    let summary = scanner::scan_roots(&roots, summary_options, &config, false).unwrap();
+    assert!(summary.summary_only);
+    assert_eq!(summary.duplicates.items, results.duplicates.items);
+    assert_eq!(summary.duplicates.size_bytes, results.duplicates.size_bytes);
+    assert!(summary.duplicates.paths.is_empty());
+    assert!(summary.duplicates_groups.is_none());
+}
+
 #[test]
 fn test_config_defaults() {
     let config = Config::default();
@@ -338,3 +408,289 @@ fn test_calculate_dir_size_nested() {
     let size = utils::calculate_dir_size(&dir);
     assert_eq!(size, 8);
 }
+
+#[test]
+fn test_hidden_files_reported_separately() {
+    let temp_dir = create_test_dir();
+    let visible = temp_dir.path().join("video.mp4");
+    let hidden = temp_dir.path().join(".backup.img");
+    fs::write(&visible, "abc").unwrap();
+    fs::write(&hidden, "defgh").unwrap();
+
+    assert!(!utils::is_hidden_or_system(&visible));
+    assert!(utils::is_hidden_or_system(&hidden));
+
+    let mut result = wole::output::CategoryResult::default();
+    result.set_hidden(
+        vec![
+            (visible.clone(), 3),
+            (hidden.clone(), 5),
+            (hidden.clone(), 1),
+        ],
+        2,
+    );
+    assert_eq!(result.hidden_paths, vec![hidden.clone(), visible]);
+    assert_eq!(result.hidden_bytes, 8);
+    assert_eq!(result.items, 0);
+}
+
+#[test]
+fn test_summary_only_results_keep_totals_and_refuse_cleaning() {
+    let temp_dir = create_test_dir();
+    let file = temp_dir.path().join("setup.tmp");
+    fs::write(&file, "abcdef").unwrap();
+
+    let mut results = wole::output::ScanResults {
+        temp: wole::output::CategoryResult {
+            items: 1,
+            size_bytes: 6,
+            paths: vec![file.clone()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    results.summarize();
+    assert!(results.temp.paths.is_empty());
+    assert_eq!((results.temp.items, results.temp.size_bytes), (1, 6));
+
+    let json = wole::output::to_json(&results, None).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(report["summary_only"], true);
+    assert_eq!(report["summary"]["total_bytes"], 6);
+    assert_eq!(report["categories"]["temp"]["paths"], serde_json::json!([]));
+
+    assert!(
+        wole::cleaner::clean_all(&results, true, OutputMode::Quiet, true, false, false).is_err()
+    );
+    assert!(file.exists());
+}
+
+#[test]
+fn test_registry_runs_registered_cleaners_and_hooks() {
+    use wole::cleaner::strategy::BatchCleaner;
+    use wole::cleaner::{CleanHooks, CleanOptions, CleanerRegistry};
+
+    /// Refuses to delete anything named `keep.*`
+    struct KeepFiles;
+    impl CleanHooks for KeepFiles {
+        fn before_delete(&self, path: &std::path::Path, _category: &str) -> Result<(), String> {
+            match path.file_stem() {
+                Some(stem) if stem == "keep" => Err("kept by policy".to_string()),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    let temp_dir = create_test_dir();
+    let keep = temp_dir.path().join("keep.log");
+    let drop = temp_dir.path().join("drop.log");
+    fs::write(&keep, "keep").unwrap();
+    fs::write(&drop, "drop").unwrap();
+
+    let results = wole::output::ScanResults {
+        custom: wole::output::CategoryResult {
+            items: 2,
+            size_bytes: 8,
+            paths: vec![keep.clone(), drop.clone()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut registry = CleanerRegistry::empty().with_hooks(KeepFiles);
+    registry.register(BatchCleaner {
+        name: "logs",
+        result: |r| &r.custom,
+    });
+    let options = CleanOptions {
+        skip_confirm: true,
+        mode: OutputMode::Quiet,
+        permanent: true,
+        ..Default::default()
+    };
+
+    let log = wole::cleaner::clean_with(&results, &registry, &options)
+        .unwrap()
+        .unwrap();
+    assert!(keep.exists());
+    assert!(!drop.exists());
+    assert_eq!((log.total_items, log.errors), (2, 1));
+    let refused = log.failures().next().unwrap();
+    assert_eq!(refused.category, "logs");
+    assert_eq!(refused.error.as_deref(), Some("kept by policy"));
+}
+
+/// Cleaner accounting under simulated partial failure
+/// (`cargo test --features failure-injection`)
+#[cfg(feature = "failure-injection")]
+mod failure_injection {
+    use super::create_test_dir;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
+    use wole::cleaner::failure_injection::{fault_for, Fault, FaultPlan};
+    use wole::cleaner::{clean_paths_batch, delete_with_precheck, DeleteOutcome};
+    use wole::output::{CategoryResult, OutputMode, ScanResults};
+
+    fn make_files(root: &std::path::Path, count: usize) -> Vec<PathBuf> {
+        (0..count)
+            .map(|i| {
+                let path = root.join(format!("item-{:03}.tmp", i));
+                fs::write(&path, "0123456789").unwrap();
+                path
+            })
+            .collect()
+    }
+
+    fn count_faults(paths: &[PathBuf]) -> HashMap<Option<Fault>, usize> {
+        let mut counts = HashMap::new();
+        for path in paths {
+            *counts.entry(fault_for(path)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn permanent_batch_accounts_for_every_fault() {
+        let temp_dir = create_test_dir();
+        let paths = make_files(temp_dir.path(), 60);
+        let _guard = FaultPlan {
+            seed: 1,
+            locked: 0.2,
+            access_denied: 0.2,
+            vanished: 0.2,
+            trash_unavailable: 0.2,
+            ..FaultPlan::new(temp_dir.path())
+        }
+        .install();
+        let faults = count_faults(&paths);
+        let n = |fault| faults.get(&fault).copied().unwrap_or(0);
+        // The Recycle Bin isn't involved in permanent deletes
+        let deleted = n(None) + n(Some(Fault::TrashUnavailable));
+
+        let result = clean_paths_batch(&paths, true);
+        assert_eq!(result.success_count, deleted);
+        assert_eq!(result.deleted_paths.len(), deleted);
+        assert_eq!(result.locked_paths.len(), n(Some(Fault::Locked)));
+        assert_eq!(
+            result.permission_denied_paths.len(),
+            n(Some(Fault::AccessDenied))
+        );
+        assert_eq!(result.skipped_paths.len(), n(Some(Fault::Vanished)));
+        assert_eq!(
+            result.error_count,
+            n(Some(Fault::Locked)) + n(Some(Fault::AccessDenied))
+        );
+        assert_eq!(
+            result.success_count + result.error_count + result.skipped_paths.len(),
+            paths.len()
+        );
+        for path in &result.locked_paths {
+            assert!(path.exists(), "locked path was deleted anyway");
+        }
+        for path in &result.deleted_paths {
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    fn recycle_fallback_accounts_for_every_fault() {
+        let temp_dir = create_test_dir();
+        let paths = make_files(temp_dir.path(), 40);
+        // Rates add up to 1 so nothing reaches the real Recycle Bin
+        let _guard = FaultPlan {
+            seed: 2,
+            locked: 0.25,
+            access_denied: 0.25,
+            vanished: 0.25,
+            trash_unavailable: 0.25,
+            ..FaultPlan::new(temp_dir.path())
+        }
+        .install();
+        let faults = count_faults(&paths);
+        let n = |fault| faults.get(&fault).copied().unwrap_or(0);
+        assert_eq!(n(None), 0);
+
+        let result = clean_paths_batch(&paths, false);
+        // A path that vanished mid-delete is gone, which is what was asked
+        assert_eq!(result.success_count, n(Some(Fault::Vanished)));
+        assert_eq!(result.locked_paths.len(), n(Some(Fault::Locked)));
+        assert_eq!(
+            result.permission_denied_paths.len(),
+            n(Some(Fault::AccessDenied))
+        );
+        assert_eq!(result.error_count, paths.len() - n(Some(Fault::Vanished)));
+        // Every failure keeps a reason for the deletion log
+        assert_eq!(result.failure_details().len(), result.error_count);
+        for (path, info) in result.failure_details() {
+            assert!(path.exists());
+            assert!(!info.message.is_empty());
+        }
+    }
+
+    #[test]
+    fn single_delete_classifies_faults() {
+        let temp_dir = create_test_dir();
+        let paths = make_files(temp_dir.path(), 30);
+        let _guard = FaultPlan {
+            seed: 3,
+            locked: 0.3,
+            access_denied: 0.3,
+            vanished: 0.3,
+            ..FaultPlan::new(temp_dir.path())
+        }
+        .install();
+
+        for path in &paths {
+            let outcome = delete_with_precheck(path, true).unwrap();
+            let expected = match fault_for(path) {
+                None | Some(Fault::TrashUnavailable) => DeleteOutcome::Deleted,
+                Some(Fault::Locked) => DeleteOutcome::SkippedLocked,
+                Some(Fault::AccessDenied) => DeleteOutcome::SkippedPermission,
+                Some(Fault::Vanished) => DeleteOutcome::SkippedMissing,
+            };
+            assert_eq!(outcome, expected, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn clean_all_logs_partial_failure_and_still_succeeds() {
+        let temp_dir = create_test_dir();
+        let paths = make_files(temp_dir.path(), 50);
+        let _guard = FaultPlan {
+            seed: 4,
+            locked: 0.15,
+            access_denied: 0.15,
+            ..FaultPlan::new(temp_dir.path())
+        }
+        .install();
+        let faults = count_faults(&paths);
+        let failed = paths.len() - faults.get(&None).copied().unwrap_or(0);
+
+        let results = ScanResults {
+            temp: CategoryResult {
+                items: paths.len(),
+                size_bytes: 10 * paths.len() as u64,
+                paths: paths.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // Partial failure is reported in the log, not as an error (exit code 0)
+        let log = wole::cleaner::clean_all(&results, true, OutputMode::Quiet, true, false, false)
+            .unwrap()
+            .expect("a deletion log");
+
+        assert_eq!(log.errors, failed);
+        assert_eq!(log.records.len(), paths.len());
+        assert_eq!(log.total_items, paths.len());
+        assert_eq!(
+            log.records.iter().filter(|r| r.success).count(),
+            paths.len() - failed
+        );
+        assert_eq!(log.total_bytes_cleaned, 10 * (paths.len() - failed) as u64);
+        for record in log.records.iter().filter(|r| !r.success) {
+            assert!(record.error.is_some());
+            assert!(PathBuf::from(&record.path).exists());
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Duplicate file grouping
+//!
+//! Which copies of a duplicate group get flagged: files named like copies
+//! (`report (1).pdf`, `photo - Copy.jpg`) when there are any, otherwise
+//! every file but the alphabetically first one.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Files with identical content. `P` is a `PathBuf` in the scanner and a
+/// `String` for paths read from a report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateGroup<P = String> {
+    pub hash: String,
+    pub size: u64,
+    pub paths: Vec<P>,
+}
+
+impl<P> DuplicateGroup<P> {
+    /// Space taken by all copies but one
+    pub fn wasted(&self) -> u64 {
+        self.paths.len().saturating_sub(1) as u64 * self.size
+    }
+}
+
+/// Group `(hash, size, path)` entries by hash, keeping groups of two or
+/// more files, biggest waste first
+pub fn group_by_hash<P>(
+    files: impl IntoIterator<Item = (String, u64, P)>,
+) -> Vec<DuplicateGroup<P>> {
+    let mut by_hash: BTreeMap<String, DuplicateGroup<P>> = BTreeMap::new();
+    for (hash, size, path) in files {
+        by_hash
+            .entry(hash.clone())
+            .or_insert_with(|| DuplicateGroup {
+                hash,
+                size,
+                paths: Vec::new(),
+            })
+            .paths
+            .push(path);
+    }
+    let mut groups: Vec<DuplicateGroup<P>> = by_hash
+        .into_values()
+        .filter(|group| group.paths.len() > 1)
+        .collect();
+    sort_by_wasted(&mut groups);
+    groups
+}
+
+/// Sort groups by wasted space, descending
+pub fn sort_by_wasted<P>(groups: &mut [DuplicateGroup<P>]) {
+    groups.sort_by_key(|group| core::cmp::Reverse(group.wasted()));
+}
+
+/// Extract the number from a filename suffix pattern like " (1)" or " (2)".
+/// Returns u32::MAX if no number is found (to sort files without numbers first)
+pub fn suffix_number(filename: &str) -> u32 {
+    if let Some(start) = filename.rfind(" (") {
+        if let Some(end) = filename[start + 2..].find(')') {
+            if let Ok(num) = filename[start + 2..start + 2 + end].parse::<u32>() {
+                return num;
+            }
+        }
+    }
+    u32::MAX
+}
+
+/// Check if a filename looks like a copy (" (1)", " - Copy", "_copy", ...)
+pub fn is_copy_name(filename: &str) -> bool {
+    filename.contains(" (") && filename.contains(')')
+        || filename.contains(" - Copy")
+        || filename.contains(" - copy")
+        || filename.contains("_copy")
+        || filename.contains("_Copy")
+}
+
+/// Indices (into `names`) of the files to flag in one duplicate group, in
+/// deletion order. Files named like copies are flagged (lowest copy number
+/// first) and the others kept; if none is, all but the alphabetically
+/// first file are flagged.
+pub fn copies_to_remove(names: &[&str]) -> Vec<usize> {
+    let mut copies: Vec<usize> = (0..names.len())
+        .filter(|&i| is_copy_name(names[i]))
+        .collect();
+    if !copies.is_empty() {
+        copies.sort_by_key(|&i| suffix_number(names[i]));
+        return copies;
+    }
+    let mut originals: Vec<usize> = (0..names.len()).collect();
+    originals.sort_by_key(|&i| names[i]);
+    originals.into_iter().skip(1).collect()
+}
+
+/// Paths to flag in a group read from a report
+pub fn flagged_paths(group: &DuplicateGroup) -> Vec<&str> {
+    let names: Vec<&str> = group.paths.iter().map(|p| crate::file_name(p)).collect();
+    copies_to_remove(&names)
+        .into_iter()
+        .map(|i| group.paths[i].as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn groups_and_flags_copies() {
+        let groups = group_by_hash(vec![
+            ("a".to_string(), 100, r"C:\Docs\report (2).pdf".to_string()),
+            ("a".to_string(), 100, r"C:\Docs\report.pdf".to_string()),
+            ("a".to_string(), 100, r"C:\Docs\report (1).pdf".to_string()),
+            ("b".to_string(), 10, "/home/u/b.txt".to_string()),
+            ("b".to_string(), 10, "/home/u/a.txt".to_string()),
+            ("c".to_string(), 999, "/home/u/unique.bin".to_string()),
+        ]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].hash, "a");
+        assert_eq!(groups[0].wasted(), 200);
+
+        assert_eq!(
+            flagged_paths(&groups[0]),
+            vec![r"C:\Docs\report (1).pdf", r"C:\Docs\report (2).pdf"]
+        );
+        // No copy-like names: keep the alphabetically first file
+        assert_eq!(flagged_paths(&groups[1]), vec!["/home/u/b.txt"]);
+    }
+}
@@ -0,0 +1,43 @@
+//! Filesystem-free core of wole
+//!
+//! The data structures and analysis logic behind wole's scan reports. The
+//! crate is `no_std` (it only needs `alloc`), so it has no filesystem,
+//! thread or platform access and the same code builds for
+//! `wasm32-unknown-unknown`. Web dashboards can load an uploaded
+//! `wole scan --json` report and apply exactly the grouping and policy
+//! logic the CLI uses:
+//!
+//! ```
+//! let json = r#"{"version":"1.0","timestamp":"","categories":{"temp":{"items":1,"size_bytes":2048,"size_human":"2.0 KiB","paths":["C:\\Temp\\a.tmp"]}},"summary":{"total_items":1,"total_bytes":2048,"total_human":"2.0 KiB"}}"#;
+//! let report = wole_core::report::ScanReport::from_json(json).unwrap();
+//! let summary = wole_core::policy::evaluate(&report);
+//! assert_eq!(summary.safe_bytes, 2048);
+//! ```
+//!
+//! Paths are plain strings with either `/` or `\` separators, since reports
+//! are often read on a different platform than the one that produced them.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod duplicates;
+pub mod policy;
+pub mod report;
+pub mod size;
+
+/// Last component of a `/`- or `\`-separated path
+pub fn file_name(path: &str) -> &str {
+    path.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("")
+}
+
+/// Second-to-last component of a `/`- or `\`-separated path
+pub fn parent_name(path: &str) -> &str {
+    path.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .nth(1)
+        .unwrap_or("")
+}
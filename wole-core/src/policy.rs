@@ -0,0 +1,221 @@
+//! Category policies
+//!
+//! How safe each category is to clean, as shown in the Status column of
+//! `wole scan`, and the split of a report into what can be cleaned right
+//! away and what needs a look first.
+
+use crate::report::ScanReport;
+
+/// How much care a category's findings need before cleaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Safety {
+    /// Rebuilt or re-downloaded on demand
+    Safe,
+    /// May hold files the user still wants
+    Review,
+    /// Needs administrator rights to clean
+    Admin,
+}
+
+/// A category as listed by `wole scan` and `wole categories`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategoryPolicy {
+    /// CLI key (`app_cache`); stable, also used in JSON reports and config
+    pub key: &'static str,
+    /// Table label (`Application cache`)
+    pub label: &'static str,
+    pub safety: Safety,
+    /// Status column text
+    pub status: &'static str,
+    /// What the category finds
+    pub description: &'static str,
+    /// Only finds anything on Windows
+    pub windows_only: bool,
+}
+
+const fn policy(
+    key: &'static str,
+    label: &'static str,
+    safety: Safety,
+    status: &'static str,
+) -> CategoryPolicy {
+    CategoryPolicy {
+        key,
+        label,
+        safety,
+        status,
+        description: "",
+        windows_only: false,
+    }
+}
+
+impl CategoryPolicy {
+    const fn about(self, description: &'static str) -> Self {
+        Self {
+            description,
+            ..self
+        }
+    }
+
+    const fn windows_only(self) -> Self {
+        Self {
+            windows_only: true,
+            ..self
+        }
+    }
+}
+
+const SAFE: &str = "[OK] Safe to clean";
+const REVIEW: &str = "[!] Review suggested";
+const ADMIN: &str = "[!] Requires admin";
+
+/// Every category, in the order `wole scan` lists them
+pub const CATEGORIES: [CategoryPolicy; 19] = [
+    policy("cache", "Package cache", Safety::Safe, SAFE)
+        .about("Package manager caches (npm/yarn/pnpm, NuGet, Cargo, pip)"),
+    policy("app_cache", "Application cache", Safety::Safe, SAFE)
+        .about("Application caches (Discord, VS Code, Slack, Spotify)"),
+    policy("temp", "Temp", Safety::Safe, SAFE)
+        .about("Temp files plus stale editor and package manager locks"),
+    policy("trash", "Trash", Safety::Safe, SAFE)
+        .about("Recycle Bin contents")
+        .windows_only(),
+    policy("build", "Build", Safety::Safe, "[OK] Inactive projects")
+        .about("Build artifacts from inactive projects (node_modules, target/, bin/obj)"),
+    policy("downloads", "Downloads", Safety::Safe, "[OK] Old files")
+        .about("Old files in Downloads"),
+    policy("large", "Large", Safety::Review, REVIEW).about("Large files"),
+    policy("old", "Old", Safety::Review, REVIEW).about("Files not accessed in a while"),
+    policy("applications", "Applications", Safety::Review, REVIEW)
+        .about("Installed applications")
+        .windows_only(),
+    policy("browser", "Browser", Safety::Safe, SAFE)
+        .about("Browser caches (Chrome, Edge, Firefox, Brave)"),
+    policy("system", "System", Safety::Safe, SAFE)
+        .about("Windows system caches (thumbnails, updates, icons)")
+        .windows_only(),
+    policy("empty", "Empty", Safety::Safe, SAFE).about("Empty folders"),
+    policy("duplicates", "Duplicates", Safety::Review, REVIEW).about("Duplicate files"),
+    policy("windows_update", "Windows Update", Safety::Admin, ADMIN)
+        .about("Windows Update download files")
+        .windows_only(),
+    policy("event_logs", "Event Logs", Safety::Admin, ADMIN)
+        .about("Windows Event Log files")
+        .windows_only(),
+    policy("orphaned", "Orphaned", Safety::Review, REVIEW)
+        .about("Files owned by deleted or unknown accounts")
+        .windows_only(),
+    policy("custom", "Custom", Safety::Review, REVIEW)
+        .about("Files matched by your [[custom_categories]] rules"),
+    policy("ide", "IDE", Safety::Safe, SAFE)
+        .about("IDE and toolchain caches (JetBrains, VS Code, Android SDK, Gradle)"),
+    policy("rust", "Rust", Safety::Safe, SAFE)
+        .about("Stale Cargo registry crates, unused rustup toolchains, sccache"),
+];
+
+/// Policy for the category with CLI key `key`
+pub fn category(key: &str) -> Option<&'static CategoryPolicy> {
+    CATEGORIES.iter().find(|policy| policy.key == key)
+}
+
+impl CategoryPolicy {
+    /// Policy for a category's actual findings: Application cache needs a
+    /// review when it includes state-adjacent folders
+    pub fn for_paths<S: AsRef<str>>(&self, paths: impl IntoIterator<Item = S>) -> Self {
+        if self.key == "app_cache"
+            && paths
+                .into_iter()
+                .any(|p| is_review_worthy_app_cache_path(p.as_ref()))
+        {
+            return Self {
+                safety: Safety::Review,
+                status: REVIEW,
+                ..*self
+            };
+        }
+        *self
+    }
+}
+
+/// Application cache folders next to app state that should be reviewed
+/// before cleaning (Notion roaming `Partitions`, Cursor / Cursor Nightly
+/// `snapshots`)
+pub fn is_review_worthy_app_cache_path(path: &str) -> bool {
+    let leaf = crate::file_name(path);
+    let parent = crate::parent_name(path);
+
+    if leaf.eq_ignore_ascii_case("Partitions") {
+        return parent.eq_ignore_ascii_case("Notion");
+    }
+    if leaf.eq_ignore_ascii_case("snapshots") {
+        return parent.eq_ignore_ascii_case("Cursor")
+            || parent.eq_ignore_ascii_case("Cursor Nightly");
+    }
+    false
+}
+
+/// Report totals split by how much care they need
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PolicySummary {
+    pub safe_items: usize,
+    pub safe_bytes: u64,
+    pub review_items: usize,
+    pub review_bytes: u64,
+    pub admin_items: usize,
+    pub admin_bytes: u64,
+}
+
+/// Apply the category policies to every finding in `report`
+pub fn evaluate(report: &ScanReport) -> PolicySummary {
+    let mut summary = PolicySummary::default();
+    for (key, result) in report.categories.iter() {
+        let Some(policy) = category(key) else {
+            continue;
+        };
+        let (items, bytes) = match policy.for_paths(&result.paths).safety {
+            Safety::Safe => (&mut summary.safe_items, &mut summary.safe_bytes),
+            Safety::Review => (&mut summary.review_items, &mut summary.review_bytes),
+            Safety::Admin => (&mut summary.admin_items, &mut summary.admin_bytes),
+        };
+        *items += result.items;
+        *bytes += result.size_bytes;
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::CategoryReport;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn splits_report_by_safety() {
+        let mut report = ScanReport::default();
+        report.categories.temp = CategoryReport {
+            items: 2,
+            size_bytes: 300,
+            ..Default::default()
+        };
+        report.categories.large = CategoryReport {
+            items: 1,
+            size_bytes: 5_000,
+            ..Default::default()
+        };
+        report.categories.app_cache = CategoryReport {
+            items: 1,
+            size_bytes: 40,
+            paths: vec![r"C:\Users\x\AppData\Roaming\Notion\Partitions".to_string()],
+            ..Default::default()
+        };
+
+        let json = report.to_json().unwrap();
+        let summary = evaluate(&ScanReport::from_json(&json).unwrap());
+        assert_eq!((summary.safe_items, summary.safe_bytes), (2, 300));
+        assert_eq!((summary.review_items, summary.review_bytes), (2, 5_040));
+        assert!(!is_review_worthy_app_cache_path(
+            "/home/x/.config/Notion/Cache"
+        ));
+    }
+}
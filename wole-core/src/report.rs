@@ -0,0 +1,400 @@
+//! The `--json` scan report
+//!
+//! Written by `wole scan --json` / `wole clean --json` and readable back
+//! with [`ScanReport::from_json`]. Fields the CLI leaves out when empty
+//! default to empty, so reports from older versions still load.
+
+use crate::size;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// A scan (and, for `wole clean --json`, its cleanup)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanReport {
+    pub version: String,
+    pub timestamp: String,
+    pub categories: Categories,
+    pub summary: Summary,
+    /// Totals per drive, when results span several
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<VolumeTotal>,
+    /// Images that look alike; review only, never cleaned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub similar_images: Vec<Vec<Image>>,
+    /// Downloaded OneDrive files that could go online-only; review only, never cleaned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cloud_files: Vec<CloudFileItem>,
+    /// Outcome of `wole clean --json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clean: Option<CleanReport>,
+    /// Sampling of a quick estimate (`wole scan --quick`); sizes are extrapolated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<Estimate>,
+    /// Totals only (`wole scan --summary-only`): every path list is empty
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub summary_only: bool,
+}
+
+impl ScanReport {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Every category, in the order the CLI lists them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Categories {
+    pub cache: CategoryReport,
+    pub app_cache: CategoryReport,
+    pub temp: CategoryReport,
+    pub trash: CategoryReport,
+    pub build: CategoryReport,
+    pub downloads: CategoryReport,
+    pub large: CategoryReport,
+    pub old: CategoryReport,
+    pub applications: CategoryReport,
+    pub browser: CategoryReport,
+    pub system: CategoryReport,
+    pub empty: CategoryReport,
+    pub duplicates: CategoryReport,
+    pub windows_update: CategoryReport,
+    pub event_logs: CategoryReport,
+    pub orphaned: CategoryReport,
+    pub custom: CategoryReport,
+    pub ide: CategoryReport,
+    pub rust: CategoryReport,
+}
+
+impl Categories {
+    /// Every category with its CLI key (`cache`, `app_cache`, ...)
+    pub fn iter(&self) -> [(&'static str, &CategoryReport); 19] {
+        [
+            ("cache", &self.cache),
+            ("app_cache", &self.app_cache),
+            ("temp", &self.temp),
+            ("trash", &self.trash),
+            ("build", &self.build),
+            ("downloads", &self.downloads),
+            ("large", &self.large),
+            ("old", &self.old),
+            ("applications", &self.applications),
+            ("browser", &self.browser),
+            ("system", &self.system),
+            ("empty", &self.empty),
+            ("duplicates", &self.duplicates),
+            ("windows_update", &self.windows_update),
+            ("event_logs", &self.event_logs),
+            ("orphaned", &self.orphaned),
+            ("custom", &self.custom),
+            ("ide", &self.ide),
+            ("rust", &self.rust),
+        ]
+    }
+}
+
+/// One category's findings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CategoryReport {
+    pub items: usize,
+    pub size_bytes: u64,
+    pub size_human: String,
+    pub paths: Vec<String>,
+    /// Hidden/system matches, reported separately (see `--include-hidden`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hidden_size_bytes: u64,
+    /// Machine-wide caches, reported apart from the per-user totals
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub machine_paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub machine_size_bytes: u64,
+    /// Estimated cost to recreate each folder (build artifacts and package caches)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recreate_costs: Vec<RecreateCost>,
+    /// Largest files inside each directory finding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub largest_files: Vec<LargestFiles>,
+    /// Created/modified/accessed times of each path, where the filesystem records them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_times: Vec<ItemTimes>,
+    /// Type, access age and in-use status of each file (large files)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_hints: Vec<FileHint>,
+    /// How old each file is and which timestamp said so (old files)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ages: Vec<ItemAge>,
+    /// Artifacts whose repository has uncommitted or unpushed work (build artifacts)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_flags: Vec<GitFlags>,
+    /// Each item in the Recycle Bin (trash)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recycle_bin: Vec<RecycleBinItem>,
+}
+
+/// One Recycle Bin entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecycleBinItem {
+    pub name: String,
+    pub original_path: String,
+    /// When it was deleted (RFC 3339, UTC)
+    pub deleted: String,
+    pub age_days: u64,
+    pub size_bytes: u64,
+}
+
+/// Work in an artifact's repository that exists nowhere else
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GitFlags {
+    pub path: String,
+    pub dirty: bool,
+    pub unpushed: bool,
+}
+
+/// Age of one file by the configured timestamp
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemAge {
+    pub path: String,
+    pub days: u64,
+    /// "accessed", "modified" or "created"
+    pub basis: String,
+}
+
+/// What helps judge whether a file can go
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileHint {
+    pub path: String,
+    pub file_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessed_days: Option<u64>,
+    /// Why the file looks in use ("in use by Code.exe", "in use by another process")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_use: Option<String>,
+}
+
+/// Timestamps of one path (RFC 3339, UTC)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemTimes {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessed: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LargestFiles {
+    pub path: String,
+    pub files: Vec<FileSize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecreateCost {
+    pub path: String,
+    /// "redownload", "rebuild" or "regenerated"
+    pub kind: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub download_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Image {
+    pub path: String,
+    pub size_bytes: u64,
+    pub size_human: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VolumeTotal {
+    /// Drive (`C:`) or mount point
+    pub volume: String,
+    pub items: usize,
+    pub size_bytes: u64,
+    pub size_human: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CloudFileItem {
+    pub path: String,
+    pub size_bytes: u64,
+    pub size_human: String,
+    /// Days since it was last used
+    pub unused_days: u64,
+    /// Timestamp `unused_days` was taken from: "accessed", "modified" or "created"
+    pub basis: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileSize {
+    /// Path relative to the flagged directory
+    pub name: String,
+    pub size_bytes: u64,
+    pub size_human: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    /// Pass to `--seed` to sample the same folders again
+    pub seed: u64,
+    /// Share of subfolders actually walked, 0-100
+    pub coverage_percent: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_items: usize,
+    pub total_bytes: u64,
+    pub total_human: String,
+}
+
+impl Summary {
+    /// Totals over every category
+    pub fn of(categories: &Categories) -> Self {
+        let (total_items, total_bytes) = categories
+            .iter()
+            .iter()
+            .fold((0, 0), |(items, bytes), (_, category)| {
+                (items + category.items, bytes + category.size_bytes)
+            });
+        Self {
+            total_items,
+            total_bytes,
+            total_human: size::format_total(total_bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CleanReport {
+    pub cleaned: usize,
+    pub cleaned_bytes: u64,
+    pub errors: usize,
+    pub failures: Vec<Failure>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Failure {
+    pub path: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<FailureReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Why a deletion failed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailureReason {
+    /// File is in use by another process (worth retrying later)
+    Locked,
+    /// Missing permissions (retry elevated)
+    AccessDenied,
+    /// Path disappeared before it could be deleted
+    NotFound,
+    /// Path exceeds the length the API could handle
+    PathTooLong,
+    /// Path became a link or a different file since the scan (not retried)
+    Changed,
+    /// Path is on the protected list (`[safety] protected_paths`, user
+    /// folders, git worktrees); not retried
+    Protected,
+    Other,
+}
+
+impl FailureReason {
+    /// Classify an OS error code for the current platform
+    pub fn from_os_code(code: i32) -> Self {
+        #[cfg(windows)]
+        {
+            match code {
+                // ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION, ERROR_USER_MAPPED_FILE
+                32 | 33 | 1224 => Self::Locked,
+                // ERROR_ACCESS_DENIED, ERROR_PRIVILEGE_NOT_HELD
+                5 | 1314 => Self::AccessDenied,
+                // ERROR_FILE_NOT_FOUND, ERROR_PATH_NOT_FOUND
+                2 | 3 => Self::NotFound,
+                // ERROR_BUFFER_OVERFLOW, ERROR_FILENAME_EXCED_RANGE
+                111 | 206 => Self::PathTooLong,
+                _ => Self::Other,
+            }
+        }
+        #[cfg(not(windows))]
+        {
+            match code {
+                // EBUSY, ETXTBSY
+                16 | 26 => Self::Locked,
+                // EPERM, EACCES, EROFS
+                1 | 13 | 30 => Self::AccessDenied,
+                // ENOENT
+                2 => Self::NotFound,
+                // ENAMETOOLONG
+                36 => Self::PathTooLong,
+                _ => Self::Other,
+            }
+        }
+    }
+
+    /// Best-effort classification of an error message without a code
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("locked") || lower.contains("being used by another process") {
+            Self::Locked
+        } else if lower.contains("permission denied") || lower.contains("access is denied") {
+            Self::AccessDenied
+        } else if lower.contains("not found") || lower.contains("cannot find") {
+            Self::NotFound
+        } else if lower.contains("too long") {
+            Self::PathTooLong
+        } else {
+            Self::Other
+        }
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn reports_parse_and_round_trip() {
+        // An older report: only the fields every version writes
+        let json = r#"{"version":"1.0","timestamp":"t","categories":{"build":{"items":2,"size_bytes":1536,"size_human":"1.5 KB","paths":["a","b"]}},"summary":{"total_items":2,"total_bytes":1536,"total_human":"1.5 kiB"}}"#;
+        let report = ScanReport::from_json(json).unwrap();
+        assert_eq!(report.categories.build.paths, vec!["a", "b"]);
+        assert_eq!(report.categories.cache, CategoryReport::default());
+        assert!(report.volumes.is_empty() && report.clean.is_none() && !report.summary_only);
+        assert_eq!(report.summary, Summary::of(&report.categories));
+
+        let mut report = report;
+        report.categories.temp.items = 1;
+        report.categories.temp.size_bytes = 1024 * 1024;
+        report.categories.temp.paths = vec!["c".to_string()];
+        report.summary = Summary::of(&report.categories);
+        assert_eq!(report.summary.total_items, 3);
+        assert_eq!(report.summary.total_human, "1.0 MiB");
+        assert_eq!(
+            ScanReport::from_json(&report.to_json().unwrap()).unwrap(),
+            report
+        );
+
+        assert!(ScanReport::from_json(r#"{"version":"1.0"}"#).is_err());
+    }
+}
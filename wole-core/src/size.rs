@@ -0,0 +1,123 @@
+//! Human-readable sizes
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// Size string that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSizeError(String);
+
+impl fmt::Display for ParseSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::error::Error for ParseSizeError {}
+
+/// Parse human-readable size strings to bytes
+///
+/// Supports: B, KB, MB, GB, TB (case-insensitive, binary multiples)
+pub fn parse_size(s: &str) -> Result<u64, ParseSizeError> {
+    let s = s.trim();
+
+    if s.is_empty() {
+        return Err(ParseSizeError("Empty size string".to_string()));
+    }
+
+    // Find where the number ends and unit begins
+    let num_end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+
+    if num_end == s.len() {
+        // No unit found, assume bytes
+        return s.parse::<u64>().map_err(|e| ParseSizeError(e.to_string()));
+    }
+
+    if num_end == 0 {
+        return Err(ParseSizeError(format!(
+            "Size string must start with a number: {}",
+            s
+        )));
+    }
+
+    let num_str = &s[..num_end];
+    let unit_str = s[num_end..].trim().to_uppercase();
+
+    let num: f64 = num_str
+        .parse()
+        .map_err(|_| ParseSizeError(format!("Invalid number: {}", num_str)))?;
+
+    let multiplier = match unit_str.as_str() {
+        "B" => 1u64,
+        "KB" => 1024u64,
+        "MB" => 1024u64 * 1024,
+        "GB" => 1024u64 * 1024 * 1024,
+        "TB" => 1024u64 * 1024 * 1024 * 1024,
+        _ => {
+            return Err(ParseSizeError(format!(
+                "Unknown size unit: {}. Supported: B, KB, MB, GB, TB",
+                unit_str
+            )))
+        }
+    };
+
+    Ok((num * multiplier as f64) as u64)
+}
+
+/// Size as shown in reports (`1.5 MB`): decimal units, stepping up at each
+/// power of 1024
+pub fn format_size(bytes: u64) -> String {
+    to_string(bytes, 1000, 1024, b"KMGTPE", "B")
+}
+
+/// Size as shown in report totals (`1.5 GiB`): binary units, stepping up at
+/// each power of 1000
+pub fn format_total(bytes: u64) -> String {
+    to_string(bytes, 1024, 1000, b"kMGTPE", "iB")
+}
+
+/// The formatting of `bytesize` 1.3, which the rest of the CLI uses, with
+/// integer steps instead of logarithms (which need `std`)
+fn to_string(bytes: u64, unit: u64, step: u64, prefixes: &[u8], suffix: &str) -> String {
+    if bytes < unit {
+        return format!("{} B", bytes);
+    }
+    let mut exp = 1;
+    while exp < prefixes.len() && step.pow(exp as u32 + 1) <= bytes {
+        exp += 1;
+    }
+    format!(
+        "{:.1} {}{}",
+        bytes as f64 / unit.pow(exp as u32) as f64,
+        prefixes[exp - 1] as char,
+        suffix
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_parse_and_format() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("10mb"), Ok(10 * 1024 * 1024));
+        assert_eq!(parse_size(" 1.5 GB "), Ok(1536 * 1024 * 1024));
+        assert!(parse_size("").is_err());
+        assert!(parse_size("GB").is_err());
+        assert!(parse_size("5 PB").is_err());
+
+        assert_eq!(format_size(999), "999 B");
+        assert_eq!(format_size(1023), "1.0 KB");
+        assert_eq!(format_size(1_500_000), "1.5 MB");
+        // Like the CLI: the unit steps up at each power of 1024
+        assert_eq!(format_size(1_000_000_000), "1000.0 MB");
+        assert_eq!(format_total(1023), "1023 B");
+        assert_eq!(format_total(1536), "1.5 kiB");
+        assert_eq!(format_total(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+        assert_eq!(format_total(5 * 1024u64.pow(5)), "5.0 PiB");
+    }
+}